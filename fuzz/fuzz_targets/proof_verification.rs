@@ -0,0 +1,43 @@
+#![no_main]
+
+//! Schnorr proof verification must never panic, and must reject arbitrary proofs
+
+use generic_ec::{
+    curves::{Ed25519, Secp256k1},
+    fuzzing::Input,
+    Curve, Point, Scalar,
+};
+use generic_ec_zkp::{
+    schnorr_pok::{Commit, Proof},
+    transcript::Transcript,
+};
+use libfuzzer_sys::fuzz_target;
+
+fn check<E: Curve>(data: &[u8]) {
+    let mut input = Input::new(data);
+    let public_key = input.point::<E>();
+    if public_key.is_zero() {
+        // Proof of knowledge of zero is trivial
+        return;
+    }
+    let commit_len = usize::from(input.byte()) % (2 * Point::<E>::serialized_len(false));
+    let Ok(commit) = Commit::<E>::from_bytes(input.bytes(commit_len)) else {
+        return;
+    };
+    let Ok(proof) = Proof::<E>::from_bytes(input.bytes(Scalar::<E>::serialized_len())) else {
+        return;
+    };
+
+    let mut transcript = Transcript::<sha2::Sha256>::new(b"generic-ec-fuzz");
+    assert!(
+        proof
+            .verify_with_rederived_challenge(&mut transcript, &commit, &public_key)
+            .is_err(),
+        "arbitrary proof is accepted"
+    );
+}
+
+fuzz_target!(|data: &[u8]| {
+    check::<Secp256k1>(data);
+    check::<Ed25519>(data);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use generic_ec::{
+    curves::{Ed25519, Secp256k1, Secp256r1, Stark},
+    fuzzing,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    fuzzing::scalar_reduction::<Secp256k1>(data);
+    fuzzing::scalar_reduction::<Secp256r1>(data);
+    fuzzing::scalar_reduction::<Stark>(data);
+    fuzzing::scalar_reduction::<Ed25519>(data);
+});
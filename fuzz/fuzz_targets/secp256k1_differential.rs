@@ -0,0 +1,38 @@
+#![no_main]
+
+//! Differential testing of k256 backend against reference secp256k1 implementation
+
+use generic_ec::{coords::HasAffineXY, curves::Secp256k1, fuzzing, Point};
+use generic_ec_fuzz::secp256k1_reference as reference;
+use libfuzzer_sys::fuzz_target;
+
+fn coords(point: &Point<Secp256k1>) -> Option<([u8; 32], [u8; 32])> {
+    let coords = point.coords()?;
+    Some((
+        coords.x.as_be_bytes().try_into().ok()?,
+        coords.y.as_be_bytes().try_into().ok()?,
+    ))
+}
+
+fuzz_target!(|data: &[u8]| {
+    fuzzing::arithmetic::<Secp256k1>(data);
+
+    let mut input = fuzzing::Input::new(data);
+    let a = input.scalar::<Secp256k1>();
+    let b = input.scalar::<Secp256k1>();
+
+    let p = Point::generator() * a;
+    let q = Point::generator() * b;
+    let ref_p = reference::Point::generator().mul(&a.to_be_bytes());
+    let ref_q = reference::Point::generator().mul(&b.to_be_bytes());
+    assert!(ref_p.is_on_curve() && ref_q.is_on_curve());
+
+    assert_eq!(coords(&p), ref_p.coords(), "scalar multiplication");
+    assert_eq!(coords(&(p + q)), ref_p.add(&ref_q).coords(), "addition");
+    assert_eq!(coords(&p.double()), ref_p.double().coords(), "doubling");
+    assert_eq!(
+        coords(&(p * b)),
+        ref_p.mul(&b.to_be_bytes()).coords(),
+        "variable base multiplication"
+    );
+});
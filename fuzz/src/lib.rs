@@ -0,0 +1,6 @@
+//! Shared code of the fuzz targets
+//!
+//! Contains a reference implementation of secp256k1 arithmetic used for differential testing of
+//! the k256 backend.
+
+pub mod secp256k1_reference;
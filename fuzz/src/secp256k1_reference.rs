@@ -0,0 +1,122 @@
+//! Textbook secp256k1 arithmetic in affine coordinates
+//!
+//! Straightforward and slow, not constant time, written for readability so it can serve as a
+//! reference for differential testing. Field arithmetic is provided by `crypto-bigint`, which is
+//! independent from the k256 backend.
+
+use crypto_bigint::{
+    impl_modulus,
+    modular::constant_mod::{Residue, ResidueParams},
+    Encoding, U256,
+};
+
+impl_modulus!(
+    FieldModulus,
+    U256,
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+);
+
+/// Element of the base field
+pub type Fe = Residue<FieldModulus, { U256::LIMBS }>;
+
+/// Curve equation: $y^2 = x^3 + 7$
+const B: Fe = Fe::new(&U256::from_u8(7));
+
+/// Point on secp256k1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Point {
+    /// Point at infinity
+    Infinity,
+    /// Point with affine coordinates
+    Affine {
+        /// $x$ coordinate
+        x: Fe,
+        /// $y$ coordinate
+        y: Fe,
+    },
+}
+
+impl Point {
+    /// Curve generator
+    pub fn generator() -> Self {
+        Self::Affine {
+            x: Fe::new(&U256::from_be_hex(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            )),
+            y: Fe::new(&U256::from_be_hex(
+                "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            )),
+        }
+    }
+
+    /// Checks that the point satisfies the curve equation
+    pub fn is_on_curve(&self) -> bool {
+        match self {
+            Self::Infinity => true,
+            Self::Affine { x, y } => y.square() == x.square().mul(x).add(&B),
+        }
+    }
+
+    /// Point addition
+    pub fn add(&self, other: &Self) -> Self {
+        let (x1, y1, x2, y2) = match (*self, *other) {
+            (Self::Infinity, q) => return q,
+            (p, Self::Infinity) => return p,
+            (Self::Affine { x: x1, y: y1 }, Self::Affine { x: x2, y: y2 }) => (x1, y1, x2, y2),
+        };
+        if x1 == x2 {
+            return if y1 == y2 {
+                self.double()
+            } else {
+                Self::Infinity
+            };
+        }
+        let lambda = y2.sub(&y1).mul(&inv(&x2.sub(&x1)));
+        Self::from_lambda(lambda, x1, y1, x2)
+    }
+
+    /// Point doubling
+    pub fn double(&self) -> Self {
+        match *self {
+            Self::Affine { x, y } if y != Fe::ZERO => {
+                let three_x2 = x.square().mul(&Fe::new(&U256::from_u8(3)));
+                let lambda = three_x2.mul(&inv(&y.add(&y)));
+                Self::from_lambda(lambda, x, y, x)
+            }
+            _ => Self::Infinity,
+        }
+    }
+
+    /// Scalar multiplication via double-and-add, scalar is given in big-endian bytes
+    pub fn mul(&self, scalar_be: &[u8]) -> Self {
+        let mut result = Self::Infinity;
+        for byte in scalar_be {
+            for i in (0..8).rev() {
+                result = result.double();
+                if (byte >> i) & 1 == 1 {
+                    result = result.add(self);
+                }
+            }
+        }
+        result
+    }
+
+    /// Big-endian affine coordinates, or `None` for point at infinity
+    pub fn coords(&self) -> Option<([u8; 32], [u8; 32])> {
+        match self {
+            Self::Infinity => None,
+            Self::Affine { x, y } => Some((x.retrieve().to_be_bytes(), y.retrieve().to_be_bytes())),
+        }
+    }
+
+    fn from_lambda(lambda: Fe, x1: Fe, y1: Fe, x2: Fe) -> Self {
+        let x3 = lambda.square().sub(&x1).sub(&x2);
+        let y3 = lambda.mul(&x1.sub(&x3)).sub(&y1);
+        Self::Affine { x: x3, y: y3 }
+    }
+}
+
+/// Field inversion via Fermat's little theorem, input must be non-zero
+fn inv(x: &Fe) -> Fe {
+    x.pow(&FieldModulus::MODULUS.wrapping_sub(&U256::from_u8(2)))
+}
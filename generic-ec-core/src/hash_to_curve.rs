@@ -5,6 +5,18 @@ pub trait HashToCurve: Curve {
     fn hash_to_scalar(ctx: Tag, msgs: &[&[u8]]) -> Result<Self::Scalar, Error>;
 }
 
+/// Deterministic map from field element to a point, e.g. SSWU or Elligator 2
+pub trait MapToCurve: Curve {
+    /// Size of the map input in bytes
+    const MAP_INPUT_LEN: usize;
+
+    /// Interprets `u` as big-endian integer, reduces it modulo field order, and maps the
+    /// resulting field element to a point
+    ///
+    /// Returns error if `u.len() != MAP_INPUT_LEN`
+    fn map_to_curve(u: &[u8]) -> Result<Self::Point, Error>;
+}
+
 /// Domain separation tag
 ///
 /// DST is a unique identifier of the protocol in which hash to curve primitive is used.
@@ -24,6 +36,7 @@ impl<'s> Tag<'s> {
     pub const fn new_unwrap(tag: &'s [u8]) -> Self {
         match Self::new(tag) {
             Some(tag) => tag,
+            #[allow(clippy::panic)]
             None => panic!("tag must not be empty"),
         }
     }
@@ -4,7 +4,15 @@
 
 #![no_std]
 #![cfg_attr(not(test), forbid(unused_crate_dependencies))]
-#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used))]
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unreachable
+    )
+)]
 
 use core::fmt::Debug;
 use core::hash::Hash;
@@ -23,6 +31,11 @@ pub mod hash_to_curve;
 /// encoding and etc.
 pub trait Curve: Debug + Copy + Eq + Ord + Hash + Default + Sync + Send + 'static {
     const CURVE_NAME: &'static str;
+    /// Security level of the curve in bits
+    ///
+    /// Roughly, the best known attack on discrete logarithm takes $2^{\text{SECURITY\_LEVEL}}$
+    /// group operations.
+    const SECURITY_LEVEL: u32;
 
     type Point: Additive
         + From<CurveGenerator>
@@ -48,6 +61,7 @@ pub trait Curve: Debug + Copy + Eq + Ord + Hash + Default + Sync + Send + 'stati
         + Invertible
         + Zero
         + One
+        + IsHigh
         + Samplable
         + Zeroize
         + Copy
@@ -66,6 +80,75 @@ pub trait Curve: Debug + Copy + Eq + Ord + Hash + Default + Sync + Send + 'stati
     type CoordinateArray: ByteArray;
 }
 
+/// Curve providing at least 128 bits of security
+///
+/// Allows protocols to require minimal security level in the type system. Implementor must
+/// have [`Curve::SECURITY_LEVEL`] at least 128.
+pub trait Curve128: Curve {}
+
+/// Curve providing at least 192 bits of security
+///
+/// Implementor must have [`Curve::SECURITY_LEVEL`] at least 192.
+pub trait Curve192: Curve128 {}
+
+/// Curve supporting hash to curve primitive
+///
+/// Implemented for every curve implementing [`HashToCurve`](hash_to_curve::HashToCurve).
+/// Protocols hashing messages to points (e.g. VRFs) should bound on this trait, so they don't
+/// compile for curves without hash to curve.
+#[diagnostic::on_unimplemented(
+    message = "curve `{Self}` doesn't support hash to curve",
+    label = "protocol requires hashing to curve points",
+    note = "hash to curve is implemented for secp256k1 and secp256r1 curves"
+)]
+pub trait SupportsHashToCurve: Curve + hash_to_curve::HashToCurve {}
+
+impl<E: Curve + hash_to_curve::HashToCurve> SupportsHashToCurve for E {}
+
+/// Curve which order is not prime, i.e. it has small subgroups
+///
+/// Points of such curves need to be checked for being in the prime order subgroup, which is
+/// guaranteed for `Point<E>`. Protocols that operate on raw curve points (e.g. clear cofactor
+/// of hash output, as ECVRF does) may require this trait. Implementor must have
+/// [`COFACTOR`](Self::COFACTOR) greater than 1.
+#[diagnostic::on_unimplemented(
+    message = "curve `{Self}` has prime order",
+    label = "protocol requires a curve with cofactor",
+    note = "ed25519 is the only curve with cofactor supported out of box"
+)]
+pub trait HasCofactor: Curve {
+    /// Ratio of the curve order to the order of its prime subgroup
+    const COFACTOR: u32;
+}
+
+/// Curve equipped with a bilinear pairing
+///
+/// None of the curves supported out of box are pairing-friendly. Marker lets protocols that
+/// rely on pairings (e.g. BLS signatures) require them in the type system, curves implemented
+/// outside of this crate may implement it.
+#[diagnostic::on_unimplemented(
+    message = "curve `{Self}` is not pairing-friendly",
+    label = "protocol requires a pairing-friendly curve"
+)]
+pub trait PairingFriendly: Curve {}
+
+/// Curve with efficiently computable endomorphism $\phi(P) = \lambda P$
+///
+/// Endomorphism is used to speed up scalar multiplication via GLV method. On secp256k1,
+/// $\phi(x, y) = (\beta x, y)$ where $\beta$ is a cube root of unity modulo $p$.
+#[diagnostic::on_unimplemented(
+    message = "curve `{Self}` doesn't have efficiently computable endomorphism",
+    label = "protocol requires an endomorphism",
+    note = "endomorphism is implemented for secp256k1"
+)]
+pub trait HasEndomorphism: Curve {
+    /// Returns $\lambda$ such that $\phi(P) = \lambda P$ for every point $P$ in the prime
+    /// order subgroup
+    fn lambda() -> Self::Scalar;
+    /// Computes $\phi(P)$
+    fn endomorphism(point: &Self::Point) -> Self::Point;
+}
+
 pub trait Additive {
     fn add(a: &Self, b: &Self) -> Self;
     fn sub(a: &Self, b: &Self) -> Self;
@@ -110,6 +193,12 @@ pub trait One {
     fn is_one(x: &Self) -> Choice;
 }
 
+/// Scalar that can be compared against half of the group order
+pub trait IsHigh {
+    /// Checks (in constant-time) if $x > (q - 1) / 2$ where $q$ is the group order
+    fn is_high(x: &Self) -> Choice;
+}
+
 /// Type can be uniformely sampled from source of randomness
 pub trait Samplable {
     /// Uniformely samples a random value of `Self`
@@ -166,7 +255,30 @@ pub trait Decode: Sized {
 
 pub struct Error;
 
+/// Encoding of points as bytes indistinguishable from uniformly random, e.g. Elligator 2
+pub trait UniformEncoding: Curve {
+    /// Byte array holding the encoding
+    type UniformBytes: ByteArray;
+
+    /// Encodes a point as uniformly random bytes
+    ///
+    /// Only about half of the points are representable, returns `None` if `point` is not.
+    /// Encoding is randomized using `rng`, so encoding a random point gives uniformly random
+    /// bytes.
+    fn to_uniform_bytes<R: RngCore>(point: &Self::Point, rng: &mut R)
+        -> Option<Self::UniformBytes>;
+
+    /// Decodes a point from its uniform encoding
+    ///
+    /// Returned point is in the prime order subgroup. Returns `None` for a negligible
+    /// fraction of inputs that don't correspond to any point.
+    fn from_uniform_bytes(bytes: &Self::UniformBytes) -> Option<Self::Point>;
+}
+
 pub trait ByteArray: AsRef<[u8]> + AsMut<[u8]> + Clone + Send + Sync + 'static {
+    /// Length of the array
+    const LEN: usize;
+
     /// New byte array of zeroes
     ///
     /// Alternative to [`Default`] that is not implemented for generic `[T; N]`
@@ -175,12 +287,16 @@ pub trait ByteArray: AsRef<[u8]> + AsMut<[u8]> + Clone + Send + Sync + 'static {
 }
 
 impl<const N: usize> ByteArray for [u8; N] {
+    const LEN: usize = N;
+
     fn zeroes() -> Self {
         [0; N]
     }
 }
 
 impl<N: ArrayLength<u8>> ByteArray for GenericArray<u8, N> {
+    const LEN: usize = N::USIZE;
+
     fn zeroes() -> Self {
         GenericArray::default()
     }
@@ -1,3 +1,7 @@
+use group::ff;
+
+mod elligator;
+
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Default, zeroize::Zeroize)]
 pub struct Ed25519 {
     _private: (),
@@ -5,6 +9,7 @@ pub struct Ed25519 {
 
 impl generic_ec_core::Curve for Ed25519 {
     const CURVE_NAME: &'static str = "ed25519";
+    const SECURITY_LEVEL: u32 = 128;
 
     type Point = Point;
     type Scalar = Scalar;
@@ -18,10 +23,53 @@ impl generic_ec_core::Curve for Ed25519 {
     type CoordinateArray = [u8; 0];
 }
 
+impl generic_ec_core::Curve128 for Ed25519 {}
+const _: () = assert!(<Ed25519 as generic_ec_core::Curve>::SECURITY_LEVEL >= 128);
+
+impl generic_ec_core::HasCofactor for Ed25519 {
+    const COFACTOR: u32 = 8;
+}
+const _: () = assert!(<Ed25519 as generic_ec_core::HasCofactor>::COFACTOR > 1);
+
+/// Computes $\sum_i s_i P_i$ using variable-time multiscalar multiplication from `curve25519-dalek`
+///
+/// `curve25519-dalek` uses the fastest backend available on the target. On x86_64 it detects
+/// AVX2 support at runtime, and uses SIMD backend if CPU supports it.
+#[cfg(feature = "alloc")]
+pub fn vartime_multiscalar_mul(
+    scalars: impl IntoIterator<Item = Scalar>,
+    points: impl IntoIterator<Item = Point>,
+) -> Point {
+    use curve25519::traits::VartimeMultiscalarMul;
+    Point(curve25519::EdwardsPoint::vartime_multiscalar_mul(
+        scalars.into_iter().map(|s| s.0),
+        points.into_iter().map(|p| p.0),
+    ))
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, zeroize::Zeroize)]
 #[repr(transparent)]
 pub struct Point(pub curve25519::EdwardsPoint);
 
+impl Point {
+    /// Returns $u$ coordinate of the point on birationally equivalent Curve25519 (X25519 encoding)
+    ///
+    /// The map loses the sign of $x$ coordinate: $P$ and $-P$ have the same $u$ coordinate.
+    pub fn to_montgomery_u(&self) -> [u8; 32] {
+        self.0.to_montgomery().to_bytes()
+    }
+
+    /// Maps $u$ coordinate on Curve25519 back to the point with non-negative $x$ coordinate
+    ///
+    /// Most significant bit of `u` is ignored, as in X25519. Returns `None` if `u` doesn't
+    /// correspond to a point on the curve.
+    pub fn from_montgomery_u(u: &[u8; 32]) -> Option<Self> {
+        curve25519::montgomery::MontgomeryPoint(*u)
+            .to_edwards(0)
+            .map(Self)
+    }
+}
+
 impl generic_ec_core::Additive for Point {
     #[inline]
     fn add(a: &Self, b: &Self) -> Self {
@@ -208,6 +256,14 @@ impl generic_ec_core::One for Scalar {
     }
 }
 
+impl generic_ec_core::IsHigh for Scalar {
+    fn is_high(x: &Self) -> subtle::Choice {
+        // Group order q is odd, so 2x mod q is odd iff 2x >= q, i.e. iff x > (q-1)/2
+        let doubled = (x.0 + x.0).to_bytes();
+        subtle::Choice::from(doubled[0] & 1)
+    }
+}
+
 impl generic_ec_core::Samplable for Scalar {
     fn random<R: rand_core::RngCore>(rng: &mut R) -> Self {
         // Having crypto rng for scalar generation is not a hard requirement,
@@ -282,6 +338,8 @@ impl generic_ec_core::IntegerEncoding for Scalar {
     }
 }
 
+impl_prime_field!(impl[] for Scalar, field = curve25519::Scalar);
+
 impl core::cmp::PartialOrd for Scalar {
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
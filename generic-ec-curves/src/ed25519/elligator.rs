@@ -0,0 +1,134 @@
+//! Elligator 2 encoding of ed25519 points
+//!
+//! Points are mapped to Curve25519 in Montgomery form (birationally equivalent to ed25519), and
+//! the $u$-coordinate is mapped from/to field element $r$ via Elligator 2 map, as defined in
+//! [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380#name-elligator-2-method). The branch of
+//! Elligator 2 map selects the sign of Edwards $x$ coordinate: $x_1$ branch corresponds to
+//! non-negative $x$, and $x_2$ branch to negative $x$.
+//!
+//! Representative $r \le (p-1)/2$ takes 254 bits, the two most significant bits of the encoding
+//! are random.
+//!
+//! Uniform encoding of points in the prime order subgroup is distinguishable from random, so the
+//! point is shifted by a random low order point before encoding. Decoding clears the low order
+//! component.
+
+use crypto_bigint::{
+    impl_modulus,
+    modular::constant_mod::{Residue, ResidueParams},
+    Encoding, U256,
+};
+use curve25519::{constants::EIGHT_TORSION, montgomery::MontgomeryPoint, EdwardsPoint};
+use rand_core::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater, CtOption};
+
+use super::{Ed25519, Point};
+
+impl_modulus!(
+    FieldModulus,
+    U256,
+    "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+);
+
+type Fe = Residue<FieldModulus, { U256::LIMBS }>;
+
+/// Montgomery curve parameter $A$
+const A: Fe = Fe::new(&U256::from_u32(486662));
+/// $\sqrt{-1}$
+const SQRT_M1: Fe = Fe::new(&U256::from_be_hex(
+    "2b8324804fc1df0b2b4d00993dfbd7a72f431806ad2fe478c4ee1b274a0ea0b0",
+));
+/// $(p+3)/8$
+const SQRT_EXP: U256 =
+    U256::from_be_hex("0ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe");
+/// $(p-1)/2$
+const HALF_P: U256 =
+    U256::from_be_hex("3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff6");
+
+impl generic_ec_core::UniformEncoding for Ed25519 {
+    type UniformBytes = [u8; 32];
+
+    fn to_uniform_bytes<R: RngCore>(point: &Point, rng: &mut R) -> Option<[u8; 32]> {
+        let mut randomness = [0u8; 2];
+        rng.fill_bytes(&mut randomness);
+
+        let torsion_index = randomness[0] & 7;
+        let mut torsion = EdwardsPoint::default();
+        for (i, t) in (0u8..).zip(EIGHT_TORSION.iter()) {
+            torsion.conditional_assign(t, i.ct_eq(&torsion_index));
+        }
+        let q = point.0 + torsion;
+
+        let negative_x = Choice::from(q.compress().as_bytes()[31] >> 7);
+        let u = fe_from_bytes(&q.to_montgomery().to_bytes());
+        let u_plus_a = u.add(&A);
+
+        // x1 branch: r^2 = -(u + A) / 2u, x2 branch: r^2 = -u / 2(u + A)
+        let numerator = Fe::conditional_select(&u_plus_a, &u, negative_x).neg();
+        let denominator = Fe::conditional_select(&u, &u_plus_a, negative_x);
+        let (denominator_inv, invertible) = denominator.add(&denominator).invert();
+        let (is_square, r) = sqrt(&numerator.mul(&denominator_inv));
+
+        let r = r.retrieve();
+        let r = U256::conditional_select(&r, &r.neg_mod(&FieldModulus::MODULUS), r.ct_gt(&HALF_P));
+        let mut bytes = r.to_le_bytes();
+        bytes[31] |= randomness[1] & 0b1100_0000;
+
+        Option::from(CtOption::new(bytes, Choice::from(invertible) & is_square))
+    }
+
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Option<Point> {
+        let mut bytes = *bytes;
+        bytes[31] &= 0b0011_1111;
+        let r = fe_from_bytes(&bytes);
+
+        let (inv, invertible) = Fe::ONE.add(&r.square().add(&r.square())).invert();
+        if !bool::from(Choice::from(invertible)) {
+            return None;
+        }
+        let x1 = A.mul(&inv).neg();
+        let gx1 = x1.mul(&x1.mul(&x1.add(&A)).add(&Fe::ONE));
+        let (gx1_is_square, _) = sqrt(&gx1);
+        let u = Fe::conditional_select(&x1.add(&A).neg(), &x1, gx1_is_square);
+        let sign = u8::conditional_select(&1, &0, gx1_is_square);
+
+        let q = MontgomeryPoint(u.retrieve().to_le_bytes()).to_edwards(sign)?;
+        let cofactor_inv = curve25519::Scalar::from(8u8).invert();
+        Some(Point(q.mul_by_cofactor() * cofactor_inv))
+    }
+}
+
+fn fe_from_bytes(bytes: &[u8; 32]) -> Fe {
+    Fe::new(&U256::from_le_bytes(*bytes))
+}
+
+/// Returns `(is_square, sqrt(a))`, the root is meaningful only if `a` is a square
+fn sqrt(a: &Fe) -> (Choice, Fe) {
+    let candidate = a.pow(&SQRT_EXP);
+    let candidate_sq = candidate.square();
+    let is_root = candidate_sq.ct_eq(a);
+    let is_root_times_sqrt_m1 = candidate_sq.ct_eq(&a.neg());
+    let root = Fe::conditional_select(&candidate, &candidate.mul(&SQRT_M1), is_root_times_sqrt_m1);
+    (is_root | is_root_times_sqrt_m1, root)
+}
+
+#[cfg(test)]
+mod tests {
+    use subtle::ConstantTimeEq;
+
+    use super::{sqrt, Fe, SQRT_M1};
+
+    #[test]
+    fn field_constants() {
+        assert!(bool::from(SQRT_M1.square().ct_eq(&Fe::ONE.neg())));
+
+        let four = Fe::ONE.add(&Fe::ONE).square();
+        let (is_square, root) = sqrt(&four);
+        assert!(bool::from(is_square));
+        assert!(bool::from(root.square().ct_eq(&four)));
+
+        // 2 is not a square modulo 2^255 - 19
+        let (is_square, _) = sqrt(&Fe::ONE.add(&Fe::ONE));
+        assert!(!bool::from(is_square));
+    }
+}
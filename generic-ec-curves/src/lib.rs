@@ -4,12 +4,56 @@
 //! Refer to its documentation to learn more.
 //!
 //! [`generic-ec` crate]: https://docs.rs/generic-ec
+//!
+//! ## Backend tuning
+//!
+//! Performance-related options of backend crates are exposed as features of this crate, so you
+//! don't need to depend on backend crates directly:
+//!
+//! | Feature                        | Backend option                          | Effect |
+//! |--------------------------------|-----------------------------------------|--------|
+//! | `secp256k1-precomputed-tables` | `k256/precomputed-tables`, `k256/std`   | Generator multiplication uses precomputed tables which are computed lazily on first use. Requires `std` |
+//! | `alloc`                        | `curve25519-dalek/alloc`                | Enables `ed25519::vartime_multiscalar_mul` |
+//!
+//! Some backend options are configured via `--cfg` flags rather than features, they can be set
+//! in `RUSTFLAGS`:
+//!
+//! | Flag                                       | Effect |
+//! |--------------------------------------------|--------|
+//! | `curve25519_dalek_backend="simd"`          | Forces SIMD backend for ed25519 (x86_64 only). By default, it's chosen automatically: AVX2 support is detected at runtime |
+//! | `curve25519_dalek_backend="serial"`        | Forces serial (non-SIMD) backend for ed25519 |
+//! | `curve25519_dalek_backend="fiat"`          | Uses formally verified [fiat-crypto] field arithmetic for ed25519 |
+//! | `curve25519_dalek_bits="32"` / `"64"`      | Overrides word size of ed25519 field arithmetic, by default it's chosen based on target |
+//!
+//! Secp256r1 and stark curves don't have backend options affecting performance.
+//!
+//! [fiat-crypto]: https://github.com/mit-plv/fiat-crypto
+//!
+//! ## Reference backend
+//!
+//! `reference-backend` feature enables [`reference`](mod@reference) module with slow but simple
+//! implementation of secp256k1 and ed25519 curves, used for differential testing of the optimized
+//! backends.
 
-#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used))]
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unreachable
+    )
+)]
 #![no_std]
 
+#[cfg(any(feature = "ed25519", feature = "rust-crypto"))]
+#[macro_use]
+mod prime_field;
+
 #[cfg(feature = "ed25519")]
 pub mod ed25519;
+#[cfg(feature = "reference-backend")]
+pub mod reference;
 #[cfg(feature = "rust-crypto")]
 pub mod rust_crypto;
 
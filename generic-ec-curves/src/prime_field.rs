@@ -0,0 +1,124 @@
+/// Implements [`ff::Field`] and [`ff::PrimeField`] for a newtype over a prime field element
+///
+/// Backend scalar has to implement `ff::PrimeField` for `generic-ec` to implement zkcrypto traits
+/// for its `Scalar<E>` and `Point<E>`. All methods, constants, and arithmetic ops are forwarded
+/// to the wrapped field element. Expects `ff` to be in scope at the call site. Optional `where`
+/// clause applies to `ff::PrimeField` implementation.
+macro_rules! impl_prime_field {
+    (impl[$($generics:tt)*] for $ty:ty, field = $field:ty $(, where [$($where:tt)*])?) => {
+        impl<$($generics)*> ff::Field for $ty {
+            const ZERO: Self = Self(<$field as ff::Field>::ZERO);
+            const ONE: Self = Self(<$field as ff::Field>::ONE);
+
+            fn random(rng: impl rand_core::RngCore) -> Self {
+                Self(<$field as ff::Field>::random(rng))
+            }
+            fn square(&self) -> Self {
+                Self(ff::Field::square(&self.0))
+            }
+            fn double(&self) -> Self {
+                Self(ff::Field::double(&self.0))
+            }
+            fn invert(&self) -> subtle::CtOption<Self> {
+                ff::Field::invert(&self.0).map(Self)
+            }
+            fn sqrt_ratio(num: &Self, div: &Self) -> (subtle::Choice, Self) {
+                let (is_square, root) = <$field as ff::Field>::sqrt_ratio(&num.0, &div.0);
+                (is_square, Self(root))
+            }
+        }
+
+        impl<$($generics)*> ff::PrimeField for $ty $(where $($where)*)? {
+            type Repr = <$field as ff::PrimeField>::Repr;
+
+            const MODULUS: &'static str = <$field as ff::PrimeField>::MODULUS;
+            const NUM_BITS: u32 = <$field as ff::PrimeField>::NUM_BITS;
+            const CAPACITY: u32 = <$field as ff::PrimeField>::CAPACITY;
+            const TWO_INV: Self = Self(<$field as ff::PrimeField>::TWO_INV);
+            const MULTIPLICATIVE_GENERATOR: Self =
+                Self(<$field as ff::PrimeField>::MULTIPLICATIVE_GENERATOR);
+            const S: u32 = <$field as ff::PrimeField>::S;
+            const ROOT_OF_UNITY: Self = Self(<$field as ff::PrimeField>::ROOT_OF_UNITY);
+            const ROOT_OF_UNITY_INV: Self = Self(<$field as ff::PrimeField>::ROOT_OF_UNITY_INV);
+            const DELTA: Self = Self(<$field as ff::PrimeField>::DELTA);
+
+            fn from_repr(repr: Self::Repr) -> subtle::CtOption<Self> {
+                <$field as ff::PrimeField>::from_repr(repr).map(Self)
+            }
+            fn to_repr(&self) -> Self::Repr {
+                ff::PrimeField::to_repr(&self.0)
+            }
+            fn is_odd(&self) -> subtle::Choice {
+                ff::PrimeField::is_odd(&self.0)
+            }
+        }
+
+        impl<$($generics)*> From<u64> for $ty {
+            fn from(x: u64) -> Self {
+                Self(<$field>::from(x))
+            }
+        }
+
+        impl<$($generics)*> core::fmt::Debug for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_tuple("Scalar").field(&self.0).finish()
+            }
+        }
+
+        impl<$($generics)*> core::ops::Neg for $ty {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl_prime_field!(@op impl[$($generics)*] for $ty, Add add AddAssign add_assign);
+        impl_prime_field!(@op impl[$($generics)*] for $ty, Sub sub SubAssign sub_assign);
+        impl_prime_field!(@op impl[$($generics)*] for $ty, Mul mul MulAssign mul_assign);
+
+        impl<$($generics)*> core::iter::Sum for $ty {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(<Self as ff::Field>::ZERO, |acc, x| acc + x)
+            }
+        }
+        impl<'a, $($generics)*> core::iter::Sum<&'a $ty> for $ty {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(<Self as ff::Field>::ZERO, |acc, x| acc + x)
+            }
+        }
+        impl<$($generics)*> core::iter::Product for $ty {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(<Self as ff::Field>::ONE, |acc, x| acc * x)
+            }
+        }
+        impl<'a, $($generics)*> core::iter::Product<&'a $ty> for $ty {
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(<Self as ff::Field>::ONE, |acc, x| acc * x)
+            }
+        }
+    };
+    (@op impl[$($generics:tt)*] for $ty:ty, $op:ident $op_fn:ident $assign:ident $assign_fn:ident) => {
+        impl<$($generics)*> core::ops::$op for $ty {
+            type Output = Self;
+            fn $op_fn(self, rhs: Self) -> Self {
+                Self(core::ops::$op::$op_fn(self.0, rhs.0))
+            }
+        }
+        impl<'a, $($generics)*> core::ops::$op<&'a $ty> for $ty {
+            type Output = Self;
+            fn $op_fn(self, rhs: &'a Self) -> Self {
+                Self(core::ops::$op::$op_fn(self.0, rhs.0))
+            }
+        }
+        impl<$($generics)*> core::ops::$assign for $ty {
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = core::ops::$op::$op_fn(*self, rhs)
+            }
+        }
+        impl<'a, $($generics)*> core::ops::$assign<&'a $ty> for $ty {
+            fn $assign_fn(&mut self, rhs: &'a Self) {
+                *self = core::ops::$op::$op_fn(*self, rhs)
+            }
+        }
+    };
+}
@@ -0,0 +1,177 @@
+//! Reference implementation of secp256k1 and ed25519 curves
+//!
+//! Deliberately simple backend written to be easy to audit rather than fast: field and scalar
+//! arithmetic is done over big integers provided by `crypto-bigint`, points are kept in affine
+//! coordinates and added using textbook formulas, scalar multiplication is plain double-and-add.
+//! It doesn't share any code with optimized backends ([`Secp256k1`](crate::Secp256k1) is based on
+//! `k256`, [`Ed25519`](crate::Ed25519) on `curve25519-dalek`), but produces exactly the same
+//! encodings of points and scalars, so both can be tested against each other.
+//!
+//! **Reference backend is slow and not constant-time. It must never be used in production.**
+
+use crypto_bigint::{modular::constant_mod::ResidueParams, Encoding, U256};
+use generic_ec_core::{Additive, Zero};
+use rand_core::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater, CtOption};
+
+pub mod ed25519;
+pub mod secp256k1;
+
+pub use self::{ed25519::Ed25519, secp256k1::Secp256k1};
+
+const LIMBS: usize = U256::LIMBS;
+
+type Residue<M> = crypto_bigint::modular::constant_mod::Residue<M, LIMBS>;
+
+/// Scalar modulo group order `M`
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct Scalar<M: ResidueParams<LIMBS>>(pub Residue<M>);
+
+impl<M: ResidueParams<LIMBS>> Scalar<M> {
+    /// Returns scalar as an integer in range $[0, q)$
+    pub fn to_uint(&self) -> U256 {
+        self.0.retrieve()
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> generic_ec_core::Additive for Scalar<M> {
+    fn add(a: &Self, b: &Self) -> Self {
+        Self(a.0.add(&b.0))
+    }
+
+    fn sub(a: &Self, b: &Self) -> Self {
+        Self(a.0.sub(&b.0))
+    }
+
+    fn negate(x: &Self) -> Self {
+        Self(x.0.neg())
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> generic_ec_core::Multiplicative<Scalar<M>> for Scalar<M> {
+    type Output = Self;
+
+    fn mul(a: &Self, b: &Self) -> Self {
+        Self(a.0.mul(&b.0))
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> generic_ec_core::Invertible for Scalar<M> {
+    fn invert(x: &Self) -> CtOption<Self> {
+        let (inv, is_some) = x.0.invert();
+        CtOption::new(Self(inv), Choice::from(is_some))
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> generic_ec_core::Zero for Scalar<M> {
+    fn zero() -> Self {
+        Self(Residue::ZERO)
+    }
+
+    fn is_zero(x: &Self) -> Choice {
+        x.0.ct_eq(&Residue::ZERO)
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> generic_ec_core::One for Scalar<M> {
+    fn one() -> Self {
+        Self(Residue::ONE)
+    }
+
+    fn is_one(x: &Self) -> Choice {
+        x.0.ct_eq(&Residue::ONE)
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> generic_ec_core::IsHigh for Scalar<M> {
+    fn is_high(x: &Self) -> Choice {
+        // Group order is odd, so (q - 1) / 2 = q >> 1
+        x.to_uint().ct_gt(&M::MODULUS.shr_vartime(1))
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> generic_ec_core::Samplable for Scalar<M> {
+    fn random<R: RngCore>(rng: &mut R) -> Self {
+        // Rejection sampling: take as many random bits as group order has, retry if the
+        // integer is out of range
+        let mask = U256::MAX.shr_vartime(U256::BITS - M::MODULUS.bits_vartime());
+        loop {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            let x = U256::from_be_bytes(bytes).bitand(&mask);
+            if x < M::MODULUS {
+                return Self(Residue::new(&x));
+            }
+        }
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> zeroize::Zeroize for Scalar<M> {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> ConstantTimeEq for Scalar<M> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<M: ResidueParams<LIMBS> + Copy> ConditionallySelectable for Scalar<M> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(Residue::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> generic_ec_core::IntegerEncoding for Scalar<M> {
+    type Bytes = [u8; 32];
+
+    fn to_be_bytes(&self) -> Self::Bytes {
+        self.to_uint().to_be_bytes()
+    }
+
+    fn to_le_bytes(&self) -> Self::Bytes {
+        self.to_uint().to_le_bytes()
+    }
+
+    fn from_be_bytes(bytes: &Self::Bytes) -> Self {
+        Self(Residue::new(&U256::from_be_bytes(*bytes)))
+    }
+
+    fn from_le_bytes(bytes: &Self::Bytes) -> Self {
+        Self(Residue::new(&U256::from_le_bytes(*bytes)))
+    }
+
+    fn from_be_bytes_exact(bytes: &Self::Bytes) -> Option<Self> {
+        Self::from_uint_exact(U256::from_be_bytes(*bytes))
+    }
+
+    fn from_le_bytes_exact(bytes: &Self::Bytes) -> Option<Self> {
+        Self::from_uint_exact(U256::from_le_bytes(*bytes))
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> Scalar<M> {
+    fn from_uint_exact(x: U256) -> Option<Self> {
+        (x < M::MODULUS).then(|| Self(Residue::new(&x)))
+    }
+}
+
+impl<M: ResidueParams<LIMBS>> core::fmt::Debug for Scalar<M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Scalar").field(&self.to_uint()).finish()
+    }
+}
+
+/// Computes $k P$ via double-and-add, going from the most significant bit of $k$
+fn double_and_add<P: Additive + Zero>(k: &U256, point: &P) -> P {
+    (0..U256::BITS).rev().fold(P::zero(), |acc, i| {
+        let acc = P::double(&acc);
+        if k.bit_vartime(i) {
+            P::add(&acc, point)
+        } else {
+            acc
+        }
+    })
+}
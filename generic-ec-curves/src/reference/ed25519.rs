@@ -0,0 +1,262 @@
+//! Reference implementation of ed25519 curve
+//!
+//! Curve is twisted Edwards curve $-x^2 + y^2 = 1 + d x^2 y^2$ over $\mathbb{F}_p$,
+//! $p = 2^{255} - 19$. Addition formula is complete, so it works for any pair of points
+//! including the neutral element $(0, 1)$. Points are encoded as defined in
+//! [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.2): $y$ coordinate in
+//! little-endian with sign of $x$ stored in the most significant bit.
+//!
+//! Decoding is as permissive as in [`Ed25519`](crate::Ed25519): $y \ge p$ is reduced modulo $p$,
+//! and sign bit set for $x = 0$ is ignored.
+
+use crypto_bigint::{impl_modulus, modular::constant_mod::ResidueParams, Encoding, U256};
+use generic_ec_core::CurveGenerator;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use super::{double_and_add, Residue};
+
+impl_modulus!(
+    FieldModulus,
+    U256,
+    "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+);
+impl_modulus!(
+    GroupOrder,
+    U256,
+    "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed"
+);
+
+/// Element of the base field
+type Fe = Residue<FieldModulus>;
+
+/// Curve parameter $d = -121665/121666$
+const D: Fe = Fe::new(&U256::from_be_hex(
+    "52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3",
+));
+/// $\sqrt{-1}$
+const SQRT_M1: Fe = Fe::new(&U256::from_be_hex(
+    "2b8324804fc1df0b2b4d00993dfbd7a72f431806ad2fe478c4ee1b274a0ea0b0",
+));
+/// $(p-5)/8$
+const SQRT_RATIO_EXP: U256 =
+    U256::from_be_hex("0ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffd");
+
+/// Reference implementation of ed25519 curve
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Default, zeroize::Zeroize)]
+pub struct Ed25519 {
+    _private: (),
+}
+
+impl generic_ec_core::Curve for Ed25519 {
+    const CURVE_NAME: &'static str = "ed25519";
+    const SECURITY_LEVEL: u32 = 128;
+
+    type Point = Point;
+    type Scalar = Scalar;
+
+    type CompressedPointArray = [u8; 32];
+    type UncompressedPointArray = [u8; 32];
+
+    type ScalarArray = [u8; 32];
+
+    type CoordinateArray = [u8; 0];
+}
+
+impl generic_ec_core::Curve128 for Ed25519 {}
+const _: () = assert!(<Ed25519 as generic_ec_core::Curve>::SECURITY_LEVEL >= 128);
+
+impl generic_ec_core::HasCofactor for Ed25519 {
+    const COFACTOR: u32 = 8;
+}
+const _: () = assert!(<Ed25519 as generic_ec_core::HasCofactor>::COFACTOR > 1);
+
+/// Scalar modulo order of the prime subgroup
+pub type Scalar = super::Scalar<GroupOrder>;
+
+/// Point in affine coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Point {
+    /// $x$ coordinate
+    pub x: Fe,
+    /// $y$ coordinate
+    pub y: Fe,
+}
+
+impl Point {
+    /// Neutral element $(0, 1)$
+    pub const IDENTITY: Self = Self {
+        x: Fe::ZERO,
+        y: Fe::ONE,
+    };
+}
+
+impl generic_ec_core::Additive for Point {
+    fn add(a: &Self, b: &Self) -> Self {
+        // x3 = (x1 y2 + y1 x2) / (1 + d x1 x2 y1 y2)
+        // y3 = (y1 y2 + x1 x2) / (1 - d x1 x2 y1 y2)
+        //
+        // d is not a square, so denominators are never zero
+        let t = D.mul(&a.x).mul(&b.x).mul(&a.y).mul(&b.y);
+        let x3 = a.x.mul(&b.y).add(&a.y.mul(&b.x));
+        let y3 = a.y.mul(&b.y).add(&a.x.mul(&b.x));
+        Self {
+            x: x3.mul(&Fe::ONE.add(&t).invert().0),
+            y: y3.mul(&Fe::ONE.sub(&t).invert().0),
+        }
+    }
+
+    fn sub(a: &Self, b: &Self) -> Self {
+        Self::add(a, &Self::negate(b))
+    }
+
+    fn negate(x: &Self) -> Self {
+        Self {
+            x: x.x.neg(),
+            y: x.y,
+        }
+    }
+}
+
+impl From<CurveGenerator> for Point {
+    fn from(_: CurveGenerator) -> Self {
+        Self {
+            x: Fe::new(&U256::from_be_hex(
+                "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a",
+            )),
+            y: Fe::new(&U256::from_be_hex(
+                "6666666666666666666666666666666666666666666666666666666666666658",
+            )),
+        }
+    }
+}
+
+impl generic_ec_core::Zero for Point {
+    fn zero() -> Self {
+        Self::IDENTITY
+    }
+
+    fn is_zero(x: &Self) -> Choice {
+        x.ct_eq(&Self::IDENTITY)
+    }
+}
+
+impl generic_ec_core::OnCurve for Point {
+    fn is_on_curve(&self) -> Choice {
+        let x2 = self.x.square();
+        let y2 = self.y.square();
+        let lhs = y2.sub(&x2);
+        let rhs = Fe::ONE.add(&D.mul(&x2).mul(&y2));
+        Choice::from(u8::from(lhs == rhs))
+    }
+}
+
+impl generic_ec_core::SmallFactor for Point {
+    fn is_torsion_free(&self) -> Choice {
+        // Point is in the prime order subgroup iff `l P = 0` where `l` is order of the subgroup
+        let l_p = double_and_add(&GroupOrder::MODULUS, self);
+        l_p.ct_eq(&Self::IDENTITY)
+    }
+}
+
+impl zeroize::Zeroize for Point {
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+        self.y.zeroize();
+        *self = Self::IDENTITY
+    }
+}
+
+impl ConstantTimeEq for Point {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.x.ct_eq(&other.x) & self.y.ct_eq(&other.y)
+    }
+}
+
+impl ConditionallySelectable for Point {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            x: Fe::conditional_select(&a.x, &b.x, choice),
+            y: Fe::conditional_select(&a.y, &b.y, choice),
+        }
+    }
+}
+
+impl Default for Point {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl generic_ec_core::CompressedEncoding for Point {
+    type Bytes = [u8; 32];
+
+    fn to_bytes_compressed(&self) -> Self::Bytes {
+        let mut bytes = self.y.retrieve().to_le_bytes();
+        bytes[31] |= u8::from(is_negative(&self.x)) << 7;
+        bytes
+    }
+}
+
+impl generic_ec_core::UncompressedEncoding for Point {
+    type Bytes = [u8; 32];
+
+    fn to_bytes_uncompressed(&self) -> Self::Bytes {
+        generic_ec_core::CompressedEncoding::to_bytes_compressed(self)
+    }
+}
+
+impl generic_ec_core::Decode for Point {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut bytes: [u8; 32] = bytes.try_into().ok()?;
+        let x_is_negative = bytes[31] >> 7 == 1;
+        bytes[31] &= 0x7f;
+        let y = Fe::new(&U256::from_le_bytes(bytes));
+
+        // x^2 = (y^2 - 1) / (d y^2 + 1)
+        let y2 = y.square();
+        let x = sqrt_ratio(&y2.sub(&Fe::ONE), &D.mul(&y2).add(&Fe::ONE))?;
+        let x = if is_negative(&x) { x.neg() } else { x };
+        let x = if x_is_negative { x.neg() } else { x };
+        Some(Self { x, y })
+    }
+}
+
+impl generic_ec_core::Multiplicative<Point> for Scalar {
+    type Output = Point;
+
+    fn mul(a: &Self, b: &Point) -> Point {
+        double_and_add(&a.to_uint(), b)
+    }
+}
+
+impl generic_ec_core::Multiplicative<CurveGenerator> for Scalar {
+    type Output = Point;
+
+    fn mul(a: &Self, _: &CurveGenerator) -> Point {
+        double_and_add(&a.to_uint(), &Point::from(CurveGenerator))
+    }
+}
+
+/// Computes $\sqrt{u/v}$, returns `None` if it's not a square
+///
+/// Uses the method from [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.3):
+/// candidate root is $u v^3 (u v^7)^{(p-5)/8}$, which needs to be multiplied at $\sqrt{-1}$
+/// if its square is $-u/v$.
+fn sqrt_ratio(u: &Fe, v: &Fe) -> Option<Fe> {
+    let v3 = v.square().mul(v);
+    let v7 = v3.square().mul(v);
+    let x = u.mul(&v3).mul(&u.mul(&v7).pow(&SQRT_RATIO_EXP));
+    let vx2 = v.mul(&x.square());
+    if vx2 == *u {
+        Some(x)
+    } else if vx2 == u.neg() {
+        Some(x.mul(&SQRT_M1))
+    } else {
+        None
+    }
+}
+
+/// Field element is negative if its canonical representation is odd
+fn is_negative(x: &Fe) -> bool {
+    x.retrieve().bit_vartime(0)
+}
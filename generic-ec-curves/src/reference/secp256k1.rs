@@ -0,0 +1,364 @@
+//! Reference implementation of secp256k1 curve
+//!
+//! Curve equation is $y^2 = x^3 + 7$ over $\mathbb{F}_p$, points are encoded as defined in
+//! [SEC1](https://www.secg.org/sec1-v2.pdf), section 2.3.3. Point at infinity is encoded as
+//! all-zero bytes, same as [`Secp256k1`](crate::Secp256k1) does.
+
+use crypto_bigint::{impl_modulus, modular::constant_mod::ResidueParams, Encoding, U256};
+use generic_ec_core::{
+    coords::{HasAffineX, HasAffineXAndParity, HasAffineXY, HasAffineY, Parity},
+    CurveGenerator,
+};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use super::{double_and_add, Residue};
+
+impl_modulus!(
+    FieldModulus,
+    U256,
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+);
+impl_modulus!(
+    GroupOrder,
+    U256,
+    "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+);
+
+/// Element of the base field
+type Fe = Residue<FieldModulus>;
+
+/// Curve parameter $b$
+const B: Fe = Fe::new(&U256::from_u8(7));
+/// $\beta$, cube root of unity modulo $p$ defining endomorphism $\phi(x, y) = (\beta x, y)$
+const BETA: Fe = Fe::new(&U256::from_be_hex(
+    "7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee",
+));
+/// $\lambda$, cube root of unity modulo group order such that $\phi(P) = \lambda P$
+const LAMBDA: U256 =
+    U256::from_be_hex("5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72");
+/// $(p+1)/4$, used to compute square roots as $p = 3 \pmod 4$
+const SQRT_EXP: U256 =
+    U256::from_be_hex("3fffffffffffffffffffffffffffffffffffffffffffffffffffffffbfffff0c");
+
+/// Reference implementation of secp256k1 curve
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Default, zeroize::Zeroize)]
+pub struct Secp256k1 {
+    _private: (),
+}
+
+impl generic_ec_core::Curve for Secp256k1 {
+    const CURVE_NAME: &'static str = "secp256k1";
+    const SECURITY_LEVEL: u32 = 128;
+
+    type Point = Point;
+    type Scalar = Scalar;
+
+    type CompressedPointArray = [u8; 33];
+    type UncompressedPointArray = [u8; 65];
+
+    type ScalarArray = [u8; 32];
+
+    type CoordinateArray = [u8; 32];
+}
+
+impl generic_ec_core::Curve128 for Secp256k1 {}
+const _: () = assert!(<Secp256k1 as generic_ec_core::Curve>::SECURITY_LEVEL >= 128);
+
+impl generic_ec_core::HasEndomorphism for Secp256k1 {
+    fn lambda() -> Scalar {
+        super::Scalar(Residue::new(&LAMBDA))
+    }
+
+    fn endomorphism(point: &Point) -> Point {
+        match *point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine { x, y } => Point::Affine { x: x.mul(&BETA), y },
+        }
+    }
+}
+
+/// Scalar modulo group order
+pub type Scalar = super::Scalar<GroupOrder>;
+
+/// Point in affine coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Point {
+    /// Point at infinity
+    #[default]
+    Infinity,
+    /// Point with affine coordinates $(x, y)$
+    Affine {
+        /// $x$ coordinate
+        x: Fe,
+        /// $y$ coordinate
+        y: Fe,
+    },
+}
+
+impl Point {
+    /// Constructs a point from its affine coordinates, returns `None` if point is not on the curve
+    pub fn from_coords(x: Fe, y: Fe) -> Option<Self> {
+        let point = Self::Affine { x, y };
+        bool::from(generic_ec_core::OnCurve::is_on_curve(&point)).then_some(point)
+    }
+
+    /// Constructs a point from $x$ coordinate and parity of $y$
+    ///
+    /// Returns `None` if $x^3 + 7$ is not a square
+    fn from_x(x: Fe, y_is_odd: bool) -> Option<Self> {
+        let y2 = x.square().mul(&x).add(&B);
+        let y = y2.pow(&SQRT_EXP);
+        if y.square() != y2 {
+            return None;
+        }
+        let y = if is_odd(&y) == y_is_odd { y } else { y.neg() };
+        Some(Self::Affine { x, y })
+    }
+
+    fn from_lambda(lambda: Fe, x1: Fe, y1: Fe, x2: Fe) -> Self {
+        let x3 = lambda.square().sub(&x1).sub(&x2);
+        let y3 = lambda.mul(&x1.sub(&x3)).sub(&y1);
+        Self::Affine { x: x3, y: y3 }
+    }
+}
+
+impl generic_ec_core::Additive for Point {
+    fn add(a: &Self, b: &Self) -> Self {
+        let (x1, y1, x2, y2) = match (*a, *b) {
+            (Self::Infinity, q) => return q,
+            (p, Self::Infinity) => return p,
+            (Self::Affine { x: x1, y: y1 }, Self::Affine { x: x2, y: y2 }) => (x1, y1, x2, y2),
+        };
+        if x1 == x2 {
+            // Either P + P, or P + (-P)
+            return if y1 == y2 {
+                Self::double(a)
+            } else {
+                Self::Infinity
+            };
+        }
+        // x1 != x2, so denominator is invertible
+        let lambda = y2.sub(&y1).mul(&x2.sub(&x1).invert().0);
+        Self::from_lambda(lambda, x1, y1, x2)
+    }
+
+    fn sub(a: &Self, b: &Self) -> Self {
+        Self::add(a, &Self::negate(b))
+    }
+
+    fn negate(x: &Self) -> Self {
+        match *x {
+            Self::Infinity => Self::Infinity,
+            Self::Affine { x, y } => Self::Affine { x, y: y.neg() },
+        }
+    }
+
+    fn double(x: &Self) -> Self {
+        match *x {
+            // Point of order 2 would have y = 0, but there's no such point on secp256k1
+            Self::Affine { x, y } if y != Fe::ZERO => {
+                let three_x2 = x.square().mul(&Fe::new(&U256::from_u8(3)));
+                let lambda = three_x2.mul(&y.add(&y).invert().0);
+                Self::from_lambda(lambda, x, y, x)
+            }
+            _ => Self::Infinity,
+        }
+    }
+}
+
+impl From<CurveGenerator> for Point {
+    fn from(_: CurveGenerator) -> Self {
+        Self::Affine {
+            x: Fe::new(&U256::from_be_hex(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            )),
+            y: Fe::new(&U256::from_be_hex(
+                "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            )),
+        }
+    }
+}
+
+impl generic_ec_core::Zero for Point {
+    fn zero() -> Self {
+        Self::Infinity
+    }
+
+    fn is_zero(x: &Self) -> Choice {
+        Choice::from(u8::from(*x == Self::Infinity))
+    }
+}
+
+impl generic_ec_core::OnCurve for Point {
+    fn is_on_curve(&self) -> Choice {
+        let on_curve = match self {
+            Self::Infinity => true,
+            Self::Affine { x, y } => y.square() == x.square().mul(x).add(&B),
+        };
+        Choice::from(u8::from(on_curve))
+    }
+}
+
+impl generic_ec_core::SmallFactor for Point {
+    fn is_torsion_free(&self) -> Choice {
+        // Cofactor is 1
+        Choice::from(1)
+    }
+}
+
+impl zeroize::Zeroize for Point {
+    fn zeroize(&mut self) {
+        if let Self::Affine { x, y } = self {
+            x.zeroize();
+            y.zeroize();
+        }
+        *self = Self::Infinity
+    }
+}
+
+impl ConstantTimeEq for Point {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Choice::from(u8::from(self == other))
+    }
+}
+
+impl ConditionallySelectable for Point {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        if bool::from(choice) {
+            *b
+        } else {
+            *a
+        }
+    }
+}
+
+impl generic_ec_core::CompressedEncoding for Point {
+    type Bytes = [u8; 33];
+
+    fn to_bytes_compressed(&self) -> Self::Bytes {
+        let mut bytes = [0u8; 33];
+        if let Self::Affine { x, y } = self {
+            bytes[0] = if is_odd(y) { 0x03 } else { 0x02 };
+            bytes[1..].copy_from_slice(&x.retrieve().to_be_bytes());
+        }
+        bytes
+    }
+}
+
+impl generic_ec_core::UncompressedEncoding for Point {
+    type Bytes = [u8; 65];
+
+    fn to_bytes_uncompressed(&self) -> Self::Bytes {
+        let mut bytes = [0u8; 65];
+        if let Self::Affine { x, y } = self {
+            bytes[0] = 0x04;
+            bytes[1..33].copy_from_slice(&x.retrieve().to_be_bytes());
+            bytes[33..].copy_from_slice(&y.retrieve().to_be_bytes());
+        }
+        bytes
+    }
+}
+
+impl generic_ec_core::Decode for Point {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.iter().all(|b| *b == 0) {
+            return Some(Self::Infinity);
+        }
+        let (tag, rest) = bytes.split_first()?;
+        match (tag, rest) {
+            (0x02 | 0x03, x) if x.len() == 32 => Self::from_x(fe_from_be_bytes(x)?, *tag == 0x03),
+            (0x04, xy) if xy.len() == 64 => {
+                Self::from_coords(fe_from_be_bytes(&xy[..32])?, fe_from_be_bytes(&xy[32..])?)
+            }
+            // Compact encoding defined in BIP-340: only x coordinate, y is even
+            (0x05, x) if x.len() == 32 => Self::from_x(fe_from_be_bytes(x)?, false),
+            _ => None,
+        }
+    }
+}
+
+impl generic_ec_core::Multiplicative<Point> for Scalar {
+    type Output = Point;
+
+    fn mul(a: &Self, b: &Point) -> Point {
+        double_and_add(&a.to_uint(), b)
+    }
+}
+
+impl generic_ec_core::Multiplicative<CurveGenerator> for Scalar {
+    type Output = Point;
+
+    fn mul(a: &Self, _: &CurveGenerator) -> Point {
+        double_and_add(&a.to_uint(), &Point::from(CurveGenerator))
+    }
+}
+
+impl HasAffineX for Secp256k1 {
+    fn x(point: &Point) -> Option<[u8; 32]> {
+        Self::x_and_y(point).map(|(x, _)| x)
+    }
+}
+
+impl HasAffineY for Secp256k1 {
+    fn y(point: &Point) -> Option<[u8; 32]> {
+        Self::x_and_y(point).map(|(_, y)| y)
+    }
+}
+
+impl HasAffineXY for Secp256k1 {
+    fn x_and_y(point: &Point) -> Option<([u8; 32], [u8; 32])> {
+        match point {
+            Point::Infinity => None,
+            Point::Affine { x, y } => {
+                Some((x.retrieve().to_be_bytes(), y.retrieve().to_be_bytes()))
+            }
+        }
+    }
+
+    fn from_x_and_y(x: &[u8; 32], y: &[u8; 32]) -> Option<Point> {
+        Point::from_coords(fe_from_be_bytes(x)?, fe_from_be_bytes(y)?)
+    }
+}
+
+impl HasAffineXAndParity for Secp256k1 {
+    fn x_and_parity(point: &Point) -> Option<([u8; 32], Parity)> {
+        match point {
+            Point::Infinity => None,
+            Point::Affine { x, y } => {
+                let parity = if is_odd(y) { Parity::Odd } else { Parity::Even };
+                Some((x.retrieve().to_be_bytes(), parity))
+            }
+        }
+    }
+
+    fn from_x_and_parity(x: &[u8; 32], y_parity: Parity) -> Option<Point> {
+        Point::from_x(fe_from_be_bytes(x)?, y_parity.is_odd())
+    }
+}
+
+/// Parses a field element, returns `None` if it's not in canonical form (i.e. not less than $p$)
+fn fe_from_be_bytes(bytes: &[u8]) -> Option<Fe> {
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    let x = U256::from_be_bytes(bytes);
+    (x < FieldModulus::MODULUS).then(|| Fe::new(&x))
+}
+
+fn is_odd(x: &Fe) -> bool {
+    x.retrieve().bit_vartime(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use generic_ec_core::{CurveGenerator, HasEndomorphism, Multiplicative};
+
+    use super::{Point, Secp256k1};
+
+    #[test]
+    fn endomorphism() {
+        let g = Point::from(CurveGenerator);
+        assert_eq!(
+            Secp256k1::endomorphism(&g),
+            Multiplicative::mul(&Secp256k1::lambda(), &g)
+        );
+    }
+}
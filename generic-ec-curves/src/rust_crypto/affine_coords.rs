@@ -24,9 +24,8 @@ where
         {
             Coordinates::Identity => None,
             Coordinates::Uncompressed { x, .. } => Some(x.clone()),
-            Coordinates::Compact { .. } | Coordinates::Compressed { .. } => {
-                unreachable!("point was encoded in uncompressed form")
-            }
+            // Never happens as point was encoded in uncompressed form
+            Coordinates::Compact { .. } | Coordinates::Compressed { .. } => None,
         }
     }
 }
@@ -53,9 +52,8 @@ where
             Coordinates::Compressed { x, y_is_odd } => {
                 Some((x.clone(), if y_is_odd { Parity::Odd } else { Parity::Even }))
             }
-            Coordinates::Compact { .. } | Coordinates::Uncompressed { .. } => {
-                unreachable!("point was encoded in uncompressed form")
-            }
+            // Never happens as point was encoded in compressed form
+            Coordinates::Compact { .. } | Coordinates::Uncompressed { .. } => None,
         }
     }
 
@@ -91,9 +89,8 @@ where
         {
             Coordinates::Identity => None,
             Coordinates::Uncompressed { y, .. } => Some(y.clone()),
-            Coordinates::Compact { .. } | Coordinates::Compressed { .. } => {
-                unreachable!("point was encoded in uncompressed form")
-            }
+            // Never happens as point was encoded in uncompressed form
+            Coordinates::Compact { .. } | Coordinates::Compressed { .. } => None,
         }
     }
 }
@@ -118,9 +115,8 @@ where
         {
             Coordinates::Identity => None,
             Coordinates::Uncompressed { x, y } => Some((x.clone(), y.clone())),
-            Coordinates::Compact { .. } | Coordinates::Compressed { .. } => {
-                unreachable!("point was encoded in uncompressed form")
-            }
+            // Never happens as point was encoded in uncompressed form
+            Coordinates::Compact { .. } | Coordinates::Compressed { .. } => None,
         }
     }
 
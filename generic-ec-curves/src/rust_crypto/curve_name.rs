@@ -1,18 +1,23 @@
 pub trait CurveName {
     const CURVE_NAME: &'static str;
+    const SECURITY_LEVEL: u32;
 }
 
 #[cfg(feature = "secp256r1")]
 impl CurveName for p256::NistP256 {
     const CURVE_NAME: &'static str = "secp256r1";
+    const SECURITY_LEVEL: u32 = 128;
 }
 
 #[cfg(feature = "secp256k1")]
 impl CurveName for k256::Secp256k1 {
     const CURVE_NAME: &'static str = "secp256k1";
+    const SECURITY_LEVEL: u32 = 128;
 }
 
 #[cfg(feature = "stark")]
 impl CurveName for stark_curve::StarkCurve {
     const CURVE_NAME: &'static str = "stark";
+    // Group order is ~2^251, so Pollard's rho takes ~2^125.5 operations
+    const SECURITY_LEVEL: u32 = 125;
 }
@@ -1,9 +1,10 @@
 use elliptic_curve::{
+    generic_array::{typenum::Unsigned, GenericArray},
     group::cofactor::CofactorGroup,
-    hash2curve::{ExpandMsg, FromOkm, GroupDigest},
+    hash2curve::{ExpandMsg, FromOkm, GroupDigest, MapToCurve as _},
     CurveArithmetic,
 };
-use generic_ec_core::hash_to_curve::{HashToCurve, Tag};
+use generic_ec_core::hash_to_curve::{HashToCurve, MapToCurve, Tag};
 
 use super::{RustCryptoCurve, RustCryptoPoint, RustCryptoScalar};
 
@@ -28,3 +29,18 @@ where
         Ok(RustCryptoScalar(scalar))
     }
 }
+
+impl<C, X> MapToCurve for RustCryptoCurve<C, X>
+where
+    C: CurveArithmetic + GroupDigest,
+    C::ProjectivePoint: CofactorGroup,
+    RustCryptoCurve<C, X>: generic_ec_core::Curve<Point = RustCryptoPoint<C>>,
+{
+    const MAP_INPUT_LEN: usize = <<C::FieldElement as FromOkm>::Length as Unsigned>::USIZE;
+
+    fn map_to_curve(u: &[u8]) -> Result<Self::Point, generic_ec_core::Error> {
+        let u = GenericArray::from_exact_iter(u.iter().copied()).ok_or(generic_ec_core::Error)?;
+        let point = C::FieldElement::from_okm(&u).map_to_curve();
+        Ok(RustCryptoPoint(point.clear_cofactor().into()))
+    }
+}
@@ -82,6 +82,7 @@ where
     X: 'static,
 {
     const CURVE_NAME: &'static str = C::CURVE_NAME;
+    const SECURITY_LEVEL: u32 = C::SECURITY_LEVEL;
 
     type Point = RustCryptoPoint<C>;
     type Scalar = RustCryptoScalar<C>;
@@ -94,6 +95,29 @@ where
     type CoordinateArray = elliptic_curve::FieldBytes<C>;
 }
 
+#[cfg(feature = "secp256k1")]
+impl generic_ec_core::Curve128 for Secp256k1 {}
+#[cfg(feature = "secp256k1")]
+const _: () = assert!(<Secp256k1 as Curve>::SECURITY_LEVEL >= 128);
+
+#[cfg(feature = "secp256k1")]
+impl generic_ec_core::HasEndomorphism for Secp256k1 {
+    fn lambda() -> Self::Scalar {
+        RustCryptoScalar(Reduce::reduce(crypto_bigint::U256::from_be_hex(
+            "5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72",
+        )))
+    }
+
+    fn endomorphism(point: &Self::Point) -> Self::Point {
+        RustCryptoPoint(point.0.endomorphism())
+    }
+}
+
+#[cfg(feature = "secp256r1")]
+impl generic_ec_core::Curve128 for Secp256r1 {}
+#[cfg(feature = "secp256r1")]
+const _: () = assert!(<Secp256r1 as Curve>::SECURITY_LEVEL >= 128);
+
 impl<C: CurveName, X> fmt::Debug for RustCryptoCurve<C, X> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("RustCryptoCurve")
@@ -149,7 +173,7 @@ impl<C, X> Default for RustCryptoCurve<C, X> {
 mod tests {
     use generic_ec_core::{
         coords::{HasAffineX, HasAffineXAndParity, HasAffineXY},
-        hash_to_curve::HashToCurve,
+        hash_to_curve::{HashToCurve, MapToCurve},
         Curve,
     };
 
@@ -158,7 +182,7 @@ mod tests {
     /// Asserts that `E` implements `Curve`
     fn _impls_curve<E: Curve>() {}
     fn _exposes_affine_coords<E: HasAffineX + HasAffineXAndParity + HasAffineXY>() {}
-    fn _impls_hash_to_curve<E: HashToCurve>() {}
+    fn _impls_hash_to_curve<E: HashToCurve + MapToCurve>() {}
 
     fn _curves_impl_trait() {
         _impls_curve::<Secp256k1>();
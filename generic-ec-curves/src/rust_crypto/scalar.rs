@@ -2,10 +2,13 @@ use core::ops::Mul;
 
 use crypto_bigint::prelude::ArrayEncoding;
 use crypto_bigint::ByteArray;
-use elliptic_curve::ops::Reduce;
-use elliptic_curve::{Curve, CurveArithmetic, Field, Group, PrimeField, ScalarPrimitive};
+use elliptic_curve::ff;
+use elliptic_curve::ops::{MulByGenerator, Reduce};
+use elliptic_curve::scalar::IsHigh as _;
+use elliptic_curve::{Curve, CurveArithmetic, Field, PrimeField, ScalarPrimitive};
 use generic_ec_core::{
-    Additive, CurveGenerator, IntegerEncoding, Invertible, Multiplicative, One, Samplable, Zero,
+    Additive, CurveGenerator, IntegerEncoding, Invertible, IsHigh, Multiplicative, One, Samplable,
+    Zero,
 };
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 use zeroize::DefaultIsZeroes;
@@ -54,7 +57,10 @@ where
     type Output = super::RustCryptoPoint<E>;
 
     fn mul(a: &Self, _b: &CurveGenerator) -> Self::Output {
-        super::RustCryptoPoint(E::ProjectivePoint::generator() * a.0)
+        // Uses precomputed tables if backend provides them
+        super::RustCryptoPoint(<E::ProjectivePoint as MulByGenerator>::mul_by_generator(
+            &a.0,
+        ))
     }
 }
 
@@ -84,6 +90,12 @@ impl<E: CurveArithmetic> One for RustCryptoScalar<E> {
     }
 }
 
+impl<E: CurveArithmetic> IsHigh for RustCryptoScalar<E> {
+    fn is_high(x: &Self) -> Choice {
+        x.0.is_high()
+    }
+}
+
 impl<E: CurveArithmetic> Samplable for RustCryptoScalar<E> {
     fn random<R: rand_core::RngCore>(rng: &mut R) -> Self {
         let mut bytes: <E::Scalar as PrimeField>::Repr = Default::default();
@@ -197,3 +209,9 @@ where
         Some(Self(E::Scalar::from(scalar_core?)))
     }
 }
+
+impl_prime_field!(
+    impl[E: CurveArithmetic] for RustCryptoScalar<E>,
+    field = E::Scalar,
+    where [<E::FieldBytesSize as elliptic_curve::generic_array::ArrayLength<u8>>::ArrayType: Copy]
+);
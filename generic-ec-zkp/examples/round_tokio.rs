@@ -0,0 +1,71 @@
+//! Drives [`Round`] on tokio
+//!
+//! Three parties jointly evaluate [DVRF](generic_ec_zkp::dvrf), each party running in its own
+//! task. Partial evaluations are broadcast through tokio channels, which stand in for a real
+//! network transport.
+
+use generic_ec::{curves::Secp256k1, hash_to_curve::Tag, Point, SecretScalar};
+use generic_ec_zkp::{
+    dvrf,
+    party_index::PartyIndex,
+    polynomial::Polynomial,
+    round::{MessageOf, Round},
+    transcript::Transcript,
+};
+use rand::rngs::OsRng;
+use tokio::sync::mpsc;
+
+/// Feeds the round with messages received from the channel until it outputs the result
+///
+/// Rejected messages are reported, but don't abort the round.
+async fn drive<R>(mut round: R, mut incoming: mpsc::Receiver<MessageOf<R>>) -> Option<R::Output>
+where
+    R: Round,
+    R::Error: std::fmt::Display,
+{
+    while let Some(msg) = incoming.recv().await {
+        match round.receive(&mut OsRng, msg) {
+            Ok(Some(output)) => return Some(output),
+            Ok(None) => {}
+            Err(err) => eprintln!("message rejected: {err}"),
+        }
+    }
+    None
+}
+
+const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-example-dvrf");
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Group key shared among 3 parties with threshold 2, normally generated via DKG
+    let f = Polynomial::<SecretScalar<Secp256k1>>::sample(&mut OsRng, 1);
+    let group_key = f.commit(&Point::generator().to_point());
+    let new_round = {
+        let group_key = group_key.clone();
+        move || {
+            let transcript = Transcript::<sha2::Sha256>::new(b"example-dvrf");
+            dvrf::Round::new(TAG, b"lottery #1", transcript, group_key.clone())
+        }
+    };
+
+    let (outgoing, incoming): (Vec<_>, Vec<_>) = (0..3).map(|_| mpsc::channel(3)).unzip();
+    let mut parties = Vec::new();
+    for (i, incoming) in (0u16..).map(PartyIndex).zip(incoming) {
+        let round = new_round()?;
+        let key_share = f.eval_ct(&i.share_index().to_scalar());
+
+        // Broadcast partial evaluation to everyone, including the party itself
+        let partial = round.partial_evaluation(&mut OsRng, i, &key_share);
+        for party in &outgoing {
+            party.send(partial.clone()).await?;
+        }
+        parties.push(tokio::spawn(drive(round, incoming)));
+    }
+
+    for (i, party) in parties.into_iter().enumerate() {
+        let output = party.await?.ok_or("round didn't complete")?;
+        new_round()?.verify(&mut OsRng, &output)?;
+        println!("party {i} obtained VRF output {:?}", output.output);
+    }
+    Ok(())
+}
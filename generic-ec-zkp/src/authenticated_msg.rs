@@ -0,0 +1,367 @@
+//! Authenticated protocol messages
+//!
+//! Protocols in this crate are [sans-IO](crate::round): they don't care how messages are
+//! delivered. When messages travel over a raw transport, recipients need to know who sent the
+//! message and that it isn't replayed. [`AuthenticatedMsg`] wraps a serialized message along
+//! with index of the sender and sequence number, all signed with the sender's key.
+//!
+//! * [`MsgSigner`] holds party's secret key and signs outgoing messages, assigning them
+//!   consecutive sequence numbers
+//! * [`MsgVerifier`] knows public keys of all parties, verifies incoming messages and rejects
+//!   the ones that were already seen, i.e. whose sequence number isn't greater than sequence
+//!   number of the last accepted message from the same sender
+//!
+//! Signature covers the curve name, the [transcript](crate::transcript) (which should contain
+//! session identifier, so messages can't be replayed across sessions), sender index, sequence
+//! number, and the message itself.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{authenticated_msg::{MsgSigner, MsgVerifier}, party_index::PartyIndex};
+//! use generic_ec_zkp::transcript::Transcript;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let secret_keys = [(); 3].map(|_| SecretScalar::<Secp256k1>::random(&mut rng));
+//! let public_keys = secret_keys.iter().map(|sk| Point::generator() * sk).collect();
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-messages");
+//! transcript.append_message(b"session_id", b"session #1");
+//!
+//! let mut signer = MsgSigner::new(PartyIndex(1), secret_keys[1].clone());
+//! let msg = signer.sign(&mut rng, &transcript, b"round 1 message");
+//!
+//! let mut verifier = MsgVerifier::new(public_keys);
+//! let payload = verifier.verify(&transcript, msg.clone())?;
+//! assert_eq!(payload, b"round 1 message");
+//!
+//! // The same message can't be accepted twice
+//! assert!(verifier.verify(&transcript, msg).is_err());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Message $m$ sent by party $i$ with sequence number $\ell$ is signed with Schnorr signature
+//! $(R, s)$ under party's key $X_i = x_i \cdot G$: $R = r \cdot G$, $s = r + c x_i$,
+//! where $c = H(\text{transcript}, \text{curve}, i, \ell, m, R, X_i)$.
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{party_index::PartyIndex, transcript::Transcript};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Message signed by its sender
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct AuthenticatedMsg<E: Curve, T> {
+    /// Index of the sender
+    pub sender: PartyIndex,
+    /// Sequence number of the message
+    pub seq: u64,
+    /// Serialized message
+    pub payload: T,
+    /// Schnorr signature $(R, s)$
+    pub signature: (Point<E>, Scalar<E>),
+}
+
+impl<E: Curve, T: AsRef<[u8]>> AuthenticatedMsg<E, T> {
+    /// Verifies signature of the message under sender's `public_key`
+    ///
+    /// Doesn't check for replays, use [`MsgVerifier`] for that.
+    pub fn verify_signature<D: Digest + Clone>(
+        &self,
+        transcript: &Transcript<D>,
+        public_key: &Point<E>,
+    ) -> Result<(), InvalidSignature> {
+        let (r, s) = &self.signature;
+        let c = challenge(
+            transcript,
+            self.sender,
+            self.seq,
+            self.payload.as_ref(),
+            r,
+            public_key,
+        );
+        if Point::generator() * s == r + public_key * c {
+            Ok(())
+        } else {
+            Err(InvalidSignature)
+        }
+    }
+}
+
+/// Signs outgoing messages of the local party
+pub struct MsgSigner<E: Curve> {
+    index: PartyIndex,
+    secret_key: SecretScalar<E>,
+    next_seq: u64,
+}
+
+impl<E: Curve> MsgSigner<E> {
+    /// Constructs a signer of party `index` holding `secret_key`
+    pub fn new(index: PartyIndex, secret_key: SecretScalar<E>) -> Self {
+        Self {
+            index,
+            secret_key,
+            next_seq: 0,
+        }
+    }
+
+    /// Returns public key of the party $X_i$
+    pub fn public_key(&self) -> Point<E> {
+        Point::generator() * &self.secret_key
+    }
+
+    /// Signs the message
+    ///
+    /// Every signed message gets the next sequence number. Recipients must use the same
+    /// transcript for verification.
+    pub fn sign<D: Digest + Clone, R: RngCore + CryptoRng, T: AsRef<[u8]>>(
+        &mut self,
+        rng: &mut R,
+        transcript: &Transcript<D>,
+        payload: T,
+    ) -> AuthenticatedMsg<E, T> {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        let nonce = SecretScalar::<E>::random(rng);
+        let r = Point::generator() * &nonce;
+        let c = challenge(
+            transcript,
+            self.index,
+            seq,
+            payload.as_ref(),
+            &r,
+            &self.public_key(),
+        );
+        let s = &nonce + c * &self.secret_key;
+        AuthenticatedMsg {
+            sender: self.index,
+            seq,
+            payload,
+            signature: (r, s),
+        }
+    }
+}
+
+/// Verifies incoming messages and rejects replays
+pub struct MsgVerifier<E: Curve> {
+    public_keys: Vec<Point<E>>,
+    next_seq: Vec<u64>,
+}
+
+impl<E: Curve> MsgVerifier<E> {
+    /// Constructs a verifier
+    ///
+    /// Party $i$ has public key `public_keys[i]`.
+    pub fn new(public_keys: Vec<Point<E>>) -> Self {
+        let next_seq = alloc::vec![0; public_keys.len()];
+        Self {
+            public_keys,
+            next_seq,
+        }
+    }
+
+    /// Verifies the message, returns its payload
+    ///
+    /// Message is rejected if its sender is unknown, signature is invalid, or a message from
+    /// the same sender with the same or greater sequence number was already accepted. Rejected
+    /// messages don't affect the state of the verifier.
+    pub fn verify<D: Digest + Clone, T: AsRef<[u8]>>(
+        &mut self,
+        transcript: &Transcript<D>,
+        msg: AuthenticatedMsg<E, T>,
+    ) -> Result<T, RejectedMsg> {
+        let sender = msg.sender;
+        let (Some(public_key), Some(next_seq)) = (
+            self.public_keys.get(usize::from(sender)),
+            self.next_seq.get_mut(usize::from(sender)),
+        ) else {
+            return Err(RejectedMsg::UnknownSender { sender });
+        };
+        if msg.seq < *next_seq {
+            return Err(RejectedMsg::Replayed {
+                sender,
+                seq: msg.seq,
+            });
+        }
+        msg.verify_signature(transcript, public_key)
+            .map_err(|_| RejectedMsg::InvalidSignature { sender })?;
+        *next_seq = msg.seq.saturating_add(1);
+        Ok(msg.payload)
+    }
+}
+
+/// Computes $H(\text{transcript}, \text{curve}, i, \ell, m, R, X_i)$
+fn challenge<E: Curve, D: Digest + Clone>(
+    transcript: &Transcript<D>,
+    sender: PartyIndex,
+    seq: u64,
+    payload: &[u8],
+    r: &Point<E>,
+    public_key: &Point<E>,
+) -> Scalar<E> {
+    let mut transcript = transcript.clone();
+    transcript.append_message(b"authenticated_msg/curve", E::CURVE_NAME.as_bytes());
+    transcript.append_u64(b"authenticated_msg/sender", sender.0.into());
+    transcript.append_u64(b"authenticated_msg/seq", seq);
+    transcript.append_message(b"authenticated_msg/payload", payload);
+    transcript.append_point(b"authenticated_msg/R", r);
+    transcript.append_point(b"authenticated_msg/X", public_key);
+    transcript.challenge_scalar(b"authenticated_msg/c")
+}
+
+/// Signature is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidSignature;
+
+impl core::fmt::Display for InvalidSignature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid message signature")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSignature {}
+
+/// Message is rejected by [`MsgVerifier`]
+#[derive(Debug, Clone, Copy)]
+pub enum RejectedMsg {
+    /// Sender index is out of range
+    UnknownSender {
+        /// Index of the sender
+        sender: PartyIndex,
+    },
+    /// Message with the same or greater sequence number was already accepted
+    Replayed {
+        /// Index of the sender
+        sender: PartyIndex,
+        /// Sequence number of the rejected message
+        seq: u64,
+    },
+    /// Signature is invalid
+    InvalidSignature {
+        /// Index of the sender
+        sender: PartyIndex,
+    },
+}
+
+impl core::fmt::Display for RejectedMsg {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownSender { sender } => write!(f, "unknown sender {sender}"),
+            Self::Replayed { sender, seq } => {
+                write!(f, "message {seq} from party {sender} is replayed")
+            }
+            Self::InvalidSignature { sender } => {
+                write!(f, "invalid signature of message from party {sender}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RejectedMsg {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{MsgSigner, MsgVerifier, RejectedMsg};
+    use crate::{party_index::PartyIndex, transcript::Transcript};
+
+    fn transcript(session: &[u8]) -> Transcript<sha2::Sha256> {
+        let mut transcript = Transcript::new(b"test");
+        transcript.append_message(b"session", session);
+        transcript
+    }
+
+    #[test]
+    fn authentication<E: Curve>() {
+        let mut rng = DevRng::new();
+        let secret_keys: Vec<_> = (0..3)
+            .map(|_| SecretScalar::<E>::random(&mut rng))
+            .collect();
+        let public_keys: Vec<_> = secret_keys
+            .iter()
+            .map(|sk| Point::generator() * sk)
+            .collect();
+        let t = transcript(b"session");
+
+        let mut signer = MsgSigner::new(PartyIndex(2), secret_keys[2].clone());
+        let msgs: Vec<_> = (0..3).map(|i| signer.sign(&mut rng, &t, [i; 4])).collect();
+        assert_eq!(msgs[2].seq, 2);
+
+        let mut verifier = MsgVerifier::new(public_keys.clone());
+        assert_eq!(verifier.verify(&t, msgs[0].clone()).unwrap(), [0; 4]);
+
+        // Replays are rejected, skipped sequence numbers are not
+        assert!(matches!(
+            verifier.verify(&t, msgs[0].clone()),
+            Err(RejectedMsg::Replayed {
+                sender: PartyIndex(2),
+                seq: 0
+            })
+        ));
+        assert_eq!(verifier.verify(&t, msgs[2].clone()).unwrap(), [2; 4]);
+        assert!(matches!(
+            verifier.verify(&t, msgs[1].clone()),
+            Err(RejectedMsg::Replayed {
+                sender: PartyIndex(2),
+                seq: 1
+            })
+        ));
+
+        // Tampered message, message from another session, message claiming another sender,
+        // or from unknown sender are rejected
+        let mut verifier = MsgVerifier::new(public_keys.clone());
+        let mut tampered = msgs[0].clone();
+        tampered.payload[0] = 1;
+        assert!(matches!(
+            verifier.verify(&t, tampered),
+            Err(RejectedMsg::InvalidSignature {
+                sender: PartyIndex(2)
+            })
+        ));
+        assert!(verifier
+            .verify(&transcript(b"other session"), msgs[0].clone())
+            .is_err());
+        let mut impersonated = msgs[0].clone();
+        impersonated.sender = PartyIndex(1);
+        assert!(verifier.verify(&t, impersonated).is_err());
+        let mut unknown = msgs[0].clone();
+        unknown.sender = PartyIndex(3);
+        assert!(matches!(
+            verifier.verify(&t, unknown),
+            Err(RejectedMsg::UnknownSender {
+                sender: PartyIndex(3)
+            })
+        ));
+
+        // Rejected messages don't affect verifier state
+        verifier.verify(&t, msgs[0].clone()).unwrap();
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -0,0 +1,473 @@
+//! Threshold randomness beacon
+//!
+//! Primitives for a drand-style beacon: $n$ parties hold shares of the group secret key, and in
+//! each round any $t$ of them jointly produce an unpredictable value that anyone can verify
+//! against the group public key. Nobody, including any $t - 1$ colluding parties, can predict
+//! the value before $t$ parties contributed to the round.
+//!
+//! Curves in this crate have no pairings, so BLS threshold signatures can't be used. Instead,
+//! each party publishes its partial evaluation $\sigma_i = x_i \cdot H$ on the round point $H$
+//! along with a proof of equality of discrete logs
+//! ([DLEQ](crate::proof_bundle::LinearRelation::dleq)) with its public key share. Beacon
+//! output $\sigma = x \cdot H$ is interpolated from $t$ verified partial evaluations and is
+//! shipped together with them, so it can be verified by re-checking the proofs.
+//!
+//! Group key is a Feldman commitment to the polynomial $f$ sharing the secret key:
+//! [`Polynomial<Point<E>>`](Polynomial) with coefficients $f_j \cdot G$, e.g. obtained via
+//! [`Polynomial::commit`]. Party $i \in [0, n)$ holds key share $x_i = f(i + 1)$, see
+//! [`party_index`](crate::party_index).
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{beacon, party_index::PartyIndex, polynomial::Polynomial, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-beacon-v0.1.0");
+//! let transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-beacon");
+//!
+//! // Group key shared among 3 parties with threshold 2, normally generated via DKG
+//! let f = Polynomial::<generic_ec::SecretScalar<Secp256k1>>::sample(&mut rng, 1);
+//! let group_key = f.commit(&Point::generator().to_point());
+//! let key_shares: Vec<_> = (1..=3u16).map(|i| f.eval_ct(&Scalar::from(i))).collect();
+//!
+//! // Round point is derived from the round number and the previous beacon output
+//! let round_point = beacon::round_point::<Secp256k1>(TAG, 1, b"genesis")?;
+//!
+//! // Parties 0 and 2 publish their partial evaluations
+//! let partials = [PartyIndex(0), PartyIndex(2)].map(|i| {
+//!     let key_share = &key_shares[usize::from(i)];
+//!     beacon::PartialEvaluation::new(&mut rng, transcript.clone(), i, key_share, &round_point)
+//! });
+//!
+//! // Aggregator verifies partial evaluations and aggregates them
+//! let verified = partials
+//!     .iter()
+//!     .map(|p| p.verify(&mut transcript.clone(), &mut rng, &group_key, &round_point))
+//!     .collect::<Result<Vec<_>, _>>()?;
+//! let beacon = beacon::aggregate(&group_key, verified)?;
+//!
+//! // Anyone can verify the beacon against the group key
+//! beacon.verify(&transcript, &mut rng, &group_key, &round_point)?;
+//! let randomness = beacon.randomness::<sha2::Sha256>();
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! * Round point: $H = \mathcal{H}(\text{round} \| \text{prev})$ via hash to curve
+//! * Partial evaluation of party $i$: $\sigma_i = x_i \cdot H$ and a proof that
+//!   $\log_G X_i = \log_H \sigma_i$, where $X_i = \sum_j (i + 1)^j \cdot F_j$
+//! * Aggregation: $\sigma = \sum_{i \in S} \lambda_i \cdot \sigma_i = x \cdot H$ for
+//!   $|S| \ge t$ verified partial evaluations
+//! * Randomness: $\text{Hash}(\sigma)$
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{
+    errors::HashError,
+    hash_to_curve::{FromHash, Tag},
+    Curve, NonZero, Point, Scalar, SecretScalar,
+};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    party_index::PartyIndex,
+    polynomial::{lagrange_coefficient, Polynomial},
+    proof_bundle::{BundleProver, InvalidProof, LinearRelation, ProofBundle},
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Derives round point $H$ from the round number and output of the previous round
+pub fn round_point<E: Curve>(
+    tag: Tag,
+    round: u64,
+    previous_output: &[u8],
+) -> Result<Point<E>, HashError>
+where
+    Point<E>: FromHash,
+{
+    Point::hash_concat(tag, &[&round.to_be_bytes(), previous_output])
+}
+
+/// Partial evaluation $\sigma_i$ of party $i$ along with the DLEQ proof
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct PartialEvaluation<E: Curve> {
+    /// Index of the party
+    pub index: PartyIndex,
+    /// Partial evaluation $\sigma_i = x_i \cdot H$
+    pub sigma: Point<E>,
+    /// Proof that $\log_G X_i = \log_H \sigma_i$
+    pub proof: ProofBundle<E>,
+}
+
+/// Partial evaluation which proof was verified
+///
+/// Can only be obtained via [`PartialEvaluation::verify`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedPartialEvaluation<E: Curve>(PartialEvaluation<E>);
+
+/// Beacon output $\sigma$ along with partial evaluations it was aggregated from
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Beacon<E: Curve> {
+    /// Beacon output $\sigma = x \cdot H$
+    pub output: Point<E>,
+    /// Partial evaluations
+    pub partials: Vec<PartialEvaluation<E>>,
+}
+
+impl<E: Curve> PartialEvaluation<E> {
+    /// Computes partial evaluation of party `index` and proves its correctness
+    ///
+    /// Index of the party is appended to the `transcript`
+    pub fn new<D: Digest + Clone, R: RngCore + CryptoRng>(
+        rng: &mut R,
+        mut transcript: Transcript<D>,
+        index: PartyIndex,
+        key_share: &SecretScalar<E>,
+        round_point: &Point<E>,
+    ) -> Self {
+        transcript.append_u64(b"party_index", index.0.into());
+        let sigma = round_point * key_share;
+
+        let mut prover = BundleProver::new(transcript);
+        prover.add_satisfied(
+            rng,
+            relation(Point::generator() * key_share, round_point, sigma),
+            core::slice::from_ref(key_share),
+        );
+        Self {
+            index,
+            sigma,
+            proof: prover.prove(),
+        }
+    }
+
+    /// Verifies partial evaluation against the group key
+    ///
+    /// Transcript must be in the same state as it was passed to [`PartialEvaluation::new`].
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        group_key: &Polynomial<Point<E>>,
+        round_point: &Point<E>,
+    ) -> Result<VerifiedPartialEvaluation<E>, InvalidProof> {
+        transcript.append_u64(b"party_index", self.index.0.into());
+        let public_share = group_key.value(&self.index.share_index().to_scalar());
+        let relation = relation(public_share, round_point, self.sigma);
+        self.proof
+            .verify(transcript, rng, core::slice::from_ref(&relation))?;
+        Ok(VerifiedPartialEvaluation(self.clone()))
+    }
+}
+
+impl<E: Curve> VerifiedPartialEvaluation<E> {
+    /// Index of the party
+    pub fn index(&self) -> PartyIndex {
+        self.0.index
+    }
+
+    /// Partial evaluation $\sigma_i$
+    pub fn sigma(&self) -> Point<E> {
+        self.0.sigma
+    }
+
+    /// Returns partial evaluation along with the proof
+    pub fn into_inner(self) -> PartialEvaluation<E> {
+        self.0
+    }
+}
+
+/// Aggregates verified partial evaluations into the beacon output
+///
+/// Returns error if there are less partial evaluations than threshold (amount of coefficients
+/// in the `group_key`), or if indexes of the parties are not distinct.
+pub fn aggregate<E: Curve>(
+    group_key: &Polynomial<Point<E>>,
+    partials: Vec<VerifiedPartialEvaluation<E>>,
+) -> Result<Beacon<E>, AggregationError> {
+    let partials: Vec<_> = partials
+        .into_iter()
+        .map(VerifiedPartialEvaluation::into_inner)
+        .collect();
+    let output = interpolate(group_key, &partials)?;
+    Ok(Beacon { output, partials })
+}
+
+impl<E: Curve> Beacon<E> {
+    /// Verifies the beacon against the group key
+    ///
+    /// Checks that every partial evaluation is valid, and that the output is aggregated from
+    /// them. Transcript must be in the same state as it was passed to [`PartialEvaluation::new`].
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &Transcript<D>,
+        rng: &mut R,
+        group_key: &Polynomial<Point<E>>,
+        round_point: &Point<E>,
+    ) -> Result<(), InvalidBeacon> {
+        for partial in &self.partials {
+            partial
+                .verify(&mut transcript.clone(), rng, group_key, round_point)
+                .map_err(|_| InvalidBeacon)?;
+        }
+        let output = interpolate(group_key, &self.partials).map_err(|_| InvalidBeacon)?;
+        if output != self.output {
+            return Err(InvalidBeacon);
+        }
+        Ok(())
+    }
+
+    /// Derives beacon randomness by hashing the output
+    pub fn randomness<D: Digest>(&self) -> digest::Output<D> {
+        D::digest(self.output.to_bytes(true))
+    }
+}
+
+/// $\log_G X_i = \log_H \sigma_i$
+fn relation<E: Curve>(
+    public_share: Point<E>,
+    round_point: &Point<E>,
+    sigma: Point<E>,
+) -> LinearRelation<E> {
+    LinearRelation::dleq(
+        Point::generator().to_point(),
+        public_share,
+        *round_point,
+        sigma,
+    )
+}
+
+/// Interpolates $\sigma = \sum_i \lambda_i \cdot \sigma_i$
+fn interpolate<E: Curve>(
+    group_key: &Polynomial<Point<E>>,
+    partials: &[PartialEvaluation<E>],
+) -> Result<Point<E>, AggregationError> {
+    if partials.len() < group_key.coefs().len() {
+        return Err(AggregationError::NotEnoughPartials);
+    }
+    let xs: Vec<NonZero<Scalar<E>>> = partials
+        .iter()
+        .map(|p| p.index.share_index().to_scalar())
+        .collect();
+    (0..xs.len())
+        .map(|j| lagrange_coefficient(Scalar::zero(), j, &xs))
+        .zip(partials)
+        .map(|(lambda_j, p)| {
+            lambda_j
+                .map(|lambda_j| p.sigma * lambda_j)
+                .ok_or(AggregationError::DuplicateIndexes)
+        })
+        .sum()
+}
+
+/// Partial evaluations can't be aggregated
+#[derive(Debug, Clone, Copy)]
+pub enum AggregationError {
+    /// Less partial evaluations than threshold
+    NotEnoughPartials,
+    /// Several partial evaluations from the same party
+    DuplicateIndexes,
+}
+
+impl core::fmt::Display for AggregationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotEnoughPartials => f.write_str("not enough partial evaluations"),
+            Self::DuplicateIndexes => {
+                f.write_str("several partial evaluations from the same party")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AggregationError {}
+
+/// Beacon is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidBeacon;
+
+impl core::fmt::Display for InvalidBeacon {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid beacon")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidBeacon {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{hash_to_curve::FromHash, Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{PartialEvaluation, VerifiedPartialEvaluation};
+    use crate::{party_index::PartyIndex, polynomial::Polynomial, transcript::Transcript};
+
+    const TAG: generic_ec::hash_to_curve::Tag = generic_ec::hash_to_curve::Tag::new_unwrap(b"test");
+
+    struct Setup<E: Curve> {
+        secret_key: SecretScalar<E>,
+        group_key: Polynomial<Point<E>>,
+        key_shares: Vec<SecretScalar<E>>,
+        transcript: Transcript<sha2::Sha256>,
+    }
+
+    fn setup<E: Curve>(rng: &mut DevRng, t: usize, n: u16) -> Setup<E> {
+        let secret_key = SecretScalar::random(rng);
+        let f = Polynomial::sample_with_const_term(rng, t - 1, secret_key.clone());
+        Setup {
+            secret_key,
+            group_key: f.commit(&Point::generator().to_point()),
+            key_shares: (1..=n).map(|i| f.eval_ct(&Scalar::from(i))).collect(),
+            transcript: Transcript::new(b"test"),
+        }
+    }
+
+    fn partial<E: Curve>(
+        rng: &mut DevRng,
+        setup: &Setup<E>,
+        i: u16,
+        round_point: &Point<E>,
+    ) -> VerifiedPartialEvaluation<E> {
+        PartialEvaluation::new(
+            rng,
+            setup.transcript.clone(),
+            PartyIndex(i),
+            &setup.key_shares[usize::from(i)],
+            round_point,
+        )
+        .verify(
+            &mut setup.transcript.clone(),
+            rng,
+            &setup.group_key,
+            round_point,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn beacon_round<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let setup = setup::<E>(&mut rng, 3, 5);
+        let round_point = super::round_point::<E>(TAG, 1, b"genesis").unwrap();
+
+        let beacon = super::aggregate(
+            &setup.group_key,
+            [4, 0, 2]
+                .into_iter()
+                .map(|i| partial(&mut rng, &setup, i, &round_point))
+                .collect(),
+        )
+        .unwrap();
+        assert_eq!(beacon.output, round_point * &setup.secret_key);
+        beacon
+            .verify(&setup.transcript, &mut rng, &setup.group_key, &round_point)
+            .unwrap();
+
+        // Any other set of parties produces the same output
+        let other = super::aggregate(
+            &setup.group_key,
+            [1, 3, 2, 0]
+                .into_iter()
+                .map(|i| partial(&mut rng, &setup, i, &round_point))
+                .collect(),
+        )
+        .unwrap();
+        assert_eq!(beacon.output, other.output);
+        assert_eq!(
+            beacon.randomness::<sha2::Sha256>(),
+            other.randomness::<sha2::Sha256>()
+        );
+
+        // Beacon is bound to the round
+        let next_round_point =
+            super::round_point::<E>(TAG, 2, &beacon.randomness::<sha2::Sha256>()).unwrap();
+        assert!(beacon
+            .verify(
+                &setup.transcript,
+                &mut rng,
+                &setup.group_key,
+                &next_round_point
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn invalid_partials_are_rejected<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let setup = setup::<E>(&mut rng, 2, 3);
+        let round_point = super::round_point::<E>(TAG, 1, b"genesis").unwrap();
+
+        // Partial evaluation claimed by another party
+        let mut p = PartialEvaluation::new(
+            &mut rng,
+            setup.transcript.clone(),
+            PartyIndex(0),
+            &setup.key_shares[0],
+            &round_point,
+        );
+        p.index = PartyIndex(1);
+        assert!(p
+            .verify(
+                &mut setup.transcript.clone(),
+                &mut rng,
+                &setup.group_key,
+                &round_point
+            )
+            .is_err());
+
+        // Not enough partial evaluations
+        assert!(super::aggregate(
+            &setup.group_key,
+            [partial(&mut rng, &setup, 0, &round_point)].into()
+        )
+        .is_err());
+
+        // Duplicate partial evaluations
+        assert!(super::aggregate(
+            &setup.group_key,
+            [0, 0]
+                .into_iter()
+                .map(|i| partial(&mut rng, &setup, i, &round_point))
+                .collect()
+        )
+        .is_err());
+
+        // Tampered output
+        let mut beacon = super::aggregate(
+            &setup.group_key,
+            [0, 1]
+                .into_iter()
+                .map(|i| partial(&mut rng, &setup, i, &round_point))
+                .collect(),
+        )
+        .unwrap();
+        beacon.output += Point::generator();
+        assert!(beacon
+            .verify(&setup.transcript, &mut rng, &setup.group_key, &round_point)
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
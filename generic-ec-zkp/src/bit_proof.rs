@@ -0,0 +1,390 @@
+//! Proof that committed value is a bit $\Pi^\text{bit}$
+//!
+//! Proves that [Pedersen commitment](crate::pedersen) $C = b \cdot G + r \cdot H$ commits to
+//! $b \in \\{0, 1\\}$ without revealing $b$. It's an OR-composition of two Schnorr proofs (see
+//! [CDS94]): prover knows discrete log of either $C$ or $C - G$ relative to $H$. The proof is made
+//! non-interactive via Fiat-Shamir [transcript](crate::transcript).
+//!
+//! [`prove_small_range`] proves that committed value is in range $[0, 2^n)$ by committing to every
+//! bit of the value separately. Proof size is linear in $n$, so it's only suitable for tiny ranges.
+//! For larger ranges, consider [Bulletproofs](crate::range_proof).
+//!
+//! [CDS94]: https://link.springer.com/chapter/10.1007/3-540-48658-5_19
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{bit_proof, pedersen, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-bit-proof-v0.1.0");
+//! let params = pedersen::Params::<Secp256k1>::derive(TAG)?;
+//!
+//! let blinding = Scalar::random(&mut rng);
+//! let commitment = params.commit(&Scalar::one(), &blinding);
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! let proof = bit_proof::prove(&mut transcript, &params, &mut rng, true, &blinding);
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! proof.verify(&mut transcript, &params, &commitment)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Let $Y_0 = C$, $Y_1 = C - G$. Prover knows $r$ such that $Y_b = r \cdot H$.
+//!
+//! * Prove
+//!   1. Prover simulates the proof for $Y_{1-b}$: samples $e_{1-b}, z_{1-b} \gets \Z_q$, sets
+//!      $A_{1-b} = z_{1-b} \cdot H - e_{1-b} \cdot Y_{1-b}$
+//!   2. Prover samples $k \gets \Z_q$, sets $A_b = k \cdot H$
+//!   3. Prover obtains challenge $e$ from the transcript, sets $e_b = e - e_{1-b}$,
+//!      $z_b = k + e_b r$
+//!   4. Proof is $(A_0, A_1, e_0, z_0, z_1)$
+//! * Verification \
+//!   Verifier obtains challenge $e$, sets $e_1 = e - e_0$, and checks that
+//!   $z_0 \cdot H \\? A_0 + e_0 \cdot Y_0$ and $z_1 \cdot H \\? A_1 + e_1 \cdot Y_1$
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar};
+use rand_core::{CryptoRng, RngCore};
+use subtle::{Choice, ConditionallySelectable};
+
+use crate::{pedersen, transcript::Transcript};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Proof that committed value is a bit
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+#[allow(non_snake_case)]
+pub struct BitProof<E: Curve> {
+    /// Commitment $A_0$
+    pub A0: Point<E>,
+    /// Commitment $A_1$
+    pub A1: Point<E>,
+    /// Challenge $e_0$, challenge $e_1$ is derived from it
+    pub e0: Scalar<E>,
+    /// Response $z_0$
+    pub z0: Scalar<E>,
+    /// Response $z_1$
+    pub z1: Scalar<E>,
+}
+
+/// Proves that commitment $C = \text{bit} \cdot G + \text{blinding} \cdot H$ commits to a bit
+///
+/// Commitment is appended to the transcript before deriving the challenge. Proof is generated in
+/// constant time: commitment is computed via constant-time [`pedersen::Params::commit`], and
+/// prover doesn't branch on the `bit`, choice between real and simulated proofs is done in
+/// constant time.
+#[allow(non_snake_case)]
+pub fn prove<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    transcript: &mut Transcript<D>,
+    params: &pedersen::Params<E>,
+    rng: &mut R,
+    bit: bool,
+    blinding: &Scalar<E>,
+) -> BitProof<E> {
+    let choice = Choice::from(u8::from(bit));
+    let C = params.commit(&Scalar::from(u8::from(bit)), blinding);
+
+    // Simulated proof for $Y_{1-b}$
+    let e_sim = Scalar::random(rng);
+    let z_sim = Scalar::random(rng);
+    let Y_sim = Point::conditional_select(&(C - params.g), &C, choice);
+    let A_sim = params.h * z_sim - Y_sim * e_sim;
+
+    // Real proof for $Y_b$
+    let k = Scalar::random(rng);
+    let A_real = params.h * k;
+
+    let A0 = Point::conditional_select(&A_real, &A_sim, choice);
+    let A1 = Point::conditional_select(&A_sim, &A_real, choice);
+    let e = challenge(transcript, &C, &A0, &A1);
+
+    let e_real = e - e_sim;
+    let z_real = k + e_real * blinding;
+
+    BitProof {
+        A0,
+        A1,
+        e0: Scalar::conditional_select(&e_real, &e_sim, choice),
+        z0: Scalar::conditional_select(&z_real, &z_sim, choice),
+        z1: Scalar::conditional_select(&z_sim, &z_real, choice),
+    }
+}
+
+impl<E: Curve> BitProof<E> {
+    /// Verifies that `commitment` commits to a bit
+    ///
+    /// Transcript must be in the same state as it was at the moment of [proving](prove).
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        params: &pedersen::Params<E>,
+        commitment: &Point<E>,
+    ) -> Result<(), InvalidProof> {
+        let e = challenge(transcript, commitment, &self.A0, &self.A1);
+        let e1 = e - self.e0;
+
+        let check0 = params.h * self.z0 == self.A0 + commitment * self.e0;
+        let check1 = params.h * self.z1 == self.A1 + (commitment - params.g) * e1;
+        if check0 && check1 {
+            Ok(())
+        } else {
+            Err(InvalidProof)
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn challenge<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    C: &Point<E>,
+    A0: &Point<E>,
+    A1: &Point<E>,
+) -> Scalar<E> {
+    transcript.append_point(b"bit_proof/C", C);
+    transcript.append_point(b"bit_proof/A0", A0);
+    transcript.append_point(b"bit_proof/A1", A1);
+    transcript.challenge_scalar(b"bit_proof/e")
+}
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use requires_alloc::*;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+mod requires_alloc {
+    use alloc::vec::Vec;
+    use core::iter;
+
+    use digest::Digest;
+    use generic_ec::{Curve, Point, Scalar};
+    use rand_core::{CryptoRng, RngCore};
+
+    #[cfg(feature = "serde")]
+    use serde::{Deserialize, Serialize};
+
+    use super::{BitProof, InvalidInput, InvalidProof};
+    use crate::{pedersen, transcript::Transcript};
+
+    /// Proof that committed value is in range $[0, 2^n)$
+    ///
+    /// Consists of commitments to every bit of the value along with [bit proofs](BitProof)
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+    #[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+    pub struct SmallRangeProof<E: Curve> {
+        /// `bits[i]` is a commitment to $i$-th least significant bit and a proof that it's a bit
+        pub bits: Vec<(Point<E>, BitProof<E>)>,
+    }
+
+    /// Proves that commitment $V = \text{value} \cdot G + \text{blinding} \cdot H$ commits to value
+    /// in range $[0, 2^\text{bits})$
+    ///
+    /// Returns the proof and commitment $V$.
+    ///
+    /// Returns error if `value` is out of range or `bits` is zero or greater than $64$.
+    pub fn prove_small_range<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+        transcript: &mut Transcript<D>,
+        params: &pedersen::Params<E>,
+        rng: &mut R,
+        value: u64,
+        blinding: &Scalar<E>,
+        bits: usize,
+    ) -> Result<(SmallRangeProof<E>, Point<E>), InvalidInput> {
+        if bits == 0 || bits > 64 || (bits < 64 && value >> bits != 0) {
+            return Err(InvalidInput);
+        }
+
+        // Blindings of bits are chosen such as $\sum_i 2^i r_i = \text{blinding}$, so verifier can
+        // check that bits sum up to the value
+        let mut bit_blindings: Vec<Scalar<E>> = iter::once(Scalar::zero())
+            .chain(iter::repeat_with(|| Scalar::random(rng)).take(bits - 1))
+            .collect();
+        bit_blindings[0] =
+            blinding - weighted_sum(&bit_blindings, |r_i, two_i| r_i * two_i, Scalar::zero());
+
+        let commitment = params.commit(&Scalar::from(value), blinding);
+        transcript.append_u64(b"bit_proof/bits", bits as u64);
+        transcript.append_point(b"bit_proof/V", &commitment);
+
+        let proofs = bit_blindings
+            .iter()
+            .enumerate()
+            .map(|(i, r_i)| {
+                let bit = (value >> i) & 1 == 1;
+                let c_i = params.commit(&Scalar::from(u8::from(bit)), r_i);
+                (c_i, super::prove(transcript, params, rng, bit, r_i))
+            })
+            .collect();
+
+        Ok((SmallRangeProof { bits: proofs }, commitment))
+    }
+
+    impl<E: Curve> SmallRangeProof<E> {
+        /// Verifies that `commitment` commits to value in range $[0, 2^\text{bits})$
+        ///
+        /// Transcript must be in the same state as it was at the moment of [proving](prove_small_range).
+        pub fn verify<D: Digest + Clone>(
+            &self,
+            transcript: &mut Transcript<D>,
+            params: &pedersen::Params<E>,
+            commitment: &Point<E>,
+            bits: usize,
+        ) -> Result<(), InvalidProof> {
+            if bits == 0 || bits > 64 || self.bits.len() != bits {
+                return Err(InvalidProof);
+            }
+            transcript.append_u64(b"bit_proof/bits", bits as u64);
+            transcript.append_point(b"bit_proof/V", commitment);
+
+            for (c_i, proof_i) in &self.bits {
+                proof_i.verify(transcript, params, c_i)?;
+            }
+
+            let commitments: Vec<_> = self.bits.iter().map(|(c_i, _)| *c_i).collect();
+            let sum = weighted_sum(&commitments, |c_i, two_i| c_i * two_i, Point::zero());
+            if sum == *commitment {
+                Ok(())
+            } else {
+                Err(InvalidProof)
+            }
+        }
+    }
+
+    /// Computes $\sum_i 2^i x_i$
+    fn weighted_sum<E: Curve, T: Copy + core::ops::Add<Output = T>>(
+        xs: &[T],
+        mul: impl Fn(&T, &Scalar<E>) -> T,
+        zero: T,
+    ) -> T {
+        let two = Scalar::<E>::from(2u8);
+        xs.iter()
+            .zip(iter::successors(Some(Scalar::one()), |acc| Some(acc * two)))
+            .fold(zero, |acc, (x_i, two_i)| acc + mul(x_i, &two_i))
+    }
+}
+
+/// Invalid input error
+///
+/// Returned when value is out of range or bit length is not supported
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid bit proof input")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid bit proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    use crate::{pedersen, transcript::Transcript};
+
+    const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-bit-proof-test");
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn bit_proof<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = pedersen::Params::<E>::derive(TAG).unwrap();
+
+        for bit in [false, true] {
+            let blinding = Scalar::random(&mut rng);
+            let commitment = params.commit(&Scalar::from(u8::from(bit)), &blinding);
+            let proof = super::prove(&mut transcript(), &params, &mut rng, bit, &blinding);
+            proof
+                .verify(&mut transcript(), &params, &commitment)
+                .unwrap();
+
+            // Proof doesn't verify for commitment to a different value
+            let wrong_commitment = commitment + params.g;
+            assert!(proof
+                .verify(&mut transcript(), &params, &wrong_commitment)
+                .is_err());
+        }
+
+        // Commitment to `2` can't be proven
+        let blinding = Scalar::random(&mut rng);
+        let commitment = params.commit(&Scalar::from(2u8), &blinding);
+        for bit in [false, true] {
+            let proof = super::prove(&mut transcript(), &params, &mut rng, bit, &blinding);
+            assert!(proof
+                .verify(&mut transcript(), &params, &commitment)
+                .is_err());
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn small_range<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = pedersen::Params::<E>::derive(TAG).unwrap();
+
+        for (value, bits) in [(0, 1), (1, 1), (5, 3), (200, 8)] {
+            let blinding = Scalar::random(&mut rng);
+            let (proof, commitment) = super::prove_small_range(
+                &mut transcript(),
+                &params,
+                &mut rng,
+                value,
+                &blinding,
+                bits,
+            )
+            .unwrap();
+            assert_eq!(commitment, params.commit(&Scalar::from(value), &blinding));
+            proof
+                .verify(&mut transcript(), &params, &commitment, bits)
+                .unwrap();
+            assert!(proof
+                .verify(&mut transcript(), &params, &(commitment + params.g), bits)
+                .is_err());
+        }
+
+        let blinding = Scalar::random(&mut rng);
+        assert!(
+            super::prove_small_range(&mut transcript(), &params, &mut rng, 8, &blinding, 3)
+                .is_err()
+        );
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
@@ -0,0 +1,496 @@
+//! Blind Schnorr signatures
+//!
+//! Three-move protocol that lets user obtain Schnorr signature on a message from signer, while
+//! signer learns neither the message nor the resulting signature. It's a building block for
+//! e-cash and anonymous tokens: the issuer signs a token without being able to link it to the
+//! issuance session later.
+//!
+//! ## Parallel sessions limit
+//! Blind Schnorr signatures are [known to be insecure][ROS] when signer runs many signing sessions
+//! concurrently: with $\ell > \log_2 q$ open sessions, user can obtain $\ell + 1$ signatures in
+//! polynomial time, and with fewer sessions, Wagner's algorithm still gives a sub-exponential
+//! attack. [`Signer`] mitigates the attack by limiting amount of sessions that can be open at the
+//! same time. The limit should be kept as small as possible, limit $1$ (sessions are strictly
+//! sequential) is the safest choice.
+//!
+//! [ROS]: https://eprint.iacr.org/2020/945
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::blind_schnorr;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let mut signer = blind_schnorr::Signer::<_, sha2::Sha256>::new(secret_key, 1);
+//! let public_key = signer.public_key();
+//!
+//! // 1. Signer starts a session and sends commitment to the user
+//! let (session, commitment) = signer.commit(&mut rng)?;
+//! // 2. User blinds the commitment and sends blinded challenge to the signer
+//! let message = b"token #1";
+//! let (user, challenge) = blind_schnorr::User::<_, sha2::Sha256>::blind(
+//!     &mut rng, &public_key, &commitment, message,
+//! );
+//! // 3. Signer responds, which closes the session
+//! let response = signer.respond(session, &challenge)?;
+//! // 4. User unblinds the response and obtains the signature
+//! let signature = user.unblind(&response)?;
+//!
+//! signature.verify::<sha2::Sha256>(&public_key, message)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Signer has secret key $x$ and public key $X = x \cdot G$. Signature on message $m$ is a pair
+//! $(R, s)$ such as $s \cdot G = R + H(R, X, m) \cdot X$.
+//!
+//! 1. Signer samples $k \gets \Z_q$, sends $R = k \cdot G$
+//! 2. User samples $\alpha, \beta \gets \Z_q$, computes $R' = R + \alpha \cdot G + \beta \cdot X$,
+//!    $c' = H(R', X, m)$, and sends $c = c' + \beta$
+//! 3. Signer sends $s = k + c x$
+//! 4. User checks that $s \cdot G \\? R + c \cdot X$, and outputs signature $(R', s + \alpha)$
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::transcript::Transcript;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Signer
+///
+/// Holds secret key and nonces of open sessions. Signatures issued by the signer are verified
+/// using hash function `D`.
+pub struct Signer<E: Curve, D> {
+    secret_key: SecretScalar<E>,
+    max_parallel_sessions: usize,
+    sessions: Vec<(SessionId, SecretScalar<E>)>,
+    next_session: u64,
+    _hash: PhantomData<fn() -> D>,
+}
+
+/// Identifies a signing session
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionId(pub u64);
+
+/// Signer commitment $R$
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Commitment<E: Curve>(pub Point<E>);
+
+/// Blinded challenge $c$ sent by user
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct BlindedChallenge<E: Curve>(pub Scalar<E>);
+
+/// Signer response $s$
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Response<E: Curve>(pub Scalar<E>);
+
+/// Schnorr signature $(R, s)$
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Signature<E: Curve> {
+    pub r: Point<E>,
+    pub s: Scalar<E>,
+}
+
+impl<E: Curve, D> Signer<E, D> {
+    /// Constructs a signer
+    ///
+    /// At most `max_parallel_sessions` can be open at the same time. See [module-level docs](self)
+    /// for why it's important.
+    pub fn new(secret_key: SecretScalar<E>, max_parallel_sessions: usize) -> Self {
+        Self {
+            secret_key,
+            max_parallel_sessions,
+            sessions: Vec::new(),
+            next_session: 0,
+            _hash: PhantomData,
+        }
+    }
+
+    /// Returns signer public key $X$
+    pub fn public_key(&self) -> Point<E> {
+        Point::generator() * &self.secret_key
+    }
+
+    /// Returns amount of currently open sessions
+    pub fn open_sessions(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Opens a new session, returns session ID and commitment that needs to be sent to the user
+    ///
+    /// Returns error if limit of parallel sessions is reached.
+    pub fn commit<R: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<(SessionId, Commitment<E>), SignerError> {
+        if self.sessions.len() >= self.max_parallel_sessions {
+            return Err(SignerError(Reason::TooManySessions));
+        }
+        let id = SessionId(self.next_session);
+        self.next_session = self
+            .next_session
+            .checked_add(1)
+            .ok_or(SignerError(Reason::SessionIdsExhausted))?;
+
+        let nonce = SecretScalar::random(rng);
+        let commitment = Commitment(Point::generator() * &nonce);
+        self.sessions.push((id, nonce));
+        Ok((id, commitment))
+    }
+
+    /// Responds to user challenge and closes the session
+    ///
+    /// Returns error if session doesn't exist or is already closed.
+    pub fn respond(
+        &mut self,
+        session: SessionId,
+        challenge: &BlindedChallenge<E>,
+    ) -> Result<Response<E>, SignerError> {
+        let nonce = self.take_session(session)?;
+        Ok(Response(&nonce + challenge.0 * &self.secret_key))
+    }
+
+    /// Closes the session without responding
+    ///
+    /// Returns error if session doesn't exist or is already closed.
+    pub fn abort(&mut self, session: SessionId) -> Result<(), SignerError> {
+        self.take_session(session).map(|_| ())
+    }
+
+    fn take_session(&mut self, session: SessionId) -> Result<SecretScalar<E>, SignerError> {
+        let index = self
+            .sessions
+            .iter()
+            .position(|(id, _)| *id == session)
+            .ok_or(SignerError(Reason::UnknownSession))?;
+        Ok(self.sessions.swap_remove(index).1)
+    }
+}
+
+/// User state between blinding the challenge and unblinding the response
+pub struct User<E: Curve, D> {
+    public_key: Point<E>,
+    commitment: Point<E>,
+    challenge: Scalar<E>,
+    alpha: Scalar<E>,
+    signature_r: Point<E>,
+    _hash: PhantomData<D>,
+}
+
+impl<E: Curve, D: Digest + Clone> User<E, D> {
+    /// Blinds signer commitment, returns user state and challenge that needs to be sent to signer
+    pub fn blind<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        public_key: &Point<E>,
+        commitment: &Commitment<E>,
+        message: &[u8],
+    ) -> (Self, BlindedChallenge<E>) {
+        let alpha = Scalar::random(rng);
+        let beta = Scalar::random(rng);
+        let signature_r = Scalar::multiscalar_mul([
+            (Scalar::one(), commitment.0),
+            (alpha, Point::generator().to_point()),
+            (beta, *public_key),
+        ]);
+        let challenge = challenge::<E, D>(&signature_r, public_key, message) + beta;
+        let user = Self {
+            public_key: *public_key,
+            commitment: commitment.0,
+            challenge,
+            alpha,
+            signature_r,
+            _hash: PhantomData,
+        };
+        (user, BlindedChallenge(challenge))
+    }
+
+    /// Unblinds signer response and outputs the signature
+    ///
+    /// Returns error if signer response is invalid.
+    pub fn unblind(self, response: &Response<E>) -> Result<Signature<E>, InvalidResponse> {
+        if Point::generator() * response.0 != self.commitment + self.public_key * self.challenge {
+            return Err(InvalidResponse);
+        }
+        Ok(Signature {
+            r: self.signature_r,
+            s: response.0 + self.alpha,
+        })
+    }
+}
+
+impl<E: Curve> Signature<E> {
+    /// Verifies the signature on `message` under `public_key`
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        public_key: &Point<E>,
+        message: &[u8],
+    ) -> Result<(), InvalidSignature> {
+        let c = challenge::<E, D>(&self.r, public_key, message);
+        if Point::generator() * self.s == self.r + public_key * c {
+            Ok(())
+        } else {
+            Err(InvalidSignature)
+        }
+    }
+}
+
+/// Public key that verifies signatures via [`signature::Verifier`] trait
+///
+/// Lets blind Schnorr signatures be used by code generic over RustCrypto signature traits.
+/// Signatures are verified using hash function `D`, see [`Signature::verify`].
+#[cfg(feature = "signature")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signature")))]
+pub struct VerifyingKey<E: Curve, D> {
+    public_key: Point<E>,
+    _hash: PhantomData<fn() -> D>,
+}
+
+#[cfg(feature = "signature")]
+impl<E: Curve, D> VerifyingKey<E, D> {
+    /// Constructs verifying key from signer public key $X$
+    pub fn new(public_key: Point<E>) -> Self {
+        Self {
+            public_key,
+            _hash: PhantomData,
+        }
+    }
+
+    /// Returns signer public key $X$
+    pub fn public_key(&self) -> &Point<E> {
+        &self.public_key
+    }
+}
+
+#[cfg(feature = "signature")]
+impl<E: Curve, D> Clone for VerifyingKey<E, D> {
+    fn clone(&self) -> Self {
+        Self::new(self.public_key)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl<E: Curve, D> core::fmt::Debug for VerifyingKey<E, D> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VerifyingKey")
+            .field("public_key", &self.public_key)
+            .finish()
+    }
+}
+
+#[cfg(feature = "signature")]
+impl<E: Curve, D: Digest + Clone> signature::Verifier<Signature<E>> for VerifyingKey<E, D> {
+    fn verify(&self, msg: &[u8], signature: &Signature<E>) -> Result<(), signature::Error> {
+        signature
+            .verify::<D>(&self.public_key, msg)
+            .map_err(|_| signature::Error::new())
+    }
+}
+
+/// Signing is interactive, so [`Signer`] doesn't implement [`signature::Signer`], but it exposes
+/// its public key
+#[cfg(feature = "signature")]
+impl<E: Curve, D: Digest + Clone> signature::Keypair for Signer<E, D> {
+    type VerifyingKey = VerifyingKey<E, D>;
+
+    fn verifying_key(&self) -> VerifyingKey<E, D> {
+        VerifyingKey::new(self.public_key())
+    }
+}
+
+/// Computes $H(R, X, m)$
+fn challenge<E: Curve, D: Digest + Clone>(
+    r: &Point<E>,
+    public_key: &Point<E>,
+    message: &[u8],
+) -> Scalar<E> {
+    let mut transcript = Transcript::<D>::new(b"generic-ec-zkp/blind_schnorr");
+    transcript.append_point(b"R", r);
+    transcript.append_point(b"X", public_key);
+    transcript.append_message(b"m", message);
+    transcript.challenge_scalar(b"c")
+}
+
+/// Signer error
+#[derive(Debug, Clone, Copy)]
+pub struct SignerError(Reason);
+
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    TooManySessions,
+    UnknownSession,
+    SessionIdsExhausted,
+}
+
+impl core::fmt::Display for SignerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Reason::TooManySessions => f.write_str("limit of parallel sessions is reached"),
+            Reason::UnknownSession => f.write_str("session doesn't exist or is already closed"),
+            Reason::SessionIdsExhausted => f.write_str("session IDs are exhausted"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignerError {}
+
+/// Signer response is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidResponse;
+
+impl core::fmt::Display for InvalidResponse {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid signer response")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidResponse {}
+
+/// Invalid signature error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidSignature;
+
+impl core::fmt::Display for InvalidSignature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid signature")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSignature {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{BlindedChallenge, Signer};
+
+    type User<E> = super::User<E, sha2::Sha256>;
+
+    #[test]
+    fn blind_signing<E: Curve>() {
+        let mut rng = DevRng::new();
+        let mut signer = Signer::<E, sha2::Sha256>::new(SecretScalar::random(&mut rng), 1);
+        let public_key = signer.public_key();
+
+        let (session, commitment) = signer.commit(&mut rng).unwrap();
+        let (user, challenge) = User::blind(&mut rng, &public_key, &commitment, b"message");
+        let response = signer.respond(session, &challenge).unwrap();
+        let signature = user.unblind(&response).unwrap();
+
+        signature
+            .verify::<sha2::Sha256>(&public_key, b"message")
+            .unwrap();
+        assert!(signature
+            .verify::<sha2::Sha256>(&public_key, b"other message")
+            .is_err());
+
+        // Signer can't link the signature to the session it participated in
+        assert_ne!(signature.r, commitment.0);
+        assert_ne!(signature.s, response.0);
+    }
+
+    #[test]
+    fn invalid_response_is_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let mut signer = Signer::<E, sha2::Sha256>::new(SecretScalar::random(&mut rng), 1);
+        let public_key = signer.public_key();
+
+        let (session, commitment) = signer.commit(&mut rng).unwrap();
+        let (user, challenge) = User::blind(&mut rng, &public_key, &commitment, b"message");
+        let mut response = signer.respond(session, &challenge).unwrap();
+        response.0 += Scalar::one();
+        assert!(user.unblind(&response).is_err());
+    }
+
+    #[test]
+    fn parallel_sessions_are_limited<E: Curve>() {
+        let mut rng = DevRng::new();
+        let mut signer = Signer::<E, sha2::Sha256>::new(SecretScalar::random(&mut rng), 2);
+        let challenge = BlindedChallenge(Scalar::<E>::random(&mut rng));
+
+        let (s1, _) = signer.commit(&mut rng).unwrap();
+        let (s2, _) = signer.commit(&mut rng).unwrap();
+        assert!(signer.commit(&mut rng).is_err());
+        assert_eq!(signer.open_sessions(), 2);
+
+        // Closing a session frees up a slot
+        signer.respond(s1, &challenge).unwrap();
+        let (s3, _) = signer.commit(&mut rng).unwrap();
+
+        // Every session can be used only once
+        assert!(signer.respond(s1, &challenge).is_err());
+
+        signer.abort(s2).unwrap();
+        assert!(signer.respond(s2, &challenge).is_err());
+        signer.respond(s3, &challenge).unwrap();
+        assert_eq!(signer.open_sessions(), 0);
+    }
+
+    #[test]
+    fn signatures_on_same_message_are_unlinkable<E: Curve>() {
+        let mut rng = DevRng::new();
+        let mut signer = Signer::<E, sha2::Sha256>::new(SecretScalar::random(&mut rng), 1);
+        let public_key = signer.public_key();
+
+        let mut sign = |rng: &mut DevRng| {
+            let (session, commitment) = signer.commit(rng).unwrap();
+            let (user, challenge) = User::blind(rng, &public_key, &commitment, b"message");
+            let response = signer.respond(session, &challenge).unwrap();
+            user.unblind(&response).unwrap()
+        };
+        let sig1 = sign(&mut rng);
+        let sig2 = sign(&mut rng);
+        assert_ne!(sig1, sig2);
+    }
+
+    #[cfg(feature = "signature")]
+    #[test]
+    fn signature_traits<E: Curve>() {
+        use signature::{Keypair, Verifier};
+
+        let mut rng = DevRng::new();
+        let mut signer = Signer::<E, sha2::Sha256>::new(SecretScalar::random(&mut rng), 1);
+        let verifying_key = signer.verifying_key();
+
+        let (session, commitment) = signer.commit(&mut rng).unwrap();
+        let (user, challenge) = User::blind(
+            &mut rng,
+            verifying_key.public_key(),
+            &commitment,
+            b"message",
+        );
+        let response = signer.respond(session, &challenge).unwrap();
+        let signature = user.unblind(&response).unwrap();
+
+        verifying_key.verify(b"message", &signature).unwrap();
+        assert!(verifying_key.verify(b"other message", &signature).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
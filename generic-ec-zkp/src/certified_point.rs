@@ -0,0 +1,262 @@
+//! Public key certified by a proof of knowledge of its secret key
+//!
+//! Aggregation protocols (e.g. aggregated or multi-signatures, distributed key generation)
+//! sum up public keys provided by the parties. A malicious party may pick its public key
+//! after seeing others' keys as $X_{rogue} = x \cdot G - \sum_j X_j$, so aggregated key is
+//! $x \cdot G$ and the adversary knows it in full (rogue-key attack). The attack is prevented
+//! if every party proves knowledge of the secret key of its public key.
+//!
+//! [`CertifiedPoint<E, D>`] bundles a public key with a non-interactive [Schnorr
+//! proof](crate::schnorr_pok) of knowledge of its secret key. The proof is checked on
+//! construction and deserialization, so a value of this type always carries a valid proof:
+//! protocols that accept only `CertifiedPoint` are protected from rogue keys at the type level.
+//!
+//! The proof is not bound to any session: a certified point can be replayed by anyone who
+//! observed it. It doesn't help the adversary as it doesn't know the secret key of a replayed
+//! point, but protocols which need to attribute keys to parties should additionally
+//! authenticate them.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{curves::Secp256k1, NonZero, SecretScalar};
+//! use generic_ec_zkp::certified_point::CertifiedPoint;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let secret_key = NonZero::<SecretScalar<Secp256k1>>::random(&mut rng);
+//! let public_key = CertifiedPoint::<_, sha2::Sha256>::certify(&mut rng, &secret_key);
+//!
+//! // Receiver reconstructs the certified point from the received parts (or
+//! // deserializes it), which verifies the proof
+//! let (point, commit, proof) = (*public_key.point(), public_key.commit().clone(), public_key.proof().clone());
+//! let received = CertifiedPoint::<Secp256k1, sha2::Sha256>::from_parts(point, commit, proof)?;
+//! assert_eq!(received, public_key);
+//! # Ok::<(), generic_ec_zkp::schnorr_pok::InvalidProof>(())
+//! ```
+
+use core::marker::PhantomData;
+
+use digest::Digest;
+use generic_ec::{Curve, NonZero, Point, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    schnorr_pok::{self, Challenge, Commit, InvalidProof, Proof},
+    transcript::Transcript,
+};
+
+const DOMAIN: &[u8] = b"generic-ec-zkp/certified_point";
+
+/// Public key with a proof of knowledge of its secret key
+///
+/// Proof is non-interactive, its challenge is derived using hash function `D`. See
+/// [module-level docs](self) for details.
+pub struct CertifiedPoint<E: Curve, D> {
+    point: NonZero<Point<E>>,
+    commit: Commit<E>,
+    proof: Proof<E>,
+    _hash: PhantomData<fn() -> D>,
+}
+
+impl<E: Curve, D: Digest + Clone> CertifiedPoint<E, D> {
+    /// Computes public key of `secret_key` and certifies it
+    pub fn certify<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        secret_key: &NonZero<SecretScalar<E>>,
+    ) -> Self {
+        let point = Point::generator() * secret_key;
+        let (eph_secret, commit) = schnorr_pok::prover_commits_ephemeral_secret::<E, _>(rng);
+        let challenge = Challenge::derive(&mut transcript::<E, D>(), &point, &commit);
+        let proof = schnorr_pok::prove(&eph_secret, &challenge, secret_key.as_ref());
+        Self {
+            point,
+            commit,
+            proof,
+            _hash: PhantomData,
+        }
+    }
+
+    /// Constructs certified point from its parts, verifies the proof
+    pub fn from_parts(
+        point: NonZero<Point<E>>,
+        commit: Commit<E>,
+        proof: Proof<E>,
+    ) -> Result<Self, InvalidProof> {
+        proof.verify_with_rederived_challenge(&mut transcript::<E, D>(), &commit, &point)?;
+        Ok(Self {
+            point,
+            commit,
+            proof,
+            _hash: PhantomData,
+        })
+    }
+
+    /// Certified public key
+    pub fn point(&self) -> &NonZero<Point<E>> {
+        &self.point
+    }
+
+    /// Returns certified public key, discarding the proof
+    pub fn into_point(self) -> NonZero<Point<E>> {
+        self.point
+    }
+
+    /// Prover commitment of the proof
+    pub fn commit(&self) -> &Commit<E> {
+        &self.commit
+    }
+
+    /// Proof of knowledge of the secret key
+    pub fn proof(&self) -> &Proof<E> {
+        &self.proof
+    }
+}
+
+fn transcript<E: Curve, D: Digest + Clone>() -> Transcript<D> {
+    let mut transcript = Transcript::new(DOMAIN);
+    transcript.append_message(b"curve", E::CURVE_NAME.as_bytes());
+    transcript
+}
+
+impl<E: Curve, D> Clone for CertifiedPoint<E, D> {
+    fn clone(&self) -> Self {
+        Self {
+            point: self.point,
+            commit: self.commit.clone(),
+            proof: self.proof.clone(),
+            _hash: PhantomData,
+        }
+    }
+}
+
+impl<E: Curve, D> core::fmt::Debug for CertifiedPoint<E, D> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("CertifiedPoint").field(&self.point).finish()
+    }
+}
+
+impl<E: Curve, D> PartialEq for CertifiedPoint<E, D> {
+    /// Compares certified points, proofs are not compared
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<E: Curve, D> Eq for CertifiedPoint<E, D> {}
+
+impl<E: Curve, D> AsRef<Point<E>> for CertifiedPoint<E, D> {
+    fn as_ref(&self) -> &Point<E> {
+        self.point.as_ref()
+    }
+}
+
+impl<E: Curve, D> From<CertifiedPoint<E, D>> for NonZero<Point<E>> {
+    fn from(certified: CertifiedPoint<E, D>) -> Self {
+        certified.point
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(bound = "", rename = "CertifiedPoint")]
+struct Parts<E: Curve> {
+    point: NonZero<Point<E>>,
+    commit: Commit<E>,
+    proof: Proof<E>,
+}
+
+#[cfg(feature = "serde")]
+impl<E: Curve, D> serde::Serialize for CertifiedPoint<E, D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Parts {
+            point: self.point,
+            commit: self.commit.clone(),
+            proof: self.proof.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, E: Curve, D: Digest + Clone> serde::Deserialize<'de> for CertifiedPoint<E, D> {
+    fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+    where
+        De: serde::Deserializer<'de>,
+    {
+        let parts = Parts::<E>::deserialize(deserializer)?;
+        Self::from_parts(parts.point, parts.commit, parts.proof)
+            .map_err(<De::Error as serde::de::Error>::custom)
+    }
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, NonZero, Point, SecretScalar};
+    use sha2::Sha256;
+
+    use super::CertifiedPoint;
+
+    #[test]
+    fn certify_and_verify<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        let secret_key = NonZero::<SecretScalar<E>>::random(&mut rng);
+        let certified = CertifiedPoint::<E, Sha256>::certify(&mut rng, &secret_key);
+        assert_eq!(*certified.point(), Point::generator() * &secret_key);
+
+        let restored = CertifiedPoint::<E, Sha256>::from_parts(
+            *certified.point(),
+            certified.commit().clone(),
+            certified.proof().clone(),
+        )
+        .unwrap();
+        assert_eq!(restored, certified);
+
+        // Rogue key: the proof doesn't verify for another point
+        let other = Point::generator() * &NonZero::<SecretScalar<E>>::random(&mut rng);
+        assert!(CertifiedPoint::<E, Sha256>::from_parts(
+            other,
+            certified.commit().clone(),
+            certified.proof().clone(),
+        )
+        .is_err());
+
+        // Proof is bound to the hash function
+        assert!(CertifiedPoint::<E, sha2::Sha512>::from_parts(
+            *certified.point(),
+            certified.commit().clone(),
+            certified.proof().clone(),
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn verified_on_deserialization<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        let secret_key = NonZero::<SecretScalar<E>>::random(&mut rng);
+        let certified = CertifiedPoint::<E, Sha256>::certify(&mut rng, &secret_key);
+
+        let json = serde_json::to_value(&certified).unwrap();
+        let restored: CertifiedPoint<E, Sha256> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(restored, certified);
+
+        let mut invalid = json;
+        invalid["proof"] = serde_json::to_value(crate::schnorr_pok::Proof(
+            certified.proof().0 + generic_ec::Scalar::one(),
+        ))
+        .unwrap();
+        assert!(serde_json::from_value::<CertifiedPoint<E, Sha256>>(invalid).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+    #[instantiate_tests(<generic_ec::curves::Ed25519>)]
+    mod ed25519 {}
+}
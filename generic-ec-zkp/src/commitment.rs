@@ -0,0 +1,246 @@
+//! Commitment schemes
+//!
+//! [`CommitmentScheme`] abstracts over commitment schemes, so protocols can be written once and
+//! instantiated with any of them. The crate provides two implementations:
+//!
+//! * [`HashCommitment`] commits to a byte string via hash function, commitment is
+//!   computationally hiding and binding
+//! * [Pedersen commitment](crate::pedersen::Params) commits to a scalar, commitment is perfectly
+//!   hiding, computationally binding, and additively homomorphic (see [`HomomorphicCommitmentScheme`])
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{commitment::{CommitmentScheme, HashCommitment}, pedersen};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Protocol written once for any commitment scheme
+//! fn commit_reveal<S: CommitmentScheme>(
+//!     scheme: &S,
+//!     rng: &mut (impl rand::RngCore + rand::CryptoRng),
+//!     value: &S::Value,
+//! ) -> Result<(), generic_ec_zkp::commitment::InvalidOpening> {
+//!     let (commitment, opening) = scheme.commit(rng, value);
+//!     // ... commitment is sent first, value and opening are revealed later
+//!     scheme.verify(&commitment, value, &opening)
+//! }
+//!
+//! commit_reveal(&HashCommitment::<sha2::Sha256>::new(), &mut rng, b"some value".as_slice())?;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-pedersen-v0.1.0");
+//! let params = pedersen::Params::<Secp256k1>::derive(TAG)?;
+//! commit_reveal(&params, &mut rng, &Scalar::from(42))?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use core::marker::PhantomData;
+
+use digest::{Digest, Output};
+use generic_ec::{Curve, Point, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::pedersen;
+
+/// Commitment scheme
+pub trait CommitmentScheme {
+    /// Value that can be committed
+    type Value: ?Sized;
+    /// Randomness that opens the commitment
+    type Opening;
+    /// Commitment
+    type Commitment: PartialEq;
+
+    /// Samples a random opening
+    fn sample_opening<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Self::Opening;
+
+    /// Deterministically commits to the `value` using given `opening`
+    fn commit_with_opening(&self, value: &Self::Value, opening: &Self::Opening)
+        -> Self::Commitment;
+
+    /// Commits to the `value`, returns commitment and opening
+    fn commit<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        value: &Self::Value,
+    ) -> (Self::Commitment, Self::Opening) {
+        let opening = self.sample_opening(rng);
+        let commitment = self.commit_with_opening(value, &opening);
+        (commitment, opening)
+    }
+
+    /// Verifies that `commitment` commits to the `value`
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        value: &Self::Value,
+        opening: &Self::Opening,
+    ) -> Result<(), InvalidOpening> {
+        if self.commit_with_opening(value, opening) == *commitment {
+            Ok(())
+        } else {
+            Err(InvalidOpening)
+        }
+    }
+}
+
+/// Additively homomorphic commitment scheme
+///
+/// Sum of commitments to $v_1$ and $v_2$ is a commitment to $v_1 + v_2$ which can be opened with
+/// sum of the openings.
+pub trait HomomorphicCommitmentScheme: CommitmentScheme {
+    /// Adds two commitments
+    fn add_commitments(&self, a: &Self::Commitment, b: &Self::Commitment) -> Self::Commitment;
+    /// Adds two values
+    fn add_values(&self, a: &Self::Value, b: &Self::Value) -> Self::Value
+    where
+        Self::Value: Sized;
+    /// Adds two openings
+    fn add_openings(&self, a: &Self::Opening, b: &Self::Opening) -> Self::Opening;
+}
+
+impl<E: Curve> CommitmentScheme for pedersen::Params<E> {
+    type Value = Scalar<E>;
+    type Opening = Scalar<E>;
+    type Commitment = Point<E>;
+
+    fn sample_opening<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Self::Opening {
+        Scalar::random(rng)
+    }
+
+    fn commit_with_opening(&self, value: &Scalar<E>, opening: &Scalar<E>) -> Point<E> {
+        self.commit(value, opening)
+    }
+}
+
+impl<E: Curve> HomomorphicCommitmentScheme for pedersen::Params<E> {
+    fn add_commitments(&self, a: &Point<E>, b: &Point<E>) -> Point<E> {
+        a + b
+    }
+    fn add_values(&self, a: &Scalar<E>, b: &Scalar<E>) -> Scalar<E> {
+        a + b
+    }
+    fn add_openings(&self, a: &Scalar<E>, b: &Scalar<E>) -> Scalar<E> {
+        a + b
+    }
+}
+
+/// Hash commitment
+///
+/// Commitment to value $v$ with random salt $r$ is $H(\text{tag} \parallel r \parallel v)$ where
+/// every component is prefixed with its length. Salt is 32 bytes long.
+pub struct HashCommitment<D> {
+    _hash: PhantomData<D>,
+}
+
+impl<D: Digest> HashCommitment<D> {
+    /// Constructs hash commitment scheme
+    pub fn new() -> Self {
+        Self { _hash: PhantomData }
+    }
+}
+
+impl<D: Digest> Default for HashCommitment<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Digest> CommitmentScheme for HashCommitment<D> {
+    type Value = [u8];
+    type Opening = [u8; 32];
+    type Commitment = Output<D>;
+
+    fn sample_opening<R: RngCore + CryptoRng>(&self, rng: &mut R) -> [u8; 32] {
+        let mut salt = [0u8; 32];
+        rng.fill_bytes(&mut salt);
+        salt
+    }
+
+    fn commit_with_opening(&self, value: &[u8], opening: &[u8; 32]) -> Output<D> {
+        let tag: &[u8] = b"generic-ec-zkp/hash_commitment";
+        let mut hash = D::new();
+        for component in [tag, opening, value] {
+            hash.update((component.len() as u64).to_be_bytes());
+            hash.update(component);
+        }
+        hash.finalize()
+    }
+}
+
+/// Opening doesn't match the commitment
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidOpening;
+
+impl core::fmt::Display for InvalidOpening {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("opening doesn't match the commitment")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidOpening {}
+
+#[cfg(test)]
+mod tests {
+    use rand_dev::DevRng;
+
+    use super::{CommitmentScheme, HashCommitment};
+
+    fn commit_and_open<S: CommitmentScheme>(
+        scheme: &S,
+        rng: &mut DevRng,
+        value: &S::Value,
+        other_value: &S::Value,
+    ) {
+        let (commitment, opening) = scheme.commit(rng, value);
+        scheme.verify(&commitment, value, &opening).unwrap();
+        assert!(scheme.verify(&commitment, other_value, &opening).is_err());
+
+        let other_opening = scheme.sample_opening(rng);
+        assert!(scheme.verify(&commitment, value, &other_opening).is_err());
+    }
+
+    #[test]
+    fn hash_commitment() {
+        let mut rng = DevRng::new();
+        let scheme = HashCommitment::<sha2::Sha256>::new();
+        commit_and_open(&scheme, &mut rng, b"value", b"other value");
+        commit_and_open(&scheme, &mut rng, b"", b"\x00");
+    }
+
+    #[generic_tests::define]
+    mod pedersen {
+        use generic_ec::{hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar};
+        use rand_dev::DevRng;
+
+        use crate::commitment::{CommitmentScheme, HomomorphicCommitmentScheme};
+
+        const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-commitment-test");
+
+        #[test]
+        fn pedersen_commitment<E: Curve>()
+        where
+            Point<E>: FromHash,
+        {
+            let mut rng = DevRng::new();
+            let params = crate::pedersen::Params::<E>::derive(TAG).unwrap();
+            let [v1, v2] = [(); 2].map(|_| Scalar::random(&mut rng));
+            super::commit_and_open(&params, &mut rng, &v1, &v2);
+
+            let (c1, o1) = CommitmentScheme::commit(&params, &mut rng, &v1);
+            let (c2, o2) = CommitmentScheme::commit(&params, &mut rng, &v2);
+            CommitmentScheme::verify(
+                &params,
+                &params.add_commitments(&c1, &c2),
+                &params.add_values(&v1, &v2),
+                &params.add_openings(&o1, &o2),
+            )
+            .unwrap();
+        }
+
+        #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+        mod secp256k1 {}
+        #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+        mod secp256r1 {}
+    }
+}
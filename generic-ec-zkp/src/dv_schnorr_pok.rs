@@ -0,0 +1,234 @@
+//! Designated-verifier Schnorr Proof of Knowledge $\Pi^\text{dv-sch}$
+//!
+//! Variant of [Schnorr PoK](crate::schnorr_pok) which convinces only a designated verifier $\V$
+//! holding secret key $y$ of public key $Y = y \cdot G$. Prover proves that it knows either $x$
+//! such as $X = x \cdot G$, or $y$ (OR-composition of two Schnorr proofs, see [CDS94]). $\V$ knows
+//! that prover doesn't know $y$, so it's convinced that prover knows $x$. However, $\V$ itself
+//! can produce such proof for any $X$ via [`simulate`], so the proof doesn't convince anyone else.
+//! That makes the proof deniable.
+//!
+//! The proof is made non-interactive via Fiat-Shamir [transcript](crate::transcript).
+//!
+//! [CDS94]: https://link.springer.com/chapter/10.1007/3-540-48658-5_19
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{dv_schnorr_pok, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Designated verifier key pair
+//! let y = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let Y = Point::generator() * &y;
+//!
+//! // Prover knows `x` and proves it to the verifier
+//! let x = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let X = Point::generator() * &x;
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! let proof = dv_schnorr_pok::prove(&mut transcript, &mut rng, &x, &Y);
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! proof.verify(&mut transcript, &X, &Y)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Let $Y_0 = X$, $Y_1 = Y$. Prover knows $x_b$ such that $Y_b = x_b \cdot G$ ($b = 0$ for honest
+//! prover, $b = 1$ for simulator).
+//!
+//! * Prove
+//!   1. Prover simulates the proof for $Y_{1-b}$: samples $e_{1-b}, z_{1-b} \gets \Z_q$, sets
+//!      $A_{1-b} = z_{1-b} \cdot G - e_{1-b} \cdot Y_{1-b}$
+//!   2. Prover samples $k \gets \Z_q$, sets $A_b = k \cdot G$
+//!   3. Prover obtains challenge $e$ from the transcript, sets $e_b = e - e_{1-b}$,
+//!      $z_b = k + e_b x_b$
+//!   4. Proof is $(A_0, A_1, e_0, z_0, z_1)$
+//! * Verification \
+//!   Verifier obtains challenge $e$, sets $e_1 = e - e_0$, and checks that
+//!   $z_0 \cdot G \\? A_0 + e_0 \cdot Y_0$ and $z_1 \cdot G \\? A_1 + e_1 \cdot Y_1$
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::transcript::Transcript;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Designated-verifier proof of knowledge of discrete log
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+#[allow(non_snake_case)]
+pub struct Proof<E: Curve> {
+    /// Commitment $A_0$
+    pub A0: Point<E>,
+    /// Commitment $A_1$
+    pub A1: Point<E>,
+    /// Challenge $e_0$, challenge $e_1$ is derived from it
+    pub e0: Scalar<E>,
+    /// Response $z_0$
+    pub z0: Scalar<E>,
+    /// Response $z_1$
+    pub z1: Scalar<E>,
+}
+
+/// Proves knowledge of `secret` to the verifier with public key `verifier_key`
+///
+/// Point $X = \text{secret} \cdot G$ and verifier key are appended to the transcript before
+/// deriving the challenge.
+#[allow(non_snake_case)]
+pub fn prove<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    transcript: &mut Transcript<D>,
+    rng: &mut R,
+    secret: &SecretScalar<E>,
+    verifier_key: &Point<E>,
+) -> Proof<E> {
+    let X = Point::generator() * secret;
+
+    let e1 = Scalar::random(rng);
+    let z1 = Scalar::random(rng);
+    let A1 = Point::generator() * z1 - verifier_key * e1;
+
+    let k = SecretScalar::<E>::random(rng);
+    let A0 = Point::generator() * &k;
+
+    let e = challenge(transcript, &X, verifier_key, &A0, &A1);
+    let e0 = e - e1;
+    let z0 = &k + e0 * secret;
+
+    Proof { A0, A1, e0, z0, z1 }
+}
+
+/// Simulates a proof of knowledge of discrete log of $X$ using verifier secret key
+///
+/// Simulated proof is indistinguishable from [honest one](prove), and it verifies even though
+/// simulator doesn't know discrete log of $X$. It's what makes the proof deniable.
+#[allow(non_snake_case)]
+pub fn simulate<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    transcript: &mut Transcript<D>,
+    rng: &mut R,
+    X: &Point<E>,
+    verifier_secret: &SecretScalar<E>,
+) -> Proof<E> {
+    let Y = Point::generator() * verifier_secret;
+
+    let e0 = Scalar::random(rng);
+    let z0 = Scalar::random(rng);
+    let A0 = Point::generator() * z0 - X * e0;
+
+    let k = SecretScalar::<E>::random(rng);
+    let A1 = Point::generator() * &k;
+
+    let e = challenge(transcript, X, &Y, &A0, &A1);
+    let e1 = e - e0;
+    let z1 = &k + e1 * verifier_secret;
+
+    Proof { A0, A1, e0, z0, z1 }
+}
+
+impl<E: Curve> Proof<E> {
+    /// Verifies that prover knows discrete log of $X$ or discrete log of `verifier_key`
+    ///
+    /// Transcript must be in the same state as it was at the moment of [proving](prove).
+    #[allow(non_snake_case)]
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        X: &Point<E>,
+        verifier_key: &Point<E>,
+    ) -> Result<(), InvalidProof> {
+        let e = challenge(transcript, X, verifier_key, &self.A0, &self.A1);
+        let e1 = e - self.e0;
+
+        let check0 = Point::generator() * self.z0 == self.A0 + X * self.e0;
+        let check1 = Point::generator() * self.z1 == self.A1 + verifier_key * e1;
+        if check0 && check1 {
+            Ok(())
+        } else {
+            Err(InvalidProof)
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn challenge<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    X: &Point<E>,
+    Y: &Point<E>,
+    A0: &Point<E>,
+    A1: &Point<E>,
+) -> Scalar<E> {
+    transcript.append_point(b"dv_schnorr_pok/X", X);
+    transcript.append_point(b"dv_schnorr_pok/Y", Y);
+    transcript.append_point(b"dv_schnorr_pok/A0", A0);
+    transcript.append_point(b"dv_schnorr_pok/A1", A1);
+    transcript.challenge_scalar(b"dv_schnorr_pok/e")
+}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid designated-verifier Schnorr PoK proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+#[allow(non_snake_case)]
+mod tests {
+    use generic_ec::{Curve, Point, SecretScalar};
+    use rand_dev::DevRng;
+
+    use crate::transcript::Transcript;
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn honest_proof_verifies<E: Curve>() {
+        let mut rng = DevRng::new();
+        let y = SecretScalar::<E>::random(&mut rng);
+        let Y = Point::generator() * &y;
+        let x = SecretScalar::<E>::random(&mut rng);
+        let X = Point::generator() * &x;
+
+        let proof = super::prove(&mut transcript(), &mut rng, &x, &Y);
+        proof.verify(&mut transcript(), &X, &Y).unwrap();
+
+        // Proof is bound to the designated verifier
+        let other_Y = Y + Point::generator();
+        assert!(proof.verify(&mut transcript(), &X, &other_Y).is_err());
+        // And to the statement
+        let wrong_X = X + Point::generator();
+        assert!(proof.verify(&mut transcript(), &wrong_X, &Y).is_err());
+    }
+
+    #[test]
+    fn verifier_can_simulate_proof<E: Curve>() {
+        let mut rng = DevRng::new();
+        let y = SecretScalar::<E>::random(&mut rng);
+        let Y = Point::generator() * &y;
+
+        // Verifier doesn't know discrete log of `X`, yet can produce a valid proof
+        let X = Point::generator() * SecretScalar::<E>::random(&mut rng);
+        let proof = super::simulate(&mut transcript(), &mut rng, &X, &y);
+        proof.verify(&mut transcript(), &X, &Y).unwrap();
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -0,0 +1,348 @@
+//! Distributed verifiable random function (DVRF)
+//!
+//! $n$ parties holding shares of the group secret key $x$ jointly evaluate VRF on an arbitrary
+//! input: any $t$ of them produce the output, which is uniquely determined by the input and
+//! the group key, so it can't be biased by the parties, and is verifiable against the group key.
+//!
+//! Curves in this crate have no pairings, so partial evaluations are DLEQ-based (see
+//! [`beacon`]) rather than threshold BLS: VRF output is $x \cdot H(\text{input})$
+//! shipped along with $t$ partial evaluations proving its correctness.
+//!
+//! [`Round`] is a sans-IO interface for evaluating VRF on a single input: it produces the local
+//! party's partial evaluation, consumes partial evaluations received from other parties (in any
+//! order, from any transport), and outputs the VRF value once enough of them are collected.
+//! It implements [`round::Round`](crate::round::Round), so it can be driven by any driver.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, SecretScalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{dvrf, party_index::PartyIndex, polynomial::Polynomial, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Group key shared among 3 parties with threshold 2, normally generated via DKG
+//! let f = Polynomial::<SecretScalar<Secp256k1>>::sample(&mut rng, 1);
+//! let group_key = f.commit(&Point::generator().to_point());
+//! let key_shares: Vec<_> = (1..=3u16).map(|i| f.eval_ct(&Scalar::from(i))).collect();
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-dvrf-v0.1.0");
+//! let new_round = || {
+//!     let transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-dvrf");
+//!     dvrf::Round::new(TAG, b"lottery #1", transcript, group_key.clone())
+//! };
+//!
+//! // Each party publishes its partial evaluation
+//! let partials = [PartyIndex(0), PartyIndex(2)].map(|i| {
+//!     let key_share = &key_shares[usize::from(i)];
+//!     new_round().map(|round| round.partial_evaluation(&mut rng, i, key_share))
+//! });
+//!
+//! // Anyone collects partial evaluations until the output is obtained
+//! let mut round = new_round()?;
+//! let mut output = None;
+//! for partial in partials {
+//!     output = round.receive(&mut rng, partial?)?;
+//! }
+//! let output = output.expect("threshold is reached");
+//!
+//! // Output can be verified by anyone who knows the group key
+//! new_round()?.verify(&mut rng, &output)?;
+//! let randomness = output.randomness::<sha2::Sha256>();
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! * Input point: $H = \mathcal{H}(\text{input})$ via hash to curve
+//! * Partial evaluation of party $i$: $\sigma_i = x_i \cdot H$ along with DLEQ proof, see
+//!   [`beacon::PartialEvaluation`]
+//! * Output: $\sigma = \sum_{i \in S} \lambda_i \cdot \sigma_i = x \cdot H$, $|S| = t$
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{
+    errors::HashError,
+    hash_to_curve::{FromHash, Tag},
+    Curve, Point, SecretScalar, SupportsHashToCurve,
+};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    beacon::{self, Beacon, PartialEvaluation, VerifiedPartialEvaluation},
+    party_index::PartyIndex,
+    polynomial::Polynomial,
+    transcript::Transcript,
+};
+
+/// Evaluation of VRF on a single input
+pub struct Round<E: Curve, D: Digest> {
+    transcript: Transcript<D>,
+    group_key: Polynomial<Point<E>>,
+    input_point: Point<E>,
+    received: Vec<VerifiedPartialEvaluation<E>>,
+}
+
+impl<E: Curve, D: Digest + Clone> Round<E, D> {
+    /// Starts evaluation of VRF on the `input`
+    ///
+    /// `group_key` is a Feldman commitment to the polynomial sharing the secret key, party
+    /// $i \in [0, n)$ holds key share $f(i + 1)$. Input is appended to the `transcript`. All
+    /// parties must start the round with the same tag, input, transcript, and group key.
+    pub fn new(
+        tag: Tag,
+        input: &[u8],
+        mut transcript: Transcript<D>,
+        group_key: Polynomial<Point<E>>,
+    ) -> Result<Self, HashError>
+    where
+        E: SupportsHashToCurve,
+    {
+        let input_point = Point::hash(tag, input)?;
+        transcript.append_message(b"dvrf/input", input);
+        Ok(Self {
+            transcript,
+            group_key,
+            input_point,
+            received: Vec::new(),
+        })
+    }
+
+    /// Computes partial evaluation of the local party
+    ///
+    /// The result needs to be sent to the parties collecting the output, including the local
+    /// party itself if it's one of them.
+    pub fn partial_evaluation<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        index: PartyIndex,
+        key_share: &SecretScalar<E>,
+    ) -> PartialEvaluation<E> {
+        PartialEvaluation::new(
+            rng,
+            self.transcript.clone(),
+            index,
+            key_share,
+            &self.input_point,
+        )
+    }
+
+    /// Processes partial evaluation received from another party
+    ///
+    /// Returns VRF output once threshold amount of valid partial evaluations is received, and
+    /// on every valid partial evaluation received after that. Returns error if partial
+    /// evaluation is invalid, or the party already sent one. Such errors don't abort the
+    /// round: other partial evaluations can still be received.
+    pub fn receive<R: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        partial: PartialEvaluation<E>,
+    ) -> Result<Option<Beacon<E>>, RoundError> {
+        if self.received.iter().any(|p| p.index() == partial.index) {
+            return Err(RoundError::DuplicatePartial {
+                index: partial.index,
+            });
+        }
+        let verified = partial
+            .verify(
+                &mut self.transcript.clone(),
+                rng,
+                &self.group_key,
+                &self.input_point,
+            )
+            .map_err(|_| RoundError::InvalidPartial {
+                index: partial.index,
+            })?;
+        self.received.push(verified);
+
+        if self.received.len() < self.group_key.coefs().len() {
+            return Ok(None);
+        }
+        beacon::aggregate(&self.group_key, self.received.clone())
+            .map(Some)
+            .map_err(RoundError::Aggregation)
+    }
+
+    /// Verifies VRF output against the group key
+    pub fn verify<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        output: &Beacon<E>,
+    ) -> Result<(), beacon::InvalidBeacon> {
+        output.verify(&self.transcript, rng, &self.group_key, &self.input_point)
+    }
+}
+
+impl<E: Curve, D: Digest + Clone> crate::round::Round for Round<E, D> {
+    type Msg = PartialEvaluation<E>;
+    type Output = Beacon<E>;
+    type Error = RoundError;
+
+    fn receive<R: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        msg: Self::Msg,
+    ) -> Result<Option<Self::Output>, Self::Error> {
+        Round::receive(self, rng, msg)
+    }
+}
+
+/// Round error
+#[derive(Debug, Clone, Copy)]
+pub enum RoundError {
+    /// Partial evaluation is invalid
+    InvalidPartial {
+        /// Index of the party
+        index: PartyIndex,
+    },
+    /// Party sent more than one partial evaluation
+    DuplicatePartial {
+        /// Index of the party
+        index: PartyIndex,
+    },
+    /// Partial evaluations can't be aggregated
+    Aggregation(beacon::AggregationError),
+}
+
+impl core::fmt::Display for RoundError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidPartial { index } => {
+                write!(f, "invalid partial evaluation from party {index}")
+            }
+            Self::DuplicatePartial { index } => {
+                write!(f, "party {index} sent more than one partial evaluation")
+            }
+            Self::Aggregation(_) => f.write_str("partial evaluations can't be aggregated"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RoundError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Aggregation(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{hash_to_curve::FromHash, Point, Scalar, SecretScalar, SupportsHashToCurve};
+    use rand_dev::DevRng;
+
+    use super::{Round, RoundError};
+    use crate::{party_index::PartyIndex, polynomial::Polynomial, transcript::Transcript};
+
+    const TAG: generic_ec::hash_to_curve::Tag = generic_ec::hash_to_curve::Tag::new_unwrap(b"test");
+
+    fn round<E: SupportsHashToCurve>(
+        input: &[u8],
+        group_key: &Polynomial<Point<E>>,
+    ) -> Round<E, sha2::Sha256> {
+        Round::new(TAG, input, Transcript::new(b"test"), group_key.clone()).unwrap()
+    }
+
+    #[test]
+    fn evaluation<E: SupportsHashToCurve>() {
+        let mut rng = DevRng::new();
+        let secret_key = SecretScalar::<E>::random(&mut rng);
+        let f = Polynomial::sample_with_const_term(&mut rng, 2, secret_key.clone());
+        let group_key = f.commit(&Point::generator().to_point());
+        let key_shares: Vec<_> = (1..=5u16).map(|i| f.eval_ct(&Scalar::from(i))).collect();
+
+        let mut collector = round(b"input", &group_key);
+        let partials: Vec<_> = [3u16, 1, 4, 0]
+            .into_iter()
+            .map(|i| {
+                round(b"input", &group_key).partial_evaluation(
+                    &mut rng,
+                    PartyIndex(i),
+                    &key_shares[usize::from(i)],
+                )
+            })
+            .collect();
+
+        // Invalid and duplicate partials are rejected without aborting the round
+        assert!(collector
+            .receive(&mut rng, partials[0].clone())
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            collector.receive(&mut rng, partials[0].clone()),
+            Err(RoundError::DuplicatePartial {
+                index: PartyIndex(3)
+            })
+        ));
+        let mut invalid = partials[1].clone();
+        invalid.sigma += Point::generator();
+        assert!(matches!(
+            collector.receive(&mut rng, invalid),
+            Err(RoundError::InvalidPartial {
+                index: PartyIndex(1)
+            })
+        ));
+        assert!(collector
+            .receive(&mut rng, partials[1].clone())
+            .unwrap()
+            .is_none());
+
+        let output = collector
+            .receive(&mut rng, partials[2].clone())
+            .unwrap()
+            .unwrap();
+        let input_point = Point::<E>::hash(TAG, b"input").unwrap();
+        assert_eq!(output.output, input_point * &secret_key);
+        round(b"input", &group_key)
+            .verify(&mut rng, &output)
+            .unwrap();
+
+        // Output is bound to the input
+        assert!(round(b"other input", &group_key)
+            .verify(&mut rng, &output)
+            .is_err());
+    }
+
+    #[test]
+    fn driven_by_sync_loop<E: SupportsHashToCurve>() {
+        let mut rng = DevRng::new();
+        let f = Polynomial::<SecretScalar<E>>::sample(&mut rng, 1);
+        let group_key = f.commit(&Point::generator().to_point());
+
+        let partials: Vec<_> = (0..3)
+            .map(|i| {
+                let i = PartyIndex(i);
+                let key_share = f.eval_ct(&i.share_index().to_scalar::<E>());
+                round(b"input", &group_key).partial_evaluation(&mut rng, i, &key_share)
+            })
+            .collect();
+
+        let mut collector = round(b"input", &group_key);
+        let output = crate::round::run(&mut collector, &mut rng, partials)
+            .unwrap()
+            .unwrap();
+        round(b"input", &group_key)
+            .verify(&mut rng, &output)
+            .unwrap();
+
+        // Not enough messages to complete the round
+        let mut collector = round::<E>(b"input", &group_key);
+        let partial = round(b"input", &group_key).partial_evaluation(
+            &mut rng,
+            PartyIndex(0),
+            &f.eval_ct(&Scalar::from(1)),
+        );
+        assert!(crate::round::run(&mut collector, &mut rng, [partial])
+            .unwrap()
+            .is_none());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
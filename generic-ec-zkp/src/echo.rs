@@ -0,0 +1,138 @@
+//! Echo broadcast
+//!
+//! Broadcast over point-to-point channels doesn't guarantee that everyone received the same
+//! message: malicious party may send different messages to different recipients. Protocols like
+//! CGGMP require all parties to agree on messages of a round before proceeding, which is enforced
+//! via echo broadcast:
+//!
+//! 1. After receiving messages of the round from all parties, each party computes echo
+//!    [commitment](commit) to them and sends it to everyone
+//! 2. Each party [verifies](verify) that received echoes match its own commitment. If they do,
+//!    all honest parties have seen the same messages.
+//!
+//! Messages are hashed via [`udigest`], i.e. any message deriving [`udigest::Digestable`] can be
+//! committed, and points and scalars are encoded the same way as everywhere else in the crate.
+//! Commitment also covers session identifier, so echoes can't be replayed across sessions.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1};
+//! use generic_ec_zkp::echo;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Round messages broadcast by 3 parties, `messages[i]` is sent by party `i`
+//! let messages = [(); 3].map(|_| Point::<Secp256k1>::generator() * Scalar::random(&mut rng));
+//!
+//! // Every party commits to the messages it received
+//! let echoes = [(); 3].map(|_| echo::commit::<sha2::Sha256, _>(b"session #1", &messages));
+//!
+//! // ... and checks echoes of other parties
+//! echo::verify::<sha2::Sha256>(&echoes[0], &echoes)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use digest::{Digest, Output};
+
+use crate::party_index::PartyIndex;
+
+/// Messages of the round along with session identifier
+#[derive(udigest::Digestable)]
+#[udigest(bound = "M: udigest::Digestable")]
+struct Round<'a, M> {
+    #[udigest(as_bytes)]
+    sid: &'a [u8],
+    messages: &'a [M],
+}
+
+/// Computes echo commitment to the messages of the round
+///
+/// `messages[i]` must be message sent by party $i$, including the message of the local party.
+/// `sid` is a session identifier.
+pub fn commit<D: Digest, M: udigest::Digestable>(sid: &[u8], messages: &[M]) -> Output<D> {
+    udigest::udigest(
+        udigest::Tag::<D>::new("generic-ec-zkp.echo"),
+        Round { sid, messages },
+    )
+}
+
+/// Verifies that echoes of all parties match the local commitment
+///
+/// `echoes[i]` must be echo sent by party $i$. Returns error identifying the first party whose
+/// echo doesn't match. Mismatch doesn't tell who's to blame: either that party lies about
+/// the messages it received, or someone sent different messages to different parties.
+pub fn verify<D: Digest>(own: &Output<D>, echoes: &[Output<D>]) -> Result<(), EchoMismatch> {
+    match (0..=u16::MAX)
+        .map(PartyIndex)
+        .zip(echoes)
+        .find(|(_, echo)| *echo != own)
+    {
+        Some((party, _)) => Err(EchoMismatch { party }),
+        None => Ok(()),
+    }
+}
+
+/// Echo of the party doesn't match the local commitment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EchoMismatch {
+    /// Index of the party that sent mismatching echo
+    pub party: PartyIndex,
+}
+
+impl core::fmt::Display for EchoMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "echo of party {} doesn't match", self.party)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EchoMismatch {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    use super::{commit, verify, EchoMismatch};
+    use crate::party_index::PartyIndex;
+
+    type D = sha2::Sha256;
+
+    #[test]
+    fn echo<E: Curve>() {
+        let mut rng = DevRng::new();
+        let messages = [(); 4].map(|_| {
+            (
+                Point::<E>::generator() * Scalar::random(&mut rng),
+                Scalar::<E>::random(&mut rng),
+            )
+        });
+        let own = commit::<D, _>(b"sid", &messages);
+        let mut echoes = [(); 4].map(|_| commit::<D, _>(b"sid", &messages));
+        verify::<D>(&own, &echoes).unwrap();
+
+        // Party 2 received a different message from party 0
+        let mut equivocated = messages;
+        equivocated[0].1 += Scalar::one();
+        echoes[2] = commit::<D, _>(b"sid", &equivocated);
+        assert_eq!(
+            verify::<D>(&own, &echoes),
+            Err(EchoMismatch {
+                party: PartyIndex(2)
+            })
+        );
+
+        // Commitment depends on the session and the order of messages
+        assert_ne!(commit::<D, _>(b"other sid", &messages), own);
+        let mut reordered = messages;
+        reordered.swap(1, 3);
+        assert_ne!(commit::<D, _>(b"sid", &reordered), own);
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
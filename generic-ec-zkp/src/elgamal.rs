@@ -0,0 +1,455 @@
+//! ElGamal encryption
+//!
+//! ElGamal encryption of point $M$ under public key $Y = y \cdot G$ with randomness $r$ is
+//! a pair $(C_1, C_2) = (r \cdot G, M + r \cdot Y)$. It's additively homomorphic: sum of two
+//! ciphertexts is an encryption of sum of plaintexts.
+//!
+//! Scalar $m$ can be encrypted "in the exponent" as point $m \cdot G$. Decryption then yields
+//! $m \cdot G$, so $m$ can only be recovered if it's small enough to be brute-forced.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1};
+//! use generic_ec_zkp::elgamal;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let (dk, ek) = elgamal::keygen::<Secp256k1, _>(&mut rng);
+//!
+//! let message = Point::generator() * Scalar::random(&mut rng);
+//! let randomness = Scalar::random(&mut rng);
+//! let ciphertext = ek.encrypt(&message, &randomness);
+//!
+//! assert_eq!(dk.decrypt(&ciphertext), message);
+//! ```
+//!
+//! ## Proofs
+//! The module provides messages carrying a ciphertext along with a [proof bundle](crate::proof_bundle)
+//! about it (requires `alloc` feature). A message can only be used through its verified
+//! counterpart obtained after verification:
+//!
+//! * [`KnownPlaintext`] proves that the sender knows plaintext $m$ and randomness $r$ of
+//!   ciphertext encrypting $m$ in the exponent, i.e. $C_1 = r \cdot G$, $C_2 = m \cdot G + r \cdot Y$
+//! * [`EqualPlaintexts`] proves that two ciphertexts, possibly under different keys $Y, Y'$,
+//!   encrypt the same plaintext. The sender knows randomness $r, r'$ of both ciphertexts such
+//!   that $C_1 = r \cdot G$, $C'_1 = r' \cdot G$, and $r \cdot Y - r' \cdot Y' = C_2 - C'_2$.
+//!
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{elgamal::{self, EqualPlaintexts}, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let (_, ek1) = elgamal::keygen::<Secp256k1, _>(&mut rng);
+//! let (dk2, ek2) = elgamal::keygen::<Secp256k1, _>(&mut rng);
+//! let transcript = || Transcript::<sha2::Sha256>::new(b"MYAPP-reencryption");
+//!
+//! // Sender encrypts the same message under two keys and proves it
+//! let message = Point::generator() * SecretScalar::random(&mut rng);
+//! let randomness = [(); 2].map(|_| SecretScalar::random(&mut rng));
+//! let msg = EqualPlaintexts::new(&mut rng, transcript(), [&ek1, &ek2], &message, &randomness);
+//!
+//! // Recipient verifies the message
+//! let verified = msg.verify(&mut transcript(), &mut rng, [&ek1, &ek2])?;
+//! assert_eq!(dk2.decrypt(&verified.ciphertexts()[1]), message);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+#[cfg(feature = "alloc")]
+use digest::Digest;
+use generic_ec::{traits::ExposeSecret, Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(feature = "alloc")]
+use crate::{
+    proof_bundle::{BundleProver, InvalidProof, LinearRelation, ProofBundle},
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Decryption key $y$
+#[derive(Clone, Debug)]
+pub struct DecryptionKey<E: Curve> {
+    pub secret: SecretScalar<E>,
+}
+
+/// Encryption key $Y = y \cdot G$
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct EncryptionKey<E: Curve>(pub Point<E>);
+
+/// ElGamal ciphertext $(C_1, C_2)$
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Ciphertext<E: Curve> {
+    /// $C_1 = r \cdot G$
+    pub c1: Point<E>,
+    /// $C_2 = M + r \cdot Y$
+    pub c2: Point<E>,
+}
+
+/// Generates a random key pair
+pub fn keygen<E: Curve, R: RngCore + CryptoRng>(
+    rng: &mut R,
+) -> (DecryptionKey<E>, EncryptionKey<E>) {
+    let secret = SecretScalar::random(rng);
+    let public = Point::generator() * &secret;
+    (DecryptionKey { secret }, EncryptionKey(public))
+}
+
+impl<E: Curve> DecryptionKey<E> {
+    /// Returns corresponding encryption key
+    pub fn encryption_key(&self) -> EncryptionKey<E> {
+        EncryptionKey(Point::generator() * &self.secret)
+    }
+
+    /// Decrypts a ciphertext, returns $M = C_2 - y \cdot C_1$
+    pub fn decrypt(&self, ciphertext: &Ciphertext<E>) -> Point<E> {
+        ciphertext.c2 - ciphertext.c1 * &self.secret
+    }
+}
+
+impl<E: Curve> EncryptionKey<E> {
+    /// Encrypts point `message` using `randomness`
+    pub fn encrypt(&self, message: &Point<E>, randomness: &Scalar<E>) -> Ciphertext<E> {
+        Ciphertext {
+            c1: Point::generator() * randomness,
+            c2: message + self.0 * randomness,
+        }
+    }
+
+    /// Encrypts scalar `message` in the exponent, i.e. encrypts point $m \cdot G$
+    ///
+    /// Computed in constant time, `message` and `randomness` may be secret.
+    pub fn encrypt_scalar(
+        &self,
+        message: &impl ExposeSecret<E>,
+        randomness: &impl ExposeSecret<E>,
+    ) -> Ciphertext<E> {
+        message.expose_secret(|m| {
+            randomness.expose_secret(|r| Ciphertext {
+                c1: Point::generator() * r,
+                c2: Point::generator() * m + self.0 * r,
+            })
+        })
+    }
+}
+
+impl<E: Curve> core::ops::Add for Ciphertext<E> {
+    type Output = Ciphertext<E>;
+    fn add(self, rhs: Self) -> Self::Output {
+        Ciphertext {
+            c1: self.c1 + rhs.c1,
+            c2: self.c2 + rhs.c2,
+        }
+    }
+}
+
+impl<E: Curve> core::ops::Sub for Ciphertext<E> {
+    type Output = Ciphertext<E>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Ciphertext {
+            c1: self.c1 - rhs.c1,
+            c2: self.c2 - rhs.c2,
+        }
+    }
+}
+
+impl<E: Curve> core::ops::Mul<&Scalar<E>> for Ciphertext<E> {
+    type Output = Ciphertext<E>;
+    fn mul(self, rhs: &Scalar<E>) -> Self::Output {
+        Ciphertext {
+            c1: self.c1 * rhs,
+            c2: self.c2 * rhs,
+        }
+    }
+}
+
+/// Ciphertext encrypting a scalar in the exponent, and proof of knowledge of its plaintext and
+/// randomness
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct KnownPlaintext<E: Curve> {
+    /// Ciphertext $(C_1, C_2) = (r \cdot G, m \cdot G + r \cdot Y)$
+    pub ciphertext: Ciphertext<E>,
+    /// Proof of knowledge of $m$ and $r$
+    pub proof: ProofBundle<E>,
+}
+
+/// Ciphertext which proof of plaintext knowledge was verified
+///
+/// Can only be obtained via [`KnownPlaintext::verify`]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiedKnownPlaintext<E: Curve> {
+    ciphertext: Ciphertext<E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Curve> KnownPlaintext<E> {
+    /// Encrypts scalar `message` in the exponent, and proves knowledge of the message and
+    /// `randomness`
+    ///
+    /// Relation and proof commitments are appended to the `transcript`
+    pub fn new<D: Digest + Clone, R: RngCore + CryptoRng>(
+        rng: &mut R,
+        transcript: Transcript<D>,
+        ek: &EncryptionKey<E>,
+        message: &SecretScalar<E>,
+        randomness: &SecretScalar<E>,
+    ) -> Self {
+        let ciphertext = ek.encrypt_scalar(message, randomness);
+
+        let mut prover = BundleProver::new(transcript);
+        prover.add_satisfied(
+            rng,
+            known_plaintext_relation(ek, &ciphertext),
+            &[message.clone(), randomness.clone()],
+        );
+        Self {
+            ciphertext,
+            proof: prover.prove(),
+        }
+    }
+
+    /// Verifies the proof
+    ///
+    /// Transcript must be in the same state as it was at the moment of proving.
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        ek: &EncryptionKey<E>,
+    ) -> Result<VerifiedKnownPlaintext<E>, InvalidProof> {
+        let relation = known_plaintext_relation(ek, &self.ciphertext);
+        self.proof
+            .verify(transcript, rng, core::slice::from_ref(&relation))?;
+        Ok(VerifiedKnownPlaintext {
+            ciphertext: self.ciphertext,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Curve> VerifiedKnownPlaintext<E> {
+    /// Ciphertext $(C_1, C_2)$
+    pub fn ciphertext(&self) -> Ciphertext<E> {
+        self.ciphertext
+    }
+}
+
+/// Relation on witness $(m, r)$: $r \cdot G = C_1$ and $m \cdot G + r \cdot Y = C_2$
+#[cfg(feature = "alloc")]
+fn known_plaintext_relation<E: Curve>(
+    ek: &EncryptionKey<E>,
+    ciphertext: &Ciphertext<E>,
+) -> LinearRelation<E> {
+    let g = Point::generator().to_point();
+    LinearRelation::new(2)
+        .with_equation([(1, g)], ciphertext.c1)
+        .with_equation([(0, g), (1, ek.0)], ciphertext.c2)
+}
+
+/// Two ciphertexts under keys $Y, Y'$, and proof that they encrypt the same plaintext
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct EqualPlaintexts<E: Curve> {
+    /// Ciphertexts $(r \cdot G, M + r \cdot Y)$ and $(r' \cdot G, M + r' \cdot Y')$
+    pub ciphertexts: [Ciphertext<E>; 2],
+    /// Proof of knowledge of $r, r'$
+    pub proof: ProofBundle<E>,
+}
+
+/// Ciphertexts which proof of plaintext equality was verified
+///
+/// Can only be obtained via [`EqualPlaintexts::verify`]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiedEqualPlaintexts<E: Curve> {
+    ciphertexts: [Ciphertext<E>; 2],
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Curve> EqualPlaintexts<E> {
+    /// Encrypts `message` under both keys `eks`, and proves that ciphertexts encrypt the same
+    /// plaintext
+    ///
+    /// `randomness[k]` is used to encrypt under `eks[k]`. Keys may be equal. Relation and proof
+    /// commitments are appended to the `transcript`.
+    pub fn new<D: Digest + Clone, R: RngCore + CryptoRng>(
+        rng: &mut R,
+        transcript: Transcript<D>,
+        eks: [&EncryptionKey<E>; 2],
+        message: &Point<E>,
+        randomness: &[SecretScalar<E>; 2],
+    ) -> Self {
+        let ciphertexts =
+            [0, 1].map(|k| randomness[k].expose_secret(|r| eks[k].encrypt(message, r)));
+
+        let mut prover = BundleProver::new(transcript);
+        prover.add_satisfied(
+            rng,
+            equal_plaintexts_relation(eks, &ciphertexts),
+            randomness,
+        );
+        Self {
+            ciphertexts,
+            proof: prover.prove(),
+        }
+    }
+
+    /// Verifies the proof
+    ///
+    /// Transcript must be in the same state as it was at the moment of proving.
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        eks: [&EncryptionKey<E>; 2],
+    ) -> Result<VerifiedEqualPlaintexts<E>, InvalidProof> {
+        let relation = equal_plaintexts_relation(eks, &self.ciphertexts);
+        self.proof
+            .verify(transcript, rng, core::slice::from_ref(&relation))?;
+        Ok(VerifiedEqualPlaintexts {
+            ciphertexts: self.ciphertexts,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Curve> VerifiedEqualPlaintexts<E> {
+    /// Ciphertexts under keys $Y$ and $Y'$ respectively
+    pub fn ciphertexts(&self) -> [Ciphertext<E>; 2] {
+        self.ciphertexts
+    }
+}
+
+/// Relation on witness $(r, r')$: $r \cdot G = C_1$, $r' \cdot G = C'_1$, and
+/// $r \cdot Y - r' \cdot Y' = C_2 - C'_2$
+#[cfg(feature = "alloc")]
+fn equal_plaintexts_relation<E: Curve>(
+    eks: [&EncryptionKey<E>; 2],
+    ciphertexts: &[Ciphertext<E>; 2],
+) -> LinearRelation<E> {
+    let g = Point::generator().to_point();
+    LinearRelation::new(2)
+        .with_equation([(0, g)], ciphertexts[0].c1)
+        .with_equation([(1, g)], ciphertexts[1].c1)
+        .with_equation(
+            [(0, eks[0].0), (1, -eks[1].0)],
+            ciphertexts[0].c2 - ciphertexts[1].c2,
+        )
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    #[test]
+    fn encrypt_decrypt<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (dk, ek) = super::keygen::<E, _>(&mut rng);
+        assert_eq!(dk.encryption_key(), ek);
+
+        let message = Point::generator() * Scalar::random(&mut rng);
+        let ciphertext = ek.encrypt(&message, &Scalar::random(&mut rng));
+        assert_eq!(dk.decrypt(&ciphertext), message);
+
+        let (other_dk, _) = super::keygen::<E, _>(&mut rng);
+        assert_ne!(other_dk.decrypt(&ciphertext), message);
+    }
+
+    #[test]
+    fn homomorphism<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (dk, ek) = super::keygen::<E, _>(&mut rng);
+
+        let a = Scalar::random(&mut rng);
+        let b = Scalar::random(&mut rng);
+        let k = Scalar::random(&mut rng);
+        let enc_a = ek.encrypt_scalar(&a, &Scalar::random(&mut rng));
+        let enc_b = ek.encrypt_scalar(&b, &Scalar::random(&mut rng));
+
+        assert_eq!(dk.decrypt(&(enc_a + enc_b)), Point::generator() * (a + b));
+        assert_eq!(dk.decrypt(&(enc_a - enc_b)), Point::generator() * (a - b));
+        assert_eq!(dk.decrypt(&(enc_a * &k)), Point::generator() * (a * k));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn known_plaintext<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (dk, ek) = super::keygen::<E, _>(&mut rng);
+        let transcript = || crate::transcript::Transcript::<sha2::Sha256>::new(b"test");
+
+        let message = SecretScalar::random(&mut rng);
+        let randomness = SecretScalar::random(&mut rng);
+        let msg = super::KnownPlaintext::new(&mut rng, transcript(), &ek, &message, &randomness);
+        let verified = msg.verify(&mut transcript(), &mut rng, &ek).unwrap();
+        assert_eq!(
+            dk.decrypt(&verified.ciphertext()),
+            Point::generator() * &message
+        );
+
+        // Proof is bound to the ciphertext and the key
+        let mut tampered = msg.clone();
+        tampered.ciphertext.c2 += Point::generator();
+        assert!(tampered.verify(&mut transcript(), &mut rng, &ek).is_err());
+        let (_, other_ek) = super::keygen::<E, _>(&mut rng);
+        assert!(msg.verify(&mut transcript(), &mut rng, &other_ek).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn equal_plaintexts<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (dk1, ek1) = super::keygen::<E, _>(&mut rng);
+        let (dk2, ek2) = super::keygen::<E, _>(&mut rng);
+        let transcript = || crate::transcript::Transcript::<sha2::Sha256>::new(b"test");
+
+        let message = Point::generator() * Scalar::random(&mut rng);
+        let randomness = [(); 2].map(|_| SecretScalar::random(&mut rng));
+        let msg = super::EqualPlaintexts::new(
+            &mut rng,
+            transcript(),
+            [&ek1, &ek2],
+            &message,
+            &randomness,
+        );
+        let verified = msg
+            .verify(&mut transcript(), &mut rng, [&ek1, &ek2])
+            .unwrap();
+        let [c1, c2] = verified.ciphertexts();
+        assert_eq!(dk1.decrypt(&c1), message);
+        assert_eq!(dk2.decrypt(&c2), message);
+
+        // Ciphertexts encrypting different plaintexts are rejected
+        let mut tampered = msg.clone();
+        tampered.ciphertexts[1].c2 += Point::generator();
+        assert!(tampered
+            .verify(&mut transcript(), &mut rng, [&ek1, &ek2])
+            .is_err());
+
+        // Proof is bound to the order of keys
+        assert!(msg
+            .verify(&mut transcript(), &mut rng, [&ek2, &ek1])
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
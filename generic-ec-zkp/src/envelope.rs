@@ -0,0 +1,421 @@
+//! Versioned proof envelopes
+//!
+//! Proofs are serialized as bare structures: nothing in the serialized proof tells which proof it
+//! is, which curve it's defined over, or which version of the format it was encoded with. Changing
+//! the format of a proof therefore requires all parties to upgrade simultaneously, as old parties
+//! would misinterpret new proofs and vice versa.
+//!
+//! [`Envelope`] wraps a proof into a self-describing structure:
+//!
+//! ```json
+//! { "version": 1, "curve": "secp256k1", "kind": "generic-ec-zkp/schnorr_pok", "proof": ... }
+//! ```
+//!
+//! When the envelope is deserialized, the header is checked against [`ProofKind`] of the expected
+//! proof type. Envelopes with unknown version, another curve, or another kind of proof are rejected
+//! with a descriptive error instead of being misinterpreted. Header is serialized before the proof,
+//! so mismatched envelope is rejected before any attempt to parse the proof, unless the format
+//! reorders the fields (e.g. sorts keys alphabetically). Unknown fields are
+//! ignored, so future versions of the envelope may carry additional metadata.
+//!
+//! During a format upgrade, a party that understands several versions can deserialize
+//! [`Header`] first, and then parse the envelope into a proof type matching the
+//! [version](Header::version).
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{curves::Secp256k1, Scalar};
+//! use generic_ec_zkp::{envelope::Envelope, schnorr_pok};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let response = Scalar::random(&mut rng);
+//! let proof = schnorr_pok::Proof::<Secp256k1>(response);
+//! let serialized = serde_json::to_vec(&Envelope(proof))?;
+//!
+//! let Envelope(received) = serde_json::from_slice::<Envelope<schnorr_pok::Proof<Secp256k1>>>(&serialized)?;
+//! assert_eq!(received.0, response);
+//!
+//! // Envelope doesn't deserialize into a different kind of proof
+//! assert!(serde_json::from_slice::<Envelope<generic_ec_zkp::dv_schnorr_pok::Proof<Secp256k1>>>(&serialized).is_err());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use generic_ec::Curve;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Proof that can be put into [`Envelope`]
+pub trait ProofKind: Serialize + for<'de> Deserialize<'de> {
+    /// Curve the proof is defined over
+    type Curve: Curve;
+    /// Identifies the kind of the proof
+    ///
+    /// Must be unique among all proofs
+    const KIND: &'static str;
+    /// Version of the proof serialization format
+    ///
+    /// Must be incremented every time serialization format of the proof changes
+    const VERSION: u16;
+}
+
+/// Self-describing proof
+///
+/// Serialized proof is accompanied with its format version, curve, and kind. See
+/// [module-level docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Envelope<P>(pub P);
+
+impl<P> Envelope<P> {
+    /// Unwraps the proof
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P> From<P> for Envelope<P> {
+    fn from(proof: P) -> Self {
+        Self(proof)
+    }
+}
+
+const FIELDS: &[&str] = &["version", "curve", "kind", "proof"];
+
+impl<P: ProofKind> Serialize for Envelope<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Envelope", FIELDS.len())?;
+        s.serialize_field("version", &P::VERSION)?;
+        s.serialize_field("curve", <P::Curve as Curve>::CURVE_NAME)?;
+        s.serialize_field("kind", P::KIND)?;
+        s.serialize_field("proof", &self.0)?;
+        s.end()
+    }
+}
+
+impl<'de, P: ProofKind> Deserialize<'de> for Envelope<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("Envelope", FIELDS, EnvelopeVisitor(PhantomData))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Version,
+    Curve,
+    Kind,
+    Proof,
+    #[serde(other)]
+    Unknown,
+}
+
+struct EnvelopeVisitor<P>(PhantomData<P>);
+
+impl<P: ProofKind> EnvelopeVisitor<P> {
+    fn check_version<E: de::Error>(version: u16) -> Result<(), E> {
+        if version == P::VERSION {
+            Ok(())
+        } else {
+            Err(E::custom(UnsupportedEnvelope(Reason::Version {
+                actual: version,
+                expected: P::VERSION,
+            })))
+        }
+    }
+    fn check_curve<E: de::Error>(matches: bool) -> Result<(), E> {
+        if matches {
+            Ok(())
+        } else {
+            Err(E::custom(UnsupportedEnvelope(Reason::Curve {
+                expected: <P::Curve as Curve>::CURVE_NAME,
+            })))
+        }
+    }
+    fn check_kind<E: de::Error>(matches: bool) -> Result<(), E> {
+        if matches {
+            Ok(())
+        } else {
+            Err(E::custom(UnsupportedEnvelope(Reason::Kind {
+                expected: P::KIND,
+            })))
+        }
+    }
+}
+
+impl<'de, P: ProofKind> Visitor<'de> for EnvelopeVisitor<P> {
+    type Value = Envelope<P>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "envelope of `{}` proof", P::KIND)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let version = seq
+            .next_element::<u16>()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        Self::check_version(version)?;
+        let curve = seq
+            .next_element_seed(MatchStr(<P::Curve as Curve>::CURVE_NAME))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Self::check_curve(curve)?;
+        let kind = seq
+            .next_element_seed(MatchStr(P::KIND))?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        Self::check_kind(kind)?;
+        let proof = seq
+            .next_element::<P>()?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+        Ok(Envelope(proof))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut version = false;
+        let mut curve = false;
+        let mut kind = false;
+        let mut proof = None;
+
+        while let Some(field) = map.next_key::<Field>()? {
+            match field {
+                Field::Version if version => return Err(de::Error::duplicate_field("version")),
+                Field::Version => {
+                    Self::check_version(map.next_value()?)?;
+                    version = true;
+                }
+                Field::Curve if curve => return Err(de::Error::duplicate_field("curve")),
+                Field::Curve => {
+                    Self::check_curve(
+                        map.next_value_seed(MatchStr(<P::Curve as Curve>::CURVE_NAME))?,
+                    )?;
+                    curve = true;
+                }
+                Field::Kind if kind => return Err(de::Error::duplicate_field("kind")),
+                Field::Kind => {
+                    Self::check_kind(map.next_value_seed(MatchStr(P::KIND))?)?;
+                    kind = true;
+                }
+                Field::Proof if proof.is_some() => return Err(de::Error::duplicate_field("proof")),
+                Field::Proof => proof = Some(map.next_value::<P>()?),
+                Field::Unknown => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        if !version {
+            return Err(de::Error::missing_field("version"));
+        }
+        if !curve {
+            return Err(de::Error::missing_field("curve"));
+        }
+        if !kind {
+            return Err(de::Error::missing_field("kind"));
+        }
+        let proof = proof.ok_or_else(|| de::Error::missing_field("proof"))?;
+        Ok(Envelope(proof))
+    }
+}
+
+/// Deserializes a string and checks whether it equals to the expected one, without allocating
+struct MatchStr(&'static str);
+
+impl<'de> DeserializeSeed<'de> for MatchStr {
+    type Value = bool;
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for MatchStr {
+    type Value = bool;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("string")
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<bool, E> {
+        Ok(v == self.0)
+    }
+}
+
+/// Header of the [`Envelope`]
+///
+/// Can be deserialized from any envelope regardless of the proof it contains. Lets the party
+/// inspect the envelope before deciding how to parse the proof.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    /// Version of the proof serialization format
+    pub version: u16,
+    /// Name of the curve
+    pub curve: alloc::string::String,
+    /// Kind of the proof
+    pub kind: alloc::string::String,
+}
+
+#[cfg(feature = "alloc")]
+impl Header {
+    /// Checks whether the envelope with this header contains a proof `P`
+    pub fn check<P: ProofKind>(&self) -> Result<(), UnsupportedEnvelope> {
+        let curve = <P::Curve as Curve>::CURVE_NAME;
+        let reason = if self.version != P::VERSION {
+            Reason::Version {
+                actual: self.version,
+                expected: P::VERSION,
+            }
+        } else if self.curve != curve {
+            Reason::Curve { expected: curve }
+        } else if self.kind != P::KIND {
+            Reason::Kind { expected: P::KIND }
+        } else {
+            return Ok(());
+        };
+        Err(UnsupportedEnvelope(reason))
+    }
+}
+
+/// Envelope doesn't contain the expected proof
+#[derive(Debug, Clone, Copy)]
+pub struct UnsupportedEnvelope(Reason);
+
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    Version { actual: u16, expected: u16 },
+    Curve { expected: &'static str },
+    Kind { expected: &'static str },
+}
+
+impl UnsupportedEnvelope {
+    /// Indicates that envelope has unsupported version of the proof format
+    ///
+    /// Returns the version found in the envelope
+    pub fn unsupported_version(&self) -> Option<u16> {
+        match self.0 {
+            Reason::Version { actual, .. } => Some(actual),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for UnsupportedEnvelope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Reason::Version { actual, expected } => write!(
+                f,
+                "unsupported proof format version {actual} (expected {expected})"
+            ),
+            Reason::Curve { expected } => write!(f, "proof is not over {expected} curve"),
+            Reason::Kind { expected } => write!(f, "envelope doesn't contain `{expected}` proof"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnsupportedEnvelope {}
+
+macro_rules! proof_kind {
+    ($($(#[$attr:meta])* $proof:ident in $module:ident => $version:expr),+$(,)?) => {$(
+        $(#[$attr])*
+        impl<E: Curve> ProofKind for crate::$module::$proof<E> {
+            type Curve = E;
+            const KIND: &'static str = concat!("generic-ec-zkp/", stringify!($module));
+            const VERSION: u16 = $version;
+        }
+    )+};
+}
+
+proof_kind! {
+    Proof in schnorr_pok => 1,
+    Proof in dv_schnorr_pok => 1,
+    BitProof in bit_proof => 1,
+    #[cfg(feature = "alloc")]
+    Proof in representation_pok => 1,
+    #[cfg(feature = "alloc")]
+    InnerProductProof in ipa => 1,
+    #[cfg(feature = "alloc")]
+    RangeProof in range_proof => 1,
+    #[cfg(feature = "alloc")]
+    ProofBundle in proof_bundle => 1,
+    #[cfg(feature = "alloc")]
+    Proof in verifiable_encryption => 1,
+    #[cfg(feature = "alloc")]
+    NonceShare in nonce_share_pok => 1,
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, Scalar};
+    use rand_dev::DevRng;
+
+    use super::{Envelope, Header, ProofKind};
+    use crate::{dv_schnorr_pok, schnorr_pok};
+
+    #[test]
+    fn roundtrip<E: Curve>() {
+        let mut rng = DevRng::new();
+        let response = Scalar::random(&mut rng);
+
+        let json = serde_json::to_string(&Envelope(schnorr_pok::Proof::<E>(response))).unwrap();
+        let Envelope(parsed) =
+            serde_json::from_str::<Envelope<schnorr_pok::Proof<E>>>(&json).unwrap();
+        assert_eq!(parsed.0, response);
+
+        let header = serde_json::from_str::<Header>(&json).unwrap();
+        assert_eq!(header.version, 1);
+        assert_eq!(header.curve, E::CURVE_NAME);
+        assert_eq!(header.kind, "generic-ec-zkp/schnorr_pok");
+        header.check::<schnorr_pok::Proof<E>>().unwrap();
+        assert!(header.check::<dv_schnorr_pok::Proof<E>>().is_err());
+    }
+
+    #[test]
+    fn rejects_mismatched_header<E: Curve>() {
+        let mut rng = DevRng::new();
+        let proof = schnorr_pok::Proof::<E>(Scalar::random(&mut rng));
+        let mut json = serde_json::to_value(Envelope(proof)).unwrap();
+
+        // Unknown fields are ignored
+        json["comment"] = "added by future version".into();
+        serde_json::from_value::<Envelope<schnorr_pok::Proof<E>>>(json.clone()).unwrap();
+
+        // Version is checked before the proof is parsed
+        let future = format!(
+            r#"{{"version":{},"curve":"{}","kind":"{}","proof":"proof in a new format"}}"#,
+            schnorr_pok::Proof::<E>::VERSION + 1,
+            E::CURVE_NAME,
+            schnorr_pok::Proof::<E>::KIND,
+        );
+        let Err(err) = serde_json::from_str::<Envelope<schnorr_pok::Proof<E>>>(&future) else {
+            panic!("envelope of unknown version must be rejected")
+        };
+        let err = err.to_string();
+        assert!(err.contains("unsupported proof format version"), "{err}");
+        let header = serde_json::from_str::<Header>(&future).unwrap();
+        let err = header.check::<schnorr_pok::Proof<E>>().unwrap_err();
+        assert_eq!(
+            err.unsupported_version(),
+            Some(schnorr_pok::Proof::<E>::VERSION + 1)
+        );
+
+        let mut other_curve = json.clone();
+        other_curve["curve"] = "other curve".into();
+        assert!(serde_json::from_value::<Envelope<schnorr_pok::Proof<E>>>(other_curve).is_err());
+
+        assert!(
+            serde_json::from_value::<Envelope<dv_schnorr_pok::Proof<E>>>(json.clone()).is_err()
+        );
+
+        let mut missing = json;
+        missing.as_object_mut().unwrap().remove("kind");
+        assert!(serde_json::from_value::<Envelope<schnorr_pok::Proof<E>>>(missing).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
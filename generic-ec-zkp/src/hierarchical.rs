@@ -0,0 +1,469 @@
+//! Hierarchical threshold secret sharing
+//!
+//! Implements [Tassa] hierarchical secret sharing: parties are split into levels $0, \dots, m$
+//! (level 0 being the most privileged, e.g. directors, then managers, etc.), and the
+//! [`Hierarchy`] sets cumulative thresholds $k_0 < k_1 < \dots < k_m$. A set of parties is
+//! authorized to reconstruct the secret if, for every level $\ell$, it contains at least
+//! $k_\ell$ parties of levels $0, \dots, \ell$. For instance, thresholds $(1, 4)$ require at
+//! least 1 director and 4 parties in total.
+//!
+//! Disjunctive policies are obtained by sharing the same secret under several hierarchies,
+//! every party keeps its share from each dealing. Policy "2 directors OR 1 director + 3 managers"
+//! is a dealing with thresholds $(2)$ among the directors, plus a dealing with thresholds
+//! $(1, 4)$ among everyone. Dealings are independent, so sets that are unauthorized in every
+//! dealing learn nothing about the secret.
+//!
+//! Dealer publishes [Feldman commitments](crate::polynomial::Polynomial::commit) to the sharing
+//! polynomial, so every party can [verify](verify_share) its share.
+//!
+//! [Tassa]: https://www.openu.ac.il/lists/mediaserver_documents/personalsites/tamirtassa/hss_conf.pdf
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{hierarchical::{self, Hierarchy}, party_index::PartyIndex};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Parties 0, 1 are directors, parties 2, 3, 4, 5 are managers. At least one director and
+//! // four parties in total are needed to reconstruct the secret.
+//! let hierarchy = Hierarchy::new(vec![1, 4])?;
+//! let levels = [0, 0, 1, 1, 1, 1];
+//!
+//! let secret = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let dealing = hierarchical::deal(&mut rng, &hierarchy, &secret, &levels)?;
+//! for (j, share) in dealing.shares.iter().enumerate() {
+//!     let share = share.expose_secret(|s| *s);
+//!     hierarchical::verify_share(&hierarchy, &dealing.commitments, PartyIndex(j as u16), levels[j], &share)?;
+//! }
+//!
+//! // Director 1 and managers 2, 4, 5 reconstruct the secret
+//! let shares: Vec<(PartyIndex, u16, Scalar<_>)> = [1, 2, 4, 5]
+//!     .into_iter()
+//!     .map(|j| (PartyIndex(j), levels[usize::from(j)], dealing.shares[usize::from(j)].expose_secret(|s| *s)))
+//!     .collect();
+//! let reconstructed = hierarchical::reconstruct(&hierarchy, &shares)?;
+//! assert_eq!(Point::generator() * reconstructed, Point::generator() * &secret);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Dealer samples polynomial $f$ of degree $k_m - 1$ with $f(0) = s$. Party $j$ of level $\ell$
+//! receives share $f^{(k_{\ell - 1})}(j + 1)$, the derivative of order $k_{\ell - 1}$ of $f$ (with
+//! $k_{-1} = 0$), so parties of level 0 receive plain Shamir shares. Reconstruction solves a
+//! linear system for coefficients of $f$ (Birkhoff interpolation), which has a unique solution
+//! for authorized sets of $k_m$ parties. Solvability is guaranteed when share indexes grow with
+//! the level, so [`deal`] requires parties to be sorted by level.
+
+use alloc::vec::Vec;
+use core::{iter, ops};
+
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{party_index::PartyIndex, polynomial::Polynomial};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Hierarchical access structure: cumulative thresholds of the levels
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hierarchy {
+    thresholds: Vec<u16>,
+}
+
+impl Hierarchy {
+    /// Constructs a hierarchy from thresholds $k_0 < k_1 < \dots < k_m$
+    ///
+    /// Threshold $k_\ell$ is the amount of parties of levels $0, \dots, \ell$ needed to
+    /// reconstruct the secret. Returns error if thresholds are empty, $k_0 = 0$, or thresholds
+    /// are not strictly increasing.
+    pub fn new(thresholds: Vec<u16>) -> Result<Self, InvalidInput> {
+        if thresholds.first().is_none_or(|k_0| *k_0 == 0)
+            || thresholds.windows(2).any(|w| w[0] >= w[1])
+        {
+            return Err(InvalidInput);
+        }
+        Ok(Self { thresholds })
+    }
+
+    /// Thresholds $k_0, \dots, k_m$
+    pub fn thresholds(&self) -> &[u16] {
+        &self.thresholds
+    }
+
+    /// Amount of levels $m + 1$
+    pub fn levels(&self) -> usize {
+        self.thresholds.len()
+    }
+
+    /// Amount of parties $k_m$ needed to reconstruct the secret
+    pub fn threshold(&self) -> usize {
+        self.thresholds.last().map_or(0, |k| usize::from(*k))
+    }
+
+    /// Checks whether parties of given levels are authorized to reconstruct the secret
+    ///
+    /// Returns `false` if any level is out of range
+    pub fn is_authorized(&self, levels: &[u16]) -> bool {
+        levels.iter().all(|l| usize::from(*l) < self.levels())
+            && self.thresholds.iter().enumerate().all(|(l, k_l)| {
+                levels
+                    .iter()
+                    .filter(|level| usize::from(**level) <= l)
+                    .count()
+                    >= usize::from(*k_l)
+            })
+    }
+
+    /// Order of derivative of the sharing polynomial given to parties of `level`
+    fn derivative_order(&self, level: u16) -> Option<usize> {
+        match usize::from(level) {
+            0 => Some(0),
+            l => self.thresholds.get(l - 1).map(|k| usize::from(*k)),
+        }
+    }
+
+    /// Row of Birkhoff matrix for party `i` of `level`
+    ///
+    /// Share of the party is a linear combination of coefficients of the sharing polynomial,
+    /// the row contains the weights: $\frac{j!}{(j - d)!} x^{j - d}$ for $j \ge d$, and zero
+    /// otherwise, where $d$ is the derivative order and $x = i + 1$.
+    fn row<E: Curve>(&self, i: PartyIndex, level: u16) -> Option<Vec<Scalar<E>>> {
+        let d = self.derivative_order(level)?;
+        let x = i.share_index().to_scalar::<E>().into_inner();
+        let mut row = Vec::with_capacity(self.threshold());
+        let mut x_pow = Scalar::one();
+        for j in 0..self.threshold() {
+            if j < d {
+                row.push(Scalar::zero());
+            } else {
+                let falling_factorial = ((j - d + 1)..=j)
+                    .map(|m| Scalar::from(m as u64))
+                    .product::<Scalar<E>>();
+                row.push(falling_factorial * x_pow);
+                x_pow *= x;
+            }
+        }
+        Some(row)
+    }
+}
+
+/// Dealt shares along with commitments to the sharing polynomial
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Dealing<E: Curve> {
+    /// Commitments $C_j = a_j \cdot G$ to coefficients of the sharing polynomial
+    pub commitments: Polynomial<Point<E>>,
+    /// Secret shares of the parties, `shares[j]` is the share of party $j$
+    pub shares: Vec<SecretScalar<E>>,
+}
+
+/// Shares a secret among parties of given levels
+///
+/// `levels[j]` is the level of party $j$. Returns error if levels are not sorted in
+/// non-decreasing order, any level is out of range, parties are not authorized as a whole, or
+/// amount of parties exceeds `u16::MAX`.
+pub fn deal<E: Curve, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    hierarchy: &Hierarchy,
+    secret: &SecretScalar<E>,
+    levels: &[u16],
+) -> Result<Dealing<E>, InvalidInput> {
+    if levels.windows(2).any(|w| w[0] > w[1])
+        || levels.len() > usize::from(u16::MAX)
+        || !hierarchy.is_authorized(levels)
+    {
+        return Err(InvalidInput);
+    }
+    let f = Polynomial::sample_with_const_term(rng, hierarchy.threshold() - 1, secret.clone());
+    let commitments = f.commit(&Point::generator().to_point());
+
+    let shares = (0u16..)
+        .map(PartyIndex)
+        .zip(levels)
+        .map(|(i, level)| {
+            let row = hierarchy.row::<E>(i, *level).ok_or(InvalidInput)?;
+            let mut share = f
+                .coefs()
+                .iter()
+                .zip(&row)
+                .map(|(a_j, w_j)| a_j * w_j)
+                .sum::<Scalar<E>>();
+            Ok(SecretScalar::new(&mut share))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Dealing {
+        commitments,
+        shares,
+    })
+}
+
+/// Computes public share $S_i \cdot G$ of party `i` of `level` from dealer's commitments
+///
+/// Returns `None` if `level` is out of range, or amount of commitments doesn't match the
+/// hierarchy.
+pub fn public_share<E: Curve>(
+    hierarchy: &Hierarchy,
+    commitments: &Polynomial<Point<E>>,
+    i: PartyIndex,
+    level: u16,
+) -> Option<Point<E>> {
+    if commitments.coefs().len() != hierarchy.threshold() {
+        return None;
+    }
+    let row = hierarchy.row::<E>(i, level)?;
+    Some(Scalar::multiscalar_mul(row.iter().zip(commitments.coefs())))
+}
+
+/// Verifies share of party `i` of `level` against dealer's commitments
+pub fn verify_share<E: Curve>(
+    hierarchy: &Hierarchy,
+    commitments: &Polynomial<Point<E>>,
+    i: PartyIndex,
+    level: u16,
+    share: &Scalar<E>,
+) -> Result<(), InvalidShare> {
+    if public_share(hierarchy, commitments, i, level) == Some(Point::generator() * share) {
+        Ok(())
+    } else {
+        Err(InvalidShare)
+    }
+}
+
+/// Computes reconstruction coefficients $\lambda_j$ for given parties
+///
+/// Takes pairs of party index and its level. Secret is reconstructed as $s = \sum_j \lambda_j
+/// S_j$, `coefficients[j]` corresponds to `parties[j]`. When more than $k_m$ parties are given,
+/// only $k_m$ parties of the lowest levels are used, others get zero coefficients.
+///
+/// Returns error if parties are not authorized, or indexes are not distinct.
+pub fn reconstruction_coefficients<E: Curve>(
+    hierarchy: &Hierarchy,
+    parties: &[(PartyIndex, u16)],
+) -> Result<Vec<Scalar<E>>, InvalidInput> {
+    let levels = parties.iter().map(|(_, level)| *level).collect::<Vec<_>>();
+    if !hierarchy.is_authorized(&levels) {
+        return Err(InvalidInput);
+    }
+    let k = hierarchy.threshold();
+    let mut chosen = (0..parties.len()).collect::<Vec<_>>();
+    chosen.sort_by_key(|j| parties[*j].1);
+    chosen.truncate(k);
+
+    // Share S_j = <row_j, a>, so s = a_0 = <lambda, S> iff M^T lambda = e_0, where M is
+    // the Birkhoff matrix made of rows of the chosen parties
+    let mut system = (0..k)
+        .map(|_| Vec::with_capacity(k + 1))
+        .collect::<Vec<Vec<Scalar<E>>>>();
+    for j in &chosen {
+        let (i, level) = parties[*j];
+        let row = hierarchy.row::<E>(i, level).ok_or(InvalidInput)?;
+        for (equation, m_ij) in system.iter_mut().zip(row) {
+            equation.push(m_ij);
+        }
+    }
+    for (n, equation) in system.iter_mut().enumerate() {
+        equation.push(if n == 0 {
+            Scalar::one()
+        } else {
+            Scalar::zero()
+        });
+    }
+    let solution = solve(system).ok_or(InvalidInput)?;
+
+    let mut coefficients = alloc::vec![Scalar::zero(); parties.len()];
+    for (j, lambda_j) in chosen.into_iter().zip(solution) {
+        coefficients[j] = lambda_j;
+    }
+    Ok(coefficients)
+}
+
+/// Reconstructs the secret from shares of authorized set of parties
+///
+/// Takes triplets of party index, its level, and its share. Values can be scalars (secret
+/// shares) or points (public shares). Shares must be [verified](verify_share) beforehand.
+/// Returns error in the same cases as [`reconstruction_coefficients`].
+pub fn reconstruct<E: Curve, V>(
+    hierarchy: &Hierarchy,
+    shares: &[(PartyIndex, u16, V)],
+) -> Result<V, InvalidInput>
+where
+    V: Copy + iter::Sum,
+    Scalar<E>: ops::Mul<V, Output = V>,
+{
+    let parties = shares
+        .iter()
+        .map(|(i, level, _)| (*i, *level))
+        .collect::<Vec<_>>();
+    let coefficients = reconstruction_coefficients::<E>(hierarchy, &parties)?;
+    Ok(coefficients
+        .into_iter()
+        .zip(shares)
+        .map(|(lambda_j, (_, _, share_j))| lambda_j * *share_j)
+        .sum())
+}
+
+/// Solves linear system given as augmented square matrix via Gaussian elimination
+///
+/// Returns `None` if the system is singular. Not constant time: only public values are expected.
+fn solve<E: Curve>(mut system: Vec<Vec<Scalar<E>>>) -> Option<Vec<Scalar<E>>> {
+    let n = system.len();
+    for col in 0..n {
+        let pivot = (col..n).find(|row| system[*row][col] != Scalar::zero())?;
+        system.swap(col, pivot);
+        let inv = system[col][col].invert()?;
+        for value in &mut system[col][col..] {
+            *value *= inv;
+        }
+        let pivot_row = system[col].clone();
+        for (row, equation) in system.iter_mut().enumerate() {
+            if row == col {
+                continue;
+            }
+            let factor = equation[col];
+            for (value, pivot_value) in equation[col..].iter_mut().zip(&pivot_row[col..]) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+    Some(system.into_iter().map(|equation| equation[n]).collect())
+}
+
+/// Invalid input error
+///
+/// Returned when hierarchy, levels, or party indexes are invalid, or parties are not authorized
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid hierarchy, levels, or party indexes")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Share doesn't match dealer's commitments
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidShare;
+
+impl core::fmt::Display for InvalidShare {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("share doesn't match commitments")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidShare {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::Hierarchy;
+    use crate::party_index::PartyIndex;
+
+    #[test]
+    fn hierarchy_validation<E: Curve>() {
+        assert!(Hierarchy::new(alloc::vec![]).is_err());
+        assert!(Hierarchy::new(alloc::vec![0, 2]).is_err());
+        assert!(Hierarchy::new(alloc::vec![2, 2]).is_err());
+        let hierarchy = Hierarchy::new(alloc::vec![1, 4]).unwrap();
+
+        assert!(hierarchy.is_authorized(&[0, 1, 1, 1]));
+        assert!(hierarchy.is_authorized(&[0, 0, 1, 1, 1]));
+        assert!(!hierarchy.is_authorized(&[1, 1, 1, 1]));
+        assert!(!hierarchy.is_authorized(&[0, 0, 1]));
+        assert!(!hierarchy.is_authorized(&[0, 1, 1, 2]));
+
+        let parties = [(PartyIndex(0), 0), (PartyIndex(1), 1), (PartyIndex(2), 1)];
+        assert!(super::reconstruction_coefficients::<E>(&hierarchy, &parties).is_err());
+        // Indexes must be distinct
+        let parties = [
+            (PartyIndex(0), 0),
+            (PartyIndex(0), 0),
+            (PartyIndex(1), 1),
+            (PartyIndex(2), 1),
+        ];
+        assert!(super::reconstruction_coefficients::<E>(&hierarchy, &parties).is_err());
+    }
+
+    #[test]
+    fn deal_and_reconstruct<E: Curve>() {
+        let mut rng = DevRng::new();
+        let hierarchy = Hierarchy::new(alloc::vec![2, 3, 5]).unwrap();
+        let levels = [0, 0, 0, 1, 1, 2, 2, 2];
+
+        let secret = SecretScalar::<E>::random(&mut rng);
+        let dealing = super::deal(&mut rng, &hierarchy, &secret, &levels).unwrap();
+        let shares: Vec<(PartyIndex, u16, Scalar<E>)> = (0u16..)
+            .zip(&levels)
+            .zip(&dealing.shares)
+            .map(|((j, level), share)| (PartyIndex(j), *level, share.expose_secret(|s| *s)))
+            .collect();
+        for (i, level, share) in &shares {
+            super::verify_share(&hierarchy, &dealing.commitments, *i, *level, share).unwrap();
+        }
+        let invalid = shares[3].2 + Scalar::one();
+        assert!(
+            super::verify_share(&hierarchy, &dealing.commitments, PartyIndex(3), 1, &invalid)
+                .is_err()
+        );
+
+        let expected = Point::generator() * &secret;
+        for subset in [
+            &[0, 1, 3, 6, 7][..],
+            &[0, 1, 2, 3, 4],
+            &[1, 2, 4, 6, 7],
+            &[0, 3, 1, 7, 5, 6, 4],
+        ] {
+            let subset_shares: Vec<_> = subset.iter().map(|j| shares[*j]).collect();
+            let reconstructed = super::reconstruct(&hierarchy, &subset_shares).unwrap();
+            assert_eq!(Point::generator() * reconstructed, expected);
+
+            // Public shares reconstruct the public key
+            let public_shares: Vec<_> = subset_shares
+                .iter()
+                .map(|(i, level, share)| (*i, *level, Point::generator() * share))
+                .collect();
+            assert_eq!(
+                super::reconstruct(&hierarchy, &public_shares).unwrap(),
+                expected
+            );
+        }
+
+        // Unauthorized sets: not enough parties of levels 0 or 0..=1, or in total
+        for subset in [&[0, 3, 5, 6, 7][..], &[0, 1, 5, 6, 7], &[0, 1, 2, 3]] {
+            let subset_shares: Vec<_> = subset.iter().map(|j| shares[*j]).collect();
+            assert!(super::reconstruct(&hierarchy, &subset_shares).is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_dealing_input<E: Curve>() {
+        let mut rng = DevRng::new();
+        let hierarchy = Hierarchy::new(alloc::vec![1, 3]).unwrap();
+        let secret = SecretScalar::<E>::random(&mut rng);
+        // Unsorted levels, level out of range, and unauthorized parties
+        for levels in [&[1, 0, 1][..], &[0, 1, 2], &[0, 1]] {
+            assert!(super::deal(&mut rng, &hierarchy, &secret, levels).is_err());
+        }
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+    #[instantiate_tests(<generic_ec::curves::Ed25519>)]
+    mod ed25519 {}
+}
@@ -0,0 +1,762 @@
+//! Hybrid public key encryption (HPKE) in base mode
+//!
+//! Implements DHKEM and HPKE base mode as defined in [RFC 9180] with HKDF-SHA256 key
+//! derivation, keyed by generic-ec points: receiver key pair is a [`NonZero<SecretScalar<E>>`]
+//! and a [`NonZero<Point<E>>`]. Supported KEMs:
+//!
+//! | Curve       | KEM                         | `kem_id` |
+//! |-------------|-----------------------------|----------|
+//! | `Secp256r1` | DHKEM(P-256, HKDF-SHA256)   | `0x0010` |
+//! | `Ed25519`   | DHKEM(X25519, HKDF-SHA256)  | `0x0020` |
+//!
+//! X25519 keys are represented by points on the birationally equivalent
+//! [`Ed25519`](generic_ec::curves::Ed25519) curve and clamped secret scalars. Public keys that
+//! don't lie in the prime-order subgroup are rejected.
+//!
+//! AEAD is provided by the caller via [`Aead`] trait, so any implementation of AES-GCM or
+//! ChaCha20-Poly1305 can be plugged in. [`ExportOnly`] AEAD can be used when HPKE is only
+//! needed to derive shared secrets via [`Context::export`].
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{curves::Secp256r1, NonZero, Point, SecretScalar};
+//! use generic_ec_zkp::hpke::{self, ExportOnly};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Receiver key pair
+//! let sk_r = NonZero::<SecretScalar<Secp256r1>>::random(&mut rng);
+//! let pk_r = Point::generator() * &sk_r;
+//!
+//! // Sender sets up the context and sends `enc` to receiver
+//! let (enc, sender) = hpke::setup_base_s::<_, ExportOnly>(&mut rng, &pk_r, b"app info")?;
+//! // Receiver sets up the matching context
+//! let receiver = hpke::setup_base_r::<_, ExportOnly>(&enc, &sk_r, b"app info")?;
+//!
+//! assert_eq!(
+//!     sender.export(b"context", 32)?,
+//!     receiver.export(b"context", 32)?,
+//! );
+//! # Ok::<(), hpke::HpkeError>(())
+//! ```
+//!
+//! [RFC 9180]: https://www.rfc-editor.org/rfc/rfc9180.html
+
+use alloc::vec::Vec;
+
+use digest::Output;
+use generic_ec::{errors::InvalidPoint, Curve, NonZero, Point, SecretScalar};
+use hkdf::{Hkdf, HkdfExtract};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+/// Hash function underlying HKDF-SHA256
+type Kdf = sha2::Sha256;
+
+/// `kdf_id` of HKDF-SHA256
+const KDF_ID: u16 = 0x0001;
+
+/// Curve that can be used as DHKEM
+///
+/// The trait is sealed and implemented for curves listed in [module docs](self).
+pub trait DhKem: Curve + sealed::Sealed {
+    /// `kem_id` of DHKEM defined over the curve
+    const KEM_ID: u16;
+}
+
+impl DhKem for generic_ec::curves::Secp256r1 {
+    const KEM_ID: u16 = 0x0010;
+}
+
+impl DhKem for generic_ec::curves::Ed25519 {
+    const KEM_ID: u16 = 0x0020;
+}
+
+mod sealed {
+    use alloc::vec::Vec;
+
+    use generic_ec::{
+        curves::{Ed25519, Secp256r1},
+        Curve, NonZero, Point, Scalar, SecretScalar,
+    };
+    use zeroize::{Zeroize, Zeroizing};
+
+    use super::{HpkeError, Reason};
+
+    /// Curve-specific part of DHKEM
+    pub trait Sealed: Curve {
+        /// `SerializePublicKey`
+        fn serialize_public_key(pk: &NonZero<Point<Self>>) -> Vec<u8>;
+        /// `DeserializePublicKey`, identity point is rejected
+        fn deserialize_public_key(bytes: &[u8]) -> Option<NonZero<Point<Self>>>;
+        /// Serialized output of `DH()` given the shared point
+        fn dh_output(shared: &NonZero<Point<Self>>) -> Zeroizing<Vec<u8>>;
+        /// Secret key derivation from `dkp_prk` in `DeriveKeyPair`
+        fn derive_secret_key(
+            dkp_prk: &[u8],
+            suite_id: &[u8],
+        ) -> Result<NonZero<SecretScalar<Self>>, HpkeError>;
+    }
+
+    impl Sealed for Secp256r1 {
+        fn serialize_public_key(pk: &NonZero<Point<Self>>) -> Vec<u8> {
+            pk.to_bytes(false).to_vec()
+        }
+
+        fn deserialize_public_key(bytes: &[u8]) -> Option<NonZero<Point<Self>>> {
+            if bytes.len() != Point::<Self>::serialized_len(false) {
+                return None;
+            }
+            NonZero::from_point(Point::from_bytes(bytes).ok()?)
+        }
+
+        fn dh_output(shared: &NonZero<Point<Self>>) -> Zeroizing<Vec<u8>> {
+            // DH output is x coordinate of the point, which is compressed SEC1 encoding without
+            // the first byte
+            Zeroizing::new(shared.to_bytes(true).get(1..).unwrap_or_default().to_vec())
+        }
+
+        fn derive_secret_key(
+            dkp_prk: &[u8],
+            suite_id: &[u8],
+        ) -> Result<NonZero<SecretScalar<Self>>, HpkeError> {
+            let sk_len = Scalar::<Self>::serialized_len();
+            for counter in 0..=255u8 {
+                let mut bytes = Zeroizing::new(super::labeled_expand(
+                    dkp_prk,
+                    suite_id,
+                    b"candidate",
+                    &[&[counter]],
+                    sk_len,
+                )?);
+                if let Ok(mut sk) = Scalar::<Self>::from_be_bytes(&*bytes) {
+                    bytes.zeroize();
+                    let sk = SecretScalar::new(&mut sk);
+                    if let Some(sk) = NonZero::from_secret_scalar(sk) {
+                        return Ok(sk);
+                    }
+                }
+            }
+            Err(HpkeError(Reason::DeriveKeyPair))
+        }
+    }
+
+    impl Sealed for Ed25519 {
+        fn serialize_public_key(pk: &NonZero<Point<Self>>) -> Vec<u8> {
+            pk.to_x25519_bytes().to_vec()
+        }
+
+        fn deserialize_public_key(bytes: &[u8]) -> Option<NonZero<Point<Self>>> {
+            let bytes: &[u8; 32] = bytes.try_into().ok()?;
+            NonZero::from_point(Point::from_x25519_bytes(bytes).ok()?)
+        }
+
+        fn dh_output(shared: &NonZero<Point<Self>>) -> Zeroizing<Vec<u8>> {
+            Zeroizing::new(shared.to_x25519_bytes().to_vec())
+        }
+
+        fn derive_secret_key(
+            dkp_prk: &[u8],
+            suite_id: &[u8],
+        ) -> Result<NonZero<SecretScalar<Self>>, HpkeError> {
+            let mut bytes = Zeroizing::new([0u8; 32]);
+            super::labeled_expand_into(dkp_prk, suite_id, b"sk", &[], &mut *bytes)?;
+            NonZero::from_secret_scalar(SecretScalar::clamp_ed25519(&bytes))
+                .ok_or(HpkeError(Reason::DeriveKeyPair))
+        }
+    }
+}
+
+/// Authenticated encryption used by HPKE
+///
+/// Implement this trait for AEAD of your choice, e.g. AES-128-GCM (`aead_id = 0x0001`) or
+/// ChaCha20-Poly1305 (`aead_id = 0x0003`).
+pub trait Aead {
+    /// `aead_id` as defined in RFC 9180
+    const AEAD_ID: u16;
+    /// Size of a key in bytes, $N_k$
+    const KEY_LEN: usize;
+    /// Size of a nonce in bytes, $N_n$
+    const NONCE_LEN: usize;
+
+    /// Encrypts and authenticates `plaintext` and `aad`
+    ///
+    /// Returns `None` if encryption is not supported
+    fn seal(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Option<Vec<u8>>;
+    /// Decrypts `ciphertext` and checks its authenticity
+    ///
+    /// Returns `None` if ciphertext is not authentic
+    fn open(key: &[u8], nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// Export-only AEAD (`aead_id = 0xFFFF`)
+///
+/// Context only supports [secret export](Context::export), sealing and opening always fail.
+#[derive(Debug, Clone, Copy)]
+pub struct ExportOnly;
+
+impl Aead for ExportOnly {
+    const AEAD_ID: u16 = 0xFFFF;
+    const KEY_LEN: usize = 0;
+    const NONCE_LEN: usize = 0;
+
+    fn seal(_key: &[u8], _nonce: &[u8], _aad: &[u8], _plaintext: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+    fn open(_key: &[u8], _nonce: &[u8], _aad: &[u8], _ciphertext: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// Encapsulated key `enc`, ephemeral public key of the sender
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncappedKey<E: Curve>(pub NonZero<Point<E>>);
+
+impl<E: DhKem> EncappedKey<E> {
+    /// Serializes encapsulated key as specified by `SerializePublicKey`
+    ///
+    /// P-256 keys are encoded as uncompressed points, X25519 keys as 32 bytes $u$ coordinate
+    pub fn to_bytes(&self) -> Vec<u8> {
+        E::serialize_public_key(&self.0)
+    }
+
+    /// Deserializes encapsulated key, identity point is rejected
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InvalidPoint> {
+        E::deserialize_public_key(bytes)
+            .map(Self)
+            .ok_or(InvalidPoint)
+    }
+}
+
+/// KEM shared secret
+pub type SharedSecret = Zeroizing<Output<Kdf>>;
+
+/// KEM key pair: secret key and corresponding public key
+pub type KeyPair<E> = (NonZero<SecretScalar<E>>, NonZero<Point<E>>);
+
+/// Derives receiver key pair from input keying material `ikm` (`DeriveKeyPair`)
+///
+/// `ikm` must have at least as much entropy as the secret key, i.e. 32 bytes for both P-256 and
+/// X25519
+pub fn derive_key_pair<E: DhKem>(ikm: &[u8]) -> Result<KeyPair<E>, HpkeError> {
+    let suite_id = kem_suite_id::<E>();
+    let dkp_prk = labeled_extract(&suite_id, b"", b"dkp_prk", &[ikm]);
+    let sk = E::derive_secret_key(&dkp_prk, &suite_id)?;
+    let pk = Point::generator() * &sk;
+    Ok((sk, pk))
+}
+
+/// Generates ephemeral key pair and encapsulates a shared secret to receiver public key `pk_r`
+pub fn encap<E: DhKem>(
+    rng: &mut (impl RngCore + CryptoRng),
+    pk_r: &NonZero<Point<E>>,
+) -> Result<(SharedSecret, EncappedKey<E>), HpkeError> {
+    let sk_e = NonZero::<SecretScalar<E>>::random(rng);
+    encap_with_ephemeral_secret(&sk_e, pk_r)
+}
+
+/// Encapsulates a shared secret to receiver public key `pk_r` using given ephemeral secret key
+///
+/// Ephemeral secret key must be fresh for every encapsulation, prefer [`encap`] which samples
+/// it. This function is useful for testing against known answers, when ephemeral key is
+/// obtained via [`derive_key_pair`].
+pub fn encap_with_ephemeral_secret<E: DhKem>(
+    sk_e: &NonZero<SecretScalar<E>>,
+    pk_r: &NonZero<Point<E>>,
+) -> Result<(SharedSecret, EncappedKey<E>), HpkeError> {
+    let enc = Point::generator() * sk_e;
+    let dh = *pk_r * sk_e;
+    let shared_secret = extract_and_expand::<E>(&dh, &enc, pk_r)?;
+    Ok((shared_secret, EncappedKey(enc)))
+}
+
+/// Decapsulates a shared secret from `enc` using receiver secret key `sk_r`
+pub fn decap<E: DhKem>(
+    enc: &EncappedKey<E>,
+    sk_r: &NonZero<SecretScalar<E>>,
+) -> Result<SharedSecret, HpkeError> {
+    let pk_r = Point::generator() * sk_r;
+    let dh = enc.0 * sk_r;
+    extract_and_expand::<E>(&dh, &enc.0, &pk_r)
+}
+
+/// Sets up sender context in base mode (`SetupBaseS`)
+///
+/// Returns encapsulated key that needs to be sent to the receiver along with the context
+pub fn setup_base_s<E: DhKem, A: Aead>(
+    rng: &mut (impl RngCore + CryptoRng),
+    pk_r: &NonZero<Point<E>>,
+    info: &[u8],
+) -> Result<(EncappedKey<E>, Context<A>), HpkeError> {
+    let (shared_secret, enc) = encap(rng, pk_r)?;
+    Ok((enc, key_schedule_base::<E, A>(&shared_secret, info)?))
+}
+
+/// Sets up receiver context in base mode (`SetupBaseR`)
+pub fn setup_base_r<E: DhKem, A: Aead>(
+    enc: &EncappedKey<E>,
+    sk_r: &NonZero<SecretScalar<E>>,
+    info: &[u8],
+) -> Result<Context<A>, HpkeError> {
+    let shared_secret = decap(enc, sk_r)?;
+    key_schedule_base::<E, A>(&shared_secret, info)
+}
+
+/// Encrypts a single message to receiver public key `pk_r` (single-shot `Seal` in base mode)
+pub fn seal_base<E: DhKem, A: Aead>(
+    rng: &mut (impl RngCore + CryptoRng),
+    pk_r: &NonZero<Point<E>>,
+    info: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<(EncappedKey<E>, Vec<u8>), HpkeError> {
+    let (enc, mut context) = setup_base_s::<E, A>(rng, pk_r, info)?;
+    let ciphertext = context.seal(aad, plaintext)?;
+    Ok((enc, ciphertext))
+}
+
+/// Decrypts a single message (single-shot `Open` in base mode)
+pub fn open_base<E: DhKem, A: Aead>(
+    enc: &EncappedKey<E>,
+    sk_r: &NonZero<SecretScalar<E>>,
+    info: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, HpkeError> {
+    setup_base_r::<E, A>(enc, sk_r, info)?.open(aad, ciphertext)
+}
+
+/// Encryption context established between sender and receiver
+///
+/// Messages must be opened in the same order as they were sealed: every message is encrypted
+/// with a nonce derived from its sequence number.
+pub struct Context<A: Aead> {
+    key: Zeroizing<Vec<u8>>,
+    base_nonce: Zeroizing<Vec<u8>>,
+    exporter_secret: Zeroizing<Output<Kdf>>,
+    suite_id: [u8; 10],
+    seq: u64,
+    _aead: core::marker::PhantomData<A>,
+}
+
+impl<A: Aead> Context<A> {
+    /// Encrypts the message, increments sequence number
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = self.compute_nonce();
+        let ciphertext =
+            A::seal(&self.key, &nonce, aad, plaintext).ok_or(HpkeError(Reason::Seal))?;
+        self.increment_seq()?;
+        Ok(ciphertext)
+    }
+
+    /// Decrypts the message, increments sequence number
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let nonce = self.compute_nonce();
+        let plaintext =
+            A::open(&self.key, &nonce, aad, ciphertext).ok_or(HpkeError(Reason::Open))?;
+        self.increment_seq()?;
+        Ok(plaintext)
+    }
+
+    /// Exports a secret of `len` bytes bound to `exporter_context`
+    ///
+    /// Returns error if `len` is larger than $255 \cdot 32$
+    pub fn export(&self, exporter_context: &[u8], len: usize) -> Result<Vec<u8>, HpkeError> {
+        labeled_expand(
+            &self.exporter_secret,
+            &self.suite_id,
+            b"sec",
+            &[exporter_context],
+            len,
+        )
+    }
+
+    /// Sequence number of the next message
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    fn compute_nonce(&self) -> Zeroizing<Vec<u8>> {
+        let mut nonce = self.base_nonce.clone();
+        nonce
+            .iter_mut()
+            .rev()
+            .zip(self.seq.to_le_bytes())
+            .for_each(|(nonce_i, seq_i)| *nonce_i ^= seq_i);
+        nonce
+    }
+
+    fn increment_seq(&mut self) -> Result<(), HpkeError> {
+        self.seq = self
+            .seq
+            .checked_add(1)
+            .ok_or(HpkeError(Reason::MessageLimitReached))?;
+        Ok(())
+    }
+}
+
+/// HPKE error
+#[derive(Debug, Clone, Copy)]
+pub struct HpkeError(Reason);
+
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    DeriveKeyPair,
+    Expand,
+    Seal,
+    Open,
+    MessageLimitReached,
+}
+
+impl core::fmt::Display for HpkeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Reason::DeriveKeyPair => f.write_str("couldn't derive key pair"),
+            Reason::Expand => f.write_str("requested output is too long"),
+            Reason::Seal => f.write_str("encryption failed"),
+            Reason::Open => f.write_str("decryption failed"),
+            Reason::MessageLimitReached => f.write_str("message limit reached"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HpkeError {}
+
+fn kem_suite_id<E: DhKem>() -> [u8; 5] {
+    let [hi, lo] = E::KEM_ID.to_be_bytes();
+    [b'K', b'E', b'M', hi, lo]
+}
+
+/// `ExtractAndExpand` of DHKEM, `kem_context = enc || pkRm`
+fn extract_and_expand<E: DhKem>(
+    dh: &NonZero<Point<E>>,
+    enc: &NonZero<Point<E>>,
+    pk_r: &NonZero<Point<E>>,
+) -> Result<SharedSecret, HpkeError> {
+    let suite_id = kem_suite_id::<E>();
+    let dh = E::dh_output(dh);
+    let eae_prk = labeled_extract(&suite_id, b"", b"eae_prk", &[&dh]);
+    let mut shared_secret = Zeroizing::new(Output::<Kdf>::default());
+    labeled_expand_into(
+        &eae_prk,
+        &suite_id,
+        b"shared_secret",
+        &[
+            &E::serialize_public_key(enc),
+            &E::serialize_public_key(pk_r),
+        ],
+        &mut shared_secret,
+    )?;
+    Ok(shared_secret)
+}
+
+/// `KeySchedule` in base mode
+fn key_schedule_base<E: DhKem, A: Aead>(
+    shared_secret: &SharedSecret,
+    info: &[u8],
+) -> Result<Context<A>, HpkeError> {
+    let mut suite_id = [0u8; 10];
+    suite_id[..4].copy_from_slice(b"HPKE");
+    suite_id[4..6].copy_from_slice(&E::KEM_ID.to_be_bytes());
+    suite_id[6..8].copy_from_slice(&KDF_ID.to_be_bytes());
+    suite_id[8..].copy_from_slice(&A::AEAD_ID.to_be_bytes());
+
+    let psk_id_hash = labeled_extract(&suite_id, b"", b"psk_id_hash", &[]);
+    let info_hash = labeled_extract(&suite_id, b"", b"info_hash", &[info]);
+    let key_schedule_context: &[&[u8]] = &[&[0x00], &psk_id_hash, &info_hash];
+
+    let secret = labeled_extract(&suite_id, shared_secret, b"secret", &[]);
+    let mut key = Zeroizing::new(alloc::vec![0u8; A::KEY_LEN]);
+    labeled_expand_into(&secret, &suite_id, b"key", key_schedule_context, &mut key)?;
+    let mut base_nonce = Zeroizing::new(alloc::vec![0u8; A::NONCE_LEN]);
+    labeled_expand_into(
+        &secret,
+        &suite_id,
+        b"base_nonce",
+        key_schedule_context,
+        &mut base_nonce,
+    )?;
+    let mut exporter_secret = Zeroizing::new(Output::<Kdf>::default());
+    labeled_expand_into(
+        &secret,
+        &suite_id,
+        b"exp",
+        key_schedule_context,
+        &mut exporter_secret,
+    )?;
+
+    Ok(Context {
+        key,
+        base_nonce,
+        exporter_secret,
+        suite_id,
+        seq: 0,
+        _aead: core::marker::PhantomData,
+    })
+}
+
+/// `LabeledExtract(salt, label, ikm)`, `ikm` is given as concatenation of several parts
+fn labeled_extract(
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &[u8],
+    ikm: &[&[u8]],
+) -> Zeroizing<Output<Kdf>> {
+    let mut extract = HkdfExtract::<Kdf>::new(Some(salt));
+    for part in [&b"HPKE-v1"[..], suite_id, label]
+        .into_iter()
+        .chain(ikm.iter().copied())
+    {
+        extract.input_ikm(part);
+    }
+    Zeroizing::new(extract.finalize().0)
+}
+
+/// `LabeledExpand(prk, label, info, len)`
+fn labeled_expand(
+    prk: &[u8],
+    suite_id: &[u8],
+    label: &[u8],
+    info: &[&[u8]],
+    len: usize,
+) -> Result<Vec<u8>, HpkeError> {
+    let mut out = alloc::vec![0u8; len];
+    labeled_expand_into(prk, suite_id, label, info, &mut out)?;
+    Ok(out)
+}
+
+/// `LabeledExpand` writing output into `out`
+///
+/// Returns error if `out.len()` is larger than $255 \cdot 32$
+fn labeled_expand_into(
+    prk: &[u8],
+    suite_id: &[u8],
+    label: &[u8],
+    info: &[&[u8]],
+    out: &mut [u8],
+) -> Result<(), HpkeError> {
+    let len = u16::try_from(out.len())
+        .or(Err(HpkeError(Reason::Expand)))?
+        .to_be_bytes();
+    let labeled_info: Vec<&[u8]> = [&len[..], b"HPKE-v1", suite_id, label]
+        .into_iter()
+        .chain(info.iter().copied())
+        .collect();
+
+    Hkdf::<Kdf>::from_prk(prk)
+        .or(Err(HpkeError(Reason::Expand)))?
+        .expand_multi_info(&labeled_info, out)
+        .or(Err(HpkeError(Reason::Expand)))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{
+        curves::{Ed25519, Secp256r1},
+        NonZero, Point, SecretScalar,
+    };
+    use rand_dev::DevRng;
+
+    use super::{Aead, EncappedKey, ExportOnly};
+
+    /// Insecure AEAD which reveals key and nonce, has the same parameters as AES-128-GCM
+    struct Transparent;
+
+    impl Aead for Transparent {
+        const AEAD_ID: u16 = 0x0001;
+        const KEY_LEN: usize = 16;
+        const NONCE_LEN: usize = 12;
+
+        fn seal(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Option<Vec<u8>> {
+            Some([key, nonce, aad, plaintext].concat())
+        }
+        fn open(key: &[u8], nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+            let header = [key, nonce, aad].concat();
+            ciphertext.strip_prefix(&header[..]).map(|pt| pt.to_vec())
+        }
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// Test vector from RFC 9180, A.3.1: DHKEM(P-256, HKDF-SHA256), HKDF-SHA256, AES-128-GCM
+    #[test]
+    fn rfc9180_p256_test_vector() {
+        let ikm_e = hex("4270e54ffd08d79d5928020af4686d8f6b7d35dbe470265f1f5aa22816ce860e");
+        let ikm_r = hex("668b37171f1072f3cf12ea8a236a45df23fc13b82af3609ad1e354f6ef817550");
+        let info = hex("4f6465206f6e2061204772656369616e2055726e");
+
+        let (sk_e, pk_e) = super::derive_key_pair::<Secp256r1>(&ikm_e).unwrap();
+        let (sk_r, pk_r) = super::derive_key_pair::<Secp256r1>(&ikm_r).unwrap();
+        assert_eq!(
+            sk_r.expose_secret(|x| x.to_be_bytes().to_vec()),
+            hex("f3ce7fdae57e1a310d87f1ebbde6f328be0a99cdbcadf4d6589cf29de4b8ffd2")
+        );
+        assert_eq!(
+            pk_r.to_bytes(false).to_vec(),
+            hex("04fe8c19ce0905191ebc298a9245792531f26f0cece2460639e8bc39cb7f706a826a779b4cf969b8a0e539c7f62fb3d30ad6aa8f80e30f1d128aafd68a2ce72ea0")
+        );
+
+        let (shared_secret, enc) = super::encap_with_ephemeral_secret(&sk_e, &pk_r).unwrap();
+        assert_eq!(enc.0, pk_e);
+        assert_eq!(
+            enc.to_bytes(),
+            hex("04a92719c6195d5085104f469a8b9814d5838ff72b60501e2c4466e5e67b325ac98536d7b61a1af4b78e5b7f951c0900be863c403ce65c9bfcb9382657222d18c4")
+        );
+        assert_eq!(
+            shared_secret.to_vec(),
+            hex("c0d26aeab536609a572b07695d933b589dcf363ff9d93c93adea537aeabb8cb8")
+        );
+        assert_eq!(*super::decap(&enc, &sk_r).unwrap(), *shared_secret);
+
+        let mut sender =
+            super::key_schedule_base::<Secp256r1, Transparent>(&shared_secret, &info).unwrap();
+        let mut receiver = super::setup_base_r::<_, Transparent>(&enc, &sk_r, &info).unwrap();
+
+        let key = hex("868c066ef58aae6dc589b6cfdd18f97e");
+        let base_nonce = hex("4e0bc5018beba4bf004cca59");
+        let ct0 = sender.seal(b"Count-0", b"msg0").unwrap();
+        assert_eq!(ct0, [&key[..], &base_nonce, b"Count-0", b"msg0"].concat());
+        let ct1 = sender.seal(b"Count-1", b"msg1").unwrap();
+        let nonce1 = hex("4e0bc5018beba4bf004cca58");
+        assert_eq!(ct1, [&key[..], &nonce1, b"Count-1", b"msg1"].concat());
+        assert_eq!(sender.seq(), 2);
+
+        assert_eq!(receiver.open(b"Count-0", &ct0).unwrap(), b"msg0");
+        // Messages opened out of order are rejected
+        let ct2 = sender.seal(b"Count-2", b"msg2").unwrap();
+        assert!(receiver.open(b"Count-2", &ct2).is_err());
+        assert_eq!(receiver.open(b"Count-1", &ct1).unwrap(), b"msg1");
+        assert_eq!(receiver.open(b"Count-2", &ct2).unwrap(), b"msg2");
+
+        for (context, exported) in [
+            (
+                "",
+                "5e9bc3d236e1911d95e65b576a8a86d478fb827e8bdfe77b741b289890490d4d",
+            ),
+            (
+                "54657374436f6e74657874",
+                "d8f1ea7942adbba7412c6d431c62d01371ea476b823eb697e1f6e6cae1dab85a",
+            ),
+        ] {
+            assert_eq!(sender.export(&hex(context), 32).unwrap(), hex(exported));
+            assert_eq!(receiver.export(&hex(context), 32).unwrap(), hex(exported));
+        }
+    }
+
+    /// Test vector from RFC 9180, A.1.1: DHKEM(X25519, HKDF-SHA256), HKDF-SHA256, AES-128-GCM
+    #[test]
+    fn rfc9180_x25519_test_vector() {
+        let ikm_e = hex("7268600d403fce431561aef583ee1613527cff655c1343f29812e66706df3234");
+        let ikm_r = hex("6db9df30aa07dd42ee5e8181afdb977e538f5e1fec8a06223f33f7013e525037");
+        let info = hex("4f6465206f6e2061204772656369616e2055726e");
+
+        let (sk_e, pk_e) = super::derive_key_pair::<Ed25519>(&ikm_e).unwrap();
+        let (sk_r, pk_r) = super::derive_key_pair::<Ed25519>(&ikm_r).unwrap();
+        // Test vector lists secret key before clamping
+        let sk_rm: [u8; 32] =
+            hex("4612c550263fc8ad58375df3f557aac531d26850903e55a9f23f21d8534e8ac8")
+                .try_into()
+                .unwrap();
+        assert_eq!(
+            sk_r.expose_secret(|x| x.to_be_bytes()),
+            SecretScalar::<Ed25519>::clamp_ed25519(&sk_rm).expose_secret(|x| x.to_be_bytes())
+        );
+        assert_eq!(
+            pk_r.to_x25519_bytes().to_vec(),
+            hex("3948cfe0ad1ddb695d780e59077195da6c56506b027329794ab02bca80815c4d")
+        );
+
+        let (shared_secret, enc) = super::encap_with_ephemeral_secret(&sk_e, &pk_r).unwrap();
+        assert_eq!(enc.0, pk_e);
+        assert_eq!(
+            enc.to_bytes(),
+            hex("37fda3567bdbd628e88668c3c8d7e97d1d1253b6d4ea6d44c150f741f1bf4431")
+        );
+        assert_eq!(
+            shared_secret.to_vec(),
+            hex("fe0e18c9f024ce43799ae393c7e8fe8fce9d218875e8227b0187c04e7d2ea1fc")
+        );
+        // Receiver decodes `enc` from the wire, which may flip the sign of the point
+        let enc = EncappedKey::<Ed25519>::from_bytes(&enc.to_bytes()).unwrap();
+        assert_eq!(*super::decap(&enc, &sk_r).unwrap(), *shared_secret);
+
+        let mut sender =
+            super::key_schedule_base::<Ed25519, Transparent>(&shared_secret, &info).unwrap();
+        let mut receiver = super::setup_base_r::<_, Transparent>(&enc, &sk_r, &info).unwrap();
+
+        let key = hex("4531685d41d65f03dc48f6b8302c05b0");
+        let base_nonce = hex("56d890e5accaaf011cff4b7d");
+        let ct0 = sender.seal(b"Count-0", b"msg0").unwrap();
+        assert_eq!(ct0, [&key[..], &base_nonce, b"Count-0", b"msg0"].concat());
+        let ct1 = sender.seal(b"Count-1", b"msg1").unwrap();
+        let nonce1 = hex("56d890e5accaaf011cff4b7c");
+        assert_eq!(ct1, [&key[..], &nonce1, b"Count-1", b"msg1"].concat());
+        assert_eq!(receiver.open(b"Count-0", &ct0).unwrap(), b"msg0");
+        assert_eq!(receiver.open(b"Count-1", &ct1).unwrap(), b"msg1");
+
+        for (context, exported) in [
+            (
+                "",
+                "3853fe2b4035195a573ffc53856e77058e15d9ea064de3e59f4961d0095250ee",
+            ),
+            (
+                "00",
+                "2e8f0b54673c7029649d4eb9d5e33bf1872cf76d623ff164ac185da9e88c21a5",
+            ),
+            (
+                "54657374436f6e74657874",
+                "e9e43065102c3836401bed8c3c3c75ae46be1639869391d62c61f1ec7af54931",
+            ),
+        ] {
+            assert_eq!(sender.export(&hex(context), 32).unwrap(), hex(exported));
+            assert_eq!(receiver.export(&hex(context), 32).unwrap(), hex(exported));
+        }
+
+        // Identity and points outside of prime-order subgroup aren't valid encapsulated keys
+        assert!(EncappedKey::<Ed25519>::from_bytes(&[0u8; 32]).is_err());
+        let mut low_order = [0u8; 32];
+        low_order[0] = 1;
+        assert!(EncappedKey::<Ed25519>::from_bytes(&low_order).is_err());
+        assert!(EncappedKey::<Ed25519>::from_bytes(&pk_r.to_bytes(true)[..31]).is_err());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut rng = DevRng::new();
+        let sk_r = NonZero::<SecretScalar<Secp256r1>>::random(&mut rng);
+        let pk_r = Point::generator() * &sk_r;
+
+        let (enc, ct) =
+            super::seal_base::<_, Transparent>(&mut rng, &pk_r, b"info", b"aad", b"message")
+                .unwrap();
+        let enc = EncappedKey::from_bytes(&enc.to_bytes()).unwrap();
+        let pt = super::open_base::<_, Transparent>(&enc, &sk_r, b"info", b"aad", &ct).unwrap();
+        assert_eq!(pt, b"message");
+
+        // Different info or receiver key result in a different context
+        assert!(super::open_base::<_, Transparent>(&enc, &sk_r, b"other", b"aad", &ct).is_err());
+        let other_sk = NonZero::<SecretScalar<Secp256r1>>::random(&mut rng);
+        assert!(super::open_base::<_, Transparent>(&enc, &other_sk, b"info", b"aad", &ct).is_err());
+
+        // Export-only context doesn't encrypt
+        let (_, mut context) =
+            super::setup_base_s::<_, ExportOnly>(&mut rng, &pk_r, b"info").unwrap();
+        assert!(context.seal(b"", b"message").is_err());
+        assert!(context.export(b"", 255 * 32).is_ok());
+        assert!(context.export(b"", 255 * 32 + 1).is_err());
+
+        // Identity and compressed points aren't valid encapsulated keys
+        assert!(
+            EncappedKey::<Secp256r1>::from_bytes(&Point::<Secp256r1>::zero().to_bytes(false))
+                .is_err()
+        );
+        assert!(EncappedKey::<Secp256r1>::from_bytes(&pk_r.to_bytes(true)).is_err());
+    }
+}
@@ -0,0 +1,424 @@
+//! Key packages of frost-core
+//!
+//! [frost-core] stores key share of a party in two structures: [`KeyPackage`] holds secret share
+//! of the party, and [`PublicKeyPackage`] holds public shares of all parties. This module defines
+//! them with the same serde representation, so they can be (de)serialized in any format supported
+//! by frost-core (e.g. JSON):
+//!
+//! ```json
+//! {
+//!   "header": { "version": 0, "ciphersuite": "FROST-secp256k1-SHA256-v1" },
+//!   "identifier": "00..01",
+//!   "signing_share": "...",
+//!   "verifying_share": "...",
+//!   "verifying_key": "...",
+//!   "min_signers": 2
+//! }
+//! ```
+//!
+//! Scalars and points are hex-encoded via `SerializeScalar` and `SerializeElement` of the
+//! ciphersuite defined in [RFC 9591]. Supported ciphersuites:
+//!
+//! | Curve     | Ciphersuite                 |
+//! |-----------|-----------------------------|
+//! | secp256k1 | `FROST-secp256k1-SHA256-v1` |
+//! | secp256r1 | `FROST-P256-SHA256-v1`      |
+//! | ed25519   | `FROST-ED25519-SHA512-v1`   |
+//!
+//! Party $i$ has identifier $i + 1$, which matches identifiers constructed via
+//! `Identifier::try_from(u16)` in frost-core. Identifiers derived from arbitrary strings are not
+//! supported.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{interop::frost, key_share::KeyShare, party_index::PartyIndex, polynomial::Polynomial};
+//! # let mut rng = rand::rngs::OsRng;
+//! # let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! # let f = Polynomial::sample_with_const_term(&mut rng, 1, secret_key.clone());
+//! # let shares: Vec<_> = (0..3).map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar())).collect();
+//! # let key_share = KeyShare {
+//! #     i: PartyIndex(1),
+//! #     t: 2,
+//! #     x: shares[1].clone(),
+//! #     shared_public_key: Point::generator() * &secret_key,
+//! #     public_shares: shares.iter().map(|x_j| Point::generator() * x_j).collect(),
+//! # };
+//!
+//! let (key_package, public_key_package) = frost::export(&key_share)?;
+//! let key_package = serde_json::to_string(&key_package)?;
+//! let public_key_package = serde_json::to_string(&public_key_package)?;
+//!
+//! // ... frost-core reads the packages, or they are read back:
+//! let imported: KeyShare<Secp256k1> = frost::import(
+//!     &serde_json::from_str(&key_package)?,
+//!     &serde_json::from_str(&public_key_package)?,
+//! )?;
+//! assert_eq!(imported.shared_public_key, key_share.shared_public_key);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [frost-core]: https://github.com/ZcashFoundation/frost
+//! [RFC 9591]: https://www.rfc-editor.org/rfc/rfc9591
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use core::fmt;
+
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use super::{ImportError, UnsupportedCurve};
+use crate::{key_share::KeyShare, party_index::PartyIndex};
+
+/// Version of the frost-core serialization format
+const VERSION: u8 = 0;
+
+/// Header of serialized frost-core structures
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    /// Version of the serialization format
+    pub version: u8,
+    /// Ciphersuite identifier, e.g. `FROST-secp256k1-SHA256-v1`
+    pub ciphersuite: String,
+}
+
+/// Key package of a party: secret share and public data needed for signing
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyPackage {
+    /// Header
+    pub header: Header,
+    /// Hex-encoded identifier of the party
+    pub identifier: String,
+    /// Hex-encoded secret share of the party
+    pub signing_share: Zeroizing<String>,
+    /// Hex-encoded public share of the party
+    pub verifying_share: String,
+    /// Hex-encoded shared public key
+    pub verifying_key: String,
+    /// Threshold: amount of parties needed to sign
+    pub min_signers: u16,
+}
+
+/// Public key package: public shares of all parties
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKeyPackage {
+    /// Header
+    pub header: Header,
+    /// Hex-encoded public shares of all parties indexed by hex-encoded identifiers
+    pub verifying_shares: BTreeMap<String, String>,
+    /// Hex-encoded shared public key
+    pub verifying_key: String,
+    /// Threshold: amount of parties needed to sign
+    ///
+    /// Not present in public key packages produced by frost-core before v2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_signers: Option<u16>,
+}
+
+impl fmt::Debug for KeyPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPackage")
+            .field("header", &self.header)
+            .field("identifier", &self.identifier)
+            .field("signing_share", &"<redacted>")
+            .field("verifying_share", &self.verifying_share)
+            .field("verifying_key", &self.verifying_key)
+            .field("min_signers", &self.min_signers)
+            .finish()
+    }
+}
+
+/// Converts key share into frost-core key packages
+///
+/// Returns error if the curve is not supported by FROST.
+pub fn export<E: Curve>(
+    key_share: &KeyShare<E>,
+) -> Result<(KeyPackage, PublicKeyPackage), UnsupportedCurve> {
+    let ciphersuite = Ciphersuite::of::<E>().ok_or(UnsupportedCurve)?;
+    let header = Header {
+        version: VERSION,
+        ciphersuite: ciphersuite.id.into(),
+    };
+    let identifier = |j: PartyIndex| {
+        hex::encode(ciphersuite.encode_scalar(j.share_index().to_scalar::<E>().as_ref()))
+    };
+    let verifying_key = hex::encode(key_share.shared_public_key.to_bytes(true));
+
+    let key_package = KeyPackage {
+        header: header.clone(),
+        identifier: identifier(key_share.i),
+        signing_share: Zeroizing::new(hex::encode(
+            key_share.x.expose_secret(|x| ciphersuite.encode_scalar(x)),
+        )),
+        verifying_share: hex::encode((Point::generator() * &key_share.x).to_bytes(true)),
+        verifying_key: verifying_key.clone(),
+        min_signers: key_share.t,
+    };
+    let public_key_package = PublicKeyPackage {
+        header,
+        verifying_shares: (0..=u16::MAX)
+            .map(PartyIndex)
+            .zip(&key_share.public_shares)
+            .map(|(j, public_share)| (identifier(j), hex::encode(public_share.to_bytes(true))))
+            .collect(),
+        verifying_key,
+        min_signers: Some(key_share.t),
+    };
+    Ok((key_package, public_key_package))
+}
+
+/// Converts frost-core key packages into key share
+///
+/// Returns error if packages are malformed, defined over another curve, don't match each other,
+/// or use identifiers other than $1, \dots, n$.
+pub fn import<E: Curve>(
+    key_package: &KeyPackage,
+    public_key_package: &PublicKeyPackage,
+) -> Result<KeyShare<E>, ImportError> {
+    let ciphersuite = Ciphersuite::of::<E>().ok_or(ImportError::CurveMismatch)?;
+    for header in [&key_package.header, &public_key_package.header] {
+        if header.version != VERSION {
+            return Err(ImportError::Malformed);
+        }
+        if header.ciphersuite != ciphersuite.id {
+            return Err(ImportError::CurveMismatch);
+        }
+    }
+
+    // Identifiers may be encoded in different case, so we decode them all
+    let public_shares = public_key_package
+        .verifying_shares
+        .iter()
+        .map(|(identifier, public_share)| {
+            Ok((
+                ciphersuite.decode_scalar::<E>(identifier)?,
+                decode_point(public_share)?,
+            ))
+        })
+        .collect::<Result<Vec<(Scalar<E>, Point<E>)>, ImportError>>()?;
+    let n = u16::try_from(public_shares.len()).map_err(|_| ImportError::UnsupportedShareIndexes)?;
+    let position = |identifier: &Scalar<E>| {
+        (0..n)
+            .map(PartyIndex)
+            .find(|j| j.share_index().to_scalar::<E>().as_ref() == identifier)
+            .ok_or(ImportError::UnsupportedShareIndexes)
+    };
+    // Identifiers that differ only in encoding decode to the same scalar, and would take the
+    // same position
+    let mut ordered = vec![None; public_shares.len()];
+    for (identifier, public_share) in &public_shares {
+        let slot = &mut ordered[usize::from(position(identifier)?)];
+        if slot.replace(*public_share).is_some() {
+            return Err(ImportError::Malformed);
+        }
+    }
+    let public_shares = ordered
+        .into_iter()
+        .collect::<Option<Vec<Point<E>>>>()
+        .ok_or(ImportError::Malformed)?;
+
+    let i = position(&ciphersuite.decode_scalar(&key_package.identifier)?)?;
+    let shared_public_key = decode_point(&public_key_package.verifying_key)?;
+    if decode_point::<E>(&key_package.verifying_key)? != shared_public_key
+        || public_key_package
+            .min_signers
+            .is_some_and(|t| t != key_package.min_signers)
+    {
+        return Err(ImportError::Malformed);
+    }
+    let mut x = ciphersuite.decode_scalar(&key_package.signing_share)?;
+    let key_share = KeyShare {
+        i,
+        t: key_package.min_signers,
+        x: SecretScalar::new(&mut x),
+        shared_public_key,
+        public_shares,
+    };
+    if decode_point::<E>(&key_package.verifying_share)? != Point::generator() * &key_share.x {
+        return Err(ImportError::Malformed);
+    }
+    key_share.verify_consistency()?;
+    Ok(key_share)
+}
+
+/// FROST ciphersuite defined over the curve
+struct Ciphersuite {
+    id: &'static str,
+    little_endian: bool,
+}
+
+impl Ciphersuite {
+    fn of<E: Curve>() -> Option<Self> {
+        let (id, little_endian) = match E::CURVE_NAME {
+            "secp256k1" => ("FROST-secp256k1-SHA256-v1", false),
+            "secp256r1" => ("FROST-P256-SHA256-v1", false),
+            "ed25519" => ("FROST-ED25519-SHA512-v1", true),
+            _ => return None,
+        };
+        Some(Self { id, little_endian })
+    }
+
+    fn encode_scalar<E: Curve>(&self, scalar: &Scalar<E>) -> Zeroizing<Vec<u8>> {
+        let bytes = if self.little_endian {
+            scalar.to_le_bytes()
+        } else {
+            scalar.to_be_bytes()
+        };
+        Zeroizing::new(bytes.to_vec())
+    }
+
+    fn decode_scalar<E: Curve>(&self, hex: &str) -> Result<Scalar<E>, ImportError> {
+        let bytes = Zeroizing::new(hex::decode(hex).map_err(|_| ImportError::Malformed)?);
+        if self.little_endian {
+            Scalar::from_le_bytes(&*bytes)
+        } else {
+            Scalar::from_be_bytes(&*bytes)
+        }
+        .map_err(|_| ImportError::Malformed)
+    }
+}
+
+fn decode_point<E: Curve>(hex: &str) -> Result<Point<E>, ImportError> {
+    let bytes = hex::decode(hex).map_err(|_| ImportError::Malformed)?;
+    Point::from_bytes(bytes).map_err(|_| ImportError::Malformed)
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use generic_ec::{Curve, Point, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{export, import};
+    use crate::{
+        interop::ImportError, key_share::KeyShare, party_index::PartyIndex, polynomial::Polynomial,
+    };
+
+    fn key_shares<E: Curve>(rng: &mut DevRng, t: u16, n: u16) -> Vec<KeyShare<E>> {
+        let secret_key = SecretScalar::<E>::random(rng);
+        let f = Polynomial::sample_with_const_term(rng, usize::from(t) - 1, secret_key.clone());
+        let shares: Vec<_> = (0..n)
+            .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+            .collect();
+        let public_shares: Vec<_> = shares.iter().map(|x| Point::generator() * x).collect();
+        (0..n)
+            .zip(shares)
+            .map(|(i, x)| KeyShare {
+                i: PartyIndex(i),
+                t,
+                x,
+                shared_public_key: Point::generator() * &secret_key,
+                public_shares: public_shares.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn export_import<E: Curve>() {
+        let mut rng = DevRng::new();
+        for key_share in key_shares::<E>(&mut rng, 3, 12) {
+            let (key_package, public_key_package) = export(&key_share).unwrap();
+
+            let key_package = serde_json::to_string(&key_package).unwrap();
+            let public_key_package = serde_json::to_string(&public_key_package).unwrap();
+            let key_package = serde_json::from_str(&key_package).unwrap();
+            let public_key_package = serde_json::from_str(&public_key_package).unwrap();
+
+            let imported: KeyShare<E> = import(&key_package, &public_key_package).unwrap();
+            assert_eq!(imported.i, key_share.i);
+            assert_eq!(imported.t, key_share.t);
+            assert_eq!(
+                imported.x.expose_secret(|x| *x),
+                key_share.x.expose_secret(|x| *x)
+            );
+            assert_eq!(imported.shared_public_key, key_share.shared_public_key);
+            assert_eq!(imported.public_shares, key_share.public_shares);
+        }
+    }
+
+    #[test]
+    fn invalid_packages_are_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let parties = key_shares::<E>(&mut rng, 2, 3);
+        let (key_package, public_key_package) = export(&parties[1]).unwrap();
+
+        // Identifiers are case-insensitive
+        let mut uppercase = public_key_package.clone();
+        uppercase.verifying_shares = uppercase
+            .verifying_shares
+            .into_iter()
+            .map(|(id, share)| (id.to_uppercase(), share))
+            .collect();
+        import::<E>(&key_package, &uppercase).unwrap();
+
+        let mut invalid = key_package.clone();
+        invalid.header.ciphersuite = String::from("FROST-RISTRETTO255-SHA512-v1");
+        assert_eq!(
+            import::<E>(&invalid, &public_key_package).unwrap_err(),
+            ImportError::CurveMismatch
+        );
+
+        let (other, _) = export(&parties[0]).unwrap();
+        let mut invalid = key_package.clone();
+        invalid.signing_share = other.signing_share;
+        assert_eq!(
+            import::<E>(&invalid, &public_key_package).unwrap_err(),
+            ImportError::Malformed
+        );
+
+        let mut invalid = key_package.clone();
+        invalid.min_signers = 3;
+        assert_eq!(
+            import::<E>(&invalid, &public_key_package).unwrap_err(),
+            ImportError::Malformed
+        );
+
+        // Identifier that isn't one of 1, ..., n
+        let mut invalid = public_key_package.clone();
+        let (_, share) = invalid.verifying_shares.pop_last().unwrap();
+        let (other, _) = export(&key_shares::<E>(&mut rng, 2, 5)[4]).unwrap();
+        invalid.verifying_shares.insert(other.identifier, share);
+        assert_eq!(
+            import::<E>(&key_package, &invalid).unwrap_err(),
+            ImportError::UnsupportedShareIndexes
+        );
+
+        let mut invalid = public_key_package;
+        invalid.verifying_shares.values_mut().for_each(|share| {
+            share.clone_from(&key_package.verifying_key);
+        });
+        assert!(matches!(
+            import::<E>(&key_package, &invalid).unwrap_err(),
+            ImportError::Inconsistent(_)
+        ));
+    }
+
+    #[test]
+    fn identifiers_differing_in_case_are_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let parties = key_shares::<E>(&mut rng, 2, 11);
+        let (key_package, mut public_key_package) = export(&parties[0]).unwrap();
+
+        // Replace identifier of the last party with identifier of the 10th party in uppercase
+        let (_, share) = public_key_package.verifying_shares.pop_last().unwrap();
+        let (tenth, _) = export(&parties[9]).unwrap();
+        assert_ne!(tenth.identifier, tenth.identifier.to_uppercase());
+        public_key_package
+            .verifying_shares
+            .insert(tenth.identifier.to_uppercase(), share);
+        assert_eq!(
+            import::<E>(&key_package, &public_key_package).unwrap_err(),
+            ImportError::Malformed
+        );
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Ed25519>)]
+    mod ed25519 {}
+}
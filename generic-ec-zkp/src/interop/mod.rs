@@ -0,0 +1,79 @@
+//! Conversion of key shares to formats of other threshold libraries
+//!
+//! Lets keys generated by other threshold libraries be migrated into [`KeyShare`] (and back)
+//! without re-running the key generation ceremony:
+//!
+//! * [`frost`] converts to and from key packages of [frost-core] (FROST, [RFC 9591])
+//! * [`tss_lib`] converts to and from ECDSA key shares of [tss-lib]
+//!
+//! Both libraries let parties be identified by arbitrary share indexes, while [`KeyShare`]
+//! requires party $i$ to hold share at point $i + 1$ (see [`party_index`](crate::party_index)).
+//! Only key shares with share indexes $1, \dots, n$ can be imported, other key shares have to be
+//! reshared by the original library first. Imported key shares are checked via
+//! [`KeyShare::verify_consistency`].
+//!
+//! [frost-core]: https://github.com/ZcashFoundation/frost
+//! [RFC 9591]: https://www.rfc-editor.org/rfc/rfc9591
+//! [tss-lib]: https://github.com/bnb-chain/tss-lib
+//! [`KeyShare`]: crate::key_share::KeyShare
+//! [`KeyShare::verify_consistency`]: crate::key_share::KeyShare::verify_consistency
+
+use core::fmt;
+
+use crate::key_share::InconsistentKeyShare;
+
+pub mod frost;
+pub mod tss_lib;
+
+/// Key share can't be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportError {
+    /// Key share is defined over a different curve
+    CurveMismatch,
+    /// Key share can't be parsed
+    Malformed,
+    /// Share indexes are not $1, \dots, n$
+    UnsupportedShareIndexes,
+    /// Imported key share is inconsistent
+    Inconsistent(InconsistentKeyShare),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CurveMismatch => f.write_str("key share is defined over a different curve"),
+            Self::Malformed => f.write_str("malformed key share"),
+            Self::UnsupportedShareIndexes => f.write_str("share indexes are not 1, ..., n"),
+            Self::Inconsistent(err) => write!(f, "imported key share is inconsistent: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Inconsistent(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<InconsistentKeyShare> for ImportError {
+    fn from(err: InconsistentKeyShare) -> Self {
+        Self::Inconsistent(err)
+    }
+}
+
+/// Curve is not supported by the format
+#[derive(Debug, Clone, Copy)]
+pub struct UnsupportedCurve;
+
+impl fmt::Display for UnsupportedCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("curve is not supported by the format")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnsupportedCurve {}
@@ -0,0 +1,379 @@
+//! ECDSA key shares of tss-lib
+//!
+//! [tss-lib] stores result of ECDSA key generation in `LocalPartySaveData` serialized as JSON.
+//! Integers are encoded as (big) decimal JSON numbers, and points as affine coordinates:
+//!
+//! ```json
+//! {
+//!   "Xi": 1234...,
+//!   "ShareID": 2,
+//!   "Ks": [1, 2, 3],
+//!   "BigXj": [{ "Curve": "secp256k1", "Coords": [1234..., 5678...] }, ...],
+//!   "ECDSAPub": { "Curve": "secp256k1", "Coords": [1234..., 5678...] },
+//!   ...
+//! }
+//! ```
+//!
+//! Only the fields above are converted, others (Paillier keys, ring-Pedersen parameters, etc.)
+//! are ignored on import and absent on export. tss-lib can't sign with the exported key share
+//! until they are generated and filled in.
+//!
+//! Only secp256k1 is supported. Party $i$ must have share ID $i + 1$: tss-lib sorts parties by
+//! their keys, so parties need to be keyed by $1, \dots, n$ to be imported.
+//!
+//! tss-lib threshold $t$ means that $t + 1$ parties are needed to sign, whereas [`KeyShare::t`]
+//! is amount of parties needed, so it's off by one.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{interop::tss_lib, key_share::KeyShare, party_index::PartyIndex, polynomial::Polynomial};
+//! # let mut rng = rand::rngs::OsRng;
+//! # let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! # let f = Polynomial::sample_with_const_term(&mut rng, 1, secret_key.clone());
+//! # let shares: Vec<_> = (0..3).map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar())).collect();
+//! # let key_share = KeyShare {
+//! #     i: PartyIndex(1),
+//! #     t: 2,
+//! #     x: shares[1].clone(),
+//! #     shared_public_key: Point::generator() * &secret_key,
+//! #     public_shares: shares.iter().map(|x_j| Point::generator() * x_j).collect(),
+//! # };
+//!
+//! let save_data = tss_lib::export(&key_share)?;
+//!
+//! // Key share generated by tss-lib with threshold 1
+//! let imported: KeyShare<Secp256k1> = tss_lib::import(&save_data, 2)?;
+//! assert_eq!(imported.shared_public_key, key_share.shared_public_key);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [tss-lib]: https://github.com/bnb-chain/tss-lib
+//! [`KeyShare::t`]: crate::key_share::KeyShare::t
+
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::Write;
+
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use zeroize::Zeroizing;
+
+use super::{ImportError, UnsupportedCurve};
+use crate::{key_share::KeyShare, party_index::PartyIndex};
+
+/// Name of the only curve supported by the conversion
+const CURVE: &str = "secp256k1";
+
+/// Fields of `LocalPartySaveData` that are converted
+///
+/// Numbers are borrowed as raw JSON, as they don't fit into any primitive integer.
+#[derive(Deserialize)]
+struct SaveData<'a> {
+    #[serde(rename = "Xi", borrow)]
+    xi: &'a RawValue,
+    #[serde(rename = "ShareID", borrow)]
+    share_id: &'a RawValue,
+    #[serde(rename = "Ks", borrow)]
+    ks: Vec<&'a RawValue>,
+    #[serde(rename = "BigXj", borrow)]
+    big_xj: Vec<EcPoint<'a>>,
+    #[serde(rename = "ECDSAPub", borrow)]
+    ecdsa_pub: EcPoint<'a>,
+}
+
+#[derive(Deserialize)]
+struct EcPoint<'a> {
+    #[serde(rename = "Curve")]
+    curve: &'a str,
+    #[serde(rename = "Coords", borrow)]
+    coords: [&'a RawValue; 2],
+}
+
+/// Converts key share into tss-lib `LocalPartySaveData` JSON
+///
+/// Returns error if the curve is not secp256k1.
+pub fn export<E: Curve>(key_share: &KeyShare<E>) -> Result<Zeroizing<String>, UnsupportedCurve> {
+    if E::CURVE_NAME != CURVE {
+        return Err(UnsupportedCurve);
+    }
+    let xi = to_decimal(&Zeroizing::new(
+        key_share.x.expose_secret(|x| x.to_be_bytes().to_vec()),
+    ));
+
+    let mut json = Zeroizing::new(String::new());
+    // Writing into a string never fails
+    let _ = write!(
+        json,
+        r#"{{"Xi":{},"ShareID":{},"Ks":["#,
+        xi.as_str(),
+        key_share.i.share_index(),
+    );
+    for j in (0..=u16::MAX).take(key_share.n()) {
+        if j > 0 {
+            json.push(',');
+        }
+        let _ = write!(json, "{}", PartyIndex(j).share_index());
+    }
+    json.push_str(r#"],"BigXj":["#);
+    for (j, public_share) in key_share.public_shares.iter().enumerate() {
+        if j > 0 {
+            json.push(',');
+        }
+        write_point(&mut json, public_share);
+    }
+    json.push_str(r#"],"ECDSAPub":"#);
+    write_point(&mut json, &key_share.shared_public_key);
+    json.push('}');
+    Ok(json)
+}
+
+/// Converts tss-lib `LocalPartySaveData` JSON into key share
+///
+/// `t` is amount of parties needed to sign, i.e. tss-lib threshold plus one. Returns error if
+/// save data is malformed, defined over another curve, or uses share IDs other than
+/// $1, \dots, n$.
+pub fn import<E: Curve>(save_data: &str, t: u16) -> Result<KeyShare<E>, ImportError> {
+    if E::CURVE_NAME != CURVE {
+        return Err(ImportError::CurveMismatch);
+    }
+    let save_data: SaveData =
+        serde_json::from_str(save_data).map_err(|_| ImportError::Malformed)?;
+
+    let share_index = |id: &RawValue| {
+        id.get()
+            .parse::<u32>()
+            .map_err(|_| ImportError::UnsupportedShareIndexes)
+    };
+    for (j, k) in (0..=u16::MAX).map(PartyIndex).zip(&save_data.ks) {
+        if share_index(k)? != j.share_index().get() {
+            return Err(ImportError::UnsupportedShareIndexes);
+        }
+    }
+    let i = PartyIndex::try_from(
+        usize::try_from(share_index(save_data.share_id)?)
+            .ok()
+            .and_then(|id| id.checked_sub(1))
+            .filter(|i| *i < save_data.ks.len())
+            .ok_or(ImportError::UnsupportedShareIndexes)?,
+    )
+    .map_err(|_| ImportError::UnsupportedShareIndexes)?;
+    if save_data.big_xj.len() != save_data.ks.len() {
+        return Err(ImportError::Malformed);
+    }
+
+    let xi = from_decimal(save_data.xi.get(), Scalar::<E>::serialized_len())
+        .ok_or(ImportError::Malformed)?;
+    let mut x = Scalar::from_be_bytes(&*xi).map_err(|_| ImportError::Malformed)?;
+    let key_share = KeyShare {
+        i,
+        t,
+        x: SecretScalar::new(&mut x),
+        shared_public_key: read_point(&save_data.ecdsa_pub)?,
+        public_shares: save_data
+            .big_xj
+            .iter()
+            .map(read_point)
+            .collect::<Result<_, _>>()?,
+    };
+    key_share.verify_consistency()?;
+    Ok(key_share)
+}
+
+fn write_point<E: Curve>(json: &mut String, point: &Point<E>) {
+    let bytes = point.to_bytes(false);
+    // Uncompressed point is `04 || x || y`, identity point doesn't have coordinates
+    let (x, y) = bytes
+        .get(1..)
+        .map(|coords| coords.split_at(coords.len() / 2))
+        .unwrap_or_default();
+    let _ = write!(
+        json,
+        r#"{{"Curve":"{CURVE}","Coords":[{},{}]}}"#,
+        to_decimal(x).as_str(),
+        to_decimal(y).as_str(),
+    );
+}
+
+fn read_point<E: Curve>(point: &EcPoint) -> Result<Point<E>, ImportError> {
+    if point.curve != CURVE {
+        return Err(ImportError::CurveMismatch);
+    }
+    let coord_len = (Point::<E>::serialized_len(false) - 1) / 2;
+    let mut bytes = vec![0x04];
+    for coord in point.coords {
+        bytes.extend_from_slice(
+            &from_decimal(coord.get(), coord_len).ok_or(ImportError::Malformed)?,
+        );
+    }
+    Point::from_bytes(bytes).map_err(|_| ImportError::Malformed)
+}
+
+/// Converts big-endian integer into decimal
+fn to_decimal(be_bytes: &[u8]) -> Zeroizing<String> {
+    let mut n = Zeroizing::new(be_bytes.to_vec());
+    let mut digits = Zeroizing::new(Vec::new());
+    while n.iter().any(|byte| *byte != 0) {
+        let mut rem = 0u16;
+        for byte in n.iter_mut() {
+            let acc = (rem << 8) | u16::from(*byte);
+            *byte = (acc / 10) as u8;
+            rem = acc % 10;
+        }
+        digits.push(b'0' + rem as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    Zeroizing::new(
+        digits
+            .iter()
+            .rev()
+            .map(|digit| char::from(*digit))
+            .collect(),
+    )
+}
+
+/// Converts decimal into big-endian integer of `len` bytes
+///
+/// Returns `None` if `decimal` is not a non-negative integer, or it doesn't fit into `len` bytes
+fn from_decimal(decimal: &str, len: usize) -> Option<Zeroizing<Vec<u8>>> {
+    if decimal.is_empty() {
+        return None;
+    }
+    let mut n = Zeroizing::new(vec![0u8; len]);
+    for digit in decimal.bytes() {
+        let mut carry = u16::from(digit.checked_sub(b'0').filter(|d| *d < 10)?);
+        for byte in n.iter_mut().rev() {
+            let acc = u16::from(*byte) * 10 + carry;
+            *byte = (acc & 0xff) as u8;
+            carry = acc >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec::Vec};
+
+    use generic_ec::{
+        curves::{Secp256k1, Secp256r1},
+        Point, SecretScalar,
+    };
+    use rand_dev::DevRng;
+
+    use super::{export, from_decimal, import, to_decimal};
+    use crate::{
+        interop::ImportError, key_share::KeyShare, party_index::PartyIndex, polynomial::Polynomial,
+    };
+
+    fn key_shares(rng: &mut DevRng, t: u16, n: u16) -> Vec<KeyShare<Secp256k1>> {
+        let secret_key = SecretScalar::<Secp256k1>::random(rng);
+        let f = Polynomial::sample_with_const_term(rng, usize::from(t) - 1, secret_key.clone());
+        let shares: Vec<_> = (0..n)
+            .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+            .collect();
+        let public_shares: Vec<_> = shares.iter().map(|x| Point::generator() * x).collect();
+        (0..n)
+            .zip(shares)
+            .map(|(i, x)| KeyShare {
+                i: PartyIndex(i),
+                t,
+                x,
+                shared_public_key: Point::generator() * &secret_key,
+                public_shares: public_shares.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decimal() {
+        for (bytes, decimal) in [
+            (&[0u8, 0][..], "0"),
+            (&[0, 9], "9"),
+            (&[1, 0], "256"),
+            (&[0xff, 0xff], "65535"),
+        ] {
+            assert_eq!(to_decimal(bytes).as_str(), decimal);
+            assert_eq!(from_decimal(decimal, 2).unwrap().as_slice(), bytes);
+        }
+        for invalid in ["", "65536", "-1", "1.0", "1e3"] {
+            assert!(from_decimal(invalid, 2).is_none());
+        }
+    }
+
+    #[test]
+    fn export_import() {
+        let mut rng = DevRng::new();
+        for key_share in key_shares(&mut rng, 3, 5) {
+            let save_data = export(&key_share).unwrap();
+            let imported = import::<Secp256k1>(&save_data, 3).unwrap();
+            assert_eq!(imported.i, key_share.i);
+            assert_eq!(
+                imported.x.expose_secret(|x| *x),
+                key_share.x.expose_secret(|x| *x)
+            );
+            assert_eq!(imported.shared_public_key, key_share.shared_public_key);
+            assert_eq!(imported.public_shares, key_share.public_shares);
+
+            // Wrong threshold
+            assert!(matches!(
+                import::<Secp256k1>(&save_data, 2),
+                Err(ImportError::Inconsistent(_))
+            ));
+        }
+
+        let secp256r1 = KeyShare::<Secp256r1> {
+            i: PartyIndex(0),
+            t: 1,
+            x: SecretScalar::random(&mut rng),
+            shared_public_key: Point::generator().to_point(),
+            public_shares: Vec::new(),
+        };
+        assert!(export(&secp256r1).is_err());
+    }
+
+    #[test]
+    fn other_fields_are_ignored() {
+        let mut rng = DevRng::new();
+        let key_share = key_shares(&mut rng, 2, 2).remove(1);
+        let save_data = export(&key_share).unwrap();
+        let save_data = format!(
+            r#"{{"PaillierSK":{{"N":123,"LambdaN":456}},"NTildei":789,{}"#,
+            &save_data[1..]
+        );
+        import::<Secp256k1>(&save_data, 2).unwrap();
+    }
+
+    #[test]
+    fn unsupported_share_ids() {
+        let mut rng = DevRng::new();
+        let key_share = key_shares(&mut rng, 2, 3).remove(1);
+        let save_data = export(&key_share).unwrap();
+
+        for (from, to) in [
+            (r#""Ks":[1,2,3]"#, r#""Ks":[1,2,4]"#),
+            (r#""Ks":[1,2,3]"#, r#""Ks":[3,2,1]"#),
+            (r#""ShareID":2"#, r#""ShareID":4"#),
+            (r#""ShareID":2"#, r#""ShareID":0"#),
+            (r#""ShareID":2"#, r#""ShareID":12345678901234567890"#),
+        ] {
+            let invalid: String = save_data.replace(from, to);
+            assert_ne!(invalid, *save_data);
+            assert_eq!(
+                import::<Secp256k1>(&invalid, 2).unwrap_err(),
+                ImportError::UnsupportedShareIndexes
+            );
+        }
+
+        let invalid = save_data.replace("secp256k1", "ed25519");
+        assert_eq!(
+            import::<Secp256k1>(&invalid, 2).unwrap_err(),
+            ImportError::CurveMismatch
+        );
+    }
+}
@@ -0,0 +1,458 @@
+//! Inner-product argument $\Pi^\text{ipa}$
+//!
+//! Inner-product argument (IPA) proves knowledge of vectors $\mathbf{a}, \mathbf{b} \in \Z_q^n$
+//! such that
+//!
+//! $$P = \langle \mathbf{a}, \mathbf{G} \rangle + \langle \mathbf{b}, \mathbf{H} \rangle + \langle \mathbf{a}, \mathbf{b} \rangle \cdot U$$
+//!
+//! where $\mathbf{G}, \mathbf{H} \in \G^n$ and $U \in \G$ are public bases with unknown discrete
+//! logs relative to each other. The proof consists of $2 \log_2 n$ points and $2$ scalars. It's
+//! made non-interactive via Fiat-Shamir [transcript](crate::transcript).
+//!
+//! The argument is the one from [Bulletproofs] paper (Protocol 2). It's **not** zero-knowledge on its
+//! own: proof reveals information about $\mathbf{a}$ and $\mathbf{b}$. Protocols that use IPA as a
+//! building block (like range proofs) must blind the vectors beforehand.
+//!
+//! Length of vectors $n$ must be a power of two.
+//!
+//! [Bulletproofs]: https://eprint.iacr.org/2017/1066
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1};
+//! use generic_ec_zkp::{ipa, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//! # let mut random_point = || Point::generator() * Scalar::random(&mut rng);
+//! # let g: Vec<Point<Secp256k1>> = (0..4).map(|_| random_point()).collect();
+//! # let h: Vec<Point<Secp256k1>> = (0..4).map(|_| random_point()).collect();
+//! # let u = random_point();
+//! // Public bases `g`, `h`, and `u` must have unknown discrete logs relative to each other
+//!
+//! // Prover knows vectors `a` and `b`
+//! let a: Vec<Scalar<Secp256k1>> = (1..=4).map(Scalar::from).collect();
+//! let b: Vec<Scalar<Secp256k1>> = (5..=8).map(Scalar::from).collect();
+//! let p = ipa::commit(&g, &h, &u, &a, &b)?;
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! transcript.append_point(b"P", &p);
+//! let proof = ipa::prove(&mut transcript, &g, &h, &u, a, b)?;
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! transcript.append_point(b"P", &p);
+//! proof.verify(&mut transcript, &g, &h, &u, &p)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! While $n > 1$, prover splits the vectors in halves $\mathbf{a} = \mathbf{a}_L \| \mathbf{a}_R$ (same
+//! for $\mathbf{b}$, $\mathbf{G}$, $\mathbf{H}$) and:
+//! 1. Sends $L = \langle \mathbf{a}_L, \mathbf{G}_R \rangle + \langle \mathbf{b}_R, \mathbf{H}_L \rangle + \langle \mathbf{a}_L, \mathbf{b}_R \rangle \cdot U$
+//!    and $R = \langle \mathbf{a}_R, \mathbf{G}_L \rangle + \langle \mathbf{b}_L, \mathbf{H}_R \rangle + \langle \mathbf{a}_R, \mathbf{b}_L \rangle \cdot U$
+//! 2. Obtains challenge $u$ from the transcript
+//! 3. Folds $\mathbf{a} \gets u \mathbf{a}_L + u^{-1} \mathbf{a}_R$,
+//!    $\mathbf{b} \gets u^{-1} \mathbf{b}_L + u \mathbf{b}_R$,
+//!    $\mathbf{G} \gets u^{-1} \mathbf{G}_L + u \mathbf{G}_R$,
+//!    $\mathbf{H} \gets u \mathbf{H}_L + u^{-1} \mathbf{H}_R$
+//!
+//! Finally, prover sends remaining scalars $a, b$. Verifier recomputes challenges $u_1, \dots, u_k$
+//! and checks single multiscalar equation
+//!
+//! $$P + \sum_j (u_j^2 L_j + u_j^{-2} R_j) \\? a \langle \mathbf{s}, \mathbf{G} \rangle + b \langle \mathbf{s}^{-1}, \mathbf{H} \rangle + ab \cdot U$$
+//!
+//! where $s_i = \prod_j u_j^{\pm 1}$, the sign is positive iff $i$ is in the right half at round $j$.
+
+use alloc::vec::Vec;
+use core::iter;
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar};
+
+use crate::transcript::Transcript;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Inner-product proof
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+#[allow(non_snake_case)]
+pub struct InnerProductProof<E: Curve> {
+    /// $L_j$ points sent at every round
+    pub L: Vec<Point<E>>,
+    /// $R_j$ points sent at every round
+    pub R: Vec<Point<E>>,
+    /// Folded vector $\mathbf{a}$
+    pub a: Scalar<E>,
+    /// Folded vector $\mathbf{b}$
+    pub b: Scalar<E>,
+}
+
+/// Scalars that verifier uses to check the proof
+///
+/// Obtained via [`InnerProductProof::verification_scalars`]. Protocols that use IPA as a building
+/// block may combine them with their own checks into a single multiscalar multiplication.
+#[derive(Clone, Debug)]
+pub struct VerificationScalars<E: Curve> {
+    /// $u_j^2$ for every round $j$
+    pub u_sq: Vec<Scalar<E>>,
+    /// $u_j^{-2}$ for every round $j$
+    pub u_inv_sq: Vec<Scalar<E>>,
+    /// $s_i$ for every $i \in [0, n)$
+    pub s: Vec<Scalar<E>>,
+}
+
+/// Computes $P = \langle \mathbf{a}, \mathbf{G} \rangle + \langle \mathbf{b}, \mathbf{H} \rangle + \langle \mathbf{a}, \mathbf{b} \rangle \cdot U$
+///
+/// Returns error if lengths of vectors mismatch.
+pub fn commit<E: Curve>(
+    g: &[Point<E>],
+    h: &[Point<E>],
+    u: &Point<E>,
+    a: &[Scalar<E>],
+    b: &[Scalar<E>],
+) -> Result<Point<E>, InvalidInput> {
+    let n = a.len();
+    if b.len() != n || g.len() != n || h.len() != n {
+        return Err(InvalidInput);
+    }
+    let c = inner_product(a, b);
+    Ok(Scalar::multiscalar_mul(
+        a.iter()
+            .zip(g)
+            .chain(b.iter().zip(h))
+            .chain(iter::once((&c, u))),
+    ))
+}
+
+/// Proves knowledge of $\mathbf{a}, \mathbf{b}$
+///
+/// Statement (point $P$ and bases) must be appended to the transcript by the caller beforehand.
+///
+/// Returns error if lengths of vectors mismatch or aren't a power of two.
+#[allow(non_snake_case)]
+pub fn prove<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    g: &[Point<E>],
+    h: &[Point<E>],
+    u: &Point<E>,
+    mut a: Vec<Scalar<E>>,
+    mut b: Vec<Scalar<E>>,
+) -> Result<InnerProductProof<E>, InvalidInput> {
+    let mut n = a.len();
+    if b.len() != n || g.len() != n || h.len() != n || !n.is_power_of_two() {
+        return Err(InvalidInput);
+    }
+    transcript.append_u64(b"ipa/n", n as u64);
+
+    let mut g = g.to_vec();
+    let mut h = h.to_vec();
+    let rounds = n.trailing_zeros() as usize;
+    let mut L_vec = Vec::with_capacity(rounds);
+    let mut R_vec = Vec::with_capacity(rounds);
+
+    while n > 1 {
+        n /= 2;
+        let (a_l, a_r) = a.split_at(n);
+        let (b_l, b_r) = b.split_at(n);
+        let (g_l, g_r) = g.split_at(n);
+        let (h_l, h_r) = h.split_at(n);
+
+        let c_l = inner_product(a_l, b_r);
+        let c_r = inner_product(a_r, b_l);
+
+        // Vectors may be secret (e.g. in range proofs), so `L` and `R` are computed in constant
+        // time instead of using multiscalar multiplication
+        let L = a_l
+            .iter()
+            .zip(g_r)
+            .chain(b_r.iter().zip(h_l))
+            .chain(iter::once((&c_l, u)))
+            .map(|(s, p)| p * s)
+            .sum::<Point<E>>();
+        let R = a_r
+            .iter()
+            .zip(g_l)
+            .chain(b_l.iter().zip(h_r))
+            .chain(iter::once((&c_r, u)))
+            .map(|(s, p)| p * s)
+            .sum::<Point<E>>();
+
+        transcript.append_point(b"ipa/L", &L);
+        transcript.append_point(b"ipa/R", &R);
+        let (x, x_inv) = challenge(transcript)?;
+
+        a = a_l
+            .iter()
+            .zip(a_r)
+            .map(|(a_l, a_r)| a_l * x + a_r * x_inv)
+            .collect();
+        b = b_l
+            .iter()
+            .zip(b_r)
+            .map(|(b_l, b_r)| b_l * x_inv + b_r * x)
+            .collect();
+        g = g_l
+            .iter()
+            .zip(g_r)
+            .map(|(g_l, g_r)| Scalar::multiscalar_mul([(x_inv, g_l), (x, g_r)]))
+            .collect();
+        h = h_l
+            .iter()
+            .zip(h_r)
+            .map(|(h_l, h_r)| Scalar::multiscalar_mul([(x, h_l), (x_inv, h_r)]))
+            .collect();
+
+        L_vec.push(L);
+        R_vec.push(R);
+    }
+
+    Ok(InnerProductProof {
+        L: L_vec,
+        R: R_vec,
+        a: a[0],
+        b: b[0],
+    })
+}
+
+impl<E: Curve> InnerProductProof<E> {
+    /// Verifies the proof
+    ///
+    /// Transcript must be in the same state as it was at the moment of [proving](prove).
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        g: &[Point<E>],
+        h: &[Point<E>],
+        u: &Point<E>,
+        p: &Point<E>,
+    ) -> Result<(), InvalidProof> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("ipa::verify", n = g.len()).entered();
+        let n = g.len();
+        if h.len() != n {
+            verification_failed!(E, transcript, "vectors g and h have different length");
+            return Err(InvalidProof);
+        }
+        let Ok(VerificationScalars { u_sq, u_inv_sq, s }) =
+            self.verification_scalars(transcript, n)
+        else {
+            verification_failed!(E, transcript, "malformed inner-product proof");
+            return Err(InvalidProof);
+        };
+
+        let g_scalars = s.iter().map(|s_i| self.a * s_i);
+        // $s_i^{-1} = s_{n - 1 - i}$
+        let h_scalars = s.iter().rev().map(|s_i_inv| self.b * s_i_inv);
+        let ab = self.a * self.b;
+
+        let rhs = Scalar::multiscalar_mul(
+            g_scalars
+                .zip(g)
+                .chain(h_scalars.zip(h))
+                .chain(iter::once((ab, u))),
+        );
+        let lhs =
+            Scalar::multiscalar_mul(u_sq.iter().zip(&self.L).chain(u_inv_sq.iter().zip(&self.R)))
+                + p;
+
+        if lhs == rhs {
+            Ok(())
+        } else {
+            verification_failed!(E, transcript, "inner-product equation doesn't hold");
+            Err(InvalidProof)
+        }
+    }
+
+    /// Recomputes challenges from the transcript and derives scalars needed for verification
+    ///
+    /// `n` is a length of vectors. Transcript must be in the same state as it was at the moment
+    /// of [proving](prove).
+    #[allow(non_snake_case)]
+    pub fn verification_scalars<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        n: usize,
+    ) -> Result<VerificationScalars<E>, InvalidProof> {
+        if !n.is_power_of_two()
+            || self.L.len() != self.R.len()
+            || self.L.len() != n.trailing_zeros() as usize
+        {
+            return Err(InvalidProof);
+        }
+        transcript.append_u64(b"ipa/n", n as u64);
+
+        let rounds = self.L.len();
+        let mut u = Vec::with_capacity(rounds);
+        let mut u_inv = Vec::with_capacity(rounds);
+        for (L, R) in self.L.iter().zip(&self.R) {
+            transcript.append_point(b"ipa/L", L);
+            transcript.append_point(b"ipa/R", R);
+            let (x, x_inv) = challenge(transcript).map_err(|_| InvalidProof)?;
+            u.push(x);
+            u_inv.push(x_inv);
+        }
+
+        let u_sq: Vec<_> = u.iter().map(|x| x * x).collect();
+        let u_inv_sq: Vec<_> = u_inv.iter().map(|x| x * x).collect();
+
+        // $s_0 = \prod_j u_j^{-1}$, then every next $s_i$ is obtained from $s_{i - 2^k}$
+        // by flipping the sign of exponent at the round corresponding to bit $k$
+        let mut s = Vec::with_capacity(n);
+        s.push(u_inv.iter().product::<Scalar<E>>());
+        for i in 1..n {
+            let k = usize::BITS - 1 - i.leading_zeros();
+            let prev = s[i - (1 << k)];
+            s.push(prev * u_sq[rounds - 1 - k as usize]);
+        }
+
+        Ok(VerificationScalars { u_sq, u_inv_sq, s })
+    }
+}
+
+fn challenge<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+) -> Result<(Scalar<E>, Scalar<E>), InvalidInput> {
+    let x: Scalar<E> = transcript.challenge_scalar(b"ipa/u");
+    let x_inv = x.invert().ok_or(InvalidInput)?;
+    Ok((x, x_inv))
+}
+
+fn inner_product<E: Curve>(a: &[Scalar<E>], b: &[Scalar<E>]) -> Scalar<E> {
+    a.iter().zip(b).map(|(a_i, b_i)| a_i * b_i).sum()
+}
+
+/// Invalid input error
+///
+/// Returned when lengths of input vectors mismatch or aren't a power of two
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid inner-product argument input")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid inner-product proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    use crate::transcript::Transcript;
+
+    struct Instance<E: Curve> {
+        g: Vec<Point<E>>,
+        h: Vec<Point<E>>,
+        u: Point<E>,
+        a: Vec<Scalar<E>>,
+        b: Vec<Scalar<E>>,
+    }
+
+    impl<E: Curve> Instance<E> {
+        fn random(rng: &mut DevRng, n: usize) -> Self {
+            let mut random_point = || Point::generator() * Scalar::random(rng);
+            let g = (0..n).map(|_| random_point()).collect();
+            let h = (0..n).map(|_| random_point()).collect();
+            let u = random_point();
+            Self {
+                g,
+                h,
+                u,
+                a: (0..n).map(|_| Scalar::random(rng)).collect(),
+                b: (0..n).map(|_| Scalar::random(rng)).collect(),
+            }
+        }
+
+        fn commit(&self) -> Point<E> {
+            super::commit(&self.g, &self.h, &self.u, &self.a, &self.b).unwrap()
+        }
+
+        fn prove(&self) -> Result<super::InnerProductProof<E>, super::InvalidInput> {
+            super::prove(
+                &mut transcript(b"test"),
+                &self.g,
+                &self.h,
+                &self.u,
+                self.a.clone(),
+                self.b.clone(),
+            )
+        }
+
+        fn verify(
+            &self,
+            proof: &super::InnerProductProof<E>,
+            domain: &[u8],
+            p: &Point<E>,
+        ) -> Result<(), super::InvalidProof> {
+            proof.verify(&mut transcript(domain), &self.g, &self.h, &self.u, p)
+        }
+    }
+
+    fn transcript(domain: &[u8]) -> Transcript<sha2::Sha256> {
+        Transcript::new(domain)
+    }
+
+    #[test]
+    fn honest_proof_verifies<E: Curve>() {
+        let mut rng = DevRng::new();
+        for n in [1, 2, 4, 8, 32] {
+            let instance = Instance::<E>::random(&mut rng, n);
+            let p = instance.commit();
+
+            let proof = instance.prove().unwrap();
+            assert_eq!(proof.L.len(), n.trailing_zeros() as usize);
+            instance.verify(&proof, b"test", &p).unwrap();
+        }
+    }
+
+    #[test]
+    fn wrong_statement_is_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let instance = Instance::<E>::random(&mut rng, 8);
+        let p = instance.commit();
+        let proof = instance.prove().unwrap();
+
+        let wrong_p = p + Point::generator();
+        assert!(instance.verify(&proof, b"test", &wrong_p).is_err());
+        assert!(instance.verify(&proof, b"other", &p).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.a += Scalar::one();
+        assert!(instance.verify(&tampered, b"test", &p).is_err());
+    }
+
+    #[test]
+    fn invalid_input_is_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let instance = Instance::<E>::random(&mut rng, 3);
+        assert!(instance.prove().is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
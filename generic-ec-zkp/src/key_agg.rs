@@ -0,0 +1,200 @@
+//! Rogue-key resistant public key aggregation
+//!
+//! Multi-key protocols (e.g. multi-signatures) combine public keys $X_1, \dots, X_n$ of the
+//! parties into a single aggregated key. Plain sum $\sum_i X_i$ is insecure: a malicious party
+//! may choose its key after seeing others' keys as $X_{rogue} = x \cdot G - \sum_{j} X_j$ and
+//! learn the aggregated secret key $x$ (rogue-key attack). This module provides two standard
+//! countermeasures:
+//!
+//! * [`aggregate_keys`] weights every key with coefficient $a_i = H(L, X_i)$ derived from the
+//!   whole list of keys $L$, as in MuSig. The aggregated key is $\tilde X = \sum_i a_i \cdot X_i$.
+//!   It doesn't require any extra messages, but every signer needs to multiply its secret key
+//!   by its coefficient.
+//! * [`aggregate_certified_keys`] sums up the keys, each accompanied by a proof of possession
+//!   ([`CertifiedPoint`]). All coefficients are equal to one.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::key_agg::aggregate_keys;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let secret_keys = [(); 3].map(|_| SecretScalar::<Secp256k1>::random(&mut rng));
+//! let public_keys = secret_keys.each_ref().map(|x| Point::generator() * x);
+//!
+//! let aggregated = aggregate_keys::<_, sha2::Sha256>(&public_keys);
+//!
+//! // Each signer weights its secret key with its coefficient
+//! let weighted = secret_keys
+//!     .iter()
+//!     .enumerate()
+//!     .map(|(i, x)| aggregated.weighted_secret_key(i, x))
+//!     .collect::<Option<Vec<_>>>()
+//!     .ok_or("index out of bounds")?;
+//! let aggregated_secret_key = SecretScalar::new(&mut weighted.iter().sum());
+//! assert_eq!(*aggregated.key(), Point::generator() * &aggregated_secret_key);
+//! # Ok::<(), &'static str>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Keys $L = (X_1, \dots, X_n)$ are appended to the [transcript](crate::transcript) with
+//! a fixed domain separation tag. Coefficient $a_i$ is a challenge derived from a copy of the
+//! transcript with $X_i$ appended. Equal keys get equal coefficients, and reordering the keys
+//! changes all the coefficients, so all parties must agree on the order of keys.
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{certified_point::CertifiedPoint, transcript::Transcript};
+
+const DOMAIN: &[u8] = b"generic-ec-zkp/key_agg";
+
+/// Aggregated public key along with coefficients of the individual keys
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct AggregatedKey<E: Curve> {
+    key: Point<E>,
+    coefficients: Vec<Scalar<E>>,
+}
+
+impl<E: Curve> AggregatedKey<E> {
+    /// Aggregated public key $\tilde X = \sum_i a_i \cdot X_i$
+    pub fn key(&self) -> &Point<E> {
+        &self.key
+    }
+
+    /// Coefficients $a_1, \dots, a_n$, in the same order as the aggregated keys
+    pub fn coefficients(&self) -> &[Scalar<E>] {
+        &self.coefficients
+    }
+
+    /// Coefficient $a_i$ of $i$-th key (indexed from zero)
+    ///
+    /// Returns `None` if `i` is out of bounds
+    pub fn coefficient(&self, i: usize) -> Option<&Scalar<E>> {
+        self.coefficients.get(i)
+    }
+
+    /// Weights secret key $x_i$ of $i$-th party with its coefficient: $a_i \cdot x_i$
+    ///
+    /// Sum of weighted secret keys of all the parties is the secret key of the aggregated key.
+    /// Returns `None` if `i` is out of bounds.
+    pub fn weighted_secret_key(
+        &self,
+        i: usize,
+        secret_key: &SecretScalar<E>,
+    ) -> Option<SecretScalar<E>> {
+        let coefficient = self.coefficient(i)?;
+        let mut weighted = secret_key * coefficient;
+        Some(SecretScalar::new(&mut weighted))
+    }
+}
+
+/// Aggregates public keys with MuSig-style coefficients $a_i = H(L, X_i)$
+///
+/// Coefficients are derived using hash function `D`. All parties must provide the keys in the
+/// same order. Aggregating an empty list results into zero point.
+pub fn aggregate_keys<E: Curve, D: Digest + Clone>(keys: &[Point<E>]) -> AggregatedKey<E> {
+    let mut transcript = Transcript::<D>::new(DOMAIN);
+    transcript.append_message(b"curve", E::CURVE_NAME.as_bytes());
+    transcript.append_points(b"keys", keys);
+
+    let coefficients = keys
+        .iter()
+        .map(|key| {
+            let mut transcript = transcript.clone();
+            transcript.append_point(b"key", key);
+            transcript.challenge_scalar(b"coefficient")
+        })
+        .collect::<Vec<_>>();
+    let key = Scalar::multiscalar_mul(coefficients.iter().zip(keys));
+
+    AggregatedKey { key, coefficients }
+}
+
+/// Aggregates public keys with proofs of possession
+///
+/// Proofs are verified when [`CertifiedPoint`] is constructed, so the keys are simply summed
+/// up and all coefficients are equal to one. Aggregating an empty list results into zero point.
+pub fn aggregate_certified_keys<E: Curve, D: Digest + Clone>(
+    keys: &[CertifiedPoint<E, D>],
+) -> AggregatedKey<E> {
+    AggregatedKey {
+        key: keys.iter().map(|key| **key.point()).sum(),
+        coefficients: alloc::vec![Scalar::one(); keys.len()],
+    }
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, NonZero, Point, Scalar, SecretScalar};
+    use sha2::Sha256;
+
+    use crate::certified_point::CertifiedPoint;
+
+    #[test]
+    fn aggregated_secret_key_matches<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        let secret_keys = [(); 4].map(|_| SecretScalar::<E>::random(&mut rng));
+        let public_keys = secret_keys.each_ref().map(|x| Point::generator() * x);
+
+        let aggregated = super::aggregate_keys::<E, Sha256>(&public_keys);
+        assert_eq!(aggregated.coefficients().len(), 4);
+        let secret_key: Scalar<E> = (0..4)
+            .map(|i| aggregated.weighted_secret_key(i, &secret_keys[i]).unwrap())
+            .sum();
+        assert_eq!(*aggregated.key(), Point::generator() * secret_key);
+        assert!(aggregated.weighted_secret_key(4, &secret_keys[0]).is_none());
+
+        // Coefficients depend on the order of keys
+        let mut reordered = public_keys;
+        reordered.swap(0, 1);
+        let aggregated2 = super::aggregate_keys::<E, Sha256>(&reordered);
+        assert_ne!(aggregated2.coefficient(0), aggregated.coefficient(1));
+    }
+
+    #[test]
+    fn rogue_key_is_not_cancelled<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        let honest_key = Point::generator() * Scalar::<E>::random(&mut rng);
+        let adversary_secret = Scalar::<E>::random(&mut rng);
+        let rogue_key = Point::generator() * adversary_secret - honest_key;
+
+        // Plain sum is fully controlled by the adversary, weighted sum is not
+        let aggregated = super::aggregate_keys::<E, Sha256>(&[honest_key, rogue_key]);
+        assert_ne!(*aggregated.key(), Point::generator() * adversary_secret);
+    }
+
+    #[test]
+    fn certified_keys<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        let secret_keys = [(); 3].map(|_| NonZero::<SecretScalar<E>>::random(&mut rng));
+        let certified = secret_keys
+            .each_ref()
+            .map(|x| CertifiedPoint::<E, Sha256>::certify(&mut rng, x));
+
+        let aggregated = super::aggregate_certified_keys(&certified);
+        let secret_key: Scalar<E> = secret_keys.iter().map(|x| x.as_ref()).sum();
+        assert_eq!(*aggregated.key(), Point::generator() * secret_key);
+        assert!(aggregated
+            .coefficients()
+            .iter()
+            .all(|a| *a == Scalar::one()));
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+    #[instantiate_tests(<generic_ec::curves::Ed25519>)]
+    mod ed25519 {}
+}
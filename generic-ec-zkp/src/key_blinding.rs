@@ -0,0 +1,159 @@
+//! Proof of correct key blinding
+//!
+//! Key owner can rerandomize its public key $A$ into blinded key $A' = h \cdot A$ (see
+//! [`Point::blind`]) which is unlinkable to $A$ for anyone who doesn't know the blinding factor
+//! $h$. When the blinding factor is secret, e.g. in credential systems, [`BlindedPublicKey`]
+//! convinces the verifier who knows $A$ that $A'$ is a correct blinding of $A$, without
+//! revealing $h$.
+//!
+//! The proof is a non-interactive [bundle](crate::proof_bundle) bound to the
+//! [transcript](crate::transcript).
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{key_blinding::BlindedPublicKey, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let transcript = || Transcript::<sha2::Sha256>::new(b"MYAPP-credential-show");
+//!
+//! let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let public_key = Point::generator() * &secret_key;
+//!
+//! // Key owner blinds its key
+//! let blinding = SecretScalar::random(&mut rng);
+//! let blinded = BlindedPublicKey::new(&mut rng, transcript(), &public_key, &blinding);
+//! let blinded_secret_key = blinding.expose_secret(|h| secret_key.blind(h));
+//!
+//! // Verifier checks that blinded key is derived from `public_key`
+//! let blinded_key = blinded.verify(&mut transcript(), &mut rng, &public_key)?;
+//! assert_eq!(blinded_key, Point::generator() * &blinded_secret_key);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Proof is a [proof bundle](crate::proof_bundle) for linear relation on witness $h$:
+//! $h \cdot A = A'$. Verifier additionally checks that $A' \ne \O$.
+
+use digest::Digest;
+use generic_ec::{Curve, Point, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    proof_bundle::{BundleProver, InvalidProof, LinearRelation, ProofBundle},
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Blinded public key $A'$ along with proof of correct blinding
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct BlindedPublicKey<E: Curve> {
+    /// Blinded public key $A' = h \cdot A$
+    pub blinded_key: Point<E>,
+    /// Proof of knowledge of $h$
+    pub proof: ProofBundle<E>,
+}
+
+impl<E: Curve> BlindedPublicKey<E> {
+    /// Blinds the public key and proves correctness of blinding
+    ///
+    /// Relation and proof commitments are appended to the `transcript`
+    pub fn new<D: Digest + Clone, R: RngCore + CryptoRng>(
+        rng: &mut R,
+        transcript: Transcript<D>,
+        public_key: &Point<E>,
+        blinding: &SecretScalar<E>,
+    ) -> Self {
+        let blinded_key = blinding.expose_secret(|h| public_key.blind(h));
+
+        let mut prover = BundleProver::new(transcript);
+        prover.add_satisfied(
+            rng,
+            relation(public_key, blinded_key),
+            core::slice::from_ref(blinding),
+        );
+        Self {
+            blinded_key,
+            proof: prover.prove(),
+        }
+    }
+
+    /// Verifies that blinded key is derived from `public_key`, returns blinded key
+    ///
+    /// Transcript must be in the same state as it was at the moment of proving.
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        public_key: &Point<E>,
+    ) -> Result<Point<E>, InvalidProof> {
+        if self.blinded_key.is_zero() {
+            return Err(InvalidProof);
+        }
+        let relation = relation(public_key, self.blinded_key);
+        self.proof
+            .verify(transcript, rng, core::slice::from_ref(&relation))?;
+        Ok(self.blinded_key)
+    }
+}
+
+/// $h \cdot A = A'$
+fn relation<E: Curve>(public_key: &Point<E>, blinded_key: Point<E>) -> LinearRelation<E> {
+    LinearRelation::new(1).with_equation([(0, *public_key)], blinded_key)
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, Point, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::BlindedPublicKey;
+    use crate::transcript::Transcript;
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn blinding<E: Curve>() {
+        let mut rng = DevRng::new();
+        let secret_key = SecretScalar::<E>::random(&mut rng);
+        let public_key = Point::generator() * &secret_key;
+        let blinding = SecretScalar::<E>::random(&mut rng);
+
+        let blinded = BlindedPublicKey::new(&mut rng, transcript(), &public_key, &blinding);
+        let blinded_key = blinded
+            .verify(&mut transcript(), &mut rng, &public_key)
+            .unwrap();
+        assert_ne!(blinded_key, public_key);
+        assert_eq!(
+            blinded_key,
+            Point::generator() * blinding.expose_secret(|h| secret_key.blind(h))
+        );
+
+        // Blinded key is not derived from other key
+        let other_key = Point::generator() * SecretScalar::<E>::random(&mut rng);
+        assert!(blinded
+            .verify(&mut transcript(), &mut rng, &other_key)
+            .is_err());
+
+        // Zero blinding is rejected
+        let zero =
+            BlindedPublicKey::new(&mut rng, transcript(), &public_key, &SecretScalar::zero());
+        assert!(zero
+            .verify(&mut transcript(), &mut rng, &public_key)
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -0,0 +1,722 @@
+//! Key share of a threshold protocol
+//!
+//! [`KeyShare`] is the artifact produced by a key generation protocol (or a trusted dealer) and
+//! consumed by signing and other threshold modules. It bundles party's secret share with public
+//! data that everyone in the group agrees on: the shared public key, and public shares of every
+//! party $X_j = x_j \cdot G$, which are needed to verify partial results (partial signatures,
+//! partial evaluations, etc.) of other parties.
+//!
+//! Key share received from an untrusted source (e.g. deserialized from disk or received from the
+//! network) must be checked via [`KeyShare::verify_consistency`] before use.
+//!
+//! [`WeightedKeyShare`] is a key share of weighted threshold scheme, where each party holds
+//! several shares proportionally to its weight (e.g. stake of a validator).
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{key_share::KeyShare, party_index::PartyIndex, polynomial::Polynomial};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Trusted dealer shares a key among 3 parties with threshold 2
+//! let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let f = Polynomial::sample_with_const_term(&mut rng, 1, secret_key.clone());
+//! let shares: Vec<_> = (0..3)
+//!     .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+//!     .collect();
+//!
+//! let key_share = KeyShare {
+//!     i: PartyIndex(1),
+//!     t: 2,
+//!     x: shares[1].clone(),
+//!     shared_public_key: Point::generator() * &secret_key,
+//!     public_shares: shares.iter().map(|x_j| Point::generator() * x_j).collect(),
+//! };
+//! key_share.verify_consistency()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use alloc::vec::Vec;
+
+use generic_ec::{Curve, NonZero, Point, Scalar, SecretScalar};
+
+use crate::{party_index::PartyIndex, polynomial::LagrangeBasis};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Key share of party $i$ in $t$-out-of-$n$ threshold scheme
+///
+/// Secret key $x$ is shared via polynomial $f$ of degree $t - 1$, $f(0) = x$. Party $i$ holds
+/// secret share $x_i = f(i + 1)$, see [`party_index`](crate::party_index).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct KeyShare<E: Curve> {
+    /// Index of the party $i$
+    pub i: PartyIndex,
+    /// Threshold $t$: amount of parties needed to use the key
+    pub t: u16,
+    /// Secret share $x_i$
+    pub x: SecretScalar<E>,
+    /// Shared public key $X = x \cdot G$
+    pub shared_public_key: Point<E>,
+    /// Public shares of all parties $X_j = x_j \cdot G$, $j \in [0, n)$
+    pub public_shares: Vec<Point<E>>,
+}
+
+impl<E: Curve> KeyShare<E> {
+    /// Amount of parties $n$ holding the key
+    pub fn n(&self) -> usize {
+        self.public_shares.len()
+    }
+
+    /// Returns public share of party `j`, or `None` if `j` is out of range
+    pub fn public_share(&self, j: PartyIndex) -> Option<Point<E>> {
+        self.public_shares.get(usize::from(j)).copied()
+    }
+
+    /// Checks that key share is consistent
+    ///
+    /// Checks that:
+    /// * $1 \le t \le n$, and $i < n$
+    /// * Secret share matches public share of the party: $X_i = x_i \cdot G$
+    /// * Public shares and the shared public key lie on the same polynomial of degree $t - 1$
+    ///
+    /// Takes $O(n t^2)$ scalar multiplications.
+    pub fn verify_consistency(&self) -> Result<(), InconsistentKeyShare> {
+        let n = self.n();
+        let t = usize::from(self.t);
+        if t == 0 || t > n || n > usize::from(u16::MAX) + 1 {
+            return Err(InconsistentKeyShare::InvalidThreshold);
+        }
+        if Point::generator() * &self.x
+            != self
+                .public_share(self.i)
+                .ok_or(InconsistentKeyShare::IndexOutOfRange)?
+        {
+            return Err(InconsistentKeyShare::SecretShareMismatch);
+        }
+        verify_public_shares(t, &self.shared_public_key, &self.public_shares)
+    }
+}
+
+/// Checks that public shares $X_j$ at points $j + 1$ and the shared public key lie on the same
+/// polynomial of degree $t - 1$
+///
+/// Expects $1 \le t \le n$ and $n \le 2^{16}$.
+fn verify_public_shares<E: Curve>(
+    t: usize,
+    shared_public_key: &Point<E>,
+    public_shares: &[Point<E>],
+) -> Result<(), InconsistentKeyShare> {
+    // Any `t` public shares define the polynomial. We interpolate it from the first `t`
+    // shares, and check that all other shares and the public key match it.
+    let xs: Vec<NonZero<Scalar<E>>> = (0..=u16::MAX)
+        .take(public_shares.len())
+        .map(|j| PartyIndex(j).share_index().to_scalar())
+        .collect();
+    let (defining, rest) = public_shares.split_at(t);
+    let interpolate = |x: Scalar<E>| {
+        LagrangeBasis::new(x, &xs[..t]).and_then(|basis| basis.interpolate(defining))
+    };
+    if interpolate(Scalar::zero()) != Some(*shared_public_key) {
+        return Err(InconsistentKeyShare::PublicSharesMismatch);
+    }
+    for (x_j, public_share) in xs[t..].iter().zip(rest) {
+        if interpolate(*x_j.as_ref()) != Some(*public_share) {
+            return Err(InconsistentKeyShare::PublicSharesMismatch);
+        }
+    }
+    Ok(())
+}
+
+impl<E: Curve> KeyShare<E> {
+    /// Adds tweak $t$ to the shared secret key: $x' = x + t$
+    ///
+    /// Every party applies the same tweak to its own key share. Secret share and all public
+    /// shares are shifted by the tweak, $x'_i = x_i + t$, which corresponds to sharing
+    /// polynomial $f'(z) = f(z) + t$. Lagrange coefficients at $0$ sum up to $1$, so any $t$
+    /// parties reconstruct $\sum_i \lambda_i x'_i = x + t$ without any extra adjustments.
+    pub fn tweak(&self, tweak: &Scalar<E>) -> Self {
+        let tweak_public = Point::generator() * tweak;
+        Self {
+            i: self.i,
+            t: self.t,
+            x: SecretScalar::new(&mut (tweak + &self.x)),
+            shared_public_key: self.shared_public_key + tweak_public,
+            public_shares: self
+                .public_shares
+                .iter()
+                .map(|x_j| x_j + tweak_public)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "hd")]
+impl<E: generic_ec::bip39::MasterKeyCurve> KeyShare<E> {
+    /// Derives key share of non-hardened BIP32 child of the shared key
+    ///
+    /// The shared public key along with `chain_code` form the extended public key. Derivation
+    /// along `path` yields the child public key and tweak $t$, which is then
+    /// [applied](Self::tweak) to the key share. All parties must use the same `chain_code` and
+    /// `path` to obtain key shares of the same child key.
+    ///
+    /// Parties must not derive children of their secret shares individually: BIP32 tweak depends
+    /// on the public key, so parties would apply different tweaks and shares would no longer
+    /// reconstruct to any key.
+    ///
+    /// Returns derived key share and extended public key of the child. Returns error if `path`
+    /// contains a hardened index, or the curve supports only hardened derivation.
+    pub fn derive_child(
+        &self,
+        chain_code: &[u8; 32],
+        path: &generic_ec::bip39::DerivationPath,
+    ) -> Result<
+        (Self, generic_ec::bip39::ExtendedPublicKey<E>),
+        generic_ec::errors::InvalidDerivationPath,
+    > {
+        let parent = generic_ec::bip39::ExtendedPublicKey::new(self.shared_public_key, *chain_code);
+        let (child, tweak) = parent.derive_path_with_tweak(path)?;
+        Ok((self.tweak(&tweak), child))
+    }
+}
+
+/// Key share of party $i$ in weighted threshold scheme
+///
+/// Every party $j$ has weight $w_j$ (e.g. its stake) and holds $w_j$ virtual shares of the secret
+/// key. Virtual shares are numbered consecutively across the parties: party $j$ holds shares
+/// $o_j, \dots, o_j + w_j - 1$ where $o_j = \sum_{m < j} w_m$. Virtual share $v$ is the value
+/// $f(v + 1)$ of the polynomial $f$ of degree $t - 1$, $f(0) = x$, i.e. it's evaluated at the same
+/// point as share of [`PartyIndex(v)`](PartyIndex) in [`KeyShare`].
+///
+/// Any set of parties with total weight at least $t$ can use the key. Each of them turns its
+/// virtual shares into an [additive share](Self::additive_share) of the secret key.
+///
+/// ## Example
+/// ```rust
+/// use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+/// use generic_ec_zkp::{
+///     key_share::WeightedKeyShare, party_index::PartyIndex, polynomial::Polynomial,
+/// };
+/// # let mut rng = rand::rngs::OsRng;
+///
+/// // Trusted dealer shares a key among 3 parties with weights 3, 1, 2 and threshold 4
+/// let weights = vec![3, 1, 2];
+/// let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+/// let f = Polynomial::sample_with_const_term(&mut rng, 3, secret_key.clone());
+/// let shares: Vec<_> = (0..6)
+///     .map(|v| f.eval_ct(&PartyIndex(v).share_index().to_scalar()))
+///     .collect();
+///
+/// let key_share = WeightedKeyShare {
+///     i: PartyIndex(2),
+///     t: 4,
+///     weights,
+///     x: shares[4..6].to_vec(),
+///     shared_public_key: Point::generator() * &secret_key,
+///     public_shares: shares.iter().map(|x_v| Point::generator() * x_v).collect(),
+/// };
+/// key_share.verify_consistency()?;
+///
+/// // Parties 0 and 2 have enough weight to use the key
+/// let signers = [PartyIndex(0), PartyIndex(2)];
+/// let additive_share = key_share.additive_share(&signers).ok_or("not enough weight")?;
+/// assert_eq!(
+///     Point::generator() * &additive_share,
+///     key_share.additive_public_share(PartyIndex(2), &signers).ok_or("not enough weight")?,
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct WeightedKeyShare<E: Curve> {
+    /// Index of the party $i$
+    pub i: PartyIndex,
+    /// Threshold $t$: total weight of parties needed to use the key
+    pub t: u16,
+    /// Weights of all parties $w_j$, $j \in [0, n)$
+    pub weights: Vec<u16>,
+    /// Virtual secret shares of the party, $w_i$ of them
+    pub x: Vec<SecretScalar<E>>,
+    /// Shared public key $X = x \cdot G$
+    pub shared_public_key: Point<E>,
+    /// Public shares of all virtual shares $X_v = x_v \cdot G$, $v \in [0, \sum_j w_j)$
+    pub public_shares: Vec<Point<E>>,
+}
+
+impl<E: Curve> WeightedKeyShare<E> {
+    /// Amount of parties $n$ holding the key
+    pub fn n(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Total weight of all parties $W = \sum_j w_j$, i.e. amount of virtual shares
+    pub fn total_weight(&self) -> usize {
+        self.weights.iter().map(|w| usize::from(*w)).sum()
+    }
+
+    /// Returns range of virtual shares held by party `j`, or `None` if `j` is out of range
+    pub fn virtual_shares(&self, j: PartyIndex) -> Option<core::ops::Range<usize>> {
+        let j = usize::from(j);
+        let weight = usize::from(*self.weights.get(j)?);
+        let offset = self.weights[..j]
+            .iter()
+            .map(|w| usize::from(*w))
+            .sum::<usize>();
+        Some(offset..offset + weight)
+    }
+
+    /// Returns public shares of party `j`, or `None` if `j` is out of range
+    pub fn public_shares_of(&self, j: PartyIndex) -> Option<&[Point<E>]> {
+        self.public_shares.get(self.virtual_shares(j)?)
+    }
+
+    /// Checks that key share is consistent
+    ///
+    /// Checks that:
+    /// * $1 \le t \le W$, and $i < n$
+    /// * Party has exactly $w_i$ secret shares, and there are $W$ public shares
+    /// * Secret shares match public shares of the party
+    /// * Public shares and the shared public key lie on the same polynomial of degree $t - 1$
+    ///
+    /// Takes $O(W t^2)$ scalar multiplications.
+    pub fn verify_consistency(&self) -> Result<(), InconsistentKeyShare> {
+        let total_weight = self.total_weight();
+        let t = usize::from(self.t);
+        if t == 0 || t > total_weight || total_weight > usize::from(u16::MAX) + 1 {
+            return Err(InconsistentKeyShare::InvalidThreshold);
+        }
+        if self.public_shares.len() != total_weight {
+            return Err(InconsistentKeyShare::WeightsMismatch);
+        }
+        let public_shares = self
+            .public_shares_of(self.i)
+            .ok_or(InconsistentKeyShare::IndexOutOfRange)?;
+        if public_shares.len() != self.x.len() {
+            return Err(InconsistentKeyShare::WeightsMismatch);
+        }
+        if self
+            .x
+            .iter()
+            .zip(public_shares)
+            .any(|(x_v, public_share)| Point::generator() * x_v != *public_share)
+        {
+            return Err(InconsistentKeyShare::SecretShareMismatch);
+        }
+        verify_public_shares(t, &self.shared_public_key, &self.public_shares)
+    }
+
+    /// Lagrange basis at $0$ over all virtual shares of `signers`
+    ///
+    /// Returns basis along with virtual share indexes it's defined at
+    fn signers_basis(&self, signers: &[PartyIndex]) -> Option<(LagrangeBasis<E>, Vec<usize>)> {
+        let mut indexes = Vec::with_capacity(self.total_weight());
+        for j in signers {
+            indexes.extend(self.virtual_shares(*j)?);
+        }
+        if indexes.len() < usize::from(self.t) {
+            return None;
+        }
+        let xs = indexes
+            .iter()
+            .map(|v| Some(PartyIndex::try_from(*v).ok()?.share_index().to_scalar()))
+            .collect::<Option<Vec<_>>>()?;
+        Some((LagrangeBasis::new(Scalar::zero(), &xs)?, indexes))
+    }
+
+    /// Lagrange coefficients of virtual shares of party `j` for reconstructing the secret key
+    /// by `signers`
+    ///
+    /// Coefficients of all virtual shares of all `signers` interpolate the polynomial at $0$.
+    /// Returns `None` if total weight of `signers` is less than $t$, `signers` aren't distinct,
+    /// or `j` is not among the `signers`.
+    pub fn lagrange_coefficients(
+        &self,
+        j: PartyIndex,
+        signers: &[PartyIndex],
+    ) -> Option<Vec<Scalar<E>>> {
+        if !signers.contains(&j) {
+            return None;
+        }
+        let (basis, indexes) = self.signers_basis(signers)?;
+        let own = self.virtual_shares(j)?;
+        Some(
+            indexes
+                .iter()
+                .zip(basis.coefficients())
+                .filter(|(v, _)| own.contains(v))
+                .map(|(_, lambda_v)| *lambda_v.as_ref())
+                .collect(),
+        )
+    }
+
+    /// Additive share of the secret key $\sum_v \lambda_v x_v$ of the party among `signers`
+    ///
+    /// Additive shares of all `signers` sum up to the secret key $x$. Returns `None` in the same
+    /// cases as [`lagrange_coefficients`](Self::lagrange_coefficients).
+    pub fn additive_share(&self, signers: &[PartyIndex]) -> Option<SecretScalar<E>> {
+        let lambdas = self.lagrange_coefficients(self.i, signers)?;
+        let mut share = self
+            .x
+            .iter()
+            .zip(&lambdas)
+            .map(|(x_v, lambda_v)| x_v * lambda_v)
+            .sum::<Scalar<E>>();
+        Some(SecretScalar::new(&mut share))
+    }
+
+    /// Additive public share $\sum_v \lambda_v X_v$ of party `j` among `signers`
+    ///
+    /// Matches [additive share](Self::additive_share) of party `j`, used to verify its partial
+    /// results. Returns `None` in the same cases as
+    /// [`lagrange_coefficients`](Self::lagrange_coefficients).
+    pub fn additive_public_share(&self, j: PartyIndex, signers: &[PartyIndex]) -> Option<Point<E>> {
+        let lambdas = self.lagrange_coefficients(j, signers)?;
+        let public_shares = self.public_shares_of(j)?;
+        Some(Scalar::multiscalar_mul(lambdas.iter().zip(public_shares)))
+    }
+}
+
+/// Key share is inconsistent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InconsistentKeyShare {
+    /// Threshold is zero or exceeds amount of parties, or there are too many parties
+    InvalidThreshold,
+    /// Index of the party is out of range
+    IndexOutOfRange,
+    /// Secret share doesn't match public share of the party
+    SecretShareMismatch,
+    /// Public shares and shared public key don't lie on a polynomial of degree $t - 1$
+    PublicSharesMismatch,
+    /// Amount of shares doesn't match the weights
+    WeightsMismatch,
+}
+
+impl core::fmt::Display for InconsistentKeyShare {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::InvalidThreshold => "invalid threshold or amount of parties",
+            Self::IndexOutOfRange => "party index is out of range",
+            Self::SecretShareMismatch => "secret share doesn't match public share",
+            Self::PublicSharesMismatch => "public shares are inconsistent with each other",
+            Self::WeightsMismatch => "amount of shares doesn't match the weights",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InconsistentKeyShare {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{InconsistentKeyShare, KeyShare, WeightedKeyShare};
+    use crate::{
+        party_index::PartyIndex,
+        polynomial::{LagrangeBasis, Polynomial},
+    };
+
+    fn key_shares<E: Curve>(rng: &mut DevRng, t: u16, n: u16) -> Vec<KeyShare<E>> {
+        let secret_key = SecretScalar::<E>::random(rng);
+        let f = Polynomial::sample_with_const_term(rng, usize::from(t) - 1, secret_key.clone());
+        let shares: Vec<_> = (0..n)
+            .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+            .collect();
+        let public_shares: Vec<_> = shares.iter().map(|x| Point::generator() * x).collect();
+        (0..n)
+            .zip(shares)
+            .map(|(i, x)| KeyShare {
+                i: PartyIndex(i),
+                t,
+                x,
+                shared_public_key: Point::generator() * &secret_key,
+                public_shares: public_shares.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn consistency<E: Curve>() {
+        let mut rng = DevRng::new();
+        for (t, n) in [(1, 1), (1, 3), (2, 3), (3, 5), (5, 5)] {
+            for key_share in key_shares::<E>(&mut rng, t, n) {
+                key_share.verify_consistency().unwrap();
+            }
+        }
+
+        let key_share = key_shares::<E>(&mut rng, 3, 5).remove(2);
+
+        let mut invalid = key_share.clone();
+        invalid.t = 6;
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::InvalidThreshold)
+        );
+        invalid.t = 0;
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::InvalidThreshold)
+        );
+
+        let mut invalid = key_share.clone();
+        invalid.i = PartyIndex(5);
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::IndexOutOfRange)
+        );
+        invalid.i = PartyIndex(1);
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::SecretShareMismatch)
+        );
+
+        // Public shares don't match the threshold
+        let mut invalid = key_share.clone();
+        invalid.t = 2;
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::PublicSharesMismatch)
+        );
+
+        for j in [0, 4] {
+            let mut invalid = key_share.clone();
+            invalid.public_shares[j] += Point::generator();
+            assert_eq!(
+                invalid.verify_consistency(),
+                Err(InconsistentKeyShare::PublicSharesMismatch)
+            );
+        }
+
+        let mut invalid = key_share;
+        invalid.shared_public_key += Point::generator();
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::PublicSharesMismatch)
+        );
+    }
+
+    #[test]
+    fn tweak<E: Curve>() {
+        let mut rng = DevRng::new();
+        let key_shares = key_shares::<E>(&mut rng, 2, 3);
+        let tweak = Scalar::<E>::random(&mut rng);
+
+        let tweaked: Vec<_> = key_shares.iter().map(|s| s.tweak(&tweak)).collect();
+        for key_share in &tweaked {
+            key_share.verify_consistency().unwrap();
+        }
+        let expected_public_key = key_shares[0].shared_public_key + Point::generator() * tweak;
+        assert_eq!(tweaked[0].shared_public_key, expected_public_key);
+
+        // Any `t` parties reconstruct the tweaked key
+        let xs = [
+            PartyIndex(0).share_index().to_scalar(),
+            PartyIndex(2).share_index().to_scalar(),
+        ];
+        let shares = [
+            tweaked[0].x.expose_secret(|x| *x),
+            tweaked[2].x.expose_secret(|x| *x),
+        ];
+        let secret_key = LagrangeBasis::new(Scalar::zero(), &xs)
+            .unwrap()
+            .interpolate(&shares)
+            .unwrap();
+        assert_eq!(Point::generator() * secret_key, expected_public_key);
+    }
+
+    fn weighted_key_shares<E: Curve>(
+        rng: &mut DevRng,
+        t: u16,
+        weights: &[u16],
+    ) -> (SecretScalar<E>, Vec<WeightedKeyShare<E>>) {
+        let secret_key = SecretScalar::<E>::random(rng);
+        let f = Polynomial::sample_with_const_term(rng, usize::from(t) - 1, secret_key.clone());
+        let total_weight: u16 = weights.iter().sum();
+        let shares: Vec<_> = (0..total_weight)
+            .map(|v| f.eval_ct(&PartyIndex(v).share_index().to_scalar()))
+            .collect();
+        let public_shares: Vec<_> = shares.iter().map(|x| Point::generator() * x).collect();
+        let mut offset = 0;
+        let key_shares = (0..)
+            .zip(weights)
+            .map(|(i, w)| {
+                let x = shares[offset..offset + usize::from(*w)].to_vec();
+                offset += usize::from(*w);
+                WeightedKeyShare {
+                    i: PartyIndex(i),
+                    t,
+                    weights: weights.to_vec(),
+                    x,
+                    shared_public_key: Point::generator() * &secret_key,
+                    public_shares: public_shares.clone(),
+                }
+            })
+            .collect();
+        (secret_key, key_shares)
+    }
+
+    #[test]
+    fn weighted_reconstruction<E: Curve>() {
+        let mut rng = DevRng::new();
+        let weights = [3, 1, 0, 2, 4];
+        let (secret_key, key_shares) = weighted_key_shares::<E>(&mut rng, 5, &weights);
+        for key_share in &key_shares {
+            key_share.verify_consistency().unwrap();
+        }
+        assert_eq!(key_shares[3].virtual_shares(PartyIndex(3)), Some(4..6));
+
+        for signers in [&[0, 3][..], &[4, 1], &[1, 2, 3, 4], &[0, 1, 2, 3, 4]] {
+            let signers: Vec<_> = signers.iter().copied().map(PartyIndex).collect();
+            let mut reconstructed = Scalar::<E>::zero();
+            for j in &signers {
+                let key_share = &key_shares[usize::from(*j)];
+                let additive_share = key_share.additive_share(&signers).unwrap();
+                assert_eq!(
+                    Some(Point::generator() * &additive_share),
+                    key_shares[0].additive_public_share(*j, &signers)
+                );
+                reconstructed += &additive_share;
+            }
+            assert_eq!(
+                Point::generator() * reconstructed,
+                Point::generator() * &secret_key
+            );
+        }
+
+        // Not enough weight, duplicated signers, and party not among signers
+        let signers = [PartyIndex(0), PartyIndex(1)];
+        assert!(key_shares[0].additive_share(&signers).is_none());
+        let signers = [PartyIndex(0), PartyIndex(0)];
+        assert!(key_shares[0].additive_share(&signers).is_none());
+        let signers = [PartyIndex(0), PartyIndex(4)];
+        assert!(key_shares[3].additive_share(&signers).is_none());
+        let signers = [PartyIndex(0), PartyIndex(5)];
+        assert!(key_shares[0].additive_share(&signers).is_none());
+    }
+
+    #[test]
+    fn weighted_consistency<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (_, mut key_shares) = weighted_key_shares::<E>(&mut rng, 3, &[2, 1, 2]);
+        let key_share = key_shares.remove(2);
+
+        let mut invalid = key_share.clone();
+        invalid.t = 6;
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::InvalidThreshold)
+        );
+
+        let mut invalid = key_share.clone();
+        invalid.i = PartyIndex(3);
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::IndexOutOfRange)
+        );
+
+        let mut invalid = key_share.clone();
+        invalid.x.pop();
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::WeightsMismatch)
+        );
+
+        let mut invalid = key_share.clone();
+        invalid.x.swap(0, 1);
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::SecretShareMismatch)
+        );
+
+        let mut invalid = key_share;
+        invalid.public_shares[0] += Point::generator();
+        assert_eq!(
+            invalid.verify_consistency(),
+            Err(InconsistentKeyShare::PublicSharesMismatch)
+        );
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
+
+#[cfg(all(test, feature = "hd"))]
+mod hd_tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{
+        bip39::{self, DerivationPath},
+        curves::{Ed25519, Secp256k1},
+        Point,
+    };
+
+    use super::KeyShare;
+    use crate::{party_index::PartyIndex, polynomial::Polynomial};
+
+    #[test]
+    fn shared_key_derivation_matches_bip32() {
+        let mut rng = rand_dev::DevRng::new();
+        let master_key = bip39::master_key::<Secp256k1>(b"threshold hd derivation seed");
+        let secret_key = master_key.secret_key().as_ref().clone();
+        let f = Polynomial::sample_with_const_term(&mut rng, 1, secret_key.clone());
+        let shares: Vec<_> = (0..3)
+            .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+            .collect();
+        let public_shares: Vec<_> = shares.iter().map(|x| Point::generator() * x).collect();
+        let key_shares: Vec<_> = (0..3)
+            .zip(shares)
+            .map(|(i, x)| KeyShare {
+                i: PartyIndex(i),
+                t: 2,
+                x,
+                shared_public_key: Point::generator() * &secret_key,
+                public_shares: public_shares.clone(),
+            })
+            .collect();
+
+        let path: DerivationPath = "m/0/1/2".parse().unwrap();
+        let expected = master_key.derive_path(&path).unwrap();
+        for key_share in &key_shares {
+            let (child, child_xpub) = key_share
+                .derive_child(master_key.chain_code(), &path)
+                .unwrap();
+            child.verify_consistency().unwrap();
+            assert_eq!(
+                child.shared_public_key,
+                Point::generator() * expected.secret_key()
+            );
+            assert_eq!(child_xpub, expected.extended_public_key());
+        }
+
+        // Hardened derivation requires the secret key
+        let hardened = "m/0'".parse().unwrap();
+        assert!(key_shares[0]
+            .derive_child(master_key.chain_code(), &hardened)
+            .is_err());
+    }
+
+    #[test]
+    fn ed25519_is_not_supported() {
+        let mut rng = rand_dev::DevRng::new();
+        let x = generic_ec::SecretScalar::<Ed25519>::random(&mut rng);
+        let key_share = KeyShare {
+            i: PartyIndex(0),
+            t: 1,
+            shared_public_key: Point::generator() * &x,
+            public_shares: alloc::vec![Point::generator() * &x],
+            x,
+        };
+        let path = "m/0".parse().unwrap();
+        assert!(key_share.derive_child(&[0; 32], &path).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! Proof of knowledge of a witness satisfying a system of group-linear equations
+//!
+//! Statement is given as a matrix of bases $B$ of size $m \times n$ and a vector of images $X$
+//! of size $m$: prover shows knowledge of witness $w_1, \dots, w_n$ such that
+//! $\sum_j w_j \cdot B_{i,j} = X_i$ for every $i$. Identity entries of the matrix mean that
+//! the witness doesn't appear in the equation. Many protocols are exactly this shape: Schnorr
+//! PoK, DLEQ, proof of representation, ElGamal encryption of a known plaintext, etc.
+//!
+//! Proof consists of $m$ points and $n$ scalars. It's a matrix-shaped front-end for
+//! [proof bundle](crate::proof_bundle) proving a single [`LinearRelation`]. Use
+//! [`zk_statement!`](crate::zk_statement) instead if statement shape is known at compile time.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{linear_proof, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//! # let random_point = |rng: &mut _| Point::<Secp256k1>::generator() * SecretScalar::random(rng);
+//! # let (h, g2) = (random_point(&mut rng), random_point(&mut rng));
+//!
+//! // X = x G, Y = x H + r G2
+//! let g = Point::generator().to_point();
+//! let bases = [[g, Point::zero()], [h, g2]];
+//! let witness = [SecretScalar::random(&mut rng), SecretScalar::random(&mut rng)];
+//! let images = linear_proof::images(&bases, &witness)?;
+//!
+//! let transcript = || Transcript::<sha2::Sha256>::new(b"MYAPP-linear-proof");
+//! let proof = linear_proof::prove(&mut rng, transcript(), &bases, &witness)?;
+//! linear_proof::verify(&mut transcript(), &mut rng, &bases, &images, &proof)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{Curve, Point, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    proof_bundle::{
+        BundleProver, InvalidInput, InvalidProof, LinearProof, LinearRelation, ProofBundle,
+    },
+    transcript::Transcript,
+};
+
+/// Computes images $X_i = \sum_j w_j \cdot B_{i,j}$
+///
+/// Returns error if matrix rows have different length, or it doesn't match length of the witness
+pub fn images<E: Curve>(
+    bases: &[impl AsRef<[Point<E>]>],
+    witness: &[SecretScalar<E>],
+) -> Result<Vec<Point<E>>, InvalidInput> {
+    if bases.iter().any(|row| row.as_ref().len() != witness.len()) {
+        return Err(InvalidInput);
+    }
+    Ok(bases
+        .iter()
+        // Witness is secret, so we can't use multiscalar multiplication as it's not constant time
+        .map(|row| witness.iter().zip(row.as_ref()).map(|(w, b)| b * w).sum())
+        .collect())
+}
+
+/// Constructs linear relation corresponding to the matrix statement
+///
+/// Returns `None` if matrix rows have different length, or amount of rows doesn't match amount
+/// of images
+pub fn relation<E: Curve>(
+    bases: &[impl AsRef<[Point<E>]>],
+    images: &[Point<E>],
+) -> Option<LinearRelation<E>> {
+    let witnesses = bases.first().map(|row| row.as_ref().len()).unwrap_or(0);
+    if bases.len() != images.len() || bases.iter().any(|row| row.as_ref().len() != witnesses) {
+        return None;
+    }
+    Some(
+        bases
+            .iter()
+            .zip(images)
+            .fold(LinearRelation::new(witnesses), |relation, (row, image)| {
+                let terms = row
+                    .as_ref()
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .filter(|(_, base)| !base.is_zero());
+                relation.with_equation(terms, *image)
+            }),
+    )
+}
+
+/// Proves knowledge of `witness` satisfying the statement
+///
+/// Images $X = B \cdot w$ are computed from the witness, they must be known to the verifier (see
+/// [`images`]). Relation and proof commitments are appended to the `transcript`.
+///
+/// Returns error if matrix rows have different length, or it doesn't match length of the witness
+pub fn prove<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    transcript: Transcript<D>,
+    bases: &[impl AsRef<[Point<E>]>],
+    witness: &[SecretScalar<E>],
+) -> Result<LinearProof<E>, InvalidInput> {
+    let images = images(bases, witness)?;
+    let relation = relation(bases, &images).ok_or(InvalidInput)?;
+
+    let mut prover = BundleProver::new(transcript);
+    prover.add(rng, relation, witness)?;
+    let ProofBundle { proofs } = prover.prove();
+    proofs.into_iter().next().ok_or(InvalidInput)
+}
+
+/// Verifies that prover knows a witness $w$ such as $B \cdot w = X$
+///
+/// Transcript must be in the same state as it was at the moment of proving.
+pub fn verify<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    transcript: &mut Transcript<D>,
+    rng: &mut R,
+    bases: &[impl AsRef<[Point<E>]>],
+    images: &[Point<E>],
+    proof: &LinearProof<E>,
+) -> Result<(), InvalidProof> {
+    let relation = relation(bases, images).ok_or(InvalidProof)?;
+    let bundle = ProofBundle {
+        proofs: alloc::vec![proof.clone()],
+    };
+    bundle.verify(transcript, rng, core::slice::from_ref(&relation))
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use crate::transcript::Transcript;
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn prove_and_verify<E: Curve>() {
+        let mut rng = DevRng::new();
+        let bases: Vec<Vec<Point<E>>> = (0..3)
+            .map(|i| {
+                (0..4)
+                    .map(|j| {
+                        if (i + j) % 3 == 0 {
+                            Point::zero()
+                        } else {
+                            Point::generator() * Scalar::random(&mut rng)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let witness: Vec<_> = (0..4)
+            .map(|_| SecretScalar::<E>::random(&mut rng))
+            .collect();
+        let images = super::images(&bases, &witness).unwrap();
+
+        let proof = super::prove(&mut rng, transcript(), &bases, &witness).unwrap();
+        assert_eq!(proof.commitments.len(), 3);
+        assert_eq!(proof.responses.len(), 4);
+        super::verify(&mut transcript(), &mut rng, &bases, &images, &proof).unwrap();
+
+        // Wrong images
+        let mut wrong_images = images.clone();
+        wrong_images[1] += Point::generator();
+        assert!(super::verify(&mut transcript(), &mut rng, &bases, &wrong_images, &proof).is_err());
+
+        // Malformed statement
+        assert!(super::verify(&mut transcript(), &mut rng, &bases, &images[1..], &proof).is_err());
+        assert!(super::prove(&mut rng, transcript(), &bases, &witness[1..]).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -0,0 +1,331 @@
+//! Proof that a ciphertext encrypts discrete log of a point $\Pi^\text{log*}$
+//!
+//! MPC protocols (e.g. threshold ECDSA) often need to prove that ciphertext $C = \text{Enc}(x; \rho)$
+//! of external encryption scheme (Paillier, class groups, etc.) and elliptic point $X = x \cdot B$
+//! hide the same $x$. The proof consists of two halves sharing one challenge: the EC half,
+//! implemented in this module, and the encryption half, provided by the encryption scheme via
+//! [`LinkedEncryption`] trait. MPC libraries implement the trait for their encryption scheme, and
+//! get the complete proof.
+//!
+//! The trait is implemented for [ElGamal](crate::elgamal) encryption key, which can be used as a
+//! reference implementation.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{elgamal, linked_encryption::{self, Statement}, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let (_, ek) = elgamal::keygen::<Secp256k1, _>(&mut rng);
+//!
+//! let x = SecretScalar::random(&mut rng);
+//! let rho = SecretScalar::random(&mut rng);
+//! let statement = Statement {
+//!     ciphertext: ek.encrypt_scalar(&x, &rho),
+//!     base: Point::generator().to_point(),
+//!     point: Point::generator() * &x,
+//! };
+//!
+//! let transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! let proof = linked_encryption::prove(&mut rng, transcript, &ek, &statement, &(x, rho));
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! proof.verify(&mut transcript, &ek, &statement)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! * Prove
+//!   1. Prover [commits](LinkedEncryption::commit) ephemeral secrets $\alpha, r$ on the encryption
+//!      side: $A = \text{Enc}(\alpha; r)$, and computes $Y = \alpha \cdot B$
+//!   2. Statement, $A$ and $Y$ are appended to the transcript, challenge $e$ is derived from it
+//!   3. Prover computes [response](LinkedEncryption::respond) on the encryption side, which
+//!      includes $z = \alpha + e x$
+//! * Verification \
+//!   Verifier derives $e$, checks the encryption side via [`LinkedEncryption::verify`] (e.g. that
+//!   $\text{Enc}(z; \dots) = A \oplus e \odot C$ and $z$ is within expected range), and checks that
+//!   $z \cdot B \\? Y + e \cdot X$
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{elgamal, transcript::Transcript};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Encryption side of the proof
+///
+/// Implemented by an encryption key of additively homomorphic encryption scheme.
+pub trait LinkedEncryption<E: Curve> {
+    /// Ciphertext $C = \text{Enc}(x; \rho)$
+    type Ciphertext;
+    /// Plaintext $x$ and encryption nonce $\rho$
+    type Witness;
+    /// Prover ephemeral secrets $\alpha, r$
+    type ProverSecret;
+    /// Commitment $A = \text{Enc}(\alpha; r)$
+    type Commitment;
+    /// Response which includes $z = \alpha + e x$
+    type Response;
+
+    /// Samples ephemeral secrets and commits to them
+    ///
+    /// If plaintext space is larger than scalar field (e.g. Paillier), $\alpha$ must be sampled
+    /// from a range large enough to statistically hide $e x$.
+    fn commit<R: RngCore + CryptoRng>(&self, rng: &mut R)
+        -> (Self::ProverSecret, Self::Commitment);
+    /// Returns $\alpha \bmod q$
+    fn mask(&self, secret: &Self::ProverSecret) -> SecretScalar<E>;
+    /// Computes response to the challenge $e$
+    fn respond(
+        &self,
+        secret: Self::ProverSecret,
+        witness: &Self::Witness,
+        challenge: &Scalar<E>,
+    ) -> Self::Response;
+    /// Returns $z \bmod q$
+    fn response_scalar(&self, response: &Self::Response) -> Scalar<E>;
+    /// Verifies encryption side of the proof, including range checks if any
+    fn verify(
+        &self,
+        ciphertext: &Self::Ciphertext,
+        commitment: &Self::Commitment,
+        challenge: &Scalar<E>,
+        response: &Self::Response,
+    ) -> bool;
+    /// Appends encryption key, ciphertext and commitment to the transcript
+    fn append_to_transcript<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        ciphertext: &Self::Ciphertext,
+        commitment: &Self::Commitment,
+    );
+}
+
+/// Statement: ciphertext $C = \text{Enc}(x; \rho)$ and point $X = x \cdot B$ share the same $x$
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(serialize = "C: Serialize", deserialize = "C: Deserialize<'de>"))
+)]
+pub struct Statement<E: Curve, C> {
+    /// Ciphertext $C$
+    pub ciphertext: C,
+    /// Base $B$
+    pub base: Point<E>,
+    /// Point $X$
+    pub point: Point<E>,
+}
+
+/// Proof that ciphertext encrypts discrete log of a point
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(
+        serialize = "S::Commitment: Serialize, S::Response: Serialize",
+        deserialize = "S::Commitment: Deserialize<'de>, S::Response: Deserialize<'de>"
+    ))
+)]
+pub struct Proof<E: Curve, S: LinkedEncryption<E>> {
+    /// Commitment $A$ on encryption side
+    pub commitment: S::Commitment,
+    /// Commitment $Y = \alpha \cdot B$ on EC side
+    pub y: Point<E>,
+    /// Response on encryption side
+    pub response: S::Response,
+}
+
+/// Proves that ciphertext encrypts discrete log of a point
+///
+/// Statement and commitments are appended to the `transcript`. Witness is not validated:
+/// the proof simply won't verify if it doesn't correspond to the statement.
+pub fn prove<E: Curve, S: LinkedEncryption<E>, D: Digest + Clone, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    mut transcript: Transcript<D>,
+    scheme: &S,
+    statement: &Statement<E, S::Ciphertext>,
+    witness: &S::Witness,
+) -> Proof<E, S> {
+    let (secret, commitment) = scheme.commit(rng);
+    let y = statement.base * &scheme.mask(&secret);
+    let e = challenge(&mut transcript, scheme, statement, &commitment, &y);
+    let response = scheme.respond(secret, witness, &e);
+    Proof {
+        commitment,
+        y,
+        response,
+    }
+}
+
+impl<E: Curve, S: LinkedEncryption<E>> Proof<E, S> {
+    /// Verifies the proof
+    ///
+    /// Transcript must be in the same state as it was at the moment of proving.
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        scheme: &S,
+        statement: &Statement<E, S::Ciphertext>,
+    ) -> Result<(), InvalidProof> {
+        let e = challenge(transcript, scheme, statement, &self.commitment, &self.y);
+        if !scheme.verify(&statement.ciphertext, &self.commitment, &e, &self.response) {
+            return Err(InvalidProof);
+        }
+        let z = scheme.response_scalar(&self.response);
+        if statement.base * z == self.y + statement.point * e {
+            Ok(())
+        } else {
+            Err(InvalidProof)
+        }
+    }
+}
+
+fn challenge<E: Curve, S: LinkedEncryption<E>, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    scheme: &S,
+    statement: &Statement<E, S::Ciphertext>,
+    commitment: &S::Commitment,
+    y: &Point<E>,
+) -> Scalar<E> {
+    scheme.append_to_transcript(transcript, &statement.ciphertext, commitment);
+    transcript.append_point(b"linked_encryption/base", &statement.base);
+    transcript.append_point(b"linked_encryption/point", &statement.point);
+    transcript.append_point(b"linked_encryption/y", y);
+    transcript.challenge_scalar(b"linked_encryption/e")
+}
+
+/// ElGamal encryption "in the exponent"
+///
+/// Witness is plaintext $x$ and randomness $\rho$ such as $C = $
+/// [`encrypt_scalar(x, ρ)`](elgamal::EncryptionKey::encrypt_scalar). Note that ElGamal
+/// plaintext space is the scalar field, so no range is implied.
+impl<E: Curve> LinkedEncryption<E> for elgamal::EncryptionKey<E> {
+    type Ciphertext = elgamal::Ciphertext<E>;
+    type Witness = (SecretScalar<E>, SecretScalar<E>);
+    type ProverSecret = (SecretScalar<E>, SecretScalar<E>);
+    type Commitment = elgamal::Ciphertext<E>;
+    type Response = (Scalar<E>, Scalar<E>);
+
+    fn commit<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+    ) -> (Self::ProverSecret, Self::Commitment) {
+        let alpha = SecretScalar::random(rng);
+        let r = SecretScalar::random(rng);
+        let commitment = self.encrypt_scalar(&alpha, &r);
+        ((alpha, r), commitment)
+    }
+
+    fn mask(&self, (alpha, _): &Self::ProverSecret) -> SecretScalar<E> {
+        alpha.clone()
+    }
+
+    fn respond(
+        &self,
+        (alpha, r): Self::ProverSecret,
+        (x, rho): &Self::Witness,
+        challenge: &Scalar<E>,
+    ) -> Self::Response {
+        (alpha + challenge * x, r + challenge * rho)
+    }
+
+    fn response_scalar(&self, (z, _): &Self::Response) -> Scalar<E> {
+        *z
+    }
+
+    fn verify(
+        &self,
+        ciphertext: &Self::Ciphertext,
+        commitment: &Self::Commitment,
+        challenge: &Scalar<E>,
+        (z, w): &Self::Response,
+    ) -> bool {
+        self.encrypt_scalar(z, w) == *commitment + *ciphertext * challenge
+    }
+
+    fn append_to_transcript<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        ciphertext: &Self::Ciphertext,
+        commitment: &Self::Commitment,
+    ) {
+        transcript.append_point(b"linked_encryption/elgamal/ek", &self.0);
+        transcript.append_point(b"linked_encryption/elgamal/c1", &ciphertext.c1);
+        transcript.append_point(b"linked_encryption/elgamal/c2", &ciphertext.c2);
+        transcript.append_point(b"linked_encryption/elgamal/a1", &commitment.c1);
+        transcript.append_point(b"linked_encryption/elgamal/a2", &commitment.c2);
+    }
+}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, Point, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{prove, Statement};
+    use crate::{elgamal, transcript::Transcript};
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn elgamal_proof<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (_, ek) = elgamal::keygen::<E, _>(&mut rng);
+
+        let x = SecretScalar::<E>::random(&mut rng);
+        let rho = SecretScalar::<E>::random(&mut rng);
+        let base = Point::generator() * SecretScalar::<E>::random(&mut rng);
+        let statement = Statement {
+            ciphertext: ek.encrypt_scalar(&x, &rho),
+            base,
+            point: base * &x,
+        };
+
+        let proof = prove(&mut rng, transcript(), &ek, &statement, &(x, rho.clone()));
+        proof.verify(&mut transcript(), &ek, &statement).unwrap();
+
+        // Point doesn't match the ciphertext
+        let other_x = SecretScalar::<E>::random(&mut rng);
+        let wrong_statement = Statement {
+            point: base * &other_x,
+            ..statement
+        };
+        let proof = prove(
+            &mut rng,
+            transcript(),
+            &ek,
+            &wrong_statement,
+            &(other_x, rho),
+        );
+        assert!(proof
+            .verify(&mut transcript(), &ek, &wrong_statement)
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -0,0 +1,262 @@
+//! Diffie-Hellman primitive for the Noise protocol framework
+//!
+//! [`NoiseDh<E>`] holds a static or ephemeral key pair on curve `E` and performs the `DH()`
+//! function of the [Noise protocol framework] on it. It mirrors the methods of the `Dh` trait
+//! from the `snow` crate, so transport layers of MPC applications can reuse generic-ec curves
+//! for their Noise handshakes: the trait implementation is a one-line forwarding of every
+//! method. Supported curves:
+//!
+//! | Curve       | DH name | `DHLEN` | Public key encoding     |
+//! |-------------|---------|---------|-------------------------|
+//! | `Secp256r1` | `P256`  | 32      | compressed, 33 bytes    |
+//!
+//! DH output is the x-coordinate of the shared point, as in ECDH defined by SEC1. Noise `25519`
+//! DH function is not supported: it's defined over Curve25519 in Montgomery form with X25519 key
+//! clamping, which can't be expressed via [`Ed25519`](generic_ec::curves::Ed25519).
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::curves::Secp256r1;
+//! use generic_ec_zkp::noise::NoiseDh;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let mut alice = NoiseDh::<Secp256r1>::default();
+//! alice.generate(&mut rng);
+//! let mut bob = NoiseDh::<Secp256r1>::default();
+//! bob.generate(&mut rng);
+//!
+//! let mut alice_out = [0u8; 32];
+//! alice.dh(bob.pubkey(), &mut alice_out)?;
+//! let mut bob_out = [0u8; 32];
+//! bob.dh(alice.pubkey(), &mut bob_out)?;
+//! assert_eq!(alice_out, bob_out);
+//! # Ok::<(), generic_ec_zkp::noise::NoiseDhError>(())
+//! ```
+//!
+//! [Noise protocol framework]: https://noiseprotocol.org/noise.html
+
+use generic_ec::{Curve, EncodedPoint, EncodedScalar, NonZero, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+/// Curve that can be used as Noise DH function
+///
+/// The trait is sealed and implemented for curves listed in [module docs](self).
+pub trait NoiseCurve: Curve + sealed::Sealed {
+    /// Name of DH function used in Noise protocol names
+    const DH_NAME: &'static str;
+}
+
+impl NoiseCurve for generic_ec::curves::Secp256r1 {
+    const DH_NAME: &'static str = "P256";
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for generic_ec::curves::Secp256r1 {}
+}
+
+/// Noise DH key pair on curve `E`
+///
+/// Initially empty, the key pair is set via [`generate`](Self::generate) or
+/// [`set`](Self::set). Secret key is erased from memory on drop.
+pub struct NoiseDh<E: NoiseCurve> {
+    keypair: Option<KeyPair<E>>,
+}
+
+struct KeyPair<E: Curve> {
+    secret: NonZero<SecretScalar<E>>,
+    secret_bytes: EncodedScalar<E>,
+    public_bytes: EncodedPoint<E>,
+}
+
+impl<E: NoiseCurve> NoiseDh<E> {
+    /// Name of DH function, e.g. `P256`
+    pub fn name(&self) -> &'static str {
+        E::DH_NAME
+    }
+
+    /// Size of encoded public key in bytes
+    pub fn pub_len(&self) -> usize {
+        Point::<E>::serialized_len(true)
+    }
+
+    /// Size of encoded secret key in bytes
+    pub fn priv_len(&self) -> usize {
+        Scalar::<E>::serialized_len()
+    }
+
+    /// Size of DH output in bytes, `DHLEN`
+    pub fn dh_len(&self) -> usize {
+        Point::<E>::serialized_len(true) - 1
+    }
+
+    /// Sets the key pair from big-endian encoded secret key
+    ///
+    /// Returns error if `privkey` is not a valid non-zero scalar, in which case the key pair
+    /// is left unchanged.
+    pub fn set(&mut self, privkey: &[u8]) -> Result<(), NoiseDhError> {
+        let secret = SecretScalar::<E>::from_be_bytes(privkey)
+            .ok()
+            .and_then(NonZero::from_secret_scalar)
+            .ok_or(NoiseDhError(Reason::InvalidSecretKey))?;
+        self.keypair = Some(KeyPair::new(secret));
+        Ok(())
+    }
+
+    /// Generates a random key pair
+    pub fn generate(&mut self, rng: &mut (impl RngCore + CryptoRng)) {
+        self.keypair = Some(KeyPair::new(NonZero::<SecretScalar<E>>::random(rng)));
+    }
+
+    /// Returns compressed public key, or empty slice if key pair is not set
+    pub fn pubkey(&self) -> &[u8] {
+        self.keypair
+            .as_ref()
+            .map(|keypair| keypair.public_bytes.as_bytes())
+            .unwrap_or_default()
+    }
+
+    /// Returns big-endian encoded secret key, or empty slice if key pair is not set
+    pub fn privkey(&self) -> &[u8] {
+        self.keypair
+            .as_ref()
+            .map(|keypair| keypair.secret_bytes.as_bytes())
+            .unwrap_or_default()
+    }
+
+    /// Performs DH with the other party public key `pubkey` and writes the result to `out`
+    ///
+    /// `pubkey` may be compressed or uncompressed, identity point is rejected. The first
+    /// [`dh_len`](Self::dh_len) bytes of `out` are overwritten.
+    pub fn dh(&self, pubkey: &[u8], out: &mut [u8]) -> Result<(), NoiseDhError> {
+        let keypair = self
+            .keypair
+            .as_ref()
+            .ok_or(NoiseDhError(Reason::KeyPairNotSet))?;
+        let pubkey = Point::<E>::from_bytes(pubkey)
+            .ok()
+            .and_then(NonZero::from_point)
+            .ok_or(NoiseDhError(Reason::InvalidPublicKey))?;
+        let out = out
+            .get_mut(..self.dh_len())
+            .ok_or(NoiseDhError(Reason::OutputTooShort))?;
+
+        let shared = pubkey * &keypair.secret;
+        let shared = shared.to_bytes(true);
+        let x = shared
+            .get(1..)
+            .ok_or(NoiseDhError(Reason::InvalidPublicKey))?;
+        out.copy_from_slice(x);
+        Ok(())
+    }
+}
+
+impl<E: Curve> KeyPair<E> {
+    fn new(secret: NonZero<SecretScalar<E>>) -> Self {
+        let public = Point::generator() * &secret;
+        Self {
+            secret_bytes: secret.as_ref().expose_secret(|x| x.to_be_bytes()),
+            public_bytes: public.to_bytes(true),
+            secret,
+        }
+    }
+}
+
+impl<E: Curve> Drop for KeyPair<E> {
+    fn drop(&mut self) {
+        self.secret_bytes.as_mut().zeroize()
+    }
+}
+
+impl<E: NoiseCurve> Default for NoiseDh<E> {
+    fn default() -> Self {
+        Self { keypair: None }
+    }
+}
+
+impl<E: NoiseCurve> core::fmt::Debug for NoiseDh<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NoiseDh")
+            .field("name", &E::DH_NAME)
+            .field("pubkey", &self.keypair.as_ref().map(|k| &k.public_bytes))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Noise DH error
+#[derive(Debug, Clone, Copy)]
+pub struct NoiseDhError(Reason);
+
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    KeyPairNotSet,
+    InvalidSecretKey,
+    InvalidPublicKey,
+    OutputTooShort,
+}
+
+impl core::fmt::Display for NoiseDhError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Reason::KeyPairNotSet => f.write_str("key pair is not set"),
+            Reason::InvalidSecretKey => f.write_str("invalid secret key"),
+            Reason::InvalidPublicKey => f.write_str("invalid public key"),
+            Reason::OutputTooShort => f.write_str("output buffer is too short"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoiseDhError {}
+
+#[cfg(test)]
+mod tests {
+    use generic_ec::{curves::Secp256r1, NonZero, Point, SecretScalar};
+
+    use super::NoiseDh;
+
+    #[test]
+    fn dh_matches_ecdh() {
+        let mut rng = rand_dev::DevRng::new();
+
+        let mut alice = NoiseDh::<Secp256r1>::default();
+        alice.generate(&mut rng);
+        assert_eq!(alice.pubkey().len(), alice.pub_len());
+        assert_eq!(alice.privkey().len(), alice.priv_len());
+
+        // Restoring key pair from secret key gives the same public key
+        let mut restored = NoiseDh::<Secp256r1>::default();
+        restored.set(alice.privkey()).unwrap();
+        assert_eq!(restored.pubkey(), alice.pubkey());
+
+        let bob_sk = NonZero::<SecretScalar<Secp256r1>>::random(&mut rng);
+        let bob_pk = Point::generator() * &bob_sk;
+        let alice_pk = Point::<Secp256r1>::from_bytes(alice.pubkey()).unwrap();
+
+        let mut out = [0u8; 32];
+        alice.dh(&bob_pk.to_bytes(false), &mut out).unwrap();
+        let expected = (alice_pk * &bob_sk).to_bytes(true);
+        assert_eq!(out[..], expected[1..]);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let mut rng = rand_dev::DevRng::new();
+        let mut dh = NoiseDh::<Secp256r1>::default();
+        let mut out = [0u8; 32];
+
+        assert!(dh.pubkey().is_empty());
+        let other = Point::<Secp256r1>::generator().to_point().to_bytes(true);
+        assert!(dh.dh(&other, &mut out).is_err());
+
+        assert!(dh.set(&[0u8; 32]).is_err());
+        dh.generate(&mut rng);
+        assert!(dh
+            .dh(&Point::<Secp256r1>::zero().to_bytes(true), &mut out)
+            .is_err());
+        assert!(dh.dh(&other, &mut out[..31]).is_err());
+        assert!(dh.dh(&other, &mut out).is_ok());
+    }
+}
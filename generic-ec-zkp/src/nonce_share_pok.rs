@@ -0,0 +1,225 @@
+//! Proof of knowledge of ECDSA nonce share $\Pi^\text{nonce}$
+//!
+//! In threshold ECDSA signing, each party publishes its nonce share $\Gamma_i = k_i \cdot G$
+//! along with [Pedersen commitment](crate::pedersen) $C_i = k_i \cdot G' + \rho_i \cdot H$ that
+//! is later used in the protocol. The proof convinces other parties that the party knows $k_i$
+//! and $\rho_i$, and that both $\Gamma_i$ and $C_i$ refer to the same $k_i$.
+//!
+//! [`NonceShare`] packages $\Gamma_i$, $C_i$ and the proof into one message. It's non-interactive:
+//! the proof is a [bundle](crate::proof_bundle) of a single linear relation bound to the
+//! [transcript](crate::transcript), which should include the session identifier and index of
+//! the party to prevent proof replay. The message can only be used through [`VerifiedNonceShare`]
+//! obtained after verification.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{SecretScalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{nonce_share_pok::NonceShare, pedersen, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-pedersen-v0.1.0");
+//! let params = pedersen::Params::<Secp256k1>::derive(TAG)?;
+//!
+//! let transcript = || {
+//!     let mut transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-signing");
+//!     transcript.append_message(b"session_id", b"session #1");
+//!     transcript.append_u64(b"party_index", 1);
+//!     transcript
+//! };
+//!
+//! // Party samples its nonce share and commits to it
+//! let k = SecretScalar::random(&mut rng);
+//! let blinding = SecretScalar::random(&mut rng);
+//! let message = NonceShare::new(&mut rng, transcript(), &params, &k, &blinding);
+//!
+//! // Other parties verify the message
+//! let verified = message.verify(&mut transcript(), &mut rng, &params)?;
+//! let (gamma, commitment) = (verified.gamma(), verified.commitment());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Proof is a [proof bundle](crate::proof_bundle) for linear relation on witness $(k, \rho)$:
+//! $k \cdot G = \Gamma$ and $k \cdot G' + \rho \cdot H = C$.
+
+use digest::Digest;
+use generic_ec::{Curve, Point, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    pedersen,
+    proof_bundle::{BundleProver, InvalidProof, LinearRelation, ProofBundle},
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Nonce share $\Gamma$, commitment $C$, and proof that they're consistent
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct NonceShare<E: Curve> {
+    /// Nonce share $\Gamma = k \cdot G$
+    pub gamma: Point<E>,
+    /// Commitment $C = k \cdot G' + \rho \cdot H$
+    pub commitment: Point<E>,
+    /// Proof of knowledge of $k$ and $\rho$
+    pub proof: ProofBundle<E>,
+}
+
+/// Nonce share which proof was verified
+///
+/// Can only be obtained via [`NonceShare::verify`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiedNonceShare<E: Curve> {
+    gamma: Point<E>,
+    commitment: Point<E>,
+}
+
+impl<E: Curve> NonceShare<E> {
+    /// Computes nonce share $\Gamma$, commitment $C$, and proves their consistency
+    ///
+    /// Relation and proof commitments are appended to the `transcript`
+    pub fn new<D: Digest + Clone, R: RngCore + CryptoRng>(
+        rng: &mut R,
+        transcript: Transcript<D>,
+        params: &pedersen::Params<E>,
+        k: &SecretScalar<E>,
+        blinding: &SecretScalar<E>,
+    ) -> Self {
+        let gamma = Point::generator() * k;
+        let commitment = k.expose_secret(|k| blinding.expose_secret(|r| params.commit(k, r)));
+
+        let mut prover = BundleProver::new(transcript);
+        prover.add_satisfied(
+            rng,
+            relation(params, gamma, commitment),
+            &[k.clone(), blinding.clone()],
+        );
+        Self {
+            gamma,
+            commitment,
+            proof: prover.prove(),
+        }
+    }
+
+    /// Verifies the proof
+    ///
+    /// Transcript must be in the same state as it was at the moment of proving.
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        params: &pedersen::Params<E>,
+    ) -> Result<VerifiedNonceShare<E>, InvalidProof> {
+        let relation = relation(params, self.gamma, self.commitment);
+        self.proof
+            .verify(transcript, rng, core::slice::from_ref(&relation))?;
+        Ok(VerifiedNonceShare {
+            gamma: self.gamma,
+            commitment: self.commitment,
+        })
+    }
+}
+
+impl<E: Curve> VerifiedNonceShare<E> {
+    /// Nonce share $\Gamma = k \cdot G$
+    pub fn gamma(&self) -> Point<E> {
+        self.gamma
+    }
+
+    /// Commitment $C = k \cdot G' + \rho \cdot H$
+    pub fn commitment(&self) -> Point<E> {
+        self.commitment
+    }
+}
+
+/// Linear relation on witness $(k, \rho)$
+fn relation<E: Curve>(
+    params: &pedersen::Params<E>,
+    gamma: Point<E>,
+    commitment: Point<E>,
+) -> LinearRelation<E> {
+    LinearRelation::new(2)
+        .with_equation([(0, Point::generator().to_point())], gamma)
+        .with_equation([(0, params.g), (1, params.h)], commitment)
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{hash_to_curve::FromHash, Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::NonceShare;
+    use crate::{pedersen, transcript::Transcript};
+
+    fn params<E: Curve>() -> pedersen::Params<E>
+    where
+        Point<E>: FromHash,
+    {
+        pedersen::Params::derive(generic_ec::hash_to_curve::Tag::new_unwrap(b"test")).unwrap()
+    }
+
+    fn transcript(party: u64) -> Transcript<sha2::Sha256> {
+        let mut transcript = Transcript::new(b"test");
+        transcript.append_u64(b"party_index", party);
+        transcript
+    }
+
+    #[test]
+    fn valid_proof<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = params::<E>();
+        let k = SecretScalar::<E>::random(&mut rng);
+        let blinding = SecretScalar::<E>::random(&mut rng);
+
+        let message = NonceShare::new(&mut rng, transcript(1), &params, &k, &blinding);
+        let verified = message
+            .verify(&mut transcript(1), &mut rng, &params)
+            .unwrap();
+        assert_eq!(verified.gamma(), Point::generator() * &k);
+        assert_eq!(
+            verified.commitment(),
+            k.expose_secret(|k| blinding.expose_secret(|r| params.commit(k, r)))
+        );
+
+        // Proof is bound to the transcript
+        assert!(message
+            .verify(&mut transcript(2), &mut rng, &params)
+            .is_err());
+    }
+
+    #[test]
+    fn inconsistent_nonce_share_is_rejected<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = params::<E>();
+        let k = SecretScalar::<E>::random(&mut rng);
+        let blinding = SecretScalar::<E>::random(&mut rng);
+
+        let mut message = NonceShare::new(&mut rng, transcript(1), &params, &k, &blinding);
+        message.gamma = Point::generator() * SecretScalar::<E>::random(&mut rng);
+        assert!(message
+            .verify(&mut transcript(1), &mut rng, &params)
+            .is_err());
+
+        let mut message = NonceShare::new(&mut rng, transcript(1), &params, &k, &blinding);
+        message.commitment =
+            blinding.expose_secret(|r| params.commit(&Scalar::<E>::random(&mut rng), r));
+        assert!(message
+            .verify(&mut transcript(1), &mut rng, &params)
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
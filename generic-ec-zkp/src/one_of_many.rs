@@ -0,0 +1,490 @@
+//! One-out-of-many proof
+//!
+//! Proves that one of $N$ public [Pedersen commitments](crate::pedersen) $C_0, \dots, C_{N-1}$
+//! opens to zero, i.e. prover knows index $\ell$ and blinding $r$ such that $C_\ell = r \cdot H$,
+//! without revealing $\ell$. Implements [Groth-Kohlweiss] proof with $n = 2$ (see also
+//! [Bootle et al.]): proof consists of $4m$ points and $3m + 1$ scalars where
+//! $m = \lceil \log_2 N \rceil$.
+//!
+//! It's a building block for set membership and anonymity: to prove that committed value
+//! $C = v \cdot G + r \cdot H$ belongs to a public set $\{v_i\}$, prove that one of
+//! $C - v_i \cdot G$ opens to zero. Lelantus and Triptych use the proof to hide the spent coin
+//! among a set of coins.
+//!
+//! Set is padded to the next power of two by repeating the last commitment. Statement (the
+//! commitments) is appended to the transcript by the proof.
+//!
+//! [Groth-Kohlweiss]: https://eprint.iacr.org/2014/764
+//! [Bootle et al.]: https://eprint.iacr.org/2015/643
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{one_of_many, pedersen, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-pedersen-v0.1.0");
+//! let params = pedersen::Params::<Secp256k1>::derive(TAG)?;
+//!
+//! // Set of commitments, one of them opens to zero
+//! let mut commitments: Vec<Point<Secp256k1>> = (0..10)
+//!     .map(|_| params.commit(&Scalar::random(&mut rng), &Scalar::random(&mut rng)))
+//!     .collect();
+//! let blinding = Scalar::random(&mut rng);
+//! commitments[7] = params.commit(&Scalar::zero(), &blinding);
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-spend");
+//! let proof = one_of_many::prove(&mut transcript, &params, &mut rng, &commitments, 7, &blinding)?;
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-spend");
+//! proof.verify(&mut transcript, &params, &commitments)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Let $\ell_j$ be the $j$-th bit of $\ell$, $\mathrm{Com}(v; r) = v \cdot G + r \cdot H$.
+//!
+//! 1. For every $j \in [0, m)$, prover samples $r_j, a_j, s_j, t_j, \rho_j$ and commits
+//!    $C_{\ell,j} = \mathrm{Com}(\ell_j; r_j)$, $C_{a,j} = \mathrm{Com}(a_j; s_j)$,
+//!    $C_{b,j} = \mathrm{Com}(\ell_j a_j; t_j)$
+//! 2. Let $f_{j,1}(x) = \ell_j x + a_j$, $f_{j,0}(x) = x - f_{j,1}(x)$, and
+//!    $p_i(x) = \prod_j f_{j,i_j}(x) = \delta_{i,\ell} x^m + \sum_{k < m} p_{i,k} x^k$.
+//!    Prover commits $C_{d,k} = \sum_i p_{i,k} \cdot C_i + \rho_k \cdot H$.
+//! 3. Challenge $x$ is derived from the transcript
+//! 4. Prover responds with $f_j = \ell_j x + a_j$, $z_{a,j} = r_j x + s_j$,
+//!    $z_{b,j} = r_j (x - f_j) + t_j$, and $z_d = r x^m - \sum_k \rho_k x^k$
+//! 5. Verifier checks that $x \cdot C_{\ell,j} + C_{a,j} = \mathrm{Com}(f_j; z_{a,j})$,
+//!    $(x - f_j) \cdot C_{\ell,j} + C_{b,j} = \mathrm{Com}(0; z_{b,j})$ (i.e. $\ell_j$ is a bit),
+//!    and $\sum_i \prod_j f_{j,i_j} \cdot C_i - \sum_k x^k \cdot C_{d,k} = \mathrm{Com}(0; z_d)$,
+//!    where $f_{j,1} = f_j$ and $f_{j,0} = x - f_j$
+
+use alloc::vec::Vec;
+use core::iter;
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{pedersen, transcript::Transcript};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One-out-of-many proof
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct OneOfManyProof<E: Curve> {
+    /// Commitments to bits of the index $C_{\ell,j}$
+    pub c_l: Vec<Point<E>>,
+    /// Commitments $C_{a,j}$
+    pub c_a: Vec<Point<E>>,
+    /// Commitments $C_{b,j}$
+    pub c_b: Vec<Point<E>>,
+    /// Commitments $C_{d,k}$
+    pub c_d: Vec<Point<E>>,
+    /// Responses $f_j$
+    pub f: Vec<Scalar<E>>,
+    /// Responses $z_{a,j}$
+    pub z_a: Vec<Scalar<E>>,
+    /// Responses $z_{b,j}$
+    pub z_b: Vec<Scalar<E>>,
+    /// Response $z_d$
+    pub z_d: Scalar<E>,
+}
+
+/// Proves that `commitments[index]` opens to zero with `blinding`
+///
+/// Returns error if there are less than 2 commitments, `index` is out of range, or
+/// `commitments[index]` doesn't equal to $\text{blinding} \cdot H$.
+pub fn prove<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    transcript: &mut Transcript<D>,
+    params: &pedersen::Params<E>,
+    rng: &mut R,
+    commitments: &[Point<E>],
+    index: usize,
+    blinding: &Scalar<E>,
+) -> Result<OneOfManyProof<E>, InvalidInput> {
+    if commitments.len() < 2 || commitments.get(index) != Some(&(params.h * blinding)) {
+        return Err(InvalidInput);
+    }
+    let commitments = padded(commitments).ok_or(InvalidInput)?;
+    let m = commitments.len().trailing_zeros() as usize;
+    append_statement(transcript, &commitments);
+
+    let bits: Vec<Scalar<E>> = (0..m)
+        .map(|j| Scalar::from((index >> j) as u64 & 1))
+        .collect();
+    let [r, a, s, t, rho] =
+        [(); 5].map(|_| (0..m).map(|_| Scalar::<E>::random(rng)).collect::<Vec<_>>());
+    let c_l: Vec<_> = (0..m).map(|j| params.commit(&bits[j], &r[j])).collect();
+    let c_a: Vec<_> = (0..m).map(|j| params.commit(&a[j], &s[j])).collect();
+    let c_b: Vec<_> = (0..m)
+        .map(|j| params.commit(&(bits[j] * a[j]), &t[j]))
+        .collect();
+
+    // Coefficients of p_i(x), `p[i][k]` is the coefficient at x^k
+    let p: Vec<Vec<Scalar<E>>> = (0..commitments.len())
+        .map(|i| {
+            (0..m).fold(alloc::vec![Scalar::one()], |poly, j| {
+                // f_{j,1}(x) = l_j x + a_j, f_{j,0}(x) = (1 - l_j) x - a_j
+                let (c0, c1) = if (i >> j) & 1 == 1 {
+                    (a[j], bits[j])
+                } else {
+                    (-a[j], Scalar::one() - bits[j])
+                };
+                let mut product = alloc::vec![Scalar::zero(); poly.len() + 1];
+                for (k, coef) in poly.iter().enumerate() {
+                    product[k] += coef * c0;
+                    product[k + 1] += coef * c1;
+                }
+                product
+            })
+        })
+        .collect();
+    let c_d: Vec<_> = (0..m)
+        .map(|k| {
+            Scalar::multiscalar_mul(
+                p.iter()
+                    .map(|p_i| p_i[k])
+                    .zip(commitments.iter().copied())
+                    .chain(iter::once((rho[k], params.h))),
+            )
+        })
+        .collect();
+
+    let x = challenge(transcript, &c_l, &c_a, &c_b, &c_d);
+
+    let f: Vec<_> = (0..m).map(|j| bits[j] * x + a[j]).collect();
+    let z_a = (0..m).map(|j| r[j] * x + s[j]).collect();
+    let z_b = (0..m).map(|j| r[j] * (x - f[j]) + t[j]).collect();
+    let x_pows = powers(x, m + 1);
+    let z_d = blinding * x_pows[m]
+        - rho
+            .iter()
+            .zip(&x_pows)
+            .map(|(rho_k, x_k)| rho_k * x_k)
+            .sum::<Scalar<E>>();
+
+    Ok(OneOfManyProof {
+        c_l,
+        c_a,
+        c_b,
+        c_d,
+        f,
+        z_a,
+        z_b,
+        z_d,
+    })
+}
+
+impl<E: Curve> OneOfManyProof<E> {
+    /// Verifies that one of `commitments` opens to zero
+    ///
+    /// Transcript must be in the same state as it was at the moment of proving.
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        params: &pedersen::Params<E>,
+        commitments: &[Point<E>],
+    ) -> Result<(), InvalidProof> {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("one_of_many::verify", commitments = commitments.len()).entered();
+        let commitments = match padded(commitments) {
+            Some(commitments) if commitments.len() >= 2 => commitments,
+            _ => {
+                verification_failed!(E, "unsupported amount of commitments");
+                return Err(InvalidProof);
+            }
+        };
+        let m = commitments.len().trailing_zeros() as usize;
+        if [
+            self.c_l.len(),
+            self.c_a.len(),
+            self.c_b.len(),
+            self.c_d.len(),
+            self.f.len(),
+            self.z_a.len(),
+            self.z_b.len(),
+        ]
+        .iter()
+        .any(|len| *len != m)
+        {
+            verification_failed!(E, "proof doesn't match amount of commitments");
+            return Err(InvalidProof);
+        }
+        append_statement(transcript, &commitments);
+        let x = challenge(transcript, &self.c_l, &self.c_a, &self.c_b, &self.c_d);
+
+        // Every committed l_j is a bit, and f_j is computed correctly
+        for j in 0..m {
+            let bit_check = Scalar::multiscalar_mul([
+                (x, self.c_l[j]),
+                (Scalar::one(), self.c_a[j]),
+                (-self.f[j], params.g),
+                (-self.z_a[j], params.h),
+            ]);
+            let product_check = Scalar::multiscalar_mul([
+                (x - self.f[j], self.c_l[j]),
+                (Scalar::one(), self.c_b[j]),
+                (-self.z_b[j], params.h),
+            ]);
+            if !bit_check.is_zero() || !product_check.is_zero() {
+                verification_failed!(E, transcript, "index is not committed bitwise");
+                return Err(InvalidProof);
+            }
+        }
+
+        // p_i(x) = \prod_j f_{j,i_j}, computed for all i by doubling
+        let mut p = alloc::vec![Scalar::<E>::one()];
+        for f_j in &self.f {
+            let f_j0 = x - f_j;
+            let high: Vec<_> = p.iter().map(|p_i| p_i * f_j).collect();
+            p.iter_mut().for_each(|p_i| *p_i *= f_j0);
+            p.extend(high);
+        }
+        let x_pows = powers(x, m);
+        let check = Scalar::multiscalar_mul(
+            p.into_iter()
+                .zip(commitments.iter().copied())
+                .chain(x_pows.iter().map(|x_k| -x_k).zip(self.c_d.iter().copied()))
+                .chain(iter::once((-self.z_d, params.h))),
+        );
+        if !check.is_zero() {
+            verification_failed!(E, transcript, "none of commitments opens to zero");
+            return Err(InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+/// Pads commitments to the next power of two by repeating the last one
+///
+/// Returns `None` if the next power of two overflows `usize`
+fn padded<E: Curve>(commitments: &[Point<E>]) -> Option<Vec<Point<E>>> {
+    let n = commitments.len().checked_next_power_of_two()?;
+    let last = commitments.last().copied().unwrap_or_else(Point::zero);
+    Some(
+        commitments
+            .iter()
+            .copied()
+            .chain(iter::repeat(last))
+            .take(n)
+            .collect(),
+    )
+}
+
+fn append_statement<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    commitments: &[Point<E>],
+) {
+    transcript.append_u64(b"one_of_many/N", commitments.len() as u64);
+    transcript.append_points(b"one_of_many/C", commitments);
+}
+
+fn challenge<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    c_l: &[Point<E>],
+    c_a: &[Point<E>],
+    c_b: &[Point<E>],
+    c_d: &[Point<E>],
+) -> Scalar<E> {
+    transcript.append_points(b"one_of_many/c_l", c_l);
+    transcript.append_points(b"one_of_many/c_a", c_a);
+    transcript.append_points(b"one_of_many/c_b", c_b);
+    transcript.append_points(b"one_of_many/c_d", c_d);
+    transcript.challenge_scalar(b"one_of_many/x")
+}
+
+/// Returns $[1, x, x^2, \dots, x^{n-1}]$
+fn powers<E: Curve>(x: Scalar<E>, n: usize) -> Vec<Scalar<E>> {
+    iter::successors(Some(Scalar::one()), |acc| Some(acc * x))
+        .take(n)
+        .collect()
+}
+
+/// Invalid input error
+///
+/// Returned when there are less than 2 commitments, or the commitment at given index doesn't
+/// open to zero
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid input")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid one-out-of-many proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    use crate::{pedersen, transcript::Transcript};
+
+    const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-one-of-many-test");
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    fn random_commitments<E: Curve>(
+        rng: &mut DevRng,
+        params: &pedersen::Params<E>,
+        n: usize,
+    ) -> Vec<Point<E>> {
+        (0..n)
+            .map(|_| params.commit(&Scalar::random(rng), &Scalar::random(rng)))
+            .collect()
+    }
+
+    #[test]
+    fn prove_and_verify<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = pedersen::Params::<E>::derive(TAG).unwrap();
+
+        for (n, index) in [(2, 0), (2, 1), (5, 4), (8, 3), (13, 12), (13, 0)] {
+            let mut commitments = random_commitments(&mut rng, &params, n);
+            let blinding = Scalar::random(&mut rng);
+            commitments[index] = params.commit(&Scalar::zero(), &blinding);
+
+            let proof = super::prove(
+                &mut transcript(),
+                &params,
+                &mut rng,
+                &commitments,
+                index,
+                &blinding,
+            )
+            .unwrap();
+            let m = n.next_power_of_two().trailing_zeros() as usize;
+            assert_eq!(proof.c_d.len(), m);
+            proof
+                .verify(&mut transcript(), &params, &commitments)
+                .unwrap();
+
+            // Proof doesn't verify once the commitment is replaced
+            let mut other = commitments.clone();
+            other[index] = params.commit(&Scalar::zero(), &Scalar::random(&mut rng));
+            assert!(proof.verify(&mut transcript(), &params, &other).is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_input<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = pedersen::Params::<E>::derive(TAG).unwrap();
+        let blinding = Scalar::random(&mut rng);
+        let mut commitments = random_commitments(&mut rng, &params, 4);
+        commitments[1] = params.commit(&Scalar::zero(), &blinding);
+
+        // Commitment at index doesn't open to zero
+        assert!(super::prove(
+            &mut transcript(),
+            &params,
+            &mut rng,
+            &commitments,
+            2,
+            &blinding
+        )
+        .is_err());
+        // Index out of range
+        assert!(super::prove(
+            &mut transcript(),
+            &params,
+            &mut rng,
+            &commitments,
+            4,
+            &blinding
+        )
+        .is_err());
+        // Set is too small
+        assert!(super::prove(
+            &mut transcript(),
+            &params,
+            &mut rng,
+            &commitments[1..2],
+            0,
+            &blinding
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn tampered_proof_is_rejected<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = pedersen::Params::<E>::derive(TAG).unwrap();
+        let blinding = Scalar::random(&mut rng);
+        let mut commitments = random_commitments(&mut rng, &params, 6);
+        commitments[5] = params.commit(&Scalar::zero(), &blinding);
+        let proof = super::prove(
+            &mut transcript(),
+            &params,
+            &mut rng,
+            &commitments,
+            5,
+            &blinding,
+        )
+        .unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.f[1] += Scalar::one();
+        assert!(tampered
+            .verify(&mut transcript(), &params, &commitments)
+            .is_err());
+
+        let mut tampered = proof.clone();
+        tampered.z_d += Scalar::one();
+        assert!(tampered
+            .verify(&mut transcript(), &params, &commitments)
+            .is_err());
+
+        let mut tampered = proof.clone();
+        tampered.c_d.pop();
+        assert!(tampered
+            .verify(&mut transcript(), &params, &commitments)
+            .is_err());
+
+        // Proof is bound to the transcript
+        let mut other_transcript = Transcript::<sha2::Sha256>::new(b"other");
+        assert!(proof
+            .verify(&mut other_transcript, &params, &commitments)
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
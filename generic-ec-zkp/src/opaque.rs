@@ -0,0 +1,1267 @@
+//! OPAQUE asymmetric password-authenticated key exchange
+//!
+//! [OPAQUE] lets a client authenticate to a server with a password, while the server never
+//! learns the password, not even during registration, and the data stored by the server
+//! (a [`RegistrationRecord`]) doesn't allow offline dictionary attacks without first
+//! compromising the server's OPRF seed. Successful login establishes a shared session key
+//! between client and server.
+//!
+//! The protocol is composed of:
+//! * OPRF: 2HashDH oblivious pseudorandom function from [RFC 9497], password is hashed to curve
+//!   and blinded by the client, the server evaluates it under a per-user OPRF key
+//! * Envelope: client key pair is derived from the OPRF output, the envelope authenticates
+//!   server public key and identities under the same output
+//! * 3DH authenticated key exchange
+//!
+//! All message types are sans-IO: they're plain structs with public fields, and it's up to the
+//! caller to serialize them and carry them over the network. Parties are generic over the curve
+//! `E`, which determines the OPRF ciphersuite (see [`OprfCurve`]), and hash function `D` used
+//! for HKDF and HMAC.
+//!
+//! The implementation follows [OPAQUE] and is tested against its test vectors for
+//! `P256-SHA256` OPRF with SHA-256, HKDF-SHA256 and HMAC-SHA256. Key stretching function is
+//! identity, so passwords should be processed by a memory-hard function (e.g. Argon2 or scrypt)
+//! before they're passed to the protocol.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::curves::Secp256r1;
+//! use generic_ec_zkp::opaque::{ClientLogin, ClientRegistration, ServerLogin, ServerSetup};
+//! # let mut rng = rand::rngs::OsRng;
+//! type E = Secp256r1;
+//! type D = sha2::Sha256;
+//!
+//! let server_setup = ServerSetup::<E, D>::new(&mut rng);
+//!
+//! // Registration
+//! let (client, request) = ClientRegistration::<E>::start(&mut rng, b"password")?;
+//! let response = server_setup.registration_response(b"alice", &request)?;
+//! let (record, export_key) = client.finish::<D>(&mut rng, &response, b"alice", b"server")?;
+//! // server stores the `record` for user `alice`
+//!
+//! // Login
+//! let (client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"password")?;
+//! let (server, ke2) = ServerLogin::start(
+//!     &mut rng, &server_setup, &record, b"alice", &ke1, b"alice", b"server", b"app context",
+//! )?;
+//! let (ke3, client_output) = client.finish(&ke2, b"alice", b"server", b"app context")?;
+//! let server_session_key = server.finish(&ke3)?;
+//!
+//! assert_eq!(client_output.session_key, server_session_key);
+//! assert_eq!(client_output.export_key, export_key);
+//! # Ok::<_, generic_ec_zkp::opaque::OpaqueError>(())
+//! ```
+//!
+//! [OPAQUE]: https://www.rfc-editor.org/rfc/rfc9807.html
+//! [RFC 9497]: https://www.rfc-editor.org/rfc/rfc9497.html
+
+use alloc::vec::Vec;
+
+use digest::{crypto_common::BlockSizeUser, Digest, Output};
+use generic_ec::{
+    errors::HashError,
+    hash_to_curve::{FromHash, Tag},
+    Curve, NonZero, Point, Scalar, SecretScalar,
+};
+use hkdf::{SimpleHkdf, SimpleHkdfExtract};
+use hmac::{Mac, SimpleHmac};
+use rand_core::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+/// Size of nonces in bytes
+pub const NONCE_LEN: usize = 32;
+/// Size of seeds from which key pairs are derived, $N_{seed}$
+const SEED_LEN: usize = 32;
+
+/// Curve with OPRF ciphersuite defined in [RFC 9497](https://www.rfc-editor.org/rfc/rfc9497.html)
+///
+/// The trait is sealed and implemented for `Secp256r1` (`P256-SHA256` ciphersuite).
+pub trait OprfCurve: Curve + sealed::Sealed {
+    /// Ciphersuite identifier
+    const IDENTIFIER: &'static [u8];
+    /// Hash function of the ciphersuite
+    type Hash: Digest;
+}
+
+impl OprfCurve for generic_ec::curves::Secp256r1 {
+    const IDENTIFIER: &'static [u8] = b"P256-SHA256";
+    type Hash = sha2::Sha256;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for generic_ec::curves::Secp256r1 {}
+}
+
+/// Secret key known only to the client, derived from the password and the envelope
+///
+/// Can be used for application-specific purposes, e.g. encrypting data stored on the server.
+pub type ExportKey<D> = Zeroizing<Output<D>>;
+
+/// Session key shared by client and server after successful login
+pub type SessionKey<D> = Zeroizing<Output<D>>;
+
+/// Server long-term parameters, shared by all users
+pub struct ServerSetup<E: Curve, D: Digest> {
+    oprf_seed: Zeroizing<Output<D>>,
+    secret_key: NonZero<SecretScalar<E>>,
+    public_key: NonZero<Point<E>>,
+}
+
+impl<E: OprfCurve, D: Digest + BlockSizeUser + Clone> ServerSetup<E, D> {
+    /// Generates server OPRF seed and key pair
+    pub fn new(rng: &mut (impl RngCore + CryptoRng)) -> Self {
+        let mut oprf_seed = Output::<D>::default();
+        rng.fill_bytes(&mut oprf_seed);
+        let secret_key = NonZero::<SecretScalar<E>>::random(rng);
+        Self::from_parts(oprf_seed, secret_key)
+    }
+
+    /// Constructs server setup from OPRF seed and server secret key
+    pub fn from_parts(oprf_seed: Output<D>, secret_key: NonZero<SecretScalar<E>>) -> Self {
+        let public_key = Point::generator() * &secret_key;
+        Self {
+            oprf_seed: Zeroizing::new(oprf_seed),
+            secret_key,
+            public_key,
+        }
+    }
+
+    /// Server public key
+    pub fn public_key(&self) -> &NonZero<Point<E>> {
+        &self.public_key
+    }
+
+    /// Responds to client registration request
+    ///
+    /// `credential_id` uniquely identifies the user on the server, the same value must be
+    /// used in [`ServerLogin::start`].
+    pub fn registration_response(
+        &self,
+        credential_id: &[u8],
+        request: &RegistrationRequest<E>,
+    ) -> Result<RegistrationResponse<E>, OpaqueError>
+    where
+        Scalar<E>: FromHash,
+    {
+        Ok(RegistrationResponse {
+            evaluated: self.evaluate(credential_id, &request.blinded)?,
+            server_public_key: self.public_key,
+        })
+    }
+
+    /// Generates a fake record for login attempts to unregistered users
+    ///
+    /// Responding to such attempts with a fake record rather than with an error prevents
+    /// client enumeration. The fake record must be stored and reused for the same
+    /// credential identifier.
+    pub fn fake_record(&self, rng: &mut (impl RngCore + CryptoRng)) -> RegistrationRecord<E, D> {
+        let mut masking_key = Output::<D>::default();
+        rng.fill_bytes(&mut masking_key);
+        let mut auth_tag = Output::<D>::default();
+        rng.fill_bytes(&mut auth_tag);
+        RegistrationRecord {
+            client_public_key: Point::generator() * NonZero::<Scalar<E>>::random(rng),
+            masking_key,
+            envelope: Envelope {
+                nonce: random_nonce(rng),
+                auth_tag,
+            },
+        }
+    }
+
+    /// OPRF evaluation of `blinded` element under OPRF key of `credential_id`
+    fn evaluate(&self, credential_id: &[u8], blinded: &Point<E>) -> Result<Point<E>, OpaqueError>
+    where
+        Scalar<E>: FromHash,
+    {
+        if blinded.is_zero() {
+            return Err(OpaqueError(Reason::InvalidMessage));
+        }
+        let mut seed = Zeroizing::new(alloc::vec![0u8; Scalar::<E>::serialized_len()]);
+        expand_into::<D>(&self.oprf_seed, &[credential_id, b"OprfKey"], &mut seed)?;
+        let oprf_key = derive_key_pair::<E>(&seed, b"OPAQUE-DeriveKeyPair")?;
+        Ok(*blinded * &oprf_key)
+    }
+}
+
+/// Registration request, sent by the client to the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistrationRequest<E: Curve> {
+    /// Blinded password
+    pub blinded: Point<E>,
+}
+
+/// Registration response, sent by the server to the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistrationResponse<E: Curve> {
+    /// OPRF evaluation of the blinded password
+    pub evaluated: Point<E>,
+    /// Server public key
+    pub server_public_key: NonZero<Point<E>>,
+}
+
+/// Registration record, sent by the client to the server and stored by the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrationRecord<E: Curve, D: Digest> {
+    /// Client public key
+    pub client_public_key: NonZero<Point<E>>,
+    /// Key masking the credential response
+    pub masking_key: Output<D>,
+    /// Client envelope
+    pub envelope: Envelope<D>,
+}
+
+/// Envelope authenticating server public key and identities
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope<D: Digest> {
+    /// Envelope nonce
+    pub nonce: [u8; NONCE_LEN],
+    /// Authentication tag
+    pub auth_tag: Output<D>,
+}
+
+/// Client state during registration
+pub struct ClientRegistration<E: Curve> {
+    password: Zeroizing<Vec<u8>>,
+    blind: NonZero<SecretScalar<E>>,
+}
+
+impl<E: OprfCurve> ClientRegistration<E>
+where
+    Point<E>: FromHash,
+    Scalar<E>: FromHash,
+{
+    /// Starts registration
+    ///
+    /// Returns client state and request that needs to be sent to the server.
+    pub fn start(
+        rng: &mut (impl RngCore + CryptoRng),
+        password: &[u8],
+    ) -> Result<(Self, RegistrationRequest<E>), OpaqueError> {
+        Self::start_with_blind(NonZero::<SecretScalar<E>>::random(rng), password)
+    }
+
+    fn start_with_blind(
+        blind: NonZero<SecretScalar<E>>,
+        password: &[u8],
+    ) -> Result<(Self, RegistrationRequest<E>), OpaqueError> {
+        let blinded = hash_to_group::<E>(password)? * &blind;
+        let client = Self {
+            password: Zeroizing::new(password.to_vec()),
+            blind,
+        };
+        Ok((client, RegistrationRequest { blinded }))
+    }
+
+    /// Completes registration
+    ///
+    /// `client_identity` and `server_identity` are bound to the envelope, the same identities
+    /// must be used during login. If empty, client and server public keys are used as
+    /// identities.
+    ///
+    /// Returns record that needs to be sent to the server, and [export key](ExportKey).
+    pub fn finish<D: Digest + BlockSizeUser + Clone>(
+        self,
+        rng: &mut (impl RngCore + CryptoRng),
+        response: &RegistrationResponse<E>,
+        client_identity: &[u8],
+        server_identity: &[u8],
+    ) -> Result<(RegistrationRecord<E, D>, ExportKey<D>), OpaqueError> {
+        self.finish_with_nonce(
+            random_nonce(rng),
+            response,
+            client_identity,
+            server_identity,
+        )
+    }
+
+    fn finish_with_nonce<D: Digest + BlockSizeUser + Clone>(
+        self,
+        nonce: [u8; NONCE_LEN],
+        response: &RegistrationResponse<E>,
+        client_identity: &[u8],
+        server_identity: &[u8],
+    ) -> Result<(RegistrationRecord<E, D>, ExportKey<D>), OpaqueError> {
+        let randomized_password =
+            finalize::<E, D>(&self.password, &self.blind, &response.evaluated)?;
+        let keys = EnvelopeKeys::<E, D>::derive(&randomized_password, &nonce)?;
+
+        let server_public_key = response.server_public_key.to_bytes(true);
+        let auth_tag =
+            keys.auth_tag(&server_public_key, client_identity, server_identity, &nonce)?;
+
+        let record = RegistrationRecord {
+            client_public_key: keys.client_public_key,
+            masking_key: (*expand::<D>(&randomized_password, &[b"MaskingKey"])?).clone(),
+            envelope: Envelope { nonce, auth_tag },
+        };
+        Ok((record, keys.export_key))
+    }
+}
+
+/// First login message, sent by the client to the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ke1<E: Curve> {
+    /// Blinded password
+    pub blinded: Point<E>,
+    /// Client nonce
+    pub client_nonce: [u8; NONCE_LEN],
+    /// Client ephemeral public key
+    pub client_keyshare: NonZero<Point<E>>,
+}
+
+/// Second login message, sent by the server to the client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ke2<E: Curve, D: Digest> {
+    /// OPRF evaluation of the blinded password
+    pub evaluated: Point<E>,
+    /// Nonce of masked response
+    pub masking_nonce: [u8; NONCE_LEN],
+    /// Masked server public key and client envelope
+    pub masked_response: Vec<u8>,
+    /// Server nonce
+    pub server_nonce: [u8; NONCE_LEN],
+    /// Server ephemeral public key
+    pub server_keyshare: NonZero<Point<E>>,
+    /// Server key confirmation
+    pub server_mac: Output<D>,
+}
+
+/// Third login message, sent by the client to the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ke3<D: Digest> {
+    /// Client key confirmation
+    pub client_mac: Output<D>,
+}
+
+/// Client output of successful login
+pub struct ClientLoginOutput<D: Digest> {
+    /// Session key shared with the server
+    pub session_key: SessionKey<D>,
+    /// Export key, the same as obtained during registration
+    pub export_key: ExportKey<D>,
+    /// Server public key authenticated by the envelope
+    pub server_public_key: Vec<u8>,
+}
+
+/// Client state during login
+pub struct ClientLogin<E: Curve, D: Digest> {
+    password: Zeroizing<Vec<u8>>,
+    blind: NonZero<SecretScalar<E>>,
+    ephemeral_secret: NonZero<SecretScalar<E>>,
+    ke1: Ke1<E>,
+    _hash: core::marker::PhantomData<D>,
+}
+
+impl<E: OprfCurve, D: Digest + BlockSizeUser + Clone> ClientLogin<E, D>
+where
+    Point<E>: FromHash,
+    Scalar<E>: FromHash,
+{
+    /// Starts login
+    ///
+    /// Returns client state and message that needs to be sent to the server.
+    pub fn start(
+        rng: &mut (impl RngCore + CryptoRng),
+        password: &[u8],
+    ) -> Result<(Self, Ke1<E>), OpaqueError> {
+        let blind = NonZero::<SecretScalar<E>>::random(rng);
+        let client_nonce = random_nonce(rng);
+        let keyshare_seed = random_seed(rng);
+        Self::start_with_randomness(blind, client_nonce, &keyshare_seed, password)
+    }
+
+    fn start_with_randomness(
+        blind: NonZero<SecretScalar<E>>,
+        client_nonce: [u8; NONCE_LEN],
+        keyshare_seed: &[u8; SEED_LEN],
+        password: &[u8],
+    ) -> Result<(Self, Ke1<E>), OpaqueError> {
+        let blinded = hash_to_group::<E>(password)? * &blind;
+        let ephemeral_secret = derive_diffie_hellman_key_pair::<E>(keyshare_seed)?;
+        let ke1 = Ke1 {
+            blinded,
+            client_nonce,
+            client_keyshare: Point::generator() * &ephemeral_secret,
+        };
+        let client = Self {
+            password: Zeroizing::new(password.to_vec()),
+            blind,
+            ephemeral_secret,
+            ke1,
+            _hash: core::marker::PhantomData,
+        };
+        Ok((client, ke1))
+    }
+
+    /// Completes login
+    ///
+    /// Identities must be the same as used during registration, `context` must be the same as
+    /// used by the server. Returns message that needs to be sent to the server, and client
+    /// output. Returns error if password is wrong or the server is not authentic.
+    pub fn finish(
+        self,
+        ke2: &Ke2<E, D>,
+        client_identity: &[u8],
+        server_identity: &[u8],
+        context: &[u8],
+    ) -> Result<(Ke3<D>, ClientLoginOutput<D>), OpaqueError> {
+        let randomized_password = finalize::<E, D>(&self.password, &self.blind, &ke2.evaluated)?;
+
+        // Recover the envelope
+        let masking_key = expand::<D>(&randomized_password, &[b"MaskingKey"])?;
+        let mut response = ke2.masked_response.clone();
+        let pad = masking_pad::<E, D>(&masking_key, &ke2.masking_nonce)?;
+        if pad.len() != response.len() {
+            return Err(OpaqueError(Reason::InvalidMessage));
+        }
+        response
+            .iter_mut()
+            .zip(pad.iter())
+            .for_each(|(r, p)| *r ^= p);
+        let (server_public_key, rest) = response.split_at(Point::<E>::serialized_len(true));
+        let (nonce, auth_tag) = rest.split_at(NONCE_LEN);
+        let nonce: [u8; NONCE_LEN] = nonce
+            .try_into()
+            .map_err(|_| OpaqueError(Reason::InvalidMessage))?;
+        let server_public_key = Point::<E>::from_bytes(server_public_key)
+            .ok()
+            .and_then(NonZero::from_point)
+            .ok_or(OpaqueError(Reason::AuthenticationFailed))?;
+
+        let keys = EnvelopeKeys::<E, D>::derive(&randomized_password, &nonce)?;
+        let expected_tag = keys.auth_tag(
+            &server_public_key.to_bytes(true),
+            client_identity,
+            server_identity,
+            &nonce,
+        )?;
+        if !bool::from(expected_tag.as_slice().ct_eq(auth_tag)) {
+            return Err(OpaqueError(Reason::AuthenticationFailed));
+        }
+
+        // 3DH
+        let dh = [
+            ke2.server_keyshare * &self.ephemeral_secret,
+            server_public_key * &self.ephemeral_secret,
+            ke2.server_keyshare * &keys.client_secret_key,
+        ];
+        let client_public_key = keys.client_public_key.to_bytes(true);
+        let server_public_key = server_public_key.to_bytes(true);
+        let preamble = preamble::<E, D>(
+            context,
+            or_default(client_identity, &client_public_key),
+            &self.ke1,
+            or_default(server_identity, &server_public_key),
+            ke2,
+        )?;
+        let ake = AkeKeys::<D>::derive(&dh, preamble.clone().finalize())?;
+        if !bool::from(ake.server_mac()?.ct_eq(&ke2.server_mac)) {
+            return Err(OpaqueError(Reason::AuthenticationFailed));
+        }
+        let client_mac = ake.client_mac(preamble, &ke2.server_mac)?;
+
+        let output = ClientLoginOutput {
+            session_key: ake.session_key,
+            export_key: keys.export_key,
+            server_public_key: server_public_key.to_vec(),
+        };
+        Ok((Ke3 { client_mac }, output))
+    }
+}
+
+/// Server state during login
+pub struct ServerLogin<D: Digest> {
+    expected_client_mac: Output<D>,
+    session_key: SessionKey<D>,
+}
+
+impl<D: Digest + BlockSizeUser + Clone> ServerLogin<D> {
+    /// Responds to client login request
+    ///
+    /// `record` is the record stored during registration of the user identified by
+    /// `credential_id`, or a [fake record](ServerSetup::fake_record) if there's no such user.
+    /// Identities must be the same as used during registration, `context` must be the same as
+    /// used by the client.
+    ///
+    /// Returns server state and message that needs to be sent to the client.
+    #[allow(clippy::too_many_arguments)]
+    pub fn start<E: OprfCurve>(
+        rng: &mut (impl RngCore + CryptoRng),
+        setup: &ServerSetup<E, D>,
+        record: &RegistrationRecord<E, D>,
+        credential_id: &[u8],
+        ke1: &Ke1<E>,
+        client_identity: &[u8],
+        server_identity: &[u8],
+        context: &[u8],
+    ) -> Result<(Self, Ke2<E, D>), OpaqueError>
+    where
+        Scalar<E>: FromHash,
+    {
+        let randomness = ServerLoginRandomness {
+            masking_nonce: random_nonce(rng),
+            server_nonce: random_nonce(rng),
+            keyshare_seed: random_seed(rng),
+        };
+        Self::start_with_randomness(
+            randomness,
+            setup,
+            record,
+            credential_id,
+            ke1,
+            client_identity,
+            server_identity,
+            context,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn start_with_randomness<E: OprfCurve>(
+        randomness: ServerLoginRandomness,
+        setup: &ServerSetup<E, D>,
+        record: &RegistrationRecord<E, D>,
+        credential_id: &[u8],
+        ke1: &Ke1<E>,
+        client_identity: &[u8],
+        server_identity: &[u8],
+        context: &[u8],
+    ) -> Result<(Self, Ke2<E, D>), OpaqueError>
+    where
+        Scalar<E>: FromHash,
+    {
+        let evaluated = setup.evaluate(credential_id, &ke1.blinded)?;
+
+        // Mask server public key and the envelope
+        let server_public_key = setup.public_key.to_bytes(true);
+        let mut masked_response =
+            masking_pad::<E, D>(&record.masking_key, &randomness.masking_nonce)?;
+        masked_response
+            .iter_mut()
+            .zip(
+                server_public_key
+                    .iter()
+                    .chain(&record.envelope.nonce)
+                    .chain(&record.envelope.auth_tag),
+            )
+            .for_each(|(m, x)| *m ^= x);
+
+        let ephemeral_secret = derive_diffie_hellman_key_pair::<E>(&randomness.keyshare_seed)?;
+        let mut ke2 = Ke2 {
+            evaluated,
+            masking_nonce: randomness.masking_nonce,
+            masked_response,
+            server_nonce: randomness.server_nonce,
+            server_keyshare: Point::generator() * &ephemeral_secret,
+            server_mac: Output::<D>::default(),
+        };
+
+        // 3DH
+        let dh = [
+            ke1.client_keyshare * &ephemeral_secret,
+            ke1.client_keyshare * &setup.secret_key,
+            record.client_public_key * &ephemeral_secret,
+        ];
+        let client_public_key = record.client_public_key.to_bytes(true);
+        let preamble = preamble::<E, D>(
+            context,
+            or_default(client_identity, &client_public_key),
+            ke1,
+            or_default(server_identity, &server_public_key),
+            &ke2,
+        )?;
+        let ake = AkeKeys::<D>::derive(&dh, preamble.clone().finalize())?;
+        ke2.server_mac = ake.server_mac()?;
+
+        let server = Self {
+            expected_client_mac: ake.client_mac(preamble, &ke2.server_mac)?,
+            session_key: ake.session_key,
+        };
+        Ok((server, ke2))
+    }
+
+    /// Completes login
+    ///
+    /// Returns session key shared with the client. Returns error if client is not authentic,
+    /// e.g. it used a wrong password.
+    pub fn finish(self, ke3: &Ke3<D>) -> Result<SessionKey<D>, OpaqueError> {
+        if bool::from(self.expected_client_mac.ct_eq(&ke3.client_mac)) {
+            Ok(self.session_key)
+        } else {
+            Err(OpaqueError(Reason::AuthenticationFailed))
+        }
+    }
+}
+
+/// Randomness sampled by the server in [`ServerLogin::start`]
+struct ServerLoginRandomness {
+    masking_nonce: [u8; NONCE_LEN],
+    server_nonce: [u8; NONCE_LEN],
+    keyshare_seed: Zeroizing<[u8; SEED_LEN]>,
+}
+
+/// OPAQUE protocol error
+#[derive(Debug, Clone, Copy)]
+pub struct OpaqueError(Reason);
+
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    Hash(#[cfg_attr(not(feature = "std"), allow(dead_code))] HashError),
+    DeriveKeyPair,
+    InvalidMessage,
+    AuthenticationFailed,
+    Length,
+}
+
+impl From<HashError> for OpaqueError {
+    fn from(err: HashError) -> Self {
+        Self(Reason::Hash(err))
+    }
+}
+
+impl core::fmt::Display for OpaqueError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Reason::Hash(_) => f.write_str("hash to curve failed"),
+            Reason::DeriveKeyPair => f.write_str("couldn't derive key pair"),
+            Reason::InvalidMessage => f.write_str("invalid message"),
+            Reason::AuthenticationFailed => f.write_str("authentication failed"),
+            Reason::Length => f.write_str("input length is out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OpaqueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.0 {
+            Reason::Hash(err) => Some(err),
+            Reason::DeriveKeyPair
+            | Reason::InvalidMessage
+            | Reason::AuthenticationFailed
+            | Reason::Length => None,
+        }
+    }
+}
+
+/// Keys derived from randomized password and envelope nonce
+struct EnvelopeKeys<E: Curve, D: Digest> {
+    auth_key: Zeroizing<Output<D>>,
+    export_key: Zeroizing<Output<D>>,
+    client_secret_key: NonZero<SecretScalar<E>>,
+    client_public_key: NonZero<Point<E>>,
+}
+
+impl<E: OprfCurve, D: Digest + BlockSizeUser + Clone> EnvelopeKeys<E, D>
+where
+    Scalar<E>: FromHash,
+{
+    fn derive(randomized_password: &[u8], nonce: &[u8; NONCE_LEN]) -> Result<Self, OpaqueError> {
+        let mut seed = Zeroizing::new([0u8; SEED_LEN]);
+        expand_into::<D>(
+            randomized_password,
+            &[nonce, b"PrivateKey"],
+            seed.as_mut_slice(),
+        )?;
+        let client_secret_key = derive_diffie_hellman_key_pair::<E>(&seed)?;
+        Ok(Self {
+            auth_key: expand::<D>(randomized_password, &[nonce, b"AuthKey"])?,
+            export_key: expand::<D>(randomized_password, &[nonce, b"ExportKey"])?,
+            client_public_key: Point::generator() * &client_secret_key,
+            client_secret_key,
+        })
+    }
+
+    fn auth_tag(
+        &self,
+        server_public_key: &[u8],
+        client_identity: &[u8],
+        server_identity: &[u8],
+        nonce: &[u8; NONCE_LEN],
+    ) -> Result<Output<D>, OpaqueError> {
+        let client_public_key = self.client_public_key.to_bytes(true);
+        let server_identity = or_default(server_identity, server_public_key);
+        let client_identity = or_default(client_identity, &client_public_key);
+        hmac::<D>(
+            &self.auth_key,
+            &[
+                &nonce[..],
+                server_public_key,
+                &len_prefix(server_identity)?,
+                server_identity,
+                &len_prefix(client_identity)?,
+                client_identity,
+            ],
+        )
+    }
+}
+
+/// Keys of 3DH key exchange
+struct AkeKeys<D: Digest> {
+    session_key: Zeroizing<Output<D>>,
+    km2: Zeroizing<Output<D>>,
+    km3: Zeroizing<Output<D>>,
+    preamble_hash: Output<D>,
+}
+
+impl<D: Digest + BlockSizeUser + Clone> AkeKeys<D> {
+    fn derive<E: Curve>(
+        dh: &[NonZero<Point<E>>; 3],
+        preamble_hash: Output<D>,
+    ) -> Result<Self, OpaqueError> {
+        let [dh1, dh2, dh3] = dh.map(|p| p.to_bytes(true));
+        let prk = extract::<D>(&[&dh1, &dh2, &dh3]);
+        let handshake_secret = expand_label::<D>(&prk, b"HandshakeSecret", &preamble_hash)?;
+        Ok(Self {
+            session_key: expand_label::<D>(&prk, b"SessionKey", &preamble_hash)?,
+            km2: expand_label::<D>(&handshake_secret, b"ServerMAC", b"")?,
+            km3: expand_label::<D>(&handshake_secret, b"ClientMAC", b"")?,
+            preamble_hash,
+        })
+    }
+
+    fn server_mac(&self) -> Result<Output<D>, OpaqueError> {
+        hmac::<D>(&self.km2, &[&self.preamble_hash])
+    }
+
+    fn client_mac(
+        &self,
+        mut preamble: D,
+        server_mac: &Output<D>,
+    ) -> Result<Output<D>, OpaqueError> {
+        preamble.update(server_mac);
+        hmac::<D>(&self.km3, &[&preamble.finalize()])
+    }
+}
+
+/// Hashes the preamble: context, identities, KE1 and KE2 without server MAC
+fn preamble<E: Curve, D: Digest>(
+    context: &[u8],
+    client_identity: &[u8],
+    ke1: &Ke1<E>,
+    server_identity: &[u8],
+    ke2: &Ke2<E, D>,
+) -> Result<D, OpaqueError> {
+    let mut hash = D::new();
+    hash.update(b"OPAQUEv1-");
+    hash.update(len_prefix(context)?);
+    hash.update(context);
+    hash.update(len_prefix(client_identity)?);
+    hash.update(client_identity);
+    hash.update(ke1.blinded.to_bytes(true));
+    hash.update(ke1.client_nonce);
+    hash.update(ke1.client_keyshare.to_bytes(true));
+    hash.update(len_prefix(server_identity)?);
+    hash.update(server_identity);
+    hash.update(ke2.evaluated.to_bytes(true));
+    hash.update(ke2.masking_nonce);
+    hash.update(&ke2.masked_response);
+    hash.update(ke2.server_nonce);
+    hash.update(ke2.server_keyshare.to_bytes(true));
+    Ok(hash)
+}
+
+/// `contextString` of OPRF mode of the ciphersuite
+fn oprf_context<E: OprfCurve>() -> Vec<u8> {
+    [&b"OPRFV1-\x00-"[..], E::IDENTIFIER].concat()
+}
+
+/// `HashToGroup` of the OPRF ciphersuite
+fn hash_to_group<E: OprfCurve>(input: &[u8]) -> Result<Point<E>, OpaqueError>
+where
+    Point<E>: FromHash,
+{
+    let dst = [&b"HashToGroup-"[..], &oprf_context::<E>()].concat();
+    let tag = Tag::new(&dst).ok_or(OpaqueError(Reason::Length))?;
+    Ok(Point::<E>::hash(tag, input)?)
+}
+
+/// `DeriveKeyPair(seed, info)` of the OPRF ciphersuite, returns the secret key
+fn derive_key_pair<E: OprfCurve>(
+    seed: &[u8],
+    info: &[u8],
+) -> Result<NonZero<SecretScalar<E>>, OpaqueError>
+where
+    Scalar<E>: FromHash,
+{
+    let dst = [&b"DeriveKeyPair"[..], &oprf_context::<E>()].concat();
+    let tag = Tag::new(&dst).ok_or(OpaqueError(Reason::Length))?;
+    let info_len = len_prefix(info)?;
+    for counter in 0..=255u8 {
+        let mut sk = Scalar::<E>::hash_concat(tag, &[seed, &info_len, info, &[counter]])?;
+        if let Some(sk) = NonZero::from_secret_scalar(SecretScalar::new(&mut sk)) {
+            return Ok(sk);
+        }
+    }
+    Err(OpaqueError(Reason::DeriveKeyPair))
+}
+
+/// `DeriveDiffieHellmanKeyPair(seed)`, returns the secret key
+fn derive_diffie_hellman_key_pair<E: OprfCurve>(
+    seed: &[u8; SEED_LEN],
+) -> Result<NonZero<SecretScalar<E>>, OpaqueError>
+where
+    Scalar<E>: FromHash,
+{
+    derive_key_pair::<E>(seed, b"OPAQUE-DeriveDiffieHellmanKeyPair")
+}
+
+/// Unblinds OPRF evaluation and derives randomized password
+fn finalize<E: OprfCurve, D: Digest + BlockSizeUser + Clone>(
+    password: &[u8],
+    blind: &NonZero<SecretScalar<E>>,
+    evaluated: &Point<E>,
+) -> Result<Zeroizing<Output<D>>, OpaqueError> {
+    if evaluated.is_zero() {
+        return Err(OpaqueError(Reason::InvalidMessage));
+    }
+    let unblinded = (*evaluated * blind.invert()).to_bytes(true);
+    let oprf_output = Zeroizing::new(
+        E::Hash::new()
+            .chain_update(len_prefix(password)?)
+            .chain_update(password)
+            .chain_update(len_prefix(&unblinded)?)
+            .chain_update(&unblinded)
+            .chain_update(b"Finalize")
+            .finalize(),
+    );
+    // Key stretching function is identity, so stretched output is the same as OPRF output
+    Ok(extract::<D>(&[&oprf_output, &oprf_output]))
+}
+
+/// Pad masking server public key and the envelope
+fn masking_pad<E: Curve, D: Digest + BlockSizeUser + Clone>(
+    masking_key: &[u8],
+    masking_nonce: &[u8; NONCE_LEN],
+) -> Result<Vec<u8>, OpaqueError> {
+    let len = Point::<E>::serialized_len(true) + NONCE_LEN + <D as Digest>::output_size();
+    let mut pad = alloc::vec![0u8; len];
+    expand_into::<D>(
+        masking_key,
+        &[masking_nonce, b"CredentialResponsePad"],
+        &mut pad,
+    )?;
+    Ok(pad)
+}
+
+/// `HMAC(key, message)`, where `message` is concatenation of several parts
+fn hmac<D: Digest + BlockSizeUser>(
+    key: &[u8],
+    message: &[&[u8]],
+) -> Result<Output<D>, OpaqueError> {
+    let mut mac =
+        <SimpleHmac<D> as Mac>::new_from_slice(key).or(Err(OpaqueError(Reason::Length)))?;
+    for part in message {
+        mac.update(part);
+    }
+    Ok(mac.finalize().into_bytes())
+}
+
+/// `HKDF-Extract` with empty salt, `ikm` is concatenation of several parts
+fn extract<D: Digest + BlockSizeUser + Clone>(ikm: &[&[u8]]) -> Zeroizing<Output<D>> {
+    let mut extract = SimpleHkdfExtract::<D>::new(None);
+    for part in ikm {
+        extract.input_ikm(part);
+    }
+    Zeroizing::new(extract.finalize().0)
+}
+
+fn expand<D: Digest + BlockSizeUser + Clone>(
+    prk: &[u8],
+    info: &[&[u8]],
+) -> Result<Zeroizing<Output<D>>, OpaqueError> {
+    let mut out = Zeroizing::new(Output::<D>::default());
+    expand_into::<D>(prk, info, &mut out)?;
+    Ok(out)
+}
+
+fn expand_into<D: Digest + BlockSizeUser + Clone>(
+    prk: &[u8],
+    info: &[&[u8]],
+    out: &mut [u8],
+) -> Result<(), OpaqueError> {
+    SimpleHkdf::<D>::from_prk(prk)
+        .or(Err(OpaqueError(Reason::Length)))?
+        .expand_multi_info(info, out)
+        .or(Err(OpaqueError(Reason::Length)))
+}
+
+/// `Expand-Label(secret, label, context, Nx)`
+fn expand_label<D: Digest + BlockSizeUser + Clone>(
+    secret: &[u8],
+    label: &[u8],
+    context: &[u8],
+) -> Result<Zeroizing<Output<D>>, OpaqueError> {
+    const LABEL_PREFIX: &[u8] = b"OPAQUE-";
+    let length = len_prefix(&Output::<D>::default())?;
+    let label_len =
+        u8::try_from(LABEL_PREFIX.len() + label.len()).or(Err(OpaqueError(Reason::Length)))?;
+    let context_len = u8::try_from(context.len()).or(Err(OpaqueError(Reason::Length)))?;
+    expand::<D>(
+        secret,
+        &[
+            &length,
+            &[label_len],
+            LABEL_PREFIX,
+            label,
+            &[context_len],
+            context,
+        ],
+    )
+}
+
+fn random_nonce(rng: &mut (impl RngCore + CryptoRng)) -> [u8; NONCE_LEN] {
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+    nonce
+}
+
+fn random_seed(rng: &mut (impl RngCore + CryptoRng)) -> Zeroizing<[u8; SEED_LEN]> {
+    let mut seed = Zeroizing::new([0u8; SEED_LEN]);
+    rng.fill_bytes(seed.as_mut_slice());
+    seed
+}
+
+/// Length of `data` as 2-byte big-endian integer, returns error if `data` is longer than
+/// $2^{16} - 1$ bytes
+fn len_prefix(data: &[u8]) -> Result<[u8; 2], OpaqueError> {
+    u16::try_from(data.len())
+        .map(u16::to_be_bytes)
+        .or(Err(OpaqueError(Reason::Length)))
+}
+
+/// Returns `identity`, or `default` if identity is empty
+fn or_default<'a>(identity: &'a [u8], default: &'a [u8]) -> &'a [u8] {
+    if identity.is_empty() {
+        default
+    } else {
+        identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use digest::Output;
+    use generic_ec::{curves::Secp256r1, NonZero, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+    use zeroize::Zeroizing;
+
+    use super::{
+        ClientLogin, ClientRegistration, RegistrationRecord, ServerLogin, ServerLoginRandomness,
+        ServerSetup,
+    };
+
+    type E = Secp256r1;
+    type D = sha2::Sha256;
+
+    fn register(
+        rng: &mut DevRng,
+        setup: &ServerSetup<E, D>,
+        password: &[u8],
+    ) -> RegistrationRecord<E, D> {
+        let (client, request) = ClientRegistration::<E>::start(rng, password).unwrap();
+        let response = setup.registration_response(b"alice", &request).unwrap();
+        let (record, _export_key) = client.finish::<D>(rng, &response, b"", b"server").unwrap();
+        record
+    }
+
+    #[test]
+    fn login() {
+        let mut rng = DevRng::new();
+        let setup = ServerSetup::<E, D>::new(&mut rng);
+        let record = register(&mut rng, &setup, b"password");
+
+        let (client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"password").unwrap();
+        let (server, ke2) = ServerLogin::start(
+            &mut rng, &setup, &record, b"alice", &ke1, b"", b"server", b"ctx",
+        )
+        .unwrap();
+        let (ke3, output) = client.finish(&ke2, b"", b"server", b"ctx").unwrap();
+        let session_key = server.finish(&ke3).unwrap();
+        assert_eq!(output.session_key, session_key);
+        assert_eq!(
+            &output.server_public_key[..],
+            &*setup.public_key().to_bytes(true)
+        );
+
+        // Session keys are fresh in every login
+        let (client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"password").unwrap();
+        let (server, ke2) = ServerLogin::start(
+            &mut rng, &setup, &record, b"alice", &ke1, b"", b"server", b"ctx",
+        )
+        .unwrap();
+        let (ke3, output2) = client.finish(&ke2, b"", b"server", b"ctx").unwrap();
+        assert_eq!(server.finish(&ke3).unwrap(), output2.session_key);
+        assert_ne!(output.session_key, output2.session_key);
+        assert_eq!(output.export_key, output2.export_key);
+    }
+
+    #[test]
+    fn wrong_password() {
+        let mut rng = DevRng::new();
+        let setup = ServerSetup::<E, D>::new(&mut rng);
+        let record = register(&mut rng, &setup, b"password");
+
+        let (client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"passw0rd").unwrap();
+        let (_server, ke2) = ServerLogin::start(
+            &mut rng, &setup, &record, b"alice", &ke1, b"", b"server", b"ctx",
+        )
+        .unwrap();
+        assert!(client.finish(&ke2, b"", b"server", b"ctx").is_err());
+
+        // Login of unregistered user with a fake record fails the same way
+        let fake = setup.fake_record(&mut rng);
+        let (client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"password").unwrap();
+        let (_server, ke2) = ServerLogin::start(
+            &mut rng, &setup, &fake, b"bob", &ke1, b"", b"server", b"ctx",
+        )
+        .unwrap();
+        assert_eq!(ke2.masked_response.len(), {
+            let (_, ke2) = ServerLogin::start(
+                &mut rng, &setup, &record, b"alice", &ke1, b"", b"server", b"ctx",
+            )
+            .unwrap();
+            ke2.masked_response.len()
+        });
+        assert!(client.finish(&ke2, b"", b"server", b"ctx").is_err());
+    }
+
+    #[test]
+    fn mismatched_context() {
+        let mut rng = DevRng::new();
+        let setup = ServerSetup::<E, D>::new(&mut rng);
+        let record = register(&mut rng, &setup, b"password");
+
+        // Different server identity
+        let (client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"password").unwrap();
+        let (_server, ke2) = ServerLogin::start(
+            &mut rng, &setup, &record, b"alice", &ke1, b"", b"server", b"ctx",
+        )
+        .unwrap();
+        assert!(client.finish(&ke2, b"", b"evil server", b"ctx").is_err());
+
+        // Different context
+        let (client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"password").unwrap();
+        let (_server, ke2) = ServerLogin::start(
+            &mut rng, &setup, &record, b"alice", &ke1, b"", b"server", b"ctx",
+        )
+        .unwrap();
+        assert!(client.finish(&ke2, b"", b"server", b"other ctx").is_err());
+
+        // Tampered client MAC
+        let (client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"password").unwrap();
+        let (server, ke2) = ServerLogin::start(
+            &mut rng, &setup, &record, b"alice", &ke1, b"", b"server", b"ctx",
+        )
+        .unwrap();
+        let (mut ke3, _) = client.finish(&ke2, b"", b"server", b"ctx").unwrap();
+        ke3.client_mac[0] ^= 1;
+        assert!(server.finish(&ke3).is_err());
+    }
+
+    #[test]
+    fn oversize_input_is_rejected() {
+        let mut rng = DevRng::new();
+        let setup = ServerSetup::<E, D>::new(&mut rng);
+        let record = register(&mut rng, &setup, b"password");
+        let long = alloc::vec![0u8; usize::from(u16::MAX) + 1];
+
+        // Password, identities and context are length-prefixed with 2 bytes
+        let (client, request) = ClientRegistration::<E>::start(&mut rng, &long).unwrap();
+        let response = setup.registration_response(b"alice", &request).unwrap();
+        assert!(client
+            .finish::<D>(&mut rng, &response, b"", b"server")
+            .is_err());
+        let (client, request) = ClientRegistration::<E>::start(&mut rng, b"password").unwrap();
+        let response = setup.registration_response(b"alice", &request).unwrap();
+        assert!(client
+            .finish::<D>(&mut rng, &response, &long, b"server")
+            .is_err());
+
+        let (_client, ke1) = ClientLogin::<E, D>::start(&mut rng, b"password").unwrap();
+        assert!(ServerLogin::start(
+            &mut rng, &setup, &record, b"alice", &ke1, b"", b"server", &long,
+        )
+        .is_err());
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn hex_array<const N: usize>(s: &str) -> [u8; N] {
+        hex(s).try_into().unwrap()
+    }
+
+    /// Test vectors from RFC 9807, C.1.5 and C.1.6: P256-SHA256, identity KSF, HKDF-SHA256,
+    /// HMAC-SHA256
+    #[test]
+    fn rfc9807_test_vectors() {
+        struct Vector {
+            client_identity: &'static [u8],
+            server_identity: &'static [u8],
+            registration_upload: &'static str,
+            ke2: &'static str,
+            ke3: &'static str,
+            session_key: &'static str,
+        }
+
+        let vectors = [
+            Vector {
+                client_identity: b"",
+                server_identity: b"",
+                registration_upload: "03b218507d978c3db570ca994aaf36695a731ddb2db272c817f79746fc37ae52147f0ed53532d3ae8e505ecc70d42d2b814b6b0e48156def71ea029148b2803aafa921f2a014513bd8a90e477a629794e89fec12d12206dde662ebdcf65670e51fad30bbcfc1f8eda0211553ab9aaf26345ad59a128e80188f035fe4924fad67b8",
+                ke2: "0246da9fe4d41d5ba69faa6c509a1d5bafd49a48615a47a8dd4b0823cc1476481138fe59af0df2c79f57b8780278f5ae47355fe1f817119041951c80f612fdfc6d2f0c547f70deaeca54d878c14c1aa5e1ab405dec833777132eea905c2fbb12504a67dcbe0e66740c76b62c13b04a38a77926e19072953319ec65e41f9bfd2ae26837b6ce688bf9af2542f04eec9ab96a1b9328812dc2f5c89182ed47fead61f09f71cd9960ecef2fe0d0f7494986fa3d8b2bb01963537e60efb13981e138e3d4a103c1701353219b53acf337bf6456a83cefed8f563f1040b65afbf3b65d3bc9a19b50a73b145bc87a157e8c58c0342e2047ee22ae37b63db17e0a82a30fcc4ecf7b",
+                ke3: "e97cab4433aa39d598e76f13e768bba61c682947bdcf9936035e8a3a3ebfb66e",
+                session_key: "484ad345715ccce138ca49e4ea362c6183f0949aaaa1125dc3bc3f80876e7cd1",
+            },
+            Vector {
+                client_identity: b"alice",
+                server_identity: b"bob",
+                registration_upload: "03b218507d978c3db570ca994aaf36695a731ddb2db272c817f79746fc37ae52147f0ed53532d3ae8e505ecc70d42d2b814b6b0e48156def71ea029148b2803aafa921f2a014513bd8a90e477a629794e89fec12d12206dde662ebdcf65670e51f4d7773a36a208a866301dbb2858e40dc5638017527cf91aef32d3848eebe0971",
+                ke2: "0246da9fe4d41d5ba69faa6c509a1d5bafd49a48615a47a8dd4b0823cc1476481138fe59af0df2c79f57b8780278f5ae47355fe1f817119041951c80f612fdfc6d2f0c547f70deaeca54d878c14c1aa5e1ab405dec833777132eea905c2fbb12504a67dcbe0e66740c76b62c13b04a38a77926e19072953319ec65e41f9bfd2ae268d7f106042021c80300e4c6f585980cf39fc51a4a6bba41b0729f9b240c729e5671cd9960ecef2fe0d0f7494986fa3d8b2bb01963537e60efb13981e138e3d4a103c1701353219b53acf337bf6456a83cefed8f563f1040b65afbf3b65d3bc9a19b84922c7e5d074838a8f278592c53f61fb59f031e85ad480c0c71086b871e1b24",
+                ke3: "46833578cee137775f6be3f01b80748daac5a694101ad0e9e7025480552da56a",
+                session_key: "27766fabd8dd88ff37fbd0ef1a491e601d10d9f016c2b28c4bd1b0fb7511a3c3",
+            },
+        ];
+
+        let secret = |s: &str| {
+            let mut scalar = Scalar::<E>::from_be_bytes(hex(s)).unwrap();
+            NonZero::from_secret_scalar(SecretScalar::new(&mut scalar)).unwrap()
+        };
+        let context = hex("4f50415155452d504f43");
+        let credential_id = hex("31323334");
+        let password = hex("436f7272656374486f72736542617474657279537461706c65");
+
+        for vector in vectors {
+            let setup = ServerSetup::<E, D>::from_parts(
+                Output::<D>::clone_from_slice(&hex(
+                    "62f60b286d20ce4fd1d64809b0021dad6ed5d52a2c8cf27ae6582543a0a8dce2",
+                )),
+                secret("c36139381df63bfc91c850db0b9cfbec7a62e86d80040a41aa7725bf0e79d5e5"),
+            );
+            assert_eq!(
+                setup.public_key().to_bytes(true).to_vec(),
+                hex("035f40ff9cf88aa1f5cd4fe5fd3da9ea65a4923a5594f84fd9f2092d6067784874")
+            );
+
+            // Registration
+            let (client, request) = ClientRegistration::<E>::start_with_blind(
+                secret("411bf1a62d119afe30df682b91a0a33d777972d4f2daa4b34ca527d597078153"),
+                &password,
+            )
+            .unwrap();
+            assert_eq!(
+                request.blinded.to_bytes(true).to_vec(),
+                hex("029e949a29cfa0bf7c1287333d2fb3dc586c41aa652f5070d26a5315a1b50229f8")
+            );
+            let response = setup
+                .registration_response(&credential_id, &request)
+                .unwrap();
+            assert_eq!(
+                [
+                    &response.evaluated.to_bytes(true)[..],
+                    &response.server_public_key.to_bytes(true),
+                ]
+                .concat(),
+                hex("0350d3694c00978f00a5ce7cd08a00547e4ab5fb5fc2b2f6717cdaa6c89136efef035f40ff9cf88aa1f5cd4fe5fd3da9ea65a4923a5594f84fd9f2092d6067784874")
+            );
+            let (record, export_key) = client
+                .finish_with_nonce::<D>(
+                    hex_array("a921f2a014513bd8a90e477a629794e89fec12d12206dde662ebdcf65670e51f"),
+                    &response,
+                    vector.client_identity,
+                    vector.server_identity,
+                )
+                .unwrap();
+            assert_eq!(
+                [
+                    &record.client_public_key.to_bytes(true)[..],
+                    &record.masking_key,
+                    &record.envelope.nonce,
+                    &record.envelope.auth_tag,
+                ]
+                .concat(),
+                hex(vector.registration_upload)
+            );
+            assert_eq!(
+                export_key.to_vec(),
+                hex("c3c9a1b0e33ac84dd83d0b7e8af6794e17e7a3caadff289fbd9dc769a853c64b")
+            );
+
+            // Login
+            let (client, ke1) = ClientLogin::<E, D>::start_with_randomness(
+                secret("c497fddf6056d241e6cf9fb7ac37c384f49b357a221eb0a802c989b9942256c1"),
+                hex_array("ab3d33bde0e93eda72392346a7a73051110674bbf6b1b7ffab8be4f91fdaeeb1"),
+                &hex_array("633b875d74d1556d2a2789309972b06db21dfcc4f5ad51d7e74d783b7cfab8dc"),
+                &password,
+            )
+            .unwrap();
+            assert_eq!(
+                [
+                    &ke1.blinded.to_bytes(true)[..],
+                    &ke1.client_nonce,
+                    &ke1.client_keyshare.to_bytes(true),
+                ]
+                .concat(),
+                hex("037342f0bcb3ecea754c1e67576c86aa90c1de3875f390ad599a26686cdfee6e07ab3d33bde0e93eda72392346a7a73051110674bbf6b1b7ffab8be4f91fdaeeb1022ed3f32f318f81bab80da321fecab3cd9b6eea11a95666dfa6beeaab321280b6")
+            );
+
+            let randomness = ServerLoginRandomness {
+                masking_nonce: hex_array(
+                    "38fe59af0df2c79f57b8780278f5ae47355fe1f817119041951c80f612fdfc6d",
+                ),
+                server_nonce: hex_array(
+                    "71cd9960ecef2fe0d0f7494986fa3d8b2bb01963537e60efb13981e138e3d4a1",
+                ),
+                keyshare_seed: Zeroizing::new(hex_array(
+                    "05a4f54206eef1ba2f615bc0aa285cb22f26d1153b5b40a1e85ff80da12f982f",
+                )),
+            };
+            let (server, ke2) = ServerLogin::start_with_randomness(
+                randomness,
+                &setup,
+                &record,
+                &credential_id,
+                &ke1,
+                vector.client_identity,
+                vector.server_identity,
+                &context,
+            )
+            .unwrap();
+            assert_eq!(
+                [
+                    &ke2.evaluated.to_bytes(true)[..],
+                    &ke2.masking_nonce,
+                    &ke2.masked_response,
+                    &ke2.server_nonce,
+                    &ke2.server_keyshare.to_bytes(true),
+                    &ke2.server_mac,
+                ]
+                .concat(),
+                hex(vector.ke2)
+            );
+
+            let (ke3, output) = client
+                .finish(
+                    &ke2,
+                    vector.client_identity,
+                    vector.server_identity,
+                    &context,
+                )
+                .unwrap();
+            assert_eq!(ke3.client_mac.to_vec(), hex(vector.ke3));
+            assert_eq!(output.export_key, export_key);
+            assert_eq!(output.session_key.to_vec(), hex(vector.session_key));
+            assert_eq!(server.finish(&ke3).unwrap(), output.session_key);
+        }
+    }
+}
@@ -0,0 +1,445 @@
+//! Password-authenticated key exchange (PAKE): SPAKE2 and CPace
+//!
+//! Two parties sharing a low-entropy password establish a high-entropy shared key over an
+//! insecure channel. An attacker who doesn't know the password can test only one password guess
+//! per protocol execution, and passive observers learn nothing about the password.
+//!
+//! Both protocols are generic over curves that implement hash to curve (i.e.
+//! `Point<E>: FromHash`), and parametrized by hash function `D` used to derive the key. Each
+//! party sends a single point to the other party, so the protocols are one round.
+//!
+//! * [`Spake2`] is SPAKE2 from [RFC 9382]. Curve-specific points $M$ and $N$ are derived via
+//!   hash to curve ([`spake2_generators`]), so nobody knows their discrete logarithms. Parties
+//!   have distinct roles $A$ and $B$ and identities.
+//! * [`CPace`] is CPace from [draft-irtf-cfrg-cpace]. Password-dependent generator is derived
+//!   via hash to curve from the password, channel identifier and session identifier.
+//!
+//! Protocols follow the structure of the specifications, but they're not wire-compatible with
+//! them: $M$, $N$ and password-dependent generator are derived with domain separation tags
+//! of this crate, and the key schedule is simplified to hashing the transcript with `D`.
+//!
+//! Passwords should be processed by a memory-hard function (e.g. Argon2 or scrypt) before they're
+//! passed to the protocol: it's mapped to a scalar or point via hash to curve which is fast.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::curves::Secp256k1;
+//! use generic_ec_zkp::pake::{Role, Spake2};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let (alice, msg_a) = Spake2::<Secp256k1, sha2::Sha256>::start(
+//!     &mut rng, Role::A, b"password", b"alice", b"bob",
+//! )?;
+//! let (bob, msg_b) = Spake2::<Secp256k1, sha2::Sha256>::start(
+//!     &mut rng, Role::B, b"password", b"alice", b"bob",
+//! )?;
+//!
+//! let alice = alice.finish(&msg_b)?;
+//! let bob = bob.finish(&msg_a)?;
+//!
+//! // Parties exchange key confirmation messages to make sure they derived the same key, the
+//! // key is only available after confirmation of the other party is verified
+//! let (confirmation_a, confirmation_b) = (alice.confirmation(), bob.confirmation());
+//! let alice_key = alice.verify_confirmation(&confirmation_b)?;
+//! let bob_key = bob.verify_confirmation(&confirmation_a)?;
+//! assert_eq!(alice_key, bob_key);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [RFC 9382]: https://www.rfc-editor.org/rfc/rfc9382.html
+//! [draft-irtf-cfrg-cpace]: https://datatracker.ietf.org/doc/draft-irtf-cfrg-cpace/
+
+use digest::{Digest, Output};
+use generic_ec::{
+    errors::HashError,
+    hash_to_curve::{FromHash, Tag},
+    Curve, Point, Scalar, SecretScalar,
+};
+use rand_core::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+const SPAKE2_GENERATORS_TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp/pake/spake2/generators");
+const SPAKE2_PASSWORD_TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp/pake/spake2/password");
+const CPACE_GENERATOR_TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp/pake/cpace/generator");
+
+/// Key established via PAKE
+pub type SessionKey<D> = Zeroizing<Output<D>>;
+
+/// Role of the party in the protocol
+///
+/// Parties must take different roles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Party $A$ in SPAKE2, initiator in CPace
+    A,
+    /// Party $B$ in SPAKE2, responder in CPace
+    B,
+}
+
+impl Role {
+    fn other(self) -> Self {
+        match self {
+            Role::A => Role::B,
+            Role::B => Role::A,
+        }
+    }
+}
+
+/// Derives SPAKE2 points $M$ and $N$ on curve `E`
+///
+/// Points are obtained via hash to curve, so their discrete logarithms are unknown.
+pub fn spake2_generators<E: Curve>() -> Result<(Point<E>, Point<E>), HashError>
+where
+    Point<E>: FromHash,
+{
+    let m = Point::hash_concat(SPAKE2_GENERATORS_TAG, &[E::CURVE_NAME.as_bytes(), b"/M"])?;
+    let n = Point::hash_concat(SPAKE2_GENERATORS_TAG, &[E::CURVE_NAME.as_bytes(), b"/N"])?;
+    Ok((m, n))
+}
+
+/// SPAKE2 party state after sending its message
+pub struct Spake2<E: Curve, D: Digest> {
+    role: Role,
+    w: SecretScalar<E>,
+    x: SecretScalar<E>,
+    m: Point<E>,
+    n: Point<E>,
+    msg: Point<E>,
+    /// Hash of the transcript prefix: identities of parties
+    transcript: D,
+}
+
+impl<E: Curve, D: Digest> Spake2<E, D>
+where
+    Point<E>: FromHash,
+    Scalar<E>: FromHash,
+{
+    /// Starts the protocol
+    ///
+    /// `id_a` and `id_b` are identities of parties $A$ and $B$, may be empty if parties have no
+    /// identities. Both parties must use the same identities and the same password.
+    ///
+    /// Returns party state and message that needs to be sent to the other party.
+    pub fn start(
+        rng: &mut (impl RngCore + CryptoRng),
+        role: Role,
+        password: &[u8],
+        id_a: &[u8],
+        id_b: &[u8],
+    ) -> Result<(Self, Point<E>), HashError> {
+        let (m, n) = spake2_generators::<E>()?;
+        let mut w = Scalar::hash(SPAKE2_PASSWORD_TAG, password)?;
+        let w = SecretScalar::new(&mut w);
+        let x = SecretScalar::random(rng);
+
+        let blinding = match role {
+            Role::A => m,
+            Role::B => n,
+        };
+        let msg = Point::generator() * &x + blinding * &w;
+
+        let mut transcript = D::new();
+        append_lv(&mut transcript, id_a);
+        append_lv(&mut transcript, id_b);
+
+        let party = Self {
+            role,
+            w,
+            x,
+            m,
+            n,
+            msg,
+            transcript,
+        };
+        Ok((party, msg))
+    }
+
+    /// Completes the protocol using message received from the other party
+    ///
+    /// Returns error if message is invalid. Note that successful completion doesn't mean that
+    /// parties used the same password: it's detected by exchanging
+    /// [key confirmations](Spake2Key::confirmation).
+    pub fn finish(self, peer_msg: &Point<E>) -> Result<Spake2Key<D>, PakeError> {
+        if peer_msg.is_zero() {
+            return Err(PakeError(Reason::InvalidMessage));
+        }
+        let peer_blinding = match self.role {
+            Role::A => self.n,
+            Role::B => self.m,
+        };
+        let k = (*peer_msg - peer_blinding * &self.w) * &self.x;
+        if k.is_zero() {
+            return Err(PakeError(Reason::InvalidMessage));
+        }
+
+        let (msg_a, msg_b) = match self.role {
+            Role::A => (&self.msg, peer_msg),
+            Role::B => (peer_msg, &self.msg),
+        };
+        let mut transcript = self.transcript;
+        append_lv(&mut transcript, &msg_a.to_bytes(true));
+        append_lv(&mut transcript, &msg_b.to_bytes(true));
+        append_lv(&mut transcript, &k.to_bytes(true));
+        self.w
+            .expose_secret(|w| append_lv(&mut transcript, &w.to_be_bytes()));
+        let transcript = Zeroizing::new(transcript.finalize());
+
+        Ok(Spake2Key {
+            role: self.role,
+            key: derive::<D>(b"spake2/key", &transcript),
+            confirmation_a: derive::<D>(b"spake2/confirmation/A", &transcript),
+            confirmation_b: derive::<D>(b"spake2/confirmation/B", &transcript),
+        })
+    }
+}
+
+/// Key established via SPAKE2, awaiting key confirmation
+///
+/// The key is handed out by [`verify_confirmation`](Self::verify_confirmation) once the other
+/// party proved that it derived the same key.
+pub struct Spake2Key<D: Digest> {
+    role: Role,
+    key: SessionKey<D>,
+    confirmation_a: Zeroizing<Output<D>>,
+    confirmation_b: Zeroizing<Output<D>>,
+}
+
+impl<D: Digest> Spake2Key<D> {
+    /// Key confirmation message that needs to be sent to the other party
+    pub fn confirmation(&self) -> Output<D> {
+        match self.role {
+            Role::A => (*self.confirmation_a).clone(),
+            Role::B => (*self.confirmation_b).clone(),
+        }
+    }
+
+    /// Verifies key confirmation received from the other party, returns the shared key
+    ///
+    /// Returns error if parties derived different keys, which means they used different
+    /// passwords or identities, or the protocol was attacked.
+    pub fn verify_confirmation(self, confirmation: &[u8]) -> Result<SessionKey<D>, PakeError> {
+        let expected = match self.role.other() {
+            Role::A => &self.confirmation_a,
+            Role::B => &self.confirmation_b,
+        };
+        if bool::from(expected.as_slice().ct_eq(confirmation)) {
+            Ok(self.key)
+        } else {
+            Err(PakeError(Reason::ConfirmationMismatch))
+        }
+    }
+}
+
+/// CPace party state after sending its message
+pub struct CPace<E: Curve, D: Digest> {
+    role: Role,
+    y: SecretScalar<E>,
+    msg: Point<E>,
+    sid: D,
+}
+
+impl<E: Curve, D: Digest> CPace<E, D>
+where
+    Point<E>: FromHash,
+{
+    /// Starts the protocol
+    ///
+    /// * `channel_id` identifies the parties and the channel between them, e.g. concatenation
+    ///   of parties identities
+    /// * `sid` is a session identifier, unique per protocol execution. If parties don't have
+    ///   one, it may be empty, but then the protocol doesn't guarantee that the established
+    ///   key is unique per execution
+    ///
+    /// Both parties must use the same password, channel and session identifiers. Returns party
+    /// state and message that needs to be sent to the other party.
+    pub fn start(
+        rng: &mut (impl RngCore + CryptoRng),
+        role: Role,
+        password: &[u8],
+        channel_id: &[u8],
+        sid: &[u8],
+    ) -> Result<(Self, Point<E>), HashError> {
+        let g = Point::<E>::hash_concat(
+            CPACE_GENERATOR_TAG,
+            &[
+                &lv_prefix(password),
+                password,
+                &lv_prefix(channel_id),
+                channel_id,
+                &lv_prefix(sid),
+                sid,
+            ],
+        )?;
+        let y = SecretScalar::random(rng);
+        let msg = g * &y;
+
+        let mut sid_hash = D::new();
+        append_lv(&mut sid_hash, sid);
+
+        let party = Self {
+            role,
+            y,
+            msg,
+            sid: sid_hash,
+        };
+        Ok((party, msg))
+    }
+
+    /// Completes the protocol using message received from the other party
+    ///
+    /// Returns intermediate session key. Returns error if message is invalid. Parties used the
+    /// same password if and only if they derived the same key, which can be checked by
+    /// the higher-level protocol, e.g. via authenticated encryption.
+    pub fn finish(self, peer_msg: &Point<E>) -> Result<SessionKey<D>, PakeError> {
+        let k = *peer_msg * &self.y;
+        if k.is_zero() {
+            return Err(PakeError(Reason::InvalidMessage));
+        }
+        let (msg_a, msg_b) = match self.role {
+            Role::A => (&self.msg, peer_msg),
+            Role::B => (peer_msg, &self.msg),
+        };
+        let mut isk = self.sid;
+        append_lv(&mut isk, b"cpace/isk");
+        append_lv(&mut isk, &k.to_bytes(true));
+        append_lv(&mut isk, &msg_a.to_bytes(true));
+        append_lv(&mut isk, &msg_b.to_bytes(true));
+        Ok(Zeroizing::new(isk.finalize()))
+    }
+}
+
+/// PAKE protocol error
+#[derive(Debug, Clone, Copy)]
+pub struct PakeError(Reason);
+
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    InvalidMessage,
+    ConfirmationMismatch,
+}
+
+impl core::fmt::Display for PakeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Reason::InvalidMessage => f.write_str("invalid message from the other party"),
+            Reason::ConfirmationMismatch => f.write_str("key confirmation mismatch"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PakeError {}
+
+/// Length prefix of `data`: its length as 8-byte little-endian integer
+fn lv_prefix(data: &[u8]) -> [u8; 8] {
+    (data.len() as u64).to_le_bytes()
+}
+
+/// Appends length-prefixed `data` to the hash
+fn append_lv(hash: &mut impl Digest, data: &[u8]) {
+    hash.update(lv_prefix(data));
+    hash.update(data);
+}
+
+fn derive<D: Digest>(label: &[u8], transcript: &Output<D>) -> Zeroizing<Output<D>> {
+    let mut hash = D::new();
+    append_lv(&mut hash, label);
+    append_lv(&mut hash, transcript);
+    Zeroizing::new(hash.finalize())
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{hash_to_curve::FromHash, Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    use super::{CPace, Role, Spake2};
+
+    type D = sha2::Sha256;
+
+    #[test]
+    fn spake2<E: Curve>()
+    where
+        Point<E>: FromHash,
+        Scalar<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+
+        let (m, n) = super::spake2_generators::<E>().unwrap();
+        assert_ne!(m, n);
+        assert!(!m.is_zero() && !n.is_zero());
+
+        let start = |rng: &mut DevRng, role, password: &[u8]| {
+            Spake2::<E, D>::start(rng, role, password, b"alice", b"bob").unwrap()
+        };
+
+        // Same password
+        let (alice, msg_a) = start(&mut rng, Role::A, b"password");
+        let (bob, msg_b) = start(&mut rng, Role::B, b"password");
+        let alice = alice.finish(&msg_b).unwrap();
+        let bob = bob.finish(&msg_a).unwrap();
+        let (confirmation_a, confirmation_b) = (alice.confirmation(), bob.confirmation());
+        assert_ne!(confirmation_a, confirmation_b);
+        let alice_key = alice.verify_confirmation(&confirmation_b).unwrap();
+        let bob_key = bob.verify_confirmation(&confirmation_a).unwrap();
+        assert_eq!(alice_key, bob_key);
+
+        // Party's own confirmation is not accepted
+        let (alice, _) = start(&mut rng, Role::A, b"password");
+        let (_, msg_b) = start(&mut rng, Role::B, b"password");
+        let alice = alice.finish(&msg_b).unwrap();
+        let confirmation_a = alice.confirmation();
+        assert!(alice.verify_confirmation(&confirmation_a).is_err());
+
+        // Different passwords
+        let (alice, msg_a) = start(&mut rng, Role::A, b"password");
+        let (bob, msg_b) = start(&mut rng, Role::B, b"passw0rd");
+        let alice = alice.finish(&msg_b).unwrap();
+        let bob = bob.finish(&msg_a).unwrap();
+        let (confirmation_a, confirmation_b) = (alice.confirmation(), bob.confirmation());
+        assert!(alice.verify_confirmation(&confirmation_b).is_err());
+        assert!(bob.verify_confirmation(&confirmation_a).is_err());
+
+        // Identity point is rejected
+        let (alice, _) = start(&mut rng, Role::A, b"password");
+        assert!(alice.finish(&Point::zero()).is_err());
+    }
+
+    #[test]
+    fn cpace<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+
+        let start = |rng: &mut DevRng, role, password: &[u8], sid: &[u8]| {
+            CPace::<E, D>::start(rng, role, password, b"alice/bob", sid).unwrap()
+        };
+
+        // Same password
+        let (alice, msg_a) = start(&mut rng, Role::A, b"password", b"session 1");
+        let (bob, msg_b) = start(&mut rng, Role::B, b"password", b"session 1");
+        assert_eq!(alice.finish(&msg_b).unwrap(), bob.finish(&msg_a).unwrap());
+
+        // Different passwords
+        let (alice, msg_a) = start(&mut rng, Role::A, b"password", b"session 1");
+        let (bob, msg_b) = start(&mut rng, Role::B, b"passw0rd", b"session 1");
+        assert_ne!(alice.finish(&msg_b).unwrap(), bob.finish(&msg_a).unwrap());
+
+        // Different sessions
+        let (alice, msg_a) = start(&mut rng, Role::A, b"password", b"session 1");
+        let (bob, msg_b) = start(&mut rng, Role::B, b"password", b"session 2");
+        assert_ne!(alice.finish(&msg_b).unwrap(), bob.finish(&msg_a).unwrap());
+
+        // Identity point is rejected
+        let (alice, _) = start(&mut rng, Role::A, b"password", b"session 1");
+        assert!(alice.finish(&Point::zero()).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
@@ -0,0 +1,158 @@
+//! Indexes of parties and shares in threshold protocols
+//!
+//! Threshold modules of this crate ([beacon](crate::beacon), [DVRF](crate::dvrf),
+//! [PVSS](crate::pvss), etc.) share a secret via polynomial $f$: party $i \in [0, n)$ holds share
+//! $f(i + 1)$, as $f(0)$ is the secret itself. Mixing up these two numbers is an easy mistake:
+//! evaluating $f$ at party index leaks the secret to the first party, and interpolating at wrong
+//! points silently produces a wrong result.
+//!
+//! Two newtypes keep them apart:
+//!
+//! * [`PartyIndex`] is zero-based position of the party, used for addressing parties and
+//!   looking up their keys
+//! * [`ShareIndex`] is a point at which sharing polynomial is evaluated. It's never zero, and it
+//!   can be converted into a [non-zero scalar](generic_ec::NonZero) for evaluation and
+//!   interpolation
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Scalar, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{party_index::PartyIndex, polynomial::{Polynomial, lagrange_coefficient}};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let secret = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let f = Polynomial::sample_with_const_term(&mut rng, 1, secret.clone());
+//!
+//! let parties = [PartyIndex(0), PartyIndex(2)];
+//! let xs = parties.map(|i| i.share_index().to_scalar());
+//! let shares = xs.map(|x| f.value::<_, Scalar<_>>(&x));
+//!
+//! let reconstructed: Scalar<_> = (0..2)
+//!     .map(|j| lagrange_coefficient(Scalar::zero(), j, &xs).map(|l| l * shares[j]))
+//!     .sum::<Option<_>>()
+//!     .expect("indexes are distinct");
+//! assert_eq!(reconstructed, secret.expose_secret(|x| *x));
+//! ```
+
+use core::fmt;
+use core::num::NonZeroU32;
+
+use generic_ec::{Curve, NonZero, Scalar};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Zero-based index of a party $i \in [0, n)$
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable))]
+pub struct PartyIndex(pub u16);
+
+/// Index of a share $i + 1$, i.e. non-zero point at which sharing polynomial is evaluated
+///
+/// Can only be obtained from [`PartyIndex::share_index`], or checked via [`ShareIndex::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShareIndex(NonZeroU32);
+
+impl PartyIndex {
+    /// Returns index of the share held by the party
+    pub fn share_index(self) -> ShareIndex {
+        ShareIndex(NonZeroU32::MIN.saturating_add(self.0.into()))
+    }
+}
+
+impl ShareIndex {
+    /// Constructs share index
+    ///
+    /// Returns `None` if `x` is zero or doesn't correspond to any [`PartyIndex`]
+    pub fn new(x: u32) -> Option<Self> {
+        let i = u16::try_from(x.checked_sub(1)?).ok()?;
+        Some(PartyIndex(i).share_index())
+    }
+
+    /// Returns index of the party holding the share
+    pub fn party_index(self) -> PartyIndex {
+        // Share index is constructed from `u16` incremented by one
+        PartyIndex(u16::try_from(self.0.get() - 1).unwrap_or(u16::MAX))
+    }
+
+    /// Returns share index as a scalar
+    pub fn to_scalar<E: Curve>(self) -> NonZero<Scalar<E>> {
+        #[allow(clippy::expect_used)]
+        NonZero::from_scalar(Scalar::from(self.0.get()))
+            .expect("share index is less than 2^17 and curve order")
+    }
+
+    /// Returns share index as an integer
+    pub fn get(self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl From<u16> for PartyIndex {
+    fn from(i: u16) -> Self {
+        Self(i)
+    }
+}
+
+impl From<PartyIndex> for u16 {
+    fn from(i: PartyIndex) -> Self {
+        i.0
+    }
+}
+
+impl From<PartyIndex> for usize {
+    fn from(i: PartyIndex) -> Self {
+        i.0.into()
+    }
+}
+
+impl TryFrom<usize> for PartyIndex {
+    type Error = core::num::TryFromIntError;
+    fn try_from(i: usize) -> Result<Self, Self::Error> {
+        u16::try_from(i).map(Self)
+    }
+}
+
+impl From<PartyIndex> for ShareIndex {
+    fn from(i: PartyIndex) -> Self {
+        i.share_index()
+    }
+}
+
+impl fmt::Display for PartyIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for ShareIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use generic_ec::{curves::Secp256k1, Scalar};
+
+    use super::{PartyIndex, ShareIndex};
+
+    #[test]
+    fn conversions() {
+        for i in [0, 1, 1000, u16::MAX] {
+            let party = PartyIndex(i);
+            let share = party.share_index();
+            assert_eq!(share.get(), u32::from(i) + 1);
+            assert_eq!(share.party_index(), party);
+            assert_eq!(ShareIndex::new(share.get()), Some(share));
+            assert_eq!(
+                share.to_scalar::<Secp256k1>(),
+                Scalar::from(u32::from(i) + 1)
+            );
+        }
+        assert_eq!(ShareIndex::new(0), None);
+        assert_eq!(ShareIndex::new(u32::from(u16::MAX) + 2), None);
+        assert!(PartyIndex::try_from(usize::from(u16::MAX) + 1).is_err());
+    }
+}
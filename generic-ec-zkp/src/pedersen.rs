@@ -0,0 +1,171 @@
+//! Pedersen commitments
+//!
+//! Pedersen commitment to value $v$ with blinding $r$ is $C = v \cdot G + r \cdot H$ where $G, H$ are
+//! generators with unknown discrete log relative to each other. Commitment is perfectly hiding and
+//! computationally binding.
+//!
+//! [`VectorParams`] generalize it to vectors: $C = \langle \mathbf{a}, \mathbf{G} \rangle + \langle \mathbf{b}, \mathbf{H} \rangle$.
+//!
+//! Generators are derived via [hash to curve](generic_ec::hash_to_curve), so nobody knows their discrete
+//! logs. Hence, parameters are only available for curves that implement hash to curve primitive.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::pedersen;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-pedersen-v0.1.0");
+//! let params = pedersen::Params::<Secp256k1>::derive(TAG)?;
+//!
+//! let value = Scalar::from(42);
+//! let blinding = Scalar::random(&mut rng);
+//! let commitment = params.commit(&value, &blinding);
+//! # Ok::<(), generic_ec::errors::HashError>(())
+//! ```
+
+use generic_ec::{
+    errors::HashError, hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Pedersen commitment parameters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Params<E: Curve> {
+    /// Generator $G$ which value is multiplied at
+    pub g: Point<E>,
+    /// Generator $H$ which blinding is multiplied at
+    pub h: Point<E>,
+}
+
+impl<E: Curve> Params<E> {
+    /// Derives parameters
+    ///
+    /// $G$ is a curve generator, $H$ is derived from the `tag` via hash to curve.
+    pub fn derive(tag: Tag) -> Result<Self, HashError>
+    where
+        Point<E>: FromHash,
+    {
+        Ok(Self {
+            g: Point::generator().to_point(),
+            h: Point::hash(tag, b"h")?,
+        })
+    }
+
+    /// Commits to the `value` using `blinding`
+    ///
+    /// Computed in constant time, so `value` and `blinding` may be secret.
+    pub fn commit(&self, value: &Scalar<E>, blinding: &Scalar<E>) -> Point<E> {
+        // Multiscalar multiplication is not constant time, so it can't be used with secrets
+        self.g * value + self.h * blinding
+    }
+}
+
+/// Vector Pedersen commitment parameters
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct VectorParams<E: Curve> {
+    /// Generators $\mathbf{G}$
+    pub g: alloc::vec::Vec<Point<E>>,
+    /// Generators $\mathbf{H}$
+    pub h: alloc::vec::Vec<Point<E>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Curve> VectorParams<E> {
+    /// Derives parameters for vectors of length `n`
+    ///
+    /// All generators are derived from the `tag` via hash to curve. Parameters derived for length
+    /// `n` are a prefix of parameters derived for any length greater than `n`.
+    pub fn derive(tag: Tag, n: usize) -> Result<Self, HashError>
+    where
+        Point<E>: FromHash,
+    {
+        let derive = |prefix: &[u8]| {
+            (0..n as u64)
+                .map(|i| Point::hash_concat(tag, &[prefix, &i.to_be_bytes()]))
+                .collect::<Result<_, _>>()
+        };
+        Ok(Self {
+            g: derive(b"g")?,
+            h: derive(b"h")?,
+        })
+    }
+
+    /// Length of vectors that can be committed using these parameters
+    pub fn len(&self) -> usize {
+        self.g.len()
+    }
+
+    /// Indicates whether parameters are empty
+    pub fn is_empty(&self) -> bool {
+        self.g.is_empty()
+    }
+
+    /// Computes $\langle \mathbf{a}, \mathbf{G} \rangle + \langle \mathbf{b}, \mathbf{H} \rangle$
+    ///
+    /// Computed in constant time, so `a` and `b` may be secret. Returns `None` if `a` or `b` are
+    /// longer than parameters
+    pub fn commit(&self, a: &[Scalar<E>], b: &[Scalar<E>]) -> Option<Point<E>> {
+        if a.len() > self.g.len() || b.len() > self.h.len() {
+            return None;
+        }
+        Some(
+            a.iter()
+                .zip(&self.g)
+                .chain(b.iter().zip(&self.h))
+                .map(|(s, p)| p * s)
+                .sum(),
+        )
+    }
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-pedersen-test");
+
+    #[test]
+    fn commitment_is_homomorphic<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = super::Params::<E>::derive(TAG).unwrap();
+        assert_ne!(params.g, params.h);
+
+        let [v1, r1, v2, r2] = [(); 4].map(|_| Scalar::random(&mut rng));
+        assert_eq!(
+            params.commit(&v1, &r1) + params.commit(&v2, &r2),
+            params.commit(&(v1 + v2), &(r1 + r2))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vector_params_are_prefix_stable<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let short = super::VectorParams::<E>::derive(TAG, 4).unwrap();
+        let long = super::VectorParams::<E>::derive(TAG, 8).unwrap();
+        assert_eq!(short.g[..], long.g[..4]);
+        assert_eq!(short.h[..], long.h[..4]);
+        assert!(short.commit(&[Scalar::one(); 5], &[]).is_none());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
@@ -0,0 +1,180 @@
+//! Pedersen hash
+//!
+//! Pedersen hash maps byte string to a curve point as $H(m) = \sum_k (m_k + 1) \cdot G_k$ where
+//! $m_k$ is $k$-th $4$-bit window of the message, and $G_k$ are fixed generators with unknown discrete
+//! logs relative to each other. Finding a collision implies finding a non-trivial discrete log
+//! relation between generators, so the hash is collision resistant as long as discrete log is hard.
+//!
+//! Unlike byte-oriented hash functions, Pedersen hash is algebraic, which makes it cheap to
+//! evaluate inside arithmetic circuits over the same curve. Note that the hash is collision
+//! resistant, but it's not a random oracle: it's not hiding and has a linear structure.
+//!
+//! Every generator comes with a lookup table of its $16$ multiples, so hashing a message takes
+//! only two point additions per byte, with no scalar multiplications.
+//!
+//! Generators are derived via [hash to curve](generic_ec::hash_to_curve), hence the hash is only
+//! available for curves that implement hash to curve primitive.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::pedersen_hash::PedersenHash;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-pedersen-hash-v0.1.0");
+//! // Hash that can process messages up to 64 bytes long
+//! let hash = PedersenHash::<Secp256k1>::derive(TAG, 64)?;
+//!
+//! let digest = hash.hash(b"hello")?;
+//! assert_ne!(digest, hash.hash(b"hello!")?);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use alloc::vec::Vec;
+use core::iter;
+
+use generic_ec::{errors::HashError, hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point};
+
+/// Size of the window in bits
+const WINDOW_BITS: usize = 4;
+/// Amount of points in every lookup table
+const TABLE_SIZE: usize = 1 << WINDOW_BITS;
+
+/// Pedersen hash parameters
+///
+/// Holds lookup tables for all generators, see [module-level docs](self)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PedersenHash<E: Curve> {
+    /// `tables[k][v]` is $(v + 1) \cdot G_k$
+    tables: Vec<[Point<E>; TABLE_SIZE]>,
+}
+
+impl<E: Curve> PedersenHash<E> {
+    /// Derives parameters that can hash messages up to `max_len` bytes long
+    ///
+    /// All generators are derived from the `tag` via hash to curve. Generators derived for
+    /// `max_len` are a prefix of generators derived for any greater `max_len`, so the hash of
+    /// a message doesn't depend on `max_len`.
+    pub fn derive(tag: Tag, max_len: usize) -> Result<Self, HashError>
+    where
+        Point<E>: FromHash,
+    {
+        let windows = max_len * 8 / WINDOW_BITS;
+        let tables = (0..windows as u64)
+            .map(|k| {
+                let generator = Point::<E>::hash_concat(tag, &[b"g", &k.to_be_bytes()])?;
+                let mut table = [generator; TABLE_SIZE];
+                for v in 1..TABLE_SIZE {
+                    table[v] = table[v - 1] + generator;
+                }
+                Ok(table)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { tables })
+    }
+
+    /// Maximum length of the message in bytes that can be hashed using these parameters
+    pub fn max_len(&self) -> usize {
+        self.tables.len() * WINDOW_BITS / 8
+    }
+
+    /// Returns generators $G_k$
+    pub fn generators(&self) -> impl Iterator<Item = Point<E>> + '_ {
+        self.tables.iter().map(|table| table[0])
+    }
+
+    /// Hashes the message
+    ///
+    /// Returns error if message is longer than [`max_len`](Self::max_len)
+    pub fn hash(&self, message: &[u8]) -> Result<Point<E>, MessageTooLong> {
+        if message.len() > self.max_len() {
+            return Err(MessageTooLong);
+        }
+        Ok(windows(message)
+            .zip(&self.tables)
+            .fold(Point::zero(), |acc, (m_k, table)| {
+                acc + table[usize::from(m_k)]
+            }))
+    }
+}
+
+/// Splits the message into 4-bit windows, least significant half of every byte goes first
+fn windows(message: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    message
+        .iter()
+        .flat_map(|byte| iter::once(byte & 0x0f).chain(iter::once(byte >> 4)))
+}
+
+/// Message is longer than parameters support
+#[derive(Debug, Clone, Copy)]
+pub struct MessageTooLong;
+
+impl core::fmt::Display for MessageTooLong {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("message is too long")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MessageTooLong {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar};
+
+    use super::PedersenHash;
+
+    const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-pedersen-hash-test");
+
+    #[test]
+    fn matches_definition<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let hash = PedersenHash::<E>::derive(TAG, 8).unwrap();
+        assert_eq!(hash.max_len(), 8);
+        let generators: Vec<_> = hash.generators().collect();
+
+        let message = b"\x00\x1f\xa5\xff";
+        let expected = Scalar::multiscalar_mul(
+            [0u8, 0, 15, 1, 5, 10, 15, 15]
+                .iter()
+                .map(|m_k| Scalar::from(m_k + 1))
+                .zip(generators),
+        );
+        assert_eq!(hash.hash(message).unwrap(), expected);
+    }
+
+    #[test]
+    fn distinct_messages_have_distinct_hashes<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let hash = PedersenHash::<E>::derive(TAG, 4).unwrap();
+        let messages: [&[u8]; 6] = [b"", b"\x00", b"\x00\x00", b"\x01", b"\x10", b"\x01\x00"];
+        for (i, m1) in messages.iter().enumerate() {
+            assert_eq!(hash.hash(m1).unwrap(), hash.hash(m1).unwrap());
+            for m2 in &messages[i + 1..] {
+                assert_ne!(hash.hash(m1).unwrap(), hash.hash(m2).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn hash_doesnt_depend_on_max_len<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let short = PedersenHash::<E>::derive(TAG, 4).unwrap();
+        let long = PedersenHash::<E>::derive(TAG, 16).unwrap();
+        assert_eq!(short.hash(b"abcd").unwrap(), long.hash(b"abcd").unwrap());
+        assert!(short.hash(b"abcde").is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
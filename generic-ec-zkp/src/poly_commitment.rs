@@ -0,0 +1,397 @@
+//! Polynomial commitment schemes
+//!
+//! [`PolynomialCommitmentScheme`] commits to a polynomial with a short commitment, and later
+//! proves its evaluations at arbitrary points. Protocols written against the trait (e.g. DKG with
+//! polynomial commitments) don't depend on a particular scheme.
+//!
+//! The crate provides [`Ipa`] scheme based on [inner-product argument](crate::ipa): commitment
+//! is a single point, opening proof consists of $2 \log_2 n$ points and $2$ scalars, verification
+//! takes $O(n)$ scalar multiplications. It doesn't need trusted setup or pairings. KZG scheme
+//! requires pairing-friendly curves, which are not available in the crate.
+//!
+//! Opening proofs are **not** zero-knowledge, and commitments are not hiding: they reveal
+//! information about the polynomial beyond its evaluations.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{
+//!     poly_commitment::{Ipa, PolynomialCommitmentScheme},
+//!     polynomial::Polynomial,
+//!     transcript::Transcript,
+//! };
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-pcs-v0.1.0");
+//! let scheme = Ipa::<Secp256k1>::derive(TAG, 8)?;
+//!
+//! let f = Polynomial::<Scalar<Secp256k1>>::sample(&mut rng, 5);
+//! let commitment = scheme.commit(&f)?;
+//!
+//! let x = Scalar::from(42);
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! transcript.append_point(b"commitment", &commitment);
+//! let (y, proof) = scheme.open(&mut transcript, &f, &x)?;
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! transcript.append_point(b"commitment", &commitment);
+//! scheme.verify(&mut transcript, &commitment, &x, &y, &proof)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Batch opening of polynomials $f_1, \dots, f_m$ at the same point $x$ derives challenge $\rho$
+//! from the transcript, and opens $\sum_k \rho^{k-1} f_k$ at $x$. By linearity, it's committed by
+//! $\sum_k \rho^{k-1} C_k$ and evaluates to $\sum_k \rho^{k-1} y_k$.
+//!
+//! [`Ipa`] commits to polynomial $f$ of degree less than $n$ as $C = \langle \mathbf{f}, \mathbf{G} \rangle$,
+//! where $\mathbf{f}$ are coefficients of $f$. To open it at $x$, prover and verifier set
+//! $\mathbf{b} = (1, x, \dots, x^{n-1})$ and derive challenge $\xi$, then prover proves via IPA
+//! that $C + \langle \mathbf{b}, \mathbf{H} \rangle + y \xi \cdot U$ commits to $\mathbf{f}$ and
+//! $\mathbf{b}$ with inner product $y = f(x)$.
+
+use alloc::vec::Vec;
+use core::{iter, ops};
+
+use digest::Digest;
+use generic_ec::{
+    errors::HashError,
+    hash_to_curve::{FromHash, Tag},
+    Curve, Point, Scalar,
+};
+
+use crate::{ipa, pedersen, polynomial::Polynomial, transcript::Transcript};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Polynomial commitment scheme
+///
+/// Statement (commitments) must be appended to the transcript by the caller before opening and
+/// verification, evaluation points and values are appended by the scheme.
+pub trait PolynomialCommitmentScheme<E: Curve> {
+    /// Commitment to a polynomial
+    ///
+    /// Must be additively homomorphic, which is used for batching.
+    type Commitment: Clone + PartialEq + iter::Sum + ops::Mul<Scalar<E>, Output = Self::Commitment>;
+    /// Proof of evaluation
+    type Proof;
+
+    /// Maximal degree of polynomials that can be committed
+    fn max_degree(&self) -> usize;
+
+    /// Commits to the polynomial
+    ///
+    /// Returns error if degree of the polynomial exceeds [`max_degree`](Self::max_degree).
+    fn commit(&self, f: &Polynomial<Scalar<E>>) -> Result<Self::Commitment, InvalidInput>;
+
+    /// Opens the polynomial at point `x`
+    ///
+    /// Returns the value $y = f(x)$ and the proof.
+    fn open<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        f: &Polynomial<Scalar<E>>,
+        x: &Scalar<E>,
+    ) -> Result<(Scalar<E>, Self::Proof), InvalidInput>;
+
+    /// Verifies that committed polynomial evaluates to `y` at `x`
+    fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        commitment: &Self::Commitment,
+        x: &Scalar<E>,
+        y: &Scalar<E>,
+        proof: &Self::Proof,
+    ) -> Result<(), InvalidProof>;
+
+    /// Opens several polynomials at the same point `x` with a single proof
+    ///
+    /// Returns values $y_k = f_k(x)$ and the proof.
+    fn batch_open<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        fs: &[Polynomial<Scalar<E>>],
+        x: &Scalar<E>,
+    ) -> Result<(Vec<Scalar<E>>, Self::Proof), InvalidInput> {
+        let ys: Vec<Scalar<E>> = fs.iter().map(|f| f.value(x)).collect();
+        let powers = batch_challenge(transcript, x, &ys);
+
+        let mut coefs = Vec::new();
+        for (f, rho) in fs.iter().zip(powers) {
+            if coefs.len() < f.coefs().len() {
+                coefs.resize(f.coefs().len(), Scalar::zero());
+            }
+            for (acc, coef) in coefs.iter_mut().zip(f.coefs()) {
+                *acc += rho * coef;
+            }
+        }
+        let (_, proof) = self.open(transcript, &Polynomial::from_coefs(coefs), x)?;
+        Ok((ys, proof))
+    }
+
+    /// Verifies that committed polynomials evaluate to `ys` at `x`
+    fn batch_verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        commitments: &[Self::Commitment],
+        x: &Scalar<E>,
+        ys: &[Scalar<E>],
+        proof: &Self::Proof,
+    ) -> Result<(), InvalidProof> {
+        if commitments.len() != ys.len() {
+            return Err(InvalidProof);
+        }
+        let powers = batch_challenge(transcript, x, ys);
+        let commitment = commitments
+            .iter()
+            .zip(&powers)
+            .map(|(c, rho)| c.clone() * *rho)
+            .sum();
+        let y = ys.iter().zip(&powers).map(|(y, rho)| y * rho).sum();
+        self.verify(transcript, &commitment, x, &y, proof)
+    }
+}
+
+/// Derives powers of batching challenge $\rho^0, \rho^1, \dots$
+fn batch_challenge<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    x: &Scalar<E>,
+    ys: &[Scalar<E>],
+) -> Vec<Scalar<E>> {
+    transcript.append_scalar(b"pcs/batch/x", x);
+    transcript.append_u64(b"pcs/batch/m", ys.len() as u64);
+    for y in ys {
+        transcript.append_scalar(b"pcs/batch/y", y);
+    }
+    let rho: Scalar<E> = transcript.challenge_scalar(b"pcs/batch/rho");
+    iter::successors(Some(Scalar::one()), |acc| Some(acc * rho))
+        .take(ys.len())
+        .collect()
+}
+
+/// Polynomial commitment scheme based on inner-product argument
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Ipa<E: Curve> {
+    /// Generators $\mathbf{G}$ and $\mathbf{H}$, their length $n$ must be a power of two
+    pub vector: pedersen::VectorParams<E>,
+    /// Generator $U$
+    pub u: Point<E>,
+}
+
+impl<E: Curve> Ipa<E> {
+    /// Derives parameters supporting polynomials of degree less than `n`
+    ///
+    /// `n` is rounded up to the next power of two. All generators are derived from the `tag` via
+    /// hash to curve.
+    pub fn derive(tag: Tag, n: usize) -> Result<Self, HashError>
+    where
+        Point<E>: FromHash,
+    {
+        Ok(Self {
+            vector: pedersen::VectorParams::derive(tag, n.next_power_of_two())?,
+            u: Point::hash_concat(tag, &[b"u"])?,
+        })
+    }
+
+    /// Returns $\mathbf{b} = (1, x, \dots, x^{n-1})$ and $\xi \cdot U$
+    fn evaluation_bases<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        x: &Scalar<E>,
+        y: &Scalar<E>,
+    ) -> (Vec<Scalar<E>>, Point<E>) {
+        transcript.append_scalar(b"pcs/ipa/x", x);
+        transcript.append_scalar(b"pcs/ipa/y", y);
+        let xi: Scalar<E> = transcript.challenge_scalar(b"pcs/ipa/xi");
+        let b = iter::successors(Some(Scalar::one()), |acc| Some(acc * x))
+            .take(self.vector.len())
+            .collect();
+        (b, self.u * xi)
+    }
+}
+
+impl<E: Curve> PolynomialCommitmentScheme<E> for Ipa<E> {
+    type Commitment = Point<E>;
+    type Proof = ipa::InnerProductProof<E>;
+
+    fn max_degree(&self) -> usize {
+        self.vector.len().saturating_sub(1)
+    }
+
+    fn commit(&self, f: &Polynomial<Scalar<E>>) -> Result<Point<E>, InvalidInput> {
+        if f.coefs().len() > self.vector.len() {
+            return Err(InvalidInput);
+        }
+        Ok(Scalar::multiscalar_mul(
+            f.coefs().iter().zip(&self.vector.g),
+        ))
+    }
+
+    fn open<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        f: &Polynomial<Scalar<E>>,
+        x: &Scalar<E>,
+    ) -> Result<(Scalar<E>, Self::Proof), InvalidInput> {
+        let n = self.vector.len();
+        if f.coefs().len() > n || !n.is_power_of_two() {
+            return Err(InvalidInput);
+        }
+        let y: Scalar<E> = f.value(x);
+        let (b, u) = self.evaluation_bases(transcript, x, &y);
+        let mut a = f.coefs().to_vec();
+        a.resize(n, Scalar::zero());
+        let proof = ipa::prove(transcript, &self.vector.g, &self.vector.h, &u, a, b)
+            .map_err(|_| InvalidInput)?;
+        Ok((y, proof))
+    }
+
+    fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        commitment: &Point<E>,
+        x: &Scalar<E>,
+        y: &Scalar<E>,
+        proof: &Self::Proof,
+    ) -> Result<(), InvalidProof> {
+        let (b, u) = self.evaluation_bases(transcript, x, y);
+        let p = commitment + Scalar::multiscalar_mul(b.iter().zip(&self.vector.h)) + u * y;
+        proof
+            .verify(transcript, &self.vector.g, &self.vector.h, &u, &p)
+            .map_err(|_| InvalidProof)
+    }
+}
+
+/// Polynomial degree exceeds maximal supported degree, or parameters are malformed
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("polynomial degree is not supported by the parameters")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Evaluation proof is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid evaluation proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    use super::{Ipa, PolynomialCommitmentScheme};
+    use crate::{polynomial::Polynomial, transcript::Transcript};
+
+    const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-pcs-test");
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    /// Tests written against the trait apply to any scheme
+    fn open_and_verify<E: Curve, S: PolynomialCommitmentScheme<E>>(scheme: &S, rng: &mut DevRng) {
+        let max_degree = scheme.max_degree();
+        for degree in [0, 1, max_degree / 2, max_degree].map(|d| d.min(max_degree)) {
+            let f = Polynomial::<Scalar<E>>::sample(rng, degree);
+            let commitment = scheme.commit(&f).unwrap();
+            let x = Scalar::random(rng);
+
+            let (y, proof) = scheme.open(&mut transcript(), &f, &x).unwrap();
+            assert_eq!(y, f.value::<_, Scalar<E>>(&x));
+            scheme
+                .verify(&mut transcript(), &commitment, &x, &y, &proof)
+                .unwrap();
+
+            let wrong_y = y + Scalar::one();
+            assert!(scheme
+                .verify(&mut transcript(), &commitment, &x, &wrong_y, &proof)
+                .is_err());
+            // Constant polynomial has the same value at any point
+            if degree > 0 {
+                let wrong_x = x + Scalar::one();
+                assert!(scheme
+                    .verify(&mut transcript(), &commitment, &wrong_x, &y, &proof)
+                    .is_err());
+            }
+        }
+
+        let too_big = Polynomial::<Scalar<E>>::sample(rng, max_degree + 1);
+        assert!(scheme.commit(&too_big).is_err());
+        assert!(scheme
+            .open(&mut transcript(), &too_big, &Scalar::one())
+            .is_err());
+    }
+
+    fn batch_open_and_verify<E: Curve, S: PolynomialCommitmentScheme<E>>(
+        scheme: &S,
+        rng: &mut DevRng,
+    ) {
+        let fs: Vec<_> = [1, scheme.max_degree(), 0, 3]
+            .map(|degree| Polynomial::<Scalar<E>>::sample(rng, degree.min(scheme.max_degree())))
+            .into_iter()
+            .collect();
+        let commitments: Vec<_> = fs.iter().map(|f| scheme.commit(f).unwrap()).collect();
+        let x = Scalar::random(rng);
+
+        let (ys, proof) = scheme.batch_open(&mut transcript(), &fs, &x).unwrap();
+        for (f, y) in fs.iter().zip(&ys) {
+            assert_eq!(*y, f.value::<_, Scalar<E>>(&x));
+        }
+        scheme
+            .batch_verify(&mut transcript(), &commitments, &x, &ys, &proof)
+            .unwrap();
+
+        let mut wrong_ys = ys.clone();
+        wrong_ys[2] += Scalar::one();
+        assert!(scheme
+            .batch_verify(&mut transcript(), &commitments, &x, &wrong_ys, &proof)
+            .is_err());
+        let mut swapped = commitments.clone();
+        swapped.swap(0, 1);
+        assert!(scheme
+            .batch_verify(&mut transcript(), &swapped, &x, &ys, &proof)
+            .is_err());
+        assert!(scheme
+            .batch_verify(&mut transcript(), &commitments[1..], &x, &ys[1..], &proof)
+            .is_err());
+    }
+
+    #[test]
+    fn ipa<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        for n in [1, 2, 5, 16] {
+            let scheme = Ipa::<E>::derive(TAG, n).unwrap();
+            assert_eq!(scheme.max_degree(), n.next_power_of_two() - 1);
+            open_and_verify(&scheme, &mut rng);
+            batch_open_and_verify(&scheme, &mut rng);
+        }
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
@@ -1,4 +1,6 @@
 //! Provides [polynomial](Polynomial) primitive, typically used in secret sharing and threshold DKG
+//!
+//! [`FixedPolynomial`] and [`interpolate`] don't require `alloc` feature.
 
 #[cfg(feature = "alloc")]
 #[doc(inline)]
@@ -10,9 +12,12 @@ mod requires_alloc {
     use alloc::{vec, vec::Vec};
     use core::{iter, ops};
 
-    use generic_ec::traits::{IsZero, Samplable, Zero};
+    use generic_ec::traits::{ExposeSecret, IsZero, Samplable, Zero};
+    use generic_ec::{Curve, NonZero, Point, Scalar, SecretScalar};
     use rand_core::RngCore;
 
+    use super::{FixedPolynomial, HomomorphicCommitment};
+
     /// Polynomial $f(x) = \sum_i a_i x^i$ defined as a list of coefficients $[a_0, \dots, a_{\text{degree}}]$
     ///
     /// Polynomial is generic over type of coefficients `C`, it can be `Scalar<E>`, `NonZero<Scalar<E>>`, `SecretScalar<E>`, `Point<E>`,
@@ -65,6 +70,12 @@ mod requires_alloc {
         }
     }
 
+    impl<C: IsZero, const N: usize> From<FixedPolynomial<C, N>> for Polynomial<C> {
+        fn from(polynomial: FixedPolynomial<C, N>) -> Self {
+            Self::from_coefs(polynomial.into_coefs().into())
+        }
+    }
+
     impl<C> Polynomial<C> {
         /// Returns polynomial degree
         ///
@@ -162,6 +173,11 @@ mod requires_alloc {
         ///     F.value::<_, Point<_>>(&x)
         /// );
         /// ```
+        ///
+        /// ## Timing
+        /// This method is not guaranteed to run in constant time: its timing depends on
+        /// implementation of arithmetic operations of types `C`, `P`, and `O`. When coefficients
+        /// are secret (e.g. dealer evaluates shares of the secret), use [`eval_ct`](Self::eval_ct).
         pub fn value<P, O>(&self, point: &P) -> O
         where
             O: Zero,
@@ -172,6 +188,172 @@ mod requires_alloc {
                 .rev()
                 .fold(O::zero(), |acc, coef_i| acc * point + coef_i)
         }
+
+        /// Evaluates polynomial with secret scalar coefficients at given point in constant time
+        ///
+        /// Evaluation is branch-free, and timing doesn't depend on values of the coefficients
+        /// or the point: only on the number of coefficients, which is public. Accumulator and
+        /// products computed at every step are erased, and the output is returned as
+        /// [`SecretScalar`] as it's typically a secret share.
+        ///
+        /// Note that [`from_coefs`](Polynomial::from_coefs) truncates trailing zero coefficients
+        /// in variable time, so the number of coefficients reveals whether the leading
+        /// coefficients are zero. Polynomials [sampled](Polynomial::sample) at random don't have
+        /// zero coefficients with overwhelming probability.
+        ///
+        /// ```rust
+        /// use generic_ec::{Scalar, SecretScalar, NonZero, curves::Secp256k1};
+        /// use generic_ec_zkp::polynomial::Polynomial;
+        /// # use rand_core::OsRng;
+        ///
+        /// let secret = SecretScalar::<Secp256k1>::random(&mut OsRng);
+        /// let f = Polynomial::sample_with_const_term(&mut OsRng, 2, secret);
+        ///
+        /// let i = NonZero::from_scalar(Scalar::from(1)).unwrap();
+        /// let share = f.eval_ct(&i);
+        /// assert_eq!(share.into_inner_unprotected(), f.value::<_, Scalar<_>>(&i));
+        /// ```
+        pub fn eval_ct<E: Curve>(&self, point: &impl AsRef<Scalar<E>>) -> SecretScalar<E>
+        where
+            C: ExposeSecret<E>,
+        {
+            super::horner_ct(self.coefs.iter().rev(), point.as_ref())
+        }
+    }
+
+    impl<C> Polynomial<C> {
+        /// Evaluates polynomial with point coefficients at many points: $F(x_1), \dots, F(x_n)$
+        ///
+        /// Typically used to verify Feldman commitments to the shares of many parties. Evaluating
+        /// $F(x)$ via [`value`](Self::value) takes $t$ scalar multiplications, where $t$ is
+        /// degree of $F$. This method computes powers $x_i^k$ (which is cheap) and evaluates each
+        /// $F(x_i) = \sum_k x_i^k A_k$ as [multiscalar multiplication](Scalar::multiscalar_mul),
+        /// which is several times faster.
+        ///
+        /// If polynomial needs to be evaluated at points $1, \dots, n$, use faster
+        /// [`values_at_indexes`](Self::values_at_indexes).
+        pub fn values<E: Curve>(&self, xs: &[Scalar<E>]) -> Vec<Point<E>>
+        where
+            C: AsRef<Point<E>>,
+        {
+            let mut powers = vec![Scalar::<E>::one(); self.coefs.len()];
+            xs.iter()
+                .map(|x| {
+                    for k in 1..powers.len() {
+                        powers[k] = powers[k - 1] * x;
+                    }
+                    Scalar::multiscalar_mul(powers.iter().zip(&self.coefs))
+                })
+                .collect()
+        }
+
+        /// Evaluates polynomial with point coefficients at points $1, \dots, n$: $F(1), \dots, F(n)$
+        ///
+        /// Uses finite differences method: $t + 1$ values are evaluated via
+        /// [`values`](Self::values), and each next value is obtained from previous ones
+        /// using $t$ point additions, with no scalar multiplications. It's much faster than
+        /// evaluating the polynomial at each point separately when $n \gg t$, which is the case
+        /// when verifying Feldman commitments to the shares of many parties.
+        pub fn values_at_indexes<E: Curve>(&self, n: usize) -> Vec<Point<E>>
+        where
+            C: AsRef<Point<E>>,
+        {
+            if self.coefs.is_empty() {
+                return vec![Point::zero(); n];
+            }
+            let xs = (1u64..)
+                .map(Scalar::<E>::from)
+                .take(n.min(self.coefs.len()))
+                .collect::<Vec<_>>();
+            let mut values = self.values(&xs);
+            if n <= values.len() {
+                return values;
+            }
+
+            // Compute backward differences at the last evaluated point `m = t + 1`:
+            // diffs[k] = \nabla^k F(m). Table is differenced in place: after k-th pass,
+            // `diffs[m - 1 - k]` holds \nabla^k F(m) and is never touched again.
+            let mut diffs = values.clone();
+            let m = diffs.len();
+            for k in 1..m {
+                for i in 0..m - k {
+                    diffs[i] = diffs[i + 1] - diffs[i];
+                }
+            }
+            diffs.reverse();
+
+            // Difference of order `t` is constant, differences of lower orders are updated
+            // as \nabla^k F(i + 1) = \nabla^k F(i) + \nabla^{k+1} F(i + 1)
+            values.reserve(n - values.len());
+            while values.len() < n {
+                for k in (0..m - 1).rev() {
+                    let next_order_diff = diffs[k + 1];
+                    diffs[k] += next_order_diff;
+                }
+                values.push(diffs[0]);
+            }
+            values
+        }
+    }
+
+    impl<C> Polynomial<C> {
+        /// Commits to every coefficient of the polynomial
+        ///
+        /// ## Example
+        /// ```rust
+        /// use generic_ec::{Point, Scalar, SecretScalar, NonZero, curves::Secp256k1};
+        /// use generic_ec_zkp::polynomial::Polynomial;
+        /// # use rand_core::OsRng;
+        ///
+        /// let secret = SecretScalar::<Secp256k1>::random(&mut OsRng);
+        /// let f = Polynomial::sample_with_const_term(&mut OsRng, 2, secret);
+        /// let commitment = f.commit(&Point::generator().to_point());
+        ///
+        /// let i = NonZero::from_scalar(Scalar::from(1)).unwrap();
+        /// let share = f.eval_ct(&i);
+        /// assert!(commitment.verify_share(&Point::generator().to_point(), &i, &share.into_inner_unprotected()));
+        /// ```
+        pub fn commit<E: Curve, H: HomomorphicCommitment<E>>(
+            &self,
+            scheme: &H,
+        ) -> Polynomial<H::Commitment>
+        where
+            C: ExposeSecret<E>,
+        {
+            Polynomial {
+                coefs: self
+                    .coefs
+                    .iter()
+                    .map(|coef_i| coef_i.expose_secret(|coef_i| scheme.commit(coef_i)))
+                    .collect(),
+            }
+        }
+
+        /// Evaluates polynomial with commitment coefficients at given point using `scheme`
+        /// group operations
+        pub fn eval_commitment<E: Curve, H: HomomorphicCommitment<E, Commitment = C>>(
+            &self,
+            scheme: &H,
+            point: &impl AsRef<Scalar<E>>,
+        ) -> C {
+            let point = point.as_ref();
+            self.coefs.iter().rev().fold(scheme.zero(), |acc, coef_i| {
+                scheme.add(&scheme.mul(&acc, point), coef_i)
+            })
+        }
+
+        /// Checks that `share` is the value of committed polynomial at `point`
+        pub fn verify_share<E: Curve, H: HomomorphicCommitment<E, Commitment = C>>(
+            &self,
+            scheme: &H,
+            point: &impl AsRef<Scalar<E>>,
+            share: &Scalar<E>,
+        ) -> bool
+        where
+            C: PartialEq,
+        {
+            scheme.commit(share) == self.eval_commitment(scheme, point)
+        }
     }
 
     /// Multiplies polyinomial $F(x)$ at $k$ returning resulting polyinomial
@@ -294,9 +476,406 @@ mod requires_alloc {
             Ok(Self::from_coefs(coefs))
         }
     }
+
+    /// Precomputed Lagrange coefficients for a fixed set of points
+    ///
+    /// Computing [`lagrange_coefficient`](super::lagrange_coefficient) for every $j$ takes
+    /// $O(n^2)$ multiplications and $n$ inversions, which are recomputed every time even if
+    /// the set of points doesn't change (e.g. threshold signing with a stable quorum). `LagrangeBasis`
+    /// computes all coefficients $\lambda_0, \dots, \lambda_{n-1}$ at once using a single
+    /// inversion, and stores them so they can be reused.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use generic_ec::{Scalar, SecretScalar, NonZero, curves::Secp256k1};
+    /// use generic_ec_zkp::polynomial::{Polynomial, LagrangeBasis};
+    /// # use rand_core::OsRng;
+    ///
+    /// let secret = SecretScalar::<Secp256k1>::random(&mut OsRng);
+    /// let f = Polynomial::sample_with_const_term(&mut OsRng, 2, secret.clone());
+    ///
+    /// // Indexes of signers in the quorum
+    /// let I = [1, 2, 3].map(|i| NonZero::from_scalar(Scalar::from(i)).unwrap());
+    /// // Computed once, reused in every signing session
+    /// let basis = LagrangeBasis::new(Scalar::zero(), &I).unwrap();
+    ///
+    /// let shares: [Scalar<_>; 3] = I.map(|i| f.value(&i));
+    /// let reconstructed_secret = basis.interpolate(&shares).unwrap();
+    /// assert_eq!(secret.expose_secret(|s| *s), reconstructed_secret);
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct LagrangeBasis<E: Curve> {
+        x: Scalar<E>,
+        xs: Vec<NonZero<Scalar<E>>>,
+        coefs: Vec<NonZero<Scalar<E>>>,
+    }
+
+    impl<E: Curve> LagrangeBasis<E> {
+        /// Precomputes Lagrange coefficients to interpolate a polynomial defined at points `xs`
+        /// at point `x`
+        ///
+        /// Returns `None` if elements of `xs` aren't pairwise distinct, or if `x` equals to one
+        /// of them (and `xs` has more than one element), similarly to
+        /// [`lagrange_coefficient`](super::lagrange_coefficient).
+        pub fn new(x: Scalar<E>, xs: &[NonZero<Scalar<E>>]) -> Option<Self> {
+            let n = xs.len();
+
+            // denoms[j] = \prod_{m != j} (x_j - x_m)
+            let mut denoms = xs
+                .iter()
+                .enumerate()
+                .map(|(j, x_j)| {
+                    xs.iter()
+                        .enumerate()
+                        .filter(|(m, _)| *m != j)
+                        .map(|(_, x_m)| x_j - x_m)
+                        .product::<Scalar<E>>()
+                })
+                .collect::<Vec<_>>();
+            Scalar::batch_invert(&mut denoms).ok()?;
+
+            // noms[j] = \prod_{m != j} (x - x_m) computed as product of prefix and suffix
+            let mut suffix_products = vec![Scalar::one(); n + 1];
+            for m in (0..n).rev() {
+                suffix_products[m] = suffix_products[m + 1] * (x - xs[m]);
+            }
+            let mut prefix_product = Scalar::<E>::one();
+            let mut coefs = Vec::with_capacity(n);
+            for j in 0..n {
+                let nom = prefix_product * suffix_products[j + 1];
+                coefs.push(NonZero::from_scalar(nom * denoms[j])?);
+                prefix_product *= x - xs[j];
+            }
+
+            Some(Self {
+                x,
+                xs: xs.to_vec(),
+                coefs,
+            })
+        }
+
+        /// Point at which the polynomial is interpolated
+        pub fn x(&self) -> &Scalar<E> {
+            &self.x
+        }
+
+        /// Points at which the polynomial is defined
+        pub fn xs(&self) -> &[NonZero<Scalar<E>>] {
+            &self.xs
+        }
+
+        /// Lagrange coefficients $\lambda_j$, `coefficients()[j]` corresponds to `xs()[j]`
+        pub fn coefficients(&self) -> &[NonZero<Scalar<E>>] {
+            &self.coefs
+        }
+
+        /// Interpolates value of the polynomial at point `x` given its `values` at points `xs`
+        ///
+        /// Values can be scalars (e.g. secret shares) or points (e.g. public shares). Returns
+        /// `None` if `values.len() != xs().len()`.
+        pub fn interpolate<V>(&self, values: &[V]) -> Option<V>
+        where
+            V: Copy + iter::Sum,
+            Scalar<E>: ops::Mul<V, Output = V>,
+        {
+            if values.len() != self.coefs.len() {
+                return None;
+            }
+            Some(
+                self.coefs
+                    .iter()
+                    .zip(values)
+                    .map(|(lambda_j, v_j)| *lambda_j.as_ref() * *v_j)
+                    .sum(),
+            )
+        }
+    }
 }
 
-use generic_ec::{Curve, NonZero, Scalar};
+use core::ops;
+
+use generic_ec::traits::{ExposeSecret, IsZero, Samplable, Zero};
+use generic_ec::{Curve, NonZero, Point, Scalar, SecretScalar};
+use rand_core::RngCore;
+use zeroize::Zeroize;
+
+/// Evaluates polynomial with secret coefficients via Horner's method in constant time
+///
+/// Takes coefficients starting from the highest degree. Every intermediate value is erased
+/// once it's no longer needed.
+fn horner_ct<'c, E: Curve, C: ExposeSecret<E> + 'c>(
+    coefs_rev: impl Iterator<Item = &'c C>,
+    point: &Scalar<E>,
+) -> SecretScalar<E> {
+    let mut acc = Scalar::<E>::zero();
+    for coef_i in coefs_rev {
+        let mut product = acc * point;
+        // Overwrites previous value of the accumulator
+        acc = coef_i.expose_secret(|coef_i| product + coef_i);
+        product.zeroize();
+    }
+    SecretScalar::new(&mut acc)
+}
+
+/// Additively homomorphic commitment to scalars
+///
+/// Lets commitments to polynomial coefficients live in a group other than `Point<E>` (e.g.
+/// class group), while the polynomial and the shares remain over `Scalar<E>`. Commitment
+/// must satisfy $\text{commit}(a + b) = \text{commit}(a) + \text{commit}(b)$.
+///
+/// Implemented for `Point<E>`, which commits to $v$ as $v \cdot B$ (Feldman commitment
+/// when $B$ is curve generator).
+pub trait HomomorphicCommitment<E: Curve> {
+    /// Commitment, an element of the group where commitments live
+    type Commitment: Clone + PartialEq;
+
+    /// Commits to the scalar
+    fn commit(&self, value: &Scalar<E>) -> Self::Commitment;
+    /// Neutral element of the group, commitment to zero
+    fn zero(&self) -> Self::Commitment;
+    /// Group operation
+    fn add(&self, a: &Self::Commitment, b: &Self::Commitment) -> Self::Commitment;
+    /// Multiplies commitment at scalar
+    fn mul(&self, commitment: &Self::Commitment, k: &Scalar<E>) -> Self::Commitment;
+}
+
+impl<E: Curve> HomomorphicCommitment<E> for Point<E> {
+    type Commitment = Point<E>;
+
+    fn commit(&self, value: &Scalar<E>) -> Point<E> {
+        self * value
+    }
+    fn zero(&self) -> Point<E> {
+        Point::zero()
+    }
+    fn add(&self, a: &Point<E>, b: &Point<E>) -> Point<E> {
+        a + b
+    }
+    fn mul(&self, commitment: &Point<E>, k: &Scalar<E>) -> Point<E> {
+        commitment * k
+    }
+}
+
+/// Polynomial $f(x) = \sum_{i < N} a_i x^i$ with at most $N$ coefficients stored on the stack
+///
+/// Counterpart of [`Polynomial`] that doesn't require `alloc`
+/// feature, so it can be used for secret sharing on devices without heap allocator.
+/// Capacity `N` is the number of coefficients, i.e. it's $t$ for $t$-out-of-$n$ secret sharing.
+/// Polynomial of lower degree has leading coefficients set to zero.
+///
+/// ## Example
+/// ```rust
+/// use generic_ec::{Scalar, SecretScalar, NonZero, curves::Secp256k1};
+/// use generic_ec_zkp::polynomial::{FixedPolynomial, interpolate};
+/// # use rand_core::OsRng;
+///
+/// // 3-out-of-n secret sharing
+/// let secret = SecretScalar::<Secp256k1>::random(&mut OsRng);
+/// let f = FixedPolynomial::<_, 3>::sample_with_const_term(&mut OsRng, secret.clone());
+///
+/// let I = [1, 2, 3].map(|i| NonZero::from_scalar(Scalar::from(i)).unwrap());
+/// let shares = I.map(|i| f.eval_ct(&i).into_inner_unprotected());
+///
+/// let reconstructed_secret = interpolate(Scalar::zero(), &I, &shares).unwrap();
+/// assert_eq!(secret.expose_secret(|s| *s), reconstructed_secret);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FixedPolynomial<C, const N: usize> {
+    /// `coefs[i]` is coefficient of `x^i` term
+    coefs: [C; N],
+}
+
+impl<C, const N: usize> FixedPolynomial<C, N> {
+    /// Constructs a polynomial from its coefficients
+    ///
+    /// `coefs[i]` is coefficient of `x^i` term. Resulting polynomial will be
+    /// $f(x) = \sum_i \\text{coefs}_i \cdot x^i$
+    pub fn from_coefs(coefs: [C; N]) -> Self {
+        Self { coefs }
+    }
+
+    /// Returns polynomial coefficients
+    pub fn coefs(&self) -> &[C; N] {
+        &self.coefs
+    }
+
+    /// Destructs polynomial, returns its coefficients
+    pub fn into_coefs(self) -> [C; N] {
+        self.coefs
+    }
+
+    /// Returns polynomial degree
+    ///
+    /// Polynomial degree is index of most significant non-zero coefficient. Polynomial $f(x) = 0$
+    /// considered to have degree $deg(f) = 0$.
+    pub fn degree(&self) -> usize
+    where
+        C: IsZero,
+    {
+        self.coefs
+            .iter()
+            .rposition(|coef_i| !coef_i.is_zero())
+            .unwrap_or(0)
+    }
+}
+
+impl<C: Samplable, const N: usize> FixedPolynomial<C, N> {
+    /// Samples a random polynomial with `N` coefficients, i.e. of degree $N - 1$
+    pub fn sample(rng: &mut impl RngCore) -> Self {
+        Self {
+            coefs: core::array::from_fn(|_| C::random(rng)),
+        }
+    }
+
+    /// Samples a random polynomial with `N` coefficients and given constant term
+    ///
+    /// Constant term determines value of polynomial at point zero: $f(0) = \\text{const\\_term}$.
+    /// `N` must be non-zero, otherwise `const_term` is dropped and zero polynomial is returned.
+    pub fn sample_with_const_term(rng: &mut impl RngCore, const_term: C) -> Self {
+        let mut const_term = Some(const_term);
+        Self {
+            coefs: core::array::from_fn(|_| match const_term.take() {
+                Some(const_term) => const_term,
+                None => C::random(rng),
+            }),
+        }
+    }
+}
+
+impl<C, const N: usize> FixedPolynomial<C, N> {
+    /// Evaluates polynomial value at given point: $f(\\text{point})$
+    ///
+    /// Same as [`Polynomial::value`](crate::polynomial::Polynomial::value). Not guaranteed
+    /// to run in constant time, use [`eval_ct`](Self::eval_ct) for secret coefficients.
+    pub fn value<P, O>(&self, point: &P) -> O
+    where
+        O: Zero,
+        for<'a> O: ops::Mul<&'a P, Output = O> + ops::Add<&'a C, Output = O>,
+    {
+        self.coefs
+            .iter()
+            .rev()
+            .fold(O::zero(), |acc, coef_i| acc * point + coef_i)
+    }
+
+    /// Evaluates polynomial with secret scalar coefficients at given point in constant time
+    ///
+    /// Timing depends only on `N`. Intermediate values are erased, and the output is returned
+    /// as [`SecretScalar`] as it's typically a secret share.
+    pub fn eval_ct<E: Curve>(&self, point: &impl AsRef<Scalar<E>>) -> SecretScalar<E>
+    where
+        C: ExposeSecret<E>,
+    {
+        horner_ct(self.coefs.iter().rev(), point.as_ref())
+    }
+
+    /// Commits to every coefficient of the polynomial
+    pub fn commit<E: Curve, H: HomomorphicCommitment<E>>(
+        &self,
+        scheme: &H,
+    ) -> FixedPolynomial<H::Commitment, N>
+    where
+        C: ExposeSecret<E>,
+    {
+        FixedPolynomial {
+            coefs: self
+                .coefs
+                .each_ref()
+                .map(|coef_i| coef_i.expose_secret(|coef_i| scheme.commit(coef_i))),
+        }
+    }
+
+    /// Evaluates polynomial with commitment coefficients at given point using `scheme`
+    /// group operations
+    pub fn eval_commitment<E: Curve, H: HomomorphicCommitment<E, Commitment = C>>(
+        &self,
+        scheme: &H,
+        point: &impl AsRef<Scalar<E>>,
+    ) -> C {
+        let point = point.as_ref();
+        self.coefs.iter().rev().fold(scheme.zero(), |acc, coef_i| {
+            scheme.add(&scheme.mul(&acc, point), coef_i)
+        })
+    }
+
+    /// Checks that `share` is the value of committed polynomial at `point`
+    pub fn verify_share<E: Curve, H: HomomorphicCommitment<E, Commitment = C>>(
+        &self,
+        scheme: &H,
+        point: &impl AsRef<Scalar<E>>,
+        share: &Scalar<E>,
+    ) -> bool
+    where
+        C: PartialEq,
+    {
+        scheme.commit(share) == self.eval_commitment(scheme, point)
+    }
+}
+
+/// Multiplies polynomial $F(x)$ at $k$ returning resulting polynomial $F'(x) = k \cdot F(x)$
+///
+/// $k$ can be any type as long as it can be multiplied at `C`
+impl<B, C, O, const N: usize> ops::Mul<&B> for &FixedPolynomial<C, N>
+where
+    for<'a> &'a C: ops::Mul<&'a B, Output = O>,
+{
+    type Output = FixedPolynomial<O, N>;
+
+    fn mul(self, rhs: &B) -> Self::Output {
+        FixedPolynomial {
+            coefs: self.coefs.each_ref().map(|coef_i| coef_i * rhs),
+        }
+    }
+}
+
+impl<C, const N: usize> ops::AddAssign<&FixedPolynomial<C, N>> for FixedPolynomial<C, N>
+where
+    C: for<'a> ops::AddAssign<&'a C>,
+{
+    fn add_assign(&mut self, rhs: &FixedPolynomial<C, N>) {
+        self.coefs
+            .iter_mut()
+            .zip(&rhs.coefs)
+            .for_each(|(f1_coef_i, f2_coef_i)| *f1_coef_i += f2_coef_i);
+    }
+}
+
+impl<C, const N: usize> ops::Add<&FixedPolynomial<C, N>> for FixedPolynomial<C, N>
+where
+    C: for<'a> ops::AddAssign<&'a C>,
+{
+    type Output = FixedPolynomial<C, N>;
+
+    fn add(mut self, rhs: &FixedPolynomial<C, N>) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+/// Interpolates value of the polynomial at point `x` given its `values` at points `xs`
+///
+/// Doesn't allocate, so it's available without `alloc` feature. Computes every
+/// [`lagrange_coefficient`] from scratch, when the set of points is reused consider
+/// [`LagrangeBasis`] instead.
+///
+/// Returns `None` if `values.len() != xs.len()`, or if Lagrange coefficients are not defined
+/// for `xs` and `x`.
+pub fn interpolate<E: Curve, V>(x: Scalar<E>, xs: &[NonZero<Scalar<E>>], values: &[V]) -> Option<V>
+where
+    V: Zero + ops::Add<Output = V>,
+    for<'a> Scalar<E>: ops::Mul<&'a V, Output = V>,
+{
+    if values.len() != xs.len() {
+        return None;
+    }
+    values
+        .iter()
+        .enumerate()
+        .try_fold(V::zero(), |acc, (j, v_j)| {
+            let lambda_j = lagrange_coefficient(x, j, xs)?;
+            Some(acc + lambda_j.into_inner() * v_j)
+        })
+}
 
 /// Calculates lagrange coefficient $\lambda_j$ to interpolate a polynomial at point $x$
 ///
@@ -344,7 +923,7 @@ use generic_ec::{Curve, NonZero, Scalar};
 ///     .zip(shares)
 ///     .map(|(lambda_i, x_i)| lambda_i * x_i)
 ///     .sum::<Scalar<_>>();
-/// assert_eq!(secret.as_ref(), &reconstructed_secret);
+/// assert_eq!(secret.expose_secret(|s| *s), reconstructed_secret);
 /// ```
 pub fn lagrange_coefficient<E: Curve>(
     x: Scalar<E>,
@@ -381,7 +960,7 @@ mod tests {
 
     use crate::polynomial::lagrange_coefficient;
 
-    use super::Polynomial;
+    use super::{interpolate, FixedPolynomial, HomomorphicCommitment, LagrangeBasis, Polynomial};
 
     #[test]
     fn secret_sharing<E: Curve>() {
@@ -394,7 +973,7 @@ mod tests {
         // Chech that `f(0) = secret`
         {
             let f_0: Scalar<_> = f.value(&Scalar::zero());
-            assert_eq!(secret.as_ref(), &f_0);
+            assert_eq!(secret.expose_secret(|s| *s), f_0);
         }
 
         // 2. Commit to the secret
@@ -430,7 +1009,151 @@ mod tests {
             .zip(shares)
             .map(|(lambda_i, x_i)| lambda_i * x_i)
             .sum();
-        assert_eq!(secret.as_ref(), &reconstructed_secret);
+        assert_eq!(secret.expose_secret(|s| *s), reconstructed_secret);
+    }
+
+    #[test]
+    fn lagrange_basis<E: Curve>() {
+        let mut rng = DevRng::new();
+
+        let secret = SecretScalar::<E>::random(&mut rng);
+        let f = Polynomial::sample_with_const_term(&mut rng, 4, secret.clone());
+        let F = &f * &Point::generator();
+
+        let xs = iter::repeat_with(|| NonZero::<Scalar<E>>::random(&mut rng))
+            .take(5)
+            .collect::<Vec<_>>();
+        let basis = LagrangeBasis::new(Scalar::zero(), &xs).unwrap();
+        for (j, lambda_j) in basis.coefficients().iter().enumerate() {
+            assert_eq!(
+                *lambda_j,
+                lagrange_coefficient(Scalar::zero(), j, &xs).unwrap()
+            );
+        }
+
+        let shares = xs.iter().map(|x| f.value(x)).collect::<Vec<Scalar<E>>>();
+        let public_shares = xs.iter().map(|x| F.value(x)).collect::<Vec<Point<E>>>();
+        assert_eq!(
+            basis.interpolate(&shares).unwrap(),
+            secret.expose_secret(|s| *s)
+        );
+        assert_eq!(
+            basis.interpolate(&public_shares).unwrap(),
+            Point::generator() * &secret
+        );
+        assert!(basis.interpolate(&shares[1..]).is_none());
+
+        // Interpolation at arbitrary point
+        let x = Scalar::random(&mut rng);
+        let basis = LagrangeBasis::new(x, &xs).unwrap();
+        assert_eq!(
+            basis.interpolate(&shares).unwrap(),
+            f.value::<_, Scalar<E>>(&x)
+        );
+
+        // Points must be pairwise distinct, and `x` must not be one of them
+        let mut duplicated = xs.clone();
+        duplicated[1] = duplicated[0];
+        assert!(LagrangeBasis::new(Scalar::zero(), &duplicated).is_none());
+        assert!(LagrangeBasis::new(*xs[2].as_ref(), &xs).is_none());
+    }
+
+    #[test]
+    fn multipoint_evaluation<E: Curve>() {
+        let mut rng = DevRng::new();
+
+        for degree in [0, 1, 5] {
+            let f = Polynomial::<Scalar<E>>::sample(&mut rng, degree);
+            let F = &f * &Point::generator();
+
+            let xs = iter::repeat_with(|| Scalar::random(&mut rng))
+                .take(10)
+                .collect::<Vec<_>>();
+            let expected = xs
+                .iter()
+                .map(|x| F.value::<_, Point<E>>(x))
+                .collect::<Vec<_>>();
+            assert_eq!(F.values(&xs), expected);
+
+            for n in [0, 1, degree + 1, 20] {
+                let expected = (1..=n)
+                    .map(|i| F.value::<_, Point<E>>(&Scalar::from(i as u64)))
+                    .collect::<Vec<_>>();
+                assert_eq!(F.values_at_indexes(n), expected);
+            }
+        }
+
+        let zero = Polynomial::<Point<E>>::from_coefs(Vec::new());
+        assert_eq!(zero.values_at_indexes(3), [Point::zero(); 3]);
+    }
+
+    #[test]
+    fn constant_time_evaluation<E: Curve>() {
+        let mut rng = DevRng::new();
+
+        let secret = SecretScalar::<E>::random(&mut rng);
+        let f = Polynomial::sample_with_const_term(&mut rng, 3, secret.clone());
+        assert_eq!(
+            f.eval_ct(&Scalar::zero()).into_inner_unprotected(),
+            secret.expose_secret(|s| *s)
+        );
+
+        for _ in 0..10 {
+            let x = Scalar::random(&mut rng);
+            assert_eq!(
+                f.eval_ct(&x).into_inner_unprotected(),
+                f.value::<_, Scalar<E>>(&x)
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_polynomial<E: Curve>() {
+        let mut rng = DevRng::new();
+
+        let secret = SecretScalar::<E>::random(&mut rng);
+        let f = FixedPolynomial::<_, 4>::sample_with_const_term(&mut rng, secret.clone());
+        let heap_f = Polynomial::from(FixedPolynomial::from_coefs(
+            f.coefs()
+                .clone()
+                .map(|coef_i| coef_i.into_inner_unprotected()),
+        ));
+        assert_eq!(heap_f.degree(), 3);
+
+        let commitment = f.commit(&Point::<E>::generator().to_point());
+        let I = [1, 2, 3, 4, 5].map(|i| NonZero::from_scalar(Scalar::from(i)).unwrap());
+        let shares = I.map(|i| {
+            let share = f.eval_ct(&i).into_inner_unprotected();
+            assert_eq!(share, heap_f.value::<_, Scalar<E>>(&i));
+            assert!(commitment.verify_share(&Point::generator().to_point(), &i, &share));
+            share
+        });
+
+        // Any 4 shares reconstruct the secret, 3 shares are not enough
+        let reconstructed = interpolate(Scalar::zero(), &I[1..], &shares[1..]).unwrap();
+        assert_eq!(reconstructed, secret.expose_secret(|s| *s));
+        let reconstructed = interpolate(Scalar::zero(), &I[..3], &shares[..3]).unwrap();
+        assert_ne!(reconstructed, secret.expose_secret(|s| *s));
+
+        let public_shares = shares.map(|share| Point::generator() * share);
+        assert_eq!(
+            interpolate(Scalar::zero(), &I[..4], &public_shares[..4]),
+            Some(commitment.coefs()[0])
+        );
+        assert_eq!(interpolate(Scalar::zero(), &I, &shares[..4]), None);
+
+        let g = FixedPolynomial::<Scalar<E>, 4>::from_coefs([
+            Scalar::one(),
+            Scalar::zero(),
+            Scalar::one(),
+            Scalar::zero(),
+        ]);
+        assert_eq!(g.degree(), 2);
+        let x = Scalar::random(&mut rng);
+        assert_eq!(
+            (g + &g).value::<_, Scalar<E>>(&x),
+            (&g * &Scalar::from(2)).value::<_, Scalar<E>>(&x)
+        );
     }
 
     #[test]
@@ -485,6 +1208,52 @@ mod tests {
         }
     }
 
+    /// Commitments living outside of the curve: $\text{commit}(v) = v h$ in additive group of
+    /// scalar field
+    struct ScalarCommitment<E: Curve>(Scalar<E>);
+
+    impl<E: Curve> HomomorphicCommitment<E> for ScalarCommitment<E> {
+        type Commitment = Scalar<E>;
+
+        fn commit(&self, value: &Scalar<E>) -> Scalar<E> {
+            self.0 * value
+        }
+        fn zero(&self) -> Scalar<E> {
+            Scalar::zero()
+        }
+        fn add(&self, a: &Scalar<E>, b: &Scalar<E>) -> Scalar<E> {
+            a + b
+        }
+        fn mul(&self, commitment: &Scalar<E>, k: &Scalar<E>) -> Scalar<E> {
+            commitment * k
+        }
+    }
+
+    #[test]
+    fn homomorphic_commitment<E: Curve>() {
+        let mut rng = DevRng::new();
+        let secret = SecretScalar::<E>::random(&mut rng);
+        let f = Polynomial::sample_with_const_term(&mut rng, 3, secret);
+
+        let feldman = Point::<E>::generator().to_point();
+        let other_group = ScalarCommitment(Scalar::<E>::random(&mut rng));
+        let F = f.commit(&feldman);
+        let F_other = f.commit(&other_group);
+        assert_eq!(F.coefs(), (&f * &Point::generator()).coefs());
+
+        for i in 1..=5u16 {
+            let i = NonZero::from_scalar(Scalar::from(i)).unwrap();
+            let share = f.eval_ct(&i);
+            let share = share.into_inner_unprotected();
+            assert!(F.verify_share(&feldman, &i, &share));
+            assert!(F_other.verify_share(&other_group, &i, &share));
+
+            let wrong_share = share + Scalar::one();
+            assert!(!F.verify_share(&feldman, &i, &wrong_share));
+            assert!(!F_other.verify_share(&other_group, &i, &wrong_share));
+        }
+    }
+
     #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
     mod secp256k1 {}
     #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
@@ -0,0 +1,736 @@
+//! Bundle of $\Sigma$ proofs sharing one transcript
+//!
+//! Many $\Sigma$ protocols (Schnorr PoK, [proof of representation](crate::representation_pok),
+//! equality of discrete logs, etc.) prove knowledge of a witness $w_1, \dots, w_n$ satisfying a
+//! [linear relation](LinearRelation): a set of equations $\sum_j w_j \cdot B_{i,j} = X_i$.
+//! [`ProofBundle`] proves any amount of such relations at once:
+//!
+//! * All proofs share one Fiat-Shamir [transcript](crate::transcript) and one challenge
+//! * All proofs are verified together via a single multiscalar multiplication
+//! * Bundle is [serialized](ProofBundle::to_bytes) as plain concatenation of points and scalars,
+//!   without any framing, as its shape is determined by relations known to verifier
+//! * Serialized bundle can be [verified](ProofBundle::verify_bytes) right from the message
+//!   buffer, without deserializing it into [`ProofBundle`] first
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{proof_bundle::{BundleProver, LinearRelation, ProofBundle}, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//! # let h = Point::<Secp256k1>::generator() * SecretScalar::random(&mut rng);
+//!
+//! let x = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let X = Point::generator() * &x;
+//! let (y, Y) = (x.clone(), h * &x);
+//!
+//! let relations = [
+//!     LinearRelation::schnorr(X),
+//!     LinearRelation::dleq(Point::generator().to_point(), X, h, Y),
+//! ];
+//!
+//! let mut prover = BundleProver::new(Transcript::<sha2::Sha256>::new(b"example"));
+//! prover.add(&mut rng, relations[0].clone(), &[x])?;
+//! prover.add(&mut rng, relations[1].clone(), &[y])?;
+//! let bundle = prover.prove();
+//!
+//! let bytes = bundle.to_bytes();
+//! let bundle = ProofBundle::<Secp256k1>::from_bytes(&bytes, &relations)?;
+//!
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! bundle.verify(&mut transcript, &mut rng, &relations)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! * Prove
+//!   1. For every relation, prover samples $k_j \gets \Z_q$ for every witness and computes
+//!      commitments $A_i = \sum_j k_j \cdot B_{i,j}$ for every equation
+//!   2. Prover appends all relations and commitments to the transcript and obtains challenge $e$
+//!   3. Prover sends commitments and responses $z_j = k_j + e w_j$
+//! * Verification \
+//!   Verifier obtains challenge $e$, samples $\rho_i \gets \Z_q$ for every equation of every
+//!   relation, and checks that $\sum_i \rho_i \cdot (\sum_j z_j \cdot B_{i,j} - A_i - e \cdot X_i) \\? 0$
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::transcript::Transcript;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Linear relation $\sum_j w_j \cdot B_{i,j} = X_i$ for every equation $i$
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct LinearRelation<E: Curve> {
+    /// Amount of witnesses $n$
+    pub witnesses: usize,
+    /// Equations of the relation
+    pub equations: Vec<Equation<E>>,
+}
+
+/// Equation $\sum_j w_j \cdot B_j = X$
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Equation<E: Curve> {
+    /// Terms of the left side of the equation: index of a witness $j$ and base $B_j$
+    pub terms: Vec<(usize, Point<E>)>,
+    /// Right side of the equation $X$
+    pub image: Point<E>,
+}
+
+impl<E: Curve> LinearRelation<E> {
+    /// Constructs a relation on `witnesses` with no equations
+    pub fn new(witnesses: usize) -> Self {
+        Self {
+            witnesses,
+            equations: Vec::new(),
+        }
+    }
+
+    /// Adds an equation to the relation
+    pub fn with_equation(
+        mut self,
+        terms: impl IntoIterator<Item = (usize, Point<E>)>,
+        image: Point<E>,
+    ) -> Self {
+        self.equations.push(Equation {
+            terms: terms.into_iter().collect(),
+            image,
+        });
+        self
+    }
+
+    /// Knowledge of $x$ such as $X = x \cdot G$
+    #[allow(non_snake_case)]
+    pub fn schnorr(X: Point<E>) -> Self {
+        Self::new(1).with_equation([(0, Point::generator().to_point())], X)
+    }
+
+    /// Knowledge of $x_1, \dots, x_n$ such as $X = \sum_j x_j \cdot G_j$
+    #[allow(non_snake_case)]
+    pub fn representation(bases: &[Point<E>], X: Point<E>) -> Self {
+        Self::new(bases.len()).with_equation(bases.iter().copied().enumerate(), X)
+    }
+
+    /// Knowledge of $x$ such as $X = x \cdot G$ and $Y = x \cdot H$
+    #[allow(non_snake_case)]
+    pub fn dleq(G: Point<E>, X: Point<E>, H: Point<E>, Y: Point<E>) -> Self {
+        Self::new(1)
+            .with_equation([(0, G)], X)
+            .with_equation([(0, H)], Y)
+    }
+
+    /// Checks that all terms refer to existing witnesses
+    fn is_well_formed(&self) -> bool {
+        self.equations
+            .iter()
+            .flat_map(|eq| &eq.terms)
+            .all(|(j, _)| *j < self.witnesses)
+    }
+
+    /// Evaluates left side of every equation on secret `values`
+    ///
+    /// Multiscalar multiplication is not constant time, so every term is computed separately.
+    /// Terms referring to missing values are skipped.
+    fn eval_secret<'a>(
+        &'a self,
+        values: &'a [SecretScalar<E>],
+    ) -> impl Iterator<Item = Point<E>> + 'a {
+        self.equations.iter().map(move |eq| {
+            eq.terms
+                .iter()
+                .filter_map(|(j, base)| Some(base * values.get(*j)?))
+                .sum()
+        })
+    }
+
+    fn append_to<D: Digest + Clone>(&self, transcript: &mut Transcript<D>) {
+        transcript.append_u64(b"proof_bundle/witnesses", self.witnesses as u64);
+        transcript.append_u64(b"proof_bundle/equations", self.equations.len() as u64);
+        for eq in &self.equations {
+            transcript.append_u64(b"proof_bundle/terms", eq.terms.len() as u64);
+            for (j, base) in &eq.terms {
+                transcript.append_u64(b"proof_bundle/witness_index", *j as u64);
+                transcript.append_point(b"proof_bundle/base", base);
+            }
+            transcript.append_point(b"proof_bundle/image", &eq.image);
+        }
+    }
+}
+
+/// Proof of a single relation within the bundle
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct LinearProof<E: Curve> {
+    /// Commitment $A_i$ for every equation
+    pub commitments: Vec<Point<E>>,
+    /// Response $z_j$ for every witness
+    pub responses: Vec<Scalar<E>>,
+}
+
+/// Bundle of proofs sharing one transcript
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct ProofBundle<E: Curve> {
+    /// Proof for every relation, in order they were added
+    pub proofs: Vec<LinearProof<E>>,
+}
+
+/// Collects relations and witnesses, and [proves](Self::prove) them all at once
+///
+/// Witnesses and nonces are kept in [`SecretScalar`]s, and commitments are computed in constant
+/// time.
+pub struct BundleProver<E: Curve, D: Digest> {
+    transcript: Transcript<D>,
+    relations: Vec<PendingProof<E>>,
+}
+
+/// Relation added to the bundle along with its witness and nonces
+struct PendingProof<E: Curve> {
+    relation: LinearRelation<E>,
+    witness: Vec<SecretScalar<E>>,
+    nonces: Vec<SecretScalar<E>>,
+}
+
+impl<E: Curve, D: Digest + Clone> BundleProver<E, D> {
+    /// Constructs a prover
+    ///
+    /// Relations and commitments will be appended to the `transcript`
+    pub fn new(transcript: Transcript<D>) -> Self {
+        Self {
+            transcript,
+            relations: Vec::new(),
+        }
+    }
+
+    /// Adds a relation to the bundle
+    ///
+    /// Returns error if relation is malformed, or `witness` doesn't satisfy it.
+    pub fn add<R: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        relation: LinearRelation<E>,
+        witness: &[SecretScalar<E>],
+    ) -> Result<(), InvalidInput> {
+        if !relation.is_well_formed() || relation.witnesses != witness.len() {
+            return Err(InvalidInput);
+        }
+        let satisfied = relation
+            .eval_secret(witness)
+            .zip(&relation.equations)
+            .all(|(lhs, eq)| lhs == eq.image);
+        if !satisfied {
+            return Err(InvalidInput);
+        }
+        self.add_satisfied(rng, relation, witness);
+        Ok(())
+    }
+
+    /// Adds a relation that `witness` is known to satisfy
+    ///
+    /// Meant for relations whose images are computed from the `witness` itself, so they can't
+    /// be unsatisfied. Unlike [`add`](Self::add), doesn't check it. If the relation is malformed
+    /// or not satisfied anyway, the resulting bundle simply fails to verify.
+    pub fn add_satisfied<R: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        relation: LinearRelation<E>,
+        witness: &[SecretScalar<E>],
+    ) {
+        debug_assert!(relation.is_well_formed() && relation.witnesses == witness.len());
+        let nonces = (0..relation.witnesses)
+            .map(|_| SecretScalar::random(rng))
+            .collect();
+        self.relations.push(PendingProof {
+            relation,
+            witness: witness.to_vec(),
+            nonces,
+        });
+    }
+
+    /// Proves all the relations added to the bundle
+    pub fn prove(mut self) -> ProofBundle<E> {
+        let commitments: Vec<Vec<Point<E>>> = self
+            .relations
+            .iter()
+            .map(|p| p.relation.eval_secret(&p.nonces).collect())
+            .collect();
+        let relations = self.relations.iter().map(|p| &p.relation);
+        let e = challenge(&mut self.transcript, relations, &commitments);
+
+        let proofs = self
+            .relations
+            .iter()
+            .zip(commitments)
+            .map(|(p, commitments)| LinearProof {
+                commitments,
+                responses: p
+                    .nonces
+                    .iter()
+                    .zip(&p.witness)
+                    .map(|(k, w)| k + e * w)
+                    .collect(),
+            })
+            .collect();
+        ProofBundle { proofs }
+    }
+}
+
+impl<E: Curve> ProofBundle<E> {
+    /// Verifies all proofs in the bundle
+    ///
+    /// `relations` must be given in the same order as they were added to the [prover](BundleProver).
+    /// Transcript must be in the same state as it was at the moment of proving.
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        relations: &[LinearRelation<E>],
+    ) -> Result<(), InvalidProof> {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("proof_bundle::verify", relations = relations.len()).entered();
+        if !self.matches_shape(relations) {
+            verification_failed!(E, transcript, "proof bundle doesn't match relations");
+            return Err(InvalidProof);
+        }
+        let commitments: Vec<Vec<Point<E>>> =
+            self.proofs.iter().map(|p| p.commitments.clone()).collect();
+        let e = challenge(transcript, relations.iter(), &commitments);
+
+        let mut scalar_points = Vec::new();
+        for (relation, proof) in relations.iter().zip(&self.proofs) {
+            for (eq, commitment) in relation.equations.iter().zip(&proof.commitments) {
+                let rho = Scalar::<E>::random(rng);
+                scalar_points.extend(
+                    eq.terms
+                        .iter()
+                        .map(|(j, base)| (rho * proof.responses[*j], *base)),
+                );
+                scalar_points.push((-rho, *commitment));
+                scalar_points.push((-(rho * e), eq.image));
+            }
+        }
+        if Scalar::multiscalar_mul(scalar_points).is_zero() {
+            Ok(())
+        } else {
+            verification_failed!(E, transcript, "proof bundle equations don't hold");
+            Err(InvalidProof)
+        }
+    }
+
+    /// Verifies serialized bundle without deserializing it first
+    ///
+    /// Equivalent to [`ProofBundle::from_bytes`] followed by [`ProofBundle::verify`], but
+    /// commitments are appended to the transcript as they're encoded in `bytes`, and every point
+    /// and scalar is decoded right into the multiscalar multiplication input, so no intermediate
+    /// [`LinearProof`]s are allocated. Meant for verifiers processing large volume of proofs.
+    pub fn verify_bytes<D: Digest + Clone, R: RngCore + CryptoRng>(
+        bytes: &[u8],
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        relations: &[LinearRelation<E>],
+    ) -> Result<(), InvalidProof> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "proof_bundle::verify_bytes",
+            relations = relations.len(),
+            bytes = bytes.len()
+        )
+        .entered();
+        if bytes.len() != Self::serialized_len(relations)
+            || !relations.iter().all(LinearRelation::is_well_formed)
+        {
+            verification_failed!(
+                E,
+                transcript,
+                "serialized proof bundle doesn't match relations"
+            );
+            return Err(InvalidProof);
+        }
+        let point_len = Point::<E>::serialized_len(true);
+        let scalar_len = Scalar::<E>::serialized_len();
+        let proof_len = |relation: &LinearRelation<E>| {
+            relation.equations.len() * point_len + relation.witnesses * scalar_len
+        };
+
+        let mut rest = bytes;
+        let e = challenge_with(transcript, relations.iter(), |transcript, relation| {
+            let (proof, tail) = rest.split_at(proof_len(relation));
+            rest = tail;
+            let commitments = &proof[..relation.equations.len() * point_len];
+            append_commitments(transcript, commitments.chunks_exact(point_len));
+        });
+
+        let mut scalar_points = Vec::new();
+        let mut responses = Vec::new();
+        let mut rest = bytes;
+        for relation in relations {
+            let (proof, tail) = rest.split_at(proof_len(relation));
+            rest = tail;
+            let (commitments, encoded_responses) =
+                proof.split_at(relation.equations.len() * point_len);
+
+            responses.clear();
+            for response in encoded_responses.chunks_exact(scalar_len) {
+                let Ok(response) = Scalar::<E>::from_be_bytes(response) else {
+                    verification_failed!(E, transcript, "proof bundle contains invalid scalar");
+                    return Err(InvalidProof);
+                };
+                responses.push(response);
+            }
+            for (eq, commitment) in relation
+                .equations
+                .iter()
+                .zip(commitments.chunks_exact(point_len))
+            {
+                let Ok(commitment) = Point::<E>::from_bytes(commitment) else {
+                    verification_failed!(E, transcript, "proof bundle contains invalid point");
+                    return Err(InvalidProof);
+                };
+                let rho = Scalar::<E>::random(rng);
+                scalar_points.extend(
+                    eq.terms
+                        .iter()
+                        .map(|(j, base)| (rho * responses[*j], *base)),
+                );
+                scalar_points.push((-rho, commitment));
+                scalar_points.push((-(rho * e), eq.image));
+            }
+        }
+        if Scalar::multiscalar_mul(scalar_points).is_zero() {
+            Ok(())
+        } else {
+            verification_failed!(E, transcript, "proof bundle equations don't hold");
+            Err(InvalidProof)
+        }
+    }
+
+    /// Serializes the bundle as concatenation of commitments (compressed points) and responses
+    ///
+    /// Serialized bundle doesn't contain any framing, it can only be
+    /// [deserialized](Self::from_bytes) given the relations.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for proof in &self.proofs {
+            for commitment in &proof.commitments {
+                bytes.extend_from_slice(&commitment.to_bytes(true));
+            }
+            for response in &proof.responses {
+                bytes.extend_from_slice(&response.to_be_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Returns size of serialized bundle proving given `relations`
+    pub fn serialized_len(relations: &[LinearRelation<E>]) -> usize {
+        let point_len = Point::<E>::serialized_len(true);
+        let scalar_len = Scalar::<E>::serialized_len();
+        relations
+            .iter()
+            .map(|r| r.equations.len() * point_len + r.witnesses * scalar_len)
+            .sum()
+    }
+
+    /// Deserializes the bundle proving given `relations`
+    pub fn from_bytes(bytes: &[u8], relations: &[LinearRelation<E>]) -> Result<Self, InvalidBytes> {
+        let point_len = Point::<E>::serialized_len(true);
+        let scalar_len = Scalar::<E>::serialized_len();
+        if bytes.len() != Self::serialized_len(relations) {
+            return Err(InvalidBytes);
+        }
+
+        let mut rest = bytes;
+        let mut take = |len: usize| {
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            head
+        };
+        let proofs = relations
+            .iter()
+            .map(|relation| {
+                let commitments = (0..relation.equations.len())
+                    .map(|_| Point::from_bytes(take(point_len)).map_err(|_| InvalidBytes))
+                    .collect::<Result<_, _>>()?;
+                let responses = (0..relation.witnesses)
+                    .map(|_| Scalar::from_be_bytes(take(scalar_len)).map_err(|_| InvalidBytes))
+                    .collect::<Result<_, _>>()?;
+                Ok(LinearProof {
+                    commitments,
+                    responses,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { proofs })
+    }
+
+    fn matches_shape(&self, relations: &[LinearRelation<E>]) -> bool {
+        self.proofs.len() == relations.len()
+            && relations.iter().zip(&self.proofs).all(|(relation, proof)| {
+                relation.is_well_formed()
+                    && proof.commitments.len() == relation.equations.len()
+                    && proof.responses.len() == relation.witnesses
+            })
+    }
+}
+
+fn challenge<'a, E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    relations: impl ExactSizeIterator<Item = &'a LinearRelation<E>>,
+    commitments: &[Vec<Point<E>>],
+) -> Scalar<E> {
+    let mut commitments = commitments.iter();
+    challenge_with(transcript, relations, |transcript, _| {
+        let commitments = commitments.next().map(Vec::as_slice).unwrap_or_default();
+        append_commitments(transcript, commitments.iter().map(|c| c.to_bytes(true)));
+    })
+}
+
+/// Derives a challenge, `append_commitments` is called after every relation is appended to the
+/// transcript
+fn challenge_with<'a, E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    relations: impl ExactSizeIterator<Item = &'a LinearRelation<E>>,
+    mut append_commitments: impl FnMut(&mut Transcript<D>, &LinearRelation<E>),
+) -> Scalar<E> {
+    transcript.append_u64(b"proof_bundle/relations", relations.len() as u64);
+    for relation in relations {
+        relation.append_to(transcript);
+        append_commitments(transcript, relation);
+    }
+    transcript.challenge_scalar(b"proof_bundle/e")
+}
+
+/// Appends encoded commitments to the transcript, same as [`Transcript::append_points`] does
+fn append_commitments<D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    commitments: impl ExactSizeIterator<Item = impl AsRef<[u8]>>,
+) {
+    transcript.append_u64(b"proof_bundle/commitments", commitments.len() as u64);
+    for commitment in commitments {
+        transcript.append_message(b"proof_bundle/commitments", commitment.as_ref());
+    }
+}
+
+/// Invalid input error
+///
+/// Returned when relation is malformed or witness doesn't satisfy it
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("relation is malformed or witness doesn't satisfy it")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid proof bundle")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+/// Bytes don't encode a bundle proving given relations
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidBytes;
+
+impl core::fmt::Display for InvalidBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("bytes don't encode a proof bundle")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidBytes {}
+
+#[cfg(test)]
+#[generic_tests::define]
+#[allow(non_snake_case)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{BundleProver, LinearRelation, ProofBundle};
+    use crate::transcript::Transcript;
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    fn random_point<E: Curve>(rng: &mut DevRng) -> Point<E> {
+        Point::generator() * Scalar::random(rng)
+    }
+
+    /// Returns relations along with their witnesses
+    fn instances<E: Curve>(
+        rng: &mut DevRng,
+    ) -> (Vec<LinearRelation<E>>, Vec<Vec<SecretScalar<E>>>) {
+        let x = SecretScalar::<E>::random(rng);
+        let schnorr = LinearRelation::schnorr(Point::generator() * &x);
+
+        let bases: Vec<Point<E>> = (0..3).map(|_| random_point(rng)).collect();
+        let ys: Vec<SecretScalar<E>> = (0..3).map(|_| SecretScalar::random(rng)).collect();
+        let Y = ys.iter().zip(&bases).map(|(y, b)| b * y).sum();
+        let representation = LinearRelation::representation(&bases, Y);
+
+        let H = random_point(rng);
+        let z = SecretScalar::<E>::random(rng);
+        let dleq = LinearRelation::dleq(
+            Point::generator().to_point(),
+            Point::generator() * &z,
+            H,
+            H * &z,
+        );
+
+        (
+            [schnorr, representation, dleq].into(),
+            [[x].into(), ys, [z].into()].into(),
+        )
+    }
+
+    fn prove<E: Curve>(
+        rng: &mut DevRng,
+        relations: &[LinearRelation<E>],
+        witnesses: &[Vec<SecretScalar<E>>],
+    ) -> ProofBundle<E> {
+        let mut prover = BundleProver::new(transcript());
+        for (relation, witness) in relations.iter().zip(witnesses) {
+            prover.add(rng, relation.clone(), witness).unwrap();
+        }
+        prover.prove()
+    }
+
+    #[test]
+    fn bundle_verifies<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (relations, witnesses) = instances::<E>(&mut rng);
+        let bundle = prove(&mut rng, &relations, &witnesses);
+        bundle
+            .verify(&mut transcript(), &mut rng, &relations)
+            .unwrap();
+
+        // Tampered response
+        let mut tampered = bundle.clone();
+        tampered.proofs[1].responses[2] += Scalar::one();
+        assert!(tampered
+            .verify(&mut transcript(), &mut rng, &relations)
+            .is_err());
+
+        // Different statement
+        let mut other_relations = relations.clone();
+        other_relations[2].equations[1].image += Point::generator();
+        assert!(bundle
+            .verify(&mut transcript(), &mut rng, &other_relations)
+            .is_err());
+
+        // Relations in different order
+        let mut reordered = relations.clone();
+        reordered.swap(0, 2);
+        assert!(bundle
+            .verify(&mut transcript(), &mut rng, &reordered)
+            .is_err());
+    }
+
+    #[test]
+    fn serialization_roundtrip<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (relations, witnesses) = instances::<E>(&mut rng);
+        let bundle = prove(&mut rng, &relations, &witnesses);
+
+        let bytes = bundle.to_bytes();
+        assert_eq!(bytes.len(), ProofBundle::serialized_len(&relations));
+        let decoded = ProofBundle::from_bytes(&bytes, &relations).unwrap();
+        assert_eq!(bundle, decoded);
+
+        assert!(ProofBundle::from_bytes(&bytes[1..], &relations).is_err());
+        assert!(ProofBundle::from_bytes(&bytes, &relations[..2]).is_err());
+    }
+
+    #[test]
+    fn bundle_verifies_from_bytes<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (relations, witnesses) = instances::<E>(&mut rng);
+        let bytes = prove(&mut rng, &relations, &witnesses).to_bytes();
+
+        ProofBundle::verify_bytes(&bytes, &mut transcript(), &mut rng, &relations).unwrap();
+
+        // Serialized bundle doesn't match relations
+        assert!(
+            ProofBundle::verify_bytes(&bytes[1..], &mut transcript(), &mut rng, &relations)
+                .is_err()
+        );
+        assert!(
+            ProofBundle::verify_bytes(&bytes, &mut transcript(), &mut rng, &relations[..2])
+                .is_err()
+        );
+
+        // Any modified byte invalidates the proof
+        for i in [0, bytes.len() / 2, bytes.len() - 1] {
+            let mut modified = bytes.clone();
+            modified[i] ^= 1;
+            assert!(
+                ProofBundle::verify_bytes(&modified, &mut transcript(), &mut rng, &relations)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_witness_is_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (relations, witnesses) = instances::<E>(&mut rng);
+        let mut prover = BundleProver::<E, _>::new(transcript());
+        assert!(prover
+            .add(&mut rng, relations[0].clone(), &witnesses[2])
+            .is_err());
+        assert!(prover
+            .add(&mut rng, relations[1].clone(), &witnesses[1][..2])
+            .is_err());
+        assert!(prover
+            .add(&mut rng, relations[2].clone(), &witnesses[0])
+            .is_err());
+
+        let malformed =
+            LinearRelation::<E>::new(1).with_equation([(1, random_point(&mut rng))], Point::zero());
+        assert!(prover.add(&mut rng, malformed, &witnesses[0]).is_err());
+    }
+
+    #[test]
+    fn unsatisfied_relation_added_without_check_fails_to_verify<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (relations, witnesses) = instances::<E>(&mut rng);
+        let mut prover = BundleProver::<E, _>::new(transcript());
+        prover.add_satisfied(&mut rng, relations[2].clone(), &witnesses[0]);
+        let bundle = prover.prove();
+        assert!(bundle
+            .verify(&mut transcript(), &mut rng, &relations[2..])
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -0,0 +1,420 @@
+//! Publicly verifiable secret sharing (PVSS)
+//!
+//! Implements [Schoenmakers] PVSS on elliptic curves: dealer shares a secret among $n$ parties
+//! so that any $t$ of them can reconstruct it, and anyone, not only the parties, can verify that
+//! the dealing is correct. Shares are encrypted under public keys of the parties, and each share
+//! comes with a proof of equality of discrete logs ([DLEQ](crate::proof_bundle::LinearRelation::dleq))
+//! linking it to dealer's commitment. Decrypted shares are publicly verifiable as well.
+//!
+//! Reconstructed value is a point $S = s \cdot G$ rather than the scalar $s$, which is suitable
+//! for randomness beacons: the beacon output is derived from $S$, e.g. by hashing it.
+//!
+//! [Schoenmakers]: https://www.win.tue.nl/~berry/papers/crypto99.pdf
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{party_index::PartyIndex, pvss, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let transcript = || Transcript::<sha2::Sha256>::new(b"MYAPP-beacon-round-1");
+//!
+//! // Parties' key pairs
+//! let secret_keys: Vec<SecretScalar<Secp256k1>> =
+//!     (0..3).map(|_| SecretScalar::random(&mut rng)).collect();
+//! let public_keys: Vec<_> = secret_keys.iter().map(|sk| Point::generator() * sk).collect();
+//!
+//! // Dealer shares a secret among 3 parties with threshold 2, anyone can verify the dealing
+//! let secret = SecretScalar::random(&mut rng);
+//! let dealing = pvss::deal(&mut rng, transcript(), &secret, 2, &public_keys)?;
+//! dealing.verify(&mut transcript(), &mut rng, 2, &public_keys)?;
+//!
+//! // Parties 0 and 2 decrypt their shares, anyone can verify the decryption
+//! let mut shares = Vec::new();
+//! for i in [PartyIndex(0), PartyIndex(2)] {
+//!     let j = usize::from(i);
+//!     let share =
+//!         pvss::decrypt_share(&mut rng, transcript(), &secret_keys[j], &dealing.encrypted_shares[j])?;
+//!     shares.push((i, share));
+//! }
+//! for (i, share) in &shares {
+//!     let j = usize::from(*i);
+//!     share.verify(&mut transcript(), &mut rng, &public_keys[j], &dealing.encrypted_shares[j])?;
+//! }
+//!
+//! let reconstructed = pvss::reconstruct(shares.iter().map(|(i, share)| (*i, share.share)))?;
+//! assert_eq!(reconstructed, Point::generator() * &secret);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Party $i \in [1, n]$ has key pair $y_i$, $Y_i = y_i \cdot G$.
+//!
+//! * Dealing
+//!   1. Dealer samples polynomial $f$ of degree $t - 1$ with $f(0) = s$, and publishes commitments
+//!      $C_j = a_j \cdot G$ to its coefficients
+//!   2. Dealer publishes encrypted shares $\hat Y_i = f(i) \cdot Y_i$
+//!   3. Dealer proves that $\log_G X_i = \log_{Y_i} \hat Y_i$ for every $i$, where
+//!      $X_i = \sum_j i^j \cdot C_j$
+//! * Verification: anyone computes $X_i$ from commitments and verifies the proofs
+//! * Decryption: party $i$ publishes $S_i = y_i^{-1} \cdot \hat Y_i = f(i) \cdot G$ and proves
+//!   that $\log_G Y_i = \log_{S_i} \hat Y_i$
+//! * Reconstruction: $S = \sum_i \lambda_i \cdot S_i = s \cdot G$ for any $t$ decrypted shares
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{errors::ZeroScalar, Curve, NonZero, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    party_index::PartyIndex,
+    polynomial::{lagrange_coefficient, Polynomial},
+    proof_bundle::{BundleProver, LinearRelation, ProofBundle},
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Dealing: commitments, encrypted shares, and proof of their correctness
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Dealing<E: Curve> {
+    /// Commitments $C_j = a_j \cdot G$ to polynomial coefficients
+    pub commitments: Polynomial<Point<E>>,
+    /// Share of party $i$ encrypted under its public key: $\hat Y_i = f(i) \cdot Y_i$
+    pub encrypted_shares: Vec<Point<E>>,
+    /// DLEQ proofs for every encrypted share
+    pub proof: ProofBundle<E>,
+}
+
+/// Decrypted share $S_i = f(i) \cdot G$ along with proof of correct decryption
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct DecryptedShare<E: Curve> {
+    /// Decrypted share $S_i$
+    pub share: Point<E>,
+    /// DLEQ proof
+    pub proof: ProofBundle<E>,
+}
+
+/// Deals a secret among parties with given public keys
+///
+/// Any `threshold` parties can reconstruct $s \cdot G$. Returns error if `threshold` is zero or
+/// exceeds amount of parties, or amount of parties exceeds `u16::MAX`.
+pub fn deal<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    transcript: Transcript<D>,
+    secret: &SecretScalar<E>,
+    threshold: usize,
+    public_keys: &[Point<E>],
+) -> Result<Dealing<E>, InvalidInput> {
+    if threshold == 0 || threshold > public_keys.len() || public_keys.len() > u16::MAX.into() {
+        return Err(InvalidInput);
+    }
+    let f = Polynomial::sample_with_const_term(rng, threshold - 1, secret.clone());
+    let commitments = f.commit(&Point::generator().to_point());
+
+    let mut prover = BundleProver::new(transcript);
+    let mut encrypted_shares = Vec::with_capacity(public_keys.len());
+    for (i, public_key) in (0u16..).map(PartyIndex).zip(public_keys) {
+        let share = f.eval_ct(&i.share_index().to_scalar::<E>());
+        let encrypted_share = public_key * &share;
+        let relation = LinearRelation::dleq(
+            Point::generator().to_point(),
+            Point::generator() * &share,
+            *public_key,
+            encrypted_share,
+        );
+        prover.add_satisfied(rng, relation, &[share]);
+        encrypted_shares.push(encrypted_share);
+    }
+
+    Ok(Dealing {
+        commitments,
+        encrypted_shares,
+        proof: prover.prove(),
+    })
+}
+
+impl<E: Curve> Dealing<E> {
+    /// Verifies the dealing
+    ///
+    /// Transcript must be in the same state as it was at the moment of dealing.
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        threshold: usize,
+        public_keys: &[Point<E>],
+    ) -> Result<(), InvalidDealing> {
+        if self.commitments.coefs().len() != threshold
+            || self.encrypted_shares.len() != public_keys.len()
+        {
+            return Err(InvalidDealing);
+        }
+        let relations: Vec<_> = self
+            .commitments
+            .values_at_indexes(public_keys.len())
+            .into_iter()
+            .zip(public_keys)
+            .zip(&self.encrypted_shares)
+            .map(|((x_i, public_key), encrypted_share)| {
+                LinearRelation::dleq(
+                    Point::generator().to_point(),
+                    x_i,
+                    *public_key,
+                    *encrypted_share,
+                )
+            })
+            .collect();
+        self.proof
+            .verify(transcript, rng, &relations)
+            .map_err(|_| InvalidDealing)
+    }
+}
+
+/// Decrypts a share and proves correctness of decryption
+///
+/// Returns error if secret key is zero
+pub fn decrypt_share<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    transcript: Transcript<D>,
+    secret_key: &SecretScalar<E>,
+    encrypted_share: &Point<E>,
+) -> Result<DecryptedShare<E>, ZeroScalar> {
+    let secret_key_inv = secret_key.invert().ok_or(ZeroScalar)?;
+    let share = encrypted_share * &secret_key_inv;
+
+    let mut prover = BundleProver::new(transcript);
+    prover.add_satisfied(
+        rng,
+        decryption_relation(&(Point::generator() * secret_key), encrypted_share, &share),
+        core::slice::from_ref(secret_key),
+    );
+    Ok(DecryptedShare {
+        share,
+        proof: prover.prove(),
+    })
+}
+
+impl<E: Curve> DecryptedShare<E> {
+    /// Verifies that the share was decrypted correctly
+    ///
+    /// Transcript must be in the same state as it was at the moment of decryption.
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        public_key: &Point<E>,
+        encrypted_share: &Point<E>,
+    ) -> Result<(), InvalidDecryption> {
+        let relation = decryption_relation(public_key, encrypted_share, &self.share);
+        self.proof
+            .verify(transcript, rng, core::slice::from_ref(&relation))
+            .map_err(|_| InvalidDecryption)
+    }
+}
+
+/// $\log_G Y_i = \log_{S_i} \hat Y_i$
+fn decryption_relation<E: Curve>(
+    public_key: &Point<E>,
+    encrypted_share: &Point<E>,
+    share: &Point<E>,
+) -> LinearRelation<E> {
+    LinearRelation::dleq(
+        Point::generator().to_point(),
+        *public_key,
+        *share,
+        *encrypted_share,
+    )
+}
+
+/// Reconstructs $S = s \cdot G$ from decrypted shares
+///
+/// Takes pairs of party index (position of its public key in the dealing) and the decrypted
+/// share. Shares must be [verified](DecryptedShare::verify) beforehand, and there must be at
+/// least `threshold` of them, otherwise reconstructed value is incorrect. Returns error if
+/// indexes are not distinct.
+pub fn reconstruct<E: Curve>(
+    shares: impl IntoIterator<Item = (PartyIndex, Point<E>)>,
+) -> Result<Point<E>, InvalidInput> {
+    let (indexes, shares): (Vec<_>, Vec<_>) = shares.into_iter().unzip();
+    let xs: Vec<NonZero<Scalar<E>>> = indexes
+        .iter()
+        .map(|i| i.share_index().to_scalar())
+        .collect();
+    (0..xs.len())
+        .map(|j| lagrange_coefficient(Scalar::zero(), j, &xs).ok_or(InvalidInput))
+        .zip(&shares)
+        .map(|(lambda_j, share_j)| Ok(share_j * lambda_j?))
+        .sum()
+}
+
+/// Invalid input error
+///
+/// Returned when threshold or party indexes are invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid threshold or party indexes")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Dealing is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidDealing;
+
+impl core::fmt::Display for InvalidDealing {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid dealing")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidDealing {}
+
+/// Share was decrypted incorrectly
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidDecryption;
+
+impl core::fmt::Display for InvalidDecryption {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("share was decrypted incorrectly")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidDecryption {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, SecretScalar};
+    use rand_dev::DevRng;
+
+    use crate::{party_index::PartyIndex, transcript::Transcript};
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    fn keys<E: Curve>(rng: &mut DevRng, n: usize) -> (Vec<SecretScalar<E>>, Vec<Point<E>>) {
+        let secret_keys: Vec<_> = (0..n).map(|_| SecretScalar::random(rng)).collect();
+        let public_keys = secret_keys
+            .iter()
+            .map(|sk| Point::generator() * sk)
+            .collect();
+        (secret_keys, public_keys)
+    }
+
+    #[test]
+    fn sharing_and_reconstruction<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (secret_keys, public_keys) = keys::<E>(&mut rng, 5);
+        let secret = SecretScalar::<E>::random(&mut rng);
+
+        let dealing = super::deal(&mut rng, transcript(), &secret, 3, &public_keys).unwrap();
+        dealing
+            .verify(&mut transcript(), &mut rng, 3, &public_keys)
+            .unwrap();
+
+        let shares: Vec<_> = [4, 1, 2]
+            .into_iter()
+            .map(|i| {
+                let share = super::decrypt_share(
+                    &mut rng,
+                    transcript(),
+                    &secret_keys[i],
+                    &dealing.encrypted_shares[i],
+                )
+                .unwrap();
+                share
+                    .verify(
+                        &mut transcript(),
+                        &mut rng,
+                        &public_keys[i],
+                        &dealing.encrypted_shares[i],
+                    )
+                    .unwrap();
+                (PartyIndex::try_from(i).unwrap(), share.share)
+            })
+            .collect();
+        let reconstructed = super::reconstruct(shares.iter().copied()).unwrap();
+        assert_eq!(reconstructed, Point::generator() * &secret);
+
+        // Not enough shares
+        let reconstructed = super::reconstruct(shares[..2].iter().copied()).unwrap();
+        assert_ne!(reconstructed, Point::generator() * &secret);
+    }
+
+    #[test]
+    fn invalid_dealing_is_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (_, public_keys) = keys::<E>(&mut rng, 4);
+        let secret = SecretScalar::<E>::random(&mut rng);
+        let dealing = super::deal(&mut rng, transcript(), &secret, 2, &public_keys).unwrap();
+
+        // Wrong threshold
+        assert!(dealing
+            .verify(&mut transcript(), &mut rng, 3, &public_keys)
+            .is_err());
+
+        // Tampered encrypted share
+        let mut tampered = dealing.clone();
+        tampered.encrypted_shares[1] += Point::generator();
+        assert!(tampered
+            .verify(&mut transcript(), &mut rng, 2, &public_keys)
+            .is_err());
+
+        // Shares encrypted under other keys
+        let (_, other_keys) = keys::<E>(&mut rng, 4);
+        assert!(dealing
+            .verify(&mut transcript(), &mut rng, 2, &other_keys)
+            .is_err());
+
+        assert!(super::deal(&mut rng, transcript(), &secret, 0, &public_keys).is_err());
+        assert!(super::deal(&mut rng, transcript(), &secret, 5, &public_keys).is_err());
+    }
+
+    #[test]
+    fn invalid_decryption_is_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (secret_keys, public_keys) = keys::<E>(&mut rng, 2);
+        let secret = SecretScalar::<E>::random(&mut rng);
+        let dealing = super::deal(&mut rng, transcript(), &secret, 2, &public_keys).unwrap();
+
+        // Party 1 decrypts share of party 0 with its own key
+        let share = super::decrypt_share(
+            &mut rng,
+            transcript(),
+            &secret_keys[1],
+            &dealing.encrypted_shares[0],
+        )
+        .unwrap();
+        assert!(share
+            .verify(
+                &mut transcript(),
+                &mut rng,
+                &public_keys[0],
+                &dealing.encrypted_shares[0],
+            )
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
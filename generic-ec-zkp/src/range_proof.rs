@@ -0,0 +1,560 @@
+//! Bulletproofs range proofs $\Pi^\text{range}$
+//!
+//! Range proof convinces verifier that [Pedersen commitment](crate::pedersen) $V = v \cdot G + \gamma \cdot H$
+//! commits to value $v \in [0, 2^n)$ without revealing $v$. Several values can be proven at once
+//! via single aggregated proof, which is much shorter than separate proofs: proof size is
+//! $2 \log_2(nm) + 4$ points and $5$ scalars where $m$ is amount of values.
+//!
+//! The protocol is the one from [Bulletproofs] paper (Sections 4.1-4.3), made non-interactive via
+//! Fiat-Shamir [transcript](crate::transcript), and built on top of [inner-product argument](crate::ipa).
+//!
+//! Bit length $n$ must be a power of two not greater than $64$, and amount of aggregated values
+//! $m$ must be a power of two.
+//!
+//! [Bulletproofs]: https://eprint.iacr.org/2017/1066
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{range_proof, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-range-proof-v0.1.0");
+//! // Parameters that can be used to prove up to 4 values 32 bits each
+//! let params = range_proof::Params::<Secp256k1>::derive(TAG, 4 * 32)?;
+//!
+//! // Prover commits to the value and proves that it's in range $[0, 2^{32})$
+//! let blinding = Scalar::random(&mut rng);
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! let (proof, commitment) =
+//!     range_proof::prove(&mut transcript, &params, &mut rng, 1234, &blinding, 32)?;
+//!
+//! // Verifier checks the proof
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! proof.verify(&mut transcript, &params, &[commitment], 32)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use alloc::vec::Vec;
+use core::iter;
+
+use digest::Digest;
+use generic_ec::{
+    errors::HashError, hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar,
+};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    ipa::{self, InnerProductProof},
+    pedersen,
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Range proof parameters
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Params<E: Curve> {
+    /// Parameters used to commit to values
+    pub pedersen: pedersen::Params<E>,
+    /// Parameters used to commit to bits of values
+    ///
+    /// Its length limits the aggregation: proving $m$ values $n$ bits each requires
+    /// length at least $nm$
+    pub vector: pedersen::VectorParams<E>,
+}
+
+impl<E: Curve> Params<E> {
+    /// Derives parameters that support proofs for up to `capacity` bits in total
+    pub fn derive(tag: Tag, capacity: usize) -> Result<Self, HashError>
+    where
+        Point<E>: FromHash,
+    {
+        Ok(Self {
+            pedersen: pedersen::Params::derive(tag)?,
+            vector: pedersen::VectorParams::derive(tag, capacity)?,
+        })
+    }
+}
+
+/// Range proof
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+#[allow(non_snake_case)]
+pub struct RangeProof<E: Curve> {
+    /// Commitment to bits of values
+    pub A: Point<E>,
+    /// Commitment to blinding vectors
+    pub S: Point<E>,
+    /// Commitment to $t_1$ coefficient
+    pub T1: Point<E>,
+    /// Commitment to $t_2$ coefficient
+    pub T2: Point<E>,
+    /// Blinding of $\hat t$
+    pub tau_x: Scalar<E>,
+    /// Blinding of $A$ and $S$
+    pub mu: Scalar<E>,
+    /// Inner product $\hat t = \langle \mathbf{l}, \mathbf{r} \rangle$
+    pub t_hat: Scalar<E>,
+    /// Proof that $\hat t$ is correctly computed
+    pub ipa: InnerProductProof<E>,
+}
+
+/// Proves that `value` is in range $[0, 2^\text{bits})$
+///
+/// Returns the proof and commitment $V = \text{value} \cdot G + \text{blinding} \cdot H$
+///
+/// Returns error if `value` is out of range, `bits` is not supported, or `params` are too short.
+pub fn prove<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    transcript: &mut Transcript<D>,
+    params: &Params<E>,
+    rng: &mut R,
+    value: u64,
+    blinding: &Scalar<E>,
+    bits: usize,
+) -> Result<(RangeProof<E>, Point<E>), InvalidInput> {
+    let (proof, commitments) =
+        prove_aggregated(transcript, params, rng, &[value], &[*blinding], bits)?;
+    let commitment = commitments.first().copied().ok_or(InvalidInput)?;
+    Ok((proof, commitment))
+}
+
+/// Proves that every value in `values` is in range $[0, 2^\text{bits})$
+///
+/// Returns the proof and commitments $V_j = \text{values}_j \cdot G + \text{blindings}_j \cdot H$
+///
+/// Returns error if any of values is out of range, `bits` or amount of values is not supported,
+/// amount of values and blindings mismatch, or `params` are too short.
+#[allow(non_snake_case)]
+pub fn prove_aggregated<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    transcript: &mut Transcript<D>,
+    params: &Params<E>,
+    rng: &mut R,
+    values: &[u64],
+    blindings: &[Scalar<E>],
+    bits: usize,
+) -> Result<(RangeProof<E>, Vec<Point<E>>), InvalidInput> {
+    let n = bits;
+    let m = values.len();
+    if !is_supported(params, n, m) || blindings.len() != m {
+        return Err(InvalidInput);
+    }
+    if n < 64 && values.iter().any(|v| v >> n != 0) {
+        return Err(InvalidInput);
+    }
+    let nm = n * m;
+    let g = &params.vector.g[..nm];
+    let h = &params.vector.h[..nm];
+    let B = params.pedersen.g;
+    let B_blinding = params.pedersen.h;
+
+    let commitments: Vec<_> = values
+        .iter()
+        .zip(blindings)
+        .map(|(v, gamma)| params.pedersen.commit(&Scalar::from(*v), gamma))
+        .collect();
+    append_statement(transcript, n, &commitments);
+
+    // Commit to bits of values
+    let a_l: Vec<Scalar<E>> = values
+        .iter()
+        .flat_map(|v| (0..n).map(move |i| Scalar::from((v >> i) & 1)))
+        .collect();
+    let a_r: Vec<Scalar<E>> = a_l.iter().map(|a| a - Scalar::one()).collect();
+    let alpha = Scalar::random(rng);
+    // Bits and blindings are secret, so commitments are computed in constant time
+    let A = params.vector.commit(&a_l, &a_r).ok_or(InvalidInput)? + B_blinding * alpha;
+
+    let s_l: Vec<Scalar<E>> = iter::repeat_with(|| Scalar::random(rng)).take(nm).collect();
+    let s_r: Vec<Scalar<E>> = iter::repeat_with(|| Scalar::random(rng)).take(nm).collect();
+    let rho = Scalar::random(rng);
+    let S = params.vector.commit(&s_l, &s_r).ok_or(InvalidInput)? + B_blinding * rho;
+
+    transcript.append_point(b"range_proof/A", &A);
+    transcript.append_point(b"range_proof/S", &S);
+    let y: Scalar<E> = transcript.challenge_scalar(b"range_proof/y");
+    let z: Scalar<E> = transcript.challenge_scalar(b"range_proof/z");
+    let y_inv = y.invert().ok_or(InvalidInput)?;
+
+    // Compute coefficients of $l(X) = l_0 + l_1 X$, $r(X) = r_0 + r_1 X$, and $t(X) = \langle l(X), r(X) \rangle$
+    let y_pows = powers(y, nm);
+    let zz = bit_weights(z, n, m);
+    let l0: Vec<_> = a_l.iter().map(|a| a - z).collect();
+    let l1 = s_l;
+    let r0: Vec<_> = a_r
+        .iter()
+        .zip(&y_pows)
+        .zip(&zz)
+        .map(|((a, y_i), zz_i)| y_i * (a + z) + zz_i)
+        .collect();
+    let r1: Vec<_> = s_r.iter().zip(&y_pows).map(|(s, y_i)| y_i * s).collect();
+
+    let t1 = inner_product(&l0, &r1) + inner_product(&l1, &r0);
+    let t2 = inner_product(&l1, &r1);
+
+    let tau1 = Scalar::random(rng);
+    let tau2 = Scalar::random(rng);
+    let T1 = params.pedersen.commit(&t1, &tau1);
+    let T2 = params.pedersen.commit(&t2, &tau2);
+
+    transcript.append_point(b"range_proof/T1", &T1);
+    transcript.append_point(b"range_proof/T2", &T2);
+    let x: Scalar<E> = transcript.challenge_scalar(b"range_proof/x");
+
+    let l: Vec<_> = l0.iter().zip(&l1).map(|(l0, l1)| l0 + x * l1).collect();
+    let r: Vec<_> = r0.iter().zip(&r1).map(|(r0, r1)| r0 + x * r1).collect();
+    let t_hat = inner_product(&l, &r);
+    let tau_x = tau2 * x * x
+        + tau1 * x
+        + z_pows(z, m)
+            .zip(blindings)
+            .map(|(z_j, gamma)| z_j * gamma)
+            .sum::<Scalar<E>>();
+    let mu = alpha + rho * x;
+
+    transcript.append_scalar(b"range_proof/tau_x", &tau_x);
+    transcript.append_scalar(b"range_proof/mu", &mu);
+    transcript.append_scalar(b"range_proof/t_hat", &t_hat);
+    let w: Scalar<E> = transcript.challenge_scalar(b"range_proof/w");
+
+    // Prove that $\hat t = \langle l, r \rangle$ using bases $\mathbf{H}'_i = y^{-i} \mathbf{H}_i$
+    let h_prime: Vec<_> = h
+        .iter()
+        .zip(powers(y_inv, nm))
+        .map(|(h_i, y_inv_i)| h_i * y_inv_i)
+        .collect();
+    let ipa = ipa::prove(transcript, g, &h_prime, &(B * w), l, r).or(Err(InvalidInput))?;
+
+    Ok((
+        RangeProof {
+            A,
+            S,
+            T1,
+            T2,
+            tau_x,
+            mu,
+            t_hat,
+            ipa,
+        },
+        commitments,
+    ))
+}
+
+impl<E: Curve> RangeProof<E> {
+    /// Verifies that every commitment commits to value in range $[0, 2^\text{bits})$
+    ///
+    /// Transcript must be in the same state as it was at the moment of [proving](prove_aggregated).
+    #[allow(non_snake_case)]
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        params: &Params<E>,
+        commitments: &[Point<E>],
+        bits: usize,
+    ) -> Result<(), InvalidProof> {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("range_proof::verify", bits, commitments = commitments.len())
+                .entered();
+        let n = bits;
+        let m = commitments.len();
+        if !is_supported(params, n, m) {
+            verification_failed!(E, transcript, "unsupported amount of bits or commitments");
+            return Err(InvalidProof);
+        }
+        let nm = n * m;
+        let g = &params.vector.g[..nm];
+        let h = &params.vector.h[..nm];
+        let B = params.pedersen.g;
+        let B_blinding = params.pedersen.h;
+
+        append_statement(transcript, n, commitments);
+        #[cfg(feature = "tracing")]
+        let statement = transcript.clone();
+        transcript.append_point(b"range_proof/A", &self.A);
+        transcript.append_point(b"range_proof/S", &self.S);
+        let y: Scalar<E> = transcript.challenge_scalar(b"range_proof/y");
+        let z: Scalar<E> = transcript.challenge_scalar(b"range_proof/z");
+        transcript.append_point(b"range_proof/T1", &self.T1);
+        transcript.append_point(b"range_proof/T2", &self.T2);
+        let x: Scalar<E> = transcript.challenge_scalar(b"range_proof/x");
+        transcript.append_scalar(b"range_proof/tau_x", &self.tau_x);
+        transcript.append_scalar(b"range_proof/mu", &self.mu);
+        transcript.append_scalar(b"range_proof/t_hat", &self.t_hat);
+        let w: Scalar<E> = transcript.challenge_scalar(b"range_proof/w");
+
+        let y_inv = y.invert().ok_or(InvalidProof)?;
+        let y_pows = powers(y, nm);
+        let zz = bit_weights(z, n, m);
+
+        // Check that $\hat t$ is consistent with commitments:
+        // $\hat t G + \tau_x H = \sum_j z^{2+j} V_j + \delta(y, z) G + x T_1 + x^2 T_2$
+        let sum_y_pows = y_pows.iter().sum::<Scalar<E>>();
+        let sum_two_pows = powers(Scalar::from(2u8), n).into_iter().sum::<Scalar<E>>();
+        let delta = (z - z * z) * sum_y_pows - z * z_pows(z, m).sum::<Scalar<E>>() * sum_two_pows;
+        let t_check =
+            Scalar::multiscalar_mul(z_pows(z, m).map(|z_j| -z_j).zip(commitments).chain([
+                (self.t_hat - delta, &B),
+                (self.tau_x, &B_blinding),
+                (-x, &self.T1),
+                (-(x * x), &self.T2),
+            ]));
+        if !t_check.is_zero() {
+            verification_failed!(E, statement, "t_hat is inconsistent with commitments");
+            return Err(InvalidProof);
+        }
+
+        // Check the inner-product argument along with correctness of $A$ and $S$ in single
+        // multiscalar multiplication
+        let ipa::VerificationScalars { u_sq, u_inv_sq, s } =
+            self.ipa.verification_scalars(transcript, nm).map_err(|_| {
+                verification_failed!(E, statement, "malformed inner-product proof");
+                InvalidProof
+            })?;
+        let a = self.ipa.a;
+        let b = self.ipa.b;
+        let g_scalars = s.iter().map(|s_i| -z - a * s_i);
+        let h_scalars = s
+            .iter()
+            .rev()
+            .zip(&zz)
+            .zip(powers(y_inv, nm))
+            .map(|((s_i_inv, zz_i), y_inv_i)| z + (zz_i - b * s_i_inv) * y_inv_i);
+        let ipa_check = Scalar::multiscalar_mul(
+            u_sq.into_iter()
+                .zip(&self.ipa.L)
+                .chain(u_inv_sq.into_iter().zip(&self.ipa.R))
+                .chain([(Scalar::one(), &self.A), (x, &self.S)])
+                .chain(g_scalars.zip(g))
+                .chain(h_scalars.zip(h))
+                .chain([((self.t_hat - a * b) * w, &B), (-self.mu, &B_blinding)]),
+        );
+        if !ipa_check.is_zero() {
+            verification_failed!(E, statement, "inner-product argument doesn't hold");
+            return Err(InvalidProof);
+        }
+
+        Ok(())
+    }
+}
+
+fn is_supported<E: Curve>(params: &Params<E>, n: usize, m: usize) -> bool {
+    n.is_power_of_two()
+        && n <= 64
+        && m.is_power_of_two()
+        && n.checked_mul(m)
+            .map(|nm| nm <= params.vector.g.len() && nm <= params.vector.h.len())
+            .unwrap_or(false)
+}
+
+fn append_statement<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    n: usize,
+    commitments: &[Point<E>],
+) {
+    transcript.append_u64(b"range_proof/n", n as u64);
+    transcript.append_points(b"range_proof/V", commitments);
+}
+
+/// Returns $[1, x, x^2, \dots, x^{n-1}]$
+fn powers<E: Curve>(x: Scalar<E>, n: usize) -> Vec<Scalar<E>> {
+    iter::successors(Some(Scalar::one()), |acc| Some(acc * x))
+        .take(n)
+        .collect()
+}
+
+/// Returns $z^{2+j}$ for every $j \in [0, m)$
+fn z_pows<E: Curve>(z: Scalar<E>, m: usize) -> impl Iterator<Item = Scalar<E>> {
+    iter::successors(Some(z * z), move |acc| Some(acc * z)).take(m)
+}
+
+/// Returns $z^{2+j} \cdot 2^k$ for every $i = jn + k$
+fn bit_weights<E: Curve>(z: Scalar<E>, n: usize, m: usize) -> Vec<Scalar<E>> {
+    let two_pows = powers(Scalar::from(2u8), n);
+    z_pows(z, m)
+        .flat_map(|z_j| two_pows.iter().map(move |two_k| z_j * two_k))
+        .collect()
+}
+
+fn inner_product<E: Curve>(a: &[Scalar<E>], b: &[Scalar<E>]) -> Scalar<E> {
+    a.iter().zip(b).map(|(a_i, b_i)| a_i * b_i).sum()
+}
+
+/// Invalid input error
+///
+/// Returned when values are out of range, amount of values or bit length is not supported,
+/// or parameters are too short
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid range proof input")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid range proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    use crate::transcript::Transcript;
+
+    const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-range-proof-test");
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn single_value<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = super::Params::<E>::derive(TAG, 64).unwrap();
+
+        for (value, bits) in [(0, 8), (255, 8), (1234, 16), (u64::MAX, 64)] {
+            let blinding = Scalar::random(&mut rng);
+            let (proof, commitment) =
+                super::prove(&mut transcript(), &params, &mut rng, value, &blinding, bits).unwrap();
+            assert_eq!(
+                commitment,
+                params.pedersen.commit(&Scalar::from(value), &blinding)
+            );
+            proof
+                .verify(&mut transcript(), &params, &[commitment], bits)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn aggregated<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = super::Params::<E>::derive(TAG, 4 * 32).unwrap();
+
+        let values = [1, 2, 3, u32::MAX.into()];
+        let blindings: Vec<_> = (0..4).map(|_| Scalar::random(&mut rng)).collect();
+        let (proof, commitments) = super::prove_aggregated(
+            &mut transcript(),
+            &params,
+            &mut rng,
+            &values,
+            &blindings,
+            32,
+        )
+        .unwrap();
+        proof
+            .verify(&mut transcript(), &params, &commitments, 32)
+            .unwrap();
+
+        // Proof doesn't verify against different commitments
+        let mut wrong_commitments = commitments.clone();
+        wrong_commitments[2] += params.pedersen.g;
+        assert!(proof
+            .verify(&mut transcript(), &params, &wrong_commitments, 32)
+            .is_err());
+        // or different bit length
+        assert!(proof
+            .verify(&mut transcript(), &params, &commitments, 16)
+            .is_err());
+        // or different transcript
+        assert!(proof
+            .verify(
+                &mut Transcript::<sha2::Sha256>::new(b"other"),
+                &params,
+                &commitments,
+                32
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn tampered_proof_is_rejected<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = super::Params::<E>::derive(TAG, 8).unwrap();
+        let blinding = Scalar::random(&mut rng);
+        let (proof, commitment) =
+            super::prove(&mut transcript(), &params, &mut rng, 100, &blinding, 8).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.t_hat += Scalar::one();
+        assert!(tampered
+            .verify(&mut transcript(), &params, &[commitment], 8)
+            .is_err());
+
+        let mut tampered = proof.clone();
+        tampered.mu += Scalar::one();
+        assert!(tampered
+            .verify(&mut transcript(), &params, &[commitment], 8)
+            .is_err());
+
+        let mut tampered = proof;
+        tampered.A += params.pedersen.g;
+        assert!(tampered
+            .verify(&mut transcript(), &params, &[commitment], 8)
+            .is_err());
+    }
+
+    #[test]
+    fn invalid_input_is_rejected<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = super::Params::<E>::derive(TAG, 16).unwrap();
+        let blinding = Scalar::random(&mut rng);
+
+        // Value out of range
+        assert!(super::prove(&mut transcript(), &params, &mut rng, 256, &blinding, 8).is_err());
+        // Bit length is not a power of two
+        assert!(super::prove(&mut transcript(), &params, &mut rng, 1, &blinding, 6).is_err());
+        // Params are too short
+        assert!(super::prove(&mut transcript(), &params, &mut rng, 1, &blinding, 32).is_err());
+        // Amount of values is not a power of two
+        let blindings = [blinding; 3];
+        assert!(super::prove_aggregated(
+            &mut transcript(),
+            &params,
+            &mut rng,
+            &[1, 2, 3],
+            &blindings,
+            4
+        )
+        .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
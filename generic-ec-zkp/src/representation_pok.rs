@@ -0,0 +1,321 @@
+//! Proof of Knowledge of Representation $\Pi^\text{rep}$
+//!
+//! Generalization of [Schnorr PoK](crate::schnorr_pok) to arbitrary amount of bases: it's an
+//! interactive $\Sigma$ protocol that lets prover $\P$ convince verifier $\V$ that it knows secrets
+//! $x_1, \dots, x_n$ such as $X = \sum_i x_i \cdot G_i$ for public bases $G_1, \dots, G_n$.
+//! Proof size is linear in $n$.
+//!
+//! Many proofs for the same bases can be verified at once via [`batch_verify`], which is faster
+//! than verifying them one by one.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Curve, Point, SecretScalar};
+//! use generic_ec_zkp::representation_pok::*;
+//! # use rand::rngs::OsRng;
+//! # fn doc_fn<E: Curve>(bases: &[Point<E>]) -> Result<(), Box<dyn std::error::Error>> {
+//! // Prover knows secrets `x` such as `X = sum_i x_i G_i`
+//! let x: Vec<SecretScalar<E>> = bases.iter().map(|_| SecretScalar::random(&mut OsRng)).collect();
+//! let X: Point<E> = x.iter().zip(bases).map(|(x, g)| g * x).sum();
+//!
+//! // 1. Prover commits ephemeral secrets, `commit` is sent to verifier
+//! let (eph_secret, commit) = prover_commits_ephemeral_secret(&mut OsRng, bases);
+//! // 2. Verifier responds with a challenge
+//! let challenge = Challenge::<E>::generate(&mut OsRng);
+//! // 3. Prover responds with a proof
+//! let proof = prove(&eph_secret, &challenge, &x)?;
+//! // 4. Verifier checks the proof
+//! proof.verify(&commit, &challenge, bases, &X)?;
+//! # Ok(()) }
+//! ```
+//!
+//! ## Algorithm
+//!
+//! * Prove
+//!   1. Prover samples $\alpha_1, \dots, \alpha_n \gets \Z_q$ and sends $A = \sum_i \alpha_i \cdot G_i$ to verifier
+//!   2. Verifier replies with $e \gets \Z_q$
+//!   3. Prover sends $z_i = \alpha_i + e x_i$ for every $i$
+//! * Verification \
+//!   Verifier checks that $\sum_i z_i \cdot G_i \\? A + e \cdot X$
+//! * Batch verification \
+//!   Verifier samples $\rho_k \gets \Z_q$ for every proof $k$ and checks that
+//!   $\sum_i (\sum_k \rho_k z_{k,i}) \cdot G_i - \sum_k \rho_k \cdot (A_k + e_k \cdot X_k) \\? 0$
+
+use alloc::vec::Vec;
+use core::iter;
+
+use generic_ec::{traits::ExposeSecret, Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Committed prover ephemeral secrets
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Commit<E: Curve>(pub Point<E>);
+
+/// Prover ephemeral secrets
+#[derive(Debug)]
+pub struct ProverSecret<E: Curve> {
+    pub nonces: Vec<SecretScalar<E>>,
+}
+
+/// Challenge generated by verifier
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Challenge<E: Curve> {
+    pub nonce: Scalar<E>,
+}
+
+impl<E: Curve> Challenge<E> {
+    /// Generates a random challenge
+    pub fn generate<R: RngCore>(rng: &mut R) -> Self {
+        Self {
+            nonce: Scalar::random(rng),
+        }
+    }
+}
+
+/// The proof that can convince $\V$ that $\P$ knows secrets $x_1, \dots, x_n$
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Proof<E: Curve>(pub Vec<Scalar<E>>);
+
+impl<E: Curve> Proof<E> {
+    /// Verifies that prover knows secrets $x_1, \dots, x_n$ such as $X = \sum_i x_i \cdot G_i$
+    #[allow(non_snake_case)]
+    pub fn verify(
+        &self,
+        commit: &Commit<E>,
+        challenge: &Challenge<E>,
+        bases: &[Point<E>],
+        X: &Point<E>,
+    ) -> Result<(), InvalidProof> {
+        if self.0.len() != bases.len() {
+            return Err(InvalidProof);
+        }
+        let lhs = Scalar::multiscalar_mul(self.0.iter().zip(bases));
+        let rhs = commit.0 + challenge.nonce * X;
+        if lhs == rhs {
+            Ok(())
+        } else {
+            Err(InvalidProof)
+        }
+    }
+}
+
+/// Generates and commits prover ephemeral secrets
+pub fn prover_commits_ephemeral_secret<E: Curve, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    bases: &[Point<E>],
+) -> (ProverSecret<E>, Commit<E>) {
+    let nonces: Vec<SecretScalar<E>> = iter::repeat_with(|| SecretScalar::random(rng))
+        .take(bases.len())
+        .collect();
+    // Nonces are secret, so we can't use multiscalar multiplication as it's not constant time
+    let public = nonces.iter().zip(bases).map(|(k, b)| b * k).sum();
+    (ProverSecret { nonces }, Commit(public))
+}
+
+/// Proves knowledge of `secrets`
+///
+/// Returns error if amount of secrets doesn't match amount of committed ephemeral secrets
+pub fn prove<E: Curve>(
+    committed_secret: &ProverSecret<E>,
+    challenge: &Challenge<E>,
+    secrets: &[impl ExposeSecret<E>],
+) -> Result<Proof<E>, InvalidInput> {
+    if committed_secret.nonces.len() != secrets.len() {
+        return Err(InvalidInput);
+    }
+    Ok(Proof(
+        committed_secret
+            .nonces
+            .iter()
+            .zip(secrets)
+            .map(|(nonce, x)| x.expose_secret(|x| nonce + challenge.nonce * x))
+            .collect(),
+    ))
+}
+
+/// Verifies many proofs for the same `bases` at once
+///
+/// Each item is a tuple of the proof, commitment, challenge, and point $X$ which representation is
+/// claimed to be known. Returns error if any of the proofs is invalid, but doesn't tell which one.
+///
+/// Soundness relies on the batching coefficients being unpredictable to the prover, so `rng` must
+/// be cryptographically secure.
+#[allow(non_snake_case)]
+pub fn batch_verify<'a, E: Curve, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    bases: &[Point<E>],
+    proofs: impl IntoIterator<Item = (&'a Proof<E>, &'a Commit<E>, &'a Challenge<E>, &'a Point<E>)>,
+) -> Result<(), InvalidProof> {
+    let mut bases_scalars = alloc::vec![Scalar::<E>::zero(); bases.len()];
+    let mut other_scalar_points = Vec::new();
+
+    for (proof, commit, challenge, X) in proofs {
+        if proof.0.len() != bases.len() {
+            return Err(InvalidProof);
+        }
+        let rho = Scalar::random(rng);
+        bases_scalars
+            .iter_mut()
+            .zip(&proof.0)
+            .for_each(|(acc, z_i)| *acc += rho * z_i);
+        other_scalar_points.push((-rho, commit.0));
+        other_scalar_points.push((-(rho * challenge.nonce), *X));
+    }
+
+    let sum = Scalar::multiscalar_mul(
+        bases_scalars
+            .into_iter()
+            .zip(bases.iter().copied())
+            .chain(other_scalar_points),
+    );
+    if sum.is_zero() {
+        Ok(())
+    } else {
+        Err(InvalidProof)
+    }
+}
+
+/// Invalid input error
+///
+/// Returned when amount of secrets doesn't match amount of bases
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("amount of secrets doesn't match amount of bases")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid representation PoK proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+#[allow(non_snake_case)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::*;
+
+    struct Instance<E: Curve> {
+        x: Vec<SecretScalar<E>>,
+        X: Point<E>,
+        commit: Commit<E>,
+        challenge: Challenge<E>,
+        proof: Proof<E>,
+    }
+
+    fn prove_random<E: Curve>(rng: &mut DevRng, bases: &[Point<E>]) -> Instance<E> {
+        let x: Vec<SecretScalar<E>> = bases.iter().map(|_| SecretScalar::random(rng)).collect();
+        let X = x.iter().zip(bases).map(|(x, b)| b * x).sum();
+
+        let (eph_secret, commit) = prover_commits_ephemeral_secret(rng, bases);
+        let challenge = Challenge::generate(rng);
+        let proof = prove(&eph_secret, &challenge, &x).unwrap();
+        Instance {
+            x,
+            X,
+            commit,
+            challenge,
+            proof,
+        }
+    }
+
+    fn random_bases<E: Curve>(rng: &mut DevRng, n: usize) -> Vec<Point<E>> {
+        (0..n)
+            .map(|_| Point::generator() * Scalar::random(rng))
+            .collect()
+    }
+
+    #[test]
+    fn honest_proof_verifies<E: Curve>() {
+        let mut rng = DevRng::new();
+        for n in [1, 2, 5] {
+            let bases = random_bases::<E>(&mut rng, n);
+            let instance = prove_random(&mut rng, &bases);
+            assert_eq!(instance.x.len(), n);
+            instance
+                .proof
+                .verify(&instance.commit, &instance.challenge, &bases, &instance.X)
+                .unwrap();
+
+            let wrong_X = instance.X + Point::generator();
+            assert!(instance
+                .proof
+                .verify(&instance.commit, &instance.challenge, &bases, &wrong_X)
+                .is_err());
+        }
+    }
+
+    fn batch_items<E: Curve>(
+        instances: &[Instance<E>],
+    ) -> impl Iterator<Item = (&Proof<E>, &Commit<E>, &Challenge<E>, &Point<E>)> {
+        instances
+            .iter()
+            .map(|i| (&i.proof, &i.commit, &i.challenge, &i.X))
+    }
+
+    #[test]
+    fn batch_verification<E: Curve>() {
+        let mut rng = DevRng::new();
+        let bases = random_bases::<E>(&mut rng, 3);
+        let mut instances: Vec<_> = (0..5).map(|_| prove_random(&mut rng, &bases)).collect();
+
+        batch_verify(&mut rng, &bases, batch_items(&instances)).unwrap();
+
+        instances[3].proof.0[1] += Scalar::one();
+        assert!(batch_verify(&mut rng, &bases, batch_items(&instances)).is_err());
+    }
+
+    #[test]
+    fn mismatched_lengths_are_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let bases = random_bases::<E>(&mut rng, 3);
+        let instance = prove_random(&mut rng, &bases);
+        assert!(instance
+            .proof
+            .verify(
+                &instance.commit,
+                &instance.challenge,
+                &bases[..2],
+                &instance.X
+            )
+            .is_err());
+
+        let (eph_secret, _commit) = prover_commits_ephemeral_secret(&mut rng, &bases);
+        assert!(prove(&eph_secret, &instance.challenge, &instance.x[..2]).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
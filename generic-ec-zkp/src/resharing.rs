@@ -0,0 +1,772 @@
+//! Resharing of a key to a new committee
+//!
+//! Moves a key shared among committee $A$ ($t$-out-of-$n$) to committee $B$ ($t'$-out-of-$n'$)
+//! without ever reconstructing the key. Shared public key stays the same, while shares of
+//! committee $A$ become useless for the new sharing. Committees may overlap, and $t'$, $n'$ may
+//! be greater or smaller than $t$, $n$.
+//!
+//! Resharing is defined by [`Plan`] agreed by everyone: public data of the old
+//! [key share](KeyShare), a set of at least $t$ dealers from committee $A$, and $t'$, $n'$.
+//!
+//! 1. Each dealer [deals](Plan::deal) its share: publishes [`Dealing`] and sends secret share to
+//!    every member of committee $B$. Secret shares must be sent over a private and
+//!    [authenticated](crate::authenticated_msg) channel.
+//! 2. Each member of committee $B$ [verifies](Plan::verify_share) every dealing and its secret
+//!    share, which identifies misbehaving dealers, and then [combines](Plan::combine) them into
+//!    a new [`KeyShare`].
+//!
+//! ## Identifiable aborts
+//! Private shares can't be checked by anyone but their recipient, so an invalid share alone
+//! doesn't let other parties tell whether the dealer or the recipient is lying. To make misbehavior
+//! provable, the dealer signs every [share payload](Dealing::share_payload) via
+//! [`MsgSigner`](crate::authenticated_msg::MsgSigner). Recipient checks it via
+//! [`Plan::receive_share`], which, if the share is invalid, returns [`Complaint`] with the
+//! dealing and the signed share. Anyone can [verify](Complaint::verify) the complaint and exclude
+//! the dealer. Complaint reveals the share, which is fine as the share is invalid anyway.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{key_share::KeyShare, party_index::PartyIndex, polynomial::Polynomial, resharing};
+//! # let mut rng = rand::rngs::OsRng;
+//! # let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! # let f = Polynomial::sample_with_const_term(&mut rng, 1, secret_key.clone());
+//! # let shares: Vec<_> = (0..3).map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar())).collect();
+//! # let old_key_shares: Vec<_> = (0..3).map(|i| KeyShare {
+//! #     i: PartyIndex(i),
+//! #     t: 2,
+//! #     x: shares[usize::from(i)].clone(),
+//! #     shared_public_key: Point::generator() * &secret_key,
+//! #     public_shares: shares.iter().map(|x_j| Point::generator() * x_j).collect(),
+//! # }).collect();
+//!
+//! // Key is shared 2-out-of-3, parties 0 and 2 reshare it 3-out-of-5
+//! let plan = resharing::Plan::new(
+//!     old_key_shares[0].shared_public_key,
+//!     old_key_shares[0].public_shares.clone(),
+//!     vec![PartyIndex(0), PartyIndex(2)],
+//!     3,
+//!     5,
+//! )?;
+//!
+//! let mut dealings = vec![];
+//! let mut secret_shares = vec![];
+//! for dealer in &plan.dealers {
+//!     let (dealing, shares) = plan.deal(&mut rng, &old_key_shares[usize::from(*dealer)])?;
+//!     dealings.push(dealing);
+//!     secret_shares.push(shares);
+//! }
+//!
+//! // Every new party combines shares received from the dealers
+//! for j in 0..5 {
+//!     let received: Vec<_> = secret_shares.iter().map(|shares| shares[j].clone()).collect();
+//!     let new_key_share = plan.combine(PartyIndex(j as u16), &dealings, &received)?;
+//!     assert_eq!(new_key_share.shared_public_key, old_key_shares[0].shared_public_key);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Dealers $S$, $|S| \ge t$, hold shares $x_i$ such as $x = \sum_{i \in S} \lambda_i x_i$, where
+//! $\lambda_i$ are Lagrange coefficients of $S$ at zero.
+//!
+//! * Dealing: dealer $i$ samples polynomial $g_i$ of degree $t' - 1$ with $g_i(0) = \lambda_i x_i$,
+//!   publishes Feldman commitment $G_i = g_i \cdot G$, and sends $g_i(j + 1)$ to new party $j$
+//! * Verification: $G_i(0) = \lambda_i X_i$ where $X_i$ is old public share of the dealer, and
+//!   $g_i(j + 1) \cdot G = G_i(j + 1)$
+//! * Combining: new party $j$ obtains $x'_j = \sum_i g_i(j + 1)$, new public shares are
+//!   $X'_m = \sum_i G_i(m + 1)$, shared public key $\sum_i G_i(0)$ must be equal to $X$
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{Curve, NonZero, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    authenticated_msg::AuthenticatedMsg,
+    key_share::KeyShare,
+    party_index::PartyIndex,
+    polynomial::{LagrangeBasis, Polynomial},
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Resharing plan known to all participants
+#[derive(Clone, Debug)]
+pub struct Plan<E: Curve> {
+    /// Shared public key $X$
+    pub shared_public_key: Point<E>,
+    /// Public shares of the old committee
+    pub old_public_shares: Vec<Point<E>>,
+    /// Old parties dealing their shares
+    pub dealers: Vec<PartyIndex>,
+    /// New threshold $t'$
+    pub new_t: u16,
+    /// Size of the new committee $n'$
+    pub new_n: u16,
+    /// Lagrange coefficients of the dealers
+    lambdas: Vec<NonZero<Scalar<E>>>,
+}
+
+/// Public part of the dealing
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Dealing<E: Curve> {
+    /// Index of the dealer in the old committee
+    pub dealer: PartyIndex,
+    /// Feldman commitment $G_i$ to the dealer polynomial
+    pub commitment: Polynomial<Point<E>>,
+}
+
+/// Evidence that the dealer sent invalid share
+///
+/// Obtained from [`Plan::receive_share`], can be verified by anyone via [`Complaint::verify`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Complaint<E: Curve> {
+    /// Dealing of the accused dealer
+    pub dealing: Dealing<E>,
+    /// Index of the new party that received invalid share
+    pub accuser: PartyIndex,
+    /// Share payload received by the accuser, signed by the dealer
+    pub msg: AuthenticatedMsg<E, Vec<u8>>,
+}
+
+/// Prefix of share payloads, separates them from other messages signed by the dealer
+const SHARE_PAYLOAD_TAG: &[u8] = b"generic-ec-zkp/resharing/share";
+
+impl<E: Curve> Plan<E> {
+    /// Constructs a resharing plan
+    ///
+    /// `dealers` must be distinct members of the old committee, and there must be at least
+    /// threshold of them, otherwise new key share can't be [combined](Self::combine). Returns
+    /// error if dealers are not distinct or out of range, or if new threshold is zero or exceeds
+    /// size of the new committee.
+    pub fn new(
+        shared_public_key: Point<E>,
+        old_public_shares: Vec<Point<E>>,
+        dealers: Vec<PartyIndex>,
+        new_t: u16,
+        new_n: u16,
+    ) -> Result<Self, InvalidPlan> {
+        if dealers.is_empty()
+            || new_t == 0
+            || new_t > new_n
+            || dealers
+                .iter()
+                .any(|i| usize::from(*i) >= old_public_shares.len())
+        {
+            return Err(InvalidPlan);
+        }
+        let xs: Vec<NonZero<Scalar<E>>> = dealers
+            .iter()
+            .map(|i| i.share_index().to_scalar())
+            .collect();
+        let lambdas = LagrangeBasis::new(Scalar::zero(), &xs)
+            .ok_or(InvalidPlan)?
+            .coefficients()
+            .to_vec();
+        Ok(Self {
+            shared_public_key,
+            old_public_shares,
+            dealers,
+            new_t,
+            new_n,
+            lambdas,
+        })
+    }
+
+    /// Deals the key share
+    ///
+    /// Returns public dealing and secret shares, `shares[j]` must be privately sent to new
+    /// party $j$. Returns error if the party is not a dealer, or the key share doesn't match
+    /// the plan.
+    pub fn deal<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        key_share: &KeyShare<E>,
+    ) -> Result<(Dealing<E>, Vec<SecretScalar<E>>), InvalidInput> {
+        if key_share.shared_public_key != self.shared_public_key
+            || key_share.public_shares != self.old_public_shares
+        {
+            return Err(InvalidInput);
+        }
+        let lambda = self.lambda(key_share.i).ok_or(InvalidInput)?;
+
+        let mut const_term = *lambda.as_ref() * &key_share.x;
+        let g = Polynomial::sample_with_const_term(
+            rng,
+            usize::from(self.new_t) - 1,
+            SecretScalar::new(&mut const_term),
+        );
+        let shares = (0..self.new_n)
+            .map(|j| g.eval_ct(&PartyIndex(j).share_index().to_scalar::<E>()))
+            .collect();
+        let dealing = Dealing {
+            dealer: key_share.i,
+            commitment: g.commit(&Point::generator().to_point()),
+        };
+        Ok((dealing, shares))
+    }
+
+    /// Verifies public dealing
+    ///
+    /// Checks that dealer is in the plan, and that the dealing shares its old key share with
+    /// the new threshold.
+    pub fn verify_dealing(&self, dealing: &Dealing<E>) -> Result<(), ResharingError> {
+        let dealer = dealing.dealer;
+        let invalid = ResharingError::InvalidDealing { dealer };
+        let (Some(lambda), Some(public_share)) = (
+            self.lambda(dealer),
+            self.old_public_shares.get(usize::from(dealer)),
+        ) else {
+            return Err(invalid);
+        };
+        let coefs = dealing.commitment.coefs();
+        if coefs.len() != usize::from(self.new_t) || coefs[0] != public_share * lambda {
+            return Err(invalid);
+        }
+        Ok(())
+    }
+
+    /// Verifies public dealing and secret share received by new party `j`
+    pub fn verify_share(
+        &self,
+        dealing: &Dealing<E>,
+        j: PartyIndex,
+        share: &SecretScalar<E>,
+    ) -> Result<(), ResharingError> {
+        self.verify_dealing(dealing)?;
+        if j.0 >= self.new_n
+            || !share.expose_secret(|share| {
+                dealing.commitment.verify_share(
+                    &Point::generator().to_point(),
+                    &j.share_index().to_scalar::<E>(),
+                    share,
+                )
+            })
+        {
+            return Err(ResharingError::InvalidShare {
+                dealer: dealing.dealer,
+            });
+        }
+        Ok(())
+    }
+
+    /// Combines dealings into key share of new party `j`
+    ///
+    /// `dealings[k]` and `shares[k]` must come from dealer `self.dealers[k]`. Every dealing and
+    /// share is [verified](Self::verify_share). Returns error if any of them is invalid, or if
+    /// resulting shared public key doesn't match the old one, which happens when there are
+    /// less dealers than the old threshold.
+    pub fn combine(
+        &self,
+        j: PartyIndex,
+        dealings: &[Dealing<E>],
+        shares: &[SecretScalar<E>],
+    ) -> Result<KeyShare<E>, ResharingError> {
+        if dealings.len() != self.dealers.len() || shares.len() != self.dealers.len() {
+            return Err(ResharingError::MissingDealings);
+        }
+        for ((dealer, dealing), share) in self.dealers.iter().zip(dealings).zip(shares) {
+            if dealing.dealer != *dealer {
+                return Err(ResharingError::MissingDealings);
+            }
+            self.verify_share(dealing, j, share)?;
+        }
+
+        let commitment: Polynomial<Point<E>> = dealings.iter().map(|d| &d.commitment).sum();
+        let shared_public_key = commitment.coefs().first().copied().unwrap_or_default();
+        if shared_public_key != self.shared_public_key {
+            return Err(ResharingError::PublicKeyMismatch);
+        }
+        let mut x = shares.iter().sum::<Scalar<E>>();
+        Ok(KeyShare {
+            i: j,
+            t: self.new_t,
+            x: SecretScalar::new(&mut x),
+            shared_public_key,
+            public_shares: commitment.values_at_indexes(usize::from(self.new_n)),
+        })
+    }
+
+    /// Verifies signed share received by new party `j`
+    ///
+    /// `msg` must be signed by the dealer with `dealer_public_key` under `transcript`. Returns
+    /// the share if it's valid, or [`Complaint`] against the dealer if the share or the dealing
+    /// is invalid. Returns [`ReceiveError::InvalidMessage`] if `msg` isn't signed by the dealer
+    /// or doesn't correspond to the dealing, as it doesn't prove anything.
+    pub fn receive_share<D: Digest + Clone>(
+        &self,
+        transcript: &Transcript<D>,
+        dealer_public_key: &Point<E>,
+        dealing: &Dealing<E>,
+        j: PartyIndex,
+        msg: AuthenticatedMsg<E, Vec<u8>>,
+    ) -> Result<SecretScalar<E>, ReceiveError<E>> {
+        if j.0 >= self.new_n
+            || msg.sender != dealing.dealer
+            || msg.verify_signature(transcript, dealer_public_key).is_err()
+        {
+            return Err(ReceiveError::InvalidMessage);
+        }
+        let share = dealing
+            .share_from_payload(j, &msg.payload)
+            .ok_or(ReceiveError::InvalidMessage)?;
+        match share.map(|mut share| SecretScalar::new(&mut share)) {
+            Some(share) if self.verify_share(dealing, j, &share).is_ok() => Ok(share),
+            _ => Err(ReceiveError::Complaint(Complaint {
+                dealing: dealing.clone(),
+                accuser: j,
+                msg,
+            })),
+        }
+    }
+
+    fn lambda(&self, dealer: PartyIndex) -> Option<NonZero<Scalar<E>>> {
+        let k = self.dealers.iter().position(|i| *i == dealer)?;
+        self.lambdas.get(k).copied()
+    }
+}
+
+impl<E: Curve> Dealing<E> {
+    /// Encodes secret share of new party `j` into payload to be signed by the dealer
+    ///
+    /// Payload binds the share to the dealing and its recipient. It contains the secret share,
+    /// so it must be sent over a private channel.
+    pub fn share_payload(&self, j: PartyIndex, share: &SecretScalar<E>) -> Vec<u8> {
+        let mut payload = self.share_payload_prefix(j);
+        share.expose_secret(|share| payload.extend_from_slice(&share.to_be_bytes()));
+        payload
+    }
+
+    fn share_payload_prefix(&self, j: PartyIndex) -> Vec<u8> {
+        let mut prefix = SHARE_PAYLOAD_TAG.to_vec();
+        prefix.extend_from_slice(&j.0.to_be_bytes());
+        for coef in self.commitment.coefs() {
+            prefix.extend_from_slice(&coef.to_bytes(true));
+        }
+        prefix
+    }
+
+    /// Parses share payload
+    ///
+    /// Returns `None` if payload doesn't correspond to the dealing and recipient, and
+    /// `Some(None)` if the share is not a valid scalar.
+    fn share_from_payload(&self, j: PartyIndex, payload: &[u8]) -> Option<Option<Scalar<E>>> {
+        let share = payload.strip_prefix(self.share_payload_prefix(j).as_slice())?;
+        Some(Scalar::from_be_bytes(share).ok())
+    }
+}
+
+impl<E: Curve> Complaint<E> {
+    /// Verifies the complaint
+    ///
+    /// Returns `Ok(())` if the complaint proves that the dealer misbehaved: the share is signed
+    /// by the dealer with `dealer_public_key` under `transcript`, and either the share or the
+    /// dealing is invalid. Otherwise, the accuser is to blame.
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        plan: &Plan<E>,
+        transcript: &Transcript<D>,
+        dealer_public_key: &Point<E>,
+    ) -> Result<(), InvalidComplaint> {
+        if self.accuser.0 >= plan.new_n
+            || self.msg.sender != self.dealing.dealer
+            || self
+                .msg
+                .verify_signature(transcript, dealer_public_key)
+                .is_err()
+        {
+            return Err(InvalidComplaint);
+        }
+        match self
+            .dealing
+            .share_from_payload(self.accuser, &self.msg.payload)
+        {
+            None => Err(InvalidComplaint),
+            Some(None) => Ok(()),
+            Some(Some(mut share)) => {
+                let share = SecretScalar::new(&mut share);
+                match plan.verify_share(&self.dealing, self.accuser, &share) {
+                    Ok(()) => Err(InvalidComplaint),
+                    Err(_) => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Returns index of the accused dealer
+    pub fn dealer(&self) -> PartyIndex {
+        self.dealing.dealer
+    }
+}
+
+/// Resharing plan is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidPlan;
+
+impl core::fmt::Display for InvalidPlan {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid dealers or new threshold")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidPlan {}
+
+/// Key share can't be dealt according to the plan
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("party is not a dealer or key share doesn't match the plan")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Resharing error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResharingError {
+    /// Public dealing is invalid
+    InvalidDealing {
+        /// Index of the dealer
+        dealer: PartyIndex,
+    },
+    /// Secret share doesn't match public dealing
+    InvalidShare {
+        /// Index of the dealer
+        dealer: PartyIndex,
+    },
+    /// Dealings don't correspond to the dealers of the plan
+    MissingDealings,
+    /// Resulting shared public key doesn't match the old one
+    PublicKeyMismatch,
+}
+
+impl core::fmt::Display for ResharingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidDealing { dealer } => write!(f, "invalid dealing from party {dealer}"),
+            Self::InvalidShare { dealer } => write!(f, "invalid share from party {dealer}"),
+            Self::MissingDealings => f.write_str("dealings don't match dealers of the plan"),
+            Self::PublicKeyMismatch => {
+                f.write_str("resulting shared public key doesn't match the old one")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResharingError {}
+
+/// Signed share is rejected
+#[derive(Debug, Clone)]
+pub enum ReceiveError<E: Curve> {
+    /// Message isn't signed by the dealer or doesn't correspond to the dealing, nobody can be
+    /// blamed
+    InvalidMessage,
+    /// Share or dealing is invalid, the dealer is to blame
+    Complaint(Complaint<E>),
+}
+
+impl<E: Curve> core::fmt::Display for ReceiveError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidMessage => f.write_str("share message is not signed by the dealer"),
+            Self::Complaint(complaint) => {
+                write!(f, "invalid share from party {}", complaint.dealer())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Curve> std::error::Error for ReceiveError<E> {}
+
+/// Complaint doesn't prove misbehavior of the dealer
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidComplaint;
+
+impl core::fmt::Display for InvalidComplaint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("complaint doesn't prove misbehavior of the dealer")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidComplaint {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{Plan, ReceiveError, ResharingError};
+    use crate::{
+        authenticated_msg::MsgSigner,
+        key_share::KeyShare,
+        party_index::PartyIndex,
+        polynomial::{LagrangeBasis, Polynomial},
+        transcript::Transcript,
+    };
+
+    fn key_shares<E: Curve>(
+        rng: &mut DevRng,
+        secret_key: &SecretScalar<E>,
+        t: u16,
+        n: u16,
+    ) -> Vec<KeyShare<E>> {
+        let f = Polynomial::sample_with_const_term(rng, usize::from(t) - 1, secret_key.clone());
+        let shares: Vec<_> = (0..n)
+            .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+            .collect();
+        let public_shares: Vec<_> = shares.iter().map(|x| Point::generator() * x).collect();
+        (0..n)
+            .zip(shares)
+            .map(|(i, x)| KeyShare {
+                i: PartyIndex(i),
+                t,
+                x,
+                shared_public_key: Point::generator() * secret_key,
+                public_shares: public_shares.clone(),
+            })
+            .collect()
+    }
+
+    fn plan<E: Curve>(old: &KeyShare<E>, dealers: &[u16], new_t: u16, new_n: u16) -> Plan<E> {
+        Plan::new(
+            old.shared_public_key,
+            old.public_shares.clone(),
+            dealers.iter().copied().map(PartyIndex).collect(),
+            new_t,
+            new_n,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn resharing<E: Curve>() {
+        let mut rng = DevRng::new();
+        let secret_key = SecretScalar::<E>::random(&mut rng);
+        let old = key_shares(&mut rng, &secret_key, 2, 3);
+
+        for (dealers, new_t, new_n) in [(&[0, 2][..], 3, 5), (&[2, 1, 0], 1, 2), (&[1, 2], 4, 4)] {
+            let plan = plan(&old[0], dealers, new_t, new_n);
+            let (dealings, shares): (Vec<_>, Vec<_>) = plan
+                .dealers
+                .iter()
+                .map(|i| plan.deal(&mut rng, &old[usize::from(*i)]).unwrap())
+                .unzip();
+
+            let new: Vec<_> = (0..new_n)
+                .map(|j| {
+                    let received: Vec<_> =
+                        shares.iter().map(|s| s[usize::from(j)].clone()).collect();
+                    plan.combine(PartyIndex(j), &dealings, &received).unwrap()
+                })
+                .collect();
+            for key_share in &new {
+                key_share.verify_consistency().unwrap();
+                assert_eq!(key_share.public_shares, new[0].public_shares);
+                assert_eq!(key_share.shared_public_key, old[0].shared_public_key);
+            }
+
+            // New threshold of shares reconstructs the same secret key
+            let xs: Vec<_> = (0..new_t)
+                .map(|j| PartyIndex(j).share_index().to_scalar())
+                .collect();
+            let values: Vec<Scalar<E>> = new[..usize::from(new_t)]
+                .iter()
+                .map(|k| k.x.expose_secret(|x| *x))
+                .collect();
+            let reconstructed = LagrangeBasis::new(Scalar::zero(), &xs)
+                .unwrap()
+                .interpolate(&values)
+                .unwrap();
+            assert_eq!(reconstructed, secret_key.expose_secret(|x| *x));
+        }
+    }
+
+    #[test]
+    fn misbehaving_dealers_are_identified<E: Curve>() {
+        let mut rng = DevRng::new();
+        let secret_key = SecretScalar::<E>::random(&mut rng);
+        let old = key_shares(&mut rng, &secret_key, 2, 3);
+        let plan = plan(&old[0], &[0, 1], 2, 3);
+        let (dealings, shares): (Vec<_>, Vec<_>) = [0, 1]
+            .map(|i| plan.deal(&mut rng, &old[i]).unwrap())
+            .into_iter()
+            .unzip();
+        let received = |j: usize| -> Vec<_> { shares.iter().map(|s| s[j].clone()).collect() };
+
+        // Party that isn't a dealer can't deal
+        assert!(plan.deal(&mut rng, &old[2]).is_err());
+
+        // Dealing of another share
+        let mut invalid = dealings.clone();
+        invalid[1] = plan.deal(&mut rng, &old[0]).unwrap().0;
+        invalid[1].dealer = PartyIndex(1);
+        assert_eq!(
+            plan.combine(PartyIndex(0), &invalid, &received(0))
+                .unwrap_err(),
+            ResharingError::InvalidDealing {
+                dealer: PartyIndex(1)
+            }
+        );
+
+        // Dealing with lower threshold
+        let lower = Plan::new(
+            plan.shared_public_key,
+            plan.old_public_shares.clone(),
+            plan.dealers.clone(),
+            1,
+            3,
+        )
+        .unwrap();
+        let mut invalid = dealings.clone();
+        invalid[0] = lower.deal(&mut rng, &old[0]).unwrap().0;
+        assert!(plan.verify_dealing(&invalid[0]).is_err());
+
+        // Secret share not matching the dealing
+        let mut tampered = received(2);
+        tampered[0] = SecretScalar::random(&mut rng);
+        assert_eq!(
+            plan.combine(PartyIndex(2), &dealings, &tampered)
+                .unwrap_err(),
+            ResharingError::InvalidShare {
+                dealer: PartyIndex(0)
+            }
+        );
+
+        // Missing dealing
+        assert_eq!(
+            plan.combine(PartyIndex(2), &dealings[..1], &received(2)[..1])
+                .unwrap_err(),
+            ResharingError::MissingDealings
+        );
+
+        // Not enough dealers
+        let plan = super::Plan::new(
+            plan.shared_public_key,
+            plan.old_public_shares.clone(),
+            vec![PartyIndex(1)],
+            2,
+            3,
+        )
+        .unwrap();
+        let (dealing, shares) = plan.deal(&mut rng, &old[1]).unwrap();
+        assert_eq!(
+            plan.combine(PartyIndex(0), &[dealing], &shares[..1])
+                .unwrap_err(),
+            ResharingError::PublicKeyMismatch
+        );
+    }
+
+    #[test]
+    fn complaints<E: Curve>() {
+        let mut rng = DevRng::new();
+        let secret_key = SecretScalar::<E>::random(&mut rng);
+        let old = key_shares(&mut rng, &secret_key, 2, 3);
+        let plan = plan(&old[0], &[0, 1], 2, 3);
+        let transcript = Transcript::<sha2::Sha256>::new(b"resharing-test");
+
+        let signing_key = SecretScalar::<E>::random(&mut rng);
+        let public_key = Point::generator() * &signing_key;
+        let mut signer = MsgSigner::new(PartyIndex(1), signing_key);
+        let (dealing, shares) = plan.deal(&mut rng, &old[1]).unwrap();
+
+        // Honest dealer
+        for (j, share) in (0..3).map(PartyIndex).zip(&shares) {
+            let msg = signer.sign(&mut rng, &transcript, dealing.share_payload(j, share));
+            let received = plan
+                .receive_share(&transcript, &public_key, &dealing, j, msg)
+                .unwrap();
+            assert_eq!(received.expose_secret(|x| *x), share.expose_secret(|x| *x));
+        }
+
+        // Dealer sends invalid share
+        let invalid_share = SecretScalar::random(&mut rng);
+        let msg = signer.sign(
+            &mut rng,
+            &transcript,
+            dealing.share_payload(PartyIndex(2), &invalid_share),
+        );
+        let Err(ReceiveError::Complaint(complaint)) =
+            plan.receive_share(&transcript, &public_key, &dealing, PartyIndex(2), msg)
+        else {
+            panic!("complaint expected")
+        };
+        assert_eq!(complaint.dealer(), PartyIndex(1));
+        complaint.verify(&plan, &transcript, &public_key).unwrap();
+
+        // Dealer publishes invalid dealing
+        let mut invalid_dealing = dealing.clone();
+        invalid_dealing.commitment = Polynomial::from_coefs(vec![Point::generator() * &secret_key]);
+        let msg = signer.sign(
+            &mut rng,
+            &transcript,
+            invalid_dealing.share_payload(PartyIndex(0), &shares[0]),
+        );
+        let Err(ReceiveError::Complaint(complaint)) = plan.receive_share(
+            &transcript,
+            &public_key,
+            &invalid_dealing,
+            PartyIndex(0),
+            msg,
+        ) else {
+            panic!("complaint expected")
+        };
+        complaint.verify(&plan, &transcript, &public_key).unwrap();
+
+        // Accuser tampers with the share
+        let mut forged = complaint.clone();
+        forged.dealing = dealing.clone();
+        forged.msg.payload = dealing.share_payload(PartyIndex(0), &invalid_share);
+        assert!(forged.verify(&plan, &transcript, &public_key).is_err());
+
+        // Accuser complains about a valid share
+        let mut forged = complaint.clone();
+        forged.dealing = dealing.clone();
+        forged.msg = signer.sign(
+            &mut rng,
+            &transcript,
+            dealing.share_payload(PartyIndex(0), &shares[0]),
+        );
+        assert!(forged.verify(&plan, &transcript, &public_key).is_err());
+
+        // Complaint from another party
+        let mut forged = complaint.clone();
+        forged.accuser = PartyIndex(2);
+        assert!(forged.verify(&plan, &transcript, &public_key).is_err());
+
+        // Message not signed by the dealer isn't attributable
+        let mut other_signer = MsgSigner::new(PartyIndex(1), SecretScalar::random(&mut rng));
+        let msg = other_signer.sign(
+            &mut rng,
+            &transcript,
+            dealing.share_payload(PartyIndex(2), &invalid_share),
+        );
+        assert!(matches!(
+            plan.receive_share(&transcript, &public_key, &dealing, PartyIndex(2), msg),
+            Err(ReceiveError::InvalidMessage)
+        ));
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
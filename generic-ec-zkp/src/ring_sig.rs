@@ -0,0 +1,443 @@
+//! Ring signatures
+//!
+//! Ring signature convinces verifier that message was signed by one of the members of a ring
+//! $P_0, \dots, P_{n-1}$, without revealing which one. Ring is formed spontaneously by the signer
+//! out of any public keys, members don't need to cooperate.
+//!
+//! The module implements:
+//! * Spontaneous Anonymous Group signatures (SAG) via [`sign`], see [AOS02]
+//! * Linkable SAG (LSAG) via [`sign_linkable`], see [LWW04]. Linkable signature additionally
+//!   carries a key image $I = x \cdot H_p(P)$ which is the same for all signatures produced by
+//!   the same key. It lets verifier detect that two signatures were made by the same signer (e.g.
+//!   to prevent double-spending), while still not knowing who the signer is.
+//!
+//! Signature size is linear in ring size. Challenges are derived via [transcript](crate::transcript)
+//! which binds the ring, the message, and (for LSAG) the key image.
+//!
+//! [AOS02]: https://www.iacr.org/archive/asiacrypt2002/25010414/25010414.pdf
+//! [LWW04]: https://eprint.iacr.org/2004/027
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::ring_sig;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let mut ring: Vec<Point<Secp256k1>> = (0..4)
+//!     .map(|_| Point::generator() * SecretScalar::random(&mut rng))
+//!     .collect();
+//! ring[2] = Point::generator() * &secret_key;
+//!
+//! let signature =
+//!     ring_sig::sign_linkable::<_, sha2::Sha256, _>(&mut rng, &ring, 2, &secret_key, b"msg")?;
+//! signature.verify::<sha2::Sha256>(&ring, b"msg")?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Signer knows $x$ such as $P_\pi = x \cdot G$. Let $H$ be a hash function modelled via
+//! transcript which already absorbed the ring and the message.
+//!
+//! * SAG signing
+//!   1. Signer samples $\alpha \gets \Z_q$, sets $c_{\pi+1} = H(\alpha \cdot G)$
+//!   2. For $i = \pi+1, \dots, \pi-1$ (modulo $n$), signer samples $s_i \gets \Z_q$ and sets
+//!      $c_{i+1} = H(s_i \cdot G + c_i \cdot P_i)$
+//!   3. Signer sets $s_\pi = \alpha - c_\pi x$
+//!   4. Signature is $(c_0, s_0, \dots, s_{n-1})$
+//! * SAG verification \
+//!   Verifier computes $c_{i+1} = H(s_i \cdot G + c_i \cdot P_i)$ for $i = 0, \dots, n-1$ and checks
+//!   that $c_n \\? c_0$
+//! * LSAG \
+//!   Same as above, but signer additionally outputs $I = x \cdot H_p(P_\pi)$ (absorbed into the
+//!   transcript), and challenges are computed as
+//!   $c_{i+1} = H(s_i \cdot G + c_i \cdot P_i, s_i \cdot H_p(P_i) + c_i \cdot I)$, with
+//!   $c_{\pi+1} = H(\alpha \cdot G, \alpha \cdot H_p(P_\pi))$
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{
+    hash_to_curve::{FromHash, Tag},
+    Curve, Point, Scalar, SecretScalar,
+};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::transcript::Transcript;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Tag used to hash public keys to points $H_p(P)$
+const KEY_IMAGE_TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp/ring_sig/key_image");
+
+/// Spontaneous Anonymous Group signature
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Signature<E: Curve> {
+    /// Challenge $c_0$
+    pub c0: Scalar<E>,
+    /// Responses $s_0, \dots, s_{n-1}$
+    pub s: Vec<Scalar<E>>,
+}
+
+/// Linkable Spontaneous Anonymous Group signature
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct LinkableSignature<E: Curve> {
+    /// Key image $I = x \cdot H_p(P_\pi)$
+    pub key_image: Point<E>,
+    /// Challenge $c_0$
+    pub c0: Scalar<E>,
+    /// Responses $s_0, \dots, s_{n-1}$
+    pub s: Vec<Scalar<E>>,
+}
+
+/// Signs a `message` on behalf of the `ring`
+///
+/// `secret_key` must correspond to `ring[signer_index]`, otherwise error is returned.
+pub fn sign<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    ring: &[Point<E>],
+    signer_index: usize,
+    secret_key: &SecretScalar<E>,
+    message: &[u8],
+) -> Result<Signature<E>, SignError> {
+    validate_signer(ring, signer_index, secret_key)?;
+    let transcript = sag_transcript::<E, D>(ring, message);
+
+    let alpha = SecretScalar::<E>::random(rng);
+    let first_challenge = {
+        let mut t = transcript.clone();
+        t.append_point(b"L", &(Point::generator() * &alpha));
+        t.challenge_scalar(b"c")
+    };
+    let (c0, s) = close_ring(
+        rng,
+        ring.len(),
+        signer_index,
+        secret_key,
+        &alpha,
+        first_challenge,
+        |i, c_i, s_i| sag_challenge(&transcript, &ring[i], c_i, s_i),
+    );
+    Ok(Signature { c0, s })
+}
+
+impl<E: Curve> Signature<E> {
+    /// Verifies that `message` was signed by one of the members of the `ring`
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        ring: &[Point<E>],
+        message: &[u8],
+    ) -> Result<(), InvalidSignature> {
+        if ring.is_empty() || self.s.len() != ring.len() {
+            verification_failed!(E, "ring is empty or doesn't match the signature");
+            return Err(InvalidSignature);
+        }
+        let transcript = sag_transcript::<E, D>(ring, message);
+        let c_n = ring.iter().zip(&self.s).fold(self.c0, |c_i, (p_i, s_i)| {
+            sag_challenge(&transcript, p_i, &c_i, s_i)
+        });
+        if c_n == self.c0 {
+            Ok(())
+        } else {
+            verification_failed!(E, transcript, "ring doesn't close");
+            Err(InvalidSignature)
+        }
+    }
+}
+
+/// Signs a `message` on behalf of the `ring` producing a linkable signature
+///
+/// `secret_key` must correspond to `ring[signer_index]`, otherwise error is returned.
+pub fn sign_linkable<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    ring: &[Point<E>],
+    signer_index: usize,
+    secret_key: &SecretScalar<E>,
+    message: &[u8],
+) -> Result<LinkableSignature<E>, SignError>
+where
+    Point<E>: FromHash,
+{
+    validate_signer(ring, signer_index, secret_key)?;
+    let hashed_ring = hash_ring(ring).map_err(|_| SignError(Reason::HashToCurve))?;
+    let key_image = hashed_ring[signer_index] * secret_key;
+    let transcript = lsag_transcript::<E, D>(ring, &key_image, message);
+
+    let alpha = SecretScalar::<E>::random(rng);
+    let first_challenge = {
+        let mut t = transcript.clone();
+        t.append_point(b"L", &(Point::generator() * &alpha));
+        t.append_point(b"R", &(hashed_ring[signer_index] * &alpha));
+        t.challenge_scalar(b"c")
+    };
+    let (c0, s) = close_ring(
+        rng,
+        ring.len(),
+        signer_index,
+        secret_key,
+        &alpha,
+        first_challenge,
+        |i, c_i, s_i| lsag_challenge(&transcript, &ring[i], &hashed_ring[i], &key_image, c_i, s_i),
+    );
+    Ok(LinkableSignature { key_image, c0, s })
+}
+
+impl<E: Curve> LinkableSignature<E> {
+    /// Verifies that `message` was signed by one of the members of the `ring`
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        ring: &[Point<E>],
+        message: &[u8],
+    ) -> Result<(), InvalidSignature>
+    where
+        Point<E>: FromHash,
+    {
+        if ring.is_empty() || self.s.len() != ring.len() || self.key_image.is_zero() {
+            verification_failed!(E, "ring is empty or doesn't match the signature");
+            return Err(InvalidSignature);
+        }
+        let hashed_ring = hash_ring(ring).map_err(|_| InvalidSignature)?;
+        let transcript = lsag_transcript::<E, D>(ring, &self.key_image, message);
+        let c_n =
+            ring.iter()
+                .zip(&hashed_ring)
+                .zip(&self.s)
+                .fold(self.c0, |c_i, ((p_i, h_i), s_i)| {
+                    lsag_challenge(&transcript, p_i, h_i, &self.key_image, &c_i, s_i)
+                });
+        if c_n == self.c0 {
+            Ok(())
+        } else {
+            verification_failed!(E, transcript, "ring doesn't close");
+            Err(InvalidSignature)
+        }
+    }
+
+    /// Checks whether two signatures were produced by the same secret key
+    ///
+    /// Signatures must be [verified](Self::verify) beforehand.
+    pub fn is_linked(&self, other: &Self) -> bool {
+        self.key_image == other.key_image
+    }
+}
+
+fn validate_signer<E: Curve>(
+    ring: &[Point<E>],
+    signer_index: usize,
+    secret_key: &SecretScalar<E>,
+) -> Result<(), SignError> {
+    match ring.get(signer_index) {
+        Some(public_key) if *public_key == Point::generator() * secret_key => Ok(()),
+        _ => Err(SignError(Reason::SignerNotInRing)),
+    }
+}
+
+/// Walks the ring starting right after the signer, returns $c_0$ and responses
+///
+/// `next_challenge(i, c_i, s_i)` must compute $c_{i+1}$
+fn close_ring<E: Curve, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    n: usize,
+    signer_index: usize,
+    secret_key: &SecretScalar<E>,
+    alpha: &SecretScalar<E>,
+    first_challenge: Scalar<E>,
+    next_challenge: impl Fn(usize, &Scalar<E>, &Scalar<E>) -> Scalar<E>,
+) -> (Scalar<E>, Vec<Scalar<E>>) {
+    let mut c = alloc::vec![Scalar::zero(); n];
+    let mut s = alloc::vec![Scalar::zero(); n];
+    c[(signer_index + 1) % n] = first_challenge;
+    for j in 1..n {
+        let i = (signer_index + j) % n;
+        s[i] = Scalar::random(rng);
+        c[(i + 1) % n] = next_challenge(i, &c[i], &s[i]);
+    }
+    s[signer_index] = alpha - c[signer_index] * secret_key;
+    (c[0], s)
+}
+
+fn hash_ring<E: Curve>(ring: &[Point<E>]) -> Result<Vec<Point<E>>, generic_ec::errors::HashError>
+where
+    Point<E>: FromHash,
+{
+    ring.iter()
+        .map(|p| Point::hash(KEY_IMAGE_TAG, &p.to_bytes(true)))
+        .collect()
+}
+
+fn sag_transcript<E: Curve, D: Digest + Clone>(ring: &[Point<E>], message: &[u8]) -> Transcript<D> {
+    let mut transcript = Transcript::new(b"generic-ec-zkp/ring_sig/sag");
+    transcript.append_points(b"ring", ring);
+    transcript.append_message(b"message", message);
+    transcript
+}
+
+fn lsag_transcript<E: Curve, D: Digest + Clone>(
+    ring: &[Point<E>],
+    key_image: &Point<E>,
+    message: &[u8],
+) -> Transcript<D> {
+    let mut transcript = Transcript::new(b"generic-ec-zkp/ring_sig/lsag");
+    transcript.append_points(b"ring", ring);
+    transcript.append_point(b"key_image", key_image);
+    transcript.append_message(b"message", message);
+    transcript
+}
+
+/// Computes $c_{i+1} = H(s_i \cdot G + c_i \cdot P_i)$
+fn sag_challenge<E: Curve, D: Digest + Clone>(
+    transcript: &Transcript<D>,
+    p_i: &Point<E>,
+    c_i: &Scalar<E>,
+    s_i: &Scalar<E>,
+) -> Scalar<E> {
+    let l = Scalar::multiscalar_mul([(*s_i, Point::generator().to_point()), (*c_i, *p_i)]);
+    let mut t = transcript.clone();
+    t.append_point(b"L", &l);
+    t.challenge_scalar(b"c")
+}
+
+/// Computes $c_{i+1} = H(s_i \cdot G + c_i \cdot P_i, s_i \cdot H_p(P_i) + c_i \cdot I)$
+fn lsag_challenge<E: Curve, D: Digest + Clone>(
+    transcript: &Transcript<D>,
+    p_i: &Point<E>,
+    h_i: &Point<E>,
+    key_image: &Point<E>,
+    c_i: &Scalar<E>,
+    s_i: &Scalar<E>,
+) -> Scalar<E> {
+    let l = Scalar::multiscalar_mul([(*s_i, Point::generator().to_point()), (*c_i, *p_i)]);
+    let r = Scalar::multiscalar_mul([(*s_i, *h_i), (*c_i, *key_image)]);
+    let mut t = transcript.clone();
+    t.append_point(b"L", &l);
+    t.append_point(b"R", &r);
+    t.challenge_scalar(b"c")
+}
+
+/// Signing error
+#[derive(Debug, Clone, Copy)]
+pub struct SignError(Reason);
+
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    SignerNotInRing,
+    HashToCurve,
+}
+
+impl core::fmt::Display for SignError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Reason::SignerNotInRing => {
+                f.write_str("secret key doesn't correspond to the ring member at signer index")
+            }
+            Reason::HashToCurve => f.write_str("couldn't hash public key to curve point"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignError {}
+
+/// Invalid signature error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidSignature;
+
+impl core::fmt::Display for InvalidSignature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid ring signature")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSignature {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{hash_to_curve::FromHash, Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    type D = sha2::Sha256;
+
+    fn random_ring<E: Curve>(rng: &mut DevRng, n: usize) -> (Vec<SecretScalar<E>>, Vec<Point<E>>) {
+        let secrets: Vec<SecretScalar<E>> = (0..n).map(|_| SecretScalar::random(rng)).collect();
+        let ring = secrets.iter().map(|x| Point::generator() * x).collect();
+        (secrets, ring)
+    }
+
+    #[test]
+    fn sag<E: Curve>() {
+        let mut rng = DevRng::new();
+        for n in [1, 2, 5] {
+            let (secrets, ring) = random_ring::<E>(&mut rng, n);
+            for (i, x) in secrets.iter().enumerate() {
+                let signature = super::sign::<E, D, _>(&mut rng, &ring, i, x, b"msg").unwrap();
+                signature.verify::<D>(&ring, b"msg").unwrap();
+                assert!(signature.verify::<D>(&ring, b"other msg").is_err());
+
+                let mut other_ring = ring.clone();
+                other_ring[(i + 1) % n] += Point::generator();
+                assert!(signature.verify::<D>(&other_ring, b"msg").is_err());
+
+                let mut tampered = signature.clone();
+                tampered.s[0] += Scalar::one();
+                assert!(tampered.verify::<D>(&ring, b"msg").is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn lsag<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let (secrets, ring) = random_ring::<E>(&mut rng, 4);
+
+        let sig1 =
+            super::sign_linkable::<E, D, _>(&mut rng, &ring, 1, &secrets[1], b"msg1").unwrap();
+        sig1.verify::<D>(&ring, b"msg1").unwrap();
+        assert!(sig1.verify::<D>(&ring, b"msg2").is_err());
+
+        // Same signer in a different ring produces linked signature
+        let (_, mut other_ring) = random_ring::<E>(&mut rng, 3);
+        other_ring[2] = ring[1];
+        let sig2 = super::sign_linkable::<E, D, _>(&mut rng, &other_ring, 2, &secrets[1], b"msg2")
+            .unwrap();
+        sig2.verify::<D>(&other_ring, b"msg2").unwrap();
+        assert!(sig1.is_linked(&sig2));
+
+        // Different signer produces unlinked signature
+        let sig3 =
+            super::sign_linkable::<E, D, _>(&mut rng, &ring, 3, &secrets[3], b"msg1").unwrap();
+        sig3.verify::<D>(&ring, b"msg1").unwrap();
+        assert!(!sig1.is_linked(&sig3));
+
+        // Key image can't be replaced
+        let mut tampered = sig1.clone();
+        tampered.key_image = sig3.key_image;
+        assert!(tampered.verify::<D>(&ring, b"msg1").is_err());
+    }
+
+    #[test]
+    fn signer_must_be_in_ring<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (secrets, ring) = random_ring::<E>(&mut rng, 3);
+        assert!(super::sign::<E, D, _>(&mut rng, &ring, 0, &secrets[1], b"msg").is_err());
+        assert!(super::sign::<E, D, _>(&mut rng, &ring, 3, &secrets[1], b"msg").is_err());
+        assert!(super::sign::<E, D, _>(&mut rng, &[], 0, &secrets[1], b"msg").is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
@@ -0,0 +1,78 @@
+//! Sans-IO interface of protocol rounds
+//!
+//! Interactive protocols in this crate (e.g. [DVRF](crate::dvrf)) don't do any networking.
+//! Instead, each round implements [`Round`]: it consumes messages received from other parties,
+//! in any order and from any transport, and outputs the result once enough messages are
+//! collected. This way the same round can be driven by a plain loop (see [`run`]), or by any
+//! async runtime. See `examples/round_tokio.rs` for a driver built on tokio channels.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec_zkp::round::{self, MessageOf, Round};
+//! # use rand_core::{CryptoRng, RngCore};
+//!
+//! /// Waits until `n` numbers are received and outputs their sum
+//! struct Sum { n: usize, received: Vec<u64> }
+//!
+//! impl Round for Sum {
+//!     type Msg = u64;
+//!     type Output = u64;
+//!     type Error = core::convert::Infallible;
+//!
+//!     fn receive<R: RngCore + CryptoRng>(&mut self, _rng: &mut R, msg: u64) -> Result<Option<u64>, Self::Error> {
+//!         self.received.push(msg);
+//!         Ok((self.received.len() >= self.n).then(|| self.received.iter().sum()))
+//!     }
+//! }
+//!
+//! let incoming: Vec<MessageOf<Sum>> = vec![1, 2, 3];
+//! let mut sum = Sum { n: 3, received: vec![] };
+//! let output = round::run(&mut sum, &mut rand::rngs::OsRng, incoming)?;
+//! assert_eq!(output, Some(6));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use rand_core::{CryptoRng, RngCore};
+
+/// Round of an interactive protocol
+///
+/// Round doesn't perform any IO: messages are pushed into it by the caller as they arrive.
+pub trait Round {
+    /// Message received from other parties
+    type Msg;
+    /// Output of the round
+    type Output;
+    /// Error returned when received message can't be processed
+    type Error;
+
+    /// Processes message received from another party
+    ///
+    /// Returns `Ok(None)` while more messages are needed, and round output once enough
+    /// messages are received. Unless documented otherwise by the round, an error doesn't abort
+    /// the round: it only indicates that this message is rejected.
+    fn receive<R: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        msg: Self::Msg,
+    ) -> Result<Option<Self::Output>, Self::Error>;
+}
+
+/// Message of the round `R`
+pub type MessageOf<R> = <R as Round>::Msg;
+
+/// Drives the round by feeding it messages from `incoming`
+///
+/// Returns round output as soon as it's obtained, or `None` if messages ran out before that.
+/// Returns error on the first message rejected by the round.
+pub fn run<R: Round, Rng: RngCore + CryptoRng>(
+    round: &mut R,
+    rng: &mut Rng,
+    incoming: impl IntoIterator<Item = MessageOf<R>>,
+) -> Result<Option<R::Output>, R::Error> {
+    for msg in incoming {
+        if let Some(output) = round.receive(rng, msg)? {
+            return Ok(Some(output));
+        }
+    }
+    Ok(None)
+}
@@ -68,6 +68,79 @@
 //!    # fn recall<T>() -> T { unimplemented!() }
 //!    ```
 //!
+//! ## Typestate API
+//!
+//! Functions above don't prevent misuse: nothing stops prover from answering two challenges with
+//! the same ephemeral secret (which leaks $x$), or verifier from checking the proof against
+//! a commitment from another session. [`ProverAwaitingChallenge`] and [`VerifierAwaitingCommit`]
+//! wrap the same protocol into state machines which enforce the order of steps at compile time:
+//! every transition consumes the state, so ephemeral secret can be used only once, and verifier
+//! checks the proof against the commitment and challenge of its own session.
+//!
+//! ```rust
+//! # use generic_ec::{Curve, Point, SecretScalar};
+//! # use generic_ec_zkp::schnorr_pok::*;
+//! # use rand::rngs::OsRng;
+//! # fn doc_fn<E: Curve>() -> Result<(), InvalidProof> {
+//! let x = SecretScalar::<E>::random(&mut OsRng);
+//! let X = Point::generator() * &x;
+//!
+//! let (prover, commit) = ProverAwaitingChallenge::<E>::commit(&mut OsRng);
+//! let (verifier, challenge) = VerifierAwaitingCommit::new(X).receive_commit(&mut OsRng, commit);
+//! let proof = prover.respond(&challenge, &x);
+//! verifier.verify(&proof)
+//! # }
+//! ```
+//!
+//! ## Non-interactive proof
+//!
+//! Challenge can be derived from the transcript via [`Challenge::derive`], which binds it to
+//! $X$ and the commitment. Verifier re-derives the challenge with
+//! [`Proof::verify_with_rederived_challenge`].
+//!
+//! ```rust
+//! # use generic_ec::{Curve, Point, SecretScalar};
+//! # use generic_ec_zkp::{schnorr_pok::*, transcript::Transcript};
+//! # use rand::rngs::OsRng;
+//! # fn doc_fn<E: Curve>() -> Result<(), InvalidProof> {
+//! let x = SecretScalar::<E>::random(&mut OsRng);
+//! let X = Point::generator() * &x;
+//!
+//! // Prover
+//! let (eph_secret, commit) = prover_commits_ephemeral_secret::<E, _>(&mut OsRng);
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-schnorr-pok");
+//! let challenge = Challenge::derive(&mut transcript, &X, &commit);
+//! let proof = prove(&eph_secret, &challenge, &x);
+//!
+//! // Verifier
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-schnorr-pok");
+//! proof.verify_with_rederived_challenge(&mut transcript, &commit, &X)
+//! # }
+//! ```
+//!
+//! ## Multiple bases
+//!
+//! Prover can show that it knows $x$ such as $X_i = x \cdot G_i$ for several bases $G_1, \dots,
+//! G_n$ at once, i.e. that all $X_i$ have the same discrete log (e.g. in credential presentation).
+//! Prover commits a single ephemeral secret $\alpha$ to every base via
+//! [`prover_commits_ephemeral_secret_multi_base`] and sends a single response computed by the
+//! same [`prove`] function, which is checked with [`Proof::verify_multi_base`].
+//!
+//! ```rust
+//! # use generic_ec::{Curve, Point, SecretScalar};
+//! # use generic_ec_zkp::schnorr_pok::*;
+//! # use rand::rngs::OsRng;
+//! # fn doc_fn<E: Curve>(bases: &[Point<E>]) -> Result<(), InvalidProof> {
+//! let x = SecretScalar::<E>::random(&mut OsRng);
+//! let Xs: Vec<Point<E>> = bases.iter().map(|g_i| g_i * &x).collect();
+//!
+//! let (eph_secret, commit) = prover_commits_ephemeral_secret_multi_base(&mut OsRng, bases);
+//! let challenge = Challenge::<E>::generate(&mut OsRng);
+//! let proof = prove(&eph_secret, &challenge, &x);
+//! proof.verify_multi_base(&commit, &challenge, bases, &Xs)
+//! # }
+//! ```
+//!
 //! ## Algorithm
 //!
 //! Schnor PoK is defined as:
@@ -78,11 +151,25 @@
 //!   3. Prover sends $z = \alpha + ex$
 //! * Verification \
 //!   Verifier checks that $z \cdot G \\? A + e \cdot X$
+//!
+//! With multiple bases, prover sends $A_i = \alpha \cdot G_i$ for every $i$, and verifier checks
+//! that $z \cdot G_i \\? A_i + e \cdot X_i$ for every $i$.
 
-use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use digest::Digest;
+use generic_ec::{
+    core::ByteArray,
+    errors::{InvalidPoint, InvalidScalar},
+    traits::ExposeSecret,
+    Curve, EncodedPoint, EncodedScalar, Point, Scalar, SecretScalar,
+};
 use rand_core::{CryptoRng, RngCore};
 use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+use crate::transcript::Transcript;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -92,11 +179,85 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
 pub struct Commit<E: Curve>(pub Point<E>);
 
+impl<E: Curve> Commit<E> {
+    /// Size of serialized commitment
+    ///
+    /// Commitment is serialized as a compressed point, see [`Commit::to_bytes`]
+    pub const SERIALIZED_LEN: usize = E::CompressedPointArray::LEN;
+
+    /// Serializes commitment as a compressed point
+    pub fn to_bytes(&self) -> EncodedPoint<E> {
+        self.0.to_bytes(true)
+    }
+
+    /// Deserializes commitment
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InvalidPoint> {
+        Point::from_bytes(bytes).map(Self)
+    }
+}
+
+/// Committed prover ephemeral secret, one point per base
+///
+/// $A_i = \alpha \cdot G_i$, see [multiple bases](self#multiple-bases)
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct MultiBaseCommit<E: Curve>(pub Vec<Point<E>>);
+
 /// Prover ephemeral secret
+#[derive(Debug)]
 pub struct ProverSecret<E: Curve> {
     pub nonce: SecretScalar<E>,
 }
 
+impl<E: Curve> ProverSecret<E> {
+    /// Derives ephemeral secret deterministically, returns it along with commitment
+    ///
+    /// Ephemeral secret is derived from the witness `secret`, the `statement` (any public data the
+    /// proof is bound to, e.g. session ID or message), and auxiliary randomness `aux_rand`, similarly
+    /// to nonce generation in [BIP-340]. Unlike [`prover_commits_ephemeral_secret`], a broken RNG
+    /// can't leak the witness: ephemeral secrets coincide only if all the inputs coincide.
+    /// `aux_rand` should be fresh randomness when available, it protects against side-channel
+    /// attacks, but it can be left empty.
+    ///
+    /// Note that proving two different challenges with the same ephemeral secret still leaks
+    /// the witness, so `statement` must be unique per proof when challenges are chosen by verifier.
+    ///
+    /// [BIP-340]: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#default-signing
+    #[allow(non_snake_case)]
+    pub fn derive_deterministic<D: Digest + Clone>(
+        secret: &SecretScalar<E>,
+        statement: &[u8],
+        aux_rand: &[u8],
+    ) -> (Self, Commit<E>) {
+        let X = Point::generator() * secret;
+
+        // Witness is masked with hash of auxiliary randomness before it's hashed
+        let aux_hash = D::new()
+            .chain_update(b"generic-ec-zkp/schnorr_pok/aux")
+            .chain_update(aux_rand)
+            .finalize();
+        let mut masked_secret = Zeroizing::new(secret.expose_secret(|x| x.to_be_bytes()));
+        masked_secret
+            .as_mut()
+            .iter_mut()
+            .zip(aux_hash.iter().cycle())
+            .for_each(|(byte, mask)| *byte ^= mask);
+
+        let mut transcript = Transcript::<D>::new(b"generic-ec-zkp/schnorr_pok/nonce");
+        transcript.append_message(b"masked_secret", masked_secret.as_bytes());
+        transcript.append_point(b"X", &X);
+        transcript.append_message(b"statement", statement);
+        let mut nonce = transcript.challenge_scalar(b"nonce");
+
+        let nonce = SecretScalar::new(&mut nonce);
+        let commit = Commit(Point::generator() * &nonce);
+        (Self { nonce }, commit)
+    }
+}
+
 /// Challenge generated by verifier
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
@@ -112,6 +273,50 @@ impl<E: Curve> Challenge<E> {
             nonce: Scalar::random(rng),
         }
     }
+
+    /// Derives a challenge from the transcript bound to the statement $X$ and prover commitment
+    ///
+    /// Makes the protocol non-interactive (Fiat-Shamir transform). Public key and commitment are
+    /// appended to the `transcript` before the challenge is squeezed, so the challenge can't be
+    /// reused for another statement or commitment. Any other public context (e.g. session ID)
+    /// should be appended to the `transcript` beforehand.
+    ///
+    /// Verifier should use [`Proof::verify_with_rederived_challenge`] which derives the challenge
+    /// the same way.
+    #[allow(non_snake_case)]
+    pub fn derive<D: Digest + Clone>(
+        transcript: &mut Transcript<D>,
+        X: &Point<E>,
+        commit: &Commit<E>,
+    ) -> Self {
+        transcript.append_point(b"schnorr_pok/X", X);
+        transcript.append_point(b"schnorr_pok/commit", &commit.0);
+        Self {
+            nonce: transcript.challenge_scalar(b"schnorr_pok/challenge"),
+        }
+    }
+
+    /// Derives a challenge from the transcript bound to the bases $G_i$, statements $X_i$, and
+    /// prover commitment
+    ///
+    /// Same as [`Challenge::derive`], but for [multiple bases](self#multiple-bases). Verifier
+    /// should use [`Proof::verify_multi_base_with_rederived_challenge`].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[allow(non_snake_case)]
+    pub fn derive_multi_base<D: Digest + Clone>(
+        transcript: &mut Transcript<D>,
+        bases: &[Point<E>],
+        Xs: &[Point<E>],
+        commit: &MultiBaseCommit<E>,
+    ) -> Self {
+        transcript.append_points(b"schnorr_pok/bases", bases);
+        transcript.append_points(b"schnorr_pok/Xs", Xs);
+        transcript.append_points(b"schnorr_pok/commit", &commit.0);
+        Self {
+            nonce: transcript.challenge_scalar(b"schnorr_pok/challenge"),
+        }
+    }
 }
 
 /// The proof that can convince $\V$ that $\P$ knows secret $x$
@@ -121,6 +326,21 @@ impl<E: Curve> Challenge<E> {
 pub struct Proof<E: Curve>(pub Scalar<E>);
 
 impl<E: Curve> Proof<E> {
+    /// Size of serialized proof
+    ///
+    /// Proof is serialized as a big-endian scalar, see [`Proof::to_bytes`]
+    pub const SERIALIZED_LEN: usize = E::ScalarArray::LEN;
+
+    /// Serializes proof as a big-endian scalar
+    pub fn to_bytes(&self) -> EncodedScalar<E> {
+        self.0.to_be_bytes()
+    }
+
+    /// Deserializes proof
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InvalidScalar> {
+        Scalar::from_be_bytes(bytes).map(Self)
+    }
+
     /// Verifies that prover knows secret $x$ such as $X = x \cdot G$
     #[allow(non_snake_case)]
     pub fn verify(
@@ -134,9 +354,78 @@ impl<E: Curve> Proof<E> {
         if lhs.ct_eq(&rhs).into() {
             Ok(())
         } else {
+            verification_failed!(E, "schnorr proof equation doesn't hold");
             Err(InvalidProof)
         }
     }
+
+    /// Verifies non-interactive proof, challenge is re-derived via [`Challenge::derive`]
+    ///
+    /// `transcript` must be in the same state as prover's transcript was when the challenge
+    /// was derived. Unlike [`verify`](Self::verify), this method doesn't take a challenge, so
+    /// a proof is never accepted with a challenge which is not bound to $X$ and the commitment.
+    #[allow(non_snake_case)]
+    pub fn verify_with_rederived_challenge<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        commit: &Commit<E>,
+        X: &Point<E>,
+    ) -> Result<(), InvalidProof> {
+        let challenge = Challenge::derive(transcript, X, commit);
+        self.verify(commit, &challenge, X)
+    }
+
+    /// Verifies that prover knows secret $x$ such as $X_i = x \cdot G_i$ for every base $G_i$
+    ///
+    /// Returns error if `bases` is empty, or if `bases`, `Xs`, and `commit` have different
+    /// lengths. Note that identity bases are allowed, but they don't prove anything about $x$.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[allow(non_snake_case)]
+    pub fn verify_multi_base(
+        &self,
+        commit: &MultiBaseCommit<E>,
+        challenge: &Challenge<E>,
+        bases: &[Point<E>],
+        Xs: &[Point<E>],
+    ) -> Result<(), InvalidProof> {
+        if bases.is_empty() {
+            verification_failed!(E, "no bases given");
+            return Err(InvalidProof);
+        }
+        if bases.len() != Xs.len() || bases.len() != commit.0.len() {
+            verification_failed!(
+                E,
+                "number of bases, statements, and commitments don't match"
+            );
+            return Err(InvalidProof);
+        }
+        for ((G_i, X_i), A_i) in bases.iter().zip(Xs).zip(&commit.0) {
+            let lhs = G_i * self.0;
+            let rhs = A_i + challenge.nonce * X_i;
+            if !bool::from(lhs.ct_eq(&rhs)) {
+                verification_failed!(E, "schnorr proof equation doesn't hold");
+                return Err(InvalidProof);
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies non-interactive proof for multiple bases, challenge is re-derived via
+    /// [`Challenge::derive_multi_base`]
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[allow(non_snake_case)]
+    pub fn verify_multi_base_with_rederived_challenge<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        commit: &MultiBaseCommit<E>,
+        bases: &[Point<E>],
+        Xs: &[Point<E>],
+    ) -> Result<(), InvalidProof> {
+        let challenge = Challenge::derive_multi_base(transcript, bases, Xs, commit);
+        self.verify_multi_base(commit, &challenge, bases, Xs)
+    }
 }
 
 /// Generates and commits prover ephemeral secret
@@ -148,13 +437,92 @@ pub fn prover_commits_ephemeral_secret<E: Curve, R: RngCore + CryptoRng>(
     (ProverSecret { nonce: secret }, Commit(public))
 }
 
+/// Generates prover ephemeral secret and commits it to every base
+///
+/// Response is computed by [`prove`] as usual, see [multiple bases](self#multiple-bases)
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn prover_commits_ephemeral_secret_multi_base<E: Curve, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    bases: &[Point<E>],
+) -> (ProverSecret<E>, MultiBaseCommit<E>) {
+    let secret = SecretScalar::random(rng);
+    let commit = bases.iter().map(|base| base * &secret).collect();
+    (ProverSecret { nonce: secret }, MultiBaseCommit(commit))
+}
+
 /// Proves knowledge of `secret`
 pub fn prove<E: Curve>(
     committed_secret: &ProverSecret<E>,
     challenge: &Challenge<E>,
-    secret: impl AsRef<Scalar<E>>,
+    secret: impl ExposeSecret<E>,
 ) -> Proof<E> {
-    Proof(&committed_secret.nonce + challenge.nonce * secret.as_ref())
+    Proof(secret.expose_secret(|x| &committed_secret.nonce + challenge.nonce * x))
+}
+
+/// Prover that committed ephemeral secret and waits for a challenge
+///
+/// Ephemeral secret can't be extracted or cloned, [`respond`](Self::respond) consumes the state,
+/// so the secret is never reused.
+pub struct ProverAwaitingChallenge<E: Curve> {
+    secret: ProverSecret<E>,
+}
+
+impl<E: Curve> ProverAwaitingChallenge<E> {
+    /// Generates and commits ephemeral secret, commitment needs to be sent to verifier
+    pub fn commit<R: RngCore + CryptoRng>(rng: &mut R) -> (Self, Commit<E>) {
+        let (secret, commit) = prover_commits_ephemeral_secret(rng);
+        (Self { secret }, commit)
+    }
+
+    /// Responds to the challenge proving knowledge of `secret`
+    pub fn respond(self, challenge: &Challenge<E>, secret: impl ExposeSecret<E>) -> Proof<E> {
+        prove(&self.secret, challenge, secret)
+    }
+}
+
+/// Verifier that waits for prover commitment
+#[allow(non_snake_case)]
+pub struct VerifierAwaitingCommit<E: Curve> {
+    X: Point<E>,
+}
+
+impl<E: Curve> VerifierAwaitingCommit<E> {
+    /// Starts verification of knowledge of discrete log of $X$
+    #[allow(non_snake_case)]
+    pub fn new(X: Point<E>) -> Self {
+        Self { X }
+    }
+
+    /// Receives prover commitment and generates a challenge that needs to be sent to prover
+    pub fn receive_commit<R: RngCore>(
+        self,
+        rng: &mut R,
+        commit: Commit<E>,
+    ) -> (VerifierAwaitingProof<E>, Challenge<E>) {
+        let challenge = Challenge::generate(rng);
+        let verifier = VerifierAwaitingProof {
+            X: self.X,
+            commit,
+            challenge: challenge.clone(),
+        };
+        (verifier, challenge)
+    }
+}
+
+/// Verifier that sent a challenge and waits for a proof
+#[allow(non_snake_case)]
+pub struct VerifierAwaitingProof<E: Curve> {
+    X: Point<E>,
+    commit: Commit<E>,
+    challenge: Challenge<E>,
+}
+
+impl<E: Curve> VerifierAwaitingProof<E> {
+    /// Verifies the proof against commitment and challenge of this session
+    pub fn verify(self, proof: &Proof<E>) -> Result<(), InvalidProof> {
+        proof.verify(&self.commit, &self.challenge, &self.X)
+    }
 }
 
 /// Invalid proof error
@@ -169,3 +537,200 @@ impl core::fmt::Display for InvalidProof {
 
 #[cfg(feature = "std")]
 impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+#[allow(non_snake_case)]
+mod tests {
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{
+        prove, prover_commits_ephemeral_secret, Challenge, Commit, Proof, ProverAwaitingChallenge,
+        ProverSecret, VerifierAwaitingCommit,
+    };
+    use crate::transcript::Transcript;
+
+    #[test]
+    fn typestate_flow<E: Curve>() {
+        let mut rng = DevRng::new();
+        let x = SecretScalar::<E>::random(&mut rng);
+        let X = Point::generator() * &x;
+
+        let (prover, commit) = ProverAwaitingChallenge::<E>::commit(&mut rng);
+        let (verifier, challenge) = VerifierAwaitingCommit::new(X).receive_commit(&mut rng, commit);
+        let proof = prover.respond(&challenge, &x);
+        verifier.verify(&proof).unwrap();
+
+        // Proof with a wrong witness isn't accepted
+        let (prover, commit) = ProverAwaitingChallenge::<E>::commit(&mut rng);
+        let (verifier, challenge) = VerifierAwaitingCommit::new(X).receive_commit(&mut rng, commit);
+        let wrong_proof = prover.respond(&challenge, Scalar::<E>::random(&mut rng));
+        assert!(verifier.verify(&wrong_proof).is_err());
+
+        // Proof of another session isn't accepted
+        let (_prover, commit) = ProverAwaitingChallenge::<E>::commit(&mut rng);
+        let (verifier, _challenge) =
+            VerifierAwaitingCommit::new(X).receive_commit(&mut rng, commit);
+        assert!(verifier.verify(&proof).is_err());
+    }
+
+    #[test]
+    fn deterministic_ephemeral_secret<E: Curve>() {
+        let mut rng = DevRng::new();
+        let x = SecretScalar::<E>::random(&mut rng);
+        let X = Point::generator() * &x;
+
+        let derive = |statement: &[u8], aux: &[u8]| {
+            ProverSecret::<E>::derive_deterministic::<sha2::Sha256>(&x, statement, aux)
+        };
+        let (eph_secret, commit) = derive(b"statement", b"aux");
+        assert_eq!(Point::generator() * &eph_secret.nonce, commit.0);
+
+        // Same inputs yield the same ephemeral secret, any different input yields a different one
+        assert_eq!(derive(b"statement", b"aux").1 .0, commit.0);
+        assert_ne!(derive(b"statement", b"").1 .0, commit.0);
+        assert_ne!(derive(b"other statement", b"aux").1 .0, commit.0);
+        let other_x = SecretScalar::<E>::random(&mut rng);
+        let (_, other_commit) =
+            ProverSecret::<E>::derive_deterministic::<sha2::Sha256>(&other_x, b"statement", b"aux");
+        assert_ne!(other_commit.0, commit.0);
+
+        let challenge = Challenge::generate(&mut rng);
+        let proof = prove(&eph_secret, &challenge, &x);
+        proof.verify(&commit, &challenge, &X).unwrap();
+    }
+
+    #[test]
+    fn rederived_challenge<E: Curve>() {
+        let mut rng = DevRng::new();
+        let x = SecretScalar::<E>::random(&mut rng);
+        let X = Point::generator() * &x;
+
+        let (eph_secret, commit) = prover_commits_ephemeral_secret::<E, _>(&mut rng);
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"test");
+        let challenge = Challenge::derive(&mut transcript, &X, &commit);
+        let proof = prove(&eph_secret, &challenge, &x);
+
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"test");
+        proof
+            .verify_with_rederived_challenge(&mut transcript, &commit, &X)
+            .unwrap();
+
+        // Proof isn't accepted with a different transcript or statement
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"other");
+        assert!(proof
+            .verify_with_rederived_challenge(&mut transcript, &commit, &X)
+            .is_err());
+        let other_X = Point::generator() * Scalar::<E>::random(&mut rng);
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"test");
+        assert!(proof
+            .verify_with_rederived_challenge(&mut transcript, &commit, &other_X)
+            .is_err());
+
+        // Proof made for a challenge that isn't bound to the statement isn't accepted
+        let random_challenge = Challenge::generate(&mut rng);
+        let proof = prove(&eph_secret, &random_challenge, &x);
+        proof.verify(&commit, &random_challenge, &X).unwrap();
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"test");
+        assert!(proof
+            .verify_with_rederived_challenge(&mut transcript, &commit, &X)
+            .is_err());
+    }
+
+    #[test]
+    fn serialization_roundtrip<E: Curve>() {
+        let mut rng = DevRng::new();
+        let x = SecretScalar::<E>::random(&mut rng);
+        let X = Point::generator() * &x;
+
+        let (eph_secret, commit) = prover_commits_ephemeral_secret::<E, _>(&mut rng);
+        let challenge = Challenge::generate(&mut rng);
+        let proof = prove(&eph_secret, &challenge, &x);
+
+        let commit_bytes = commit.to_bytes();
+        let proof_bytes = proof.to_bytes();
+        assert_eq!(commit_bytes.len(), Commit::<E>::SERIALIZED_LEN);
+        assert_eq!(proof_bytes.len(), Proof::<E>::SERIALIZED_LEN);
+
+        let commit = Commit::<E>::from_bytes(&commit_bytes).unwrap();
+        let proof = Proof::<E>::from_bytes(&proof_bytes).unwrap();
+        proof.verify(&commit, &challenge, &X).unwrap();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn multi_base<E: Curve>() {
+        use alloc::vec::Vec;
+
+        use super::prover_commits_ephemeral_secret_multi_base;
+
+        let mut rng = DevRng::new();
+        let bases: Vec<Point<E>> =
+            core::iter::repeat_with(|| Point::generator() * Scalar::<E>::random(&mut rng))
+                .take(3)
+                .collect();
+        let x = SecretScalar::<E>::random(&mut rng);
+        let Xs: Vec<Point<E>> = bases.iter().map(|g_i| g_i * &x).collect();
+
+        let (eph_secret, commit) = prover_commits_ephemeral_secret_multi_base(&mut rng, &bases);
+        let challenge = Challenge::generate(&mut rng);
+        let proof = prove(&eph_secret, &challenge, &x);
+        proof
+            .verify_multi_base(&commit, &challenge, &bases, &Xs)
+            .unwrap();
+
+        // Statements with different discrete logs aren't accepted
+        let mut wrong_Xs = Xs.clone();
+        wrong_Xs[1] = bases[1] * Scalar::<E>::random(&mut rng);
+        assert!(proof
+            .verify_multi_base(&commit, &challenge, &bases, &wrong_Xs)
+            .is_err());
+        // Mismatched lengths aren't accepted
+        assert!(proof
+            .verify_multi_base(&commit, &challenge, &bases[1..], &Xs[1..])
+            .is_err());
+
+        // Non-interactive proof
+        let (eph_secret, commit) = prover_commits_ephemeral_secret_multi_base(&mut rng, &bases);
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"test");
+        let challenge = Challenge::derive_multi_base(&mut transcript, &bases, &Xs, &commit);
+        let proof = prove(&eph_secret, &challenge, &x);
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"test");
+        proof
+            .verify_multi_base_with_rederived_challenge(&mut transcript, &commit, &bases, &Xs)
+            .unwrap();
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"test");
+        assert!(proof
+            .verify_multi_base_with_rederived_challenge(&mut transcript, &commit, &bases, &wrong_Xs)
+            .is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn multi_base_rejects_empty_bases<E: Curve>() {
+        use alloc::vec::Vec;
+
+        use super::MultiBaseCommit;
+
+        let mut rng = DevRng::new();
+        // Arbitrary proof would be accepted if there were no equations to check
+        let proof = Proof(Scalar::<E>::random(&mut rng));
+        let commit = MultiBaseCommit(Vec::new());
+
+        let challenge = Challenge::generate(&mut rng);
+        assert!(proof
+            .verify_multi_base(&commit, &challenge, &[], &[])
+            .is_err());
+
+        let mut transcript = Transcript::<sha2::Sha256>::new(b"test");
+        assert!(proof
+            .verify_multi_base_with_rederived_challenge(&mut transcript, &commit, &[], &[])
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
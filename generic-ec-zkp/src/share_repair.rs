@@ -0,0 +1,595 @@
+//! Repair of a lost key share, and enrollment of a new party
+//!
+//! Party $r$ who lost its secret share (or a new party joining the committee) obtains share
+//! $x_r = f(r + 1)$ of the existing sharing from a quorum of helpers, without reconstructing
+//! the key. Every helper only learns blinded values, and the recovered party only learns its
+//! own share. Shared public key and shares of other parties stay the same.
+//!
+//! Repair is defined by [`Plan`] agreed by everyone: public data of the [key share](KeyShare),
+//! a set of at least $t$ helpers, and index of the party being repaired.
+//!
+//! 1. Each helper [blinds](Plan::blind) its share: publishes [`Contribution`] and privately sends
+//!    a piece to every helper.
+//! 2. Each helper [verifies](Plan::verify_piece) received pieces and [sums](Plan::sum) them up,
+//!    then privately sends the sum to the party being repaired.
+//! 3. The party being repaired [recovers](Plan::recover) its key share from the sums.
+//!
+//! All private messages must be sent over private and [authenticated](crate::authenticated_msg)
+//! channels. Public contributions let recipients identify the helper who sent an invalid piece
+//! or sum.
+//!
+//! When the repaired party is a new one ($r = n$), other parties extend their public shares
+//! with [`Plan::repaired_public_share`].
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{key_share::KeyShare, party_index::PartyIndex, polynomial::Polynomial, share_repair};
+//! # let mut rng = rand::rngs::OsRng;
+//! # let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! # let f = Polynomial::sample_with_const_term(&mut rng, 1, secret_key.clone());
+//! # let shares: Vec<_> = (0..3).map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar())).collect();
+//! # let key_shares: Vec<_> = (0..3).map(|i| KeyShare {
+//! #     i: PartyIndex(i),
+//! #     t: 2,
+//! #     x: shares[usize::from(i)].clone(),
+//! #     shared_public_key: Point::generator() * &secret_key,
+//! #     public_shares: shares.iter().map(|x_j| Point::generator() * x_j).collect(),
+//! # }).collect();
+//!
+//! // Key is shared 2-out-of-3, party 1 lost its share, parties 0 and 2 help to repair it
+//! let plan = share_repair::Plan::new(
+//!     2,
+//!     key_shares[0].shared_public_key,
+//!     key_shares[0].public_shares.clone(),
+//!     vec![PartyIndex(0), PartyIndex(2)],
+//!     PartyIndex(1),
+//! )?;
+//!
+//! let mut contributions = vec![];
+//! let mut pieces = vec![];
+//! for helper in &plan.helpers {
+//!     let (contribution, helper_pieces) = plan.blind(&mut rng, &key_shares[usize::from(*helper)])?;
+//!     contributions.push(contribution);
+//!     pieces.push(helper_pieces);
+//! }
+//!
+//! // Every helper sums up the pieces it received
+//! let mut sums = vec![];
+//! for (k, helper) in plan.helpers.iter().enumerate() {
+//!     let received: Vec<_> = pieces.iter().map(|pieces| pieces[k].clone()).collect();
+//!     sums.push(plan.sum(*helper, &contributions, &received)?);
+//! }
+//!
+//! // Party 1 recovers its key share
+//! let repaired = plan.recover(&contributions, &sums)?;
+//! assert_eq!(
+//!     Point::generator() * &repaired.x,
+//!     Point::generator() * &key_shares[1].x,
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Helpers $H$, $|H| \ge t$, hold shares $x_i$ such as $x_r = \sum_{i \in H} \lambda_i x_i$,
+//! where $\lambda_i$ are Lagrange coefficients of $H$ at point $r + 1$.
+//!
+//! * Blinding: helper $i$ splits $\lambda_i x_i$ into random pieces $\delta_{i,k}$, $k \in H$,
+//!   $\sum_k \delta_{i,k} = \lambda_i x_i$, publishes $D_{i,k} = \delta_{i,k} \cdot G$ and sends
+//!   $\delta_{i,k}$ to helper $k$
+//! * Verification: $\sum_k D_{i,k} = \lambda_i X_i$, and $\delta_{i,k} \cdot G = D_{i,k}$
+//! * Summing: helper $k$ sends $\sigma_k = \sum_i \delta_{i,k}$ to party $r$
+//! * Recovery: $\sigma_k \cdot G = \sum_i D_{i,k}$, and $x_r = \sum_k \sigma_k$
+
+use alloc::vec::Vec;
+
+use generic_ec::{Curve, NonZero, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{key_share::KeyShare, party_index::PartyIndex, polynomial::LagrangeBasis};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Repair plan known to all participants
+#[derive(Clone, Debug)]
+pub struct Plan<E: Curve> {
+    /// Threshold $t$
+    pub t: u16,
+    /// Shared public key $X$
+    pub shared_public_key: Point<E>,
+    /// Public shares of all parties
+    pub public_shares: Vec<Point<E>>,
+    /// Parties helping to repair the share
+    pub helpers: Vec<PartyIndex>,
+    /// Party $r$ whose share is being repaired
+    pub repaired: PartyIndex,
+    /// Lagrange coefficients of the helpers at point $r + 1$
+    lambdas: Vec<NonZero<Scalar<E>>>,
+}
+
+/// Public part of helper's contribution
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Contribution<E: Curve> {
+    /// Index of the helper
+    pub helper: PartyIndex,
+    /// Commitments $D_{i,k}$ to pieces sent to every helper $k$, in the order of
+    /// [`Plan::helpers`]
+    pub commitments: Vec<Point<E>>,
+}
+
+impl<E: Curve> Plan<E> {
+    /// Constructs a repair plan
+    ///
+    /// `helpers` must be distinct parties other than the repaired one, and there must be at least
+    /// $t$ of them. Repaired party is either one of existing parties, or a new party with index
+    /// $n$. Returns error if any of these requirements is not met.
+    pub fn new(
+        t: u16,
+        shared_public_key: Point<E>,
+        public_shares: Vec<Point<E>>,
+        helpers: Vec<PartyIndex>,
+        repaired: PartyIndex,
+    ) -> Result<Self, InvalidPlan> {
+        let n = public_shares.len();
+        if t == 0
+            || helpers.len() < usize::from(t)
+            || usize::from(repaired) > n
+            || helpers.contains(&repaired)
+            || helpers.iter().any(|i| usize::from(*i) >= n)
+        {
+            return Err(InvalidPlan);
+        }
+        let xs: Vec<NonZero<Scalar<E>>> = helpers
+            .iter()
+            .map(|i| i.share_index().to_scalar())
+            .collect();
+        let x_r = repaired.share_index().to_scalar::<E>().into_inner();
+        let lambdas = LagrangeBasis::new(x_r, &xs)
+            .ok_or(InvalidPlan)?
+            .coefficients()
+            .to_vec();
+        Ok(Self {
+            t,
+            shared_public_key,
+            public_shares,
+            helpers,
+            repaired,
+            lambdas,
+        })
+    }
+
+    /// Public share $X_r$ of the repaired party
+    ///
+    /// Interpolated from public shares of the helpers, so it's available even if the repaired
+    /// party is a new one.
+    pub fn repaired_public_share(&self) -> Point<E> {
+        Scalar::multiscalar_mul(
+            self.lambdas.iter().map(|lambda| *lambda.as_ref()).zip(
+                self.helpers
+                    .iter()
+                    .map(|i| self.public_shares[usize::from(*i)]),
+            ),
+        )
+    }
+
+    /// Blinds the key share of the helper
+    ///
+    /// Returns public contribution and secret pieces, `pieces[k]` must be privately sent to
+    /// helper `self.helpers[k]`. Returns error if the party is not a helper, or the key share
+    /// doesn't match the plan.
+    pub fn blind<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        key_share: &KeyShare<E>,
+    ) -> Result<(Contribution<E>, Vec<SecretScalar<E>>), InvalidInput> {
+        if key_share.t != self.t
+            || key_share.shared_public_key != self.shared_public_key
+            || key_share.public_shares != self.public_shares
+        {
+            return Err(InvalidInput);
+        }
+        let lambda = self.lambda(key_share.i).ok_or(InvalidInput)?;
+
+        let mut pieces = (1..self.helpers.len())
+            .map(|_| SecretScalar::random(rng))
+            .collect::<Vec<_>>();
+        let mut last = *lambda.as_ref() * &key_share.x - pieces.iter().sum::<Scalar<E>>();
+        pieces.push(SecretScalar::new(&mut last));
+
+        let contribution = Contribution {
+            helper: key_share.i,
+            commitments: pieces
+                .iter()
+                .map(|piece| Point::generator() * piece)
+                .collect(),
+        };
+        Ok((contribution, pieces))
+    }
+
+    /// Verifies public contribution
+    ///
+    /// Checks that the contributor is a helper, and the pieces sum up to its blinded share.
+    pub fn verify_contribution(&self, contribution: &Contribution<E>) -> Result<(), RepairError> {
+        let helper = contribution.helper;
+        let invalid = RepairError::InvalidContribution { helper };
+        let (Some(lambda), Some(public_share)) = (
+            self.lambda(helper),
+            self.public_shares.get(usize::from(helper)),
+        ) else {
+            return Err(invalid);
+        };
+        if contribution.commitments.len() != self.helpers.len()
+            || contribution.commitments.iter().sum::<Point<E>>() != public_share * lambda
+        {
+            return Err(invalid);
+        }
+        Ok(())
+    }
+
+    /// Verifies public contribution and the piece received from it by helper `k`
+    pub fn verify_piece(
+        &self,
+        contribution: &Contribution<E>,
+        k: PartyIndex,
+        piece: &SecretScalar<E>,
+    ) -> Result<(), RepairError> {
+        self.verify_contribution(contribution)?;
+        let position = self.position(k).ok_or(RepairError::NotHelper)?;
+        if Point::generator() * piece != contribution.commitments[position] {
+            return Err(RepairError::InvalidPiece {
+                helper: contribution.helper,
+            });
+        }
+        Ok(())
+    }
+
+    /// Sums up the pieces received by helper `k`
+    ///
+    /// `contributions[m]` and `pieces[m]` must come from helper `self.helpers[m]`. Every
+    /// contribution and piece is [verified](Self::verify_piece). Returned sum must be privately
+    /// sent to the repaired party.
+    pub fn sum(
+        &self,
+        k: PartyIndex,
+        contributions: &[Contribution<E>],
+        pieces: &[SecretScalar<E>],
+    ) -> Result<SecretScalar<E>, RepairError> {
+        self.check_contributions(contributions)?;
+        if pieces.len() != self.helpers.len() {
+            return Err(RepairError::MissingContributions);
+        }
+        for (contribution, piece) in contributions.iter().zip(pieces) {
+            self.verify_piece(contribution, k, piece)?;
+        }
+        let mut sum = pieces.iter().sum::<Scalar<E>>();
+        Ok(SecretScalar::new(&mut sum))
+    }
+
+    /// Recovers key share of the repaired party
+    ///
+    /// `contributions[m]` and `sums[m]` must come from helper `self.helpers[m]`. Every
+    /// contribution and sum is verified. If the repaired party is a new one, its public share
+    /// is appended to public shares.
+    pub fn recover(
+        &self,
+        contributions: &[Contribution<E>],
+        sums: &[SecretScalar<E>],
+    ) -> Result<KeyShare<E>, RepairError> {
+        self.check_contributions(contributions)?;
+        if sums.len() != self.helpers.len() {
+            return Err(RepairError::MissingContributions);
+        }
+        for contribution in contributions {
+            self.verify_contribution(contribution)?;
+        }
+        for (k, (helper, sum)) in self.helpers.iter().zip(sums).enumerate() {
+            let expected = contributions
+                .iter()
+                .map(|contribution| contribution.commitments[k])
+                .sum::<Point<E>>();
+            if Point::generator() * sum != expected {
+                return Err(RepairError::InvalidSum { helper: *helper });
+            }
+        }
+
+        let mut x = sums.iter().sum::<Scalar<E>>();
+        let x = SecretScalar::new(&mut x);
+        let mut public_shares = self.public_shares.clone();
+        if usize::from(self.repaired) == public_shares.len() {
+            public_shares.push(self.repaired_public_share());
+        }
+        if Point::generator() * &x != public_shares[usize::from(self.repaired)] {
+            return Err(RepairError::PublicShareMismatch);
+        }
+        Ok(KeyShare {
+            i: self.repaired,
+            t: self.t,
+            x,
+            shared_public_key: self.shared_public_key,
+            public_shares,
+        })
+    }
+
+    fn check_contributions(&self, contributions: &[Contribution<E>]) -> Result<(), RepairError> {
+        if contributions.len() != self.helpers.len()
+            || self
+                .helpers
+                .iter()
+                .zip(contributions)
+                .any(|(helper, contribution)| contribution.helper != *helper)
+        {
+            return Err(RepairError::MissingContributions);
+        }
+        Ok(())
+    }
+
+    fn position(&self, helper: PartyIndex) -> Option<usize> {
+        self.helpers.iter().position(|i| *i == helper)
+    }
+
+    fn lambda(&self, helper: PartyIndex) -> Option<NonZero<Scalar<E>>> {
+        self.lambdas.get(self.position(helper)?).copied()
+    }
+}
+
+/// Repair plan is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidPlan;
+
+impl core::fmt::Display for InvalidPlan {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid helpers or repaired party")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidPlan {}
+
+/// Key share can't be blinded according to the plan
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("party is not a helper or key share doesn't match the plan")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Repair error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairError {
+    /// Public contribution is invalid
+    InvalidContribution {
+        /// Index of the helper
+        helper: PartyIndex,
+    },
+    /// Secret piece doesn't match public contribution
+    InvalidPiece {
+        /// Index of the helper
+        helper: PartyIndex,
+    },
+    /// Sum of pieces doesn't match public contributions
+    InvalidSum {
+        /// Index of the helper
+        helper: PartyIndex,
+    },
+    /// Recipient of the piece is not a helper
+    NotHelper,
+    /// Contributions don't correspond to the helpers of the plan
+    MissingContributions,
+    /// Recovered share doesn't match public share of the repaired party
+    PublicShareMismatch,
+}
+
+impl core::fmt::Display for RepairError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidContribution { helper } => {
+                write!(f, "invalid contribution from party {helper}")
+            }
+            Self::InvalidPiece { helper } => write!(f, "invalid piece from party {helper}"),
+            Self::InvalidSum { helper } => write!(f, "invalid sum from party {helper}"),
+            Self::NotHelper => f.write_str("recipient is not a helper"),
+            Self::MissingContributions => {
+                f.write_str("contributions don't match helpers of the plan")
+            }
+            Self::PublicShareMismatch => {
+                f.write_str("recovered share doesn't match public share of the party")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RepairError {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{Contribution, Plan, RepairError};
+    use crate::{key_share::KeyShare, party_index::PartyIndex, polynomial::Polynomial};
+
+    fn key_shares<E: Curve>(
+        rng: &mut DevRng,
+        t: u16,
+        n: u16,
+    ) -> (Polynomial<SecretScalar<E>>, Vec<KeyShare<E>>) {
+        let secret_key = SecretScalar::<E>::random(rng);
+        let f = Polynomial::sample_with_const_term(rng, usize::from(t) - 1, secret_key.clone());
+        let shares: Vec<_> = (0..n)
+            .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+            .collect();
+        let public_shares: Vec<_> = shares.iter().map(|x| Point::generator() * x).collect();
+        let key_shares = (0..n)
+            .zip(shares)
+            .map(|(i, x)| KeyShare {
+                i: PartyIndex(i),
+                t,
+                x,
+                shared_public_key: Point::generator() * &secret_key,
+                public_shares: public_shares.clone(),
+            })
+            .collect();
+        (f, key_shares)
+    }
+
+    fn run<E: Curve>(
+        rng: &mut DevRng,
+        plan: &Plan<E>,
+        key_shares: &[KeyShare<E>],
+    ) -> (Vec<Contribution<E>>, Vec<Vec<SecretScalar<E>>>) {
+        plan.helpers
+            .iter()
+            .map(|helper| plan.blind(rng, &key_shares[usize::from(*helper)]).unwrap())
+            .unzip()
+    }
+
+    fn received<E: Curve>(pieces: &[Vec<SecretScalar<E>>], k: usize) -> Vec<SecretScalar<E>> {
+        pieces.iter().map(|pieces| pieces[k].clone()).collect()
+    }
+
+    #[test]
+    fn repair<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (_, key_shares) = key_shares::<E>(&mut rng, 3, 5);
+        let helpers = alloc::vec![PartyIndex(4), PartyIndex(0), PartyIndex(2)];
+        let plan = Plan::new(
+            3,
+            key_shares[0].shared_public_key,
+            key_shares[0].public_shares.clone(),
+            helpers,
+            PartyIndex(1),
+        )
+        .unwrap();
+        assert_eq!(plan.repaired_public_share(), key_shares[1].public_shares[1]);
+
+        let (contributions, pieces) = run(&mut rng, &plan, &key_shares);
+        let sums: Vec<_> = plan
+            .helpers
+            .iter()
+            .enumerate()
+            .map(|(k, helper)| {
+                plan.sum(*helper, &contributions, &received(&pieces, k))
+                    .unwrap()
+            })
+            .collect();
+        let repaired = plan.recover(&contributions, &sums).unwrap();
+        repaired.verify_consistency().unwrap();
+        assert_eq!(
+            Point::generator() * &repaired.x,
+            Point::generator() * &key_shares[1].x
+        );
+        assert_eq!(repaired.public_shares, key_shares[1].public_shares);
+
+        // Invalid sum is attributed to the helper
+        let mut invalid_sums = sums.clone();
+        invalid_sums[1] = SecretScalar::random(&mut rng);
+        assert_eq!(
+            plan.recover(&contributions, &invalid_sums).unwrap_err(),
+            RepairError::InvalidSum {
+                helper: PartyIndex(0)
+            }
+        );
+
+        // Invalid piece is attributed to its sender
+        let mut invalid_pieces = received(&pieces, 0);
+        invalid_pieces[2] = SecretScalar::random(&mut rng);
+        assert_eq!(
+            plan.sum(PartyIndex(4), &contributions, &invalid_pieces)
+                .unwrap_err(),
+            RepairError::InvalidPiece {
+                helper: PartyIndex(2)
+            }
+        );
+
+        // Invalid contribution is detected by anyone
+        let mut invalid_contribution = contributions[1].clone();
+        invalid_contribution.commitments[0] += Point::generator();
+        assert_eq!(
+            plan.verify_contribution(&invalid_contribution),
+            Err(RepairError::InvalidContribution {
+                helper: PartyIndex(0)
+            })
+        );
+        assert_eq!(
+            plan.recover(&contributions[1..], &sums[1..]).unwrap_err(),
+            RepairError::MissingContributions
+        );
+    }
+
+    #[test]
+    fn enrollment<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (f, key_shares) = key_shares::<E>(&mut rng, 2, 3);
+        let plan = Plan::new(
+            2,
+            key_shares[0].shared_public_key,
+            key_shares[0].public_shares.clone(),
+            alloc::vec![PartyIndex(0), PartyIndex(2)],
+            PartyIndex(3),
+        )
+        .unwrap();
+
+        let (contributions, pieces) = run(&mut rng, &plan, &key_shares);
+        let sums: Vec<_> = plan
+            .helpers
+            .iter()
+            .enumerate()
+            .map(|(k, helper)| {
+                plan.sum(*helper, &contributions, &received(&pieces, k))
+                    .unwrap()
+            })
+            .collect();
+        let enrolled = plan.recover(&contributions, &sums).unwrap();
+        enrolled.verify_consistency().unwrap();
+        assert_eq!(enrolled.n(), 4);
+        assert_eq!(
+            Point::generator() * &enrolled.x,
+            Point::generator() * f.eval_ct(&PartyIndex(3).share_index().to_scalar())
+        );
+    }
+
+    #[test]
+    fn invalid_plan<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (_, key_shares) = key_shares::<E>(&mut rng, 2, 3);
+        let new_plan = |helpers: &[u16], repaired| {
+            Plan::new(
+                2,
+                key_shares[0].shared_public_key,
+                key_shares[0].public_shares.clone(),
+                helpers.iter().copied().map(PartyIndex).collect(),
+                PartyIndex(repaired),
+            )
+        };
+        assert!(new_plan(&[0, 2], 1).is_ok());
+        // Not enough helpers, repaired party is a helper, out of range, or duplicated helpers
+        assert!(new_plan(&[0], 1).is_err());
+        assert!(new_plan(&[0, 1], 1).is_err());
+        assert!(new_plan(&[0, 2], 4).is_err());
+        assert!(new_plan(&[0, 3], 1).is_err());
+        assert!(new_plan(&[0, 0], 1).is_err());
+
+        // Only helpers can blind their shares
+        let plan = new_plan(&[0, 2], 1).unwrap();
+        assert!(plan.blind(&mut rng, &key_shares[1]).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
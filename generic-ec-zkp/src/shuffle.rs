@@ -0,0 +1,862 @@
+//! Proof of shuffle of ElGamal ciphertexts (verifiable mixing)
+//!
+//! Shuffle takes a list of [ElGamal](crate::elgamal) ciphertexts $e_1, \dots, e_N$, permutes them
+//! and re-randomizes every ciphertext, so that outputs $e'_1, \dots, e'_N$ encrypt the same
+//! plaintexts, but nobody can link an output to the input it came from. Proof of shuffle shows
+//! that outputs are indeed a permutation of re-randomized inputs without revealing the
+//! permutation. Chain of shuffles performed by different parties (mixnet) is used in voting: votes
+//! are decrypted only after being mixed, so decrypted votes can't be traced back to voters.
+//!
+//! The proof is the shuffle argument of [Bayer and Groth]: prover commits to the permutation and
+//! to the challenge raised to the permuted powers, proves with the product argument that
+//! committed values are consistent with a permutation, and proves with the multi-exponentiation
+//! argument that outputs are the inputs permuted by it. The argument is instantiated with
+//! a single row ($m = 1$ in terms of the paper): product argument is the single value product
+//! argument, and multi-exponentiation argument is a linear relation proven with
+//! [proof bundle](crate::proof_bundle). So the proof size is linear: $8$ points and $3N + 4$
+//! scalars. Sublinear instantiation ($m > 1$) additionally requires Hadamard product and zero
+//! arguments which are not implemented.
+//!
+//! Neither [`shuffle`] nor [`prove`] branch on or index memory by the secret permutation, which
+//! costs $O(N^2)$ conditional selections of ciphertexts and integers.
+//!
+//! [Bayer and Groth]: https://www.iacr.org/archive/eurocrypt2012/72370263/72370263.pdf
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{elgamal, shuffle, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-shuffle-v0.1.0");
+//! let params = shuffle::Params::<Secp256k1>::derive(TAG, 4)?;
+//! let (dk, ek) = elgamal::keygen(&mut rng);
+//!
+//! // Encrypted votes
+//! let inputs: Vec<_> = (1..=4)
+//!     .map(|vote| ek.encrypt_scalar(&Scalar::from(vote), &Scalar::random(&mut rng)))
+//!     .collect();
+//!
+//! // Mixer shuffles the votes and proves correctness of the shuffle
+//! let (outputs, witness) = shuffle::shuffle(&mut rng, &ek, &inputs);
+//! let transcript = || Transcript::<sha2::Sha256>::new(b"MYAPP-mix-round-1");
+//! let proof = shuffle::prove(&mut rng, transcript(), &params, &ek, &inputs, &outputs, &witness)?;
+//!
+//! // Anyone can verify the shuffle
+//! proof.verify(&mut transcript(), &mut rng, &params, &ek, &inputs, &outputs)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Outputs are $e'_i = e_{\pi(i)} + \mathrm{Enc}(0; \rho_i)$ where $\mathrm{Enc}(0; \rho) = (\rho \cdot G, \rho \cdot Y)$.
+//! Parameters are generators $G_1, \dots, G_N, H$ with unknown discrete logs, vectors are
+//! committed as $\mathrm{com}(\mathbf{v}; r) = \sum_i v_i \cdot G_i + r \cdot H$. Inputs and
+//! permutation are indexed from $1$.
+//!
+//! 1. Prover commits to the permutation $c_A = \mathrm{com}(\mathbf{a}; r_A)$, $a_i = \pi(i)$.
+//!    Statement and $c_A$ are appended to the transcript, challenge $x$ is derived from it.
+//! 2. Prover commits to $c_B = \mathrm{com}(\mathbf{b}; r_B)$, $b_i = x^{\pi(i)}$. $c_B$ is
+//!    appended to the transcript, challenges $y, z$ are derived from it.
+//! 3. Product argument: vector $v_i = y a_i + b_i - z$ is committed in
+//!    $c_v = y \cdot c_A + c_B - \mathrm{com}(z, \dots, z; 0)$ with randomness $t = y r_A + r_B$.
+//!    Prover shows that $\prod_i v_i = \prod_{j=1}^N (y j + x^j - z)$, which implies that
+//!    $\mathbf a$ is a permutation of $1, \dots, N$ and $b_i = x^{a_i}$:
+//!    * Prover computes partial products $p_k = \prod_{i \le k} v_i$, samples $\mathbf{d}, r_d,
+//!      s_1, s_2$ and $\delta_2, \dots, \delta_{N-1}$, sets $\delta_1 = d_1, \delta_N = 0$, and
+//!      sends $c_d = \mathrm{com}(\mathbf d; r_d)$,
+//!      $c_\delta = \mathrm{com}((-\delta_k d_{k+1})_{k < N}; s_1)$,
+//!      $c_\Delta = \mathrm{com}((\delta_{k+1} - v_{k+1} \delta_k - p_k d_{k+1})_{k < N}; s_2)$
+//!    * Challenge $w$ is derived from the transcript
+//!    * Prover responds with $\tilde v_i = w v_i + d_i$, $\tilde p_i = w p_i + \delta_i$,
+//!      $\tilde r = w t + r_d$, $\tilde s = w s_2 + s_1$
+//!    * Verifier checks that $w \cdot c_v + c_d = \mathrm{com}(\tilde{\mathbf v}; \tilde r)$,
+//!      $w \cdot c_\Delta + c_\delta = \mathrm{com}((w \tilde p_{k+1} - \tilde p_k \tilde v_{k+1})_{k < N}; \tilde s)$,
+//!      $\tilde p_1 = \tilde v_1$, and $\tilde p_N = w \prod_{j=1}^N (y j + x^j - z)$
+//! 4. Multi-exponentiation argument: prover proves knowledge of $\mathbf b, r_B, \rho$ such that
+//!    $c_B = \mathrm{com}(\mathbf b; r_B)$ and
+//!    $\sum_i b_i \cdot e'_i - \mathrm{Enc}(0; \rho) = \sum_j x^j \cdot e_j$, i.e. outputs are
+//!    inputs permuted by the committed permutation. Honest prover has $\rho = \sum_i b_i \rho_i$.
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{
+    errors::HashError,
+    hash_to_curve::{FromHash, Tag},
+    Curve, Point, Scalar, SecretScalar,
+};
+use rand_core::{CryptoRng, RngCore};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::{
+    elgamal::{Ciphertext, EncryptionKey},
+    proof_bundle::{BundleProver, LinearRelation, ProofBundle},
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Parameters of the proof: generators $G_1, \dots, G_n, H$
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Params<E: Curve> {
+    /// Generators $G_1, \dots, G_n$ which vectors are committed at
+    pub g: Vec<Point<E>>,
+    /// Generator $H$ which blinding is multiplied at
+    pub h: Point<E>,
+}
+
+impl<E: Curve> Params<E> {
+    /// Derives parameters supporting shuffles of up to `n` ciphertexts
+    ///
+    /// All generators are derived from the `tag` via hash to curve. Parameters derived for `n`
+    /// are a prefix of parameters derived for any greater `n`.
+    pub fn derive(tag: Tag, n: usize) -> Result<Self, HashError>
+    where
+        Point<E>: FromHash,
+    {
+        Ok(Self {
+            g: (0..n as u64)
+                .map(|i| Point::hash_concat(tag, &[b"shuffle/g", &i.to_be_bytes()]))
+                .collect::<Result<_, _>>()?,
+            h: Point::hash_concat(tag, &[b"shuffle/h"])?,
+        })
+    }
+
+    /// Maximal amount of ciphertexts in a shuffle supported by these parameters
+    pub fn len(&self) -> usize {
+        self.g.len()
+    }
+
+    /// Indicates whether parameters are empty
+    pub fn is_empty(&self) -> bool {
+        self.g.is_empty()
+    }
+
+    /// Commits to public `values`
+    ///
+    /// Lengths of `values` must be already checked to not exceed [`Params::len`].
+    fn commit(&self, values: &[Scalar<E>], blinding: &Scalar<E>) -> Point<E> {
+        Scalar::multiscalar_mul(
+            values
+                .iter()
+                .zip(&self.g)
+                .chain(core::iter::once((blinding, &self.h))),
+        )
+    }
+
+    /// Commits to secret `values` in constant time
+    ///
+    /// Lengths of `values` must be already checked to not exceed [`Params::len`].
+    fn commit_secret(&self, values: &[SecretScalar<E>], blinding: &SecretScalar<E>) -> Point<E> {
+        // Multiscalar multiplication is not constant time, so every term is computed separately
+        values
+            .iter()
+            .zip(&self.g)
+            .map(|(v, g)| g * v)
+            .sum::<Point<E>>()
+            + self.h * blinding
+    }
+}
+
+/// Secret permutation and randomness used to shuffle the ciphertexts
+///
+/// Output $i$ is `inputs[permutation[i]]` re-randomized with `randomness[i]`.
+///
+/// `Debug` output redacts the permutation, it can be revealed by enabling `debug-secrets`
+/// feature.
+#[derive(Clone)]
+pub struct Witness<E: Curve> {
+    /// Permutation $\pi$
+    pub permutation: Vec<usize>,
+    /// Re-randomization $\rho_i$ of every output
+    pub randomness: Vec<SecretScalar<E>>,
+}
+
+impl<E: Curve> core::fmt::Debug for Witness<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut s = f.debug_struct("Witness");
+        #[cfg(feature = "debug-secrets")]
+        s.field("permutation", &self.permutation);
+        #[cfg(not(feature = "debug-secrets"))]
+        s.field("permutation", &format_args!("[REDACTED]"));
+        s.field("randomness", &self.randomness).finish()
+    }
+}
+
+/// Proof of shuffle
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct ShuffleProof<E: Curve> {
+    /// Commitment to the permutation $c_A$
+    pub permutation_commitment: Point<E>,
+    /// Commitment to permuted powers of the challenge $c_B$
+    pub powers_commitment: Point<E>,
+    /// Product argument
+    pub product: ProductProof<E>,
+    /// Multi-exponentiation argument
+    pub multi_exp: ProofBundle<E>,
+}
+
+/// Single value product argument: committed $\mathbf v$ has product $\prod_i v_i = P$
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct ProductProof<E: Curve> {
+    /// Commitment $c_d$
+    pub c_d: Point<E>,
+    /// Commitment $c_\delta$
+    pub c_delta: Point<E>,
+    /// Commitment $c_\Delta$
+    pub c_big_delta: Point<E>,
+    /// Responses $\tilde v_i = w v_i + d_i$
+    pub v: Vec<Scalar<E>>,
+    /// Responses $\tilde p_i = w p_i + \delta_i$
+    pub p: Vec<Scalar<E>>,
+    /// Response $\tilde r = w t + r_d$
+    pub r: Scalar<E>,
+    /// Response $\tilde s = w s_2 + s_1$
+    pub s: Scalar<E>,
+}
+
+/// Shuffles the ciphertexts with random permutation and re-randomizes them
+///
+/// Returns the shuffled ciphertexts and the witness needed to [prove] the shuffle
+pub fn shuffle<E: Curve, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    ek: &EncryptionKey<E>,
+    inputs: &[Ciphertext<E>],
+) -> (Vec<Ciphertext<E>>, Witness<E>) {
+    // Fisher-Yates shuffle. Swapped position is secret, so every preceding element is
+    // conditionally swapped.
+    let mut permutation: Vec<u64> = (0..inputs.len() as u64).collect();
+    for i in (1..permutation.len()).rev() {
+        let j = random_index(rng, i + 1) as u64;
+        let (head, tail) = permutation.split_at_mut(i);
+        for (k, item) in (0u64..).zip(head) {
+            u64::conditional_swap(item, &mut tail[0], k.ct_eq(&j));
+        }
+    }
+    let randomness: Vec<SecretScalar<E>> = (0..inputs.len())
+        .map(|_| SecretScalar::random(rng))
+        .collect();
+    let outputs = permutation
+        .iter()
+        .zip(&randomness)
+        .map(|(j, r)| select(inputs, *j) + ek.encrypt_scalar(&Scalar::zero(), r))
+        .collect();
+    (
+        outputs,
+        Witness {
+            permutation: permutation.into_iter().map(|j| j as usize).collect(),
+            randomness,
+        },
+    )
+}
+
+/// Returns `ciphertexts[index]` without accessing memory at secret `index`
+fn select<E: Curve>(ciphertexts: &[Ciphertext<E>], index: u64) -> Ciphertext<E> {
+    let mut selected = Ciphertext {
+        c1: Point::zero(),
+        c2: Point::zero(),
+    };
+    for (k, e) in (0u64..).zip(ciphertexts) {
+        let choice = k.ct_eq(&index);
+        selected.c1.conditional_assign(&e.c1, choice);
+        selected.c2.conditional_assign(&e.c2, choice);
+    }
+    selected
+}
+
+/// Samples uniformly random index in range $[0, n)$, $n$ must be non-zero
+fn random_index<R: RngCore>(rng: &mut R, n: usize) -> usize {
+    let n = n as u64;
+    // Amount of values in `0..=zone` is a multiple of `n`, values above it are rejected to
+    // avoid bias
+    let zone = u64::MAX - (u64::MAX - n + 1) % n;
+    loop {
+        let x = rng.next_u64();
+        if x <= zone {
+            return (x % n) as usize;
+        }
+    }
+}
+
+/// Proves that `outputs` are shuffled `inputs`
+///
+/// Encryption key, ciphertexts, and proof commitments are appended to the `transcript`.
+///
+/// Returns error if amount of ciphertexts is zero or exceeds [`Params::len`], or `witness` isn't
+/// a valid permutation that maps `inputs` to `outputs`.
+pub fn prove<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    mut transcript: Transcript<D>,
+    params: &Params<E>,
+    ek: &EncryptionKey<E>,
+    inputs: &[Ciphertext<E>],
+    outputs: &[Ciphertext<E>],
+    witness: &Witness<E>,
+) -> Result<ShuffleProof<E>, InvalidInput> {
+    let n = inputs.len();
+    if n == 0
+        || n > params.len()
+        || outputs.len() != n
+        || witness.permutation.len() != n
+        || witness.randomness.len() != n
+    {
+        return Err(InvalidInput);
+    }
+    if !bool::from(is_permutation(&witness.permutation)) {
+        return Err(InvalidInput);
+    }
+
+    // Commit to the permutation
+    let a: Vec<SecretScalar<E>> = witness
+        .permutation
+        .iter()
+        .map(|&j| secret(Scalar::from(j as u64 + 1)))
+        .collect();
+    let r_a = SecretScalar::random(rng);
+    let permutation_commitment = params.commit_secret(&a, &r_a);
+    let x = challenge_x(
+        &mut transcript,
+        ek,
+        inputs,
+        outputs,
+        &permutation_commitment,
+    );
+
+    // Commit to permuted powers of `x`
+    let x_squarings: Vec<Scalar<E>> = core::iter::successors(Some(x), |x| Some(x * x))
+        .take((usize::BITS - n.leading_zeros()) as usize)
+        .collect();
+    let b: Vec<SecretScalar<E>> = witness
+        .permutation
+        .iter()
+        .map(|&j| pow_secret(&x_squarings, j as u64 + 1))
+        .collect();
+    let r_b = SecretScalar::random(rng);
+    let powers_commitment = params.commit_secret(&b, &r_b);
+    let (y, z) = challenge_yz(&mut transcript, &powers_commitment);
+
+    // Product argument
+    let v: Vec<SecretScalar<E>> = a
+        .iter()
+        .zip(&b)
+        .map(|(a_i, b_i)| {
+            let mut v_i = secret(y * a_i);
+            v_i += b_i;
+            v_i -= z;
+            v_i
+        })
+        .collect();
+    let mut t = secret(y * &r_a);
+    t += &r_b;
+    let product = prove_product(rng, &mut transcript, params, &v, &t);
+
+    // Multi-exponentiation argument
+    let mut rho = SecretScalar::zero();
+    for (b_i, rho_i) in b.iter().zip(&witness.randomness) {
+        rho += &secret(b_i * rho_i);
+    }
+    let mut values = b;
+    values.extend([r_b, rho]);
+    let relation = multi_exp_relation(params, ek, inputs, outputs, &powers_commitment, &x);
+    let mut prover = BundleProver::new(transcript);
+    prover
+        .add(rng, relation, &values)
+        .map_err(|_| InvalidInput)?;
+
+    Ok(ShuffleProof {
+        permutation_commitment,
+        powers_commitment,
+        product,
+        multi_exp: prover.prove(),
+    })
+}
+
+impl<E: Curve> ShuffleProof<E> {
+    /// Verifies that `outputs` are shuffled `inputs`
+    ///
+    /// Transcript must be in the same state as it was at the moment of proving.
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        params: &Params<E>,
+        ek: &EncryptionKey<E>,
+        inputs: &[Ciphertext<E>],
+        outputs: &[Ciphertext<E>],
+    ) -> Result<(), InvalidProof> {
+        let n = inputs.len();
+        if n == 0
+            || n > params.len()
+            || outputs.len() != n
+            || self.product.v.len() != n
+            || self.product.p.len() != n
+        {
+            return Err(InvalidProof);
+        }
+        let x = challenge_x(
+            transcript,
+            ek,
+            inputs,
+            outputs,
+            &self.permutation_commitment,
+        );
+        let (y, z) = challenge_yz(transcript, &self.powers_commitment);
+
+        let c_v = self.permutation_commitment * y + self.powers_commitment
+            - params.g[..n].iter().sum::<Point<E>>() * z;
+        let mut product = Scalar::one();
+        let mut j = Scalar::zero();
+        let mut x_j = Scalar::one();
+        for _ in 0..n {
+            j += Scalar::one();
+            x_j *= x;
+            product *= y * j + x_j - z;
+        }
+        verify_product(transcript, params, &c_v, &product, &self.product)?;
+
+        let relation = multi_exp_relation(params, ek, inputs, outputs, &self.powers_commitment, &x);
+        self.multi_exp
+            .verify(transcript, rng, core::slice::from_ref(&relation))
+            .map_err(|_| InvalidProof)
+    }
+}
+
+/// Checks in constant time that every index in $[0, n)$ occurs in `permutation` exactly once
+fn is_permutation(permutation: &[usize]) -> Choice {
+    let n = permutation.len() as u64;
+    let mut valid = Choice::from(1);
+    for j in 0..n {
+        let occurrences = permutation
+            .iter()
+            .map(|&i| u64::from((i as u64).ct_eq(&j).unwrap_u8()))
+            .sum::<u64>();
+        valid &= occurrences.ct_eq(&1);
+    }
+    valid
+}
+
+/// Computes $x^k$ given $x^{2^i}$ for every bit $i$ of $k$, in constant time with respect to $k$
+fn pow_secret<E: Curve>(x_squarings: &[Scalar<E>], k: u64) -> SecretScalar<E> {
+    let mut result = SecretScalar::one();
+    for (i, x_i) in x_squarings.iter().enumerate() {
+        let bit = Choice::from(((k >> i) & 1) as u8);
+        result *= &secret(Scalar::conditional_select(&Scalar::one(), x_i, bit));
+    }
+    result
+}
+
+fn secret<E: Curve>(mut scalar: Scalar<E>) -> SecretScalar<E> {
+    SecretScalar::new(&mut scalar)
+}
+
+/// Proves that `v` committed with randomness `t` has product $P = \prod_i v_i$
+///
+/// `v` must be non-empty and not longer than [`Params::len`].
+fn prove_product<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    rng: &mut R,
+    transcript: &mut Transcript<D>,
+    params: &Params<E>,
+    v: &[SecretScalar<E>],
+    t: &SecretScalar<E>,
+) -> ProductProof<E> {
+    let n = v.len();
+    let mut p: Vec<SecretScalar<E>> = Vec::with_capacity(n);
+    for v_i in v {
+        p.push(match p.last() {
+            Some(p_prev) => secret(p_prev * v_i),
+            None => v_i.clone(),
+        });
+    }
+
+    // For a single value, `d_1 = delta_1 = delta_N` must be zero
+    let d: Vec<SecretScalar<E>> = (0..n)
+        .map(|_| {
+            if n == 1 {
+                SecretScalar::zero()
+            } else {
+                SecretScalar::random(rng)
+            }
+        })
+        .collect();
+    let delta: Vec<SecretScalar<E>> = (0..n)
+        .map(|k| match k {
+            0 => d[0].clone(),
+            k if k == n - 1 => SecretScalar::zero(),
+            _ => SecretScalar::random(rng),
+        })
+        .collect();
+    let delta_terms: Vec<SecretScalar<E>> = (0..n - 1)
+        .map(|k| -&secret(&delta[k] * &d[k + 1]))
+        .collect();
+    let big_delta_terms: Vec<SecretScalar<E>> = (0..n - 1)
+        .map(|k| {
+            let mut term = delta[k + 1].clone();
+            term -= &secret(&v[k + 1] * &delta[k]);
+            term -= &secret(&p[k] * &d[k + 1]);
+            term
+        })
+        .collect();
+    let r_d = SecretScalar::random(rng);
+    let s_1 = SecretScalar::random(rng);
+    let s_2 = SecretScalar::random(rng);
+
+    let c_d = params.commit_secret(&d, &r_d);
+    let c_delta = params.commit_secret(&delta_terms, &s_1);
+    let c_big_delta = params.commit_secret(&big_delta_terms, &s_2);
+    let w = challenge_w(transcript, &c_d, &c_delta, &c_big_delta);
+
+    ProductProof {
+        c_d,
+        c_delta,
+        c_big_delta,
+        v: v.iter().zip(&d).map(|(v_i, d_i)| w * v_i + d_i).collect(),
+        p: p.iter()
+            .zip(&delta)
+            .map(|(p_i, delta_i)| w * p_i + delta_i)
+            .collect(),
+        r: w * t + &r_d,
+        s: w * &s_2 + &s_1,
+    }
+}
+
+/// Verifies that vector committed in `c_v` has given `product`
+///
+/// Lengths of responses must be already checked to be non-zero, equal, and not exceed
+/// [`Params::len`].
+fn verify_product<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    params: &Params<E>,
+    c_v: &Point<E>,
+    product: &Scalar<E>,
+    proof: &ProductProof<E>,
+) -> Result<(), InvalidProof> {
+    let w = challenge_w(transcript, &proof.c_d, &proof.c_delta, &proof.c_big_delta);
+
+    let big_delta_terms: Vec<Scalar<E>> = proof
+        .p
+        .windows(2)
+        .zip(&proof.v[1..])
+        .map(|(p, v_next)| w * p[1] - p[0] * v_next)
+        .collect();
+    let valid = *c_v * w + proof.c_d == params.commit(&proof.v, &proof.r)
+        && proof.c_big_delta * w + proof.c_delta == params.commit(&big_delta_terms, &proof.s)
+        && proof.p.first() == proof.v.first()
+        && proof.p.last() == Some(&(w * product));
+    if valid {
+        Ok(())
+    } else {
+        Err(InvalidProof)
+    }
+}
+
+/// Appends the statement and permutation commitment to the transcript, and derives challenge $x$
+fn challenge_x<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    ek: &EncryptionKey<E>,
+    inputs: &[Ciphertext<E>],
+    outputs: &[Ciphertext<E>],
+    permutation_commitment: &Point<E>,
+) -> Scalar<E> {
+    transcript.append_point(b"shuffle/ek", &ek.0);
+    transcript.append_u64(b"shuffle/n", inputs.len() as u64);
+    for e in inputs {
+        transcript.append_point(b"shuffle/input/c1", &e.c1);
+        transcript.append_point(b"shuffle/input/c2", &e.c2);
+    }
+    for e in outputs {
+        transcript.append_point(b"shuffle/output/c1", &e.c1);
+        transcript.append_point(b"shuffle/output/c2", &e.c2);
+    }
+    transcript.append_point(b"shuffle/c_a", permutation_commitment);
+    transcript.challenge_scalar(b"shuffle/x")
+}
+
+/// Appends commitment to permuted powers to the transcript, and derives challenges $y, z$
+fn challenge_yz<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    powers_commitment: &Point<E>,
+) -> (Scalar<E>, Scalar<E>) {
+    transcript.append_point(b"shuffle/c_b", powers_commitment);
+    (
+        transcript.challenge_scalar(b"shuffle/y"),
+        transcript.challenge_scalar(b"shuffle/z"),
+    )
+}
+
+/// Appends product argument commitments to the transcript, and derives challenge $w$
+fn challenge_w<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    c_d: &Point<E>,
+    c_delta: &Point<E>,
+    c_big_delta: &Point<E>,
+) -> Scalar<E> {
+    transcript.append_point(b"shuffle/c_d", c_d);
+    transcript.append_point(b"shuffle/c_delta", c_delta);
+    transcript.append_point(b"shuffle/c_big_delta", c_big_delta);
+    transcript.challenge_scalar(b"shuffle/w")
+}
+
+/// Linear relation proven by multi-exponentiation argument
+///
+/// Witness is $(b_1, \dots, b_N, r_B, \rho)$. Lengths of all inputs must be already checked to be
+/// equal to $N$.
+fn multi_exp_relation<E: Curve>(
+    params: &Params<E>,
+    ek: &EncryptionKey<E>,
+    inputs: &[Ciphertext<E>],
+    outputs: &[Ciphertext<E>],
+    powers_commitment: &Point<E>,
+    x: &Scalar<E>,
+) -> LinearRelation<E> {
+    let n = inputs.len();
+    let x_powers: Vec<Scalar<E>> = core::iter::successors(Some(*x), |x_j| Some(x_j * x))
+        .take(n)
+        .collect();
+    let inputs_x = [
+        Scalar::multiscalar_mul(x_powers.iter().zip(inputs.iter().map(|e| e.c1))),
+        Scalar::multiscalar_mul(x_powers.iter().zip(inputs.iter().map(|e| e.c2))),
+    ];
+
+    LinearRelation::new(n + 2)
+        .with_equation(
+            params.g[..n]
+                .iter()
+                .copied()
+                .enumerate()
+                .chain([(n, params.h)]),
+            *powers_commitment,
+        )
+        .with_equation(
+            outputs
+                .iter()
+                .map(|e| e.c1)
+                .enumerate()
+                .chain([(n + 1, -Point::generator().to_point())]),
+            inputs_x[0],
+        )
+        .with_equation(
+            outputs
+                .iter()
+                .map(|e| e.c2)
+                .enumerate()
+                .chain([(n + 1, -ek.0)]),
+            inputs_x[1],
+        )
+}
+
+/// Invalid input error
+///
+/// Returned when amount of ciphertexts is not supported by parameters, or witness doesn't
+/// correspond to the shuffle
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidInput;
+
+impl core::fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid input")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInput {}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid proof of shuffle")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{hash_to_curve::FromHash, hash_to_curve::Tag, Curve, Point, Scalar};
+    use rand_dev::DevRng;
+
+    use crate::{
+        elgamal::{self, Ciphertext, EncryptionKey},
+        transcript::Transcript,
+    };
+
+    const TAG: Tag = Tag::new_unwrap(b"generic-ec-zkp-shuffle-test");
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    fn encrypt<E: Curve>(rng: &mut DevRng, ek: &EncryptionKey<E>, n: usize) -> Vec<Ciphertext<E>> {
+        (0..n)
+            .map(|_| {
+                let message = Point::generator() * Scalar::random(rng);
+                ek.encrypt(&message, &Scalar::random(rng))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shuffle_and_verify<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = super::Params::<E>::derive(TAG, 10).unwrap();
+        let (dk, ek) = elgamal::keygen(&mut rng);
+
+        for n in [1, 2, 5, 10] {
+            let inputs = encrypt(&mut rng, &ek, n);
+            let (outputs, witness) = super::shuffle(&mut rng, &ek, &inputs);
+
+            // Outputs decrypt to permuted inputs
+            for (output, &j) in outputs.iter().zip(&witness.permutation) {
+                assert_eq!(dk.decrypt(output), dk.decrypt(&inputs[j]));
+            }
+
+            let proof = super::prove(
+                &mut rng,
+                transcript(),
+                &params,
+                &ek,
+                &inputs,
+                &outputs,
+                &witness,
+            )
+            .unwrap();
+            proof
+                .verify(&mut transcript(), &mut rng, &params, &ek, &inputs, &outputs)
+                .unwrap();
+        }
+    }
+
+    #[cfg(not(feature = "debug-secrets"))]
+    #[test]
+    fn witness_debug_is_redacted<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (_, ek) = elgamal::keygen::<E, _>(&mut rng);
+        let inputs = encrypt(&mut rng, &ek, 3);
+        let (_, witness) = super::shuffle(&mut rng, &ek, &inputs);
+
+        let debug = alloc::format!("{witness:?}");
+        assert!(debug.starts_with("Witness { permutation: [REDACTED], randomness: ["));
+        assert!(!debug.contains(&alloc::format!("{:?}", witness.permutation)));
+    }
+
+    #[test]
+    fn invalid_shuffle_is_rejected<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params = super::Params::<E>::derive(TAG, 4).unwrap();
+        let (_, ek) = elgamal::keygen(&mut rng);
+        let inputs = encrypt(&mut rng, &ek, 4);
+        let (outputs, witness) = super::shuffle(&mut rng, &ek, &inputs);
+        let proof = super::prove(
+            &mut rng,
+            transcript(),
+            &params,
+            &ek,
+            &inputs,
+            &outputs,
+            &witness,
+        )
+        .unwrap();
+
+        // Output encrypts a different plaintext
+        let mut tampered = outputs.clone();
+        tampered[1] = tampered[1] + ek.encrypt(&Point::generator().to_point(), &Scalar::zero());
+        assert!(proof
+            .verify(
+                &mut transcript(),
+                &mut rng,
+                &params,
+                &ek,
+                &inputs,
+                &tampered
+            )
+            .is_err());
+
+        // Prover can't prove a shuffle that doesn't hold
+        assert!(super::prove(
+            &mut rng,
+            transcript(),
+            &params,
+            &ek,
+            &inputs,
+            &tampered,
+            &witness
+        )
+        .is_err());
+
+        // Outputs are not allowed to be reordered after proving
+        let mut reordered = outputs.clone();
+        reordered.swap(0, 3);
+        assert!(proof
+            .verify(
+                &mut transcript(),
+                &mut rng,
+                &params,
+                &ek,
+                &inputs,
+                &reordered
+            )
+            .is_err());
+
+        // Tampered product argument
+        let mut tampered_proofs = [proof.clone(), proof.clone(), proof.clone(), proof.clone()];
+        tampered_proofs[0].product.v[0] += Scalar::one();
+        tampered_proofs[1].product.p[2] += Scalar::one();
+        tampered_proofs[2].product.c_delta += Point::generator();
+        tampered_proofs[3].permutation_commitment += Point::generator();
+        for tampered in tampered_proofs {
+            assert!(tampered
+                .verify(&mut transcript(), &mut rng, &params, &ek, &inputs, &outputs)
+                .is_err());
+        }
+
+        // Witness must be a permutation
+        let mut not_permutation = witness.clone();
+        not_permutation.permutation[0] = not_permutation.permutation[1];
+        assert!(super::prove(
+            &mut rng,
+            transcript(),
+            &params,
+            &ek,
+            &inputs,
+            &outputs,
+            &not_permutation
+        )
+        .is_err());
+
+        // Shuffle is too large for the parameters
+        let inputs = encrypt(&mut rng, &ek, 5);
+        let (outputs, witness) = super::shuffle(&mut rng, &ek, &inputs);
+        assert!(super::prove(
+            &mut rng,
+            transcript(),
+            &params,
+            &ek,
+            &inputs,
+            &outputs,
+            &witness
+        )
+        .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
@@ -0,0 +1,247 @@
+//! DSL for declaring $\Sigma$-protocol statements
+//!
+//! [`zk_statement!`](crate::zk_statement) declares a statement as a list of linear equations
+//! over public points and secret scalars, and generates a struct holding the public points
+//! along with prover and verifier for it. Generated code builds a
+//! [linear relation](crate::proof_bundle::LinearRelation) out of equations and proves it via
+//! [proof bundle](crate::proof_bundle), so the statement is always bound to the
+//! [transcript](crate::transcript), and there's no hand-written verification equation that could
+//! go out of sync with the prover.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{transcript::Transcript, zk_statement};
+//! # let mut rng = rand::rngs::OsRng;
+//! # let random_point = |rng: &mut _| Point::<Secp256k1>::generator() * SecretScalar::random(rng);
+//! # let (h, g2) = (random_point(&mut rng), random_point(&mut rng));
+//!
+//! zk_statement! {
+//!     /// Knowledge of $x, r$ such as $X = x G$ and $Y = x H + r G_2$
+//!     pub struct Opening {
+//!         witnesses: (x, r),
+//!         points: (G, H, G2, X, Y),
+//!         equations: {
+//!             X = x * G,
+//!             Y = x * H + r * G2,
+//!         },
+//!     }
+//! }
+//!
+//! let x = SecretScalar::random(&mut rng);
+//! let r = SecretScalar::random(&mut rng);
+//! let statement = Opening {
+//!     G: Point::generator().to_point(),
+//!     H: h,
+//!     G2: g2,
+//!     X: Point::generator() * &x,
+//!     Y: h * &x + g2 * &r,
+//! };
+//!
+//! let transcript = || Transcript::<sha2::Sha256>::new(b"MYAPP-opening");
+//! let proof = statement.prove(&mut rng, transcript(), &x, &r)?;
+//! statement.verify(&mut transcript(), &mut rng, &proof)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Generated code
+//! For a statement `Name` with witnesses `w_1, ..., w_n` and points `P_1, ..., P_m`, macro
+//! generates:
+//!
+//! * `struct Name<E: Curve>` with public field `P_i: Point<E>` for every point
+//! * `Name::relation(&self) -> LinearRelation<E>` \
+//!   Witnesses are indexed in order they're listed
+//! * `Name::prove(&self, rng, transcript, w_1: &SecretScalar<E>, ..., w_n) -> Result<ProofBundle<E>, InvalidInput>` \
+//!   Returns error if witness doesn't satisfy the statement
+//! * `Name::verify(&self, &mut transcript, rng, &ProofBundle<E>) -> Result<(), InvalidProof>`
+//!
+//! Name of the statement is appended to the transcript, so proofs of different statements having
+//! the same equations are not interchangeable.
+//!
+//! [`InvalidInput`]: crate::proof_bundle::InvalidInput
+//! [`InvalidProof`]: crate::proof_bundle::InvalidProof
+
+/// Declares a $\Sigma$-protocol statement
+///
+/// See [module-level docs](crate::statement) for details.
+#[macro_export]
+macro_rules! zk_statement {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            witnesses: ($($witness:ident),+ $(,)?),
+            points: ($($point:ident),+ $(,)?),
+            equations: {
+                $($image:ident = $w0:ident * $b0:ident $(+ $w:ident * $b:ident)*),+ $(,)?
+            } $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[allow(non_snake_case)]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        $vis struct $name<E: $crate::statement::__private::Curve> {
+            $(
+                #[allow(missing_docs)]
+                pub $point: $crate::statement::__private::Point<E>,
+            )+
+        }
+
+        #[allow(non_snake_case)]
+        impl<E: $crate::statement::__private::Curve> $name<E> {
+            /// Linear relation corresponding to the statement
+            pub fn relation(&self) -> $crate::proof_bundle::LinearRelation<E> {
+                let mut index = 0usize;
+                $(
+                    let $witness = index;
+                    index += 1;
+                )+
+                $crate::proof_bundle::LinearRelation::new(index)
+                $(
+                    .with_equation(
+                        [(&$w0, self.$b0) $(, (&$w, self.$b))*].map(|(j, base)| (*j, base)),
+                        self.$image,
+                    )
+                )+
+            }
+
+            /// Proves the statement
+            ///
+            /// Returns error if witness doesn't satisfy the statement
+            pub fn prove<D, R>(
+                &self,
+                rng: &mut R,
+                mut transcript: $crate::transcript::Transcript<D>,
+                $($witness: &$crate::statement::__private::SecretScalar<E>),+
+            ) -> ::core::result::Result<
+                $crate::proof_bundle::ProofBundle<E>,
+                $crate::proof_bundle::InvalidInput,
+            >
+            where
+                D: $crate::statement::__private::Digest + Clone,
+                R: $crate::statement::__private::RngCore + $crate::statement::__private::CryptoRng,
+            {
+                transcript.append_message(b"zk_statement", stringify!($name).as_bytes());
+                let mut prover = $crate::proof_bundle::BundleProver::new(transcript);
+                prover.add(rng, self.relation(), &[$($witness.clone()),+])?;
+                Ok(prover.prove())
+            }
+
+            /// Verifies the proof
+            ///
+            /// Transcript must be in the same state as it was at the moment of proving.
+            pub fn verify<D, R>(
+                &self,
+                transcript: &mut $crate::transcript::Transcript<D>,
+                rng: &mut R,
+                proof: &$crate::proof_bundle::ProofBundle<E>,
+            ) -> ::core::result::Result<(), $crate::proof_bundle::InvalidProof>
+            where
+                D: $crate::statement::__private::Digest + Clone,
+                R: $crate::statement::__private::RngCore + $crate::statement::__private::CryptoRng,
+            {
+                transcript.append_message(b"zk_statement", stringify!($name).as_bytes());
+                proof.verify(transcript, rng, &[self.relation()])
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use digest::Digest;
+    pub use generic_ec::{Curve, Point, SecretScalar};
+    pub use rand_core::{CryptoRng, RngCore};
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use crate::transcript::Transcript;
+
+    crate::zk_statement! {
+        /// Knowledge of $x, r$ such as $X = x G$ and $Y = x H + r G_2$
+        pub struct Opening {
+            witnesses: (x, r),
+            points: (G, H, G2, X, Y),
+            equations: {
+                X = x * G,
+                Y = x * H + r * G2,
+            },
+        }
+    }
+
+    crate::zk_statement! {
+        /// Same equations as [`Opening`], but different statement
+        pub struct OtherOpening {
+            witnesses: (x, r),
+            points: (G, H, G2, X, Y),
+            equations: {
+                X = x * G,
+                Y = x * H + r * G2,
+            },
+        }
+    }
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn prove_and_verify<E: Curve>() {
+        let mut rng = DevRng::new();
+        let mut random_point = || Point::<E>::generator() * Scalar::random(&mut rng);
+        let (h, g2) = (random_point(), random_point());
+
+        let x = SecretScalar::<E>::random(&mut rng);
+        let r = SecretScalar::<E>::random(&mut rng);
+        let statement = Opening {
+            G: Point::generator().to_point(),
+            H: h,
+            G2: g2,
+            X: Point::generator() * &x,
+            Y: h * &x + g2 * &r,
+        };
+
+        let relation = statement.relation();
+        assert_eq!(relation.witnesses, 2);
+        assert_eq!(relation.equations.len(), 2);
+        assert_eq!(relation.equations[1].terms, [(0, h), (1, g2)]);
+
+        let proof = statement.prove(&mut rng, transcript(), &x, &r).unwrap();
+        statement
+            .verify(&mut transcript(), &mut rng, &proof)
+            .unwrap();
+
+        // Witnesses in wrong order don't satisfy the statement
+        assert!(statement.prove(&mut rng, transcript(), &r, &x).is_err());
+
+        // Proof doesn't verify against other statement
+        let mut other = statement.clone();
+        other.Y += Point::generator();
+        assert!(other.verify(&mut transcript(), &mut rng, &proof).is_err());
+
+        // Proof is bound to statement name
+        let other = OtherOpening {
+            G: statement.G,
+            H: statement.H,
+            G2: statement.G2,
+            X: statement.X,
+            Y: statement.Y,
+        };
+        assert!(other.verify(&mut transcript(), &mut rng, &proof).is_err());
+        let other_proof = other.prove(&mut rng, transcript(), &x, &r).unwrap();
+        assert!(statement
+            .verify(&mut transcript(), &mut rng, &other_proof)
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -0,0 +1,180 @@
+//! Dual-key stealth addresses
+//!
+//! Recipient publishes a stealth address: a pair of public keys $(V, S)$, view key and spend key.
+//! Sender derives a fresh one-time key $P$ for every payment, so payments to the same recipient
+//! can't be linked by observers. Recipient scans outputs using only the view secret key $v$
+//! (which can be delegated, e.g. to a watch-only wallet) and needs the spend secret key $s$ only
+//! to spend the output.
+//!
+//! Each output also carries a one-byte view tag derived from the shared secret, which lets the
+//! recipient skip ~255/256 of foreign outputs without computing $P$.
+//!
+//! All hashing is done via [transcript](crate::transcript) with a fixed domain, so every wallet
+//! using this module derives the same keys.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::stealth;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Recipient's stealth address
+//! let view_secret = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let spend_secret = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let view_pub = Point::generator() * &view_secret;
+//! let spend_pub = Point::generator() * &spend_secret;
+//!
+//! // Sender derives a one-time output
+//! let r = SecretScalar::random(&mut rng);
+//! let output = stealth::derive_output::<_, sha2::Sha256>(&view_pub, &spend_pub, &r);
+//!
+//! // Recipient detects the output and derives its secret key
+//! let owned = stealth::scan::<_, sha2::Sha256>(&view_secret, &spend_pub, &output)
+//!     .expect("output belongs to the recipient");
+//! let one_time_secret = owned.one_time_secret_key(&spend_secret);
+//! assert_eq!(Point::generator() * &one_time_secret, output.one_time_key);
+//! ```
+//!
+//! ## Algorithm
+//!
+//! * Sender samples $r$ and publishes ephemeral key $R = r \cdot G$
+//! * Shared secret: $K = r \cdot V = v \cdot R$
+//! * View tag and tweak are derived from $K$: $\text{tag} = \mathcal{H}_\text{tag}(K)$,
+//!   $h = \mathcal{H}_\text{tweak}(K)$
+//! * One-time key: $P = h \cdot G + S$, one-time secret key: $p = h + s$
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+
+use crate::transcript::Transcript;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One-time output
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct StealthOutput<E: Curve> {
+    /// Ephemeral key $R = r \cdot G$
+    pub ephemeral_key: Point<E>,
+    /// One-time key $P$
+    pub one_time_key: Point<E>,
+    /// View tag
+    pub view_tag: u8,
+}
+
+/// Output detected by [`scan`] as belonging to the recipient
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedOutput<E: Curve> {
+    tweak: Scalar<E>,
+}
+
+/// Derives one-time output for the stealth address $(V, S)$ using ephemeral secret $r$
+///
+/// `r` must be freshly sampled for every output.
+pub fn derive_output<E: Curve, D: Digest + Clone>(
+    view_pub: &Point<E>,
+    spend_pub: &Point<E>,
+    r: &SecretScalar<E>,
+) -> StealthOutput<E> {
+    let (view_tag, tweak) = derive_from_shared_secret::<E, D>(&(view_pub * r));
+    StealthOutput {
+        ephemeral_key: Point::generator() * r,
+        one_time_key: Point::generator() * tweak + spend_pub,
+        view_tag,
+    }
+}
+
+/// Checks whether the output belongs to the stealth address $(V, S)$
+///
+/// Returns `None` if it doesn't. Otherwise, returned [`OwnedOutput`] can be used to derive the
+/// one-time secret key.
+pub fn scan<E: Curve, D: Digest + Clone>(
+    view_secret: &SecretScalar<E>,
+    spend_pub: &Point<E>,
+    output: &StealthOutput<E>,
+) -> Option<OwnedOutput<E>> {
+    let (view_tag, tweak) =
+        derive_from_shared_secret::<E, D>(&(output.ephemeral_key * view_secret));
+    if view_tag != output.view_tag || Point::generator() * tweak + spend_pub != output.one_time_key
+    {
+        return None;
+    }
+    Some(OwnedOutput { tweak })
+}
+
+impl<E: Curve> OwnedOutput<E> {
+    /// Tweak $h$ such that $P = h \cdot G + S$
+    pub fn tweak(&self) -> &Scalar<E> {
+        &self.tweak
+    }
+
+    /// Derives one-time secret key $p = h + s$
+    pub fn one_time_secret_key(&self, spend_secret: &SecretScalar<E>) -> SecretScalar<E> {
+        let mut p = self.tweak + spend_secret;
+        SecretScalar::new(&mut p)
+    }
+}
+
+/// Derives view tag and tweak from shared secret $K$
+fn derive_from_shared_secret<E: Curve, D: Digest + Clone>(
+    shared_secret: &Point<E>,
+) -> (u8, Scalar<E>) {
+    let mut transcript = Transcript::<D>::new(b"generic-ec-zkp/stealth");
+    transcript.append_point(b"shared_secret", shared_secret);
+    let view_tag = transcript
+        .challenge_scalar::<E>(b"view_tag")
+        .to_be_bytes()
+        .last()
+        .copied()
+        .unwrap_or_default();
+    let tweak = transcript.challenge_scalar(b"tweak");
+    (view_tag, tweak)
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{Curve, Point, SecretScalar};
+    use rand_dev::DevRng;
+
+    type D = sha2::Sha256;
+
+    #[test]
+    fn derive_and_scan<E: Curve>() {
+        let mut rng = DevRng::new();
+        let view_secret = SecretScalar::<E>::random(&mut rng);
+        let spend_secret = SecretScalar::<E>::random(&mut rng);
+        let view_pub = Point::generator() * &view_secret;
+        let spend_pub = Point::generator() * &spend_secret;
+
+        let r = SecretScalar::random(&mut rng);
+        let output = super::derive_output::<E, D>(&view_pub, &spend_pub, &r);
+        assert_ne!(output.one_time_key, spend_pub);
+
+        let owned = super::scan::<E, D>(&view_secret, &spend_pub, &output).unwrap();
+        assert_eq!(
+            Point::generator() * owned.one_time_secret_key(&spend_secret),
+            output.one_time_key
+        );
+
+        // Outputs to the same address are unlinkable
+        let other =
+            super::derive_output::<E, D>(&view_pub, &spend_pub, &SecretScalar::random(&mut rng));
+        assert_ne!(other.one_time_key, output.one_time_key);
+        super::scan::<E, D>(&view_secret, &spend_pub, &other).unwrap();
+
+        // Other recipient doesn't detect the output
+        let other_view_secret = SecretScalar::<E>::random(&mut rng);
+        assert!(super::scan::<E, D>(&other_view_secret, &spend_pub, &output).is_none());
+        let other_spend_pub = Point::generator() * SecretScalar::<E>::random(&mut rng);
+        assert!(super::scan::<E, D>(&view_secret, &other_spend_pub, &output).is_none());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -0,0 +1,395 @@
+//! Public math of threshold ECDSA signing
+//!
+//! Threshold ECDSA protocols (e.g. [CGGMP21]) run an interactive presigning phase, which
+//! requires multiplicative-to-additive share conversion and ZK proofs, and after that signing
+//! is a single non-interactive round of elliptic curve algebra. This module implements the
+//! latter: combining presignature nonce $R$, issuing partial signatures, and assembling them
+//! into a regular ECDSA signature. Presigning phase is out of scope.
+//!
+//! [CGGMP21]: https://eprint.iacr.org/2021/060
+//!
+//! ## Algorithm
+//!
+//! Parties hold additive shares of secret key $x = \sum_i x_i$, public key $X = x \cdot G$.
+//! After presigning, each party $i$ holds presignature $(R, k_i, \chi_i)$ such as
+//! $k = \sum_i k_i$, $R = k^{-1} \cdot G$, and $\chi = \sum_i \chi_i = k \cdot x$.
+//!
+//! 1. $R$ is obtained from shares $\Gamma_i = \gamma_i \cdot G$ and $\delta_i$ such as
+//!    $\sum_i \delta_i = k \gamma$: $R = (\sum_i \delta_i)^{-1} \cdot \sum_i \Gamma_i$,
+//!    see [`combine_nonce`]
+//! 2. To sign message $m$, party $i$ computes $r = R_x \bmod q$, and sends partial signature
+//!    $\sigma_i = k_i m + r \chi_i$, see [`Presignature::issue_partial_signature`]
+//! 3. Signature is $(r, s)$ where $s = \sum_i \sigma_i$, normalized to the lower half of scalar
+//!    field, see [`PartialSignature::combine`]
+//!
+//! If shares were obtained via $t$-out-of-$n$ secret sharing, each signer first converts its
+//! presignature into additive shares via [`Presignature::to_additive_share`].
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{coords::AlwaysHasAffineX, Curve, NonZero, Point};
+//! use generic_ec_zkp::threshold_ecdsa::{DataToSign, PartialSignature, Presignature, Signature};
+//!
+//! // In reality, each signer holds only its own presignature and sends partial signature
+//! // to the others
+//! fn sign<E: Curve>(
+//!     presignatures: &[Presignature<E>],
+//!     public_key: &Point<E>,
+//!     message: &[u8],
+//! ) -> Result<Signature<E>, Box<dyn std::error::Error>>
+//! where
+//!     NonZero<Point<E>>: AlwaysHasAffineX<E>,
+//! {
+//!     let message = DataToSign::digest::<sha2::Sha256>(message);
+//!     let partial_signatures = presignatures
+//!         .iter()
+//!         .map(|presig| presig.issue_partial_signature(message));
+//!     let signature = PartialSignature::combine(partial_signatures)?;
+//!     signature.verify(public_key, message)?;
+//!     Ok(signature)
+//! }
+//! ```
+
+use digest::Digest;
+use generic_ec::{coords::AlwaysHasAffineX, Curve, NonZero, Point, Scalar, SecretScalar};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Message to be signed, reduced to a scalar
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataToSign<E: Curve>(Scalar<E>);
+
+impl<E: Curve> DataToSign<E> {
+    /// Hashes the message and converts the hash into a scalar
+    ///
+    /// Leftmost bytes of the hash are taken, as many as scalar byte length, and reduced modulo
+    /// curve order. It matches ECDSA conversion for curves whose order bit length is a multiple
+    /// of 8 (e.g. secp256k1, secp256r1).
+    pub fn digest<D: Digest>(message: &[u8]) -> Self {
+        Self::from_digest(D::new_with_prefix(message))
+    }
+
+    /// Finalizes the hash and converts it into a scalar, see [`DataToSign::digest`]
+    pub fn from_digest<D: Digest>(hash: D) -> Self {
+        let hash = hash.finalize();
+        let len = Scalar::<E>::serialized_len().min(hash.len());
+        Self(Scalar::from_be_bytes_mod_order(&hash[..len]))
+    }
+
+    /// Constructs a message from a scalar
+    ///
+    /// Scalar must be derived from the message via cryptographic hash function.
+    pub fn from_scalar(scalar: Scalar<E>) -> Self {
+        Self(scalar)
+    }
+
+    /// Returns a scalar representing the message
+    pub fn to_scalar(self) -> Scalar<E> {
+        self.0
+    }
+}
+
+/// Combines presignature nonce $R = (\sum_i \delta_i)^{-1} \cdot \sum_i \Gamma_i$
+///
+/// Returns `None` if $\sum_i \delta_i$ is zero or resulting point is zero, which indicates that
+/// presigning phase failed.
+pub fn combine_nonce<E: Curve>(
+    gammas: impl IntoIterator<Item = Point<E>>,
+    deltas: impl IntoIterator<Item = Scalar<E>>,
+) -> Option<NonZero<Point<E>>> {
+    let gamma = gammas.into_iter().sum::<Point<E>>();
+    let delta = deltas.into_iter().sum::<Scalar<E>>();
+    NonZero::from_point(gamma * delta.invert()?)
+}
+
+/// Presignature share of a signer
+#[derive(Debug)]
+pub struct Presignature<E: Curve> {
+    /// Presignature nonce $R = k^{-1} \cdot G$, same for all signers
+    pub r: NonZero<Point<E>>,
+    /// Share of $k$
+    pub k: SecretScalar<E>,
+    /// Share of $\chi = k \cdot x$
+    pub chi: SecretScalar<E>,
+}
+
+impl<E: Curve> Presignature<E> {
+    /// Converts polynomial shares of $k$ and $\chi$ into additive shares
+    ///
+    /// `lambda` is the Lagrange coefficient of the signer for the set of parties taking part in
+    /// signing, see [`lagrange_coefficient`](crate::polynomial::lagrange_coefficient).
+    pub fn to_additive_share(&self, lambda: &Scalar<E>) -> Self {
+        Self {
+            r: self.r,
+            k: SecretScalar::new(&mut (lambda * &self.k)),
+            chi: SecretScalar::new(&mut (lambda * &self.chi)),
+        }
+    }
+
+    /// Issues partial signature $\sigma_i = k_i m + r \chi_i$ on the message
+    pub fn issue_partial_signature(&self, message: DataToSign<E>) -> PartialSignature<E>
+    where
+        NonZero<Point<E>>: AlwaysHasAffineX<E>,
+    {
+        let r = self.r.x().to_scalar();
+        let sigma = &self.k * message.0 + r * &self.chi;
+        PartialSignature { r, sigma }
+    }
+}
+
+/// Partial signature $(r, \sigma_i)$ issued by a signer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct PartialSignature<E: Curve> {
+    /// $r = R_x \bmod q$
+    pub r: Scalar<E>,
+    /// Share of $s$
+    pub sigma: Scalar<E>,
+}
+
+impl<E: Curve> PartialSignature<E> {
+    /// Combines partial signatures into ECDSA signature
+    ///
+    /// Resulting signature is [normalized](Signature::normalize_s). Returns error if there are
+    /// no partial signatures, partial signatures disagree on $r$, or $r$ or $s$ is zero. Resulting
+    /// signature still needs to be [verified](Signature::verify), as it's invalid if any of the
+    /// partial signatures was invalid.
+    pub fn combine(
+        partial_signatures: impl IntoIterator<Item = PartialSignature<E>>,
+    ) -> Result<Signature<E>, CombineError> {
+        let mut partial_signatures = partial_signatures.into_iter();
+        let first = partial_signatures.next().ok_or(CombineError)?;
+        let mut s = first.sigma;
+        for partial_signature in partial_signatures {
+            if partial_signature.r != first.r {
+                return Err(CombineError);
+            }
+            s += partial_signature.sigma;
+        }
+        let r = NonZero::from_scalar(first.r).ok_or(CombineError)?;
+        let s = NonZero::from_scalar(s).ok_or(CombineError)?;
+        Ok(Signature { r, s }.normalize_s())
+    }
+}
+
+/// ECDSA signature $(r, s)$
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct Signature<E: Curve> {
+    /// $r$ component of the signature
+    pub r: NonZero<Scalar<E>>,
+    /// $s$ component of the signature
+    pub s: NonZero<Scalar<E>>,
+}
+
+impl<E: Curve> Signature<E> {
+    /// Replaces $s$ with $-s$ if $s$ is in the upper half of scalar field
+    ///
+    /// Both $(r, s)$ and $(r, -s)$ are valid signatures, some verifiers (e.g. Bitcoin) only
+    /// accept the one with lower $s$ to prevent signature malleability.
+    pub fn normalize_s(self) -> Self {
+        let neg_s = -self.s;
+        if neg_s.to_be_bytes().as_bytes() < self.s.to_be_bytes().as_bytes() {
+            Self {
+                r: self.r,
+                s: neg_s,
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Checks whether $s$ is in the lower half of scalar field
+    pub fn is_normalized(&self) -> bool {
+        self.normalize_s().s == self.s
+    }
+
+    /// Verifies signature against public key and the message
+    ///
+    /// Both normalized and non-normalized signatures are accepted.
+    pub fn verify(
+        &self,
+        public_key: &Point<E>,
+        message: DataToSign<E>,
+    ) -> Result<(), InvalidSignature>
+    where
+        NonZero<Point<E>>: AlwaysHasAffineX<E>,
+    {
+        let s_inv = self.s.invert();
+        let r = Point::generator() * (message.0 * s_inv) + public_key * (self.r * s_inv);
+        let r = NonZero::from_point(r).ok_or(InvalidSignature)?;
+        if r.x().to_scalar() == *self.r {
+            Ok(())
+        } else {
+            Err(InvalidSignature)
+        }
+    }
+}
+
+/// Partial signatures couldn't be combined into a signature
+#[derive(Debug, Clone, Copy)]
+pub struct CombineError;
+
+impl core::fmt::Display for CombineError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("partial signatures can't be combined")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CombineError {}
+
+/// Signature is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidSignature;
+
+impl core::fmt::Display for InvalidSignature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid signature")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSignature {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{coords::AlwaysHasAffineX, Curve, NonZero, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{DataToSign, PartialSignature, Presignature, Signature};
+
+    /// Trusted dealer emulating presigning phase: returns public key and additive presignatures
+    fn presign<E: Curve>(rng: &mut DevRng, n: usize) -> (Point<E>, Vec<Presignature<E>>) {
+        let x = Scalar::<E>::random(rng);
+        let k = Scalar::<E>::random(rng);
+        let gamma = Scalar::<E>::random(rng);
+
+        let k_shares = additive_shares(rng, k, n);
+        let chi_shares = additive_shares(rng, k * x, n);
+        let gamma_shares = additive_shares(rng, gamma, n);
+        let delta_shares = additive_shares(rng, k * gamma, n);
+
+        let r = super::combine_nonce(
+            gamma_shares
+                .iter()
+                .map(|gamma_i| Point::generator() * gamma_i),
+            delta_shares,
+        )
+        .unwrap();
+        assert_eq!(*r, Point::generator() * k.invert().unwrap());
+
+        let presignatures = k_shares
+            .into_iter()
+            .zip(chi_shares)
+            .map(|(mut k, mut chi)| Presignature {
+                r,
+                k: SecretScalar::new(&mut k),
+                chi: SecretScalar::new(&mut chi),
+            })
+            .collect();
+        (Point::generator() * x, presignatures)
+    }
+
+    fn additive_shares<E: Curve>(rng: &mut DevRng, secret: Scalar<E>, n: usize) -> Vec<Scalar<E>> {
+        let mut shares: Vec<_> = (1..n).map(|_| Scalar::random(rng)).collect();
+        shares.push(secret - shares.iter().sum::<Scalar<E>>());
+        shares
+    }
+
+    #[test]
+    fn signing<E: Curve>()
+    where
+        NonZero<Point<E>>: AlwaysHasAffineX<E>,
+    {
+        let mut rng = DevRng::new();
+        let (public_key, presignatures) = presign::<E>(&mut rng, 3);
+
+        let message = DataToSign::digest::<sha2::Sha256>(b"message");
+        let signature = PartialSignature::combine(
+            presignatures
+                .iter()
+                .map(|presig| presig.issue_partial_signature(message)),
+        )
+        .unwrap();
+        assert!(signature.is_normalized());
+        signature.verify(&public_key, message).unwrap();
+
+        // Signature with negated `s` is valid, but not normalized
+        let negated = Signature {
+            r: signature.r,
+            s: -signature.s,
+        };
+        assert!(!negated.is_normalized());
+        negated.verify(&public_key, message).unwrap();
+        assert_eq!(negated.normalize_s(), signature);
+
+        let other_message = DataToSign::digest::<sha2::Sha256>(b"other message");
+        assert!(signature.verify(&public_key, other_message).is_err());
+    }
+
+    #[test]
+    fn threshold_signing<E: Curve>()
+    where
+        NonZero<Point<E>>: AlwaysHasAffineX<E>,
+    {
+        let mut rng = DevRng::new();
+        let (public_key, presignatures) = presign::<E>(&mut rng, 1);
+        let presignature = &presignatures[0];
+
+        // Share `k` and `chi` via degree-1 polynomials among 3 parties
+        let k_coef = Scalar::<E>::random(&mut rng);
+        let chi_coef = Scalar::<E>::random(&mut rng);
+        let indexes = [1u16, 2, 3].map(|i| NonZero::from_scalar(Scalar::from(i)).unwrap());
+        let shares = indexes.map(|i| Presignature {
+            r: presignature.r,
+            k: SecretScalar::new(&mut (&presignature.k + k_coef * i)),
+            chi: SecretScalar::new(&mut (&presignature.chi + chi_coef * i)),
+        });
+
+        // Parties 1 and 3 sign
+        let signers = [indexes[0], indexes[2]];
+        let message = DataToSign::digest::<sha2::Sha256>(b"message");
+        let partial_signatures =
+            [&shares[0], &shares[2]]
+                .into_iter()
+                .enumerate()
+                .map(|(j, share)| {
+                    let lambda =
+                        crate::polynomial::lagrange_coefficient(Scalar::zero(), j, &signers)
+                            .unwrap();
+                    share
+                        .to_additive_share(&lambda)
+                        .issue_partial_signature(message)
+                });
+        let signature = PartialSignature::combine(partial_signatures).unwrap();
+        signature.verify(&public_key, message).unwrap();
+    }
+
+    #[test]
+    fn inconsistent_partial_signatures_are_rejected<E: Curve>()
+    where
+        NonZero<Point<E>>: AlwaysHasAffineX<E>,
+    {
+        let mut rng = DevRng::new();
+        let (_, presignatures1) = presign::<E>(&mut rng, 2);
+        let (_, presignatures2) = presign::<E>(&mut rng, 2);
+
+        let message = DataToSign::digest::<sha2::Sha256>(b"message");
+        let partial_signatures = [
+            presignatures1[0].issue_partial_signature(message),
+            presignatures2[1].issue_partial_signature(message),
+        ];
+        assert!(PartialSignature::combine(partial_signatures).is_err());
+        assert!(PartialSignature::<E>::combine([]).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
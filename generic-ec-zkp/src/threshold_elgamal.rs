@@ -0,0 +1,372 @@
+//! Threshold ElGamal decryption
+//!
+//! Decryption key is shared among $n$ parties via [`KeyShare`]: ciphertexts are
+//! [encrypted](crate::elgamal::EncryptionKey::encrypt) under the shared public key $Y = y \cdot G$,
+//! and any $t$ parties can decrypt them jointly, e.g. to tally encrypted ballots. No coalition
+//! of less than $t$ parties learns anything about the plaintext.
+//!
+//! Every party publishes [`PartialDecryption`] $D_i = y_i \cdot C_1$ along with a proof of
+//! equality of discrete logs ([DLEQ](crate::proof_bundle::LinearRelation::dleq)) with its public
+//! share. The proof only involves public data, so anyone, not only the parties, can verify partial
+//! decryptions and [combine] them into the plaintext.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{
+//!     elgamal::EncryptionKey, key_share::KeyShare, party_index::PartyIndex,
+//!     polynomial::Polynomial, threshold_elgamal::{self, PartialDecryption},
+//!     transcript::Transcript,
+//! };
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Decryption key shared among 3 parties with threshold 2, normally generated via DKG
+//! let y = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let f = Polynomial::sample_with_const_term(&mut rng, 1, y.clone());
+//! let shares: Vec<_> = (0..3)
+//!     .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+//!     .collect();
+//! let public_shares: Vec<_> = shares.iter().map(|y_i| Point::generator() * y_i).collect();
+//! let key_shares: Vec<_> = (0..3)
+//!     .zip(shares)
+//!     .map(|(i, x)| KeyShare {
+//!         i: PartyIndex(i),
+//!         t: 2,
+//!         x,
+//!         shared_public_key: Point::generator() * &y,
+//!         public_shares: public_shares.clone(),
+//!     })
+//!     .collect();
+//!
+//! // Anyone encrypts a message under the shared public key
+//! let ek = EncryptionKey(key_shares[0].shared_public_key);
+//! let message = Point::generator() * Scalar::random(&mut rng);
+//! let ciphertext = ek.encrypt(&message, &Scalar::random(&mut rng));
+//!
+//! // Parties 0 and 2 publish partial decryptions
+//! let transcript = Transcript::<sha2::Sha256>::new(b"MYAPP-tally");
+//! let partials = [&key_shares[0], &key_shares[2]]
+//!     .map(|key_share| PartialDecryption::new(&mut rng, transcript.clone(), key_share, &ciphertext));
+//!
+//! // Anyone verifies partial decryptions and combines them
+//! let verified = partials
+//!     .iter()
+//!     .map(|p| p.verify(&mut transcript.clone(), &mut rng, &public_shares, &ciphertext))
+//!     .collect::<Result<Vec<_>, _>>()?;
+//! let decrypted = threshold_elgamal::combine(&ciphertext, 2, &verified)?;
+//! assert_eq!(decrypted, message);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Party $i$ holds share $y_i = f(i + 1)$ of decryption key $y = f(0)$, public shares are
+//! $Y_i = y_i \cdot G$.
+//!
+//! * Partial decryption of $(C_1, C_2)$ by party $i$: $D_i = y_i \cdot C_1$ and a proof that
+//!   $\log_G Y_i = \log_{C_1} D_i$
+//! * Combining: $M = C_2 - \sum_{i \in S} \lambda_i \cdot D_i$ for $|S| \ge t$ verified partial
+//!   decryptions, where $\lambda_i$ are Lagrange coefficients at $0$
+
+use alloc::vec::Vec;
+
+use digest::Digest;
+use generic_ec::{Curve, NonZero, Point, Scalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    elgamal::Ciphertext,
+    key_share::KeyShare,
+    party_index::PartyIndex,
+    polynomial::LagrangeBasis,
+    proof_bundle::{BundleProver, InvalidProof, LinearRelation, ProofBundle},
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Partial decryption $D_i$ of party $i$ along with the DLEQ proof
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct PartialDecryption<E: Curve> {
+    /// Index of the party
+    pub index: PartyIndex,
+    /// Partial decryption $D_i = y_i \cdot C_1$
+    pub share: Point<E>,
+    /// Proof that $\log_G Y_i = \log_{C_1} D_i$
+    pub proof: ProofBundle<E>,
+}
+
+/// Partial decryption which proof was verified
+///
+/// Can only be obtained via [`PartialDecryption::verify`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiedPartialDecryption<E: Curve> {
+    index: PartyIndex,
+    share: Point<E>,
+    ciphertext: Ciphertext<E>,
+}
+
+impl<E: Curve> PartialDecryption<E> {
+    /// Partially decrypts the ciphertext using the key share, and proves correctness of
+    /// the decryption
+    ///
+    /// Index of the party is appended to the `transcript`
+    pub fn new<D: Digest + Clone, R: RngCore + CryptoRng>(
+        rng: &mut R,
+        mut transcript: Transcript<D>,
+        key_share: &KeyShare<E>,
+        ciphertext: &Ciphertext<E>,
+    ) -> Self {
+        transcript.append_u64(b"party_index", key_share.i.0.into());
+        let share = ciphertext.c1 * &key_share.x;
+
+        let mut prover = BundleProver::new(transcript);
+        prover.add_satisfied(
+            rng,
+            relation(Point::generator() * &key_share.x, ciphertext, share),
+            core::slice::from_ref(&key_share.x),
+        );
+        Self {
+            index: key_share.i,
+            share,
+            proof: prover.prove(),
+        }
+    }
+
+    /// Verifies the partial decryption against public shares of the parties
+    ///
+    /// `public_shares[j]` must be the public share $Y_j$ of party $j$. Transcript must be in the
+    /// same state as it was passed to [`PartialDecryption::new`].
+    pub fn verify<D: Digest + Clone, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut Transcript<D>,
+        rng: &mut R,
+        public_shares: &[Point<E>],
+        ciphertext: &Ciphertext<E>,
+    ) -> Result<VerifiedPartialDecryption<E>, InvalidProof> {
+        transcript.append_u64(b"party_index", self.index.0.into());
+        let public_share = public_shares
+            .get(usize::from(self.index))
+            .ok_or(InvalidProof)?;
+        let relation = relation(*public_share, ciphertext, self.share);
+        self.proof
+            .verify(transcript, rng, core::slice::from_ref(&relation))?;
+        Ok(VerifiedPartialDecryption {
+            index: self.index,
+            share: self.share,
+            ciphertext: *ciphertext,
+        })
+    }
+}
+
+impl<E: Curve> VerifiedPartialDecryption<E> {
+    /// Index of the party
+    pub fn index(&self) -> PartyIndex {
+        self.index
+    }
+
+    /// Partial decryption $D_i$
+    pub fn share(&self) -> Point<E> {
+        self.share
+    }
+
+    /// Ciphertext which was partially decrypted
+    pub fn ciphertext(&self) -> Ciphertext<E> {
+        self.ciphertext
+    }
+}
+
+/// Combines verified partial decryptions into the plaintext $M$
+///
+/// Returns error if there are less than `threshold` partial decryptions, if indexes of the
+/// parties are not distinct, or if some partial decryption refers to another ciphertext.
+pub fn combine<E: Curve>(
+    ciphertext: &Ciphertext<E>,
+    threshold: u16,
+    partials: &[VerifiedPartialDecryption<E>],
+) -> Result<Point<E>, CombineError> {
+    if partials.len() < usize::from(threshold) {
+        return Err(CombineError::NotEnoughPartials);
+    }
+    if partials.iter().any(|p| p.ciphertext != *ciphertext) {
+        return Err(CombineError::CiphertextMismatch);
+    }
+    let xs: Vec<NonZero<Scalar<E>>> = partials
+        .iter()
+        .map(|p| p.index.share_index().to_scalar())
+        .collect();
+    let shares: Vec<Point<E>> = partials.iter().map(|p| p.share).collect();
+    let y_c1 = LagrangeBasis::new(Scalar::zero(), &xs)
+        .and_then(|basis| basis.interpolate(&shares))
+        .ok_or(CombineError::DuplicateIndexes)?;
+    Ok(ciphertext.c2 - y_c1)
+}
+
+/// $\log_G Y_i = \log_{C_1} D_i$
+fn relation<E: Curve>(
+    public_share: Point<E>,
+    ciphertext: &Ciphertext<E>,
+    share: Point<E>,
+) -> LinearRelation<E> {
+    LinearRelation::dleq(
+        Point::generator().to_point(),
+        public_share,
+        ciphertext.c1,
+        share,
+    )
+}
+
+/// Partial decryptions can't be combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombineError {
+    /// Less partial decryptions than threshold
+    NotEnoughPartials,
+    /// Several partial decryptions from the same party
+    DuplicateIndexes,
+    /// Partial decryption of a different ciphertext
+    CiphertextMismatch,
+}
+
+impl core::fmt::Display for CombineError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotEnoughPartials => f.write_str("not enough partial decryptions"),
+            Self::DuplicateIndexes => {
+                f.write_str("several partial decryptions from the same party")
+            }
+            Self::CiphertextMismatch => f.write_str("partial decryption of a different ciphertext"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CombineError {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use alloc::vec::Vec;
+
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use super::{combine, CombineError, PartialDecryption};
+    use crate::{
+        elgamal::EncryptionKey, key_share::KeyShare, party_index::PartyIndex,
+        polynomial::Polynomial, transcript::Transcript,
+    };
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    fn key_shares<E: Curve>(rng: &mut DevRng, t: u16, n: u16) -> Vec<KeyShare<E>> {
+        let secret_key = SecretScalar::<E>::random(rng);
+        let f = Polynomial::sample_with_const_term(rng, usize::from(t) - 1, secret_key.clone());
+        let shares: Vec<_> = (0..n)
+            .map(|i| f.eval_ct(&PartyIndex(i).share_index().to_scalar()))
+            .collect();
+        let public_shares: Vec<_> = shares.iter().map(|x| Point::generator() * x).collect();
+        (0..n)
+            .zip(shares)
+            .map(|(i, x)| KeyShare {
+                i: PartyIndex(i),
+                t,
+                x,
+                shared_public_key: Point::generator() * &secret_key,
+                public_shares: public_shares.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn threshold_decryption<E: Curve>() {
+        let mut rng = DevRng::new();
+        for (t, n) in [(1, 1), (2, 3), (3, 5)] {
+            let parties = key_shares::<E>(&mut rng, t, n);
+            let public_shares = &parties[0].public_shares;
+            let ek = EncryptionKey(parties[0].shared_public_key);
+            let message = Point::generator() * Scalar::random(&mut rng);
+            let ciphertext = ek.encrypt(&message, &Scalar::random(&mut rng));
+
+            let verified: Vec<_> = parties
+                .iter()
+                .rev()
+                .take(usize::from(t))
+                .map(|key_share| {
+                    PartialDecryption::new(&mut rng, transcript(), key_share, &ciphertext)
+                        .verify(&mut transcript(), &mut rng, public_shares, &ciphertext)
+                        .unwrap()
+                })
+                .collect();
+            assert_eq!(combine(&ciphertext, t, &verified).unwrap(), message);
+
+            assert_eq!(
+                combine(&ciphertext, t, &verified[1..]),
+                Err(CombineError::NotEnoughPartials)
+            );
+            if t > 1 {
+                let mut duplicated = verified.clone();
+                duplicated[1] = duplicated[0];
+                assert_eq!(
+                    combine(&ciphertext, t, &duplicated),
+                    Err(CombineError::DuplicateIndexes)
+                );
+            }
+            let other = ek.encrypt(&message, &Scalar::random(&mut rng));
+            assert_eq!(
+                combine(&other, t, &verified),
+                Err(CombineError::CiphertextMismatch)
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_partial_decryption<E: Curve>() {
+        let mut rng = DevRng::new();
+        let parties = key_shares::<E>(&mut rng, 2, 3);
+        let public_shares = &parties[0].public_shares;
+        let ek = EncryptionKey(parties[0].shared_public_key);
+        let ciphertext = ek.encrypt(
+            &(Point::generator() * Scalar::random(&mut rng)),
+            &Scalar::random(&mut rng),
+        );
+        let partial = PartialDecryption::new(&mut rng, transcript(), &parties[1], &ciphertext);
+
+        // Wrong share
+        let mut tampered = partial.clone();
+        tampered.share += Point::generator();
+        assert!(tampered
+            .verify(&mut transcript(), &mut rng, public_shares, &ciphertext)
+            .is_err());
+
+        // Claims to be another party
+        let mut tampered = partial.clone();
+        tampered.index = PartyIndex(0);
+        assert!(tampered
+            .verify(&mut transcript(), &mut rng, public_shares, &ciphertext)
+            .is_err());
+
+        // Party index out of range
+        let mut tampered = partial.clone();
+        tampered.index = PartyIndex(3);
+        assert!(tampered
+            .verify(&mut transcript(), &mut rng, public_shares, &ciphertext)
+            .is_err());
+
+        // Another ciphertext
+        let other = ek.encrypt(&Point::generator().to_point(), &Scalar::random(&mut rng));
+        assert!(partial
+            .verify(&mut transcript(), &mut rng, public_shares, &other)
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
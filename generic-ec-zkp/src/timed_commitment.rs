@@ -0,0 +1,260 @@
+//! Pedersen commitments with openings bound to a verifiable delay function
+//!
+//! In commit-and-open protocols it's sometimes required that a commitment can't be opened
+//! earlier than some time after it was published, e.g. to prevent last-revealer bias or
+//! front-running. This module ties a [Pedersen commitment](crate::pedersen) $C$ to an output of
+//! a verifiable delay function (VDF) evaluated on a challenge derived from $C$: opening is only
+//! accepted along with a valid VDF output, which takes a fixed amount of sequential work to
+//! compute after $C$ is known.
+//!
+//! VDF itself is out of scope of this crate: it's plugged in via the [`Vdf`] trait, while the EC
+//! math stays here.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Scalar, curves::Secp256k1, hash_to_curve::Tag};
+//! use generic_ec_zkp::{pedersen, timed_commitment::{self, Vdf}, transcript::Transcript};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Toy VDF: iterated hashing, verified by re-computation
+//! struct IteratedHash(u32);
+//! impl Vdf for IteratedHash {
+//!     type Output = [u8; 32];
+//!     type Proof = ();
+//!     fn eval(&self, challenge: &[u8]) -> ([u8; 32], ()) {
+//!         use sha2::Digest;
+//!         let mut output: [u8; 32] = sha2::Sha256::digest(challenge).into();
+//!         for _ in 1..self.0 {
+//!             output = sha2::Sha256::digest(output).into();
+//!         }
+//!         (output, ())
+//!     }
+//!     fn verify(&self, challenge: &[u8], output: &[u8; 32], _proof: &()) -> bool {
+//!         self.eval(challenge).0 == *output
+//!     }
+//! }
+//!
+//! const TAG: Tag = Tag::new_unwrap(b"MYAPP-pedersen-v0.1.0");
+//! let params = pedersen::Params::<Secp256k1>::derive(TAG)?;
+//! let vdf = IteratedHash(1000);
+//! let transcript = || Transcript::<sha2::Sha256>::new(b"MYAPP-auction-bid");
+//!
+//! // Commit to the bid
+//! let bid = Scalar::from(42);
+//! let blinding = Scalar::random(&mut rng);
+//! let commitment = params.commit(&bid, &blinding);
+//!
+//! // ... commitment is published, and after a while it's opened
+//! let opening = timed_commitment::open(transcript(), &vdf, &commitment, bid, blinding);
+//! opening.verify(&mut transcript(), &params, &vdf, &commitment)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! * Commitment: $C = v \cdot G + r \cdot H$
+//! * VDF challenge: $c = \text{Hash}(\text{transcript} \| C)$
+//! * Opening: $(v, r, y, \pi)$ where $(y, \pi) = \text{VDF.Eval}(c)$
+//! * Verification: $C = v \cdot G + r \cdot H$ and $\text{VDF.Verify}(c, y, \pi)$
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar};
+
+use crate::{pedersen, transcript::Transcript};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Verifiable delay function
+pub trait Vdf {
+    /// VDF output
+    type Output;
+    /// Proof that output was computed correctly
+    type Proof;
+
+    /// Evaluates VDF on the `challenge`
+    ///
+    /// Takes a fixed amount of sequential work
+    fn eval(&self, challenge: &[u8]) -> (Self::Output, Self::Proof);
+
+    /// Verifies that `output` is a VDF evaluation on the `challenge`
+    ///
+    /// Should be much faster than [evaluation](Vdf::eval)
+    fn verify(&self, challenge: &[u8], output: &Self::Output, proof: &Self::Proof) -> bool;
+}
+
+/// Opening of Pedersen commitment bound to the VDF output
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(
+        serialize = "V::Output: Serialize, V::Proof: Serialize",
+        deserialize = "V::Output: Deserialize<'de>, V::Proof: Deserialize<'de>"
+    ))
+)]
+pub struct TimedOpening<E: Curve, V: Vdf> {
+    /// Committed value $v$
+    pub value: Scalar<E>,
+    /// Blinding $r$
+    pub blinding: Scalar<E>,
+    /// VDF output $y$
+    pub vdf_output: V::Output,
+    /// VDF proof $\pi$
+    pub vdf_proof: V::Proof,
+}
+
+/// Derives VDF challenge from the commitment
+///
+/// Commitment is appended to the `transcript`
+pub fn vdf_challenge<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    commitment: &Point<E>,
+) -> generic_ec::EncodedScalar<E> {
+    transcript.append_point(b"timed_commitment/commitment", commitment);
+    transcript
+        .challenge_scalar::<E>(b"timed_commitment/vdf_challenge")
+        .to_be_bytes()
+}
+
+/// Opens the commitment, evaluating the VDF
+///
+/// Takes as much time as [`Vdf::eval`]. Opening is not validated: it simply won't verify if
+/// `value` and `blinding` don't match the `commitment`.
+pub fn open<E: Curve, V: Vdf, D: Digest + Clone>(
+    mut transcript: Transcript<D>,
+    vdf: &V,
+    commitment: &Point<E>,
+    value: Scalar<E>,
+    blinding: Scalar<E>,
+) -> TimedOpening<E, V> {
+    let challenge = vdf_challenge(&mut transcript, commitment);
+    let (vdf_output, vdf_proof) = vdf.eval(&challenge);
+    TimedOpening {
+        value,
+        blinding,
+        vdf_output,
+        vdf_proof,
+    }
+}
+
+impl<E: Curve, V: Vdf> TimedOpening<E, V> {
+    /// Verifies that opening matches the commitment and VDF output is valid
+    ///
+    /// Transcript must be in the same state as it was passed to [`open`].
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        params: &pedersen::Params<E>,
+        vdf: &V,
+        commitment: &Point<E>,
+    ) -> Result<(), InvalidTimedOpening> {
+        if params.commit(&self.value, &self.blinding) != *commitment {
+            return Err(InvalidTimedOpening);
+        }
+        let challenge = vdf_challenge(transcript, commitment);
+        if vdf.verify(&challenge, &self.vdf_output, &self.vdf_proof) {
+            Ok(())
+        } else {
+            Err(InvalidTimedOpening)
+        }
+    }
+}
+
+/// Opening doesn't match the commitment, or VDF output is invalid
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidTimedOpening;
+
+impl core::fmt::Display for InvalidTimedOpening {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid timed opening")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidTimedOpening {}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{hash_to_curve::FromHash, Curve, Point, Scalar};
+    use rand_dev::DevRng;
+    use sha2::Digest;
+
+    use super::Vdf;
+    use crate::{pedersen, transcript::Transcript};
+
+    struct IteratedHash(u32);
+
+    impl Vdf for IteratedHash {
+        type Output = [u8; 32];
+        type Proof = ();
+
+        fn eval(&self, challenge: &[u8]) -> ([u8; 32], ()) {
+            let mut output: [u8; 32] = sha2::Sha256::digest(challenge).into();
+            for _ in 1..self.0 {
+                output = sha2::Sha256::digest(output).into();
+            }
+            (output, ())
+        }
+
+        fn verify(&self, challenge: &[u8], output: &[u8; 32], _proof: &()) -> bool {
+            self.eval(challenge).0 == *output
+        }
+    }
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn timed_opening<E: Curve>()
+    where
+        Point<E>: FromHash,
+    {
+        let mut rng = DevRng::new();
+        let params =
+            pedersen::Params::<E>::derive(generic_ec::hash_to_curve::Tag::new_unwrap(b"test"))
+                .unwrap();
+        let vdf = IteratedHash(100);
+
+        let value = Scalar::random(&mut rng);
+        let blinding = Scalar::random(&mut rng);
+        let commitment = params.commit(&value, &blinding);
+
+        let opening = super::open(transcript(), &vdf, &commitment, value, blinding);
+        opening
+            .verify(&mut transcript(), &params, &vdf, &commitment)
+            .unwrap();
+
+        // VDF output must be computed on the challenge derived from this commitment
+        let other_commitment = params.commit(&value, &Scalar::random(&mut rng));
+        let other = super::open(transcript(), &vdf, &other_commitment, value, blinding);
+        assert!(other
+            .verify(&mut transcript(), &params, &vdf, &commitment)
+            .is_err());
+
+        // VDF output must be computed with full amount of work
+        let cheap = super::open(
+            transcript(),
+            &IteratedHash(99),
+            &commitment,
+            value,
+            blinding,
+        );
+        assert!(cheap
+            .verify(&mut transcript(), &params, &vdf, &commitment)
+            .is_err());
+
+        // Opening must match the commitment
+        let mut wrong = opening;
+        wrong.value += Scalar::one();
+        assert!(wrong
+            .verify(&mut transcript(), &params, &vdf, &commitment)
+            .is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+}
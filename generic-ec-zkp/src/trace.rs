@@ -0,0 +1,39 @@
+//! Instrumentation of verification failures via `tracing` crate
+//!
+//! When `tracing` feature is disabled, macros from this module expand to nothing.
+
+/// Emits `tracing` event reporting verification failure
+///
+/// Event contains curve name, failure reason, and optionally a statement hash: fingerprint
+/// of the transcript which the statement was appended to (see [`Transcript::fingerprint`]).
+///
+/// [`Transcript::fingerprint`]: crate::transcript::Transcript::fingerprint
+macro_rules! verification_failed {
+    ($curve:ty, $transcript:expr, $reason:literal) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            curve = <$curve as generic_ec::Curve>::CURVE_NAME,
+            statement = %$crate::trace::Hex(&$transcript.fingerprint()),
+            concat!("verification failed: ", $reason)
+        );
+    };
+    ($curve:ty, $reason:literal) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            curve = <$curve as generic_ec::Curve>::CURVE_NAME,
+            concat!("verification failed: ", $reason)
+        );
+    };
+}
+
+/// Displays bytes in hex
+#[cfg(feature = "tracing")]
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+pub struct Hex<'b>(pub &'b [u8]);
+
+#[cfg(feature = "tracing")]
+impl core::fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
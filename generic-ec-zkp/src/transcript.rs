@@ -0,0 +1,118 @@
+//! Fiat-Shamir transcript
+//!
+//! [`Transcript`] turns interactive protocols into non-interactive ones: instead of receiving
+//! challenges from verifier, both prover and verifier absorb all the messages of the protocol
+//! into the transcript and derive challenges from it.
+//!
+//! Every message appended to the transcript is prefixed with its label and length, so two different
+//! sequences of messages never produce the same transcript.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1};
+//! use generic_ec_zkp::transcript::Transcript;
+//!
+//! # let point = Point::<Secp256k1>::generator().to_point();
+//! let mut prover = Transcript::<sha2::Sha256>::new(b"my-protocol");
+//! prover.append_point(b"commitment", &point);
+//! let challenge: Scalar<Secp256k1> = prover.challenge_scalar(b"challenge");
+//!
+//! // Verifier reconstructs the same transcript and obtains the same challenge
+//! let mut verifier = Transcript::<sha2::Sha256>::new(b"my-protocol");
+//! verifier.append_point(b"commitment", &point);
+//! assert_eq!(challenge, verifier.challenge_scalar(b"challenge"));
+//! ```
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar};
+
+/// Fiat-Shamir transcript built on top of hash function `D`
+///
+/// See [module-level docs](self) for details
+#[derive(Clone)]
+pub struct Transcript<D: Digest> {
+    hash: D,
+}
+
+impl<D: Digest + Clone> Transcript<D> {
+    /// Starts a new transcript
+    ///
+    /// `domain` separates transcripts of different protocols
+    pub fn new(domain: &[u8]) -> Self {
+        let mut transcript = Self { hash: D::new() };
+        transcript.append_message(b"dom-sep", domain);
+        transcript
+    }
+
+    /// Appends a message to the transcript
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.append_framed(label);
+        self.append_framed(message);
+    }
+
+    /// Appends `u64` integer to the transcript
+    pub fn append_u64(&mut self, label: &[u8], n: u64) {
+        self.append_message(label, &n.to_be_bytes())
+    }
+
+    /// Appends a point to the transcript
+    ///
+    /// Point is appended in compressed form
+    pub fn append_point<E: Curve>(&mut self, label: &[u8], point: &Point<E>) {
+        self.append_message(label, &point.to_bytes(true))
+    }
+
+    /// Appends a list of points to the transcript
+    pub fn append_points<E: Curve>(&mut self, label: &[u8], points: &[Point<E>]) {
+        self.append_u64(label, points.len() as u64);
+        for point in points {
+            self.append_point(label, point)
+        }
+    }
+
+    /// Appends a scalar to the transcript
+    pub fn append_scalar<E: Curve>(&mut self, label: &[u8], scalar: &Scalar<E>) {
+        self.append_message(label, &scalar.to_be_bytes())
+    }
+
+    /// Derives a challenge from the transcript
+    ///
+    /// Challenge is derived from everything appended to the transcript so far. The challenge
+    /// itself is appended to the transcript afterwards, so consequent challenges are different
+    /// even if nothing else was appended in between.
+    ///
+    /// In order to keep challenge distribution close to uniform, the scalar is obtained by reducing
+    /// two hash outputs modulo group order.
+    pub fn challenge_scalar<E: Curve>(&mut self, label: &[u8]) -> Scalar<E> {
+        self.append_framed(label);
+        let [hi, lo] = [0u8, 1].map(|i| {
+            let mut hash = self.hash.clone();
+            hash.update([i]);
+            hash.finalize()
+        });
+        let challenge = lo
+            .iter()
+            .fold(Scalar::<E>::from_be_bytes_mod_order(&hi), |acc, byte| {
+                acc * Scalar::from(0x100_u16) + Scalar::from(*byte)
+            });
+        self.append_scalar(b"challenge", &challenge);
+        challenge
+    }
+
+    /// Returns hash of everything appended to the transcript so far
+    ///
+    /// Transcript is not modified. Fingerprint identifies the statement and the protocol messages
+    /// absorbed by the transcript, e.g. it's reported in logs when verification fails (requires
+    /// `tracing` feature). It's computed independently of challenges, so revealing it doesn't
+    /// reveal any challenge.
+    pub fn fingerprint(&self) -> digest::Output<D> {
+        let mut hash = self.hash.clone();
+        hash.update([2u8]);
+        hash.finalize()
+    }
+
+    fn append_framed(&mut self, bytes: &[u8]) {
+        self.hash.update((bytes.len() as u64).to_be_bytes());
+        self.hash.update(bytes);
+    }
+}
@@ -0,0 +1,432 @@
+//! Verifiable encryption of a discrete log $\Pi^\text{venc}$
+//!
+//! Lets prover encrypt secret $x$ under [ElGamal](crate::elgamal) encryption key $Y$ and convince
+//! anyone that the ciphertext can be decrypted to discrete log of public point $X = x \cdot G$.
+//! It's a building block for key escrow and recoverable keys: the owner of decryption key can
+//! recover $x$, while everyone else can check that the ciphertext is well-formed.
+//!
+//! ElGamal only encrypts $x$ "in the exponent", so decryption yields $x \cdot G$ rather than $x$.
+//! To make $x$ recoverable, it's split into bytes $m_1, \dots, m_k$ (big-endian, $k$ equals
+//! [scalar size](Scalar::serialized_len)), and every byte is encrypted separately. Prover proves
+//! that every ciphertext encrypts a byte (via [small range proof](crate::bit_proof)), and that
+//! bytes compose $x$ (via Schnorr-like $\Sigma$ protocol). Decryptor brute-forces every byte from
+//! $m_i \cdot G$, which takes at most $256$ point additions per byte.
+//!
+//! The proof is made non-interactive via Fiat-Shamir [transcript](crate::transcript).
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+//! use generic_ec_zkp::{elgamal, transcript::Transcript, verifiable_encryption};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! // Escrow agent generates the key pair
+//! let (dk, ek) = elgamal::keygen::<Secp256k1, _>(&mut rng);
+//!
+//! // Prover encrypts its secret key `x` to the escrow agent
+//! let x = SecretScalar::random(&mut rng);
+//! let X = Point::generator() * &x;
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! let (ciphertext, proof) = verifiable_encryption::prove(&mut transcript, &mut rng, &ek, &x)?;
+//!
+//! // Anyone can check that ciphertext encrypts discrete log of `X`
+//! let mut transcript = Transcript::<sha2::Sha256>::new(b"example");
+//! proof.verify(&mut transcript, &ek, &X, &ciphertext)?;
+//!
+//! // Escrow agent can recover `x`
+//! let recovered = verifiable_encryption::decrypt(&dk, &ciphertext)?;
+//! assert_eq!(Point::generator() * &recovered, X);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Algorithm
+//!
+//! Let $w_i = 256^{k-i}$, so $x = \sum_i w_i m_i$.
+//!
+//! * Prove
+//!   1. Prover samples $r_i \gets \Z_q$ and encrypts every byte: $(C_{1,i}, C_{2,i}) = (r_i \cdot G, m_i \cdot G + r_i \cdot Y)$
+//!   2. Prover proves that $C_{2,i}$ is a [Pedersen commitment](crate::pedersen) with generators
+//!      $(G, Y)$ to value in range $[0, 2^8)$
+//!   3. Prover samples $a_i, b_i \gets \Z_q$ and sets $A_{1,i} = a_i \cdot G$,
+//!      $A_{2,i} = b_i \cdot G + a_i \cdot Y$, $A_X = (\sum_i w_i b_i) \cdot G$
+//!   4. Prover obtains challenge $e$ from the transcript, sets $z_{r,i} = a_i + e r_i$,
+//!      $z_{m,i} = b_i + e m_i$
+//! * Verification \
+//!   Verifier checks range proofs, obtains challenge $e$, and checks that
+//!   $z_{r,i} \cdot G \\? A_{1,i} + e \cdot C_{1,i}$,
+//!   $z_{m,i} \cdot G + z_{r,i} \cdot Y \\? A_{2,i} + e \cdot C_{2,i}$ for every $i$, and
+//!   $(\sum_i w_i z_{m,i}) \cdot G \\? A_X + e \cdot X$
+
+use alloc::vec::Vec;
+use core::iter;
+
+use digest::Digest;
+use generic_ec::{Curve, Point, Scalar, SecretScalar};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    bit_proof::{self, SmallRangeProof},
+    elgamal, pedersen,
+    transcript::Transcript,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Encryption of a scalar split into bytes
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+pub struct Ciphertext<E: Curve> {
+    /// `chunks[i]` is an encryption of $i$-th most significant byte of the scalar
+    pub chunks: Vec<elgamal::Ciphertext<E>>,
+}
+
+/// Proof that [`Ciphertext`] encrypts discrete log of a point
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+#[allow(non_snake_case)]
+pub struct Proof<E: Curve> {
+    /// `chunks[i]` proves that `i`-th ciphertext is well-formed and encrypts a byte
+    pub chunks: Vec<ChunkProof<E>>,
+    /// Commitment $A_X$
+    pub A_x: Point<E>,
+}
+
+/// Proof that a single ciphertext is well-formed and encrypts a byte
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+#[cfg_attr(feature = "udigest", derive(udigest::Digestable), udigest(bound = ""))]
+#[allow(non_snake_case)]
+pub struct ChunkProof<E: Curve> {
+    /// Proof that encrypted value is in range $[0, 2^8)$
+    pub range: SmallRangeProof<E>,
+    /// Commitment $A_1$
+    pub A1: Point<E>,
+    /// Commitment $A_2$
+    pub A2: Point<E>,
+    /// Response $z_r$
+    pub z_r: Scalar<E>,
+    /// Response $z_m$
+    pub z_m: Scalar<E>,
+}
+
+/// Encrypts `secret` under `encryption_key` and proves that ciphertext encrypts discrete log of
+/// $X = \text{secret} \cdot G$
+///
+/// Point $X$ and the encryption key are appended to the transcript before deriving the challenge.
+#[allow(non_snake_case)]
+pub fn prove<E: Curve, D: Digest + Clone, R: RngCore + CryptoRng>(
+    transcript: &mut Transcript<D>,
+    rng: &mut R,
+    encryption_key: &elgamal::EncryptionKey<E>,
+    secret: &SecretScalar<E>,
+) -> Result<(Ciphertext<E>, Proof<E>), ProveError> {
+    let X = Point::generator() * secret;
+    let bytes = secret.expose_secret(|x| x.to_be_bytes());
+    let randomness: Vec<Scalar<E>> = iter::repeat_with(|| Scalar::random(rng))
+        .take(bytes.len())
+        .collect();
+    let ciphertext = Ciphertext {
+        chunks: bytes
+            .iter()
+            .zip(&randomness)
+            .map(|(m_i, r_i)| encryption_key.encrypt_scalar(&Scalar::from(*m_i), r_i))
+            .collect(),
+    };
+    append_statement(transcript, encryption_key, &X, &ciphertext);
+
+    let params = pedersen_params(encryption_key);
+    let range_proofs = bytes
+        .iter()
+        .zip(&randomness)
+        .map(|(m_i, r_i)| {
+            bit_proof::prove_small_range(transcript, &params, rng, u64::from(*m_i), r_i, 8)
+                .map(|(proof, _commitment)| proof)
+                .map_err(|_| ProveError)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let a: Vec<Scalar<E>> = iter::repeat_with(|| Scalar::random(rng))
+        .take(bytes.len())
+        .collect();
+    let b: Vec<Scalar<E>> = iter::repeat_with(|| Scalar::random(rng))
+        .take(bytes.len())
+        .collect();
+    let A1: Vec<Point<E>> = a.iter().map(|a_i| Point::generator() * a_i).collect();
+    let A2: Vec<Point<E>> = a
+        .iter()
+        .zip(&b)
+        .map(|(a_i, b_i)| params.commit(b_i, a_i))
+        .collect();
+    let A_x = Point::generator() * weighted_sum(&b);
+
+    let e = challenge(transcript, &A1, &A2, &A_x);
+
+    let chunks = range_proofs
+        .into_iter()
+        .zip(A1.into_iter().zip(A2))
+        .zip(a.iter().zip(&b))
+        .zip(bytes.iter().zip(&randomness))
+        .map(|(((range, (A1, A2)), (a_i, b_i)), (m_i, r_i))| ChunkProof {
+            range,
+            A1,
+            A2,
+            z_r: a_i + e * r_i,
+            z_m: b_i + e * Scalar::from(*m_i),
+        })
+        .collect();
+
+    Ok((ciphertext, Proof { chunks, A_x }))
+}
+
+impl<E: Curve> Proof<E> {
+    /// Verifies that `ciphertext` encrypts discrete log of $X$ under `encryption_key`
+    ///
+    /// Transcript must be in the same state as it was at the moment of [proving](prove).
+    #[allow(non_snake_case)]
+    pub fn verify<D: Digest + Clone>(
+        &self,
+        transcript: &mut Transcript<D>,
+        encryption_key: &elgamal::EncryptionKey<E>,
+        X: &Point<E>,
+        ciphertext: &Ciphertext<E>,
+    ) -> Result<(), InvalidProof> {
+        let k = Scalar::<E>::serialized_len();
+        if ciphertext.chunks.len() != k || self.chunks.len() != k {
+            return Err(InvalidProof);
+        }
+        append_statement(transcript, encryption_key, X, ciphertext);
+
+        let params = pedersen_params(encryption_key);
+        for (chunk, ct) in self.chunks.iter().zip(&ciphertext.chunks) {
+            chunk
+                .range
+                .verify(transcript, &params, &ct.c2, 8)
+                .map_err(|_| InvalidProof)?;
+        }
+
+        let A1: Vec<_> = self.chunks.iter().map(|c| c.A1).collect();
+        let A2: Vec<_> = self.chunks.iter().map(|c| c.A2).collect();
+        let e = challenge(transcript, &A1, &A2, &self.A_x);
+
+        for (chunk, ct) in self.chunks.iter().zip(&ciphertext.chunks) {
+            let well_formed = Point::generator() * chunk.z_r == chunk.A1 + ct.c1 * e
+                && params.commit(&chunk.z_m, &chunk.z_r) == chunk.A2 + ct.c2 * e;
+            if !well_formed {
+                return Err(InvalidProof);
+            }
+        }
+
+        let z_m: Vec<_> = self.chunks.iter().map(|c| c.z_m).collect();
+        if Point::generator() * weighted_sum(&z_m) == self.A_x + X * e {
+            Ok(())
+        } else {
+            Err(InvalidProof)
+        }
+    }
+}
+
+/// Decrypts the secret
+///
+/// Returns error if any of chunks doesn't decrypt to a byte, which never happens if the
+/// ciphertext was accompanied by a valid proof.
+pub fn decrypt<E: Curve>(
+    decryption_key: &elgamal::DecryptionKey<E>,
+    ciphertext: &Ciphertext<E>,
+) -> Result<SecretScalar<E>, DecryptError> {
+    let bytes = ciphertext
+        .chunks
+        .iter()
+        .map(|chunk| {
+            let m = decryption_key.decrypt(chunk);
+            iter::successors(Some(Point::<E>::zero()), |acc| {
+                Some(acc + Point::generator())
+            })
+            .take(256)
+            .position(|candidate| candidate == m)
+            .and_then(|byte| u8::try_from(byte).ok())
+            .ok_or(DecryptError)
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+    let mut secret = Scalar::from_be_bytes_mod_order(&bytes);
+    Ok(SecretScalar::new(&mut secret))
+}
+
+/// Pedersen parameters $(G, Y)$: second component of ElGamal ciphertext is a commitment
+/// w.r.t. them
+fn pedersen_params<E: Curve>(encryption_key: &elgamal::EncryptionKey<E>) -> pedersen::Params<E> {
+    pedersen::Params {
+        g: Point::generator().to_point(),
+        h: encryption_key.0,
+    }
+}
+
+#[allow(non_snake_case)]
+fn append_statement<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    encryption_key: &elgamal::EncryptionKey<E>,
+    X: &Point<E>,
+    ciphertext: &Ciphertext<E>,
+) {
+    transcript.append_point(b"verifiable_encryption/Y", &encryption_key.0);
+    transcript.append_point(b"verifiable_encryption/X", X);
+    transcript.append_u64(
+        b"verifiable_encryption/chunks",
+        ciphertext.chunks.len() as u64,
+    );
+    for chunk in &ciphertext.chunks {
+        transcript.append_point(b"verifiable_encryption/C1", &chunk.c1);
+        transcript.append_point(b"verifiable_encryption/C2", &chunk.c2);
+    }
+}
+
+#[allow(non_snake_case)]
+fn challenge<E: Curve, D: Digest + Clone>(
+    transcript: &mut Transcript<D>,
+    A1: &[Point<E>],
+    A2: &[Point<E>],
+    A_x: &Point<E>,
+) -> Scalar<E> {
+    transcript.append_points(b"verifiable_encryption/A1", A1);
+    transcript.append_points(b"verifiable_encryption/A2", A2);
+    transcript.append_point(b"verifiable_encryption/A_x", A_x);
+    transcript.challenge_scalar(b"verifiable_encryption/e")
+}
+
+/// Computes $\sum_i 256^{k-i} x_i$
+fn weighted_sum<E: Curve>(xs: &[Scalar<E>]) -> Scalar<E> {
+    let base = Scalar::from(256u16);
+    xs.iter().fold(Scalar::zero(), |acc, x_i| acc * base + x_i)
+}
+
+/// Error during proving
+///
+/// Never returned unless there's a bug in the implementation
+#[derive(Debug, Clone, Copy)]
+pub struct ProveError;
+
+impl core::fmt::Display for ProveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("internal error: couldn't prove verifiable encryption")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProveError {}
+
+/// Decryption error
+///
+/// Returned when ciphertext doesn't encrypt a byte-chunked scalar
+#[derive(Debug, Clone, Copy)]
+pub struct DecryptError;
+
+impl core::fmt::Display for DecryptError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ciphertext chunk doesn't decrypt to a byte")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecryptError {}
+
+/// Invalid proof error
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidProof;
+
+impl core::fmt::Display for InvalidProof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid verifiable encryption proof")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidProof {}
+
+#[cfg(test)]
+#[generic_tests::define]
+#[allow(non_snake_case)]
+mod tests {
+    use generic_ec::{Curve, Point, Scalar, SecretScalar};
+    use rand_dev::DevRng;
+
+    use crate::{elgamal, transcript::Transcript};
+
+    fn transcript() -> Transcript<sha2::Sha256> {
+        Transcript::new(b"test")
+    }
+
+    #[test]
+    fn encrypt_verify_decrypt<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (dk, ek) = elgamal::keygen::<E, _>(&mut rng);
+
+        let x = SecretScalar::<E>::random(&mut rng);
+        let X = Point::generator() * &x;
+        let (ciphertext, proof) = super::prove(&mut transcript(), &mut rng, &ek, &x).unwrap();
+
+        proof
+            .verify(&mut transcript(), &ek, &X, &ciphertext)
+            .unwrap();
+        let recovered = super::decrypt(&dk, &ciphertext).unwrap();
+        assert_eq!(recovered.expose_secret(|x| *x), x.expose_secret(|x| *x));
+
+        // Proof doesn't verify for a different point
+        let wrong_X = X + Point::generator();
+        assert!(proof
+            .verify(&mut transcript(), &ek, &wrong_X, &ciphertext)
+            .is_err());
+
+        // Proof doesn't verify for a different encryption key
+        let (_, other_ek) = elgamal::keygen::<E, _>(&mut rng);
+        assert!(proof
+            .verify(&mut transcript(), &other_ek, &X, &ciphertext)
+            .is_err());
+    }
+
+    #[test]
+    fn tampered_ciphertext_is_rejected<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (_, ek) = elgamal::keygen::<E, _>(&mut rng);
+
+        let x = SecretScalar::<E>::random(&mut rng);
+        let X = Point::generator() * &x;
+        let (ciphertext, proof) = super::prove(&mut transcript(), &mut rng, &ek, &x).unwrap();
+
+        // Shift the value from one chunk to another: decrypted scalar remains the same, but
+        // chunks go out of range
+        let mut tampered = ciphertext.clone();
+        let last = tampered.chunks.len() - 1;
+        let one = ek.encrypt_scalar(&Scalar::one(), &Scalar::zero());
+        tampered.chunks[last] = tampered.chunks[last] + one * &Scalar::from(256u16);
+        tampered.chunks[last - 1] = tampered.chunks[last - 1] - one;
+        assert!(proof.verify(&mut transcript(), &ek, &X, &tampered).is_err());
+
+        let mut truncated = ciphertext;
+        truncated.chunks.pop();
+        assert!(proof
+            .verify(&mut transcript(), &ek, &X, &truncated)
+            .is_err());
+    }
+
+    #[test]
+    fn decrypt_rejects_non_byte_chunks<E: Curve>() {
+        let mut rng = DevRng::new();
+        let (dk, ek) = elgamal::keygen::<E, _>(&mut rng);
+        let ciphertext = super::Ciphertext {
+            chunks: [255u16, 256]
+                .iter()
+                .map(|m| ek.encrypt_scalar(&Scalar::from(*m), &Scalar::random(&mut rng)))
+                .collect(),
+        };
+        assert!(super::decrypt(&dk, &ciphertext).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+}
@@ -2,7 +2,40 @@ use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use crate::{Curve, Generator, NonZero, Point, Scalar, SecretScalar};
 
+/// Gives access to the value of `Scalar<E>`, `SecretScalar<E>`, and their non-zero versions
+///
+/// `SecretScalar<E>` intentionally doesn't implement `AsRef<Scalar<E>>`, so arithmetic ops use
+/// this crate-private trait instead
+pub(crate) trait AsScalar<E: Curve> {
+    fn as_scalar(&self) -> &Scalar<E>;
+}
+
+impl<E: Curve> AsScalar<E> for Scalar<E> {
+    fn as_scalar(&self) -> &Scalar<E> {
+        self
+    }
+}
+
+impl<E: Curve> AsScalar<E> for SecretScalar<E> {
+    fn as_scalar(&self) -> &Scalar<E> {
+        SecretScalar::as_scalar(self)
+    }
+}
+
+impl<E: Curve, S: AsScalar<E>> AsScalar<E> for NonZero<S> {
+    fn as_scalar(&self) -> &Scalar<E> {
+        (**self).as_scalar()
+    }
+}
+
+impl<E: Curve, S: AsScalar<E>> AsScalar<E> for &S {
+    fn as_scalar(&self) -> &Scalar<E> {
+        (**self).as_scalar()
+    }
+}
+
 mod laws {
+    use super::AsScalar;
     use crate::{
         as_raw::AsRaw,
         core::{self, *},
@@ -134,10 +167,10 @@ mod laws {
     /// Therefore, (2) holds.
     #[inline]
     pub fn mul_of_scalar_at_point_is_valid_point<E: Curve>(
-        n: impl AsRef<Scalar<E>>,
+        n: impl AsScalar<E>,
         a: &Point<E>,
     ) -> Point<E> {
-        let prod = Multiplicative::mul(n.as_ref().as_raw(), a.as_raw());
+        let prod = Multiplicative::mul(n.as_scalar().as_raw(), a.as_raw());
         // Correctness: refer to doc comment of the function
         Point::from_raw_unchecked(prod)
     }
@@ -146,7 +179,7 @@ mod laws {
     #[inline]
     pub fn mul_of_point_at_scalar_is_valid_point<E: Curve>(
         a: &Point<E>,
-        b: impl AsRef<Scalar<E>>,
+        b: impl AsScalar<E>,
     ) -> Point<E> {
         mul_of_scalar_at_point_is_valid_point(b, a)
     }
@@ -156,10 +189,10 @@ mod laws {
     /// Proof is the same as in [`mul_of_scalar_at_point_is_valid_point`] with $A = \G$
     #[inline]
     pub fn mul_of_scalar_at_generator_is_valid_point<E: Curve>(
-        n: impl AsRef<Scalar<E>>,
+        n: impl AsScalar<E>,
         _g: &Generator<E>,
     ) -> Point<E> {
-        let prod = Multiplicative::mul(n.as_ref().as_raw(), &core::CurveGenerator);
+        let prod = Multiplicative::mul(n.as_scalar().as_raw(), &core::CurveGenerator);
         // Correctness: refer to doc comment of the function
         Point::from_raw_unchecked(prod)
     }
@@ -168,7 +201,7 @@ mod laws {
     #[inline]
     pub fn mul_of_generator_at_scalar_is_valid_point<E: Curve>(
         g: &Generator<E>,
-        n: impl AsRef<Scalar<E>>,
+        n: impl AsScalar<E>,
     ) -> Point<E> {
         mul_of_scalar_at_generator_is_valid_point(n, g)
     }
@@ -185,7 +218,7 @@ mod laws {
     /// As $n$ is valid `Scalar<E>`, it's less than curve `group_order`, therefore $n A \ne O$.
     #[inline]
     pub fn mul_of_nonzero_scalar_at_nonzero_point_is_valid_nonzero_point<E: Curve>(
-        n: &(impl AsRef<Scalar<E>> + AlwaysNonZero),
+        n: &(impl AsScalar<E> + AlwaysNonZero),
         a: &NonZero<Point<E>>,
     ) -> NonZero<Point<E>> {
         let prod = mul_of_scalar_at_point_is_valid_point(n, a);
@@ -197,7 +230,7 @@ mod laws {
     #[inline]
     pub fn mul_of_nonzero_point_at_nonzero_scalar_is_valid_nonzero_point<E: Curve>(
         a: &NonZero<Point<E>>,
-        n: &(impl AsRef<Scalar<E>> + AlwaysNonZero),
+        n: &(impl AsScalar<E> + AlwaysNonZero),
     ) -> NonZero<Point<E>> {
         mul_of_nonzero_scalar_at_nonzero_point_is_valid_nonzero_point(n, a)
     }
@@ -207,7 +240,7 @@ mod laws {
     /// Proof is the same as in [`mul_of_nonzero_scalar_at_nonzero_point_is_valid_nonzero_point`]
     #[inline]
     pub fn mul_of_nonzero_scalar_at_generator_is_valid_nonzero_point<E: Curve>(
-        n: &(impl AsRef<Scalar<E>> + AlwaysNonZero),
+        n: &(impl AsScalar<E> + AlwaysNonZero),
         g: &Generator<E>,
     ) -> NonZero<Point<E>> {
         let prod = mul_of_scalar_at_generator_is_valid_point(n, g);
@@ -219,7 +252,7 @@ mod laws {
     #[inline]
     pub fn mul_of_generator_at_nonzero_scalar_is_valid_nonzero_point<E: Curve>(
         g: &Generator<E>,
-        n: &(impl AsRef<Scalar<E>> + AlwaysNonZero),
+        n: &(impl AsScalar<E> + AlwaysNonZero),
     ) -> NonZero<Point<E>> {
         mul_of_nonzero_scalar_at_generator_is_valid_nonzero_point(n, g)
     }
@@ -243,8 +276,8 @@ mod laws {
     /// Product of two non-zero integers mod $q$ can be zero if, and only if, $A \cdot B$ divides $q$.
     /// It's not possible as $q$ is prime and $A,B < q$.
     pub fn non_zero_scalar_at_non_zero_scalar_is_non_zero_scalar<E: Curve>(
-        a: &(impl AsRef<Scalar<E>> + AlwaysNonZero),
-        b: &(impl AsRef<Scalar<E>> + AlwaysNonZero),
+        a: &(impl AsScalar<E> + AlwaysNonZero),
+        b: &(impl AsScalar<E> + AlwaysNonZero),
     ) -> NonZero<Scalar<E>> {
         let prod = super::scalar::mul(a, b);
         // Correctness: refer to doc commnet of the function
@@ -253,25 +286,26 @@ mod laws {
 }
 
 mod scalar {
+    use super::AsScalar;
     use crate::as_raw::{AsRaw, FromRaw};
     use crate::{core::*, SecretScalar};
     use crate::{NonZero, Scalar};
 
     #[inline]
-    pub fn add<E: Curve>(a: impl AsRef<Scalar<E>>, b: impl AsRef<Scalar<E>>) -> Scalar<E> {
-        let sum = Additive::add(a.as_ref().as_raw(), b.as_ref().as_raw());
+    pub fn add<E: Curve>(a: impl AsScalar<E>, b: impl AsScalar<E>) -> Scalar<E> {
+        let sum = Additive::add(a.as_scalar().as_raw(), b.as_scalar().as_raw());
         Scalar::from_raw(sum)
     }
 
     #[inline]
-    pub fn sub<E: Curve>(a: impl AsRef<Scalar<E>>, b: impl AsRef<Scalar<E>>) -> Scalar<E> {
-        let result = Additive::sub(a.as_ref().as_raw(), b.as_ref().as_raw());
+    pub fn sub<E: Curve>(a: impl AsScalar<E>, b: impl AsScalar<E>) -> Scalar<E> {
+        let result = Additive::sub(a.as_scalar().as_raw(), b.as_scalar().as_raw());
         Scalar::from_raw(result)
     }
 
     #[inline]
-    pub fn mul<E: Curve>(a: impl AsRef<Scalar<E>>, b: impl AsRef<Scalar<E>>) -> Scalar<E> {
-        let prod = Multiplicative::mul(a.as_ref().as_raw(), b.as_ref().as_raw());
+    pub fn mul<E: Curve>(a: impl AsScalar<E>, b: impl AsScalar<E>) -> Scalar<E> {
+        let prod = Multiplicative::mul(a.as_scalar().as_raw(), b.as_scalar().as_raw());
         Scalar::from_raw(prod)
     }
 
@@ -281,6 +315,17 @@ mod scalar {
         Scalar::from_raw(result)
     }
 
+    #[inline]
+    pub fn neg_secret<E: Curve>(a: &SecretScalar<E>) -> SecretScalar<E> {
+        let mut neg = neg(a.as_scalar());
+        SecretScalar::new(&mut neg)
+    }
+
+    #[inline]
+    pub fn into_secret<E: Curve>(mut a: Scalar<E>) -> SecretScalar<E> {
+        SecretScalar::new(&mut a)
+    }
+
     #[inline]
     pub fn neg_nonzero<E: Curve>(a: &NonZero<Scalar<E>>) -> NonZero<Scalar<E>> {
         let neg = neg(a);
@@ -290,7 +335,7 @@ mod scalar {
 
     #[inline]
     pub fn neg_nonzero_secret<E: Curve>(a: &NonZero<SecretScalar<E>>) -> NonZero<SecretScalar<E>> {
-        let mut a: Scalar<E> = *a.as_ref();
+        let mut a: Scalar<E> = *a.as_scalar();
         a *= -Scalar::one();
         // Correctness: since `a` is not zero, `-a` is not zero by definition
         NonZero::new_unchecked(SecretScalar::new(&mut a))
@@ -359,6 +404,23 @@ macro_rules! impl_unary_ops {
     )*};
 }
 
+/// Same as `impl_op_assign` but for secret scalars, which can't be copied: result of
+/// the operation is wrapped back into a secret via `$wrap`
+macro_rules! impl_secret_op_assign {
+    ($($ty:ty, $trait:ident, $rhs:ty, $fn:ident, $op:tt, $wrap:path),+,) => {$(
+        impl<E: Curve> $trait<$rhs> for $ty {
+            fn $fn(&mut self, rhs: $rhs) {
+                self.$fn(&rhs)
+            }
+        }
+        impl<E: Curve> $trait<&$rhs> for $ty {
+            fn $fn(&mut self, rhs: &$rhs) {
+                *self = $wrap(&*self $op rhs);
+            }
+        }
+    )+};
+}
+
 macro_rules! impl_op_assign {
     ($($ty:ty, $trait:ident, $rhs:ty, $fn:ident, $op:tt),+,) => {$(
         impl<E: Curve> $trait<$rhs> for $ty {
@@ -462,6 +524,20 @@ impl_nonzero_ops! {
     Sub (SecretScalar<E>, sub, SecretScalar<E> = Scalar<E>) scalar::sub,
 }
 
+// SecretScalar <> SecretScalar, NonZero<Point> <> Generator
+impl_binary_ops! {
+    Add (SecretScalar<E>, add, SecretScalar<E> = Scalar<E>) scalar::add,
+    Sub (SecretScalar<E>, sub, SecretScalar<E> = Scalar<E>) scalar::sub,
+    Mul (SecretScalar<E>, mul, SecretScalar<E> = Scalar<E>) scalar::mul,
+    Mul (SecretScalar<E>, mul, NonZero<SecretScalar<E>> = Scalar<E>) scalar::mul,
+    Mul (NonZero<SecretScalar<E>>, mul, SecretScalar<E> = Scalar<E>) scalar::mul,
+
+    Add (NonZero<Point<E>>, add, Generator<E> = Point<E>) laws::sum_of_point_and_generator_is_valid_point,
+    Add (Generator<E>, add, NonZero<Point<E>> = Point<E>) laws::sum_of_generator_and_point_is_valid_point,
+    Sub (NonZero<Point<E>>, sub, Generator<E> = Point<E>) laws::sub_of_point_and_generator_is_valid_point,
+    Sub (Generator<E>, sub, NonZero<Point<E>> = Point<E>) laws::sub_of_generator_and_point_is_valid_point,
+}
+
 // NonZero<Scalar> * NonZero<Scalar>, Scalar * NonZero<Scalar>, NonZero<Scalar> * Scalar
 impl_binary_ops! {
     Mul (NonZero<Scalar<E>>, mul, NonZero<Scalar<E>> = NonZero<Scalar<E>>) laws::non_zero_scalar_at_non_zero_scalar_is_non_zero_scalar,
@@ -485,6 +561,7 @@ impl_binary_ops! {
 impl_unary_ops! {
     Neg (neg Point<E>) laws::neg_point_is_valid_point,
     Neg (neg Scalar<E>) scalar::neg,
+    Neg (neg SecretScalar<E>) scalar::neg_secret,
     Neg (neg NonZero<Point<E>>) laws::neg_nonzero_point_is_nonzero_point,
     Neg (neg NonZero<Scalar<E>>) scalar::neg_nonzero,
     Neg (neg NonZero<SecretScalar<E>>) scalar::neg_nonzero_secret,
@@ -525,6 +602,26 @@ impl_op_assign! {
     NonZero<Scalar<E>>, MulAssign, NonZero<SecretScalar<E>>, mul_assign, *,
 }
 
+impl_secret_op_assign! {
+    SecretScalar<E>, AddAssign, Scalar<E>, add_assign, +, scalar::into_secret,
+    SecretScalar<E>, AddAssign, NonZero<Scalar<E>>, add_assign, +, scalar::into_secret,
+    SecretScalar<E>, AddAssign, SecretScalar<E>, add_assign, +, scalar::into_secret,
+    SecretScalar<E>, AddAssign, NonZero<SecretScalar<E>>, add_assign, +, scalar::into_secret,
+
+    SecretScalar<E>, SubAssign, Scalar<E>, sub_assign, -, scalar::into_secret,
+    SecretScalar<E>, SubAssign, NonZero<Scalar<E>>, sub_assign, -, scalar::into_secret,
+    SecretScalar<E>, SubAssign, SecretScalar<E>, sub_assign, -, scalar::into_secret,
+    SecretScalar<E>, SubAssign, NonZero<SecretScalar<E>>, sub_assign, -, scalar::into_secret,
+
+    SecretScalar<E>, MulAssign, Scalar<E>, mul_assign, *, scalar::into_secret,
+    SecretScalar<E>, MulAssign, NonZero<Scalar<E>>, mul_assign, *, scalar::into_secret,
+    SecretScalar<E>, MulAssign, SecretScalar<E>, mul_assign, *, scalar::into_secret,
+    SecretScalar<E>, MulAssign, NonZero<SecretScalar<E>>, mul_assign, *, scalar::into_secret,
+
+    NonZero<SecretScalar<E>>, MulAssign, NonZero<Scalar<E>>, mul_assign, *, NonZero::into_secret,
+    NonZero<SecretScalar<E>>, MulAssign, NonZero<SecretScalar<E>>, mul_assign, *, NonZero::into_secret,
+}
+
 impl<E: Curve> Point<E> {
     /// Doubles the point, returns `self + self`
     ///
@@ -588,12 +685,16 @@ fn ensure_ops_implemented<E: Curve>(
         non_zero_point * non_zero_secret_scalar => NonZero<Point<E>>,
 
         point + point => Point<E>,
+        point + g => Point<E>,
+        non_zero_point + g => Point<E>,
         point + non_zero_point => Point<E>,
         non_zero_point + non_zero_point => Point<E>,
 
         point - point => Point<E>,
         point - non_zero_point => Point<E>,
         non_zero_point - non_zero_point => Point<E>,
+        point - g => Point<E>,
+        non_zero_point - g => Point<E>,
 
         scalar + scalar => Scalar<E>,
         scalar + non_zero_scalar => Scalar<E>,
@@ -625,6 +726,14 @@ fn ensure_ops_implemented<E: Curve>(
         scalar * non_zero_secret_scalar => Scalar<E>,
         non_zero_scalar * non_zero_secret_scalar => NonZero<Scalar<E>>,
 
+        secret_scalar + secret_scalar => Scalar<E>,
+        secret_scalar - secret_scalar => Scalar<E>,
+        secret_scalar * secret_scalar => Scalar<E>,
+
+        secret_scalar + non_zero_secret_scalar => Scalar<E>,
+        secret_scalar - non_zero_secret_scalar => Scalar<E>,
+        secret_scalar * non_zero_secret_scalar => Scalar<E>,
+
         non_zero_secret_scalar + non_zero_secret_scalar => Scalar<E>,
         non_zero_secret_scalar - non_zero_secret_scalar => Scalar<E>,
         non_zero_secret_scalar * non_zero_secret_scalar => NonZero<Scalar<E>>,
@@ -635,6 +744,7 @@ fn ensure_ops_implemented<E: Curve>(
         -non_zero_point => NonZero<Point<E>>,
         -scalar => Scalar<E>,
         -non_zero_scalar => NonZero<Scalar<E>>,
+        -secret_scalar => SecretScalar<E>,
         -non_zero_secret_scalar => NonZero<SecretScalar<E>>,
     );
 
@@ -650,9 +760,10 @@ fn ensure_ops_implemented<E: Curve>(
         point *= scalar;
         point *= non_zero_scalar;
         point *= secret_scalar;
-        point *= non_zero_scalar;
+        point *= non_zero_secret_scalar;
 
         non_zero_point *= non_zero_scalar;
+        non_zero_point *= non_zero_secret_scalar;
 
         scalar += scalar;
         scalar -= scalar;
@@ -671,5 +782,25 @@ fn ensure_ops_implemented<E: Curve>(
         scalar *= non_zero_secret_scalar;
 
         non_zero_scalar *= non_zero_scalar;
+        non_zero_scalar *= non_zero_secret_scalar;
+
+        secret_scalar += scalar;
+        secret_scalar -= scalar;
+        secret_scalar *= scalar;
+
+        secret_scalar += non_zero_scalar;
+        secret_scalar -= non_zero_scalar;
+        secret_scalar *= non_zero_scalar;
+
+        secret_scalar += secret_scalar;
+        secret_scalar -= secret_scalar;
+        secret_scalar *= secret_scalar;
+
+        secret_scalar += non_zero_secret_scalar;
+        secret_scalar -= non_zero_secret_scalar;
+        secret_scalar *= non_zero_secret_scalar;
+
+        non_zero_secret_scalar *= non_zero_scalar;
+        non_zero_secret_scalar *= non_zero_secret_scalar;
     );
 }
@@ -17,7 +17,10 @@ mod sealed {
 }
 
 /// Accesses backend library representation of the point/scalar
-pub trait AsRaw
+///
+/// Trait is sealed: it's only implemented for [`Point<E>`](crate::Point),
+/// [`Scalar<E>`](crate::Scalar), and [`EncodedScalar<E>`](crate::EncodedScalar).
+pub trait AsRaw: sealed::Sealed
 where
     Self: Sized,
 {
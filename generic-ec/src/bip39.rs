@@ -0,0 +1,686 @@
+//! BIP39 mnemonics and master key derivation
+//!
+//! Imports wallets backed up as [BIP39] mnemonic sentences (seed phrases):
+//! 1. [`mnemonic_to_seed`] derives 64 bytes seed from the mnemonic and optional passphrase
+//!    via PBKDF2-HMAC-SHA512
+//! 2. [`master_key`] derives master secret key and chain code from the seed as defined in
+//!    [BIP32] for secp256k1, and in [SLIP-10] for secp256r1 and ed25519
+//!
+//! 3. [`ExtendedSecretKey::derive_path`] derives keys of the wallet along [`DerivationPath`],
+//!    e.g. `m/44'/0'/0'`
+//!
+//! Derived keys keep their derivation path, so applications can record how a key was derived
+//! and verify it later.
+//!
+//! Non-hardened children can be derived from [`ExtendedPublicKey`] alone. It exposes the additive
+//! tweak of each derivation step, which lets threshold protocols derive children of a shared key.
+//!
+//! Mnemonic checksum can be verified via [`validate_mnemonic`] given the wordlist of the
+//! mnemonic language. Wordlists are not bundled with the crate. Seed derivation doesn't depend on
+//! the wordlist, so it works with mnemonics of any language. Mnemonic and passphrase must be in
+//! Unicode NFKD normal form as required by BIP39. ASCII strings, e.g. English mnemonics, are
+//! always normalized.
+//!
+//! All intermediate buffers holding the secrets are erased on drop.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{bip39, curves::Secp256k1, Point};
+//!
+//! let mnemonic = "abandon abandon abandon abandon abandon abandon \
+//!     abandon abandon abandon abandon abandon about";
+//! let seed = bip39::mnemonic_to_seed(mnemonic, "TREZOR")?;
+//! let master_key = bip39::master_key::<Secp256k1>(&*seed);
+//!
+//! let path = "m/44'/0'/0'/0/0".parse()?;
+//! let key = master_key.derive_path(&path)?;
+//! let public_key = Point::generator() * key.secret_key();
+//! assert_eq!(key.path(), &path);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [BIP39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+//! [BIP32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [SLIP-10]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use hmac::{digest::FixedOutput, Mac};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    errors::{
+        DerivationPathReason, InvalidDerivationPath, InvalidMnemonic,
+        InvalidMnemonicReason as Reason,
+    },
+    Curve, NonZero, Point, Scalar, SecretScalar,
+};
+
+/// Number of PBKDF2 iterations defined by BIP39
+const PBKDF2_ROUNDS: u32 = 2048;
+
+/// Derives 64 bytes seed from the mnemonic and passphrase
+///
+/// Words of the mnemonic may be separated by any whitespace. Use empty `passphrase` if the
+/// wallet has no passphrase. Returns error if the mnemonic doesn't have 12, 15, 18, 21, or 24
+/// words. Checksum is not verified, use [`validate_mnemonic`] for that.
+pub fn mnemonic_to_seed(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<Zeroizing<[u8; 64]>, InvalidMnemonic> {
+    word_count(mnemonic)?;
+    let mut normalized = Zeroizing::new(String::with_capacity(mnemonic.len()));
+    for (i, word) in mnemonic.split_whitespace().enumerate() {
+        if i > 0 {
+            normalized.push(' ');
+        }
+        normalized.push_str(word);
+    }
+
+    let salt = Zeroizing::new([b"mnemonic", passphrase.as_bytes()].concat());
+    let mut seed = Zeroizing::new([0u8; 64]);
+    pbkdf2::pbkdf2_hmac::<Sha512>(
+        normalized.as_bytes(),
+        &salt,
+        PBKDF2_ROUNDS,
+        seed.as_mut_slice(),
+    );
+    Ok(seed)
+}
+
+/// Verifies mnemonic checksum
+///
+/// `wordlist` must be BIP39 wordlist of the mnemonic language (2048 words). Returns error if
+/// the mnemonic has invalid number of words, contains a word not in the wordlist, or its
+/// checksum doesn't match.
+pub fn validate_mnemonic(mnemonic: &str, wordlist: &[&str]) -> Result<(), InvalidMnemonic> {
+    if wordlist.len() != 2048 {
+        return Err(Reason::InvalidWordlist.into());
+    }
+    let words = word_count(mnemonic)?;
+
+    // Each word encodes 11 bits, the last `words / 3` bits are the checksum
+    let mut bits = Zeroizing::new([0u8; 33]);
+    for (i, word) in mnemonic.split_whitespace().enumerate() {
+        let mut index = wordlist
+            .iter()
+            .position(|w| *w == word)
+            .ok_or(Reason::UnknownWord)?;
+        for bit in (0..11).rev() {
+            let position = i * 11 + bit;
+            bits[position / 8] |= ((index & 1) as u8) << (7 - position % 8);
+            index >>= 1;
+        }
+    }
+
+    let checksum_bits = words / 3;
+    let entropy_len = words * 4 / 3;
+    let mut hash = Sha256::digest(&bits[..entropy_len]);
+    let expected = hash[0] >> (8 - checksum_bits);
+    let actual = bits[entropy_len] >> (8 - checksum_bits);
+    hash.as_mut_slice().zeroize();
+    if expected != actual {
+        return Err(Reason::ChecksumMismatch.into());
+    }
+    Ok(())
+}
+
+/// Secret key of HD wallet with its chain code and derivation path
+///
+/// Obtained via [`master_key`] and [`derive_child`](Self::derive_child). Key keeps track of
+/// the path it was derived along, so applications can record how the key was derived, and
+/// verify it later by deriving the key from the master key again.
+pub struct ExtendedSecretKey<E: Curve> {
+    secret_key: NonZero<SecretScalar<E>>,
+    /// Key as defined in BIP32/SLIP-10: big-endian scalar for Weierstrass curves, and Ed25519
+    /// secret key for ed25519
+    key: Zeroizing<[u8; 32]>,
+    chain_code: Zeroizing<[u8; 32]>,
+    path: DerivationPath,
+}
+
+impl<E: MasterKeyCurve> ExtendedSecretKey<E> {
+    /// Secret key
+    pub fn secret_key(&self) -> &NonZero<SecretScalar<E>> {
+        &self.secret_key
+    }
+
+    /// Chain code used for child keys derivation
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Path from the master key to this key
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    /// Derives child key at `index`
+    ///
+    /// Returns error if `index` is not hardened and curve supports only hardened derivation
+    /// (ed25519).
+    pub fn derive_child(&self, index: ChildIndex) -> Result<Self, InvalidDerivationPath> {
+        if E::HARDENED_ONLY && !index.is_hardened() {
+            return Err(DerivationPathReason::NonHardened.into());
+        }
+        let index_bytes = index.to_raw().to_be_bytes();
+        let hmac = Hmac::new(&*self.chain_code);
+        let mut i = if index.is_hardened() {
+            hmac.compute(&[&[0], &*self.key, &index_bytes])
+        } else {
+            let public_key = (Point::generator() * &self.secret_key).to_bytes(true);
+            hmac.compute(&[public_key.as_bytes(), &index_bytes])
+        };
+        loop {
+            let (il, ir) = i.split_at(32);
+            let child =
+                E::child_key(il, &self.key).and_then(|key| Some((E::secret_key(&key)?, key)));
+            if let Some((secret_key, key)) = child {
+                let mut chain_code = Zeroizing::new([0u8; 32]);
+                chain_code.copy_from_slice(ir);
+                return Ok(Self {
+                    secret_key,
+                    key,
+                    chain_code,
+                    path: self.path.child(index),
+                });
+            }
+            // SLIP-10: if the key is invalid, derive again from I_R
+            i = hmac.compute(&[&[1], ir, &index_bytes]);
+        }
+    }
+
+    /// Derives key along `path` relative to this key
+    ///
+    /// For the master key, `path` is absolute path of the resulting key.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, InvalidDerivationPath> {
+        let mut indexes = path.indexes().iter();
+        let Some(first) = indexes.next() else {
+            return Ok(self.clone());
+        };
+        let mut key = self.derive_child(*first)?;
+        for index in indexes {
+            key = key.derive_child(*index)?;
+        }
+        Ok(key)
+    }
+
+    /// Returns public counterpart of the key
+    pub fn extended_public_key(&self) -> ExtendedPublicKey<E> {
+        ExtendedPublicKey {
+            public_key: (Point::generator() * &self.secret_key).into(),
+            chain_code: *self.chain_code,
+            path: self.path.clone(),
+        }
+    }
+}
+
+/// Public key of HD wallet with its chain code and derivation path
+///
+/// Derives public keys of non-hardened children without knowing the secret key. Derivation
+/// of a child adds a tweak to the parent key: $X_{child} = X + t \cdot G$. The tweak is
+/// exposed, so the same derivation can be applied to a secret key which is not known in full,
+/// e.g. to a key shared between several parties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPublicKey<E: Curve> {
+    public_key: Point<E>,
+    chain_code: [u8; 32],
+    path: DerivationPath,
+}
+
+impl<E: MasterKeyCurve> ExtendedPublicKey<E> {
+    /// Constructs extended public key with empty derivation path
+    pub fn new(public_key: Point<E>, chain_code: [u8; 32]) -> Self {
+        Self {
+            public_key,
+            chain_code,
+            path: DerivationPath::master(),
+        }
+    }
+
+    /// Public key
+    pub fn public_key(&self) -> &Point<E> {
+        &self.public_key
+    }
+
+    /// Chain code used for child keys derivation
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Path to this key
+    ///
+    /// It's relative to the key passed to [`new`](Self::new), or absolute if it was obtained
+    /// from the master [`ExtendedSecretKey`].
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    /// Derives non-hardened child key at `index`
+    pub fn derive_child(&self, index: ChildIndex) -> Result<Self, InvalidDerivationPath> {
+        self.derive_child_with_tweak(index).map(|(child, _)| child)
+    }
+
+    /// Derives non-hardened child key at `index`, returns the child key and the tweak $t$ such
+    /// that $X_{child} = X + t \cdot G$
+    ///
+    /// Returns error if `index` is hardened, or curve supports only hardened derivation
+    /// (ed25519).
+    pub fn derive_child_with_tweak(
+        &self,
+        index: ChildIndex,
+    ) -> Result<(Self, Scalar<E>), InvalidDerivationPath> {
+        if E::HARDENED_ONLY {
+            return Err(DerivationPathReason::NonHardened.into());
+        }
+        if index.is_hardened() {
+            return Err(DerivationPathReason::HardenedFromPublicKey.into());
+        }
+        let index_bytes = index.to_raw().to_be_bytes();
+        let hmac = Hmac::new(&self.chain_code);
+        let mut i = hmac.compute(&[self.public_key.to_bytes(true).as_bytes(), &index_bytes]);
+        loop {
+            let (il, ir) = i.split_at(32);
+            if let Ok(tweak) = Scalar::<E>::from_be_bytes(il) {
+                let public_key = self.public_key + Point::generator() * tweak;
+                if !public_key.is_zero() {
+                    let mut chain_code = [0u8; 32];
+                    chain_code.copy_from_slice(ir);
+                    let child = Self {
+                        public_key,
+                        chain_code,
+                        path: self.path.child(index),
+                    };
+                    return Ok((child, tweak));
+                }
+            }
+            // SLIP-10: if the key is invalid, derive again from I_R
+            i = hmac.compute(&[&[1], ir, &index_bytes]);
+        }
+    }
+
+    /// Derives key along non-hardened `path` relative to this key, returns the derived key and
+    /// the sum of tweaks of all derivation steps
+    pub fn derive_path_with_tweak(
+        &self,
+        path: &DerivationPath,
+    ) -> Result<(Self, Scalar<E>), InvalidDerivationPath> {
+        let mut key = self.clone();
+        let mut tweak = Scalar::zero();
+        for index in path.indexes() {
+            let (child, child_tweak) = key.derive_child_with_tweak(*index)?;
+            key = child;
+            tweak += child_tweak;
+        }
+        Ok((key, tweak))
+    }
+}
+
+impl<E: Curve> Clone for ExtendedSecretKey<E> {
+    fn clone(&self) -> Self {
+        Self {
+            secret_key: self.secret_key.clone(),
+            key: self.key.clone(),
+            chain_code: self.chain_code.clone(),
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<E: Curve> fmt::Debug for ExtendedSecretKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedSecretKey")
+            .field("curve", &E::CURVE_NAME)
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Curve which has master key derivation defined in BIP32 or SLIP-10
+///
+/// The trait is sealed and implemented for secp256k1, secp256r1, and ed25519.
+pub trait MasterKeyCurve: Curve + sealed::Sealed {
+    /// HMAC key used to derive master key from the seed, e.g. `Bitcoin seed`
+    const SEED_KEY: &'static [u8];
+}
+
+/// Derives master key from the seed
+///
+/// `seed` is typically output of [`mnemonic_to_seed`], BIP32 requires it to be between 16 and
+/// 64 bytes long.
+///
+/// For ed25519, SLIP-10 key is an Ed25519 secret key (seed in terms of RFC 8032), so
+/// secret scalar is derived from it in the same way as Ed25519 does: by hashing it with SHA-512
+/// and [clamping](crate::Scalar::clamp_ed25519). Public key matches the one displayed by
+/// SLIP-10 wallets.
+pub fn master_key<E: MasterKeyCurve>(seed: &[u8]) -> ExtendedSecretKey<E> {
+    let mut i = Hmac::new(E::SEED_KEY).compute(&[seed]);
+    loop {
+        let (il, ir) = i.split_at(32);
+        let mut key = Zeroizing::new([0u8; 32]);
+        key.copy_from_slice(il);
+        if let Some(secret_key) = E::secret_key(&key) {
+            let mut chain_code = Zeroizing::new([0u8; 32]);
+            chain_code.copy_from_slice(ir);
+            return ExtendedSecretKey {
+                secret_key,
+                key,
+                chain_code,
+                path: DerivationPath::master(),
+            };
+        }
+        // SLIP-10: if the key is invalid, derive again from I
+        i = Hmac::new(E::SEED_KEY).compute(&[&*i]);
+    }
+}
+
+/// Index of a child key
+///
+/// Indexes $i \ge 2^{31}$ denote hardened derivation. Displayed as `44'` for hardened index
+/// $2^{31} + 44$, and `44` for normal index $44$.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChildIndex(u32);
+
+impl ChildIndex {
+    /// Index of the first hardened child, $2^{31}$
+    pub const HARDENED_OFFSET: u32 = 1 << 31;
+
+    /// Normal child index $i$, returns `None` if $i \ge 2^{31}$
+    pub fn normal(i: u32) -> Option<Self> {
+        (i < Self::HARDENED_OFFSET).then_some(Self(i))
+    }
+
+    /// Hardened child index $2^{31} + i$, returns `None` if $i \ge 2^{31}$
+    pub fn hardened(i: u32) -> Option<Self> {
+        (i < Self::HARDENED_OFFSET).then_some(Self(Self::HARDENED_OFFSET + i))
+    }
+
+    /// Constructs child index from its raw value, as serialized in BIP32
+    pub fn from_raw(index: u32) -> Self {
+        Self(index)
+    }
+
+    /// Raw value of the index, as serialized in BIP32
+    pub fn to_raw(self) -> u32 {
+        self.0
+    }
+
+    /// Checks whether index denotes hardened derivation
+    pub fn is_hardened(self) -> bool {
+        self.0 >= Self::HARDENED_OFFSET
+    }
+}
+
+impl fmt::Display for ChildIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.0 - Self::HARDENED_OFFSET)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl core::str::FromStr for ChildIndex {
+    type Err = InvalidDerivationPath;
+
+    /// Parses index like `44` or `44'`, hardened index may also be suffixed with `h` or `H`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (i, hardened) = match s.strip_suffix(['\'', 'h', 'H']) {
+            Some(i) => (i, true),
+            None => (s, false),
+        };
+        if i.is_empty() || !i.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(DerivationPathReason::Malformed.into());
+        }
+        let i: u32 = i.parse().or(Err(DerivationPathReason::IndexOutOfRange))?;
+        let index = if hardened {
+            Self::hardened(i)
+        } else {
+            Self::normal(i)
+        };
+        index.ok_or_else(|| DerivationPathReason::IndexOutOfRange.into())
+    }
+}
+
+/// Derivation path of HD wallet key, e.g. `m/44'/0'/0'`
+///
+/// Implements [`FromStr`](core::str::FromStr) and [`Display`](fmt::Display) using BIP32
+/// notation, and serializes as a string of the same format.
+///
+/// ```rust
+/// use generic_ec::bip39::{ChildIndex, DerivationPath};
+///
+/// let path: DerivationPath = "m/44'/0'/0'".parse()?;
+/// assert_eq!(path.indexes()[0], ChildIndex::hardened(44).unwrap());
+/// assert_eq!(path.to_string(), "m/44'/0'/0'");
+/// # Ok::<_, generic_ec::errors::InvalidDerivationPath>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct DerivationPath(Vec<ChildIndex>);
+
+impl DerivationPath {
+    /// Path of the master key, `m`
+    pub fn master() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Path of the child at `index`
+    pub fn child(&self, index: ChildIndex) -> Self {
+        let mut path = self.clone();
+        path.0.push(index);
+        path
+    }
+
+    /// Indexes of the path from the master key
+    pub fn indexes(&self) -> &[ChildIndex] {
+        &self.0
+    }
+}
+
+impl From<Vec<ChildIndex>> for DerivationPath {
+    fn from(indexes: Vec<ChildIndex>) -> Self {
+        Self(indexes)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+        for index in &self.0 {
+            write!(f, "/{index}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::str::FromStr for DerivationPath {
+    type Err = InvalidDerivationPath;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(DerivationPathReason::Malformed.into());
+        }
+        components
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DerivationPath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DerivationPath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct DerivationPathVisitor;
+        impl<'de> serde::de::Visitor<'de> for DerivationPathVisitor {
+            type Value = DerivationPath;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("derivation path like m/44'/0'/0'")
+            }
+            fn visit_str<Error>(self, v: &str) -> Result<Self::Value, Error>
+            where
+                Error: serde::de::Error,
+            {
+                v.parse().map_err(Error::custom)
+            }
+        }
+        deserializer.deserialize_str(DerivationPathVisitor)
+    }
+}
+
+#[cfg(feature = "curve-secp256k1")]
+impl MasterKeyCurve for crate::curves::Secp256k1 {
+    const SEED_KEY: &'static [u8] = b"Bitcoin seed";
+}
+
+#[cfg(feature = "curve-secp256r1")]
+impl MasterKeyCurve for crate::curves::Secp256r1 {
+    const SEED_KEY: &'static [u8] = b"Nist256p1 seed";
+}
+
+#[cfg(feature = "curve-ed25519")]
+impl MasterKeyCurve for crate::curves::Ed25519 {
+    const SEED_KEY: &'static [u8] = b"ed25519 seed";
+}
+
+mod sealed {
+    use zeroize::Zeroizing;
+
+    use crate::{Curve, NonZero, SecretScalar};
+
+    pub trait Sealed: Curve {
+        /// Whether curve supports only hardened derivation
+        const HARDENED_ONLY: bool = false;
+
+        /// Converts 32 bytes key into secret scalar, returns `None` if key is invalid
+        fn secret_key(key: &[u8; 32]) -> Option<NonZero<SecretScalar<Self>>>;
+
+        /// Computes child key from `I_L` and parent key, returns `None` if `I_L` is invalid
+        fn child_key(il: &[u8], parent: &[u8; 32]) -> Option<Zeroizing<[u8; 32]>>;
+    }
+
+    /// BIP32 key of Weierstrass curve is a big-endian non-zero scalar
+    #[cfg(any(feature = "curve-secp256k1", feature = "curve-secp256r1"))]
+    fn weierstrass_secret_key<E: Curve>(key: &[u8; 32]) -> Option<NonZero<SecretScalar<E>>> {
+        SecretScalar::from_be_bytes(key)
+            .ok()
+            .and_then(NonZero::from_secret_scalar)
+    }
+
+    /// BIP32 child key of Weierstrass curve: $k_i = I_L + k_{par} \bmod n$
+    #[cfg(any(feature = "curve-secp256k1", feature = "curve-secp256r1"))]
+    fn weierstrass_child_key<E: Curve>(
+        il: &[u8],
+        parent: &[u8; 32],
+    ) -> Option<Zeroizing<[u8; 32]>> {
+        use crate::Scalar;
+        use zeroize::Zeroize;
+
+        let mut tweak = Scalar::<E>::from_be_bytes(il).ok()?;
+        let parent = SecretScalar::<E>::from_be_bytes(parent).ok()?;
+        let child = SecretScalar::new(&mut (tweak + &parent));
+        tweak.zeroize();
+
+        let child = NonZero::from_secret_scalar(child)?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        child
+            .as_ref()
+            .expose_secret(|x| key.copy_from_slice(&x.to_be_bytes()));
+        Some(key)
+    }
+
+    #[cfg(feature = "curve-secp256k1")]
+    impl Sealed for crate::curves::Secp256k1 {
+        fn secret_key(key: &[u8; 32]) -> Option<NonZero<SecretScalar<Self>>> {
+            weierstrass_secret_key(key)
+        }
+
+        fn child_key(il: &[u8], parent: &[u8; 32]) -> Option<Zeroizing<[u8; 32]>> {
+            weierstrass_child_key::<Self>(il, parent)
+        }
+    }
+
+    #[cfg(feature = "curve-secp256r1")]
+    impl Sealed for crate::curves::Secp256r1 {
+        fn secret_key(key: &[u8; 32]) -> Option<NonZero<SecretScalar<Self>>> {
+            weierstrass_secret_key(key)
+        }
+
+        fn child_key(il: &[u8], parent: &[u8; 32]) -> Option<Zeroizing<[u8; 32]>> {
+            weierstrass_child_key::<Self>(il, parent)
+        }
+    }
+
+    #[cfg(feature = "curve-ed25519")]
+    impl Sealed for crate::curves::Ed25519 {
+        const HARDENED_ONLY: bool = true;
+
+        fn secret_key(key: &[u8; 32]) -> Option<NonZero<SecretScalar<Self>>> {
+            use sha2::{Digest, Sha512};
+            use zeroize::Zeroize;
+
+            let mut hash = Sha512::digest(key);
+            let mut scalar_bytes = [0u8; 32];
+            scalar_bytes.copy_from_slice(&hash[..32]);
+            let secret_key = SecretScalar::clamp_ed25519(&scalar_bytes);
+            hash.as_mut_slice().zeroize();
+            scalar_bytes.zeroize();
+            NonZero::from_secret_scalar(secret_key)
+        }
+
+        /// SLIP-10: child key of ed25519 is $I_L$
+        fn child_key(il: &[u8], _parent: &[u8; 32]) -> Option<Zeroizing<[u8; 32]>> {
+            let mut key = Zeroizing::new([0u8; 32]);
+            key.copy_from_slice(il.get(..32)?);
+            Some(key)
+        }
+    }
+}
+
+fn word_count(mnemonic: &str) -> Result<usize, InvalidMnemonic> {
+    let words = mnemonic.split_whitespace().count();
+    if matches!(words, 12 | 15 | 18 | 21 | 24) {
+        Ok(words)
+    } else {
+        Err(Reason::InvalidWordCount.into())
+    }
+}
+
+/// HMAC-SHA512 keyed once and reused for several messages
+struct Hmac(hmac::Hmac<Sha512>);
+
+impl Hmac {
+    fn new(key: &[u8]) -> Self {
+        #[allow(clippy::expect_used)]
+        let hmac = <hmac::Hmac<Sha512> as Mac>::new_from_slice(key)
+            .expect("HMAC accepts keys of any length");
+        Self(hmac)
+    }
+
+    /// HMAC of concatenated `message` parts
+    fn compute(&self, message: &[&[u8]]) -> Zeroizing<[u8; 64]> {
+        let mut hmac = self.0.clone();
+        for part in message {
+            hmac.update(part);
+        }
+        let mut result = Zeroizing::new([0u8; 64]);
+        FixedOutput::finalize_into(hmac, result.as_mut_slice().into());
+        result
+    }
+}
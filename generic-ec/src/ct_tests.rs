@@ -0,0 +1,320 @@
+//! Constant-time tests
+//!
+//! Harnesses in this module detect timing leaks following [dudect] methodology: an operation is
+//! run many times on inputs from two classes, _fixed_ (the same secret every time) and _random_
+//! (fresh secret every time), classes are interleaved randomly. If the operation is constant-time,
+//! execution time distributions of both classes are the same, which is checked using Welch's
+//! t-test. $|t| > 4.5$ is considered as an evidence of the leak, same as in dudect.
+//!
+//! Timing depends on the hardware, compiler and its flags, and backend options, so passing the
+//! test on one machine doesn't say anything about another one. The harnesses are exposed so
+//! constant-time claims can be reproduced on the target hardware. Run them in release mode on
+//! otherwise idle machine, the more measurements are taken, the smaller leaks can be detected:
+//!
+//! ```rust,no_run
+//! use generic_ec::{curves::Secp256k1, ct_tests};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let config = ct_tests::Config { measurements: 1_000_000 };
+//! for report in ct_tests::run_all::<Secp256k1>(&mut rng, &config) {
+//!     println!("{report}");
+//!     assert!(!report.is_leaking());
+//! }
+//! ```
+//!
+//! Statistical test can only find leaks, it can't prove their absence. Negative result means
+//! that no leak was found with given number of measurements.
+//!
+//! Any other operation can be tested using [`measure`].
+//!
+//! [dudect]: https://eprint.iacr.org/2016/1123
+
+use core::{fmt, hint::black_box};
+use std::{time::Instant, vec::Vec};
+
+use rand_core::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+
+use crate::{Curve, Point, Scalar, SecretScalar};
+
+/// Threshold of $|t|$ statistic above which operation is considered leaking
+pub const LEAK_THRESHOLD: f64 = 4.5;
+
+/// Percentile of measurements kept in the cropped test
+///
+/// Cropping removes measurements affected by interrupts and context switches.
+const CROP_PERCENTILE: f64 = 0.9;
+
+/// Configuration of the harness
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Number of measurements
+    pub measurements: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            measurements: 100_000,
+        }
+    }
+}
+
+/// Class of the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    /// The same input for every measurement
+    Fixed,
+    /// Random input for every measurement
+    Random,
+}
+
+/// Result of the test
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// Name of the tested operation
+    pub name: &'static str,
+    /// Number of measurements
+    pub measurements: usize,
+    /// Welch's $t$ statistic over all measurements
+    pub t: f64,
+    /// Welch's $t$ statistic over measurements below 90th percentile
+    pub cropped_t: f64,
+}
+
+impl Report {
+    /// Largest absolute value of $t$ statistic
+    pub fn max_t(&self) -> f64 {
+        self.t.abs().max(self.cropped_t.abs())
+    }
+
+    /// Checks whether $|t|$ exceeds [`LEAK_THRESHOLD`]
+    pub fn is_leaking(&self) -> bool {
+        self.max_t() > LEAK_THRESHOLD
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} measurements, max |t| = {:.2} ({})",
+            self.name,
+            self.measurements,
+            self.max_t(),
+            if self.is_leaking() {
+                "leak detected"
+            } else {
+                "no leak found"
+            }
+        )
+    }
+}
+
+/// Runs all the harnesses against curve `E`
+///
+/// Equivalent to calling [`generator_mul`], [`scalar_mul`], [`ct_eq`], and
+/// [`secret_serialization`] one after another.
+pub fn run_all<E: Curve>(rng: &mut (impl RngCore + CryptoRng), config: &Config) -> [Report; 4] {
+    [
+        generator_mul::<E>(rng, config),
+        scalar_mul::<E>(rng, config),
+        ct_eq::<E>(rng, config),
+        secret_serialization::<E>(rng, config),
+    ]
+}
+
+/// Tests multiplication of generator at secret scalar
+///
+/// Fixed class is scalar $1$, random class is a random scalar.
+pub fn generator_mul<E: Curve>(rng: &mut (impl RngCore + CryptoRng), config: &Config) -> Report {
+    measure(
+        "generator multiplication",
+        rng,
+        config,
+        secret_scalar::<E>,
+        |s| Point::generator() * s,
+    )
+}
+
+/// Tests multiplication of a point at secret scalar
+///
+/// Point is chosen at random once. Fixed class is scalar $1$, random class is a random scalar.
+pub fn scalar_mul<E: Curve>(rng: &mut (impl RngCore + CryptoRng), config: &Config) -> Report {
+    let point = Point::<E>::generator() * Scalar::random(rng);
+    measure(
+        "scalar multiplication",
+        rng,
+        config,
+        secret_scalar::<E>,
+        |s| point * s,
+    )
+}
+
+/// Tests constant-time equality of secret scalars
+///
+/// Secret scalar is compared against another one, which is equal to it in fixed class, and
+/// random in random class.
+pub fn ct_eq<E: Curve>(rng: &mut (impl RngCore + CryptoRng), config: &Config) -> Report {
+    let secret = SecretScalar::<E>::random(rng);
+    measure(
+        "constant-time equality",
+        rng,
+        config,
+        |class, rng| match class {
+            Class::Fixed => secret.clone(),
+            Class::Random => SecretScalar::random(rng),
+        },
+        |other| {
+            for _ in 0..CHEAP_OP_REPETITIONS {
+                black_box(secret.ct_eq(black_box(other)));
+            }
+        },
+    )
+}
+
+/// Tests encoding and decoding of secret scalars
+///
+/// Fixed class is scalar $1$, random class is a random scalar.
+pub fn secret_serialization<E: Curve>(
+    rng: &mut (impl RngCore + CryptoRng),
+    config: &Config,
+) -> Report {
+    measure(
+        "secret serialization",
+        rng,
+        config,
+        secret_scalar::<E>,
+        |s| {
+            for _ in 0..CHEAP_OP_REPETITIONS {
+                let bytes = black_box(s).expose_secret(|s| s.to_be_bytes());
+                let _ = black_box(SecretScalar::<E>::from_be_bytes(&bytes));
+            }
+        },
+    )
+}
+
+/// Operations taking less than timer resolution are repeated within a single measurement
+const CHEAP_OP_REPETITIONS: usize = 100;
+
+fn secret_scalar<E: Curve>(class: Class, rng: &mut (impl RngCore + CryptoRng)) -> SecretScalar<E> {
+    match class {
+        Class::Fixed => SecretScalar::one(),
+        Class::Random => SecretScalar::random(rng),
+    }
+}
+
+/// Measures execution time of `op` on inputs of both classes
+///
+/// Inputs are generated by `input` before taking measurements, classes are chosen at random
+/// for every measurement. Output of `op` is passed through [`black_box`] so the compiler
+/// can't optimize the operation out.
+pub fn measure<R, I, O>(
+    name: &'static str,
+    rng: &mut R,
+    config: &Config,
+    mut input: impl FnMut(Class, &mut R) -> I,
+    mut op: impl FnMut(&I) -> O,
+) -> Report
+where
+    R: RngCore,
+{
+    let inputs = (0..config.measurements)
+        .map(|_| {
+            let class = if rng.next_u32() & 1 == 0 {
+                Class::Fixed
+            } else {
+                Class::Random
+            };
+            (class, input(class, rng))
+        })
+        .collect::<Vec<_>>();
+
+    // Warm up caches and branch predictor
+    for (_, input) in inputs.iter().take(100) {
+        black_box(op(input));
+    }
+
+    let timings = inputs
+        .iter()
+        .map(|(class, input)| {
+            let start = Instant::now();
+            black_box(op(black_box(input)));
+            (*class, start.elapsed().as_nanos() as f64)
+        })
+        .collect::<Vec<_>>();
+
+    let mut sorted = timings.iter().map(|(_, t)| *t).collect::<Vec<_>>();
+    sorted.sort_by(f64::total_cmp);
+    let crop_at = sorted
+        .get((sorted.len() as f64 * CROP_PERCENTILE) as usize)
+        .copied()
+        .unwrap_or(f64::INFINITY);
+
+    let mut full = WelchTest::default();
+    let mut cropped = WelchTest::default();
+    for &(class, time) in &timings {
+        full.push(class, time);
+        if time <= crop_at {
+            cropped.push(class, time);
+        }
+    }
+
+    Report {
+        name,
+        measurements: config.measurements,
+        t: full.t(),
+        cropped_t: cropped.t(),
+    }
+}
+
+/// Online Welch's t-test
+#[derive(Default)]
+struct WelchTest {
+    fixed: Moments,
+    random: Moments,
+}
+
+impl WelchTest {
+    fn push(&mut self, class: Class, x: f64) {
+        match class {
+            Class::Fixed => self.fixed.push(x),
+            Class::Random => self.random.push(x),
+        }
+    }
+
+    fn t(&self) -> f64 {
+        if self.fixed.n < 2.0 || self.random.n < 2.0 {
+            return 0.;
+        }
+        let diff = self.fixed.mean - self.random.mean;
+        let std_err =
+            (self.fixed.variance() / self.fixed.n + self.random.variance() / self.random.n).sqrt();
+        if std_err == 0. {
+            // Both classes took exactly the same time on every measurement
+            return if diff == 0. { 0. } else { f64::INFINITY };
+        }
+        diff / std_err
+    }
+}
+
+/// Mean and variance computed via Welford's algorithm
+#[derive(Default)]
+struct Moments {
+    n: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    fn push(&mut self, x: f64) {
+        self.n += 1.;
+        let delta = x - self.mean;
+        self.mean += delta / self.n;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn variance(&self) -> f64 {
+        self.m2 / (self.n - 1.)
+    }
+}
@@ -94,6 +94,12 @@ impl<E: Curve> AsMut<[u8]> for EncodedScalar<E> {
     }
 }
 
+impl<E: Curve> zeroize::Zeroize for EncodedScalar<E> {
+    fn zeroize(&mut self) {
+        self.0.as_mut().zeroize()
+    }
+}
+
 impl<E: Curve> ops::Deref for EncodedScalar<E> {
     type Target = [u8];
     fn deref(&self) -> &[u8] {
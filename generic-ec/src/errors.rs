@@ -57,6 +57,9 @@ impl fmt::Display for HashError {
             HashErrorReason::ProducedValueInvalid => {
                 f.write_str("hash to curve/scalar produced invalid point/scalar")
             }
+            HashErrorReason::InvalidMapInput => {
+                f.write_str("input to map to curve has invalid length")
+            }
         }
     }
 }
@@ -68,6 +71,7 @@ impl Error for HashError {}
 pub(crate) enum HashErrorReason {
     HashFailed,
     ProducedValueInvalid,
+    InvalidMapInput,
 }
 
 /// Appeared zero point is not expected/accepted
@@ -95,3 +99,220 @@ impl fmt::Display for ZeroScalar {
 
 #[cfg(feature = "std")]
 impl Error for ZeroScalar {}
+
+/// Indicates that randomness source is broken
+///
+/// Returned when rejection sampling didn't produce a value after many attempts, which happens
+/// with negligible probability unless randomness source is defective.
+#[derive(Debug, Clone, Copy)]
+pub struct RandomnessFailure;
+
+impl fmt::Display for RandomnessFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("defected source of randomness")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for RandomnessFailure {}
+
+/// Indicates that `SubjectPublicKeyInfo` couldn't be parsed into a public key
+#[cfg(feature = "spki")]
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidPublicKeyInfo(pub(crate) InvalidPublicKeyInfoReason);
+
+#[cfg(feature = "spki")]
+impl InvalidPublicKeyInfo {
+    pub(crate) fn der(_err: spki::der::Error) -> Self {
+        Self(InvalidPublicKeyInfoReason::MalformedDer)
+    }
+}
+
+#[cfg(feature = "spki")]
+impl fmt::Display for InvalidPublicKeyInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            InvalidPublicKeyInfoReason::MalformedDer => f.write_str("malformed DER encoding"),
+            InvalidPublicKeyInfoReason::UnsupportedAlgorithm => {
+                f.write_str("public key algorithm is not supported or doesn't match the curve")
+            }
+            InvalidPublicKeyInfoReason::InvalidPoint => f.write_str("invalid public key"),
+        }
+    }
+}
+
+#[cfg(all(feature = "spki", feature = "std"))]
+impl Error for InvalidPublicKeyInfo {}
+
+#[cfg(feature = "spki")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum InvalidPublicKeyInfoReason {
+    MalformedDer,
+    UnsupportedAlgorithm,
+    InvalidPoint,
+}
+
+#[cfg(feature = "spki")]
+impl From<InvalidPublicKeyInfoReason> for InvalidPublicKeyInfo {
+    fn from(reason: InvalidPublicKeyInfoReason) -> Self {
+        Self(reason)
+    }
+}
+
+/// Indicates that [key file](crate::key_file) couldn't be decoded
+#[cfg(feature = "key-file")]
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidKeyFile(pub(crate) InvalidKeyFileReason);
+
+#[cfg(feature = "key-file")]
+impl fmt::Display for InvalidKeyFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            InvalidKeyFileReason::Malformed => f.write_str("malformed key file"),
+            InvalidKeyFileReason::CurveMismatch { expected } => {
+                write!(
+                    f,
+                    "key file contains a key on different curve (expected {expected})"
+                )
+            }
+            InvalidKeyFileReason::InvalidScalar => f.write_str("key file contains invalid scalar"),
+        }
+    }
+}
+
+#[cfg(all(feature = "key-file", feature = "std"))]
+impl Error for InvalidKeyFile {}
+
+#[cfg(feature = "key-file")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum InvalidKeyFileReason {
+    Malformed,
+    CurveMismatch { expected: &'static str },
+    InvalidScalar,
+}
+
+#[cfg(feature = "key-file")]
+impl From<InvalidKeyFileReason> for InvalidKeyFile {
+    fn from(reason: InvalidKeyFileReason) -> Self {
+        Self(reason)
+    }
+}
+
+/// Indicates that [TLS key share](crate::tls) couldn't be decoded
+#[cfg(feature = "tls")]
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidKeyShare(pub(crate) InvalidKeyShareReason);
+
+#[cfg(feature = "tls")]
+impl fmt::Display for InvalidKeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            InvalidKeyShareReason::Malformed => f.write_str("malformed key share entry"),
+            InvalidKeyShareReason::GroupMismatch => {
+                f.write_str("key share named group doesn't match the curve")
+            }
+            InvalidKeyShareReason::InvalidPoint => f.write_str("invalid key share public key"),
+        }
+    }
+}
+
+#[cfg(all(feature = "tls", feature = "std"))]
+impl Error for InvalidKeyShare {}
+
+#[cfg(feature = "tls")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum InvalidKeyShareReason {
+    Malformed,
+    GroupMismatch,
+    InvalidPoint,
+}
+
+#[cfg(feature = "tls")]
+impl From<InvalidKeyShareReason> for InvalidKeyShare {
+    fn from(reason: InvalidKeyShareReason) -> Self {
+        Self(reason)
+    }
+}
+
+/// Indicates that [BIP39 mnemonic](crate::bip39) is invalid
+#[cfg(feature = "bip39")]
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidMnemonic(pub(crate) InvalidMnemonicReason);
+
+#[cfg(feature = "bip39")]
+impl fmt::Display for InvalidMnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            InvalidMnemonicReason::InvalidWordCount => {
+                f.write_str("mnemonic must have 12, 15, 18, 21, or 24 words")
+            }
+            InvalidMnemonicReason::InvalidWordlist => f.write_str("wordlist must have 2048 words"),
+            InvalidMnemonicReason::UnknownWord => {
+                f.write_str("mnemonic word is not in the wordlist")
+            }
+            InvalidMnemonicReason::ChecksumMismatch => f.write_str("mnemonic checksum mismatch"),
+        }
+    }
+}
+
+#[cfg(all(feature = "bip39", feature = "std"))]
+impl Error for InvalidMnemonic {}
+
+#[cfg(feature = "bip39")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum InvalidMnemonicReason {
+    InvalidWordCount,
+    InvalidWordlist,
+    UnknownWord,
+    ChecksumMismatch,
+}
+
+#[cfg(feature = "bip39")]
+impl From<InvalidMnemonicReason> for InvalidMnemonic {
+    fn from(reason: InvalidMnemonicReason) -> Self {
+        Self(reason)
+    }
+}
+
+/// Indicates that [derivation path](crate::bip39::DerivationPath) is invalid or can't be
+/// used with the curve
+#[cfg(feature = "bip39")]
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidDerivationPath(pub(crate) DerivationPathReason);
+
+#[cfg(feature = "bip39")]
+impl fmt::Display for InvalidDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            DerivationPathReason::Malformed => f.write_str("malformed derivation path"),
+            DerivationPathReason::IndexOutOfRange => {
+                f.write_str("child index must be less than 2^31")
+            }
+            DerivationPathReason::NonHardened => {
+                f.write_str("curve supports only hardened derivation")
+            }
+            DerivationPathReason::HardenedFromPublicKey => {
+                f.write_str("hardened derivation requires secret key")
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "bip39", feature = "std"))]
+impl Error for InvalidDerivationPath {}
+
+#[cfg(feature = "bip39")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DerivationPathReason {
+    Malformed,
+    IndexOutOfRange,
+    NonHardened,
+    HardenedFromPublicKey,
+}
+
+#[cfg(feature = "bip39")]
+impl From<DerivationPathReason> for InvalidDerivationPath {
+    fn from(reason: DerivationPathReason) -> Self {
+        Self(reason)
+    }
+}
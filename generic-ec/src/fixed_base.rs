@@ -0,0 +1,126 @@
+use alloc::vec::Vec;
+use core::{fmt, iter};
+
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+use crate::{Curve, NonZero, Point, Scalar, SecretScalar};
+
+/// Fixed base point with precomputed multiplication table
+///
+/// Protocols often use additional generators besides [`Point::generator()`], e.g. the second
+/// generator $H$ of Pedersen commitments. `FixedBase<E>` precomputes multiples of such a point
+/// once, so that every following multiplication at a scalar costs $\log_{16} q$ point
+/// additions and no doublings. Building the table is more expensive than a single multiplication,
+/// so it pays off when the same base is multiplied many times: construct it once and keep it
+/// alongside other protocol parameters.
+///
+/// Multiplication is constant time: all entries of the table are scanned for every digit of the
+/// scalar, so it can be used with [`SecretScalar`]s.
+///
+/// ```rust
+/// use generic_ec::{FixedBase, NonZero, Point, Scalar, SecretScalar, curves::Secp256k1};
+/// # let mut rng = rand::rngs::OsRng;
+///
+/// let h = Point::<Secp256k1>::generator() * NonZero::<Scalar<_>>::random(&mut rng);
+/// let h = FixedBase::new(h);
+///
+/// let x = SecretScalar::<Secp256k1>::random(&mut rng);
+/// assert_eq!(&h * &x, h.point() * &x);
+/// ```
+///
+/// ## Algorithm
+/// **Precomputation:** for base $P$, table $T_{i,j} = j \cdot 16^i P$ is computed for every digit
+/// position $0 \le i < \log_{16} q$ and digit $1 \le j < 16$.
+///
+/// **Multiplication:** scalar $s$ is written in radix 16 $s = s_0 + s_1 16^1 + \dots$, then
+/// $s P = \sum_i T_{i,s_i}$ where $T_{i,0} = \O$.
+#[derive(Clone)]
+pub struct FixedBase<E: Curve> {
+    base: NonZero<Point<E>>,
+    /// `table[i * 15 + j - 1] = j * 16^i * base`
+    table: Vec<Point<E>>,
+}
+
+impl<E: Curve> FixedBase<E> {
+    /// Precomputes multiplication table for the `base`
+    ///
+    /// Takes any non-zero point, including [`Generator<E>`](crate::Generator), which is useful
+    /// on curves that don't provide precomputed tables for generator multiplication.
+    pub fn new(base: impl Into<NonZero<Point<E>>>) -> Self {
+        let base = base.into();
+        let num_digits = Scalar::<E>::serialized_len() * 2;
+
+        let table = iter::successors(Some(*base), |p| Some(p.double().double().double().double()))
+            .take(num_digits)
+            .flat_map(|p_i| iter::successors(Some(p_i), move |p| Some(p + p_i)).take(15))
+            .collect();
+
+        Self { base, table }
+    }
+
+    /// Returns the base point
+    pub fn point(&self) -> &NonZero<Point<E>> {
+        &self.base
+    }
+
+    /// Returns $s P$ where $P$ is the base point
+    fn mul_scalar(&self, scalar: &Scalar<E>) -> Point<E> {
+        scalar
+            .as_radix16_le()
+            .zip(self.table.chunks_exact(15))
+            .map(|(digit, multiples)| {
+                // Constant-time lookup of `digit * 16^i * base`, zero if digit is zero
+                let mut entry = Point::zero();
+                for (j, multiple) in (1u8..).zip(multiples) {
+                    entry.conditional_assign(multiple, digit.ct_eq(&j));
+                }
+                entry
+            })
+            .sum()
+    }
+}
+
+impl<E: Curve> core::ops::Mul<&Scalar<E>> for &FixedBase<E> {
+    type Output = Point<E>;
+    fn mul(self, rhs: &Scalar<E>) -> Point<E> {
+        self.mul_scalar(rhs)
+    }
+}
+
+impl<E: Curve> core::ops::Mul<&SecretScalar<E>> for &FixedBase<E> {
+    type Output = Point<E>;
+    fn mul(self, rhs: &SecretScalar<E>) -> Point<E> {
+        self.mul_scalar(rhs.as_scalar())
+    }
+}
+
+impl<E: Curve> core::ops::Mul<&NonZero<Scalar<E>>> for &FixedBase<E> {
+    type Output = NonZero<Point<E>>;
+    fn mul(self, rhs: &NonZero<Scalar<E>>) -> NonZero<Point<E>> {
+        // Correctness: base is a non-zero point of prime order, and scalar is non-zero
+        // and less than group order, so the product is non-zero
+        NonZero::new_unchecked(self.mul_scalar(rhs.as_ref()))
+    }
+}
+
+impl<E: Curve> core::ops::Mul<&NonZero<SecretScalar<E>>> for &FixedBase<E> {
+    type Output = NonZero<Point<E>>;
+    fn mul(self, rhs: &NonZero<SecretScalar<E>>) -> NonZero<Point<E>> {
+        // Correctness: same as for `NonZero<Scalar<E>>`
+        NonZero::new_unchecked(self.mul_scalar(rhs.as_scalar()))
+    }
+}
+
+impl<E: Curve> From<NonZero<Point<E>>> for FixedBase<E> {
+    fn from(base: NonZero<Point<E>>) -> Self {
+        Self::new(base)
+    }
+}
+
+impl<E: Curve> fmt::Debug for FixedBase<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBase")
+            .field("base", &self.base)
+            .finish_non_exhaustive()
+    }
+}
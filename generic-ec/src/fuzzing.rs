@@ -0,0 +1,212 @@
+//! Fuzzing harness
+//!
+//! Fuzzer provides arbitrary bytes, which [`Input`] turns into scalars and points: every byte
+//! string maps to some valid value, so the fuzzer doesn't waste iterations on inputs rejected by
+//! the decoding. Harness functions check invariants that must hold for any input and panic if
+//! they don't, which is reported by the fuzzer as a crash.
+//!
+//! Fuzz targets built on top of the harness live in `fuzz/` directory of the repository and
+//! can be run via [`cargo-fuzz`](https://github.com/rust-fuzz/cargo-fuzz):
+//!
+//! ```text
+//! cargo +nightly fuzz run point_decoding
+//! ```
+//!
+//! Harness is also usable from regular tests:
+//!
+//! ```rust
+//! use generic_ec::{curves::Secp256k1, fuzzing};
+//!
+//! fuzzing::point_decoding::<Secp256k1>(b"arbitrary bytes");
+//! fuzzing::scalar_reduction::<Secp256k1>(b"arbitrary bytes");
+//! fuzzing::arithmetic::<Secp256k1>(b"arbitrary bytes");
+//! ```
+
+use crate::{
+    multiscalar::{MultiscalarMul, Naive},
+    Curve, NonZero, Point, Scalar,
+};
+
+/// Reads scalars and points from raw bytes provided by the fuzzer
+///
+/// When input is exhausted, missing bytes are treated as zeroes.
+pub struct Input<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    /// Wraps raw bytes
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Takes up to `n` bytes from the input
+    pub fn bytes(&mut self, n: usize) -> &'a [u8] {
+        let (taken, rest) = self.data.split_at(n.min(self.data.len()));
+        self.data = rest;
+        taken
+    }
+
+    /// Takes a byte from the input
+    pub fn byte(&mut self) -> u8 {
+        self.bytes(1).first().copied().unwrap_or(0)
+    }
+
+    /// Takes a scalar from the input
+    ///
+    /// Scalar is obtained by reducing big-endian integer modulo group order
+    pub fn scalar<E: Curve>(&mut self) -> Scalar<E> {
+        Scalar::from_be_bytes_mod_order(self.bytes(Scalar::<E>::serialized_len()))
+    }
+
+    /// Takes a point from the input
+    ///
+    /// Point is obtained by multiplying generator at the scalar taken from the input
+    pub fn point<E: Curve>(&mut self) -> Point<E> {
+        Point::generator() * self.scalar()
+    }
+
+    /// Returns the rest of the input
+    pub fn rest(self) -> &'a [u8] {
+        self.data
+    }
+}
+
+/// Checks point decoding
+///
+/// Tries to decode the input as a point. Decoded point must be re-encoded in both compressed
+/// and uncompressed forms, and the encodings must decode back to the same point.
+///
+/// ## Panics
+/// Panics if the invariant doesn't hold
+pub fn point_decoding<E: Curve>(data: &[u8]) {
+    let Ok(point) = Point::<E>::from_bytes(data) else {
+        return;
+    };
+    for compressed in [true, false] {
+        let encoded = point.to_bytes(compressed);
+        assert_eq!(
+            encoded.len(),
+            Point::<E>::serialized_len(compressed),
+            "encoding has unexpected length"
+        );
+        assert_eq!(
+            Point::<E>::from_bytes(&encoded).ok(),
+            Some(point),
+            "point doesn't decode back from its encoding"
+        );
+    }
+    assert_eq!(
+        NonZero::from_point(point).is_some(),
+        !point.is_zero(),
+        "non-zero check is inconsistent"
+    );
+}
+
+/// Checks scalar decoding and reduction
+///
+/// Input is reduced modulo group order in both byte orders, and decoded as canonical scalar.
+/// Inputs longer than 256 bytes are truncated, which is enough to exercise reduction.
+///
+/// ## Panics
+/// Panics if reduction is inconsistent with decoding, or depends on the byte order
+pub fn scalar_reduction<E: Curve>(data: &[u8]) {
+    let data = &data[..data.len().min(MAX_SCALAR_INPUT)];
+    let be = Scalar::<E>::from_be_bytes_mod_order(data);
+    let reversed = data.iter().rev().copied().collect::<ReversedBytes>();
+    let le = Scalar::<E>::from_le_bytes_mod_order(reversed.as_slice());
+    assert_eq!(be, le, "reduction depends on byte order");
+
+    assert_eq!(
+        Scalar::<E>::from_be_bytes(be.to_be_bytes()).ok(),
+        Some(be),
+        "reduced scalar doesn't decode back from its encoding"
+    );
+    if let Ok(decoded) = Scalar::<E>::from_be_bytes(data) {
+        assert_eq!(decoded, be, "canonical scalar is changed by reduction");
+    }
+
+    // Reduction is compatible with arithmetic: (hi * 2^8 + lo) mod q
+    if let Some((lo, hi)) = data.split_last() {
+        let hi = Scalar::<E>::from_be_bytes_mod_order(hi);
+        assert_eq!(
+            hi * Scalar::from(0x100_u16) + Scalar::from(*lo),
+            be,
+            "reduction is inconsistent with scalar arithmetic"
+        );
+    }
+}
+
+/// Differential testing of point arithmetic
+///
+/// Takes scalars $a, b$ and point $P$ from the input, and compares results of the optimized
+/// arithmetic provided by the curve backend with straightforward reference algorithms: scalar
+/// multiplication against double-and-add, multiscalar multiplication against [`Naive`]. Also
+/// checks group laws.
+///
+/// ## Panics
+/// Panics if results don't match
+pub fn arithmetic<E: Curve>(data: &[u8]) {
+    let mut input = Input::new(data);
+    let a = input.scalar::<E>();
+    let b = input.scalar::<E>();
+    let p = input.point::<E>();
+
+    assert_eq!(a * p, double_and_add(&a, &p), "scalar multiplication");
+    assert_eq!(
+        Point::generator() * a,
+        double_and_add(&a, &Point::generator().to_point()),
+        "generator multiplication"
+    );
+    assert_eq!((a + b) * p, a * p + b * p, "distributivity");
+    assert_eq!((a * b) * p, a * (b * p), "associativity");
+    assert_eq!(p + p, p.double(), "doubling");
+    assert_eq!(p + (-p), Point::zero(), "additive inverse");
+    assert_eq!(-(a * p), (-a) * p, "negation");
+
+    let scalars = [a, b, a * b];
+    let points = [p, Point::generator() * b, p + Point::generator()];
+    assert_eq!(
+        Scalar::multiscalar_mul(scalars.iter().zip(&points)),
+        Naive::multiscalar_mul(scalars.iter().zip(&points)),
+        "multiscalar multiplication"
+    );
+}
+
+/// Reference scalar multiplication: double-and-add over bits of the scalar
+fn double_and_add<E: Curve>(scalar: &Scalar<E>, point: &Point<E>) -> Point<E> {
+    scalar.bits_be().fold(Point::zero(), |acc, bit| {
+        let acc = acc.double();
+        if bit {
+            acc + point
+        } else {
+            acc
+        }
+    })
+}
+
+const MAX_SCALAR_INPUT: usize = 256;
+
+/// Reversed input bytes, without requiring `alloc`
+struct ReversedBytes {
+    bytes: [u8; MAX_SCALAR_INPUT],
+    len: usize,
+}
+
+impl ReversedBytes {
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl FromIterator<u8> for ReversedBytes {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut bytes = [0u8; MAX_SCALAR_INPUT];
+        let mut len = 0;
+        for (dst, src) in bytes.iter_mut().zip(iter) {
+            *dst = src;
+            len += 1;
+        }
+        Self { bytes, len }
+    }
+}
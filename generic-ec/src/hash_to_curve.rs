@@ -16,6 +16,20 @@
 //!
 //! # Ok::<_, generic_ec::errors::HashError>(())
 //! ```
+//!
+//! ## Map to curve
+//! Hash to curve is composed of hashing the message into field elements, and mapping them to
+//! points via deterministic map (SSWU for secp256k1 and secp256r1). The map is exposed separately
+//! via [`map_to_curve`] for protocols that derive field elements on their own. Curves that
+//! support it implement [`MapToCurve`] trait.
+//!
+//! ```rust
+//! use generic_ec::{Point, curves::Secp256r1, hash_to_curve};
+//!
+//! let u = [7u8; 48];
+//! let point: Point<Secp256r1> = hash_to_curve::map_to_curve(&u)?;
+//! # Ok::<_, generic_ec::errors::HashError>(())
+//! ```
 
 use crate::as_raw::{FromRaw, TryFromRaw};
 use crate::core::hash_to_curve::HashToCurve;
@@ -24,7 +38,7 @@ use crate::errors::{HashError, HashErrorReason};
 use crate::{Point, Scalar};
 
 #[doc(inline)]
-pub use crate::core::hash_to_curve::Tag;
+pub use crate::core::hash_to_curve::{MapToCurve, Tag};
 
 /// Hash to curve primitive
 pub trait FromHash
@@ -63,3 +77,20 @@ where
         Ok(Scalar::from_raw(scalar))
     }
 }
+
+/// Maps `u` to a point on curve `E` via deterministic map to curve
+///
+/// `u` must be exactly [`E::MAP_INPUT_LEN`](MapToCurve::MAP_INPUT_LEN) bytes long. It's
+/// interpreted as big-endian integer and reduced modulo field order, so `u` can be an output of
+/// `expand_message` as in `hash_to_field`, or a field element left-padded with zeroes.
+///
+/// Output is not uniformly distributed, and the map is invertible for a fraction of points,
+/// so unlike [hash to curve](FromHash), it's not a random oracle. To obtain a uniform point,
+/// map two independent field elements and add the results.
+pub fn map_to_curve<E: Curve + MapToCurve>(u: &[u8]) -> Result<Point<E>, HashError> {
+    if u.len() != E::MAP_INPUT_LEN {
+        return Err(HashError(HashErrorReason::InvalidMapInput));
+    }
+    let point = E::map_to_curve(u).or(Err(HashError(HashErrorReason::HashFailed)))?;
+    Point::try_from_raw(point).ok_or(HashError(HashErrorReason::ProducedValueInvalid))
+}
@@ -0,0 +1,99 @@
+//! Armored key files for secret scalars
+//!
+//! Stores [`SecretScalar<E>`](SecretScalar) in a text file tagged with the name of the curve, so
+//! a key generated for one curve can't be accidentally loaded as a key on another curve.
+//!
+//! Only unencrypted key files are currently supported:
+//!
+//! ```text
+//! -----BEGIN GENERIC-EC UNENCRYPTED SECRET KEY-----
+//! Curve: secp256k1
+//!
+//! 9u2YvLMRKMQvYJvGtyCZ1KnnmSq2BHhHXrYsjGfKXmE=
+//! -----END GENERIC-EC UNENCRYPTED SECRET KEY-----
+//! ```
+//!
+//! Body is a base64-encoded big-endian scalar. The key is stored in plaintext: the file must be
+//! protected by other means, e.g. file system permissions. Both the armor label and the function
+//! names say so explicitly, so passphrase-encrypted key files can be added under their own label
+//! without any ambiguity of which format a file is in. All intermediate buffers holding the
+//! secret are erased on drop.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{key_file, curves::Secp256k1, SecretScalar};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let secret_key = SecretScalar::<Secp256k1>::random(&mut rng);
+//! let file = key_file::encode_unencrypted(&secret_key);
+//!
+//! let loaded: SecretScalar<Secp256k1> = key_file::decode_unencrypted(&file)?;
+//! assert_eq!(loaded.expose_secret(|x| *x), secret_key.expose_secret(|x| *x));
+//! # Ok::<_, generic_ec::errors::InvalidKeyFile>(())
+//! ```
+
+use alloc::{string::String, vec};
+
+use base64ct::{Base64, Encoding};
+use zeroize::Zeroizing;
+
+use crate::{
+    errors::{InvalidKeyFile, InvalidKeyFileReason as Reason},
+    Curve, SecretScalar,
+};
+
+const BEGIN: &str = "-----BEGIN GENERIC-EC UNENCRYPTED SECRET KEY-----";
+const END: &str = "-----END GENERIC-EC UNENCRYPTED SECRET KEY-----";
+const CURVE_HEADER: &str = "Curve: ";
+
+/// Encodes secret scalar into armored unencrypted key file
+pub fn encode_unencrypted<E: Curve>(secret: &SecretScalar<E>) -> Zeroizing<String> {
+    let bytes = Zeroizing::new(secret.expose_secret(|s| s.to_be_bytes().to_vec()));
+    let body = Zeroizing::new(Base64::encode_string(&bytes));
+
+    let mut file = Zeroizing::new(String::with_capacity(
+        BEGIN.len() + CURVE_HEADER.len() + E::CURVE_NAME.len() + body.len() + END.len() + 5,
+    ));
+    file.push_str(BEGIN);
+    file.push('\n');
+    file.push_str(CURVE_HEADER);
+    file.push_str(E::CURVE_NAME);
+    file.push_str("\n\n");
+    file.push_str(&body);
+    file.push('\n');
+    file.push_str(END);
+    file.push('\n');
+    file
+}
+
+/// Decodes secret scalar from armored unencrypted key file
+///
+/// Returns error if file is malformed, it's not an unencrypted key file, or it contains a key on
+/// different curve.
+pub fn decode_unencrypted<E: Curve>(file: &str) -> Result<SecretScalar<E>, InvalidKeyFile> {
+    let mut lines = file.lines().map(str::trim);
+    if lines.next() != Some(BEGIN) {
+        return Err(Reason::Malformed.into());
+    }
+    let curve = lines
+        .next()
+        .and_then(|line| line.strip_prefix(CURVE_HEADER))
+        .ok_or(Reason::Malformed)?;
+    if curve != E::CURVE_NAME {
+        return Err(Reason::CurveMismatch {
+            expected: E::CURVE_NAME,
+        }
+        .into());
+    }
+    if lines.next() != Some("") {
+        return Err(Reason::Malformed.into());
+    }
+    let body = lines.next().ok_or(Reason::Malformed)?;
+    if lines.next() != Some(END) || lines.any(|line| !line.is_empty()) {
+        return Err(Reason::Malformed.into());
+    }
+
+    let mut bytes = Zeroizing::new(vec![0u8; body.len()]);
+    let bytes = Base64::decode(body, &mut bytes).map_err(|_| Reason::Malformed)?;
+    SecretScalar::from_be_bytes(bytes).map_err(|_| Reason::InvalidScalar.into())
+}
@@ -25,6 +25,19 @@
 //! Hash to curve, hash to scalar primitives, accessing affine coordinates of points are available for some curves through
 //! `FromHash` and other traits.
 //!
+//! Generic code can require curve capabilities via marker traits [`SupportsHashToCurve`],
+//! [`HasCofactor`], [`PairingFriendly`], and [`HasEndomorphism`]. Then protocol doesn't compile
+//! for curves lacking the capability, and compiler error explains which capability is missing.
+//!
+//! ```rust,compile_fail
+//! use generic_ec::{curves::Ed25519, SupportsHashToCurve};
+//!
+//! fn vrf<E: SupportsHashToCurve>() { /* ... */ }
+//!
+//! // error: curve `Ed25519` doesn't support hash to curve
+//! vrf::<Ed25519>();
+//! ```
+//!
 //! ## Security & guarantees
 //!
 //! Library mitigates a bunch of attacks (such as small-group attack) by design by enforcing following checks:
@@ -38,6 +51,11 @@
 //! always enforced. E.g. if you're deserializing a sequence of bytes that represents an invalid point,
 //! deserialization will result into error.
 //!
+//! Decoding, deserialization, and arithmetic never panic, whatever input is given: failures are reported
+//! via errors from [`errors`] module. The only functions that may panic document it in `## Panics` section
+//! (e.g. [`Scalar::random`] panics if randomness source is broken), and they have fallible counterparts
+//! (e.g. [`Scalar::try_random`]).
+//!
 //! ### `SecretScalar<E>`
 //!
 //! Sometimes your scalar represents some sensitive value like secret key, and you want to keep it safer.
@@ -101,12 +119,43 @@
 //! or you're not fine with using existing implementation, you may define your implementation of `Curve` trait
 //! and enjoy using the same handy primitives `Point<YOUR_EC>`, `Scalar<YOUR_EC>`, and etc.
 //!
+//! Make sure that your implementation passes checks from [`testing`] module (requires `testing` feature).
+//!
 //! ## Features
 //!
 //! * `curve-{name}` enables specified curve support. See list of [supported curves].
 //! * `all-curves` enables all supported curves
+//! * `curve-secp256k1-precomputed-tables` speeds up generator multiplication on secp256k1 curve using
+//!   precomputed tables (requires `std`). Other backend options are documented in [`generic-ec-curves`]
+//!   crate.
+//! * `reference-backend` enables [`curves::reference`] module with slow and simple implementation of
+//!   secp256k1 and ed25519 curves, intended for differential testing of optimized backends. Not
+//!   constant-time, never use it in production
 //! * `serde` enables points/scalar (de)serialization support. (enabled by default)
 //! * `std` enables support of standard library (enabled by default)
+//! * `debug-secrets` reveals values of secret scalars in `Debug` output (they're redacted by default).
+//!   Intended for local debugging only, never enable it in production
+//! * `tracing` emits [`tracing`](https://docs.rs/tracing) spans from multiscalar multiplication and
+//!   events on points/scalars deserialization failures (curve name and failure reason), helping to
+//!   diagnose malformed messages from specific peers
+//! * `testing` enables [`testing`] module with conformance checks for curve implementations and
+//!   deterministic randomness for reproducible tests
+//! * `fuzzing` enables [`fuzzing`] module with harness for fuzz targets: constructors of scalars and
+//!   points from raw bytes, and invariant checks of decoding and arithmetic
+//! * `ct-tests` enables [`ct_tests`] module with dudect-style harnesses detecting timing leaks in
+//!   scalar multiplication, constant-time equality, and secret scalars serialization (requires `std`)
+//! * `group` implements [zkcrypto](https://github.com/zkcrypto) traits `ff::Field`, `ff::PrimeField`,
+//!   `group::Group`, and `group::GroupEncoding` for `Scalar<E>` and `Point<E>`, so they can be consumed
+//!   by zkcrypto ecosystem (e.g. `bellman`, `halo2` gadgets)
+//! * `rust-crypto` implements `elliptic_curve::sec1::{ToEncodedPoint, FromEncodedPoint}` for points of
+//!   RustCrypto-based curves, and conversions between non-zero points/scalars and `elliptic_curve::{PublicKey,
+//!   SecretKey, NonZeroScalar}`, so RustCrypto protocol crates (e.g. `ecdsa`) accept generic-ec keys
+//! * `spki` enables [`spki`] module parsing public keys from X.509 `SubjectPublicKeyInfo` structures and
+//!   certificates, with curve auto-detection
+//! * `key-file` enables [`key_file`] module storing secret scalars in armored unencrypted text files
+//!   tagged with the curve name
+//! * `tls` enables [`tls`] module encoding and decoding public keys as TLS 1.3 `KeyShareEntry`
+//! * `bip39` enables [`bip39`] module deriving master keys of HD wallets from BIP39 mnemonics
 //!
 //! ## Examples
 //!
@@ -169,6 +218,7 @@
 //!
 //! [examples]: #examples
 //! [supported curves]: #supported-curves
+//! [`generic-ec-curves`]: https://docs.rs/generic-ec-curves#backend-tuning
 //!
 //! ## License
 //!
@@ -176,7 +226,15 @@
 
 #![forbid(missing_docs)]
 #![cfg_attr(not(test), forbid(unused_crate_dependencies))]
-#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used))]
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unreachable
+    )
+)]
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
@@ -190,16 +248,39 @@ pub use generic_ec_core as core;
 
 mod arithmetic;
 pub mod as_raw;
+#[cfg(feature = "bip39")]
+pub mod bip39;
 pub mod coords;
+#[cfg(feature = "ct-tests")]
+pub mod ct_tests;
 mod encoded;
 pub mod errors;
+#[cfg(feature = "alloc")]
+mod fixed_base;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 mod generator;
 pub mod hash_to_curve;
+#[cfg(feature = "key-file")]
+pub mod key_file;
 pub mod multiscalar;
 mod non_zero;
 mod point;
+#[cfg(feature = "rust-crypto")]
+mod rust_crypto;
+pub mod same_curve;
 mod scalar;
 mod secret_scalar;
+#[cfg(feature = "spki")]
+pub mod spki;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "tls")]
+pub mod tls;
+pub mod uniform;
+pub mod wire;
+#[cfg(feature = "group")]
+mod zkcrypto;
 
 /// Common traits for points and scalars
 pub mod traits {
@@ -211,20 +292,45 @@ pub mod traits {
         /// Checks whether `self` is zero
         fn is_zero(&self) -> bool;
     }
+
+    /// Scalar value that may be secret
+    ///
+    /// Implemented for [`Scalar<E>`](crate::Scalar), [`SecretScalar<E>`](crate::SecretScalar),
+    /// and their non-zero versions. Lets generic code accept both public and secret scalars,
+    /// while keeping every place where a secret is read easy to find.
+    pub trait ExposeSecret<E: crate::Curve> {
+        /// Gives closure `f` access to the scalar value
+        ///
+        /// For secret scalars, it's the same as
+        /// [`SecretScalar::expose_secret`](crate::SecretScalar::expose_secret)
+        fn expose_secret<R>(&self, f: impl FnOnce(&crate::Scalar<E>) -> R) -> R;
+    }
+
+    impl<E: crate::Curve, T: ExposeSecret<E>> ExposeSecret<E> for &T {
+        fn expose_secret<R>(&self, f: impl FnOnce(&crate::Scalar<E>) -> R) -> R {
+            (*self).expose_secret(f)
+        }
+    }
 }
 
 pub mod serde;
 
 pub use self::{
-    core::Curve,
+    core::{
+        Curve, Curve128, Curve192, HasCofactor, HasEndomorphism, PairingFriendly,
+        SupportsHashToCurve,
+    },
     encoded::{EncodedPoint, EncodedScalar},
     generator::Generator,
     non_zero::definition::NonZero,
-    point::definition::Point,
-    scalar::{Radix16Iter, Scalar},
+    point::{definition::Point, normalized::NormalizedPoint, x_only::XOnlyPoint},
+    scalar::{BitsIter, LimbsIter, Radix16Iter, Scalar},
     secret_scalar::definition::SecretScalar,
 };
 
+#[cfg(feature = "alloc")]
+pub use self::fixed_base::FixedBase;
+
 /// Curves supported out of the box
 pub mod curves {
     #[cfg(feature = "curve-ed25519")]
@@ -239,4 +345,14 @@ pub mod curves {
     #[cfg(feature = "curve-stark")]
     #[cfg_attr(docsrs, doc(cfg(feature = "curve-stark")))]
     pub use generic_ec_curves::Stark;
+
+    /// Slow and simple implementation of secp256k1 and ed25519 curves for differential testing
+    ///
+    /// Refer to [`generic_ec_curves::reference`] for details. Reference curves are not
+    /// constant-time and must never be used in production.
+    #[cfg(feature = "reference-backend")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "reference-backend")))]
+    pub mod reference {
+        pub use generic_ec_curves::reference::{Ed25519, Secp256k1};
+    }
 }
@@ -49,6 +49,16 @@
 //!
 //! Alternatively, if you need to use a specific algorithm, this module provides
 //! [`Straus`] and [`Pippenger`].
+//!
+//! Alternative implementations (e.g. hardware-accelerated) can be plugged in via [`MsmBackend`]
+//! trait, and used either at a particular call site through [`Offload`], or by the whole
+//! program once registered via `register_backend` (requires `std` feature).
+//!
+//! If input is too large to be buffered in memory, use [`Scalar::multiscalar_mul_iter`]
+//! (or [`Chunked`]) that processes input in chunks.
+//!
+//! For ed25519 curve, [`Dalek`] delegates multiscalar multiplication to `curve25519-dalek`
+//! which uses SIMD backend (AVX2) when CPU supports it.
 
 use core::iter;
 
@@ -78,6 +88,10 @@ pub trait MultiscalarMul<E: Curve> {
 /// * [`Straus`] when `n < 50`
 /// * [`Pippenger`] otherwise
 ///
+/// When `std` feature is on, and a [backend](MsmBackend) is [registered](register_backend)
+/// for the curve `E`, inputs of at least [`MIN_SIZE`](MsmBackend::MIN_SIZE) pairs are processed
+/// by the backend instead.
+///
 /// It may be more convenient to use [`Scalar::multiscalar_mul`] which is an alias
 /// to `Default`.
 pub struct Default;
@@ -100,15 +114,40 @@ impl<E: Curve> MultiscalarMul<E> for Default {
         S: AsRef<Scalar<E>>,
         P: AsRef<Point<E>>,
     {
+        #[cfg(feature = "std")]
+        if let Some(backend) = registry::registered_backend::<E>() {
+            let (scalars, points): (Vec<Scalar<E>>, Vec<Point<E>>) = scalar_points
+                .into_iter()
+                .map(|(scalar, point)| (*scalar.as_ref(), *point.as_ref()))
+                .unzip();
+            if scalars.len() >= backend.min_size {
+                return (backend.multiscalar_mul)(&scalars, &points);
+            }
+            let mut scalars = scalars
+                .iter()
+                .map(Scalar::as_radix16_be)
+                .collect::<Vec<_>>();
+            return Self::multiscalar_mul_inner(&mut scalars, &points);
+        }
+
         let (mut scalars, points): (Vec<Radix16Iter<E>>, Vec<Point<E>>) = scalar_points
             .into_iter()
             .map(|(scalar, point)| (scalar.as_ref().as_radix16_be(), *point.as_ref()))
             .unzip();
+        Self::multiscalar_mul_inner(&mut scalars, &points)
+    }
+}
 
+#[cfg(feature = "alloc")]
+impl Default {
+    fn multiscalar_mul_inner<E: Curve>(
+        scalars: &mut [Radix16Iter<E>],
+        points: &[Point<E>],
+    ) -> Point<E> {
         if scalars.len() < 50 {
-            Straus::multiscalar_mul_inner(&mut scalars, &points)
+            Straus::multiscalar_mul_inner(scalars, points)
         } else {
-            Pippenger::mutliscalar_mul_inner(&mut scalars, &points)
+            Pippenger::mutliscalar_mul_inner(scalars, points)
         }
     }
 }
@@ -402,3 +441,275 @@ impl Pippenger {
         result
     }
 }
+
+/// Multiscalar multiplication with bounded memory
+///
+/// Other algorithms buffer all the input before processing it, which may take too much memory
+/// when input is very large (e.g. verifying a batch of hundreds of thousands of proofs). `Chunked`
+/// consumes input iterator in chunks of `CHUNK_SIZE` pairs, each chunk is processed using
+/// algorithm `M`, and results are summed up. Memory consumption is bounded by the size of
+/// a chunk, regardless of input size.
+///
+/// Splitting input into chunks makes multiscalar multiplication slightly slower: each chunk
+/// does its own doublings. With default chunk size of $1024$ pairs, the overhead is negligible.
+///
+/// [`Scalar::multiscalar_mul_iter`] is an alias to `Chunked` with default parameters.
+#[cfg(feature = "alloc")]
+pub struct Chunked<M = Default, const CHUNK_SIZE: usize = 1024>(core::marker::PhantomData<M>);
+
+#[cfg(feature = "alloc")]
+impl<E: Curve, M: MultiscalarMul<E>, const CHUNK_SIZE: usize> MultiscalarMul<E>
+    for Chunked<M, CHUNK_SIZE>
+{
+    fn multiscalar_mul<S, P>(scalar_points: impl IntoIterator<Item = (S, P)>) -> Point<E>
+    where
+        S: AsRef<Scalar<E>>,
+        P: AsRef<Point<E>>,
+    {
+        let mut scalar_points = scalar_points.into_iter();
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE.min(scalar_points.size_hint().0));
+        let mut result = Point::zero();
+        loop {
+            chunk.clear();
+            chunk.extend(
+                scalar_points
+                    .by_ref()
+                    .take(CHUNK_SIZE.max(1))
+                    .map(|(scalar, point)| (*scalar.as_ref(), *point.as_ref())),
+            );
+            if chunk.is_empty() {
+                return result;
+            }
+            result += M::multiscalar_mul(chunk.iter().map(|(s, p)| (s, p)));
+        }
+    }
+}
+
+/// Multiscalar multiplication backend
+///
+/// Allows plugging in alternative implementations, e.g. hardware-accelerated ones (GPU via CUDA
+/// or Metal), provided by external crates. Backend takes input as slices, so it can be passed
+/// to the device as is.
+///
+/// Backend can be used in two ways:
+/// * At a particular call site, via [`Offload`] algorithm
+/// * For the whole program, via [`register_backend`] (requires `std` feature). Once
+///   registered, it's used by [`Default`](struct@Default) algorithm, and, consequently, by
+///   [`Scalar::multiscalar_mul`] and all code built on top of it, e.g. proof verifiers.
+pub trait MsmBackend<E: Curve> {
+    /// Minimal size of input processed by the backend
+    ///
+    /// Smaller inputs are processed on CPU using [`Default`](struct@Default) algorithm, as
+    /// offloading overhead typically outweighs the speedup on them.
+    const MIN_SIZE: usize = 0;
+
+    /// Performs multiscalar multiplication
+    ///
+    /// Returns `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`. Called with
+    /// `scalars.len() == points.len()`.
+    fn multiscalar_mul(scalars: &[Scalar<E>], points: &[Point<E>]) -> Point<E>;
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Curve> MsmBackend<E> for Pippenger {
+    fn multiscalar_mul(scalars: &[Scalar<E>], points: &[Point<E>]) -> Point<E> {
+        <Self as MultiscalarMul<E>>::multiscalar_mul(scalars.iter().zip(points))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Curve> MsmBackend<E> for Straus {
+    fn multiscalar_mul(scalars: &[Scalar<E>], points: &[Point<E>]) -> Point<E> {
+        <Self as MultiscalarMul<E>>::multiscalar_mul(scalars.iter().zip(points))
+    }
+}
+
+/// Offloads multiscalar multiplication to the backend `B`
+///
+/// Inputs of at least [`B::MIN_SIZE`](MsmBackend::MIN_SIZE) pairs are processed by the backend,
+/// smaller ones by [`Default`](struct@Default) algorithm.
+///
+/// ```rust
+/// use generic_ec::{Curve, Point, Scalar, multiscalar::{MsmBackend, MultiscalarMul, Offload}};
+///
+/// /// Backend provided by an external crate
+/// struct Gpu;
+/// impl<E: Curve> MsmBackend<E> for Gpu {
+///     const MIN_SIZE: usize = 1 << 16;
+///     fn multiscalar_mul(scalars: &[Scalar<E>], points: &[Point<E>]) -> Point<E> {
+///         // Transfers input to the device and computes the sum there
+///         # <generic_ec::multiscalar::Pippenger as MsmBackend<E>>::multiscalar_mul(scalars, points)
+///     }
+/// }
+///
+/// # fn doc_fn<E: Curve>(scalar_points: &[(Scalar<E>, Point<E>)]) -> Point<E> {
+/// Offload::<Gpu>::multiscalar_mul(scalar_points.iter().map(|(s, p)| (s, p)))
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub struct Offload<B>(core::marker::PhantomData<B>);
+
+#[cfg(feature = "alloc")]
+impl<E: Curve, B: MsmBackend<E>> MultiscalarMul<E> for Offload<B> {
+    fn multiscalar_mul<S, P>(scalar_points: impl IntoIterator<Item = (S, P)>) -> Point<E>
+    where
+        S: AsRef<Scalar<E>>,
+        P: AsRef<Point<E>>,
+    {
+        let (scalars, points): (Vec<Scalar<E>>, Vec<Point<E>>) = scalar_points
+            .into_iter()
+            .map(|(scalar, point)| (*scalar.as_ref(), *point.as_ref()))
+            .unzip();
+        if scalars.len() >= B::MIN_SIZE {
+            B::multiscalar_mul(&scalars, &points)
+        } else {
+            Default::multiscalar_mul(scalars.iter().zip(&points))
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod registry {
+    use std::any::{Any, TypeId};
+    use std::boxed::Box;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::RwLock;
+    use std::vec::Vec;
+
+    use super::MsmBackend;
+    use crate::{Curve, Point, Scalar};
+
+    /// [`MsmBackend::multiscalar_mul`] of a backend
+    type MultiscalarMulFn<E> = fn(&[Scalar<E>], &[Point<E>]) -> Point<E>;
+
+    /// Backend registered for curve `E`
+    pub(super) struct Registered<E: Curve> {
+        pub multiscalar_mul: MultiscalarMulFn<E>,
+        pub min_size: usize,
+    }
+
+    impl<E: Curve> Clone for Registered<E> {
+        fn clone(&self) -> Self {
+            Self {
+                multiscalar_mul: self.multiscalar_mul,
+                min_size: self.min_size,
+            }
+        }
+    }
+
+    struct Entry {
+        curve: TypeId,
+        /// Contains `Registered<E>` for curve `E` identified by `curve`
+        backend: Box<dyn Any + Send + Sync>,
+    }
+
+    static BACKENDS: RwLock<Vec<Entry>> = RwLock::new(Vec::new());
+    /// Set when at least one backend is registered, lets [`Default`](super::Default) skip
+    /// the lock when no backend is used
+    static ANY_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+    /// Registers multiscalar multiplication backend `B` for curve `E`
+    ///
+    /// Once registered, [`Default`](super::Default) algorithm (and, consequently,
+    /// [`Scalar::multiscalar_mul`](crate::Scalar::multiscalar_mul) and proof verifiers built on
+    /// top of it) uses the backend for inputs of at least
+    /// [`B::MIN_SIZE`](MsmBackend::MIN_SIZE) pairs. Smaller inputs are still processed on CPU.
+    ///
+    /// Replaces backend previously registered for the curve `E`, if any. Backend affects the
+    /// whole program, so it's meant to be registered by the application once on start up,
+    /// not by libraries. Libraries may use [`Offload`](super::Offload) instead.
+    ///
+    /// ```rust
+    /// use generic_ec::{Curve, Point, Scalar};
+    /// use generic_ec::multiscalar::{register_backend, MsmBackend};
+    ///
+    /// /// Backend provided by an external crate
+    /// struct Gpu;
+    /// impl<E: Curve> MsmBackend<E> for Gpu {
+    ///     const MIN_SIZE: usize = 1 << 16;
+    ///     fn multiscalar_mul(scalars: &[Scalar<E>], points: &[Point<E>]) -> Point<E> {
+    ///         // Transfers input to the device and computes the sum there
+    ///         # <generic_ec::multiscalar::Pippenger as MsmBackend<E>>::multiscalar_mul(scalars, points)
+    ///     }
+    /// }
+    ///
+    /// fn main_of_application<E: Curve>() {
+    ///     register_backend::<E, Gpu>();
+    ///     // Large multiscalar multiplications on curve `E` are now offloaded to GPU
+    /// }
+    /// ```
+    pub fn register_backend<E: Curve, B: MsmBackend<E>>() {
+        let registered = Registered::<E> {
+            multiscalar_mul: B::multiscalar_mul,
+            min_size: B::MIN_SIZE,
+        };
+        let mut backends = BACKENDS.write().unwrap_or_else(|e| e.into_inner());
+        backends.retain(|b| b.curve != TypeId::of::<E>());
+        backends.push(Entry {
+            curve: TypeId::of::<E>(),
+            backend: Box::new(registered),
+        });
+        ANY_REGISTERED.store(true, Ordering::Release);
+    }
+
+    /// Unregisters multiscalar multiplication backend for curve `E`
+    ///
+    /// Returns `true` if a backend was registered
+    pub fn unregister_backend<E: Curve>() -> bool {
+        let mut backends = BACKENDS.write().unwrap_or_else(|e| e.into_inner());
+        let len_before = backends.len();
+        backends.retain(|b| b.curve != TypeId::of::<E>());
+        ANY_REGISTERED.store(!backends.is_empty(), Ordering::Release);
+        backends.len() != len_before
+    }
+
+    /// Returns backend registered for curve `E`, if any
+    pub(super) fn registered_backend<E: Curve>() -> Option<Registered<E>> {
+        if !ANY_REGISTERED.load(Ordering::Acquire) {
+            return None;
+        }
+        let backends = BACKENDS.read().unwrap_or_else(|e| e.into_inner());
+        backends
+            .iter()
+            .find(|b| b.curve == TypeId::of::<E>())?
+            .backend
+            .downcast_ref::<Registered<E>>()
+            .cloned()
+    }
+}
+
+#[cfg(feature = "std")]
+pub use registry::{register_backend, unregister_backend};
+
+/// Multiscalar multiplication implemented in `curve25519-dalek`
+///
+/// Only available for [`Ed25519`](crate::curves::Ed25519) curve. `curve25519-dalek` picks
+/// the algorithm (Straus or Pippenger) based on size of input, and the fastest backend available
+/// on the target: on x86_64, AVX2 support is detected at runtime, and SIMD backend is used if
+/// it's available, which gives 2-3x speedup compared to [`Straus`] and [`Pippenger`].
+///
+/// Similarly to other algorithms in this module, it's not constant time.
+#[cfg(all(feature = "curve-ed25519", feature = "alloc"))]
+pub struct Dalek;
+
+#[cfg(all(feature = "curve-ed25519", feature = "alloc"))]
+impl MultiscalarMul<crate::curves::Ed25519> for Dalek {
+    fn multiscalar_mul<S, P>(
+        scalar_points: impl IntoIterator<Item = (S, P)>,
+    ) -> Point<crate::curves::Ed25519>
+    where
+        S: AsRef<Scalar<crate::curves::Ed25519>>,
+        P: AsRef<Point<crate::curves::Ed25519>>,
+    {
+        use crate::as_raw::AsRaw;
+
+        let (scalars, points): (Vec<_>, Vec<_>) = scalar_points
+            .into_iter()
+            .map(|(scalar, point)| (*scalar.as_ref().as_raw(), *point.as_ref().as_raw()))
+            .unzip();
+        let sum = generic_ec_curves::ed25519::vartime_multiscalar_mul(scalars, points);
+        // Correctness: sum of valid points is a valid point, i.e. it's on curve and
+        // free of torsion component
+        Point::from_raw_unchecked(sum)
+    }
+}
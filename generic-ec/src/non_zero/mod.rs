@@ -9,7 +9,7 @@ use subtle::{ConstantTimeEq, CtOption};
 use crate::{
     as_raw::FromRaw,
     core::Samplable,
-    errors::{ZeroPoint, ZeroScalar},
+    errors::{RandomnessFailure, ZeroPoint, ZeroScalar},
     Curve, Point, Scalar, SecretScalar,
 };
 
@@ -50,15 +50,25 @@ impl<E: Curve> NonZero<Scalar<E>> {
     /// ## Panics
     /// Panics if randomness source returned 100 zero scalars in a row. It happens with
     /// $2^{-25600}$ probability, which practically means that randomness source is broken.
+    /// Use [`try_random`](Self::try_random) to handle such failure instead.
     pub fn random<R: RngCore>(rng: &mut R) -> Self {
-        match iter::repeat_with(|| E::Scalar::random(rng))
+        match Self::try_random(rng) {
+            Ok(s) => s,
+            #[allow(clippy::panic)]
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Generates random non-zero scalar
+    ///
+    /// Same as [`random`](Self::random), but returns error instead of panicking if randomness
+    /// source is broken.
+    pub fn try_random<R: RngCore>(rng: &mut R) -> Result<Self, RandomnessFailure> {
+        iter::repeat_with(|| E::Scalar::random(rng))
             .take(100)
             .flat_map(|s| NonZero::from_scalar(Scalar::from_raw(s)))
             .next()
-        {
-            Some(s) => s,
-            None => panic!("defected source of randomness"),
-        }
+            .ok_or(RandomnessFailure)
     }
 
     /// Constructs $S = 1$
@@ -141,7 +151,7 @@ impl<E: Curve> NonZero<SecretScalar<E>> {
     /// Returns `None` if scalar is zero
     pub fn ct_from_secret_scalar(secret_scalar: SecretScalar<E>) -> CtOption<Self> {
         let zero = Scalar::zero();
-        let is_non_zero = !secret_scalar.as_ref().ct_eq(&zero);
+        let is_non_zero = !secret_scalar.as_scalar().ct_eq(&zero);
 
         // Correctness: although we technically construct `NonZero` regardless if
         // it's actually non-zero, `CtOption` never exposes it, so `NonZero` with
@@ -161,6 +171,14 @@ impl<E: Curve> NonZero<SecretScalar<E>> {
         // Correctness: `inv` is nonzero by definition
         Self::new_unchecked(inv)
     }
+
+    /// Takes the non-zero scalar out of the protection
+    ///
+    /// See [`SecretScalar::into_inner_unprotected`]
+    pub fn into_inner_unprotected(self) -> NonZero<Scalar<E>> {
+        // Correctness: `self` is non-zero
+        NonZero::new_unchecked(self.into_inner().into_inner_unprotected())
+    }
 }
 
 impl<E: Curve> From<NonZero<Point<E>>> for Point<E> {
@@ -302,10 +320,9 @@ impl<E: Curve> crate::traits::One for NonZero<Scalar<E>> {
     }
 }
 
-impl<E: Curve> AsRef<Scalar<E>> for NonZero<SecretScalar<E>> {
-    fn as_ref(&self) -> &Scalar<E> {
-        let secret_scalar: &SecretScalar<E> = self.as_ref();
-        secret_scalar.as_ref()
+impl<E: Curve, T: crate::traits::ExposeSecret<E>> crate::traits::ExposeSecret<E> for NonZero<T> {
+    fn expose_secret<R>(&self, f: impl FnOnce(&Scalar<E>) -> R) -> R {
+        self.as_ref().expose_secret(f)
     }
 }
 
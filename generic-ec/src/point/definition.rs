@@ -9,6 +9,18 @@ use crate::{as_raw::AsRaw, core::*};
 /// [`Point::zero()`](Point::zero).
 ///
 /// Point implements all necessary arithmetic operations: points addition, multiplication at scalar, etc.
+///
+/// ## Ordering and hashing
+/// Point implements [`Ord`] and [`Hash`], so it can be used as a key in `BTreeMap`/`HashMap`, and
+/// lists of points can be sorted deterministically (e.g. when processing messages in multiparty
+/// protocols). Both are defined by canonical compressed encoding of the point
+/// ([`.to_bytes(true)`](Point::to_bytes)): points are compared as byte strings, and the same
+/// point always has the same hash regardless of its internal representation.
+///
+/// Comparison and hashing are **not constant time**: they take time depending on the value of
+/// the point, so they must not be used on points that need to stay secret. Each call encodes
+/// the point, which involves field inversion; if the same point is compared or hashed many times,
+/// consider [normalizing](Point::normalize) it first.
 #[derive(Copy, Clone, Default, PartialEq, Eq)]
 pub struct Point<E: Curve>(E::Point);
 
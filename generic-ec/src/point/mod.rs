@@ -8,13 +8,15 @@ use crate::{
     as_raw::{AsRaw, TryFromRaw},
     core::*,
     errors::InvalidPoint,
-    EncodedPoint, Generator,
+    EncodedPoint, Generator, NonZero, Scalar,
 };
 
 use self::definition::Point;
 
 pub mod coords;
 pub mod definition;
+pub mod normalized;
+pub mod x_only;
 
 impl<E: Curve> Point<E> {
     /// Curve generator
@@ -97,6 +99,117 @@ impl<E: Curve> Point<E> {
             .and_then(Self::try_from_raw)
             .ok_or(InvalidPoint)
     }
+
+    /// Returns size of bytes buffer that can fit serialized point
+    ///
+    /// Matches length of [`to_bytes(compressed)`](Self::to_bytes) output for any point
+    pub fn serialized_len(compressed: bool) -> usize {
+        if compressed {
+            E::CompressedPointArray::LEN
+        } else {
+            E::UncompressedPointArray::LEN
+        }
+    }
+
+    /// Blinds the public key: $A' = h \cdot A$
+    ///
+    /// Blinded key is unlinkable to the original one for anyone who doesn't know the blinding
+    /// factor $h$, while the key owner can derive the matching secret key via
+    /// [`SecretScalar::blind`](crate::SecretScalar::blind). Used, for instance, in Tor onion
+    /// services (where $h$ is derived from the time period) and in credential systems.
+    ///
+    /// Blinding factor must be non-zero, otherwise blinded key is [identity point](Self::zero).
+    ///
+    /// ```rust
+    /// use generic_ec::{Point, Scalar, SecretScalar, curves::Secp256k1};
+    /// use rand::rngs::OsRng;
+    ///
+    /// let secret_key = SecretScalar::<Secp256k1>::random(&mut OsRng);
+    /// let public_key = Point::generator() * &secret_key;
+    ///
+    /// let blinding = Scalar::random(&mut OsRng);
+    /// assert_eq!(
+    ///     public_key.blind(&blinding),
+    ///     Point::generator() * secret_key.blind(&blinding),
+    /// );
+    /// ```
+    pub fn blind(&self, blinding: &Scalar<E>) -> Self {
+        self * blinding
+    }
+
+    /// Returns $P + Q$ if it's not an [identity point](Self::zero)
+    ///
+    /// Useful when protocol requires the sum to be non-zero: degenerate case is surfaced
+    /// as `None` instead of being caught by downstream validation (if ever).
+    pub fn checked_add(&self, rhs: &Self) -> Option<NonZero<Self>> {
+        NonZero::from_point(self + rhs)
+    }
+
+    /// Returns $P - Q$ if it's not an [identity point](Self::zero)
+    ///
+    /// Returns `None` if $P = Q$
+    ///
+    /// ```rust
+    /// use generic_ec::{Point, curves::Secp256k1};
+    ///
+    /// let g = Point::<Secp256k1>::generator().to_point();
+    /// assert!(g.checked_sub(&g).is_none());
+    /// assert_eq!(*g.double().checked_sub(&g).unwrap(), g);
+    /// ```
+    pub fn checked_sub(&self, rhs: &Self) -> Option<NonZero<Self>> {
+        NonZero::from_point(self - rhs)
+    }
+}
+
+impl<E: Curve + HasEndomorphism> Point<E> {
+    /// Computes endomorphism $\phi(P) = \lambda P$
+    ///
+    /// Much cheaper than multiplication at $\lambda$. Only available for curves that have
+    /// efficiently computable endomorphism, like secp256k1.
+    ///
+    /// ```rust
+    /// use generic_ec::{as_raw::FromRaw, curves::Secp256k1, HasEndomorphism, Point, Scalar};
+    /// # let mut rng = rand::rngs::OsRng;
+    ///
+    /// let point = Point::<Secp256k1>::generator() * Scalar::random(&mut rng);
+    /// let lambda = Scalar::from_raw(Secp256k1::lambda());
+    /// assert_eq!(point.endomorphism(), point * lambda);
+    /// ```
+    pub fn endomorphism(&self) -> Self {
+        // Endomorphism maps the prime order subgroup onto itself
+        Point::from_raw_unchecked(E::endomorphism(self.as_raw()))
+    }
+}
+
+#[cfg(feature = "curve-ed25519")]
+impl Point<crate::curves::Ed25519> {
+    /// Encodes the point as X25519 public key ($u$ coordinate on Curve25519)
+    ///
+    /// Encoding loses the sign of $x$ coordinate: $P$ and $-P$ have the same encoding.
+    ///
+    /// ```rust
+    /// use generic_ec::{curves::Ed25519, Point, Scalar};
+    /// # let mut rng = rand::rngs::OsRng;
+    ///
+    /// let point = Point::<Ed25519>::generator() * Scalar::random(&mut rng);
+    /// let decoded = Point::<Ed25519>::from_x25519_bytes(&point.to_x25519_bytes())?;
+    /// assert!(decoded == point || decoded == -point);
+    /// # Ok::<_, generic_ec::errors::InvalidPoint>(())
+    /// ```
+    pub fn to_x25519_bytes(&self) -> [u8; 32] {
+        self.as_raw().to_montgomery_u()
+    }
+
+    /// Decodes X25519 public key
+    ///
+    /// Out of two points $\pm P$ sharing the same $u$ coordinate, returns the one with
+    /// non-negative $x$. Returns error if `bytes` don't correspond to a point in prime-order
+    /// subgroup.
+    pub fn from_x25519_bytes(bytes: &[u8; 32]) -> Result<Self, InvalidPoint> {
+        generic_ec_curves::ed25519::Point::from_montgomery_u(bytes)
+            .and_then(Self::try_from_raw)
+            .ok_or(InvalidPoint)
+    }
 }
 
 impl<E: Curve> TryFromRaw for Point<E> {
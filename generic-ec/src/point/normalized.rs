@@ -0,0 +1,159 @@
+use core::fmt;
+use core::hash::{self, Hash};
+use core::ops::Deref;
+
+use crate::{as_raw::AsRaw, core::*, EncodedPoint, Point};
+
+/// Point with cached normalized (affine) representation
+///
+/// Points are kept in projective coordinates, so arithmetic doesn't require field inversions. The
+/// point gets normalized (converted into affine form, which takes a field inversion) whenever it's
+/// serialized, hashed, or ordered. That's redundant when the same point is encoded many times,
+/// e.g. when it's appended to several transcripts, used as a key in a map, or sorted.
+///
+/// `NormalizedPoint` is obtained by calling [`Point::normalize`]. It normalizes the point once and
+/// caches its compressed encoding, which is then reused by [`to_bytes(true)`](Self::to_bytes),
+/// [`Hash`], [`Ord`], and [`PartialEq`] implementations. Hashing, ordering, and equality are
+/// consistent with those of [`Point`].
+///
+/// ```rust
+/// use generic_ec::{Point, Scalar, curves::Secp256k1};
+/// # let mut rng = rand::rngs::OsRng;
+///
+/// let mut points: Vec<_> = (0..10)
+///     .map(|_| Point::<Secp256k1>::generator() * Scalar::random(&mut rng))
+///     // Each point is normalized only once, instead of at every comparison
+///     .map(|p| p.normalize())
+///     .collect();
+/// points.sort();
+/// ```
+#[derive(Clone)]
+pub struct NormalizedPoint<E: Curve> {
+    point: Point<E>,
+    compressed: E::CompressedPointArray,
+}
+
+impl<E: Curve> Point<E> {
+    /// Normalizes the point and caches its encoding
+    ///
+    /// See [`NormalizedPoint`]
+    pub fn normalize(&self) -> NormalizedPoint<E> {
+        NormalizedPoint {
+            point: *self,
+            compressed: self.as_raw().to_bytes_compressed(),
+        }
+    }
+}
+
+impl<E: Curve> NormalizedPoint<E> {
+    /// Returns the point
+    pub fn point(&self) -> &Point<E> {
+        &self.point
+    }
+
+    /// Returns the point, dropping cached encoding
+    pub fn into_point(self) -> Point<E> {
+        self.point
+    }
+
+    /// Encodes a point as bytes
+    ///
+    /// Compressed encoding is taken from cache, uncompressed encoding is computed from scratch.
+    /// See [`Point::to_bytes`].
+    pub fn to_bytes(&self, compressed: bool) -> EncodedPoint<E> {
+        if compressed {
+            EncodedPoint::new_compressed(self.compressed.clone())
+        } else {
+            self.point.to_bytes(false)
+        }
+    }
+
+    /// Returns cached compressed encoding of the point
+    pub fn compressed_bytes(&self) -> &[u8] {
+        self.compressed.as_ref()
+    }
+}
+
+impl<E: Curve> From<Point<E>> for NormalizedPoint<E> {
+    fn from(point: Point<E>) -> Self {
+        point.normalize()
+    }
+}
+
+impl<E: Curve> From<NormalizedPoint<E>> for Point<E> {
+    fn from(point: NormalizedPoint<E>) -> Self {
+        point.into_point()
+    }
+}
+
+impl<E: Curve> AsRef<Point<E>> for NormalizedPoint<E> {
+    fn as_ref(&self) -> &Point<E> {
+        &self.point
+    }
+}
+
+impl<E: Curve> Deref for NormalizedPoint<E> {
+    type Target = Point<E>;
+    fn deref(&self) -> &Point<E> {
+        &self.point
+    }
+}
+
+impl<E: Curve> PartialEq for NormalizedPoint<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.compressed_bytes() == other.compressed_bytes()
+    }
+}
+
+impl<E: Curve> Eq for NormalizedPoint<E> {}
+
+impl<E: Curve> Hash for NormalizedPoint<E> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        // Must match `Hash` implementation of `Point<E>`
+        state.write(self.compressed_bytes())
+    }
+}
+
+impl<E: Curve> PartialOrd for NormalizedPoint<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E: Curve> Ord for NormalizedPoint<E> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.compressed_bytes().cmp(other.compressed_bytes())
+    }
+}
+
+impl<E: Curve> fmt::Debug for NormalizedPoint<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("NormalizedPoint");
+        s.field("curve", &E::CURVE_NAME);
+
+        #[cfg(feature = "std")]
+        {
+            s.field("value", &hex::encode(self.compressed_bytes()));
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            s.field("value", &"...");
+        }
+
+        s.finish()
+    }
+}
+
+#[cfg(feature = "udigest")]
+impl<E: Curve> udigest::Digestable for NormalizedPoint<E> {
+    fn unambiguously_encode<B>(&self, encoder: udigest::encoding::EncodeValue<B>)
+    where
+        B: udigest::Buffer,
+    {
+        // Must match `Digestable` implementation of `Point<E>`
+        let mut s = encoder.encode_struct();
+        s.add_field("curve").encode_leaf_value(E::CURVE_NAME);
+        s.add_field("point").encode_leaf_value(self.to_bytes(true));
+        s.finish();
+    }
+}
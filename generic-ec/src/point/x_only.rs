@@ -0,0 +1,193 @@
+use core::fmt;
+use core::hash::{self, Hash};
+use core::ops::Deref;
+
+use crate::{as_raw::AsRaw, core::*, errors::InvalidPoint, Point};
+
+/// Point encoded by its $x$ coordinate only, with even $y$ coordinate
+///
+/// X-only encoding is used by BIP-340 (Schnorr signatures) and Taproot: out of two points with
+/// the same $x$ coordinate, the one with even $y$ is implied. `XOnlyPoint` maintains this
+/// invariant: decoding always lifts $x$ to the point with even $y$, and conversion from [`Point`]
+/// fails if its $y$ is odd. Encoding is the SEC1 compressed encoding without the first byte, i.e.
+/// 32 bytes for secp256k1, so it's only available for curves that use SEC1 encoding. Zero point
+/// can't be represented.
+///
+/// ```rust
+/// use generic_ec::{Point, Scalar, XOnlyPoint, curves::Secp256k1};
+/// # let mut rng = rand::rngs::OsRng;
+///
+/// let point = Point::<Secp256k1>::generator() * Scalar::random(&mut rng);
+///
+/// // Either `point` or `-point` has even y
+/// let (x_only, negated) = XOnlyPoint::from_point_even_y(&point)?;
+/// let bytes: [u8; 32] = x_only.to_array();
+///
+/// let decoded = XOnlyPoint::<Secp256k1>::from_bytes(bytes)?;
+/// assert_eq!(decoded, x_only);
+/// assert_eq!(Point::from(decoded), if negated { -point } else { point });
+/// # Ok::<(), generic_ec::errors::InvalidPoint>(())
+/// ```
+#[derive(Clone)]
+pub struct XOnlyPoint<E: Curve> {
+    point: Point<E>,
+    compressed: E::CompressedPointArray,
+}
+
+impl<E: Curve> XOnlyPoint<E> {
+    /// Decodes x-only point from its $x$ coordinate
+    ///
+    /// Returns error if curve doesn't use SEC1 encoding, `bytes` have wrong length, or $x$ is not
+    /// a coordinate of any point.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, InvalidPoint> {
+        let bytes = bytes.as_ref();
+        let mut compressed = E::CompressedPointArray::zeroes();
+        let encoded = compressed.as_mut();
+        if !has_sec1_encoding::<E>() || encoded.len() != bytes.len() + 1 {
+            return Err(InvalidPoint);
+        }
+        encoded[0] = 0x02;
+        encoded[1..].copy_from_slice(bytes);
+        let point = Point::from_bytes(&compressed)?;
+        if point.is_zero() {
+            return Err(InvalidPoint);
+        }
+        Ok(Self { point, compressed })
+    }
+
+    /// Returns x-only point of `point` or `-point`, whichever has even $y$
+    ///
+    /// Second returned value indicates whether the point was negated. Returns error if `point`
+    /// is zero, or curve doesn't use SEC1 encoding.
+    pub fn from_point_even_y(point: &Point<E>) -> Result<(Self, bool), InvalidPoint> {
+        let negated = !has_even_y(point)?;
+        let point = if negated { -point } else { *point };
+        Ok((Self::try_from(point)?, negated))
+    }
+
+    /// Returns $x$ coordinate of the point
+    pub fn as_bytes(&self) -> &[u8] {
+        self.compressed.as_ref().get(1..).unwrap_or_default()
+    }
+
+    /// Returns the point
+    pub fn point(&self) -> &Point<E> {
+        &self.point
+    }
+
+    /// Returns the point, dropping cached encoding
+    pub fn into_point(self) -> Point<E> {
+        self.point
+    }
+}
+
+#[cfg(feature = "curve-secp256k1")]
+impl XOnlyPoint<crate::curves::Secp256k1> {
+    /// Returns 32 bytes $x$ coordinate of the point, as used in BIP-340
+    pub fn to_array(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(self.as_bytes());
+        bytes
+    }
+}
+
+/// Checks whether SEC1 encoded point has even $y$
+///
+/// Returns error if the point is zero, or curve doesn't use SEC1 encoding
+fn has_even_y<E: Curve>(point: &Point<E>) -> Result<bool, InvalidPoint> {
+    if !has_sec1_encoding::<E>() || point.is_zero() {
+        return Err(InvalidPoint);
+    }
+    match point.as_raw().to_bytes_compressed().as_ref().first() {
+        Some(0x02) => Ok(true),
+        Some(0x03) => Ok(false),
+        _ => Err(InvalidPoint),
+    }
+}
+
+/// Checks whether curve encodes points as specified in SEC1
+fn has_sec1_encoding<E: Curve>() -> bool {
+    let compressed_len = E::CompressedPointArray::zeroes().as_ref().len();
+    let uncompressed_len = E::UncompressedPointArray::zeroes().as_ref().len();
+    uncompressed_len == 2 * compressed_len - 1
+}
+
+impl<E: Curve> TryFrom<Point<E>> for XOnlyPoint<E> {
+    type Error = InvalidPoint;
+
+    /// Converts point with even $y$ into x-only point
+    ///
+    /// Returns error if the point has odd $y$, is zero, or curve doesn't use SEC1 encoding.
+    fn try_from(point: Point<E>) -> Result<Self, Self::Error> {
+        if !has_even_y(&point)? {
+            return Err(InvalidPoint);
+        }
+        Ok(Self {
+            point,
+            compressed: point.as_raw().to_bytes_compressed(),
+        })
+    }
+}
+
+impl<E: Curve> From<XOnlyPoint<E>> for Point<E> {
+    fn from(point: XOnlyPoint<E>) -> Self {
+        point.into_point()
+    }
+}
+
+impl<E: Curve> AsRef<Point<E>> for XOnlyPoint<E> {
+    fn as_ref(&self) -> &Point<E> {
+        &self.point
+    }
+}
+
+impl<E: Curve> Deref for XOnlyPoint<E> {
+    type Target = Point<E>;
+    fn deref(&self) -> &Point<E> {
+        &self.point
+    }
+}
+
+impl<E: Curve> PartialEq for XOnlyPoint<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<E: Curve> Eq for XOnlyPoint<E> {}
+
+impl<E: Curve> Hash for XOnlyPoint<E> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        state.write(self.as_bytes())
+    }
+}
+
+impl<E: Curve> PartialOrd for XOnlyPoint<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E: Curve> Ord for XOnlyPoint<E> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl<E: Curve> fmt::Debug for XOnlyPoint<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("XOnlyPoint");
+        s.field("curve", &E::CURVE_NAME);
+
+        #[cfg(feature = "std")]
+        {
+            s.field("value", &hex::encode(self.as_bytes()));
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            s.field("value", &"...");
+        }
+
+        s.finish()
+    }
+}
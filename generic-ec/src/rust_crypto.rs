@@ -0,0 +1,126 @@
+//! Interop with [RustCrypto](https://github.com/RustCrypto/traits/tree/master/elliptic-curve) traits
+//!
+//! Points and scalars of curves backed by RustCrypto (secp256k1, secp256r1, stark) can be converted
+//! into `elliptic-curve` keys, so protocol crates such as `ecdsa` accept them without extra glue.
+//!
+//! Zkcrypto [`Group`](elliptic_curve::Group) and [`PrimeField`](elliptic_curve::PrimeField) traits,
+//! re-exported by `elliptic-curve`, are implemented by `group` feature.
+
+use elliptic_curve::group::Curve as _;
+use elliptic_curve::sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint};
+use elliptic_curve::{CurveArithmetic, FieldBytesSize, NonZeroScalar, PublicKey, SecretKey};
+use generic_ec_curves::rust_crypto::{RustCryptoCurve, RustCryptoPoint, RustCryptoScalar};
+use subtle::CtOption;
+
+use crate::{
+    as_raw::{AsRaw, FromRaw, TryFromRaw},
+    errors::InvalidPoint,
+    Curve, NonZero, Point, Scalar, SecretScalar,
+};
+
+impl<C, X> ToEncodedPoint<C> for Point<RustCryptoCurve<C, X>>
+where
+    C: CurveArithmetic,
+    C::AffinePoint: ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+    RustCryptoCurve<C, X>: Curve<Point = RustCryptoPoint<C>, Scalar = RustCryptoScalar<C>>,
+{
+    fn to_encoded_point(&self, compress: bool) -> EncodedPoint<C> {
+        self.as_raw().0.to_affine().to_encoded_point(compress)
+    }
+}
+
+impl<C, X> FromEncodedPoint<C> for Point<RustCryptoCurve<C, X>>
+where
+    C: CurveArithmetic,
+    C::AffinePoint: FromEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+    RustCryptoCurve<C, X>: Curve<Point = RustCryptoPoint<C>, Scalar = RustCryptoScalar<C>>,
+{
+    /// Decodes a point, checks that it's on the curve and free of torsion component
+    fn from_encoded_point(point: &EncodedPoint<C>) -> CtOption<Self> {
+        C::AffinePoint::from_encoded_point(point)
+            .and_then(|point| Point::ct_try_from_raw(RustCryptoPoint(point.into())))
+    }
+}
+
+impl<C, X> From<NonZero<Point<RustCryptoCurve<C, X>>>> for PublicKey<C>
+where
+    C: CurveArithmetic,
+    RustCryptoCurve<C, X>: Curve<Point = RustCryptoPoint<C>, Scalar = RustCryptoScalar<C>>,
+{
+    fn from(point: NonZero<Point<RustCryptoCurve<C, X>>>) -> Self {
+        #[allow(clippy::expect_used)]
+        PublicKey::from_affine(point.as_raw().0.to_affine())
+            .expect("non-zero point is never an identity")
+    }
+}
+
+impl<C, X> TryFrom<PublicKey<C>> for NonZero<Point<RustCryptoCurve<C, X>>>
+where
+    C: CurveArithmetic,
+    RustCryptoCurve<C, X>: Curve<Point = RustCryptoPoint<C>, Scalar = RustCryptoScalar<C>>,
+{
+    type Error = InvalidPoint;
+
+    /// Converts public key into a point
+    ///
+    /// Returns error if public key has a torsion component, which is only possible on curves
+    /// with a cofactor
+    fn try_from(public_key: PublicKey<C>) -> Result<Self, Self::Error> {
+        let point =
+            Point::try_from_raw(RustCryptoPoint(public_key.to_projective())).ok_or(InvalidPoint)?;
+        NonZero::from_point(point).ok_or(InvalidPoint)
+    }
+}
+
+impl<C, X> From<NonZero<Scalar<RustCryptoCurve<C, X>>>> for NonZeroScalar<C>
+where
+    C: CurveArithmetic,
+    RustCryptoCurve<C, X>: Curve<Point = RustCryptoPoint<C>, Scalar = RustCryptoScalar<C>>,
+{
+    fn from(scalar: NonZero<Scalar<RustCryptoCurve<C, X>>>) -> Self {
+        #[allow(clippy::expect_used)]
+        NonZeroScalar::new(scalar.as_raw().0)
+            .into_option()
+            .expect("non-zero scalar is never zero")
+    }
+}
+
+impl<C, X> From<NonZeroScalar<C>> for NonZero<Scalar<RustCryptoCurve<C, X>>>
+where
+    C: CurveArithmetic,
+    RustCryptoCurve<C, X>: Curve<Point = RustCryptoPoint<C>, Scalar = RustCryptoScalar<C>>,
+{
+    fn from(scalar: NonZeroScalar<C>) -> Self {
+        // Correctness: `NonZeroScalar` is guaranteed to be non-zero
+        NonZero::new_unchecked(Scalar::from_raw(RustCryptoScalar(*scalar)))
+    }
+}
+
+impl<C, X> From<&NonZero<SecretScalar<RustCryptoCurve<C, X>>>> for SecretKey<C>
+where
+    C: CurveArithmetic,
+    RustCryptoCurve<C, X>: Curve<Point = RustCryptoPoint<C>, Scalar = RustCryptoScalar<C>>,
+{
+    fn from(secret: &NonZero<SecretScalar<RustCryptoCurve<C, X>>>) -> Self {
+        #[allow(clippy::expect_used)]
+        let scalar = secret
+            .expose_secret(|scalar| NonZeroScalar::new(scalar.as_raw().0))
+            .into_option()
+            .expect("non-zero scalar is never zero");
+        SecretKey::from(scalar)
+    }
+}
+
+impl<C, X> From<&SecretKey<C>> for NonZero<SecretScalar<RustCryptoCurve<C, X>>>
+where
+    C: CurveArithmetic,
+    RustCryptoCurve<C, X>: Curve<Point = RustCryptoPoint<C>, Scalar = RustCryptoScalar<C>>,
+{
+    fn from(secret_key: &SecretKey<C>) -> Self {
+        let mut scalar = Scalar::from_raw(RustCryptoScalar(*secret_key.to_nonzero_scalar()));
+        // Correctness: secret key is guaranteed to be non-zero
+        NonZero::new_unchecked(SecretScalar::new(&mut scalar))
+    }
+}
@@ -0,0 +1,160 @@
+//! # Curve equality witness
+//!
+//! Generic code sometimes receives values typed with one curve parameter while it's generic over
+//! another one, e.g. plugin systems passing `Box<dyn Any>` payloads between crates, or protocols
+//! that dispatch on the curve at runtime. [`SameCurve<E1, E2>`] is a proof that `E1` and `E2` are
+//! the same curve: it can only be obtained if the curves are equal, and it casts values
+//! from one curve parameter to the other without `unsafe` code.
+//!
+//! ```rust
+//! use generic_ec::{Curve, Point, Scalar, curves::{Secp256k1, Secp256r1}};
+//! use generic_ec::same_curve::SameCurve;
+//!
+//! fn as_secp256k1<E: Curve>(point: Point<E>) -> Option<Point<Secp256k1>> {
+//!     let witness = SameCurve::<E, Secp256k1>::check()?;
+//!     Some(witness.cast(point))
+//! }
+//!
+//! let point = Point::<Secp256k1>::generator().to_point();
+//! assert_eq!(as_secp256k1(point), Some(point));
+//! assert_eq!(as_secp256k1(Point::<Secp256r1>::generator().to_point()), None);
+//! ```
+//!
+//! When curves are known to be equal at compile time, [`SameCurve::refl`] constructs the witness
+//! without any runtime check.
+
+use core::any::{Any, TypeId};
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{Curve, Generator, NonZero, Point, Scalar, SecretScalar};
+
+/// Witness that curves `E1` and `E2` are the same
+///
+/// Can be obtained via [`SameCurve::check`] or [`SameCurve::refl`].
+pub struct SameCurve<E1: Curve, E2: Curve> {
+    _curves: PhantomData<fn(E1) -> E2>,
+}
+
+impl<E1: Curve, E2: Curve> SameCurve<E1, E2> {
+    /// Checks whether `E1` and `E2` are the same curve
+    ///
+    /// Returns `None` if the curves are different. Curves are compared by their types, so
+    /// two different implementations of the same curve (e.g. `Secp256k1` from different
+    /// backends) are considered different.
+    pub fn check() -> Option<Self> {
+        if TypeId::of::<E1>() == TypeId::of::<E2>() {
+            Some(Self {
+                _curves: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Casts value from curve `E1` to curve `E2`
+    pub fn cast<T: CurveGeneric<E1>>(&self, value: T) -> T::OnCurve<E2> {
+        let mut value = Some(value);
+        let casted = (&mut value as &mut dyn Any).downcast_mut::<Option<T::OnCurve<E2>>>();
+        // Correctness: `E1` and `E2` are the same type, and `CurveGeneric` is sealed and
+        // only implemented for types where `T::OnCurve<E1> = T`, so `T::OnCurve<E2> = T`
+        #[allow(clippy::expect_used)]
+        casted
+            .and_then(Option::take)
+            .expect("E1 and E2 are the same curve")
+    }
+
+    /// Casts reference to the value from curve `E1` to curve `E2`
+    pub fn cast_ref<'a, T: CurveGeneric<E1>>(&self, value: &'a T) -> &'a T::OnCurve<E2> {
+        // Correctness: same as in `cast`
+        #[allow(clippy::expect_used)]
+        (value as &dyn Any)
+            .downcast_ref()
+            .expect("E1 and E2 are the same curve")
+    }
+
+    /// Returns witness that `E2` is the same curve as `E1`
+    pub fn reverse(&self) -> SameCurve<E2, E1> {
+        SameCurve {
+            _curves: PhantomData,
+        }
+    }
+}
+
+impl<E: Curve> SameCurve<E, E> {
+    /// Witness that a curve is the same as itself
+    ///
+    /// Useful to call generic code that takes a witness when curves are statically known to
+    /// be the same.
+    pub fn refl() -> Self {
+        Self {
+            _curves: PhantomData,
+        }
+    }
+}
+
+impl<E1: Curve, E2: Curve> Clone for SameCurve<E1, E2> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E1: Curve, E2: Curve> Copy for SameCurve<E1, E2> {}
+
+impl<E1: Curve, E2: Curve> fmt::Debug for SameCurve<E1, E2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SameCurve")
+            .field(&E1::CURVE_NAME)
+            .field(&E2::CURVE_NAME)
+            .finish()
+    }
+}
+
+/// Value generic over the curve that can be cast by [`SameCurve`]
+///
+/// `OnCurve<E2>` is the same type on curve `E2`. The trait is sealed and implemented for
+/// [`Point`], [`Scalar`], [`SecretScalar`], [`Generator`], and [`NonZero`] of those.
+pub trait CurveGeneric<E: Curve>: sealed::Sealed + Sized + 'static {
+    /// Same type on curve `E2`
+    type OnCurve<E2: Curve>: CurveGeneric<E2>;
+}
+
+impl<E: Curve> CurveGeneric<E> for Point<E> {
+    type OnCurve<E2: Curve> = Point<E2>;
+}
+
+impl<E: Curve> CurveGeneric<E> for Scalar<E> {
+    type OnCurve<E2: Curve> = Scalar<E2>;
+}
+
+impl<E: Curve> CurveGeneric<E> for SecretScalar<E> {
+    type OnCurve<E2: Curve> = SecretScalar<E2>;
+}
+
+impl<E: Curve> CurveGeneric<E> for Generator<E> {
+    type OnCurve<E2: Curve> = Generator<E2>;
+}
+
+impl<E: Curve> CurveGeneric<E> for NonZero<Point<E>> {
+    type OnCurve<E2: Curve> = NonZero<Point<E2>>;
+}
+
+impl<E: Curve> CurveGeneric<E> for NonZero<Scalar<E>> {
+    type OnCurve<E2: Curve> = NonZero<Scalar<E2>>;
+}
+
+impl<E: Curve> CurveGeneric<E> for NonZero<SecretScalar<E>> {
+    type OnCurve<E2: Curve> = NonZero<SecretScalar<E2>>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<E: crate::Curve> Sealed for crate::Point<E> {}
+    impl<E: crate::Curve> Sealed for crate::Scalar<E> {}
+    impl<E: crate::Curve> Sealed for crate::SecretScalar<E> {}
+    impl<E: crate::Curve> Sealed for crate::Generator<E> {}
+    impl<E: crate::Curve> Sealed for crate::NonZero<crate::Point<E>> {}
+    impl<E: crate::Curve> Sealed for crate::NonZero<crate::Scalar<E>> {}
+    impl<E: crate::Curve> Sealed for crate::NonZero<crate::SecretScalar<E>> {}
+}
@@ -10,12 +10,21 @@ use crate::{
     as_raw::{AsRaw, FromRaw},
     core::*,
     encoded::EncodedScalar,
-    errors::InvalidScalar,
+    errors::{InvalidScalar, RandomnessFailure},
 };
 
 /// Scalar modulo curve `E` group order
 ///
 /// Scalar is an integer modulo curve `E` group order.
+///
+/// ## Ordering and hashing
+/// Scalar implements [`Ord`] and [`Hash`], so it can be used as a key in `BTreeMap`/`HashMap`,
+/// and lists of scalars can be sorted deterministically. Both are defined by canonical big-endian
+/// encoding of the scalar ([`.to_be_bytes()`](Scalar::to_be_bytes)), so scalars are ordered as
+/// integers in range $[0, q)$.
+///
+/// Comparison and hashing are **not constant time**: they must not be used on secret values.
+/// [`SecretScalar`](crate::SecretScalar) intentionally implements neither of them.
 #[derive(Copy, Clone, PartialEq, Eq, Default)]
 pub struct Scalar<E: Curve>(E::Scalar);
 
@@ -76,6 +85,122 @@ impl<E: Curve> Scalar<E> {
         inv.map(Self::from_raw)
     }
 
+    /// Returns scalar inverse $S^{-1}$ as non-zero scalar
+    ///
+    /// Returns `None` if $S = 0$. Same as [`Scalar::invert`], but the result type carries the
+    /// guarantee that the inverse is non-zero.
+    ///
+    /// ```rust
+    /// use generic_ec::{Scalar, curves::Secp256k1};
+    ///
+    /// assert!(Scalar::<Secp256k1>::zero().checked_invert().is_none());
+    /// let two_inv = Scalar::<Secp256k1>::from(2).checked_invert().unwrap();
+    /// assert_eq!(Scalar::from(2) * two_inv, Scalar::one());
+    /// ```
+    pub fn checked_invert(&self) -> Option<NonZero<Self>> {
+        NonZero::from_scalar(*self).map(|s| s.invert())
+    }
+
+    /// Returns $S / R = S \cdot R^{-1}$
+    ///
+    /// Returns `None` if $R = 0$
+    ///
+    /// ```rust
+    /// use generic_ec::{Scalar, curves::Secp256k1};
+    ///
+    /// let six = Scalar::<Secp256k1>::from(6);
+    /// assert_eq!(six.checked_div(&Scalar::from(3)), Some(Scalar::from(2)));
+    /// assert_eq!(six.checked_div(&Scalar::zero()), None);
+    /// ```
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        Some(self * rhs.invert()?)
+    }
+
+    /// Checks (in constant-time) if $S > (q - 1) / 2$ where $q$ is the group order
+    ///
+    /// Scalar is "high" if it's in upper half of range $[0, q)$. Out of $S$ and $-S$ exactly one
+    /// is high, except for $S = 0$ which is low. Used, for instance, for low-s normalization of
+    /// ECDSA signatures.
+    ///
+    /// ```rust
+    /// use generic_ec::{Scalar, curves::Secp256k1};
+    ///
+    /// let one = Scalar::<Secp256k1>::one();
+    /// assert!(bool::from(one.is_low()));
+    /// assert!(bool::from((-one).is_high()));
+    /// ```
+    pub fn is_high(&self) -> Choice {
+        IsHigh::is_high(self.as_raw())
+    }
+
+    /// Checks (in constant-time) if $S \le (q - 1) / 2$ where $q$ is the group order
+    ///
+    /// Opposite of [`Scalar::is_high`]
+    pub fn is_low(&self) -> Choice {
+        !self.is_high()
+    }
+
+    /// Returns $-S$ if $S$ [is high](Scalar::is_high), otherwise returns $S$ (constant time)
+    ///
+    /// Resulting scalar is always low. Second returned value indicates whether the scalar was
+    /// negated, which is needed to keep track of parity, e.g. when normalizing a BIP-340 secret
+    /// key along with its public key.
+    ///
+    /// ```rust
+    /// use generic_ec::{Scalar, curves::Secp256k1};
+    /// # let mut rng = rand::rngs::OsRng;
+    ///
+    /// let s = Scalar::<Secp256k1>::random(&mut rng);
+    /// let (normalized, negated) = s.conditional_negate_if_high();
+    /// assert!(bool::from(normalized.is_low()));
+    /// assert_eq!(normalized, if negated.into() { -s } else { s });
+    /// ```
+    pub fn conditional_negate_if_high(&self) -> (Self, Choice) {
+        let is_high = self.is_high();
+        let normalized = Self::conditional_select(self, &-self, is_high);
+        (normalized, is_high)
+    }
+
+    /// Inverts all scalars in place
+    ///
+    /// Uses Montgomery's trick: computes $n$ inverses at cost of a single inversion and $3(n-1)$
+    /// multiplications, which is much faster than inverting each scalar separately. Useful in hot
+    /// loops like Lagrange interpolation over many parties.
+    ///
+    /// Returns error if any of scalars is zero, in that case `scalars` are left unchanged.
+    ///
+    /// ```rust
+    /// use generic_ec::{Scalar, curves::Secp256k1};
+    /// # let mut rng = rand::rngs::OsRng;
+    ///
+    /// let scalars = [(); 5].map(|_| Scalar::<Secp256k1>::random(&mut rng));
+    /// let mut inverses = scalars;
+    /// Scalar::batch_invert(&mut inverses)?;
+    /// for (s, s_inv) in scalars.iter().zip(&inverses) {
+    ///     assert_eq!(s * s_inv, Scalar::one());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn batch_invert(scalars: &mut [Self]) -> Result<(), crate::errors::ZeroScalar> {
+        // prefix_products[i] = scalars[0] * ... * scalars[i - 1]
+        let mut prefix_products = alloc::vec::Vec::with_capacity(scalars.len());
+        let mut product = Scalar::one();
+        for scalar in scalars.iter() {
+            prefix_products.push(product);
+            product *= scalar;
+        }
+
+        // inv = (scalars[0] * ... * scalars[i])^-1
+        let mut inv = product.invert().ok_or(crate::errors::ZeroScalar)?;
+        for (scalar, prefix_product) in scalars.iter_mut().zip(prefix_products).rev() {
+            let scalar_inv = inv * prefix_product;
+            inv *= *scalar;
+            *scalar = scalar_inv;
+        }
+        Ok(())
+    }
+
     /// Encodes scalar as bytes in big-endian order
     ///
     /// ```rust
@@ -172,13 +297,93 @@ impl<E: Curve> Scalar<E> {
     /// ## Panics
     /// Panics if randomness source returned 100 zero scalars in a row. It happens with
     /// $2^{-25600}$ probability, which practically means that randomness source is broken.
+    /// Use [`try_random`](Self::try_random) to handle such failure instead.
     pub fn random<R: RngCore>(rng: &mut R) -> Self {
         NonZero::<Scalar<E>>::random(rng).into()
     }
 
+    /// Generates random non-zero scalar
+    ///
+    /// Same as [`random`](Self::random), but returns error instead of panicking if randomness
+    /// source is broken.
+    pub fn try_random<R: RngCore>(rng: &mut R) -> Result<Self, RandomnessFailure> {
+        NonZero::<Scalar<E>>::try_random(rng).map(Into::into)
+    }
+
+    /// Generates random non-zero scalar, returns it as [`NonZero<Scalar<E>>`](NonZero)
+    ///
+    /// Same as [`NonZero::<Scalar<E>>::random`](NonZero::random)
+    pub fn random_nonzero<R: RngCore>(rng: &mut R) -> NonZero<Self> {
+        NonZero::<Scalar<E>>::random(rng)
+    }
+
+    /// Generates random scalar uniformly distributed in range $[0, \text{bound})$
+    ///
+    /// Useful for sampling values from restricted range, e.g. $2^k$-bounded challenges:
+    ///
+    /// ```rust
+    /// use generic_ec::{NonZero, Scalar, curves::Secp256k1};
+    /// use rand::rngs::OsRng;
+    ///
+    /// let bound = NonZero::from_scalar(Scalar::<Secp256k1>::from(1_u128 << 80)).unwrap();
+    /// let challenge = Scalar::random_below(&bound, &mut OsRng);
+    /// assert!(challenge < *bound);
+    /// ```
+    ///
+    /// Algorithm is based on rejection sampling: we sample an integer having the same bit length
+    /// as the bound, if it's not below the bound, try again. Each attempt succeeds with
+    /// probability at least $1/2$, so sampled value is unbiased. Running time depends on the
+    /// amount of attempts, but not on the sampled value.
+    ///
+    /// ## Panics
+    /// Panics if randomness source failed 256 attempts in a row. It happens with probability
+    /// less than $2^{-256}$, which practically means that randomness source is broken.
+    /// Use [`try_random_below`](Self::try_random_below) to handle such failure instead.
+    pub fn random_below<R: RngCore>(bound: &NonZero<Scalar<E>>, rng: &mut R) -> Self {
+        match Self::try_random_below(bound, rng) {
+            Ok(s) => s,
+            #[allow(clippy::panic)]
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Generates random scalar uniformly distributed in range $[0, \text{bound})$
+    ///
+    /// Same as [`random_below`](Self::random_below), but returns error instead of panicking
+    /// if randomness source is broken.
+    pub fn try_random_below<R: RngCore>(
+        bound: &NonZero<Scalar<E>>,
+        rng: &mut R,
+    ) -> Result<Self, RandomnessFailure> {
+        let bound = bound.as_ref().to_be_bytes();
+        // Bound is non-zero, so it has at least one non-zero byte
+        let leading_zeroes = bound.iter().take_while(|b| **b == 0).count();
+        let bound = &bound[leading_zeroes..];
+        let mask = bound
+            .first()
+            .map(|b| u8::MAX >> b.leading_zeros())
+            .unwrap_or(0);
+
+        let mut bytes = E::ScalarArray::zeroes();
+        for _ in 0..256 {
+            let candidate = &mut bytes.as_mut()[leading_zeroes..];
+            rng.fill_bytes(candidate);
+            if let Some(b) = candidate.first_mut() {
+                *b &= mask
+            }
+            if &*candidate < bound {
+                // Candidate is less than bound, so it's less than group order
+                if let Ok(scalar) = Scalar::from_be_bytes(&bytes) {
+                    return Ok(scalar);
+                }
+            }
+        }
+        Err(RandomnessFailure)
+    }
+
     /// Returns size of bytes buffer that can fit serialized scalar
     pub fn serialized_len() -> usize {
-        E::ScalarArray::zeroes().as_ref().len()
+        E::ScalarArray::LEN
     }
 
     /// Returns scalar big-endian representation in radix $2^4 = 16$
@@ -209,6 +414,48 @@ impl<E: Curve> Scalar<E> {
         Radix16Iter::new(self.to_le_bytes(), false)
     }
 
+    /// Returns bits of the scalar from least to most significant
+    ///
+    /// Amount of bits is `8 * serialized_len()`, i.e. leading zero bits are included.
+    pub fn bits_le(&self) -> BitsIter<E> {
+        BitsIter::new(self.to_le_bytes(), false)
+    }
+
+    /// Returns bits of the scalar from most to least significant
+    ///
+    /// Amount of bits is `8 * serialized_len()`, i.e. leading zero bits are included.
+    pub fn bits_be(&self) -> BitsIter<E> {
+        BitsIter::new(self.to_be_bytes(), true)
+    }
+
+    /// Returns scalar representation in 64 bits limbs from least to most significant
+    ///
+    /// Scalar is $s = l_0 + l_1 2^{64} + l_2 2^{128} + \dots$. Amount of limbs is
+    /// `serialized_len()` divided by 8 and rounded up.
+    pub fn to_limbs_u64(&self) -> LimbsIter<E> {
+        LimbsIter {
+            encoded_scalar: self.to_le_bytes(),
+            next_index: 0,
+        }
+    }
+
+    /// Constructs a scalar from 64 bits limbs given from least to most significant
+    ///
+    /// Inverse of [`to_limbs_u64`](Self::to_limbs_u64). Returns error if represented integer is
+    /// not less than group order.
+    pub fn from_limbs(limbs: &[u64]) -> Result<Self, InvalidScalar> {
+        let mut bytes = E::ScalarArray::zeroes();
+        let mut limbs_bytes = limbs.iter().flat_map(|limb| limb.to_le_bytes());
+        for (byte, limb_byte) in bytes.as_mut().iter_mut().zip(&mut limbs_bytes) {
+            *byte = limb_byte;
+        }
+        // Limbs that don't fit into the scalar must be zero
+        if limbs_bytes.any(|byte| byte != 0) {
+            return Err(InvalidScalar);
+        }
+        Self::from_le_bytes(bytes)
+    }
+
     /// Performs multiscalar multiplication
     ///
     /// Takes iterator of pairs `(scalar, point)`. Returns sum of `scalar * point`. Uses
@@ -221,8 +468,29 @@ impl<E: Curve> Scalar<E> {
         P: AsRef<crate::Point<E>>,
     {
         use crate::multiscalar::MultiscalarMul;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("multiscalar_mul", curve = E::CURVE_NAME).entered();
         crate::multiscalar::Default::multiscalar_mul(scalar_points)
     }
+
+    /// Performs multiscalar multiplication with bounded memory
+    ///
+    /// Same as [`Scalar::multiscalar_mul`], but consumes input in chunks, so it never buffers
+    /// the whole input. Suitable for very large inputs. Uses [`Chunked`](crate::multiscalar::Chunked)
+    /// algorithm.
+    #[cfg(feature = "alloc")]
+    pub fn multiscalar_mul_iter<S, P>(
+        scalar_points: impl IntoIterator<Item = (S, P)>,
+    ) -> crate::Point<E>
+    where
+        S: AsRef<Scalar<E>>,
+        P: AsRef<crate::Point<E>>,
+    {
+        use crate::multiscalar::MultiscalarMul;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("multiscalar_mul_iter", curve = E::CURVE_NAME).entered();
+        <crate::multiscalar::Chunked>::multiscalar_mul(scalar_points)
+    }
 }
 
 impl<E: Curve> AsRaw for Scalar<E> {
@@ -234,6 +502,54 @@ impl<E: Curve> AsRaw for Scalar<E> {
     }
 }
 
+/// Ed25519 and X25519 key clamping
+///
+/// Ed25519 ([RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.5)) and X25519
+/// ([RFC 7748](https://www.rfc-editor.org/rfc/rfc7748#section-5)) derive secret scalar from
+/// 32 bytes by _clamping_: the 3 lowest bits are cleared, the highest bit is cleared, and the
+/// second highest bit is set. The clamped integer $a$ is in range $[2^{254}, 2^{255})$, i.e. it
+/// exceeds the group order, and libraries keep it unreduced. Since points in generic-ec are
+/// torsion-free, $a \cdot P = (a \bmod q) \cdot P$, so the scalar is reduced modulo group order
+/// on import.
+///
+/// Keys are imported as follows:
+/// * Ed25519 secret key (32 bytes seed): hash the seed with SHA-512 and pass the first
+///   32 bytes of the hash to [`clamp_ed25519`](Self::clamp_ed25519). The second half of the
+///   hash is the nonce prefix, it's not a part of the scalar.
+/// * Ed25519 expanded secret key (64 bytes, e.g. from libsodium or `ed25519-dalek`
+///   `ExpandedSecretKey`): its first 32 bytes are already clamped, pass them to
+///   [`from_clamped_ed25519`](Self::from_clamped_ed25519).
+/// * X25519 secret key: pass it to [`clamp_ed25519`](Self::clamp_ed25519), X25519 clamps it
+///   the same way on every use. The public key obtained via ed25519 generator corresponds to
+///   X25519 public key via birational map (up to the sign of $x$ coordinate).
+///
+/// Clamping is idempotent, so clamping a pre-clamped key gives the same scalar as
+/// [`from_clamped_ed25519`](Self::from_clamped_ed25519), the latter additionally checks that the
+/// key is clamped.
+#[cfg(feature = "curve-ed25519")]
+impl Scalar<crate::curves::Ed25519> {
+    /// Clamps little-endian bytes and reduces the resulting integer modulo group order
+    pub fn clamp_ed25519(bytes: &[u8; 32]) -> Self {
+        let mut clamped = *bytes;
+        clamped[0] &= 0b1111_1000;
+        clamped[31] &= 0b0111_1111;
+        clamped[31] |= 0b0100_0000;
+        let scalar = Self::from_raw(IntegerEncoding::from_le_bytes(&clamped));
+        clamped.zeroize();
+        scalar
+    }
+
+    /// Reduces pre-clamped little-endian integer modulo group order
+    ///
+    /// Returns error if `bytes` are not clamped.
+    pub fn from_clamped_ed25519(bytes: &[u8; 32]) -> Result<Self, InvalidScalar> {
+        if bytes[0] & 0b0000_0111 != 0 || bytes[31] & 0b1100_0000 != 0b0100_0000 {
+            return Err(InvalidScalar);
+        }
+        Ok(Self::from_raw(IntegerEncoding::from_le_bytes(bytes)))
+    }
+}
+
 impl<E: Curve> Zeroize for Scalar<E> {
     #[inline]
     fn zeroize(&mut self) {
@@ -241,6 +557,14 @@ impl<E: Curve> Zeroize for Scalar<E> {
     }
 }
 
+#[cfg(feature = "group")]
+impl<E: Curve> Scalar<E> {
+    /// Same as [`FromRaw::from_raw`], but can be used in constant context
+    pub(crate) const fn from_raw_const(scalar: E::Scalar) -> Self {
+        Self(scalar)
+    }
+}
+
 impl<E: Curve> FromRaw for Scalar<E> {
     fn from_raw(scalar: E::Scalar) -> Self {
         Self(scalar)
@@ -295,6 +619,12 @@ impl<E: Curve> crate::traits::One for Scalar<E> {
     }
 }
 
+impl<E: Curve> crate::traits::ExposeSecret<E> for Scalar<E> {
+    fn expose_secret<R>(&self, f: impl FnOnce(&Scalar<E>) -> R) -> R {
+        f(self)
+    }
+}
+
 impl<E: Curve> crate::traits::Samplable for Scalar<E> {
     fn random<R: RngCore>(rng: &mut R) -> Self {
         Self::random(rng)
@@ -485,3 +815,88 @@ impl<E: Curve> ExactSizeIterator for Radix16Iter<E> {
             + if self.next_radix16.is_some() { 1 } else { 0 }
     }
 }
+
+/// Iterator over bits of the scalar
+///
+/// See [`Scalar::bits_le`] and [`Scalar::bits_be`]
+pub struct BitsIter<E: Curve> {
+    encoded_scalar: EncodedScalar<E>,
+    next_index: usize,
+
+    /// Indicates that output is in big-endian. If it's false,
+    /// output is in little-endian
+    is_be: bool,
+}
+
+impl<E: Curve> BitsIter<E> {
+    fn new(encoded_scalar: EncodedScalar<E>, is_be: bool) -> Self {
+        Self {
+            encoded_scalar,
+            next_index: 0,
+            is_be,
+        }
+    }
+}
+
+impl<E: Curve> Iterator for BitsIter<E> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let byte = self.encoded_scalar.get(self.next_index / 8)?;
+        let bit = if self.is_be {
+            7 - self.next_index % 8
+        } else {
+            self.next_index % 8
+        };
+        self.next_index += 1;
+        Some((byte >> bit) & 1 == 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<E: Curve> ExactSizeIterator for BitsIter<E> {
+    fn len(&self) -> usize {
+        self.encoded_scalar.len() * 8 - self.next_index
+    }
+}
+
+/// Iterator over 64 bits limbs of the scalar
+///
+/// See [`Scalar::to_limbs_u64`]
+pub struct LimbsIter<E: Curve> {
+    /// little-endian representation of the scalar
+    encoded_scalar: EncodedScalar<E>,
+    next_index: usize,
+}
+
+impl<E: Curve> Iterator for LimbsIter<E> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.encoded_scalar.get(self.next_index..)?;
+        if chunk.is_empty() {
+            return None;
+        }
+        let chunk = &chunk[..chunk.len().min(8)];
+        self.next_index += chunk.len();
+
+        let mut limb = [0u8; 8];
+        limb[..chunk.len()].copy_from_slice(chunk);
+        Some(u64::from_le_bytes(limb))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<E: Curve> ExactSizeIterator for LimbsIter<E> {
+    fn len(&self) -> usize {
+        (self.encoded_scalar.len() - self.next_index).div_ceil(8)
+    }
+}
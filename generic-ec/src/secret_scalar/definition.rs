@@ -20,8 +20,12 @@ mod with_alloc {
         }
     }
 
-    impl<E: Curve> AsRef<Scalar<E>> for SecretScalar<E> {
-        fn as_ref(&self) -> &Scalar<E> {
+    impl<E: Curve> SecretScalar<E> {
+        /// Returns reference to the secret scalar
+        ///
+        /// Crate-private: outside of the crate, secret is only accessible via
+        /// [`SecretScalar::expose_secret`]
+        pub(crate) fn as_scalar(&self) -> &Scalar<E> {
             &self.0
         }
     }
@@ -52,8 +56,12 @@ mod without_alloc {
         }
     }
 
-    impl<E: Curve> AsRef<Scalar<E>> for SecretScalar<E> {
-        fn as_ref(&self) -> &Scalar<E> {
+    impl<E: Curve> SecretScalar<E> {
+        /// Returns reference to the secret scalar
+        ///
+        /// Crate-private: outside of the crate, secret is only accessible via
+        /// [`SecretScalar::expose_secret`]
+        pub(crate) fn as_scalar(&self) -> &Scalar<E> {
             &self.0
         }
     }
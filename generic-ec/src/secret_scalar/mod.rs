@@ -21,12 +21,51 @@ impl<E: Curve> SecretScalar<E> {
         Self::new(&mut Scalar::one())
     }
 
+    /// Gives closure `f` access to the secret scalar
+    ///
+    /// This is the only way to read the secret value (other than [`into_inner_unprotected`]).
+    /// All the protection measures of `SecretScalar` don't apply to `&Scalar<E>`, so `f` should
+    /// avoid copying the scalar or branching on it. Having a single method to access the secret
+    /// makes every such place easy to find during code review.
+    ///
+    /// Prefer arithmetic operators when possible: they take secret scalars directly.
+    ///
+    /// ```rust
+    /// use generic_ec::{Point, SecretScalar, curves::Secp256k1};
+    /// # let mut rng = rand::rngs::OsRng;
+    ///
+    /// let secret = SecretScalar::<Secp256k1>::random(&mut rng);
+    /// let public_key = secret.expose_secret(|s| Point::generator() * s);
+    /// assert_eq!(public_key, Point::generator() * &secret);
+    /// ```
+    ///
+    /// [`into_inner_unprotected`]: Self::into_inner_unprotected
+    pub fn expose_secret<R>(&self, f: impl FnOnce(&Scalar<E>) -> R) -> R {
+        f(self.as_scalar())
+    }
+
+    /// Takes the scalar out of the protection
+    ///
+    /// Returned scalar is not zeroized on drop and can be freely copied. Use it only when
+    /// the value is not a secret anymore, e.g. it's going to be revealed.
+    pub fn into_inner_unprotected(self) -> Scalar<E> {
+        *self.as_scalar()
+    }
+
     /// Returns scalar inverse
     pub fn invert(&self) -> Option<Self> {
-        let scalar: Option<Scalar<E>> = self.as_ref().ct_invert().into();
+        let scalar: Option<Scalar<E>> = self.as_scalar().ct_invert().into();
         Some(Self::new(&mut scalar?))
     }
 
+    /// Blinds the secret key: $a' = h \cdot a$
+    ///
+    /// Matches the blinded public key obtained via [`Point::blind`](crate::Point::blind)
+    pub fn blind(&self, blinding: &Scalar<E>) -> Self {
+        let mut blinded = self * blinding;
+        Self::new(&mut blinded)
+    }
+
     /// Generates random secret scalar
     pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
         let mut scalar = Scalar::random(rng);
@@ -46,9 +85,37 @@ impl<E: Curve> SecretScalar<E> {
     }
 }
 
+/// Ed25519 and X25519 key clamping
+///
+/// See [`Scalar::clamp_ed25519`] for details on importing keys generated by other libraries.
+#[cfg(feature = "curve-ed25519")]
+impl SecretScalar<crate::curves::Ed25519> {
+    /// Clamps little-endian bytes and reduces the resulting integer modulo group order
+    ///
+    /// Same as [`Scalar::clamp_ed25519`]
+    pub fn clamp_ed25519(bytes: &[u8; 32]) -> Self {
+        let mut scalar = Scalar::clamp_ed25519(bytes);
+        Self::new(&mut scalar)
+    }
+
+    /// Reduces pre-clamped little-endian integer modulo group order
+    ///
+    /// Same as [`Scalar::from_clamped_ed25519`]
+    pub fn from_clamped_ed25519(bytes: &[u8; 32]) -> Result<Self, InvalidScalar> {
+        let mut scalar = Scalar::from_clamped_ed25519(bytes)?;
+        Ok(Self::new(&mut scalar))
+    }
+}
+
+impl<E: Curve> crate::traits::ExposeSecret<E> for SecretScalar<E> {
+    fn expose_secret<R>(&self, f: impl FnOnce(&Scalar<E>) -> R) -> R {
+        SecretScalar::expose_secret(self, f)
+    }
+}
+
 impl<E: Curve> ConstantTimeEq for SecretScalar<E> {
     fn ct_eq(&self, other: &Self) -> Choice {
-        self.as_ref().ct_eq(other.as_ref())
+        self.as_scalar().ct_eq(other.as_scalar())
     }
 }
 
@@ -78,7 +145,14 @@ impl<'s, E: Curve> Product<&'s SecretScalar<E>> for Scalar<E> {
 
 impl<E: Curve> fmt::Debug for SecretScalar<E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("SecretScalar")
+        write!(f, "SecretScalar<{}>(", E::CURVE_NAME)?;
+        #[cfg(feature = "debug-secrets")]
+        for byte in self.as_scalar().to_be_bytes().as_bytes() {
+            write!(f, "{byte:02x}")?;
+        }
+        #[cfg(not(feature = "debug-secrets"))]
+        f.write_str("[REDACTED]")?;
+        f.write_str(")")
     }
 }
 
@@ -73,6 +73,13 @@
 //! }"#);
 //! # Ok(()) }
 //! ```
+//!
+//! ## Runtime-configured format
+//!
+//! When serialization format is only known at runtime, or several wire formats need to be
+//! supported at once (e.g. x-only points, little-endian scalars), use [`SerializationConfig`]:
+//! points and scalars are serialized via [`WithConfig`] wrapper and deserialized via
+//! [`SerializationConfig::seed`].
 
 use phantom_type::PhantomType;
 
@@ -170,6 +177,8 @@ mod optional {
 
     use super::CurveName;
 
+    pub use self::config::{ConfigSeed, Endianness, PointFormat, SerializationConfig, WithConfig};
+
     impl<E: Curve> serde::Serialize for Point<E> {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -215,7 +224,7 @@ mod optional {
         where
             S: serde::Serializer,
         {
-            self.as_ref().serialize(serializer)
+            self.expose_secret(|scalar| scalar.serialize(serializer))
         }
     }
 
@@ -281,7 +290,7 @@ mod optional {
             S: serde::Serializer,
         {
             use serde::Serialize;
-            models::ScalarCompact::from(source.as_ref()).serialize(serializer)
+            source.expose_secret(|scalar| models::ScalarCompact::from(scalar).serialize(serializer))
         }
     }
 
@@ -504,6 +513,474 @@ mod optional {
         }
     }
 
+    /// Serialization format configured at runtime
+    ///
+    /// See [`WithConfig`]
+    mod config {
+        use core::{fmt, marker::PhantomData};
+
+        use serde::{
+            de::{self, DeserializeSeed},
+            ser::{self, SerializeStruct},
+            Serialize,
+        };
+        use serde_with::SerializeAs;
+
+        use crate::{core::ByteArray, Curve, Point, Scalar};
+
+        use super::{error_msg, utils, CurveName};
+
+        const POINT_FIELDS: &[&str] = &["curve", "point"];
+        const SCALAR_FIELDS: &[&str] = &["curve", "scalar"];
+
+        /// Encoding of a point
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum PointFormat {
+            /// Compressed encoding
+            Compressed,
+            /// Uncompressed encoding
+            Uncompressed,
+            /// Only $x$ coordinate of the point, as used in BIP-340
+            ///
+            /// Only supported for curves with SEC1 point encoding (i.e. not ed25519), and only
+            /// for points with even $y$ coordinate: serializing a point with odd $y$ results
+            /// into error.
+            XOnly,
+        }
+
+        /// Byte order of a scalar
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Endianness {
+            /// Big-endian
+            Big,
+            /// Little-endian
+            Little,
+        }
+
+        /// Serialization format of points and scalars
+        ///
+        /// Unlike [`Compact`](super::Compact) which is fixed at compile time, the config is a
+        /// runtime value, so one codebase can (de)serialize points and scalars in several wire
+        /// formats. It's applied via [`WithConfig`] wrapper on serialization, and via
+        /// [`ConfigSeed`] on deserialization.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct SerializationConfig {
+            /// Encoding of points
+            pub point_format: PointFormat,
+            /// Byte order of scalars
+            pub scalar_endianness: Endianness,
+            /// Whether to serialize a point/scalar as a struct with "curve" field specifying
+            /// which curve it belongs to
+            pub curve_tag: bool,
+        }
+
+        impl SerializationConfig {
+            /// Default serialization format: points are uncompressed, scalars are big-endian,
+            /// curve tag is present
+            ///
+            /// Same as serialization via [`serde::Serialize`] implemented for points and scalars
+            pub const DEFAULT: Self = Self {
+                point_format: PointFormat::Uncompressed,
+                scalar_endianness: Endianness::Big,
+                curve_tag: true,
+            };
+            /// Compact serialization format: points are compressed, scalars are big-endian,
+            /// curve tag is omitted
+            ///
+            /// Same as serialization via [`Compact`](super::Compact)
+            pub const COMPACT: Self = Self {
+                point_format: PointFormat::Compressed,
+                scalar_endianness: Endianness::Big,
+                curve_tag: false,
+            };
+
+            /// Sets encoding of points
+            pub const fn set_point_format(mut self, format: PointFormat) -> Self {
+                self.point_format = format;
+                self
+            }
+
+            /// Sets byte order of scalars
+            pub const fn set_scalar_endianness(mut self, endianness: Endianness) -> Self {
+                self.scalar_endianness = endianness;
+                self
+            }
+
+            /// Sets whether curve tag is present
+            pub const fn set_curve_tag(mut self, curve_tag: bool) -> Self {
+                self.curve_tag = curve_tag;
+                self
+            }
+
+            /// Returns [`DeserializeSeed`] deserializing `T` in this format
+            pub fn seed<T>(self) -> ConfigSeed<T> {
+                ConfigSeed {
+                    config: self,
+                    _out: PhantomData,
+                }
+            }
+        }
+
+        impl Default for SerializationConfig {
+            fn default() -> Self {
+                Self::DEFAULT
+            }
+        }
+
+        /// Serializes point or scalar in the format specified by [`SerializationConfig`]
+        ///
+        /// Implemented for [`Point<E>`] and [`Scalar<E>`].
+        ///
+        /// ```rust
+        /// # fn main() -> Result<(), serde_json::Error> {
+        /// use generic_ec::{Point, curves::Secp256k1};
+        /// use generic_ec::serde::{PointFormat, SerializationConfig, WithConfig};
+        /// use serde::de::DeserializeSeed;
+        ///
+        /// let config = SerializationConfig::COMPACT.set_point_format(PointFormat::XOnly);
+        /// let point = Point::<Secp256k1>::generator().to_point();
+        ///
+        /// let json = serde_json::to_string(&WithConfig::new(&point, config))?;
+        /// assert_eq!(json, r#""79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798""#);
+        ///
+        /// let mut deserializer = serde_json::Deserializer::from_str(&json);
+        /// let decoded = config.seed::<Point<Secp256k1>>().deserialize(&mut deserializer)?;
+        /// assert_eq!(point, decoded);
+        /// # Ok(()) }
+        /// ```
+        #[derive(Debug, Clone, Copy)]
+        pub struct WithConfig<'a, T> {
+            value: &'a T,
+            config: SerializationConfig,
+        }
+
+        impl<'a, T> WithConfig<'a, T> {
+            /// Wraps a value
+            pub fn new(value: &'a T, config: SerializationConfig) -> Self {
+                Self { value, config }
+            }
+        }
+
+        /// Deserializes point or scalar in the format specified by [`SerializationConfig`]
+        ///
+        /// Obtained via [`SerializationConfig::seed`]. Implements [`DeserializeSeed`] for
+        /// [`Point<E>`] and [`Scalar<E>`].
+        #[derive(Debug, Clone, Copy)]
+        pub struct ConfigSeed<T> {
+            config: SerializationConfig,
+            _out: PhantomData<fn() -> T>,
+        }
+
+        impl<E: Curve> Serialize for WithConfig<'_, Point<E>> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                let compressed = !matches!(self.config.point_format, PointFormat::Uncompressed);
+                let encoded = self.value.to_bytes(compressed);
+                let bytes = match self.config.point_format {
+                    PointFormat::Compressed | PointFormat::Uncompressed => &encoded[..],
+                    PointFormat::XOnly => match encoded.split_first() {
+                        Some((0x02, x)) if has_sec1_encoding::<E>() => x,
+                        _ => return Err(ser::Error::custom(error_msg::XOnlyUnsupported)),
+                    },
+                };
+                serialize_tagged::<E, S>(serializer, self.config.curve_tag, POINT_FIELDS, bytes)
+            }
+        }
+
+        impl<E: Curve> Serialize for WithConfig<'_, Scalar<E>> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                let bytes = match self.config.scalar_endianness {
+                    Endianness::Big => self.value.to_be_bytes(),
+                    Endianness::Little => self.value.to_le_bytes(),
+                };
+                serialize_tagged::<E, S>(serializer, self.config.curve_tag, SCALAR_FIELDS, &bytes)
+            }
+        }
+
+        impl<'de, E: Curve> DeserializeSeed<'de> for ConfigSeed<Point<E>> {
+            type Value = Point<E>;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let seed = PointBytes::<E> {
+                    format: self.config.point_format,
+                    _curve: PhantomData,
+                };
+                deserialize_tagged::<E, _, _>(
+                    deserializer,
+                    self.config.curve_tag,
+                    POINT_FIELDS,
+                    seed,
+                )
+            }
+        }
+
+        impl<'de, E: Curve> DeserializeSeed<'de> for ConfigSeed<Scalar<E>> {
+            type Value = Scalar<E>;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let seed = ScalarBytes::<E> {
+                    endianness: self.config.scalar_endianness,
+                    _curve: PhantomData,
+                };
+                deserialize_tagged::<E, _, _>(
+                    deserializer,
+                    self.config.curve_tag,
+                    SCALAR_FIELDS,
+                    seed,
+                )
+            }
+        }
+
+        /// Checks that compressed point is encoded as `0x02 || x` or `0x03 || x`
+        fn has_sec1_encoding<E: Curve>() -> bool {
+            let compressed_len = E::CompressedPointArray::zeroes().as_ref().len();
+            let uncompressed_len = E::UncompressedPointArray::zeroes().as_ref().len();
+            uncompressed_len == 2 * compressed_len - 1
+        }
+
+        fn serialize_tagged<E: Curve, S: serde::Serializer>(
+            serializer: S,
+            curve_tag: bool,
+            fields: &'static [&'static str],
+            bytes: &[u8],
+        ) -> Result<S::Ok, S::Error> {
+            let bytes = BytesRef(bytes);
+            if curve_tag {
+                let mut s = serializer.serialize_struct("WithConfig", 2)?;
+                s.serialize_field(fields[0], &CurveName::<E>::new())?;
+                s.serialize_field(fields[1], &bytes)?;
+                s.end()
+            } else {
+                bytes.serialize(serializer)
+            }
+        }
+
+        fn deserialize_tagged<'de, E: Curve, D: serde::Deserializer<'de>, S>(
+            deserializer: D,
+            curve_tag: bool,
+            fields: &'static [&'static str],
+            seed: S,
+        ) -> Result<S::Value, D::Error>
+        where
+            S: DeserializeSeed<'de>,
+        {
+            if curve_tag {
+                deserializer.deserialize_struct(
+                    "WithConfig",
+                    fields,
+                    TaggedVisitor::<E, S> {
+                        seed,
+                        fields,
+                        _curve: PhantomData,
+                    },
+                )
+            } else {
+                seed.deserialize(deserializer)
+            }
+        }
+
+        struct BytesRef<'a>(&'a [u8]);
+
+        impl Serialize for BytesRef<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                utils::Bytes::serialize_as(&self.0, serializer)
+            }
+        }
+
+        struct PointBytes<E> {
+            format: PointFormat,
+            _curve: PhantomData<E>,
+        }
+
+        impl<'de, E: Curve> DeserializeSeed<'de> for PointBytes<E> {
+            type Value = Point<E>;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let point = match self.format {
+                    PointFormat::Compressed => {
+                        let bytes = utils::deserialize_bytes(
+                            deserializer,
+                            E::CompressedPointArray::zeroes(),
+                        )?;
+                        Point::from_bytes(bytes)
+                    }
+                    PointFormat::Uncompressed => {
+                        let bytes = utils::deserialize_bytes(
+                            deserializer,
+                            E::UncompressedPointArray::zeroes(),
+                        )?;
+                        Point::from_bytes(bytes)
+                    }
+                    PointFormat::XOnly => {
+                        if !has_sec1_encoding::<E>() {
+                            return Err(de::Error::custom(error_msg::XOnlyUnsupported));
+                        }
+                        let mut bytes = E::CompressedPointArray::zeroes();
+                        bytes.as_mut()[0] = 0x02;
+                        let XOnly(bytes) = utils::deserialize_bytes(deserializer, XOnly(bytes))?;
+                        Point::from_bytes(bytes)
+                    }
+                };
+                point.map_err(|_| de::Error::custom(error_msg::InvalidPoint::traced::<E>()))
+            }
+        }
+
+        /// Exposes compressed point without its first byte
+        struct XOnly<T>(T);
+
+        impl<T: AsMut<[u8]>> AsMut<[u8]> for XOnly<T> {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0.as_mut()[1..]
+            }
+        }
+
+        struct ScalarBytes<E> {
+            endianness: Endianness,
+            _curve: PhantomData<E>,
+        }
+
+        impl<'de, E: Curve> DeserializeSeed<'de> for ScalarBytes<E> {
+            type Value = Scalar<E>;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let bytes = utils::deserialize_bytes(deserializer, E::ScalarArray::zeroes())?;
+                match self.endianness {
+                    Endianness::Big => Scalar::from_be_bytes(bytes),
+                    Endianness::Little => Scalar::from_le_bytes(bytes),
+                }
+                .map_err(|_| de::Error::custom(error_msg::InvalidScalar::traced::<E>()))
+            }
+        }
+
+        struct TaggedVisitor<E, S> {
+            seed: S,
+            fields: &'static [&'static str],
+            _curve: PhantomData<E>,
+        }
+
+        impl<'de, E: Curve, S: DeserializeSeed<'de>> de::Visitor<'de> for TaggedVisitor<E, S> {
+            type Value = S::Value;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "struct with fields {:?}", self.fields)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                seq.next_element::<CurveName<E>>()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let fields = self.fields;
+                seq.next_element_seed(self.seed)?
+                    .ok_or_else(|| de::Error::invalid_length(1, &ExpectedFields(fields)))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let Self { seed, fields, .. } = self;
+                let mut seed = Some(seed);
+                let mut curve = false;
+                let mut value = None;
+                while let Some(is_value) = map.next_key_seed(FieldSeed(fields))? {
+                    if !is_value {
+                        if curve {
+                            return Err(de::Error::duplicate_field(fields[0]));
+                        }
+                        map.next_value::<CurveName<E>>()?;
+                        curve = true;
+                    } else {
+                        let seed = seed
+                            .take()
+                            .ok_or_else(|| de::Error::duplicate_field(fields[1]))?;
+                        value = Some(map.next_value_seed(seed)?);
+                    }
+                }
+                if !curve {
+                    return Err(de::Error::missing_field(fields[0]));
+                }
+                value.ok_or_else(|| de::Error::missing_field(fields[1]))
+            }
+        }
+
+        struct ExpectedFields(&'static [&'static str]);
+
+        impl de::Expected for ExpectedFields {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "struct with fields {:?}", self.0)
+            }
+        }
+
+        /// Deserializes field name, returns `false` for curve tag and `true` for the value
+        struct FieldSeed(&'static [&'static str]);
+
+        impl<'de> DeserializeSeed<'de> for FieldSeed {
+            type Value = bool;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<bool, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                deserializer.deserialize_identifier(self)
+            }
+        }
+
+        impl<'de> de::Visitor<'de> for FieldSeed {
+            type Value = bool;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "one of fields {:?}", self.0)
+            }
+
+            fn visit_u64<Err: de::Error>(self, v: u64) -> Result<bool, Err> {
+                match v {
+                    0 => Ok(false),
+                    1 => Ok(true),
+                    _ => Err(Err::invalid_value(de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_str<Err: de::Error>(self, v: &str) -> Result<bool, Err> {
+                if v == self.0[0] {
+                    Ok(false)
+                } else if v == self.0[1] {
+                    Ok(true)
+                } else {
+                    Err(Err::unknown_field(v, self.0))
+                }
+            }
+
+            fn visit_bytes<Err: de::Error>(self, v: &[u8]) -> Result<bool, Err> {
+                match core::str::from_utf8(v) {
+                    Ok(v) => self.visit_str(v),
+                    Err(_) => Err(Err::invalid_value(de::Unexpected::Bytes(v), &self)),
+                }
+            }
+        }
+    }
+
     mod models {
         use core::convert::TryFrom;
 
@@ -538,7 +1015,7 @@ mod optional {
         impl<E: Curve> TryFrom<PointUncompressed<E>> for Point<E> {
             type Error = InvalidPoint;
             fn try_from(value: PointUncompressed<E>) -> Result<Self, Self::Error> {
-                Point::from_bytes(value.point).or(Err(InvalidPoint))
+                Point::from_bytes(value.point).map_err(|_| InvalidPoint::traced::<E>())
             }
         }
 
@@ -557,7 +1034,7 @@ mod optional {
         impl<E: Curve> TryFrom<PointCompact<E>> for Point<E> {
             type Error = InvalidPoint;
             fn try_from(value: PointCompact<E>) -> Result<Self, Self::Error> {
-                Point::from_bytes(value.0).or(Err(InvalidPoint))
+                Point::from_bytes(value.0).map_err(|_| InvalidPoint::traced::<E>())
             }
         }
 
@@ -581,7 +1058,7 @@ mod optional {
         impl<E: Curve> TryFrom<ScalarUncompressed<E>> for Scalar<E> {
             type Error = InvalidScalar;
             fn try_from(value: ScalarUncompressed<E>) -> Result<Self, Self::Error> {
-                Scalar::from_be_bytes(value.scalar).or(Err(InvalidScalar))
+                Scalar::from_be_bytes(value.scalar).map_err(|_| InvalidScalar::traced::<E>())
             }
         }
 
@@ -598,7 +1075,7 @@ mod optional {
         impl<E: Curve> TryFrom<ScalarCompact<E>> for Scalar<E> {
             type Error = InvalidScalar;
             fn try_from(value: ScalarCompact<E>) -> Result<Self, Self::Error> {
-                Scalar::from_be_bytes(&value.0).or(Err(InvalidScalar))
+                Scalar::from_be_bytes(&value.0).map_err(|_| InvalidScalar::traced::<E>())
             }
         }
     }
@@ -655,70 +1132,80 @@ mod optional {
             where
                 D: serde::Deserializer<'de>,
             {
-                pub struct BytesVisitor<T>(T);
-                impl<'de, T: AsMut<[u8]>> Visitor<'de> for BytesVisitor<T> {
-                    type Value = T;
-                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                        write!(f, "bytes")
-                    }
-                    fn visit_str<E>(mut self, v: &str) -> Result<Self::Value, E>
-                    where
-                        E: serde::de::Error,
-                    {
-                        hex::decode_to_slice(v, self.0.as_mut()).map_err(E::custom)?;
-                        Ok(self.0)
-                    }
-                    fn visit_bytes<E>(mut self, v: &[u8]) -> Result<Self::Value, E>
-                    where
-                        E: serde::de::Error,
-                    {
-                        let expected_len = self.0.as_mut().len();
-                        if v.len() != expected_len {
-                            return Err(E::invalid_length(
-                                v.len(),
-                                &super::error_msg::ExpectedLen(expected_len),
-                            ));
-                        }
-                        self.0.as_mut().copy_from_slice(v);
-                        Ok(self.0)
-                    }
-                    fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
-                    where
-                        A: serde::de::SeqAccess<'de>,
-                    {
-                        let expected_len = self.0.as_mut().len();
-                        let bytes = self.0.as_mut().iter_mut().enumerate();
-
-                        for (i, byte_i) in bytes {
-                            let byte_parsed = seq.next_element()?.ok_or_else(|| {
-                                <A::Error as de::Error>::invalid_length(
-                                    i,
-                                    &super::error_msg::ExpectedLen(expected_len),
-                                )
-                            })?;
-                            *byte_i = byte_parsed;
-                        }
+                deserialize_bytes(deserializer, T::zeroes())
+            }
+        }
 
-                        let mut unparsed_bytes = 0;
-                        while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {
-                            unparsed_bytes += 1
-                        }
+        /// Deserializes bytes into `buffer`, requires length of the bytes to match length of the buffer
+        pub fn deserialize_bytes<'de, D, T>(deserializer: D, buffer: T) -> Result<T, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+            T: AsMut<[u8]>,
+        {
+            let visitor = BytesVisitor(buffer);
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(visitor)
+            } else {
+                deserializer.deserialize_bytes(visitor)
+            }
+        }
 
-                        if unparsed_bytes > 0 {
-                            Err(<A::Error as de::Error>::invalid_length(
-                                expected_len + unparsed_bytes,
-                                &super::error_msg::ExpectedLen(expected_len),
-                            ))
-                        } else {
-                            Ok(self.0)
-                        }
-                    }
+        struct BytesVisitor<T>(T);
+        impl<'de, T: AsMut<[u8]>> Visitor<'de> for BytesVisitor<T> {
+            type Value = T;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "bytes")
+            }
+            fn visit_str<E>(mut self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                hex::decode_to_slice(v, self.0.as_mut()).map_err(E::custom)?;
+                Ok(self.0)
+            }
+            fn visit_bytes<E>(mut self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                let expected_len = self.0.as_mut().len();
+                if v.len() != expected_len {
+                    return Err(E::invalid_length(
+                        v.len(),
+                        &super::error_msg::ExpectedLen(expected_len),
+                    ));
+                }
+                self.0.as_mut().copy_from_slice(v);
+                Ok(self.0)
+            }
+            fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let expected_len = self.0.as_mut().len();
+                let bytes = self.0.as_mut().iter_mut().enumerate();
+
+                for (i, byte_i) in bytes {
+                    let byte_parsed = seq.next_element()?.ok_or_else(|| {
+                        <A::Error as de::Error>::invalid_length(
+                            i,
+                            &super::error_msg::ExpectedLen(expected_len),
+                        )
+                    })?;
+                    *byte_i = byte_parsed;
                 }
-                let visitor = BytesVisitor(T::zeroes());
-                if deserializer.is_human_readable() {
-                    deserializer.deserialize_str(visitor)
+
+                let mut unparsed_bytes = 0;
+                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {
+                    unparsed_bytes += 1
+                }
+
+                if unparsed_bytes > 0 {
+                    Err(<A::Error as de::Error>::invalid_length(
+                        expected_len + unparsed_bytes,
+                        &super::error_msg::ExpectedLen(expected_len),
+                    ))
                 } else {
-                    deserializer.deserialize_bytes(visitor)
+                    Ok(self.0)
                 }
             }
         }
@@ -754,6 +1241,18 @@ mod optional {
         }
 
         pub struct InvalidPoint;
+        impl InvalidPoint {
+            /// Constructs the error, emits `tracing` event if `tracing` feature is enabled
+            #[cfg_attr(not(feature = "tracing"), allow(clippy::extra_unused_type_parameters))]
+            pub fn traced<E: crate::Curve>() -> Self {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    curve = E::CURVE_NAME,
+                    "deserialization failed: invalid point"
+                );
+                Self
+            }
+        }
         impl fmt::Display for InvalidPoint {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "invalid point")
@@ -761,12 +1260,34 @@ mod optional {
         }
 
         pub struct InvalidScalar;
+        impl InvalidScalar {
+            /// Constructs the error, emits `tracing` event if `tracing` feature is enabled
+            #[cfg_attr(not(feature = "tracing"), allow(clippy::extra_unused_type_parameters))]
+            pub fn traced<E: crate::Curve>() -> Self {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    curve = E::CURVE_NAME,
+                    "deserialization failed: invalid scalar"
+                );
+                Self
+            }
+        }
         impl fmt::Display for InvalidScalar {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "invalid scalar")
             }
         }
 
+        pub struct XOnlyUnsupported;
+        impl fmt::Display for XOnlyUnsupported {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "x-only encoding requires SEC1 curve and point with even y coordinate"
+                )
+            }
+        }
+
         pub struct MalformedHex(pub core::str::Utf8Error);
         impl fmt::Display for MalformedHex {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -0,0 +1,224 @@
+//! Public keys in X.509 `SubjectPublicKeyInfo` format
+//!
+//! Parses DER-encoded [`SubjectPublicKeyInfo`](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.7)
+//! structures, either standalone or embedded into X.509 certificate, into [`Point<E>`](Point).
+//! Curve can be known in advance ([`decode_public_key`]) or detected from the algorithm identifier
+//! ([`AnyPublicKey`]).
+//!
+//! Certificate signatures are not verified, and none of certificate fields besides public key are
+//! validated: it's up to the caller to establish trust in the certificate.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{errors::InvalidPublicKeyInfo, spki::AnyPublicKey};
+//!
+//! fn curve_of_certificate(cert_der: &[u8]) -> Result<&'static str, InvalidPublicKeyInfo> {
+//!     match AnyPublicKey::from_certificate(cert_der)? {
+//!         AnyPublicKey::Secp256r1(public_key) => {
+//!             // use public key on secp256r1 curve
+//!             # let _ = public_key;
+//!             Ok("secp256r1")
+//!         }
+//!         public_key => Ok(public_key.curve_name()),
+//!     }
+//! }
+//! ```
+
+use spki::der::{self, asn1::AnyRef, Decode, Reader, SliceReader};
+use spki::{ObjectIdentifier, SubjectPublicKeyInfoRef};
+
+use crate::{
+    errors::{InvalidPublicKeyInfo, InvalidPublicKeyInfoReason},
+    Curve, NonZero, Point,
+};
+
+/// `id-ecPublicKey` algorithm identifier defined in [RFC 5480](https://www.rfc-editor.org/rfc/rfc5480)
+pub const ID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+
+/// Curve that has an algorithm identifier in `SubjectPublicKeyInfo`
+pub trait CurveOid: Curve {
+    /// Algorithm identifier, e.g. `id-ecPublicKey`
+    const ALGORITHM: ObjectIdentifier;
+    /// Algorithm parameters, if any
+    ///
+    /// For `id-ecPublicKey` algorithm, it's a named curve identifier
+    const PARAMETERS: Option<ObjectIdentifier>;
+}
+
+#[cfg(feature = "curve-secp256k1")]
+impl CurveOid for crate::curves::Secp256k1 {
+    const ALGORITHM: ObjectIdentifier = ID_EC_PUBLIC_KEY;
+    const PARAMETERS: Option<ObjectIdentifier> = Some(ObjectIdentifier::new_unwrap("1.3.132.0.10"));
+}
+
+#[cfg(feature = "curve-secp256r1")]
+impl CurveOid for crate::curves::Secp256r1 {
+    const ALGORITHM: ObjectIdentifier = ID_EC_PUBLIC_KEY;
+    const PARAMETERS: Option<ObjectIdentifier> =
+        Some(ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7"));
+}
+
+/// Ed25519 algorithm identifier defined in [RFC 8410](https://www.rfc-editor.org/rfc/rfc8410)
+#[cfg(feature = "curve-ed25519")]
+impl CurveOid for crate::curves::Ed25519 {
+    const ALGORITHM: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+    const PARAMETERS: Option<ObjectIdentifier> = None;
+}
+
+/// Decodes DER-encoded `SubjectPublicKeyInfo` into a public key on curve `E`
+///
+/// Returns error if structure is malformed, or its algorithm identifier doesn't correspond to
+/// curve `E`, or public key is not a valid non-zero point.
+pub fn decode_public_key<E: CurveOid>(
+    spki_der: &[u8],
+) -> Result<NonZero<Point<E>>, InvalidPublicKeyInfo> {
+    let spki = SubjectPublicKeyInfoRef::from_der(spki_der).map_err(InvalidPublicKeyInfo::der)?;
+    public_key_from_spki(&spki)
+}
+
+/// Extracts public key on curve `E` from DER-encoded X.509 certificate
+///
+/// Certificate signature is not verified.
+pub fn public_key_from_certificate<E: CurveOid>(
+    cert_der: &[u8],
+) -> Result<NonZero<Point<E>>, InvalidPublicKeyInfo> {
+    let spki = certificate_spki(cert_der)?;
+    public_key_from_spki(&spki)
+}
+
+/// Public key on one of supported curves
+///
+/// Curve is detected from the algorithm identifier. Only curves enabled via `curve-{name}`
+/// features are recognized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AnyPublicKey {
+    /// Public key on secp256k1 curve
+    #[cfg(feature = "curve-secp256k1")]
+    Secp256k1(NonZero<Point<crate::curves::Secp256k1>>),
+    /// Public key on secp256r1 curve
+    #[cfg(feature = "curve-secp256r1")]
+    Secp256r1(NonZero<Point<crate::curves::Secp256r1>>),
+    /// Public key on ed25519 curve
+    #[cfg(feature = "curve-ed25519")]
+    Ed25519(NonZero<Point<crate::curves::Ed25519>>),
+}
+
+impl AnyPublicKey {
+    /// Decodes DER-encoded `SubjectPublicKeyInfo`, detecting the curve
+    pub fn decode(spki_der: &[u8]) -> Result<Self, InvalidPublicKeyInfo> {
+        let spki =
+            SubjectPublicKeyInfoRef::from_der(spki_der).map_err(InvalidPublicKeyInfo::der)?;
+        Self::from_spki(&spki)
+    }
+
+    /// Extracts public key from DER-encoded X.509 certificate, detecting the curve
+    ///
+    /// Certificate signature is not verified.
+    pub fn from_certificate(cert_der: &[u8]) -> Result<Self, InvalidPublicKeyInfo> {
+        let spki = certificate_spki(cert_der)?;
+        Self::from_spki(&spki)
+    }
+
+    fn from_spki(spki: &SubjectPublicKeyInfoRef) -> Result<Self, InvalidPublicKeyInfo> {
+        #[cfg(feature = "curve-secp256k1")]
+        if algorithm_matches::<crate::curves::Secp256k1>(spki) {
+            return public_key_from_spki(spki).map(Self::Secp256k1);
+        }
+        #[cfg(feature = "curve-secp256r1")]
+        if algorithm_matches::<crate::curves::Secp256r1>(spki) {
+            return public_key_from_spki(spki).map(Self::Secp256r1);
+        }
+        #[cfg(feature = "curve-ed25519")]
+        if algorithm_matches::<crate::curves::Ed25519>(spki) {
+            return public_key_from_spki(spki).map(Self::Ed25519);
+        }
+        let _ = spki;
+        Err(InvalidPublicKeyInfoReason::UnsupportedAlgorithm.into())
+    }
+
+    /// Returns name of the curve, see [`Curve::CURVE_NAME`]
+    pub fn curve_name(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "curve-secp256k1")]
+            Self::Secp256k1(_) => crate::curves::Secp256k1::CURVE_NAME,
+            #[cfg(feature = "curve-secp256r1")]
+            Self::Secp256r1(_) => crate::curves::Secp256r1::CURVE_NAME,
+            #[cfg(feature = "curve-ed25519")]
+            Self::Ed25519(_) => crate::curves::Ed25519::CURVE_NAME,
+        }
+    }
+}
+
+fn algorithm_matches<E: CurveOid>(spki: &SubjectPublicKeyInfoRef) -> bool {
+    if spki.algorithm.oid != E::ALGORITHM {
+        return false;
+    }
+    match (E::PARAMETERS, spki.algorithm.parameters) {
+        (Some(expected), Some(actual)) => actual.decode_as::<ObjectIdentifier>() == Ok(expected),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn public_key_from_spki<E: CurveOid>(
+    spki: &SubjectPublicKeyInfoRef,
+) -> Result<NonZero<Point<E>>, InvalidPublicKeyInfo> {
+    if !algorithm_matches::<E>(spki) {
+        return Err(InvalidPublicKeyInfoReason::UnsupportedAlgorithm.into());
+    }
+    let bytes = spki
+        .subject_public_key
+        .as_bytes()
+        .ok_or(InvalidPublicKeyInfoReason::InvalidPoint)?;
+    Point::from_bytes(bytes)
+        .ok()
+        .and_then(NonZero::from_point)
+        .ok_or_else(|| InvalidPublicKeyInfoReason::InvalidPoint.into())
+}
+
+/// Locates `SubjectPublicKeyInfo` within the certificate
+///
+/// ```text
+/// Certificate ::= SEQUENCE {
+///     tbsCertificate TBSCertificate, ...
+/// }
+/// TBSCertificate ::= SEQUENCE {
+///     version [0] EXPLICIT Version DEFAULT v1,
+///     serialNumber, signature, issuer, validity, subject,
+///     subjectPublicKeyInfo SubjectPublicKeyInfo, ...
+/// }
+/// ```
+fn certificate_spki(cert_der: &[u8]) -> Result<SubjectPublicKeyInfoRef<'_>, InvalidPublicKeyInfo> {
+    let mut reader = SliceReader::new(cert_der).map_err(InvalidPublicKeyInfo::der)?;
+    let spki_der = reader
+        .sequence(|cert| {
+            let spki_der = cert.sequence(|tbs| {
+                let version_tag = der::Tag::ContextSpecific {
+                    constructed: true,
+                    number: der::TagNumber::N0,
+                };
+                if tbs.peek_tag()? == version_tag {
+                    AnyRef::decode(tbs)?;
+                }
+                // serialNumber, signature, issuer, validity, subject
+                for _ in 0..5 {
+                    AnyRef::decode(tbs)?;
+                }
+                let spki_der = tbs.tlv_bytes()?;
+                // issuerUniqueID, subjectUniqueID, extensions
+                while !tbs.is_finished() {
+                    AnyRef::decode(tbs)?;
+                }
+                Ok(spki_der)
+            })?;
+            // signatureAlgorithm, signatureValue
+            while !cert.is_finished() {
+                AnyRef::decode(cert)?;
+            }
+            Ok(spki_der)
+        })
+        .and_then(|spki_der| reader.finish(spki_der))
+        .map_err(InvalidPublicKeyInfo::der)?;
+    SubjectPublicKeyInfoRef::from_der(spki_der).map_err(InvalidPublicKeyInfo::der)
+}
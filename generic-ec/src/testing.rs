@@ -0,0 +1,434 @@
+//! Conformance test suite for curve implementations
+//!
+//! If you implemented [`Curve` trait](crate::Curve) for your own curve, you may want to make sure
+//! that implementation is sound. This module provides a battery of checks that every curve must pass:
+//! arithmetic laws, encoding round-trips, constant-time equality behavior, and subgroup checks.
+//!
+//! Checks panic with a descriptive message if implementation misbehaves, so they're intended to be
+//! called from tests:
+//!
+//! ```rust
+//! use generic_ec::curves::Secp256k1;
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! generic_ec::testing::test_curve::<Secp256k1>(&mut rng);
+//! ```
+//!
+//! Keep in mind that passing the checks doesn't prove that implementation is correct/secure, it only
+//! says that we couldn't find any problems with it.
+//!
+//! ## Deterministic randomness
+//! [`DeterministicRng`] and [`Scalar::random_seeded`] produce the same output for the same domain
+//! and seed, on every platform and in every implementation following the
+//! [derivation](DeterministicRng::new). Use them to make protocol tests reproducible, or to generate
+//! cross-implementation test vectors:
+//!
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1, testing::DeterministicRng};
+//!
+//! let mut rng = DeterministicRng::new(b"MYAPP-test-vectors", b"vector #1");
+//! let point = Point::<Secp256k1>::generator() * Scalar::random(&mut rng);
+//!
+//! let mut same_rng = DeterministicRng::new(b"MYAPP-test-vectors", b"vector #1");
+//! assert_eq!(point, Point::generator() * Scalar::random(&mut same_rng));
+//! ```
+//!
+//! Output is fully determined by the seed, so it must never be used outside of tests. That's the
+//! reason why these helpers are only available with `testing` feature.
+
+use rand_chacha::rand_core::SeedableRng;
+use rand_core::{CryptoRng, RngCore};
+use sha2::Digest;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::{
+    as_raw::AsRaw,
+    core::{ByteArray, OnCurve, SmallFactor},
+    multiscalar::{MultiscalarMul, Naive},
+    Curve, NonZero, Point, Scalar,
+};
+
+/// Runs all the checks against curve `E`
+///
+/// Equivalent to calling [`test_scalar_arithmetic`], [`test_point_arithmetic`],
+/// [`test_encoding`], [`test_constant_time_eq`], and [`test_subgroup`] one after another.
+///
+/// ## Panics
+/// Panics if curve implementation doesn't pass any of the checks
+pub fn test_curve<E: Curve>(rng: &mut impl RngCore) {
+    test_scalar_arithmetic::<E>(rng);
+    test_point_arithmetic::<E>(rng);
+    test_encoding::<E>(rng);
+    test_constant_time_eq::<E>(rng);
+    test_subgroup::<E>(rng);
+}
+
+/// Checks that scalars form a field
+///
+/// ## Panics
+/// Panics if any of the field laws doesn't hold
+pub fn test_scalar_arithmetic<E: Curve>(rng: &mut impl RngCore) {
+    let zero = Scalar::<E>::zero();
+    let one = Scalar::<E>::one();
+    assert_ne!(zero, one, "zero must not be equal to one");
+
+    let [a, b, c] = [(); 3].map(|_| Scalar::<E>::random(rng));
+
+    assert_eq!(a + zero, a, "zero is not an additive identity");
+    assert_eq!(a * one, a, "one is not a multiplicative identity");
+    assert_eq!(a * zero, zero, "multiplication at zero must produce zero");
+
+    assert_eq!(a + b, b + a, "addition is not commutative");
+    assert_eq!((a + b) + c, a + (b + c), "addition is not associative");
+    assert_eq!(a * b, b * a, "multiplication is not commutative");
+    assert_eq!(
+        (a * b) * c,
+        a * (b * c),
+        "multiplication is not associative"
+    );
+    assert_eq!(
+        a * (b + c),
+        a * b + a * c,
+        "multiplication is not distributive"
+    );
+
+    assert_eq!(a + (-a), zero, "negation doesn't produce additive inverse");
+    assert_eq!(a - b, a + (-b), "subtraction doesn't agree with negation");
+    assert_eq!(-zero, zero, "negation of zero must be zero");
+
+    let a_inv = match a.invert() {
+        Some(inv) => inv,
+        #[allow(clippy::panic)]
+        None => panic!("non-zero scalar must be invertible"),
+    };
+    assert_eq!(
+        a * a_inv,
+        one,
+        "invert doesn't produce multiplicative inverse"
+    );
+    assert!(zero.invert().is_none(), "zero must not be invertible");
+
+    assert_eq!(
+        Scalar::<E>::from(2u8) * Scalar::from(3u8),
+        Scalar::from(6u8),
+        "conversion from integers doesn't agree with multiplication"
+    );
+    assert_eq!(
+        Scalar::<E>::from(-1i8),
+        -one,
+        "conversion from negative integers doesn't agree with negation"
+    );
+
+    // (q - 1) / 2
+    let half = match Scalar::<E>::from(2u8).invert() {
+        Some(two_inv) => -two_inv,
+        #[allow(clippy::panic)]
+        None => panic!("two must be invertible"),
+    };
+    assert!(bool::from(zero.is_low()), "zero must be low");
+    assert!(bool::from(half.is_low()), "(q - 1) / 2 must be low");
+    assert!(
+        bool::from((half + one).is_high()),
+        "(q + 1) / 2 must be high"
+    );
+    assert_eq!(
+        bool::from(a.is_high()),
+        a > half,
+        "is_high doesn't agree with integer comparison"
+    );
+    assert_ne!(
+        bool::from(a.is_high()),
+        bool::from((-a).is_high()),
+        "exactly one of non-zero scalar and its negation must be high"
+    );
+}
+
+/// Checks that points form a group and agree with scalar arithmetic
+///
+/// ## Panics
+/// Panics if any of the group laws doesn't hold
+pub fn test_point_arithmetic<E: Curve>(rng: &mut impl RngCore) {
+    let g = Point::<E>::generator();
+    let zero = Point::<E>::zero();
+    assert!(zero.is_zero(), "identity point must be zero");
+    assert!(!g.to_point().is_zero(), "generator must not be zero");
+
+    let [a, b] = [(); 2].map(|_| Scalar::<E>::random(rng));
+    let [p, q, r] = [(); 3].map(|_| g * Scalar::<E>::random(rng));
+
+    assert_eq!(p + zero, p, "identity point is not an additive identity");
+    assert_eq!(p + q, q + p, "addition is not commutative");
+    assert_eq!((p + q) + r, p + (q + r), "addition is not associative");
+    assert_eq!(p + (-p), zero, "negation doesn't produce additive inverse");
+    assert_eq!(p - q, p + (-q), "subtraction doesn't agree with negation");
+    assert_eq!(p.double(), p + p, "doubling doesn't agree with addition");
+
+    assert_eq!(
+        g * a,
+        g.to_point() * a,
+        "generator multiplication doesn't agree with point multiplication"
+    );
+    assert_eq!(
+        g * (a + b),
+        g * a + g * b,
+        "scalar addition doesn't agree with point addition"
+    );
+    assert_eq!(
+        (p * a) * b,
+        p * (a * b),
+        "scalar multiplication doesn't agree with point multiplication"
+    );
+    assert_eq!(
+        p * Scalar::one(),
+        p,
+        "multiplication at one must be identity"
+    );
+    assert_eq!(
+        p * Scalar::zero(),
+        zero,
+        "multiplication at zero must be zero"
+    );
+    assert_eq!(
+        zero * a,
+        zero,
+        "identity point multiplied at scalar must be zero"
+    );
+    assert_eq!(
+        p * Scalar::from(-1i8),
+        -p,
+        "multiplication at minus one must be negation"
+    );
+
+    let scalar_points = [(a, p), (b, q)];
+    assert_eq!(
+        Scalar::multiscalar_mul(scalar_points),
+        Naive::multiscalar_mul(scalar_points),
+        "multiscalar multiplication doesn't agree with naive algorithm"
+    );
+}
+
+/// Checks that points and scalars survive encoding round-trip
+///
+/// ## Panics
+/// Panics if decoding doesn't produce the original value, or if encodings are inconsistent
+pub fn test_encoding<E: Curve>(rng: &mut impl RngCore) {
+    let random_scalar = Scalar::<E>::random(rng);
+    for s in [Scalar::zero(), Scalar::one(), -Scalar::one(), random_scalar] {
+        let be = s.to_be_bytes();
+        let le = s.to_le_bytes();
+        assert_eq!(
+            be.len(),
+            Scalar::<E>::serialized_len(),
+            "encoded scalar length doesn't match `serialized_len`"
+        );
+
+        let mut be_reversed = be.clone();
+        be_reversed.as_mut().reverse();
+        assert_eq!(
+            be_reversed, le,
+            "be and le encodings are not reverse of each other"
+        );
+
+        assert_eq!(
+            Scalar::<E>::from_be_bytes(&be).ok(),
+            Some(s),
+            "scalar be encoding doesn't round-trip"
+        );
+        assert_eq!(
+            Scalar::<E>::from_le_bytes(&le).ok(),
+            Some(s),
+            "scalar le encoding doesn't round-trip"
+        );
+        assert_eq!(
+            Scalar::<E>::from_be_bytes_mod_order(&be),
+            s,
+            "scalar be encoding doesn't round-trip via reduction"
+        );
+        assert_eq!(
+            Scalar::<E>::from_le_bytes_mod_order(&le),
+            s,
+            "scalar le encoding doesn't round-trip via reduction"
+        );
+    }
+
+    let mut out_of_range = E::ScalarArray::zeroes();
+    out_of_range.as_mut().fill(0xFF);
+    assert!(
+        Scalar::<E>::from_be_bytes(&out_of_range).is_err(),
+        "scalar larger than group order must be rejected"
+    );
+
+    let random_point = Point::generator() * Scalar::<E>::random(rng);
+    for p in [Point::zero(), Point::generator().to_point(), random_point] {
+        let compressed = p.to_bytes(true);
+        let uncompressed = p.to_bytes(false);
+        assert!(
+            compressed.len() <= uncompressed.len(),
+            "compressed encoding must not be longer than uncompressed"
+        );
+        assert_eq!(
+            Point::<E>::from_bytes(&compressed).ok(),
+            Some(p),
+            "compressed point encoding doesn't round-trip"
+        );
+        assert_eq!(
+            Point::<E>::from_bytes(&uncompressed).ok(),
+            Some(p),
+            "uncompressed point encoding doesn't round-trip"
+        );
+    }
+}
+
+/// Checks that constant-time primitives agree with their variable-time counterparts
+///
+/// ## Panics
+/// Panics if constant-time equality or selection misbehaves
+pub fn test_constant_time_eq<E: Curve>(rng: &mut impl RngCore) {
+    let [a, b] = [(); 2].map(|_| Scalar::<E>::random(rng));
+    assert!(
+        bool::from(a.ct_eq(&a)),
+        "scalar ct_eq must return true on equal"
+    );
+    assert_eq!(
+        bool::from(a.ct_eq(&b)),
+        a == b,
+        "scalar ct_eq doesn't agree with Eq"
+    );
+    assert_eq!(
+        Scalar::conditional_select(&a, &b, Choice::from(0)),
+        a,
+        "scalar conditional_select must choose `a` on 0"
+    );
+    assert_eq!(
+        Scalar::conditional_select(&a, &b, Choice::from(1)),
+        b,
+        "scalar conditional_select must choose `b` on 1"
+    );
+
+    let p = Point::generator() * a;
+    let q = Point::generator() * b;
+    assert!(
+        bool::from(p.ct_eq(&p)),
+        "point ct_eq must return true on equal"
+    );
+    assert_eq!(
+        bool::from(p.ct_eq(&q)),
+        p == q,
+        "point ct_eq doesn't agree with Eq"
+    );
+    assert!(
+        bool::from(p.ct_eq(&(p + Point::zero()))),
+        "point ct_eq must not depend on internal representation"
+    );
+    assert_eq!(
+        Point::conditional_select(&p, &q, Choice::from(0)),
+        p,
+        "point conditional_select must choose `a` on 0"
+    );
+    assert_eq!(
+        Point::conditional_select(&p, &q, Choice::from(1)),
+        q,
+        "point conditional_select must choose `b` on 1"
+    );
+    assert!(
+        bool::from(Point::<E>::zero().ct_is_zero()),
+        "ct_is_zero must return true on identity point"
+    );
+    assert!(
+        !bool::from(p.ct_is_zero()),
+        "ct_is_zero must return false on non-zero point"
+    );
+}
+
+/// Checks that generator and derived points belong to prime order subgroup
+///
+/// ## Panics
+/// Panics if any point fails on-curve or torsion-free checks
+pub fn test_subgroup<E: Curve>(rng: &mut impl RngCore) {
+    let g = Point::<E>::generator().to_point();
+    let random_point = g * Scalar::<E>::random(rng);
+    for p in [Point::zero(), g, random_point, random_point.double()] {
+        assert!(
+            bool::from(p.as_raw().is_on_curve()),
+            "point must be on curve"
+        );
+        assert!(
+            bool::from(p.as_raw().is_torsion_free()),
+            "point must be torsion free"
+        );
+    }
+
+    // Generator has prime order `q`, so multiplying at `q - 1` must produce `-G`
+    let minus_one = -Scalar::<E>::one();
+    assert_eq!(
+        g * minus_one + g,
+        Point::zero(),
+        "generator must have prime order"
+    );
+
+    let nonzero_scalar = NonZero::<Scalar<E>>::random(rng);
+    let nonzero_point = Point::generator() * nonzero_scalar;
+    assert!(
+        !nonzero_point.is_zero(),
+        "non-zero scalar multiplied at generator must be non-zero"
+    );
+}
+
+/// Deterministic randomness source for reproducible tests
+///
+/// Outputs ChaCha20 keystream keyed by domain and seed. See [module-level docs](self#deterministic-randomness).
+#[derive(Clone, Debug)]
+pub struct DeterministicRng(rand_chacha::ChaCha20Rng);
+
+impl DeterministicRng {
+    /// Constructs randomness source from domain and seed
+    ///
+    /// ChaCha20 key is derived as $\text{SHA256}(\text{len}(\text{domain}) \| \text{domain} \|
+    /// \text{seed})$ where $\text{len}(\text{domain})$ is 8 bytes big-endian, nonce and stream
+    /// position are zero. Output is consumed in the same way as by `rand_chacha::ChaCha20Rng`.
+    pub fn new(domain: &[u8], seed: &[u8]) -> Self {
+        let key = sha2::Sha256::new()
+            .chain_update((domain.len() as u64).to_be_bytes())
+            .chain_update(domain)
+            .chain_update(seed)
+            .finalize();
+        Self(rand_chacha::ChaCha20Rng::from_seed(key.into()))
+    }
+}
+
+impl RngCore for DeterministicRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+/// Output is unpredictable for anyone who doesn't know the seed, so it can be used with
+/// protocols requiring cryptographic randomness in tests
+impl CryptoRng for DeterministicRng {}
+
+impl<E: Curve> Scalar<E> {
+    /// Generates non-zero scalar deterministically derived from domain and seed
+    ///
+    /// Same as [`Scalar::random`] with [`DeterministicRng::new(domain, seed)`](DeterministicRng::new).
+    /// Intended for tests only, see [module-level docs](self#deterministic-randomness).
+    ///
+    /// ```rust
+    /// use generic_ec::{Scalar, curves::Secp256k1};
+    ///
+    /// let a = Scalar::<Secp256k1>::random_seeded(b"MYAPP-test", b"a");
+    /// assert_eq!(a, Scalar::random_seeded(b"MYAPP-test", b"a"));
+    /// assert_ne!(a, Scalar::random_seeded(b"MYAPP-test", b"b"));
+    /// ```
+    pub fn random_seeded(domain: &[u8], seed: &[u8]) -> Self {
+        Self::random(&mut DeterministicRng::new(domain, seed))
+    }
+}
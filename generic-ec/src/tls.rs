@@ -0,0 +1,187 @@
+//! TLS 1.3 key shares
+//!
+//! Encodes and decodes `KeyShareEntry` structures defined in
+//! [RFC 8446](https://www.rfc-editor.org/rfc/rfc8446#section-4.2.8) carrying (EC)DHE public
+//! keys in `key_share` extension of `ClientHello` and `ServerHello`:
+//!
+//! ```text
+//! struct {
+//!     NamedGroup group;
+//!     opaque key_exchange<1..2^16-1>;
+//! } KeyShareEntry;
+//! ```
+//!
+//! Public keys are encoded as `UncompressedPointRepresentation`, i.e. SEC1 uncompressed point.
+//! Supported named groups:
+//!
+//! | Curve       | Named group | Code     |
+//! |-------------|-------------|----------|
+//! | `Secp256r1` | `secp256r1` | `0x0017` |
+//!
+//! Other curves of this crate are not defined as TLS 1.3 named groups: secp256k1 was removed
+//! from TLS, and `x25519` is defined over Curve25519 in Montgomery form, which can't be
+//! expressed via [`Ed25519`](crate::curves::Ed25519).
+//!
+//! [`key_exchange`] and [`decode_key_exchange`] work with the opaque `key_exchange` field alone,
+//! which is what custom key exchange implementations (e.g. in rustls crypto providers) operate
+//! on.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{curves::Secp256r1, tls, NonZero, Point, SecretScalar};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let secret_key = NonZero::<SecretScalar<Secp256r1>>::random(&mut rng);
+//! let public_key = Point::generator() * &secret_key;
+//!
+//! let entry = tls::encode_key_share_entry(&public_key);
+//! assert_eq!(entry[..2], [0x00, 0x17]);
+//!
+//! let (parsed, rest) = tls::KeyShareEntry::parse(&entry)?;
+//! assert!(rest.is_empty());
+//! assert_eq!(parsed.public_key::<Secp256r1>()?, public_key);
+//! # Ok::<_, generic_ec::errors::InvalidKeyShare>(())
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    errors::{InvalidKeyShare, InvalidKeyShareReason as Reason},
+    Curve, EncodedPoint, NonZero, Point,
+};
+
+/// Curve that is defined as a TLS 1.3 named group
+pub trait NamedGroup: Curve {
+    /// `NamedGroup` code point
+    const NAMED_GROUP: u16;
+}
+
+#[cfg(feature = "curve-secp256r1")]
+impl NamedGroup for crate::curves::Secp256r1 {
+    const NAMED_GROUP: u16 = 0x0017;
+}
+
+/// Returns `key_exchange` field of a key share: uncompressed public key
+pub fn key_exchange<E: NamedGroup>(public_key: &NonZero<Point<E>>) -> EncodedPoint<E> {
+    public_key.to_bytes(false)
+}
+
+/// Decodes `key_exchange` field of a key share on curve `E`
+///
+/// Only uncompressed points are accepted as required by TLS 1.3. Returns error if point is not
+/// valid or it's an identity point.
+pub fn decode_key_exchange<E: NamedGroup>(
+    key_exchange: &[u8],
+) -> Result<NonZero<Point<E>>, InvalidKeyShare> {
+    if key_exchange.len() != Point::<E>::serialized_len(false) {
+        return Err(Reason::InvalidPoint.into());
+    }
+    Point::from_bytes(key_exchange)
+        .ok()
+        .and_then(NonZero::from_point)
+        .ok_or_else(|| Reason::InvalidPoint.into())
+}
+
+/// Encodes public key as `KeyShareEntry`
+pub fn encode_key_share_entry<E: NamedGroup>(public_key: &NonZero<Point<E>>) -> Vec<u8> {
+    let key_exchange = key_exchange(public_key);
+    // Correctness: uncompressed point of any supported curve is much shorter than 2^16 bytes
+    let len = u16::try_from(key_exchange.len()).unwrap_or(u16::MAX);
+
+    let mut entry = Vec::with_capacity(4 + key_exchange.len());
+    entry.extend_from_slice(&E::NAMED_GROUP.to_be_bytes());
+    entry.extend_from_slice(&len.to_be_bytes());
+    entry.extend_from_slice(&key_exchange);
+    entry
+}
+
+/// Decodes `KeyShareEntry` into public key on curve `E`
+///
+/// `entry` must contain exactly one `KeyShareEntry`. Returns error if entry is malformed, or its
+/// named group doesn't correspond to curve `E`, or public key is invalid.
+pub fn decode_key_share_entry<E: NamedGroup>(
+    entry: &[u8],
+) -> Result<NonZero<Point<E>>, InvalidKeyShare> {
+    let (entry, rest) = KeyShareEntry::parse(entry)?;
+    if !rest.is_empty() {
+        return Err(Reason::Malformed.into());
+    }
+    entry.public_key()
+}
+
+/// Parsed `KeyShareEntry` of any named group
+///
+/// Useful to process `client_shares` list of `ClientHello` which may contain entries of groups
+/// not supported by this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyShareEntry<'a> {
+    /// `NamedGroup` code point
+    pub group: u16,
+    /// Opaque `key_exchange` field
+    pub key_exchange: &'a [u8],
+}
+
+impl<'a> KeyShareEntry<'a> {
+    /// Parses `KeyShareEntry` from the beginning of `bytes`
+    ///
+    /// Returns parsed entry and remaining bytes. Entry itself is not validated beyond its
+    /// framing: `key_exchange` must be non-empty.
+    pub fn parse(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), InvalidKeyShare> {
+        let (group, bytes) = read_u16(bytes)?;
+        let (len, bytes) = read_u16(bytes)?;
+        let len = usize::from(len);
+        if len == 0 || bytes.len() < len {
+            return Err(Reason::Malformed.into());
+        }
+        let (key_exchange, rest) = bytes.split_at(len);
+        Ok((
+            Self {
+                group,
+                key_exchange,
+            },
+            rest,
+        ))
+    }
+
+    /// Parses `client_shares` list: concatenation of `KeyShareEntry` structures
+    ///
+    /// Length prefix of the list must be stripped by the caller. Iterator yields an error and
+    /// stops if the list is malformed.
+    pub fn parse_list(
+        mut bytes: &'a [u8],
+    ) -> impl Iterator<Item = Result<KeyShareEntry<'a>, InvalidKeyShare>> + 'a {
+        core::iter::from_fn(move || {
+            if bytes.is_empty() {
+                return None;
+            }
+            match Self::parse(bytes) {
+                Ok((entry, rest)) => {
+                    bytes = rest;
+                    Some(Ok(entry))
+                }
+                Err(err) => {
+                    bytes = &[];
+                    Some(Err(err))
+                }
+            }
+        })
+    }
+
+    /// Decodes public key on curve `E`
+    ///
+    /// Returns error if entry's named group doesn't correspond to curve `E`, or public key is
+    /// invalid.
+    pub fn public_key<E: NamedGroup>(&self) -> Result<NonZero<Point<E>>, InvalidKeyShare> {
+        if self.group != E::NAMED_GROUP {
+            return Err(Reason::GroupMismatch.into());
+        }
+        decode_key_exchange(self.key_exchange)
+    }
+}
+
+fn read_u16(bytes: &[u8]) -> Result<(u16, &[u8]), InvalidKeyShare> {
+    match bytes {
+        [hi, lo, rest @ ..] => Ok((u16::from_be_bytes([*hi, *lo]), rest)),
+        _ => Err(Reason::Malformed.into()),
+    }
+}
@@ -0,0 +1,77 @@
+//! Encoding of points as uniformly random bytes
+//!
+//! Regular point encoding is easy to tell apart from random bytes: e.g. not every bytestring is a
+//! valid point. Censorship-resistant and steganographic protocols need public keys to look like
+//! random bytes on the wire. Curves implementing [`UniformEncoding`] provide an encoding of
+//! points which is indistinguishable from uniformly random bytes when point is random:
+//! [`Point::to_uniform_bytes`] and [`Point::from_uniform_bytes`].
+//!
+//! Only about half of the points are representable. Protocols typically generate ephemeral key
+//! pairs until the public key is representable. The other half of the points must not be
+//! re-encoded by other means (e.g. retried with different randomness), as it would bias the
+//! output.
+//!
+//! Supported curves:
+//!
+//! | Curve     | Encoding   | Size, bytes |
+//! |-----------|------------|-------------|
+//! | `Ed25519` | Elligator 2 | 32         |
+//!
+//! Elligator 2 is not applicable to short Weierstrass curves with $a = 0$ like secp256k1,
+//! and other curves don't implement uniform encoding yet.
+//!
+//! ## Example
+//! ```rust
+//! use generic_ec::{curves::Ed25519, NonZero, Point, SecretScalar};
+//! # let mut rng = rand::rngs::OsRng;
+//!
+//! let (secret_key, encoded) = loop {
+//!     let secret_key = NonZero::<SecretScalar<Ed25519>>::random(&mut rng);
+//!     let public_key = Point::generator() * &secret_key;
+//!     if let Some(encoded) = public_key.to_uniform_bytes(&mut rng) {
+//!         break (secret_key, encoded);
+//!     }
+//! };
+//!
+//! let public_key = Point::<Ed25519>::from_uniform_bytes(&encoded)?;
+//! assert_eq!(public_key, Point::generator() * &secret_key);
+//! # Ok::<_, generic_ec::errors::InvalidPoint>(())
+//! ```
+
+use rand_core::RngCore;
+
+use crate::{
+    as_raw::{AsRaw, TryFromRaw},
+    core::ByteArray,
+    errors::InvalidPoint,
+    Curve, Point,
+};
+
+#[doc(inline)]
+pub use crate::core::UniformEncoding;
+
+impl<E: Curve + UniformEncoding> Point<E> {
+    /// Encodes the point as uniformly random bytes
+    ///
+    /// Returns `None` if the point is not representable, which happens for about half of the
+    /// points. Encoding is randomized: the same point has several encodings, one of which
+    /// is chosen using `rng`.
+    pub fn to_uniform_bytes<R: RngCore>(&self, rng: &mut R) -> Option<E::UniformBytes> {
+        E::to_uniform_bytes(self.as_raw(), rng)
+    }
+
+    /// Decodes the point from its uniform encoding
+    ///
+    /// Any bytestring of correct length decodes into a point, except for a negligible fraction
+    /// of them. Returns error if `bytes` has incorrect length or doesn't correspond to a point.
+    pub fn from_uniform_bytes(bytes: &[u8]) -> Result<Self, InvalidPoint> {
+        let mut array = E::UniformBytes::zeroes();
+        if array.as_ref().len() != bytes.len() {
+            return Err(InvalidPoint);
+        }
+        array.as_mut().copy_from_slice(bytes);
+        E::from_uniform_bytes(&array)
+            .and_then(Self::try_from_raw)
+            .ok_or(InvalidPoint)
+    }
+}
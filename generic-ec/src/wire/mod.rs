@@ -0,0 +1,14 @@
+//! # Versioned wire formats
+//!
+//! Encodings provided by [`Point::to_bytes`](crate::Point::to_bytes),
+//! [`Scalar::to_be_bytes`](crate::Scalar::to_be_bytes), and [serde](crate::serde) support may
+//! evolve between releases, e.g. decoding may become more permissive. Systems that need
+//! byte-for-byte reproducible encodings (consensus, hashing of commitments, signatures over
+//! encoded values) should pin to a versioned wire format instead.
+//!
+//! Each wire format lives in its own module and is frozen once released: any change of
+//! encoding results in a new module (`v2`, ...), while the old one stays untouched.
+//!
+//! * [`v1`]: compressed points and fixed-size big-endian scalars
+
+pub mod v1;
@@ -0,0 +1,271 @@
+//! # Wire format v1
+//!
+//! * Point is encoded in compressed form: SEC1 compressed encoding for Weierstrass curves
+//!   ($\[\text{0x02} \mid \text{0x03}\] \\| x$, or all zeroes for the identity point),
+//!   and RFC 8032 encoding for Ed25519. Encoding always has exactly
+//!   [`POINT_LEN`](WireV1::POINT_LEN) bytes.
+//! * Scalar is encoded as big-endian integer less than group order, padded with zeroes to
+//!   exactly [`SCALAR_LEN`](WireV1::SCALAR_LEN) bytes.
+//!
+//! Decoding is strict: blobs of any other length, uncompressed points, or non-canonical
+//! scalars are rejected, so every valid value has exactly one encoding.
+//!
+//! The format is fixed by [golden vectors](WireV1::GOLDEN_VECTORS). Applications can
+//! assert at startup that the linked implementation still matches them via
+//! [`check_golden_vectors`].
+//!
+//! ```rust
+//! use generic_ec::{Point, Scalar, curves::Secp256k1};
+//! use generic_ec::wire::v1::{self, WireV1};
+//!
+//! let point = Point::<Secp256k1>::generator() * Scalar::from(2);
+//! let bytes: [u8; Secp256k1::POINT_LEN] = v1::encode_point(&point);
+//! assert_eq!(v1::decode_point::<Secp256k1>(&bytes)?, point);
+//!
+//! // Uncompressed encoding is valid for `Point::from_bytes`, but not for v1
+//! assert!(v1::validate_point::<Secp256k1>(&point.to_bytes(false)).is_err());
+//! assert!(v1::check_golden_vectors::<Secp256k1>());
+//! # Ok::<_, generic_ec::errors::InvalidPoint>(())
+//! ```
+
+use crate::{
+    core::ByteArray,
+    errors::{InvalidPoint, InvalidScalar},
+    Curve, Point, Scalar,
+};
+
+/// Curve supported by wire format v1
+///
+/// Implemented for all curves shipped with the crate. The trait is sealed: encodings of
+/// third-party curves can't be guaranteed to be stable.
+pub trait WireV1: Curve + sealed::Sealed {
+    /// Size of encoded point in bytes
+    const POINT_LEN: usize;
+    /// Size of encoded scalar in bytes
+    const SCALAR_LEN: usize;
+    /// Known answers pinning the format, see [`GoldenVector`]
+    const GOLDEN_VECTORS: &'static [GoldenVector];
+}
+
+/// Golden vector: scalar $s$ and point $s \cdot G$ in v1 encoding, as hex strings
+#[derive(Debug, Clone, Copy)]
+pub struct GoldenVector {
+    /// Encoded scalar $s$
+    pub scalar: &'static str,
+    /// Encoded point $s \cdot G$
+    pub point: &'static str,
+}
+
+/// Encodes point in v1 format
+///
+/// Output size `N` must be [`E::POINT_LEN`](WireV1::POINT_LEN), which is checked at compile time.
+pub fn encode_point<E: WireV1, const N: usize>(point: &Point<E>) -> [u8; N] {
+    const { assert!(N == E::POINT_LEN, "N must be equal to E::POINT_LEN") };
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&point.to_bytes(true));
+    bytes
+}
+
+/// Encodes scalar in v1 format
+///
+/// Output size `N` must be [`E::SCALAR_LEN`](WireV1::SCALAR_LEN), which is checked at compile time.
+pub fn encode_scalar<E: WireV1, const N: usize>(scalar: &Scalar<E>) -> [u8; N] {
+    const { assert!(N == E::SCALAR_LEN, "N must be equal to E::SCALAR_LEN") };
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&scalar.to_be_bytes());
+    bytes
+}
+
+/// Decodes point in v1 format
+///
+/// Returns error if `bytes` is not a valid v1 encoding of a point
+pub fn decode_point<E: WireV1>(bytes: &[u8]) -> Result<Point<E>, InvalidPoint> {
+    if bytes.len() != E::POINT_LEN {
+        return Err(InvalidPoint);
+    }
+    let point = Point::from_bytes(bytes)?;
+    // Rejects any encoding accepted by the backend other than the canonical one
+    if *point.to_bytes(true) != *bytes {
+        return Err(InvalidPoint);
+    }
+    Ok(point)
+}
+
+/// Decodes scalar in v1 format
+///
+/// Returns error if `bytes` is not a valid v1 encoding of a scalar
+pub fn decode_scalar<E: WireV1>(bytes: &[u8]) -> Result<Scalar<E>, InvalidScalar> {
+    if bytes.len() != E::SCALAR_LEN {
+        return Err(InvalidScalar);
+    }
+    Scalar::from_be_bytes(bytes)
+}
+
+/// Checks that `bytes` is a valid v1 encoding of a point
+pub fn validate_point<E: WireV1>(bytes: &[u8]) -> Result<(), InvalidPoint> {
+    decode_point::<E>(bytes).map(|_| ())
+}
+
+/// Checks that `bytes` is a valid v1 encoding of a scalar
+pub fn validate_scalar<E: WireV1>(bytes: &[u8]) -> Result<(), InvalidScalar> {
+    decode_scalar::<E>(bytes).map(|_| ())
+}
+
+/// Checks that encoding and decoding match [golden vectors](WireV1::GOLDEN_VECTORS)
+///
+/// Returns `false` if format has changed, which should never happen
+pub fn check_golden_vectors<E: WireV1>() -> bool {
+    E::GOLDEN_VECTORS.iter().all(|vector| {
+        let mut scalar_bytes = E::ScalarArray::zeroes();
+        let mut point_bytes = E::CompressedPointArray::zeroes();
+        if decode_hex(vector.scalar, scalar_bytes.as_mut()).is_none()
+            || decode_hex(vector.point, point_bytes.as_mut()).is_none()
+        {
+            return false;
+        }
+        let (Ok(scalar), Ok(point)) = (
+            decode_scalar::<E>(scalar_bytes.as_ref()),
+            decode_point::<E>(point_bytes.as_ref()),
+        ) else {
+            return false;
+        };
+        Point::generator() * scalar == point && *scalar.to_be_bytes() == *scalar_bytes.as_ref()
+    })
+}
+
+/// Decodes hex string into `out`, returns `None` if it's malformed or has wrong length
+fn decode_hex(hex: &str, out: &mut [u8]) -> Option<()> {
+    if hex.len() != 2 * out.len() {
+        return None;
+    }
+    let nibble = |c: u8| (c as char).to_digit(16);
+    for (byte, pair) in out.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        *byte = u8::try_from((nibble(pair[0])? << 4) | nibble(pair[1])?).ok()?;
+    }
+    Some(())
+}
+
+#[cfg(feature = "curve-secp256k1")]
+impl WireV1 for crate::curves::Secp256k1 {
+    const POINT_LEN: usize = 33;
+    const SCALAR_LEN: usize = 32;
+    const GOLDEN_VECTORS: &'static [GoldenVector] = &[
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000000",
+            point: "000000000000000000000000000000000000000000000000000000000000000000",
+        },
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000001",
+            point: "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        },
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000002",
+            point: "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+        },
+        GoldenVector {
+            scalar: "000000000000000000000000000067656e657269632d65632077697265207631",
+            point: "03f33f54faf500df642c718f678aad20784645e7a11fd3812f90b516a17a4f0412",
+        },
+        GoldenVector {
+            scalar: "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+            point: "0379be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        },
+    ];
+}
+
+#[cfg(feature = "curve-secp256r1")]
+impl WireV1 for crate::curves::Secp256r1 {
+    const POINT_LEN: usize = 33;
+    const SCALAR_LEN: usize = 32;
+    const GOLDEN_VECTORS: &'static [GoldenVector] = &[
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000000",
+            point: "000000000000000000000000000000000000000000000000000000000000000000",
+        },
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000001",
+            point: "036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+        },
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000002",
+            point: "037cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978",
+        },
+        GoldenVector {
+            scalar: "000000000000000000000000000067656e657269632d65632077697265207631",
+            point: "03ff26687d29bd263c77ba773fca5134abae784f745a2af85eb73cd677aa70fc9f",
+        },
+        GoldenVector {
+            scalar: "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632550",
+            point: "026b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+        },
+    ];
+}
+
+#[cfg(feature = "curve-stark")]
+impl WireV1 for crate::curves::Stark {
+    const POINT_LEN: usize = 33;
+    const SCALAR_LEN: usize = 32;
+    const GOLDEN_VECTORS: &'static [GoldenVector] = &[
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000000",
+            point: "000000000000000000000000000000000000000000000000000000000000000000",
+        },
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000001",
+            point: "0301ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca",
+        },
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000002",
+            point: "030759ca09377679ecd535a81e83039658bf40959283187c654c5416f439403cf5",
+        },
+        GoldenVector {
+            scalar: "000000000000000000000000000067656e657269632d65632077697265207631",
+            point: "0207fba6c0f6630ccb868a6bff8cdcfa499e458d596346ff362a9c234e3d0faa23",
+        },
+        GoldenVector {
+            scalar: "0800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2e",
+            point: "0201ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca",
+        },
+    ];
+}
+
+#[cfg(feature = "curve-ed25519")]
+impl WireV1 for crate::curves::Ed25519 {
+    const POINT_LEN: usize = 32;
+    const SCALAR_LEN: usize = 32;
+    const GOLDEN_VECTORS: &'static [GoldenVector] = &[
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000000",
+            point: "0100000000000000000000000000000000000000000000000000000000000000",
+        },
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000001",
+            point: "5866666666666666666666666666666666666666666666666666666666666666",
+        },
+        GoldenVector {
+            scalar: "0000000000000000000000000000000000000000000000000000000000000002",
+            point: "c9a3f86aae465f0e56513864510f3997561fa2c9e85ea21dc2292309f3cd6022",
+        },
+        GoldenVector {
+            scalar: "000000000000000000000000000067656e657269632d65632077697265207631",
+            point: "c0512dea44c88e547bda7fe8b72aeacab7a747a1bf41568151eb692845cae479",
+        },
+        GoldenVector {
+            scalar: "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ec",
+            point: "58666666666666666666666666666666666666666666666666666666666666e6",
+        },
+    ];
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    #[cfg(feature = "curve-secp256k1")]
+    impl Sealed for crate::curves::Secp256k1 {}
+    #[cfg(feature = "curve-secp256r1")]
+    impl Sealed for crate::curves::Secp256r1 {}
+    #[cfg(feature = "curve-stark")]
+    impl Sealed for crate::curves::Stark {}
+    #[cfg(feature = "curve-ed25519")]
+    impl Sealed for crate::curves::Ed25519 {}
+}
@@ -0,0 +1,129 @@
+//! Implementation of zkcrypto [`ff`] and [`group`] traits
+//!
+//! Lets libraries from zkcrypto ecosystem consume [`Scalar<E>`] and [`Point<E>`] directly. Traits
+//! are implemented for every curve whose backend scalar implements [`ff::PrimeField`], which is the
+//! case for all curves provided by `generic-ec-curves`.
+
+use rand_core::RngCore;
+use subtle::{Choice, CtOption};
+
+use crate::{as_raw::AsRaw, as_raw::FromRaw, core::*, Point, Scalar};
+
+impl<E: Curve> ff::Field for Scalar<E>
+where
+    E::Scalar: ff::PrimeField,
+{
+    const ZERO: Self = Scalar::from_raw_const(<E::Scalar as ff::Field>::ZERO);
+    const ONE: Self = Scalar::from_raw_const(<E::Scalar as ff::Field>::ONE);
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Scalar::random(&mut rng)
+    }
+
+    fn square(&self) -> Self {
+        self * self
+    }
+
+    fn double(&self) -> Self {
+        self + self
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        self.ct_invert()
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        let (is_square, root) = <E::Scalar as ff::Field>::sqrt_ratio(num.as_raw(), div.as_raw());
+        (is_square, Scalar::from_raw(root))
+    }
+}
+
+impl<E: Curve> ff::PrimeField for Scalar<E>
+where
+    E::Scalar: ff::PrimeField,
+{
+    type Repr = <E::Scalar as ff::PrimeField>::Repr;
+
+    const MODULUS: &'static str = <E::Scalar as ff::PrimeField>::MODULUS;
+    const NUM_BITS: u32 = <E::Scalar as ff::PrimeField>::NUM_BITS;
+    const CAPACITY: u32 = <E::Scalar as ff::PrimeField>::CAPACITY;
+    const TWO_INV: Self = Scalar::from_raw_const(<E::Scalar as ff::PrimeField>::TWO_INV);
+    const MULTIPLICATIVE_GENERATOR: Self =
+        Scalar::from_raw_const(<E::Scalar as ff::PrimeField>::MULTIPLICATIVE_GENERATOR);
+    const S: u32 = <E::Scalar as ff::PrimeField>::S;
+    const ROOT_OF_UNITY: Self =
+        Scalar::from_raw_const(<E::Scalar as ff::PrimeField>::ROOT_OF_UNITY);
+    const ROOT_OF_UNITY_INV: Self =
+        Scalar::from_raw_const(<E::Scalar as ff::PrimeField>::ROOT_OF_UNITY_INV);
+    const DELTA: Self = Scalar::from_raw_const(<E::Scalar as ff::PrimeField>::DELTA);
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        <E::Scalar as ff::PrimeField>::from_repr(repr).map(Scalar::from_raw)
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        ff::PrimeField::to_repr(self.as_raw())
+    }
+
+    fn is_odd(&self) -> Choice {
+        ff::PrimeField::is_odd(self.as_raw())
+    }
+}
+
+impl<E: Curve> group::Group for Point<E>
+where
+    E::Scalar: ff::PrimeField,
+{
+    type Scalar = Scalar<E>;
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Point::generator() * Scalar::random(&mut rng)
+    }
+
+    fn identity() -> Self {
+        Point::zero()
+    }
+
+    fn generator() -> Self {
+        Point::generator().to_point()
+    }
+
+    fn is_identity(&self) -> Choice {
+        self.ct_is_zero()
+    }
+
+    fn double(&self) -> Self {
+        Point::double(self)
+    }
+}
+
+/// `Point<E>` is always in the prime-order subgroup, even if the curve has a cofactor
+impl<E: Curve> group::prime::PrimeGroup for Point<E>
+where
+    E::Scalar: ff::PrimeField,
+    E::CompressedPointArray: Copy + Default,
+{
+}
+
+impl<E: Curve> group::GroupEncoding for Point<E>
+where
+    E::CompressedPointArray: Copy + Default,
+{
+    type Repr = E::CompressedPointArray;
+
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+        match Point::from_bytes(bytes) {
+            Ok(point) => CtOption::new(point, Choice::from(1)),
+            Err(_) => CtOption::new(Point::zero(), Choice::from(0)),
+        }
+    }
+
+    fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+        // Points can't be constructed without validation
+        <Self as group::GroupEncoding>::from_bytes(bytes)
+    }
+
+    fn to_bytes(&self) -> Self::Repr {
+        self.as_raw().to_bytes_compressed()
+    }
+}
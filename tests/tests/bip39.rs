@@ -0,0 +1,239 @@
+use generic_ec::{
+    bip39::{self, ChildIndex, DerivationPath, ExtendedSecretKey, MasterKeyCurve},
+    curves::{Ed25519, Secp256k1, Secp256r1},
+    Point,
+};
+
+const ZERO_ENTROPY: &str = "abandon abandon abandon abandon abandon abandon \
+    abandon abandon abandon abandon abandon about";
+const FULL_ENTROPY: &str = "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong";
+
+/// Test vectors from BIP39 reference implementation (passphrase `TREZOR`)
+#[test]
+fn mnemonic_to_seed() {
+    let seed = bip39::mnemonic_to_seed(ZERO_ENTROPY, "TREZOR").unwrap();
+    assert_eq!(
+        hex::encode(*seed),
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e5349553\
+         1f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+    );
+
+    let seed = bip39::mnemonic_to_seed(FULL_ENTROPY, "TREZOR").unwrap();
+    assert_eq!(
+        hex::encode(*seed),
+        "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13\
+         332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069"
+    );
+
+    // Words separated by arbitrary whitespace give the same seed
+    let spaced = FULL_ENTROPY.replace(' ', " \n\t");
+    assert_eq!(
+        bip39::mnemonic_to_seed(&spaced, "TREZOR").unwrap(),
+        bip39::mnemonic_to_seed(FULL_ENTROPY, "TREZOR").unwrap()
+    );
+
+    assert!(bip39::mnemonic_to_seed("abandon abandon about", "").is_err());
+}
+
+#[test]
+fn validate_mnemonic() {
+    // Wordlists are not bundled, make a list that agrees with English wordlist on the words used
+    let mut words: Vec<String> = (0..2048).map(|i| format!("word{i}")).collect();
+    words[0] = "abandon".into();
+    words[3] = "about".into();
+    words[2037] = "wrong".into();
+    words[2047] = "zoo".into();
+    let wordlist: Vec<&str> = words.iter().map(String::as_str).collect();
+
+    bip39::validate_mnemonic(ZERO_ENTROPY, &wordlist).unwrap();
+    bip39::validate_mnemonic(FULL_ENTROPY, &wordlist).unwrap();
+
+    let bad_checksum = ZERO_ENTROPY.replace("about", "abandon");
+    assert!(bip39::validate_mnemonic(&bad_checksum, &wordlist).is_err());
+    let unknown_word = ZERO_ENTROPY.replace("about", "abacus");
+    assert!(bip39::validate_mnemonic(&unknown_word, &wordlist).is_err());
+    assert!(bip39::validate_mnemonic(ZERO_ENTROPY, &wordlist[1..]).is_err());
+}
+
+fn check_master_key<E: MasterKeyCurve>(secret_key: &str, chain_code: &str) {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master_key = bip39::master_key::<E>(&seed);
+    assert_eq!(
+        hex::encode(
+            master_key
+                .secret_key()
+                .as_ref()
+                .expose_secret(|x| x.to_be_bytes())
+        ),
+        secret_key
+    );
+    assert_eq!(hex::encode(master_key.chain_code()), chain_code);
+}
+
+/// Test vector 1 from BIP32 and SLIP-10
+#[test]
+fn master_key() {
+    check_master_key::<Secp256k1>(
+        "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+        "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+    );
+    check_master_key::<Secp256r1>(
+        "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2",
+        "beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea",
+    );
+
+    // SLIP-10 ed25519 key is an Ed25519 seed, compare public keys instead
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master_key = bip39::master_key::<Ed25519>(&seed);
+    let public_key = Point::generator() * master_key.secret_key();
+    assert_eq!(
+        hex::encode(public_key.to_bytes(true)),
+        "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"
+    );
+    assert_eq!(
+        hex::encode(master_key.chain_code()),
+        "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"
+    );
+}
+
+fn secret_key_hex<E: MasterKeyCurve>(key: &ExtendedSecretKey<E>) -> String {
+    hex::encode(key.secret_key().as_ref().expose_secret(|x| x.to_be_bytes()))
+}
+
+/// Test vector 1 from BIP32 and SLIP-10: path, secret key (public key for ed25519), chain code
+#[test]
+fn derive_path() {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+
+    let master_key = bip39::master_key::<Secp256k1>(&seed);
+    for (path, secret_key, chain_code) in [
+        (
+            "m/0'",
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+        ),
+        (
+            "m/0'/1",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+        ),
+        (
+            "m/0'/1/2'",
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+        ),
+    ] {
+        let path: DerivationPath = path.parse().unwrap();
+        let key = master_key.derive_path(&path).unwrap();
+        assert_eq!(key.path(), &path);
+        assert_eq!(secret_key_hex(&key), secret_key);
+        assert_eq!(hex::encode(key.chain_code()), chain_code);
+    }
+
+    let master_key = bip39::master_key::<Secp256r1>(&seed);
+    let key = master_key.derive_path(&"m/0'".parse().unwrap()).unwrap();
+    assert_eq!(
+        secret_key_hex(&key),
+        "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c"
+    );
+    assert_eq!(
+        hex::encode(key.chain_code()),
+        "3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11"
+    );
+
+    let master_key = bip39::master_key::<Ed25519>(&seed);
+    for (path, public_key, chain_code) in [
+        (
+            "m/0'",
+            "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+        ),
+        (
+            "m/0'/1'",
+            "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+            "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+        ),
+    ] {
+        let path: DerivationPath = path.parse().unwrap();
+        let key = master_key.derive_path(&path).unwrap();
+        let derived_public_key = Point::generator() * key.secret_key();
+        assert_eq!(hex::encode(derived_public_key.to_bytes(true)), public_key);
+        assert_eq!(hex::encode(key.chain_code()), chain_code);
+    }
+
+    // ed25519 supports only hardened derivation
+    assert!(master_key
+        .derive_child(ChildIndex::normal(0).unwrap())
+        .is_err());
+}
+
+#[test]
+fn derivation_path_encoding() {
+    let path: DerivationPath = "m/44'/0h/0H/1/2".parse().unwrap();
+    assert_eq!(path.to_string(), "m/44'/0'/0'/1/2");
+    assert_eq!(
+        path.indexes(),
+        [
+            ChildIndex::hardened(44).unwrap(),
+            ChildIndex::hardened(0).unwrap(),
+            ChildIndex::from_raw(0x8000_0000),
+            ChildIndex::normal(1).unwrap(),
+            ChildIndex::normal(2).unwrap(),
+        ]
+    );
+    assert_eq!(DerivationPath::master().to_string(), "m");
+    assert_eq!(
+        "m".parse::<DerivationPath>().unwrap(),
+        DerivationPath::master()
+    );
+
+    for invalid in [
+        "",
+        "44'/0'",
+        "m/",
+        "m//0",
+        "m/+1",
+        "m/-1",
+        "m/0''",
+        "m/2147483648",
+        "M/0",
+    ] {
+        assert!(invalid.parse::<DerivationPath>().is_err(), "{invalid}");
+    }
+    assert!("m/2147483647'".parse::<DerivationPath>().is_ok());
+
+    let json = serde_json::to_string(&path).unwrap();
+    assert_eq!(json, r#""m/44'/0'/0'/1/2""#);
+    assert_eq!(serde_json::from_str::<DerivationPath>(&json).unwrap(), path);
+    assert!(serde_json::from_str::<DerivationPath>(r#""m/x""#).is_err());
+}
+
+#[test]
+fn public_derivation() {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master_key = bip39::master_key::<Secp256k1>(&seed);
+    let parent = master_key.derive_path(&"m/0'".parse().unwrap()).unwrap();
+    let child = master_key
+        .derive_path(&"m/0'/1/2".parse().unwrap())
+        .unwrap();
+
+    let parent_xpub = parent.extended_public_key();
+    let (child_xpub, tweak) = parent_xpub
+        .derive_path_with_tweak(&"m/1/2".parse().unwrap())
+        .unwrap();
+    assert_eq!(
+        child_xpub.public_key(),
+        &(Point::generator() * child.secret_key())
+    );
+    assert_eq!(child_xpub.chain_code(), child.chain_code());
+    assert_eq!(
+        *child_xpub.public_key(),
+        parent_xpub.public_key() + Point::generator() * tweak
+    );
+    // Path continues the path of the secret key it was obtained from
+    assert_eq!(child_xpub.path().to_string(), "m/0'/1/2");
+
+    assert!(parent_xpub
+        .derive_child(ChildIndex::hardened(1).unwrap())
+        .is_err());
+}
@@ -0,0 +1,52 @@
+use std::hint::black_box;
+
+use generic_ec::ct_tests::{self, Class, Config};
+
+/// Harness must detect an operation that does extra work for one of the classes
+#[test]
+fn detects_leaking_operation() {
+    let mut rng = rand_dev::DevRng::new();
+    let report = ct_tests::measure(
+        "leaking operation",
+        &mut rng,
+        &Config {
+            measurements: 2_000,
+        },
+        |class, _rng| class == Class::Fixed,
+        |&slow| {
+            let iterations = if slow { 10_000 } else { 10 };
+            (0..iterations).fold(0u64, |acc, i| black_box(acc ^ i))
+        },
+    );
+    assert!(report.is_leaking(), "{report}");
+}
+
+/// Timing tests are too noisy to be asserted in CI, so we only check that harnesses work.
+/// Reproducing constant-time claims requires running them on an idle machine with many
+/// more measurements.
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{
+        ct_tests::{self, Config},
+        curves::{Ed25519, Secp256k1, Secp256r1, Stark},
+        Curve,
+    };
+
+    #[test]
+    fn run_all<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        for report in ct_tests::run_all::<E>(&mut rng, &Config { measurements: 200 }) {
+            assert_eq!(report.measurements, 200);
+            assert!(!report.t.is_nan() && !report.cropped_t.is_nan(), "{report}");
+        }
+    }
+
+    #[instantiate_tests(<Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<Stark>)]
+    mod stark {}
+    #[instantiate_tests(<Ed25519>)]
+    mod ed25519 {}
+}
@@ -2,7 +2,9 @@
 
 #[generic_tests::define]
 mod tests {
-    use generic_ec::{curves::*, Curve, EncodedScalar, Point, Scalar};
+    use generic_ec::{
+        curves::*, Curve, EncodedScalar, FixedBase, NonZero, Point, Scalar, SecretScalar,
+    };
     use rand::Rng;
     use rand_dev::DevRng;
 
@@ -110,6 +112,8 @@ mod tests {
             let bytes_compressed = point.to_bytes(true);
             let bytes_uncompressed = point.to_bytes(false);
             assert!(bytes_compressed.len() <= bytes_uncompressed.len());
+            assert_eq!(bytes_compressed.len(), Point::<E>::serialized_len(true));
+            assert_eq!(bytes_uncompressed.len(), Point::<E>::serialized_len(false));
 
             let p1 = Point::<E>::from_bytes(&bytes_compressed).unwrap();
             let p2 = Point::<E>::from_bytes(&bytes_uncompressed).unwrap();
@@ -119,6 +123,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn normalized_point<E: Curve>() {
+        let mut rng = DevRng::new();
+
+        let mut points = [(); 10].map(|_| Point::generator() * Scalar::<E>::random(&mut rng));
+        points[0] = Point::zero();
+        points[1] = Point::generator().into();
+        let mut normalized = points.map(|p| p.normalize());
+
+        for (point, normalized) in points.iter().zip(&normalized) {
+            assert_eq!(point, normalized.point());
+            assert_eq!(point.to_bytes(true), normalized.to_bytes(true));
+            assert_eq!(point.to_bytes(false), normalized.to_bytes(false));
+            assert_eq!(*normalized, (*point + Point::zero()).normalize());
+        }
+
+        points.sort();
+        normalized.sort();
+        assert!(points.iter().eq(normalized.iter().map(|p| p.point())));
+    }
+
     #[test]
     fn point_at_scalar<E: Curve>() {
         let mut rng = DevRng::new();
@@ -134,6 +159,177 @@ mod tests {
         assert_eq!(p + p * Scalar::from(-1), Point::zero());
     }
 
+    #[test]
+    fn key_blinding<E: Curve>() {
+        let mut rng = DevRng::new();
+
+        let secret_key = SecretScalar::<E>::random(&mut rng);
+        let public_key = Point::generator() * &secret_key;
+        let blinding = Scalar::<E>::random(&mut rng);
+
+        let blinded_key = public_key.blind(&blinding);
+        assert_ne!(blinded_key, public_key);
+        assert_eq!(
+            blinded_key,
+            Point::generator() * secret_key.blind(&blinding)
+        );
+    }
+
+    #[test]
+    fn canonical_ordering<E: Curve>() {
+        use std::collections::{BTreeMap, HashMap};
+        use std::hash::BuildHasher;
+
+        let mut rng = DevRng::new();
+
+        let mut scalars: Vec<_> = (0..10).map(|_| Scalar::<E>::random(&mut rng)).collect();
+        scalars.extend([Scalar::zero(), Scalar::one(), -Scalar::one()]);
+        let pairs: Vec<_> = scalars
+            .iter()
+            .map(|s| (Point::generator() * s, *s))
+            .collect();
+        let mut points: Vec<_> = pairs.iter().map(|(p, _)| *p).collect();
+
+        scalars.sort();
+        points.sort();
+        assert!(scalars
+            .windows(2)
+            .all(|w| w[0].to_be_bytes().as_bytes() < w[1].to_be_bytes().as_bytes()));
+        assert_eq!(scalars.first(), Some(&Scalar::zero()));
+        assert_eq!(scalars.last(), Some(&-Scalar::one()));
+        assert!(points
+            .windows(2)
+            .all(|w| w[0].to_bytes(true).as_bytes() < w[1].to_bytes(true).as_bytes()));
+
+        // Points and scalars can be used as map keys
+        let by_point: BTreeMap<_, _> = pairs.iter().copied().collect();
+        let by_scalar: HashMap<_, _> = pairs.iter().map(|(p, s)| (*s, *p)).collect();
+        for (point, scalar) in &pairs {
+            assert_eq!(by_point[point], *scalar);
+            assert_eq!(by_scalar[scalar], *point);
+        }
+
+        // Hash and order don't depend on how the point was computed
+        let s = Scalar::<E>::random(&mut rng);
+        let p1 = Point::generator() * s;
+        let p2 = Point::generator() * (s - Scalar::one()) + Point::generator();
+        assert_eq!(p1.cmp(&p2), core::cmp::Ordering::Equal);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(p1), state.hash_one(p2));
+    }
+
+    #[test]
+    fn checked_arithmetic<E: Curve>() {
+        let mut rng = DevRng::new();
+        let a = Scalar::<E>::random(&mut rng);
+        let b = Scalar::<E>::random(&mut rng);
+
+        assert_eq!(*a.checked_invert().unwrap(), a.invert().unwrap());
+        assert!(Scalar::<E>::zero().checked_invert().is_none());
+        assert_eq!(a.checked_div(&b).unwrap() * b, a);
+        assert!(a.checked_div(&Scalar::zero()).is_none());
+        assert_eq!(Scalar::zero().checked_div(&b), Some(Scalar::zero()));
+
+        let p = Point::generator() * a;
+        let q = Point::generator() * b;
+        assert_eq!(*p.checked_add(&q).unwrap(), p + q);
+        assert!(p.checked_add(&-p).is_none());
+        assert_eq!(*p.checked_sub(&q).unwrap(), p - q);
+        assert!(p.checked_sub(&p).is_none());
+    }
+
+    #[test]
+    fn try_random_reports_broken_rng<E: Curve>() {
+        use generic_ec::NonZero;
+
+        /// Randomness source that always outputs the same byte
+        struct ConstRng(u8);
+        impl rand::RngCore for ConstRng {
+            fn next_u32(&mut self) -> u32 {
+                u32::from_le_bytes([self.0; 4])
+            }
+            fn next_u64(&mut self) -> u64 {
+                u64::from_le_bytes([self.0; 8])
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                dest.fill(self.0)
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        assert!(Scalar::<E>::try_random(&mut ConstRng(0)).is_err());
+        assert!(NonZero::<Scalar<E>>::try_random(&mut ConstRng(0)).is_err());
+
+        let bound = NonZero::from_scalar(Scalar::<E>::from(3)).unwrap();
+        assert!(Scalar::try_random_below(&bound, &mut ConstRng(0xff)).is_err());
+        assert_eq!(
+            Scalar::try_random_below(&bound, &mut ConstRng(0)).unwrap(),
+            Scalar::zero()
+        );
+    }
+
+    #[test]
+    fn scalar_random_below<E: Curve>() {
+        use generic_ec::NonZero;
+
+        let mut rng = DevRng::new();
+
+        assert_ne!(*Scalar::<E>::random_nonzero(&mut rng), Scalar::zero());
+
+        let bound = |s: Scalar<E>| NonZero::from_scalar(s).unwrap();
+        for _ in 0..10 {
+            assert_eq!(
+                Scalar::random_below(&bound(Scalar::one()), &mut rng),
+                Scalar::zero()
+            );
+        }
+
+        // Every value below small bound is sampled
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            let s = Scalar::random_below(&bound(Scalar::from(3)), &mut rng);
+            let i = (0..3).find(|i| Scalar::from(*i) == s).unwrap();
+            seen[i] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+
+        // 2^k bound
+        let two_to_k = Scalar::<E>::from(1_u128 << 100);
+        for _ in 0..100 {
+            assert!(Scalar::random_below(&bound(two_to_k), &mut rng) < two_to_k);
+        }
+
+        // Bound is close to group order
+        let minus_one = -Scalar::<E>::one();
+        for _ in 0..100 {
+            assert!(Scalar::random_below(&bound(minus_one), &mut rng) < minus_one);
+        }
+    }
+
+    #[test]
+    fn scalar_batch_invert<E: Curve>() {
+        let mut rng = DevRng::new();
+
+        for len in [0, 1, 2, 10] {
+            let scalars = (0..len)
+                .map(|_| Scalar::<E>::random(&mut rng))
+                .collect::<Vec<_>>();
+            let mut inverses = scalars.clone();
+            Scalar::batch_invert(&mut inverses).unwrap();
+            for (s, s_inv) in scalars.iter().zip(&inverses) {
+                assert_eq!(s.invert().unwrap(), *s_inv);
+            }
+        }
+
+        let mut scalars = [Scalar::<E>::random(&mut rng), Scalar::zero()];
+        let original = scalars;
+        assert!(Scalar::batch_invert(&mut scalars).is_err());
+        assert_eq!(scalars, original);
+    }
+
     #[test]
     fn scalar_0xFF_not_valid<E: Curve>() {
         let mut encoded_scalar = EncodedScalar::<E>::default();
@@ -185,6 +381,45 @@ mod tests {
         assert_eq!(Scalar::<E>::from_be_bytes(one_be).unwrap(), one);
     }
 
+    #[test]
+    #[cfg(not(feature = "debug-secrets"))]
+    fn secret_scalar_debug_is_redacted<E: Curve>() {
+        let mut rng = DevRng::new();
+        let secret = SecretScalar::<E>::random(&mut rng);
+        assert_eq!(
+            format!("{secret:?}"),
+            format!("SecretScalar<{}>([REDACTED])", E::CURVE_NAME)
+        );
+        let hex = hex::encode(secret.expose_secret(|x| x.to_be_bytes()));
+        assert!(!format!("{:?}", Some(secret)).contains(&hex));
+    }
+
+    #[test]
+    fn conformance<E: Curve>() {
+        let mut rng = DevRng::new();
+        generic_ec::testing::test_curve::<E>(&mut rng);
+    }
+
+    #[test]
+    fn deterministic_rng<E: Curve>() {
+        use generic_ec::testing::DeterministicRng;
+
+        let sample = |domain: &[u8], seed: &[u8]| {
+            let mut rng = DeterministicRng::new(domain, seed);
+            [(); 3].map(|_| Scalar::<E>::random(&mut rng))
+        };
+        assert_eq!(sample(b"domain", b"seed"), sample(b"domain", b"seed"));
+        assert_ne!(sample(b"domain", b"seed"), sample(b"domain", b"other seed"));
+        assert_ne!(sample(b"domain", b"seed"), sample(b"other domain", b"seed"));
+        // Domain and seed are separated unambiguously
+        assert_ne!(sample(b"ab", b"c"), sample(b"a", b"bc"));
+
+        assert_eq!(
+            Scalar::<E>::random_seeded(b"domain", b"seed"),
+            sample(b"domain", b"seed")[0]
+        );
+    }
+
     fn _is_copy<T: Copy>() {}
     fn _test_point_and_scalar_are_copy<E: Curve>() {
         _is_copy::<Scalar<E>>();
@@ -232,6 +467,175 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scalar_bits<E: Curve>() {
+        let mut rng = DevRng::new();
+        for scalar in [Scalar::<E>::zero(), Scalar::one(), Scalar::from(-1)]
+            .into_iter()
+            .chain((0..10).map(|_| Scalar::random(&mut rng)))
+        {
+            let bits_le = scalar.bits_le().collect::<Vec<_>>();
+            assert_eq!(bits_le.len(), Scalar::<E>::serialized_len() * 8);
+            assert_eq!(scalar.bits_le().len(), bits_le.len());
+
+            let reconstructed = bits_le.iter().rev().fold(Scalar::<E>::zero(), |acc, bit| {
+                acc * Scalar::from(2) + Scalar::from(u8::from(*bit))
+            });
+            assert_eq!(scalar, reconstructed);
+
+            let mut bits_be = scalar.bits_be().collect::<Vec<_>>();
+            bits_be.reverse();
+            assert_eq!(bits_le, bits_be);
+        }
+    }
+
+    #[test]
+    fn secret_scalar_ops<E: Curve>() {
+        let mut rng = DevRng::new();
+        let a = Scalar::<E>::random(&mut rng);
+        let b = Scalar::<E>::random(&mut rng);
+        let secret_a = SecretScalar::new(&mut a.clone());
+        let secret_b = SecretScalar::new(&mut b.clone());
+
+        assert_eq!(&secret_a * &secret_b, a * b);
+        assert_eq!(&secret_a + &secret_b, a + b);
+        assert_eq!((-&secret_a).into_inner_unprotected(), -a);
+
+        let mut s = secret_a.clone();
+        s += &secret_b;
+        assert_eq!(s.expose_secret(|x| *x), a + b);
+        s -= b;
+        assert_eq!(s.expose_secret(|x| *x), a);
+        s *= &secret_b;
+        assert_eq!(s.expose_secret(|x| *x), a * b);
+        // Other clones of the secret are unaffected
+        assert_eq!(secret_a.expose_secret(|x| *x), a);
+
+        let nonzero_b = NonZero::from_secret_scalar(secret_b).unwrap();
+        let mut nonzero_s = NonZero::from_secret_scalar(secret_a).unwrap();
+        nonzero_s *= &nonzero_b;
+        assert_eq!(nonzero_s.expose_secret(|x| *x), a * b);
+
+        let point = Point::generator() * NonZero::<Scalar<E>>::random(&mut rng);
+        assert_eq!(
+            point + Point::generator(),
+            *point + Point::generator().to_point()
+        );
+        assert_eq!(
+            point - Point::generator(),
+            *point - Point::generator().to_point()
+        );
+    }
+
+    #[test]
+    fn fixed_base<E: Curve>() {
+        let mut rng = DevRng::new();
+        let base = Point::<E>::generator() * NonZero::<Scalar<E>>::random(&mut rng);
+        let fixed = FixedBase::new(base);
+        assert_eq!(fixed.point(), &base);
+
+        for scalar in [Scalar::<E>::zero(), Scalar::one(), Scalar::from(-1)]
+            .into_iter()
+            .chain((0..10).map(|_| Scalar::random(&mut rng)))
+        {
+            assert_eq!(&fixed * &scalar, base * scalar);
+            if let Some(nonzero) = NonZero::from_scalar(scalar) {
+                assert_eq!(*(&fixed * &nonzero), base * scalar);
+            }
+        }
+
+        let secret = SecretScalar::<E>::random(&mut rng);
+        assert_eq!(&fixed * &secret, base * &secret);
+
+        let generator = FixedBase::new(Point::<E>::generator());
+        let scalar = Scalar::<E>::random(&mut rng);
+        assert_eq!(&generator * &scalar, Point::generator() * scalar);
+    }
+
+    #[test]
+    fn same_curve<E: Curve>() {
+        use core::any::Any;
+        use generic_ec::same_curve::SameCurve;
+
+        let mut rng = DevRng::new();
+        let witness = SameCurve::<E, E>::check().unwrap();
+
+        let point = Point::<E>::generator() * Scalar::random(&mut rng);
+        assert_eq!(witness.cast(point), point);
+        assert_eq!(witness.reverse().cast_ref(&point), &point);
+        let secret = NonZero::<SecretScalar<E>>::random(&mut rng);
+        assert_eq!(
+            Point::generator() * &witness.cast(secret.clone()),
+            Point::generator() * &secret
+        );
+
+        // Payload of unknown curve, e.g. received from a plugin
+        let payload: Box<dyn Any> = Box::new(point);
+        let same_as_secp256k1 = SameCurve::<E, Secp256k1>::check();
+        assert_eq!(
+            same_as_secp256k1.is_some(),
+            payload.is::<Point<Secp256k1>>()
+        );
+        assert!(SameCurve::<Secp256k1, Secp256r1>::check().is_none());
+    }
+
+    #[test]
+    fn scalar_is_high<E: Curve>() {
+        let mut rng = DevRng::new();
+        // (q - 1) / 2
+        let half = -Scalar::<E>::from(2).invert().unwrap();
+        assert!(bool::from(half.is_low()));
+        assert!(bool::from((half + Scalar::one()).is_high()));
+        assert!(bool::from(Scalar::<E>::zero().is_low()));
+        assert!(bool::from(Scalar::<E>::from(-1).is_high()));
+
+        for scalar in [Scalar::<E>::zero(), Scalar::one(), Scalar::from(-1), half]
+            .into_iter()
+            .chain((0..20).map(|_| Scalar::random(&mut rng)))
+        {
+            let is_high = bool::from(scalar.is_high());
+            assert_eq!(is_high, scalar > half);
+            assert_eq!(bool::from(scalar.is_low()), !is_high);
+
+            let (normalized, negated) = scalar.conditional_negate_if_high();
+            assert_eq!(bool::from(negated), is_high);
+            assert!(bool::from(normalized.is_low()));
+            assert_eq!(normalized, if is_high { -scalar } else { scalar });
+        }
+    }
+
+    #[test]
+    fn scalar_limbs<E: Curve>() {
+        let mut rng = DevRng::new();
+        let expected_len = Scalar::<E>::serialized_len().div_ceil(8);
+        for scalar in [Scalar::<E>::zero(), Scalar::one(), Scalar::from(-1)]
+            .into_iter()
+            .chain((0..10).map(|_| Scalar::random(&mut rng)))
+        {
+            let limbs = scalar.to_limbs_u64().collect::<Vec<_>>();
+            assert_eq!(limbs.len(), expected_len);
+            assert_eq!(scalar.to_limbs_u64().len(), expected_len);
+
+            let reconstructed = limbs.iter().rev().fold(Scalar::<E>::zero(), |acc, limb| {
+                acc * Scalar::from(u128::from(u64::MAX) + 1) + Scalar::from(*limb)
+            });
+            assert_eq!(scalar, reconstructed);
+            assert_eq!(Scalar::from_limbs(&limbs).unwrap(), scalar);
+
+            // Extra zero limbs are allowed, non-zero ones are not
+            let mut extended = limbs.clone();
+            extended.push(0);
+            assert_eq!(Scalar::from_limbs(&extended).unwrap(), scalar);
+            extended.push(1);
+            assert!(Scalar::<E>::from_limbs(&extended).is_err());
+        }
+
+        assert_eq!(Scalar::<E>::from_limbs(&[]).unwrap(), Scalar::zero());
+        assert_eq!(Scalar::<E>::from_limbs(&[42]).unwrap(), Scalar::from(42));
+        // Integer not less than group order is rejected
+        assert!(Scalar::<E>::from_limbs(&vec![u64::MAX; expected_len]).is_err());
+    }
+
     #[instantiate_tests(<Secp256k1>)]
     mod secp256k1 {}
 
@@ -249,7 +653,7 @@ mod tests {
 mod coordinates {
     use generic_ec::coords::{HasAffineX, HasAffineXAndParity, HasAffineXY, HasAffineY};
     use generic_ec::curves::{Secp256k1, Secp256r1, Stark};
-    use generic_ec::{Curve, Point, Scalar};
+    use generic_ec::{Curve, Point, Scalar, XOnlyPoint};
 
     use rand_dev::DevRng;
 
@@ -296,6 +700,38 @@ mod coordinates {
         assert_eq!(random_point, reassembled_point);
     }
 
+    #[test]
+    fn x_only_point<E: Curve>() {
+        let mut rng = DevRng::new();
+        for _ in 0..10 {
+            let point = Point::<E>::generator() * Scalar::random(&mut rng);
+            let (x_only, negated) = XOnlyPoint::from_point_even_y(&point).unwrap();
+            let even = if negated { -point } else { point };
+            assert_eq!(x_only.point(), &even);
+            assert_eq!(x_only.as_bytes(), &even.to_bytes(true)[1..]);
+            assert_eq!(even.to_bytes(true)[0], 0x02);
+
+            // Conversions from `Point` are checked
+            assert_eq!(XOnlyPoint::try_from(even).unwrap(), x_only);
+            assert!(XOnlyPoint::try_from(-even).is_err());
+
+            // Decoding lifts x to the point with even y
+            let decoded = XOnlyPoint::<E>::from_bytes(x_only.as_bytes()).unwrap();
+            assert_eq!(decoded, x_only);
+            assert_eq!(Point::from(decoded), even);
+        }
+
+        assert!(XOnlyPoint::from_point_even_y(&Point::<E>::zero()).is_err());
+        let point = Point::<E>::generator().to_point();
+        let bytes = XOnlyPoint::from_point_even_y(&point)
+            .unwrap()
+            .0
+            .as_bytes()
+            .to_vec();
+        assert!(XOnlyPoint::<E>::from_bytes(&bytes[1..]).is_err());
+        assert!(XOnlyPoint::<E>::from_bytes(point.to_bytes(true)).is_err());
+    }
+
     #[instantiate_tests(<Secp256k1>)]
     mod secp256k1 {}
 
@@ -305,3 +741,75 @@ mod coordinates {
     #[instantiate_tests(<Stark>)]
     mod stark {}
 }
+
+#[test]
+fn x_only_point_bip340() {
+    use generic_ec::{curves::*, Point, Scalar, XOnlyPoint};
+
+    // Public key of test vector 0 of BIP-340, secret key is 3
+    let expected =
+        hex::decode("f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9").unwrap();
+    let point = Point::<Secp256k1>::generator() * Scalar::from(3);
+    let (x_only, _) = XOnlyPoint::from_point_even_y(&point).unwrap();
+    assert_eq!(x_only.to_array().as_slice(), expected);
+    assert_eq!(
+        XOnlyPoint::<Secp256k1>::from_bytes(&expected).unwrap(),
+        x_only
+    );
+
+    // Curves without SEC1 encoding don't support x-only points
+    let point = Point::<Ed25519>::generator().to_point();
+    assert!(XOnlyPoint::from_point_even_y(&point).is_err());
+    assert!(XOnlyPoint::<Ed25519>::from_bytes([0u8; 31]).is_err());
+}
+
+#[test]
+fn security_level() {
+    use generic_ec::{curves::*, Curve, Curve128};
+
+    fn security_level_128<E: Curve128>() -> u32 {
+        E::SECURITY_LEVEL
+    }
+
+    assert!(security_level_128::<Secp256k1>() >= 128);
+    assert!(security_level_128::<Secp256r1>() >= 128);
+    assert!(security_level_128::<Ed25519>() >= 128);
+    assert_eq!(Stark::SECURITY_LEVEL, 125);
+}
+
+#[test]
+fn capabilities() {
+    use generic_ec::{
+        as_raw::FromRaw, curves::*, HasCofactor, HasEndomorphism, Point, Scalar,
+        SupportsHashToCurve,
+    };
+
+    fn hash_to_curve<E: SupportsHashToCurve>() -> Point<E> {
+        use generic_ec::hash_to_curve::{FromHash, Tag};
+        Point::hash(Tag::new_unwrap(b"test"), b"message").unwrap()
+    }
+    fn cofactor<E: HasCofactor>() -> u32 {
+        E::COFACTOR
+    }
+    fn endomorphism<E: HasEndomorphism>() {
+        let mut rng = rand_dev::DevRng::new();
+        let point = Point::<E>::generator() * Scalar::random(&mut rng);
+        let lambda = Scalar::<E>::from_raw(E::lambda());
+        assert_eq!(point.endomorphism(), point * lambda);
+        assert_eq!(point.endomorphism().endomorphism().endomorphism(), point);
+        assert_eq!(lambda * lambda * lambda, Scalar::one());
+    }
+
+    assert!(!hash_to_curve::<Secp256k1>().is_zero());
+    assert!(!hash_to_curve::<Secp256r1>().is_zero());
+
+    assert_eq!(cofactor::<Ed25519>(), 8);
+    assert_eq!(cofactor::<reference::Ed25519>(), 8);
+
+    endomorphism::<Secp256k1>();
+    endomorphism::<reference::Secp256k1>();
+    assert_eq!(
+        *Scalar::<Secp256k1>::from_raw(Secp256k1::lambda()).to_be_bytes(),
+        *Scalar::<reference::Secp256k1>::from_raw(reference::Secp256k1::lambda()).to_be_bytes(),
+    );
+}
@@ -0,0 +1,74 @@
+use generic_ec::{curves::Ed25519, Point, Scalar, SecretScalar};
+use sha2::{Digest, Sha512};
+
+/// Test vectors from RFC 8032, section 7.1: secret key (seed) and public key
+const VECTORS: &[(&str, &str)] = &[
+    (
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+    ),
+    (
+        "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+    ),
+    (
+        "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+    ),
+];
+
+#[test]
+fn ed25519_seed_import() {
+    for (seed, expected_pk) in VECTORS {
+        let seed = hex::decode(seed).unwrap();
+        let expanded = Sha512::digest(&seed);
+        let scalar_bytes: [u8; 32] = expanded[..32].try_into().unwrap();
+
+        let secret = SecretScalar::<Ed25519>::clamp_ed25519(&scalar_bytes);
+        let pk = Point::generator() * &secret;
+        assert_eq!(hex::encode(pk.to_bytes(true)), *expected_pk);
+
+        // Expanded secret key stores the scalar pre-clamped
+        let mut clamped = scalar_bytes;
+        clamped[0] &= 0b1111_1000;
+        clamped[31] &= 0b0111_1111;
+        clamped[31] |= 0b0100_0000;
+        let imported = SecretScalar::<Ed25519>::from_clamped_ed25519(&clamped).unwrap();
+        assert_eq!(Point::generator() * &imported, pk);
+    }
+}
+
+#[test]
+fn clamping_is_idempotent() {
+    let mut rng = rand_dev::DevRng::new();
+    for _ in 0..20 {
+        let mut bytes = [0u8; 32];
+        rand::RngCore::fill_bytes(&mut rng, &mut bytes);
+        let scalar = Scalar::<Ed25519>::clamp_ed25519(&bytes);
+
+        let mut clamped = bytes;
+        clamped[0] &= 0b1111_1000;
+        clamped[31] = (clamped[31] & 0b0111_1111) | 0b0100_0000;
+        assert_eq!(Scalar::clamp_ed25519(&clamped), scalar);
+        assert_eq!(Scalar::from_clamped_ed25519(&clamped).unwrap(), scalar);
+    }
+}
+
+#[test]
+fn rejects_unclamped_keys() {
+    let mut bytes = [0u8; 32];
+    bytes[31] = 0b0100_0000;
+    assert!(Scalar::<Ed25519>::from_clamped_ed25519(&bytes).is_ok());
+
+    let mut low_bits = bytes;
+    low_bits[0] = 1;
+    assert!(Scalar::<Ed25519>::from_clamped_ed25519(&low_bits).is_err());
+
+    let mut high_bit = bytes;
+    high_bit[31] |= 0b1000_0000;
+    assert!(Scalar::<Ed25519>::from_clamped_ed25519(&high_bit).is_err());
+
+    let mut second_bit_unset = bytes;
+    second_bit_unset[31] = 0;
+    assert!(SecretScalar::<Ed25519>::from_clamped_ed25519(&second_bit_unset).is_err());
+}
@@ -0,0 +1,54 @@
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{
+        curves::{Ed25519, Secp256k1, Secp256r1, Stark},
+        fuzzing, Curve, Point, Scalar,
+    };
+    use rand::{Rng, RngCore};
+
+    fn inputs<E: Curve>() -> Vec<Vec<u8>> {
+        let mut rng = rand_dev::DevRng::new();
+        let mut inputs = vec![vec![], vec![0; 1], vec![0xff; 1], vec![0xff; 300]];
+        for _ in 0..20 {
+            let mut bytes = vec![0; rng.gen_range(0..=200)];
+            rng.fill_bytes(&mut bytes);
+            inputs.push(bytes);
+
+            let point = Point::<E>::generator() * Scalar::random(&mut rng);
+            inputs.push(point.to_bytes(true).to_vec());
+            inputs.push(point.to_bytes(false).to_vec());
+        }
+        inputs.push(Point::<E>::zero().to_bytes(true).to_vec());
+        inputs
+    }
+
+    #[test]
+    fn point_decoding<E: Curve>() {
+        for input in inputs::<E>() {
+            fuzzing::point_decoding::<E>(&input)
+        }
+    }
+
+    #[test]
+    fn scalar_reduction<E: Curve>() {
+        for input in inputs::<E>() {
+            fuzzing::scalar_reduction::<E>(&input)
+        }
+    }
+
+    #[test]
+    fn arithmetic<E: Curve>() {
+        for input in inputs::<E>() {
+            fuzzing::arithmetic::<E>(&input)
+        }
+    }
+
+    #[instantiate_tests(<Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<Stark>)]
+    mod stark {}
+    #[instantiate_tests(<Ed25519>)]
+    mod ed25519 {}
+}
@@ -0,0 +1,62 @@
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{key_file, Curve, SecretScalar};
+
+    #[test]
+    fn roundtrip<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        let secret = SecretScalar::<E>::random(&mut rng);
+
+        let file = key_file::encode_unencrypted(&secret);
+        assert!(file.contains(&format!("Curve: {}\n", E::CURVE_NAME)));
+
+        let decoded: SecretScalar<E> = key_file::decode_unencrypted(&file).unwrap();
+        assert_eq!(decoded.expose_secret(|x| *x), secret.expose_secret(|x| *x));
+
+        // Windows line endings are accepted
+        let decoded: SecretScalar<E> =
+            key_file::decode_unencrypted(&file.replace('\n', "\r\n")).unwrap();
+        assert_eq!(decoded.expose_secret(|x| *x), secret.expose_secret(|x| *x));
+    }
+
+    #[test]
+    fn malformed_file_is_rejected<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        let file = key_file::encode_unencrypted(&SecretScalar::<E>::random(&mut rng));
+
+        let lines: Vec<&str> = file.lines().collect();
+        for i in 0..lines.len() {
+            let mut truncated = lines.clone();
+            truncated.remove(i);
+            assert!(key_file::decode_unencrypted::<E>(&truncated.join("\n")).is_err());
+        }
+
+        let corrupted = file.replace(lines[3], &lines[3][1..]);
+        assert!(key_file::decode_unencrypted::<E>(&corrupted).is_err());
+
+        let trailing = format!("{}garbage\n", &*file);
+        assert!(key_file::decode_unencrypted::<E>(&trailing).is_err());
+
+        // File must be explicitly labeled as unencrypted
+        assert!(file.starts_with("-----BEGIN GENERIC-EC UNENCRYPTED SECRET KEY-----\n"));
+        let unlabeled = file.replace("UNENCRYPTED ", "");
+        assert!(key_file::decode_unencrypted::<E>(&unlabeled).is_err());
+    }
+
+    #[test]
+    fn key_on_other_curve_is_rejected<E: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+        let file = key_file::encode_unencrypted(&SecretScalar::<E>::random(&mut rng));
+        let other_curve = file.replace(E::CURVE_NAME, "other-curve");
+        assert!(key_file::decode_unencrypted::<E>(&other_curve).is_err());
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+    #[instantiate_tests(<generic_ec::curves::Ed25519>)]
+    mod ed25519 {}
+}
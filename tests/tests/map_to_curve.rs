@@ -0,0 +1,105 @@
+use generic_ec::{
+    curves::{Secp256k1, Secp256r1},
+    hash_to_curve::{self, FromHash, MapToCurve, Tag},
+    Curve, Point,
+};
+
+/// Test vector from RFC 9380 with `u_0`, `u_1` produced by `hash_to_field`, `q0`, `q1` being
+/// their maps, and `p = q0 + q1` being hash to curve of `msg`
+struct TestVector {
+    dst: &'static [u8],
+    msg: &'static [u8],
+    u: [&'static str; 2],
+    q: [(&'static str, &'static str); 2],
+    p: (&'static str, &'static str),
+}
+
+fn check<E: Curve + MapToCurve>(vector: &TestVector)
+where
+    Point<E>: FromHash,
+{
+    let q: Vec<Point<E>> = vector
+        .u
+        .iter()
+        .map(|u| {
+            // Field element is left-padded with zeroes to the map input size
+            let mut input = vec![0u8; E::MAP_INPUT_LEN];
+            let u = hex::decode(u).unwrap();
+            input[E::MAP_INPUT_LEN - u.len()..].copy_from_slice(&u);
+            hash_to_curve::map_to_curve::<E>(&input).unwrap()
+        })
+        .collect();
+    for (q, expected) in q.iter().zip(vector.q) {
+        assert_eq!(*q, uncompressed::<E>(expected));
+    }
+
+    let p = uncompressed::<E>(vector.p);
+    assert_eq!(q[0] + q[1], p);
+    assert_eq!(
+        Point::<E>::hash(Tag::new_unwrap(vector.dst), vector.msg).unwrap(),
+        p
+    );
+}
+
+fn uncompressed<E: Curve>((x, y): (&str, &str)) -> Point<E> {
+    let bytes = hex::decode(format!("04{x}{y}")).unwrap();
+    Point::from_bytes(bytes).unwrap()
+}
+
+#[test]
+fn secp256r1() {
+    check::<Secp256r1>(&TestVector {
+        dst: b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_",
+        msg: b"",
+        u: [
+            "ad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009",
+            "8c0f1d43204bd6f6ea70ae8013070a1518b43873bcd850aafa0a9e220e2eea5a",
+        ],
+        q: [
+            (
+                "ab640a12220d3ff283510ff3f4b1953d09fad35795140b1c5d64f313967934d5",
+                "dccb558863804a881d4fff3455716c836cef230e5209594ddd33d85c565b19b1",
+            ),
+            (
+                "51cce63c50d972a6e51c61334f0f4875c9ac1cd2d3238412f84e31da7d980ef5",
+                "b45d1a36d00ad90e5ec7840a60a4de411917fbe7c82c3949a6e699e5a1b66aac",
+            ),
+        ],
+        p: (
+            "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+            "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+        ),
+    });
+}
+
+#[test]
+fn secp256k1() {
+    check::<Secp256k1>(&TestVector {
+        dst: b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_",
+        msg: b"",
+        u: [
+            "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+            "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16",
+        ],
+        q: [
+            (
+                "74519ef88b32b425a095e4ebcc84d81b64e9e2c2675340a720bb1a1857b99f1e",
+                "c174fa322ab7c192e11748beed45b508e9fdb1ce046dee9c2cd3a2a86b410936",
+            ),
+            (
+                "44548adb1b399263ded3510554d28b4bead34b8cf9a37b4bd0bd2ba4db87ae63",
+                "96eb8e2faf05e368efe5957c6167001760233e6dd2487516b46ae725c4cce0c6",
+            ),
+        ],
+        p: (
+            "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+            "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+        ),
+    });
+}
+
+#[test]
+fn invalid_input_length() {
+    assert!(hash_to_curve::map_to_curve::<Secp256k1>(&[1u8; 32]).is_err());
+    assert!(hash_to_curve::map_to_curve::<Secp256r1>(&[]).is_err());
+}
@@ -0,0 +1,115 @@
+use core::iter;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use generic_ec::{
+    curves::{Secp256k1, Secp256r1},
+    multiscalar::{
+        register_backend, unregister_backend, MsmBackend, MultiscalarMul, Naive, Offload, Pippenger,
+    },
+    Curve, Point, Scalar,
+};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Backend that counts how many times it was called
+struct CountingBackend;
+
+impl MsmBackend<Secp256k1> for CountingBackend {
+    const MIN_SIZE: usize = 50;
+
+    fn multiscalar_mul(
+        scalars: &[Scalar<Secp256k1>],
+        points: &[Point<Secp256k1>],
+    ) -> Point<Secp256k1> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        <Pippenger as MsmBackend<Secp256k1>>::multiscalar_mul(scalars, points)
+    }
+}
+
+static REGISTERED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Backend registered at runtime. Registry is global, so it's registered for a curve
+/// not used by other tests in this file
+struct RegisteredBackend;
+
+impl MsmBackend<Secp256r1> for RegisteredBackend {
+    const MIN_SIZE: usize = 50;
+
+    fn multiscalar_mul(
+        scalars: &[Scalar<Secp256r1>],
+        points: &[Point<Secp256r1>],
+    ) -> Point<Secp256r1> {
+        REGISTERED_CALLS.fetch_add(1, Ordering::SeqCst);
+        <Pippenger as MsmBackend<Secp256r1>>::multiscalar_mul(scalars, points)
+    }
+}
+
+fn random_input<E: Curve>(n: usize) -> Vec<(Scalar<E>, Point<E>)> {
+    let mut rng = rand_dev::DevRng::new();
+    iter::repeat_with(|| {
+        (
+            Scalar::<E>::random(&mut rng),
+            Point::generator() * Scalar::random(&mut rng),
+        )
+    })
+    .take(n)
+    .collect()
+}
+
+#[test]
+fn backend_is_used_for_large_inputs() {
+    let scalar_points = random_input::<Secp256k1>(100);
+    let expected = Naive::multiscalar_mul(scalar_points.iter().copied());
+
+    // Small input is processed by built-in algorithm
+    assert_eq!(
+        Offload::<CountingBackend>::multiscalar_mul(scalar_points[..10].iter().copied()),
+        Naive::multiscalar_mul(scalar_points[..10].iter().copied())
+    );
+    assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+
+    // Large input is offloaded to the backend
+    assert_eq!(
+        Offload::<CountingBackend>::multiscalar_mul(scalar_points.iter().copied()),
+        expected
+    );
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+    // Default algorithm never uses the backend
+    assert_eq!(
+        Scalar::multiscalar_mul(scalar_points.iter().copied()),
+        expected
+    );
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn registered_backend_is_used_by_default_algorithm() {
+    let scalar_points = random_input::<Secp256r1>(100);
+    let expected = Naive::multiscalar_mul(scalar_points.iter().copied());
+
+    register_backend::<Secp256r1, RegisteredBackend>();
+
+    // Small input is processed by built-in algorithm
+    assert_eq!(
+        Scalar::multiscalar_mul(scalar_points[..10].iter().copied()),
+        Naive::multiscalar_mul(scalar_points[..10].iter().copied())
+    );
+    assert_eq!(REGISTERED_CALLS.load(Ordering::SeqCst), 0);
+
+    // Large input is offloaded to the backend
+    assert_eq!(
+        Scalar::multiscalar_mul(scalar_points.iter().copied()),
+        expected
+    );
+    assert_eq!(REGISTERED_CALLS.load(Ordering::SeqCst), 1);
+
+    // Once unregistered, backend is not used anymore
+    assert!(unregister_backend::<Secp256r1>());
+    assert!(!unregister_backend::<Secp256r1>());
+    assert_eq!(
+        Scalar::multiscalar_mul(scalar_points.iter().copied()),
+        expected
+    );
+    assert_eq!(REGISTERED_CALLS.load(Ordering::SeqCst), 1);
+}
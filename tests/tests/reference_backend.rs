@@ -0,0 +1,174 @@
+//! Differential testing of optimized curve backends against the reference backend
+//!
+//! Every test is instantiated with a pair of curves: `E` is the optimized one, `R` is its
+//! reference counterpart. Values are converted between the two via their encodings, and results
+//! of the same operation must have the same encoding.
+
+use generic_ec::curves::reference;
+
+#[test]
+fn conformance() {
+    let mut rng = rand_dev::DevRng::new();
+    generic_ec::testing::test_curve::<reference::Secp256k1>(&mut rng);
+    generic_ec::testing::test_curve::<reference::Ed25519>(&mut rng);
+}
+
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{
+        curves::{reference, Ed25519, Secp256k1},
+        Curve, Point, Scalar,
+    };
+    use rand::{Rng, RngCore};
+
+    fn scalar<R: Curve, E: Curve>(x: &Scalar<E>) -> Scalar<R> {
+        Scalar::from_be_bytes(x.to_be_bytes()).unwrap()
+    }
+
+    fn point<R: Curve, E: Curve>(x: &Point<E>) -> Point<R> {
+        Point::from_bytes(x.to_bytes(true)).unwrap()
+    }
+
+    fn assert_same_point<E: Curve, R: Curve>(expected: &Point<E>, actual: &Point<R>) {
+        assert_eq!(&*expected.to_bytes(true), &*actual.to_bytes(true));
+        assert_eq!(&*expected.to_bytes(false), &*actual.to_bytes(false));
+    }
+
+    fn assert_same_scalar<E: Curve, R: Curve>(expected: &Scalar<E>, actual: &Scalar<R>) {
+        assert_eq!(&*expected.to_be_bytes(), &*actual.to_be_bytes());
+        assert_eq!(&*expected.to_le_bytes(), &*actual.to_le_bytes());
+    }
+
+    #[test]
+    fn generator<E: Curve, R: Curve>() {
+        assert_same_point(
+            &Point::<E>::generator().to_point(),
+            &Point::<R>::generator().to_point(),
+        );
+        assert_same_point(&Point::<E>::zero(), &Point::<R>::zero());
+    }
+
+    #[test]
+    fn scalar_arithmetic<E: Curve, R: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+
+        for _ in 0..50 {
+            let a = Scalar::<E>::random(&mut rng);
+            let b = Scalar::<E>::random(&mut rng);
+            let (ra, rb) = (scalar::<R, E>(&a), scalar::<R, E>(&b));
+
+            assert_same_scalar(&(a + b), &(ra + rb));
+            assert_same_scalar(&(a - b), &(ra - rb));
+            assert_same_scalar(&(a * b), &(ra * rb));
+            assert_same_scalar(&(-a), &(-ra));
+            assert_same_scalar(&a.invert().unwrap(), &ra.invert().unwrap());
+            assert_eq!(bool::from(a.is_high()), bool::from(ra.is_high()));
+        }
+        assert!(Scalar::<R>::zero().invert().is_none());
+    }
+
+    #[test]
+    fn scalar_decoding<E: Curve, R: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+
+        let mut inputs = vec![[0u8; 32], [0xff; 32]];
+        for _ in 0..50 {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            inputs.push(bytes);
+            // Ensure that some of inputs are close to group order
+            bytes[0] = rng.gen_range(0xf0..=0xff);
+            inputs.push(bytes);
+        }
+
+        for bytes in inputs {
+            assert_same_scalar(
+                &Scalar::<E>::from_be_bytes_mod_order(bytes),
+                &Scalar::<R>::from_be_bytes_mod_order(bytes),
+            );
+            assert_same_scalar(
+                &Scalar::<E>::from_le_bytes_mod_order(bytes),
+                &Scalar::<R>::from_le_bytes_mod_order(bytes),
+            );
+            match (
+                Scalar::<E>::from_be_bytes(bytes),
+                Scalar::<R>::from_be_bytes(bytes),
+            ) {
+                (Ok(expected), Ok(actual)) => assert_same_scalar(&expected, &actual),
+                (Err(_), Err(_)) => (),
+                (expected, actual) => panic!("expected {expected:?}, actual {actual:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn point_arithmetic<E: Curve, R: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+
+        for _ in 0..10 {
+            let k = Scalar::<E>::random(&mut rng);
+            let p = Point::generator() * Scalar::<E>::random(&mut rng);
+            let q = Point::generator() * Scalar::<E>::random(&mut rng);
+            let (rk, rp, rq) = (scalar::<R, E>(&k), point::<R, E>(&p), point::<R, E>(&q));
+
+            assert_same_point(&(Point::generator() * k), &(Point::generator() * rk));
+            assert_same_point(&(p * k), &(rp * rk));
+            assert_same_point(&(p + q), &(rp + rq));
+            assert_same_point(&(p - q), &(rp - rq));
+            assert_same_point(&(p + p), &(rp + rp));
+            assert_same_point(&p.double(), &rp.double());
+            assert_same_point(&(p + (-p)), &(rp + (-rp)));
+            assert_same_point(&(-p), &(-rp));
+        }
+    }
+
+    #[test]
+    fn point_decoding<E: Curve, R: Curve>() {
+        let mut rng = rand_dev::DevRng::new();
+
+        let mut inputs = vec![vec![], vec![0], vec![0xff]];
+        // Non-canonical encodings of ed25519 identity: y = p + 1, and x = 0 with sign bit set
+        inputs.push(
+            hex::decode("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f")
+                .unwrap(),
+        );
+        inputs.push(
+            hex::decode("0100000000000000000000000000000000000000000000000000000000000080")
+                .unwrap(),
+        );
+        for len in [
+            Point::<E>::serialized_len(true),
+            Point::<E>::serialized_len(false),
+        ] {
+            inputs.push(vec![0; len]);
+            inputs.push(vec![0xff; len]);
+            for _ in 0..20 {
+                let mut bytes = vec![0u8; len];
+                rng.fill_bytes(&mut bytes);
+                inputs.push(bytes.clone());
+                // Try every tag defined in SEC1
+                bytes[0] = rng.gen_range(0..=5);
+                inputs.push(bytes);
+            }
+        }
+
+        for bytes in inputs {
+            match (
+                Point::<E>::from_bytes(&bytes),
+                Point::<R>::from_bytes(&bytes),
+            ) {
+                (Ok(expected), Ok(actual)) => assert_same_point(&expected, &actual),
+                (Err(_), Err(_)) => (),
+                (expected, actual) => panic!(
+                    "input: {}, expected {expected:?}, actual {actual:?}",
+                    hex::encode(&bytes)
+                ),
+            }
+        }
+    }
+
+    #[instantiate_tests(<Secp256k1, reference::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<Ed25519, reference::Ed25519>)]
+    mod ed25519 {}
+}
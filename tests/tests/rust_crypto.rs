@@ -0,0 +1,53 @@
+use elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use elliptic_curve::{NonZeroScalar, PublicKey, SecretKey};
+use generic_ec::{curves, Curve, NonZero, Point, Scalar, SecretScalar};
+
+fn interop<E, C>()
+where
+    E: Curve,
+    C: elliptic_curve::CurveArithmetic,
+    Point<E>: ToEncodedPoint<C> + FromEncodedPoint<C>,
+    PublicKey<C>: From<NonZero<Point<E>>>,
+    NonZero<Point<E>>: TryFrom<PublicKey<C>>,
+    NonZeroScalar<C>: From<NonZero<Scalar<E>>>,
+    NonZero<Scalar<E>>: From<NonZeroScalar<C>>,
+    for<'a> SecretKey<C>: From<&'a NonZero<SecretScalar<E>>>,
+    for<'a> NonZero<SecretScalar<E>>: From<&'a SecretKey<C>>,
+    elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+{
+    let mut rng = rand_dev::DevRng::new();
+
+    let secret = NonZero::<SecretScalar<E>>::random(&mut rng);
+    let point = Point::generator() * &secret;
+
+    // Encoding matches `Point::to_bytes`
+    for compress in [true, false] {
+        let encoded = point.to_encoded_point(compress);
+        assert_eq!(encoded.as_bytes(), &*point.to_bytes(compress));
+        assert_eq!(Point::<E>::from_encoded_point(&encoded).unwrap(), *point);
+    }
+
+    // Secret key derives the same public key
+    let secret_key = SecretKey::<C>::from(&secret);
+    let public_key = PublicKey::<C>::from(point);
+    assert_eq!(secret_key.public_key(), public_key);
+
+    let point2 = NonZero::<Point<E>>::try_from(public_key).ok().unwrap();
+    assert_eq!(point2, point);
+    let secret2 = NonZero::<SecretScalar<E>>::from(&secret_key);
+    assert_eq!(secret2.expose_secret(|x| *x), secret.expose_secret(|x| *x));
+
+    let scalar = NonZero::<Scalar<E>>::random(&mut rng);
+    let nonzero_scalar = NonZeroScalar::<C>::from(scalar);
+    assert_eq!(NonZero::<Scalar<E>>::from(nonzero_scalar), scalar);
+}
+
+#[test]
+fn secp256k1() {
+    interop::<curves::Secp256k1, k256::Secp256k1>()
+}
+
+#[test]
+fn secp256r1() {
+    interop::<curves::Secp256r1, p256::NistP256>()
+}
@@ -358,6 +358,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn serialization_config<E: Curve>() {
+        use generic_ec::serde::{Endianness, PointFormat, SerializationConfig, WithConfig};
+        use serde::de::DeserializeSeed;
+
+        let mut rng = rand_dev::DevRng::new();
+        let point = Point::<E>::generator() * Scalar::random(&mut rng);
+        let scalar = Scalar::<E>::random(&mut rng);
+
+        // Predefined configs match default and compact formats
+        fn json<T: serde::Serialize>(value: &T) -> String {
+            serde_json::to_string(value).unwrap()
+        }
+        assert_eq!(
+            json(&WithConfig::new(&point, SerializationConfig::DEFAULT)),
+            json(&point)
+        );
+        assert_eq!(
+            json(&WithConfig::new(&scalar, SerializationConfig::DEFAULT)),
+            json(&scalar)
+        );
+        assert_eq!(
+            json(&WithConfig::new(&point, SerializationConfig::COMPACT)),
+            json(&Compact(point))
+        );
+        assert_eq!(
+            json(&WithConfig::new(&scalar, SerializationConfig::COMPACT)),
+            json(&Compact(scalar))
+        );
+
+        for point_format in [
+            PointFormat::Compressed,
+            PointFormat::Uncompressed,
+            PointFormat::XOnly,
+        ] {
+            for scalar_endianness in [Endianness::Big, Endianness::Little] {
+                for curve_tag in [true, false] {
+                    let config = SerializationConfig {
+                        point_format,
+                        scalar_endianness,
+                        curve_tag,
+                    };
+
+                    // Scalar roundtrip
+                    let serialized =
+                        serde_json::to_string(&WithConfig::new(&scalar, config)).unwrap();
+                    let mut de = serde_json::Deserializer::from_str(&serialized);
+                    let deserialized = config.seed::<Scalar<E>>().deserialize(&mut de).unwrap();
+                    assert_eq!(scalar, deserialized);
+
+                    // Point roundtrip
+                    for point in [point, -point] {
+                        let serialized = serde_json::to_string(&WithConfig::new(&point, config));
+                        let has_odd_y = point.to_bytes(true)[0] == 0x03;
+                        if point_format == PointFormat::XOnly && has_odd_y {
+                            assert!(serialized.is_err());
+                            continue;
+                        }
+                        let serialized = serialized.unwrap();
+                        let mut de = serde_json::Deserializer::from_str(&serialized);
+                        let deserialized = config.seed::<Point<E>>().deserialize(&mut de).unwrap();
+                        assert_eq!(point, deserialized);
+                    }
+                }
+            }
+        }
+
+        // Little-endian scalar
+        let config = SerializationConfig::COMPACT.set_scalar_endianness(Endianness::Little);
+        let mut le_bytes = scalar.to_be_bytes().to_vec();
+        le_bytes.reverse();
+        assert_eq!(
+            json(&WithConfig::new(&scalar, config)),
+            format!("\"{}\"", hex::encode(le_bytes))
+        );
+
+        // Curve tag is checked
+        let config = SerializationConfig::DEFAULT.set_point_format(PointFormat::Compressed);
+        let serialized = json(&WithConfig::new(&point, config)).replace(E::CURVE_NAME, "other");
+        let mut de = serde_json::Deserializer::from_str(&serialized);
+        assert!(DeserializeSeed::deserialize(config.seed::<Point<E>>(), &mut de).is_err());
+    }
+
     #[derive(PartialEq, Eq, Debug)]
     struct Compact<T>(T);
     impl<T> serde::Serialize for Compact<T>
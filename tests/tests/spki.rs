@@ -0,0 +1,106 @@
+use generic_ec::{
+    curves,
+    spki::{decode_public_key, public_key_from_certificate, AnyPublicKey, CurveOid},
+    NonZero, Point,
+};
+use spki::{
+    der::{asn1::BitStringRef, Encode},
+    AlgorithmIdentifier, ObjectIdentifier, SubjectPublicKeyInfo,
+};
+
+fn encode_spki<E: CurveOid>(public_key: &Point<E>) -> Vec<u8> {
+    let bytes = public_key.to_bytes(true);
+    SubjectPublicKeyInfo {
+        algorithm: AlgorithmIdentifier::<ObjectIdentifier> {
+            oid: E::ALGORITHM,
+            parameters: E::PARAMETERS,
+        },
+        subject_public_key: BitStringRef::from_bytes(&bytes).unwrap(),
+    }
+    .to_der()
+    .unwrap()
+}
+
+fn roundtrip<E: CurveOid>(expected: impl Fn(AnyPublicKey) -> Option<NonZero<Point<E>>>) {
+    let mut rng = rand_dev::DevRng::new();
+    let public_key = Point::generator() * NonZero::<generic_ec::Scalar<E>>::random(&mut rng);
+
+    let der = encode_spki::<E>(&public_key);
+    assert_eq!(decode_public_key::<E>(&der).unwrap(), public_key);
+
+    let any = AnyPublicKey::decode(&der).unwrap();
+    assert_eq!(any.curve_name(), E::CURVE_NAME);
+    assert_eq!(expected(any), Some(public_key));
+
+    // Zero point is not a valid public key
+    let der = encode_spki::<E>(&Point::zero());
+    assert!(decode_public_key::<E>(&der).is_err());
+}
+
+fn certificate<E: CurveOid>(cert_der: &[u8], spki_der: &[u8]) {
+    let from_cert = public_key_from_certificate::<E>(cert_der).unwrap();
+    let from_spki = decode_public_key::<E>(spki_der).unwrap();
+    assert_eq!(from_cert, from_spki);
+
+    let any = AnyPublicKey::from_certificate(cert_der).unwrap();
+    assert_eq!(any, AnyPublicKey::decode(spki_der).unwrap());
+    assert_eq!(any.curve_name(), E::CURVE_NAME);
+
+    // Truncated certificate is rejected
+    assert!(public_key_from_certificate::<E>(&cert_der[..cert_der.len() - 1]).is_err());
+}
+
+#[test]
+fn secp256k1() {
+    roundtrip::<curves::Secp256k1>(|key| match key {
+        AnyPublicKey::Secp256k1(key) => Some(key),
+        _ => None,
+    });
+    certificate::<curves::Secp256k1>(
+        include_bytes!("data/secp256k1.der"),
+        include_bytes!("data/secp256k1.spki.der"),
+    );
+}
+
+#[test]
+fn secp256r1() {
+    roundtrip::<curves::Secp256r1>(|key| match key {
+        AnyPublicKey::Secp256r1(key) => Some(key),
+        _ => None,
+    });
+    certificate::<curves::Secp256r1>(
+        include_bytes!("data/secp256r1.der"),
+        include_bytes!("data/secp256r1.spki.der"),
+    );
+}
+
+#[test]
+fn ed25519() {
+    roundtrip::<curves::Ed25519>(|key| match key {
+        AnyPublicKey::Ed25519(key) => Some(key),
+        _ => None,
+    });
+    certificate::<curves::Ed25519>(
+        include_bytes!("data/ed25519.der"),
+        include_bytes!("data/ed25519.spki.der"),
+    );
+}
+
+#[test]
+fn mismatched_curve_is_rejected() {
+    let der = include_bytes!("data/secp256k1.spki.der");
+    assert!(decode_public_key::<curves::Secp256r1>(der).is_err());
+    assert!(decode_public_key::<curves::Ed25519>(der).is_err());
+}
+
+#[test]
+fn unsupported_curve_is_rejected() {
+    // secp384r1 public key
+    let der = hex::decode(
+        "3076301006072a8648ce3d020106052b8104002203620004235ee39f196d4f4447466b3c804be6069a38d9\
+         9fa7e8688c2b2e9b3fe088ccf3e7b89f602aa41effc6b317e237b0e5d4b9519d24df2c8665daf8925256\
+         4fa09a7b2be0dfcbe5b1946c813bb4673ce34b9c9f05e7946c7bb0d4afa4cd4783e3f0",
+    )
+    .unwrap();
+    assert!(AnyPublicKey::decode(&der).is_err());
+}
@@ -0,0 +1,66 @@
+use generic_ec::{
+    curves::Secp256r1,
+    tls::{self, KeyShareEntry, NamedGroup},
+    NonZero, Point, Scalar,
+};
+
+fn roundtrip<E: NamedGroup>() {
+    let mut rng = rand_dev::DevRng::new();
+    let public_key = Point::generator() * NonZero::<Scalar<E>>::random(&mut rng);
+
+    let entry = tls::encode_key_share_entry(&public_key);
+    assert_eq!(entry[..2], E::NAMED_GROUP.to_be_bytes());
+    assert_eq!(
+        tls::decode_key_share_entry::<E>(&entry).unwrap(),
+        public_key
+    );
+    assert_eq!(
+        tls::decode_key_exchange::<E>(&tls::key_exchange(&public_key)).unwrap(),
+        public_key
+    );
+
+    // Compressed points and identity are rejected
+    assert!(tls::decode_key_exchange::<E>(&public_key.to_bytes(true)).is_err());
+    assert!(tls::decode_key_exchange::<E>(&Point::<E>::zero().to_bytes(false)).is_err());
+
+    // Truncated entry and trailing bytes are rejected
+    assert!(tls::decode_key_share_entry::<E>(&entry[..entry.len() - 1]).is_err());
+    let mut extended = entry.clone();
+    extended.push(0);
+    assert!(tls::decode_key_share_entry::<E>(&extended).is_err());
+
+    // Entry of another group is rejected
+    let mut other_group = entry;
+    other_group[..2].copy_from_slice(&0x001d_u16.to_be_bytes());
+    assert!(tls::decode_key_share_entry::<E>(&other_group).is_err());
+}
+
+#[test]
+fn secp256r1() {
+    roundtrip::<Secp256r1>();
+}
+
+#[test]
+fn client_shares() {
+    let mut rng = rand_dev::DevRng::new();
+    let public_key = Point::generator() * NonZero::<Scalar<Secp256r1>>::random(&mut rng);
+
+    // x25519 share followed by secp256r1 share
+    let mut list = vec![0x00, 0x1d, 0x00, 0x20];
+    list.extend_from_slice(&[0xaa; 32]);
+    list.extend_from_slice(&tls::encode_key_share_entry(&public_key));
+
+    let entries = KeyShareEntry::parse_list(&list)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].group, 0x001d);
+    assert_eq!(entries[0].key_exchange, [0xaa; 32]);
+    assert!(entries[0].public_key::<Secp256r1>().is_err());
+    assert_eq!(entries[1].public_key::<Secp256r1>().unwrap(), public_key);
+
+    // Malformed list yields an error
+    let entries = KeyShareEntry::parse_list(&list[..list.len() - 1]).collect::<Vec<_>>();
+    assert_eq!(entries.len(), 2);
+    assert!(entries[1].is_err());
+}
@@ -0,0 +1,59 @@
+use generic_ec::{
+    core::ByteArray, curves::Ed25519, uniform::UniformEncoding, Curve, Point, Scalar,
+};
+use rand::RngCore;
+
+fn roundtrip<E: Curve + UniformEncoding>() {
+    let mut rng = rand_dev::DevRng::new();
+
+    let mut representable = 0;
+    let mut top_bits = 0u8;
+    for _ in 0..200 {
+        let point = Point::<E>::generator() * Scalar::random(&mut rng);
+        let Some(encoded) = point.to_uniform_bytes(&mut rng) else {
+            continue;
+        };
+        representable += 1;
+        top_bits |= encoded.as_ref()[encoded.as_ref().len() - 1];
+
+        let decoded = Point::<E>::from_uniform_bytes(encoded.as_ref()).unwrap();
+        assert_eq!(point, decoded);
+    }
+
+    // About half of the points are representable
+    assert!((60..=140).contains(&representable), "{representable}");
+    // Encoding uses all bits
+    assert_eq!(top_bits & 0b1100_0000, 0b1100_0000);
+}
+
+fn decode_random_bytes<E: Curve + UniformEncoding>() {
+    let mut rng = rand_dev::DevRng::new();
+
+    for _ in 0..100 {
+        let mut bytes = E::UniformBytes::zeroes();
+        rng.fill_bytes(bytes.as_mut());
+        let point = Point::<E>::from_uniform_bytes(bytes.as_ref()).unwrap();
+
+        // Re-encoding the decoded point gives some valid encoding of the same point
+        if let Some(encoded) = point.to_uniform_bytes(&mut rng) {
+            assert_eq!(
+                Point::<E>::from_uniform_bytes(encoded.as_ref()).unwrap(),
+                point
+            );
+        }
+    }
+
+    let len = E::UniformBytes::zeroes().as_ref().len();
+    assert!(Point::<E>::from_uniform_bytes(&vec![0u8; len - 1]).is_err());
+    assert!(Point::<E>::from_uniform_bytes(&vec![0u8; len + 1]).is_err());
+}
+
+#[test]
+fn ed25519_roundtrip() {
+    roundtrip::<Ed25519>()
+}
+
+#[test]
+fn ed25519_decode_random_bytes() {
+    decode_random_bytes::<Ed25519>()
+}
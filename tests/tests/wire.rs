@@ -0,0 +1,86 @@
+#[generic_tests::define]
+mod tests {
+    use generic_ec::{
+        curves::*,
+        wire::v1::{self, WireV1},
+        Point, Scalar,
+    };
+    use rand_dev::DevRng;
+
+    #[test]
+    fn golden_vectors<E: WireV1>() {
+        assert!(v1::check_golden_vectors::<E>());
+
+        for vector in E::GOLDEN_VECTORS {
+            let scalar = hex::decode(vector.scalar).unwrap();
+            let point = hex::decode(vector.point).unwrap();
+            assert_eq!(scalar.len(), E::SCALAR_LEN);
+            assert_eq!(point.len(), E::POINT_LEN);
+
+            let s = v1::decode_scalar::<E>(&scalar).unwrap();
+            let p = v1::decode_point::<E>(&point).unwrap();
+            assert_eq!(Point::generator() * s, p);
+        }
+    }
+
+    #[test]
+    fn encodings_have_fixed_size<E: WireV1>() {
+        let mut rng = DevRng::new();
+        assert_eq!(Point::<E>::serialized_len(true), E::POINT_LEN);
+        assert_eq!(Scalar::<E>::serialized_len(), E::SCALAR_LEN);
+
+        for _ in 0..10 {
+            let s = Scalar::<E>::random(&mut rng);
+            let p = Point::generator() * s;
+            assert_eq!(v1::decode_scalar::<E>(&s.to_be_bytes()).unwrap(), s);
+            assert_eq!(v1::decode_point::<E>(&p.to_bytes(true)).unwrap(), p);
+        }
+    }
+
+    #[test]
+    fn rejects_non_canonical_blobs<E: WireV1>() {
+        let mut rng = DevRng::new();
+        let s = Scalar::<E>::random(&mut rng);
+        let p = Point::generator() * s;
+
+        let mut scalar = s.to_be_bytes().to_vec();
+        scalar.insert(0, 0);
+        assert!(v1::validate_scalar::<E>(&scalar).is_err());
+        assert!(v1::validate_scalar::<E>(&scalar[2..]).is_err());
+        assert!(v1::validate_scalar::<E>(&[0xff; 32]).is_err());
+
+        let point = p.to_bytes(true);
+        assert!(v1::validate_point::<E>(&point[1..]).is_err());
+        assert!(v1::validate_point::<E>(&[&point[..], &[0]].concat()).is_err());
+        if Point::<E>::serialized_len(false) != E::POINT_LEN {
+            assert!(v1::validate_point::<E>(&p.to_bytes(false)).is_err());
+        }
+    }
+
+    #[instantiate_tests(<Secp256k1>)]
+    mod secp256k1 {}
+
+    #[instantiate_tests(<Secp256r1>)]
+    mod secp256r1 {}
+
+    #[instantiate_tests(<Stark>)]
+    mod stark {}
+
+    #[instantiate_tests(<Ed25519>)]
+    mod ed25519 {}
+}
+
+#[test]
+fn sizes_are_compile_time_constants() {
+    use generic_ec::{
+        curves::{Ed25519, Secp256k1},
+        wire::v1::{self, WireV1},
+        Point, Scalar,
+    };
+
+    let point: [u8; Secp256k1::POINT_LEN] =
+        v1::encode_point(&Point::<Secp256k1>::generator().to_point());
+    let scalar: [u8; Ed25519::SCALAR_LEN] = v1::encode_scalar(&Scalar::<Ed25519>::one());
+    assert_eq!(point.len(), 33);
+    assert_eq!(scalar[31], 1);
+}
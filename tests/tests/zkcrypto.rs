@@ -0,0 +1,88 @@
+#[generic_tests::define]
+mod tests {
+    use ff::{Field, PrimeField};
+    use generic_ec::{Curve, Point, Scalar};
+    use group::{Group, GroupEncoding};
+    use rand_dev::DevRng;
+
+    /// Function written against zkcrypto traits only
+    fn commit<G: Group>(value: u64, blinding: G::Scalar, h: G) -> G {
+        G::generator() * G::Scalar::from(value) + h * blinding
+    }
+
+    #[test]
+    fn field_ops<E: Curve>()
+    where
+        Scalar<E>: PrimeField,
+    {
+        let mut rng = DevRng::new();
+        let a = <Scalar<E> as Field>::random(&mut rng);
+        let b = <Scalar<E> as Field>::random(&mut rng);
+
+        assert_eq!(<Scalar<E> as Field>::ZERO, Scalar::zero());
+        assert_eq!(<Scalar<E> as Field>::ONE, Scalar::one());
+        assert_eq!(<Scalar<E> as Field>::square(&a), a * a);
+        assert_eq!(<Scalar<E> as Field>::double(&a), a + a);
+        assert_eq!(<Scalar<E> as Field>::invert(&a).unwrap() * a, Scalar::one());
+        assert!(bool::from(
+            <Scalar<E> as Field>::invert(&Scalar::zero()).is_none()
+        ));
+        assert_eq!(<Scalar<E> as From<u64>>::from(42), Scalar::from(42u64));
+        assert_eq!(
+            <Scalar<E> as PrimeField>::TWO_INV * Scalar::from(2),
+            Scalar::one()
+        );
+
+        let square = a.square();
+        let root = square.sqrt().unwrap();
+        assert_eq!(root * root, square);
+
+        let repr = a.to_repr();
+        assert_eq!(Scalar::<E>::from_repr(repr).unwrap(), a);
+        assert_ne!(a.to_repr().as_ref(), b.to_repr().as_ref());
+    }
+
+    #[test]
+    fn group_ops<E: Curve>()
+    where
+        Scalar<E>: PrimeField,
+        Point<E>: Group<Scalar = Scalar<E>> + GroupEncoding,
+    {
+        let mut rng = DevRng::new();
+        let r = <Scalar<E> as Field>::random(&mut rng);
+        let h = <Point<E> as Group>::random(&mut rng);
+
+        assert_eq!(
+            <Point<E> as Group>::generator(),
+            Point::generator().to_point()
+        );
+        assert!(bool::from(<Point<E> as Group>::identity().is_identity()));
+        assert_eq!(<Point<E> as Group>::double(&h), h + h);
+        assert_eq!(
+            commit(42, r, h),
+            Point::generator() * Scalar::from(42) + h * r
+        );
+
+        for point in [h, Point::zero(), Point::generator().to_point()] {
+            let bytes = GroupEncoding::to_bytes(&point);
+            assert_eq!(bytes.as_ref(), &*point.to_bytes(true));
+            let decoded = <Point<E> as GroupEncoding>::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, point);
+        }
+
+        let mut invalid = GroupEncoding::to_bytes(&h);
+        invalid.as_mut().fill(0xff);
+        assert!(bool::from(
+            <Point<E> as GroupEncoding>::from_bytes(&invalid).is_none()
+        ));
+    }
+
+    #[instantiate_tests(<generic_ec::curves::Secp256k1>)]
+    mod secp256k1 {}
+    #[instantiate_tests(<generic_ec::curves::Secp256r1>)]
+    mod secp256r1 {}
+    #[instantiate_tests(<generic_ec::curves::Stark>)]
+    mod stark {}
+    #[instantiate_tests(<generic_ec::curves::Ed25519>)]
+    mod ed25519 {}
+}